@@ -347,7 +347,7 @@ impl<Octs: AsRef<[u8]> + ?Sized> CharStr<Octs> {
     }
 
     /// Returns an iterator over the octets of the character string.
-    pub fn iter(&self) -> Iter {
+    pub fn iter(&self) -> Iter<'_> {
         Iter {
             octets: self.as_slice(),
         }
@@ -397,7 +397,7 @@ impl<Octs: AsRef<[u8]> + ?Sized> CharStr<Octs> {
     /// The returned object will display the content surrounded by double
     /// quotes. It will escape double quotes, backslashes, and non-printable
     /// octets only.
-    pub fn display_quoted(&self) -> DisplayQuoted {
+    pub fn display_quoted(&self) -> DisplayQuoted<'_> {
         DisplayQuoted(self.for_slice())
     }
 
@@ -406,7 +406,7 @@ impl<Octs: AsRef<[u8]> + ?Sized> CharStr<Octs> {
     /// The returned object will display the content without explicit
     /// delimiters and escapes space, double quotes, semicolons, backslashes,
     /// and non-printable octets.
-    pub fn display_unquoted(&self) -> DisplayUnquoted {
+    pub fn display_unquoted(&self) -> DisplayUnquoted<'_> {
         DisplayUnquoted(self.for_slice())
     }
 }
@@ -0,0 +1,936 @@
+//! DNS Stateful Operations.
+//!
+//! DNS Stateful Operations (DSO), defined in [RFC 8490], allow a client and
+//! a server to manage a long-lived session over a connection-oriented
+//! transport. DSO messages use the regular message header with the opcode
+//! [`Opcode::DSO`] but, rather than the usual four sections, the body of the
+//! message is a sequence of type-length-value elements, or TLVs.
+//!
+//! Because of this, DSO messages can’t be processed with [`Message`]’s
+//! section methods or built with the [`MessageBuilder`] section builders.
+//! Instead, this module provides [`DsoMessage`] for reading DSO messages and
+//! [`DsoBuilder`] for creating them.
+//!
+//! A TLV in its raw form is represented by [`Tlv`]. In addition, the module
+//! provides types for the data of the TLVs defined by RFC 8490 itself:
+//! [`Keepalive`], [`RetryDelay`], and [`EncryptionPadding`]. Additional TLV
+//! data types can be implemented via the traits [`TlvData`],
+//! [`ParseTlvData`], and [`ComposeTlvData`].
+//!
+//! # Example
+//!
+//! ```
+//! use domain::base::dso::{DsoBuilder, DsoMessage, Keepalive};
+//! use domain::base::MessageBuilder;
+//! use std::time::Duration;
+//!
+//! // Establish a session with a 15 second inactivity timeout and a one
+//! // minute keepalive interval.
+//! let mut msg = DsoBuilder::from_builder(MessageBuilder::new_vec());
+//! msg.header_mut().set_id(1);
+//! msg.push(&Keepalive::new(
+//!     Some(Duration::from_secs(15)), Some(Duration::from_secs(60))
+//! )).unwrap();
+//! let msg = DsoMessage::from_octets(msg.finish()).unwrap();
+//!
+//! let tlv = msg.primary_tlv().unwrap().unwrap();
+//! let keepalive = tlv.parse_data::<Keepalive>().unwrap().unwrap();
+//! assert_eq!(
+//!     keepalive.inactivity_timeout(), Some(Duration::from_secs(15))
+//! );
+//! ```
+//!
+//! [RFC 8490]: https://tools.ietf.org/html/rfc8490
+
+use super::header::{Header, HeaderSection};
+use super::iana::{DsoType, Opcode};
+use super::message::Message;
+use super::message_builder::MessageBuilder;
+use super::wire::{Compose, Composer, Parse, ParseError};
+use crate::utils::base16;
+use core::time::Duration;
+use core::{fmt, hash, mem};
+use octseq::builder::{FreezeBuilder, OctetsBuilder};
+use octseq::octets::Octets;
+use octseq::parse::Parser;
+
+//------------ DsoMessage ----------------------------------------------------
+
+/// A DSO message.
+///
+/// The type wraps a [`Message`] that has been checked to be a DSO message,
+/// i.e., that it uses the DSO opcode and has empty sections.
+///
+/// The TLVs of the message are available via [`tlvs`][Self::tlvs]. In a
+/// request, the first TLV is the primary TLV which determines the
+/// operation requested. It is also available via
+/// [`primary_tlv`][Self::primary_tlv]. Responses may omit the primary TLV,
+/// in which case the first TLV of a response is an additional TLV. Use the
+/// header’s QR bit to distinguish between the two.
+#[derive(Clone)]
+pub struct DsoMessage<Octs> {
+    /// The underlying message.
+    message: Message<Octs>,
+}
+
+impl<Octs: AsRef<[u8]>> DsoMessage<Octs> {
+    /// Creates a DSO message from octets.
+    ///
+    /// Returns an error if the octets don’t contain a DSO message.
+    pub fn from_octets(octets: Octs) -> Result<Self, ParseError> {
+        Self::from_message(
+            Message::from_octets(octets)
+                .map_err(|_| ParseError::ShortInput)?,
+        )
+    }
+
+    /// Creates a DSO message from a message.
+    ///
+    /// Returns an error if the message isn’t a DSO message. This is the
+    /// case if it has a different opcode or if any of its sections is
+    /// not empty.
+    pub fn from_message(message: Message<Octs>) -> Result<Self, ParseError> {
+        if message.header().opcode() != Opcode::DSO {
+            return Err(ParseError::form_error("not a DSO message"));
+        }
+        let counts = message.header_counts();
+        if counts.qdcount() != 0
+            || counts.ancount() != 0
+            || counts.nscount() != 0
+            || counts.arcount() != 0
+        {
+            return Err(ParseError::form_error("non-empty DSO sections"));
+        }
+        Ok(DsoMessage { message })
+    }
+
+    /// Returns the message header.
+    pub fn header(&self) -> Header {
+        self.message.header()
+    }
+
+    /// Returns whether the message is a unidirectional message.
+    ///
+    /// Unidirectional messages are requests with a message ID of zero. The
+    /// receiver must not respond to them.
+    pub fn is_unidirectional(&self) -> bool {
+        let header = self.header();
+        !header.qr() && header.id() == 0
+    }
+}
+
+impl<Octs> DsoMessage<Octs> {
+    /// Returns a reference to the underlying message.
+    pub fn as_message(&self) -> &Message<Octs> {
+        &self.message
+    }
+
+    /// Converts the value into the underlying message.
+    pub fn into_message(self) -> Message<Octs> {
+        self.message
+    }
+}
+
+impl<Octs: Octets> DsoMessage<Octs> {
+    /// Returns an iterator over all the TLVs of the message.
+    pub fn tlvs(&self) -> TlvIter<'_, Octs> {
+        let mut parser = Parser::from_ref(self.message.as_octets());
+        parser
+            .advance(mem::size_of::<HeaderSection>())
+            .expect("short message");
+        TlvIter { parser }
+    }
+
+    /// Returns the first TLV of the message.
+    ///
+    /// In a request, this is the primary TLV.
+    pub fn primary_tlv(
+        &self,
+    ) -> Option<Result<Tlv<Octs::Range<'_>>, ParseError>> {
+        self.tlvs().next()
+    }
+}
+
+//--- From
+
+impl<Octs> From<DsoMessage<Octs>> for Message<Octs> {
+    fn from(src: DsoMessage<Octs>) -> Self {
+        src.into_message()
+    }
+}
+
+//--- Debug
+
+impl<Octs: Octets> fmt::Debug for DsoMessage<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut list = f.debug_list();
+        for tlv in self.tlvs() {
+            match tlv {
+                Ok(tlv) => list.entry(&tlv),
+                Err(err) => list.entry(&err),
+            };
+        }
+        list.finish()
+    }
+}
+
+//------------ TlvIter -------------------------------------------------------
+
+/// An iterator over the TLVs of a DSO message.
+///
+/// The iterator returns an error if a TLV cannot be parsed. It will stop
+/// after returning an error.
+#[derive(Clone, Debug)]
+pub struct TlvIter<'a, Octs: ?Sized> {
+    /// The parser positioned at the next TLV.
+    parser: Parser<'a, Octs>,
+}
+
+impl<'a, Octs: Octets + ?Sized> Iterator for TlvIter<'a, Octs> {
+    type Item = Result<Tlv<Octs::Range<'a>>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.parser.remaining() == 0 {
+            return None;
+        }
+        let res = Tlv::parse(&mut self.parser);
+        if res.is_err() {
+            self.parser.advance_to_end();
+        }
+        Some(res)
+    }
+}
+
+//------------ Tlv -----------------------------------------------------------
+
+/// A DSO TLV in its raw form.
+///
+/// The TLV consists of its [`DsoType`] and the raw octets of its data. The
+/// data can be parsed into a specific type via
+/// [`parse_data`][Self::parse_data].
+#[derive(Clone)]
+pub struct Tlv<Octs> {
+    /// The type of the TLV.
+    dso_type: DsoType,
+
+    /// The data of the TLV.
+    data: Octs,
+}
+
+impl<Octs> Tlv<Octs> {
+    /// Creates a TLV from its type and data.
+    ///
+    /// Returns an error if the data is longer than 65,535 octets.
+    pub fn new(dso_type: DsoType, data: Octs) -> Result<Self, LongTlvData>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        LongTlvData::check_len(data.as_ref().len())?;
+        Ok(Tlv { dso_type, data })
+    }
+
+    /// Returns the type of the TLV.
+    pub fn dso_type(&self) -> DsoType {
+        self.dso_type
+    }
+
+    /// Returns a reference to the data of the TLV.
+    pub fn data(&self) -> &Octs {
+        &self.data
+    }
+
+    /// Converts the TLV into its data.
+    pub fn into_data(self) -> Octs {
+        self.data
+    }
+
+    /// Returns a slice of the data of the TLV.
+    pub fn as_slice(&self) -> &[u8]
+    where
+        Octs: AsRef<[u8]>,
+    {
+        self.data.as_ref()
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Tlv<Octs> {
+    /// Parses the data of the TLV into the given type.
+    ///
+    /// Returns `Ok(None)` if the TLV’s type isn’t one the data type is
+    /// for. Returns an error if the data is not valid for its type.
+    pub fn parse_data<'a, Data: ParseTlvData<'a, Octs>>(
+        &'a self,
+    ) -> Result<Option<Data>, ParseError> {
+        let mut parser = Parser::from_ref(&self.data);
+        let res = Data::parse_tlv(self.dso_type, &mut parser)?;
+        if res.is_some() && parser.remaining() != 0 {
+            return Err(ParseError::form_error("trailing TLV data"));
+        }
+        Ok(res)
+    }
+}
+
+impl<Octs> Tlv<Octs> {
+    /// Parses a TLV from its wire format.
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let dso_type = DsoType::parse(parser)?;
+        let len = u16::parse(parser)?;
+        Ok(Tlv {
+            dso_type,
+            data: parser.parse_octets(usize::from(len))?,
+        })
+    }
+}
+
+//--- TlvData and ComposeTlvData
+
+impl<Octs> TlvData for Tlv<Octs> {
+    fn dso_type(&self) -> DsoType {
+        self.dso_type
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeTlvData for Tlv<Octs> {
+    fn compose_len(&self) -> u16 {
+        self.data.as_ref().len().try_into().expect("long TLV data")
+    }
+
+    fn compose_tlv<Target: OctetsBuilder + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        target.append_slice(self.data.as_ref())
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Tlv<Other>> for Tlv<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Tlv<Other>) -> bool {
+        self.dso_type == other.dso_type
+            && self.data.as_ref() == other.data.as_ref()
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Tlv<Octs> {}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Tlv<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.dso_type.hash(state);
+        self.data.as_ref().hash(state);
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Tlv<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Tlv")
+            .field("dso_type", &self.dso_type)
+            .field(
+                "data",
+                &format_args!("{}", base16::encode_display(&self.data)),
+            )
+            .finish()
+    }
+}
+
+//------------ TlvData -------------------------------------------------------
+
+/// A type representing the data of a DSO TLV.
+pub trait TlvData {
+    /// Returns the DSO type associated with this TLV data.
+    fn dso_type(&self) -> DsoType;
+}
+
+//------------ ParseTlvData --------------------------------------------------
+
+/// TLV data that can be parsed from wire format.
+pub trait ParseTlvData<'a, Octs: ?Sized>: TlvData + Sized {
+    /// Parses the TLV data.
+    ///
+    /// The data is for a TLV of `dso_type`. The function should return
+    /// `Ok(None)` if it doesn’t handle data of that type. In this case, it
+    /// must not advance the parser.
+    ///
+    /// The `parser` is positioned at the beginning of the data and is
+    /// limited to the length of the data.
+    fn parse_tlv(
+        dso_type: DsoType,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError>;
+}
+
+//------------ ComposeTlvData ------------------------------------------------
+
+/// TLV data that can be written in wire format.
+pub trait ComposeTlvData: TlvData {
+    /// Returns the length of the wire format of the data.
+    fn compose_len(&self) -> u16;
+
+    /// Appends the wire format of the data to the target.
+    fn compose_tlv<Target: OctetsBuilder + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError>;
+}
+
+//------------ Keepalive -----------------------------------------------------
+
+/// The data of the Keepalive TLV.
+///
+/// This TLV is used by a client to establish a DSO session and by both
+/// sides to communicate the timeouts of the session. It contains two
+/// values in milliseconds. The inactivity timeout is the time the session
+/// may stay idle before the client has to close it. The keepalive
+/// interval is the longest time a session may go without any traffic at
+/// all.
+///
+/// For both values, the largest possible value of `0xFFFF_FFFF`
+/// milliseconds means infinity. The methods returning the values as a
+/// [`Duration`] map this value to `None`.
+///
+/// The TLV is defined in section 7.1 of [RFC 8490].
+///
+/// [RFC 8490]: https://tools.ietf.org/html/rfc8490
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Keepalive {
+    /// The inactivity timeout in milliseconds.
+    inactivity_timeout: u32,
+
+    /// The keepalive interval in milliseconds.
+    keepalive_interval: u32,
+}
+
+impl Keepalive {
+    /// The value representing an infinite time.
+    pub const INFINITY: u32 = u32::MAX;
+
+    /// Creates a new value from the two timeouts.
+    ///
+    /// A value of `None` means infinity. Durations too large to be
+    /// expressed are treated as infinity, too.
+    #[must_use]
+    pub fn new(
+        inactivity_timeout: Option<Duration>,
+        keepalive_interval: Option<Duration>,
+    ) -> Self {
+        Self::from_millis(
+            duration_to_millis(inactivity_timeout),
+            duration_to_millis(keepalive_interval),
+        )
+    }
+
+    /// Creates a new value from the two timeouts in milliseconds.
+    #[must_use]
+    pub fn from_millis(
+        inactivity_timeout: u32,
+        keepalive_interval: u32,
+    ) -> Self {
+        Keepalive {
+            inactivity_timeout,
+            keepalive_interval,
+        }
+    }
+
+    /// Returns the inactivity timeout or `None` for infinity.
+    #[must_use]
+    pub fn inactivity_timeout(self) -> Option<Duration> {
+        millis_to_duration(self.inactivity_timeout)
+    }
+
+    /// Returns the inactivity timeout in milliseconds.
+    #[must_use]
+    pub fn inactivity_timeout_millis(self) -> u32 {
+        self.inactivity_timeout
+    }
+
+    /// Returns the keepalive interval or `None` for infinity.
+    #[must_use]
+    pub fn keepalive_interval(self) -> Option<Duration> {
+        millis_to_duration(self.keepalive_interval)
+    }
+
+    /// Returns the keepalive interval in milliseconds.
+    #[must_use]
+    pub fn keepalive_interval_millis(self) -> u32 {
+        self.keepalive_interval
+    }
+}
+
+//--- TlvData, ParseTlvData, and ComposeTlvData
+
+impl TlvData for Keepalive {
+    fn dso_type(&self) -> DsoType {
+        DsoType::KEEPALIVE
+    }
+}
+
+impl<'a, Octs: AsRef<[u8]> + ?Sized> ParseTlvData<'a, Octs> for Keepalive {
+    fn parse_tlv(
+        dso_type: DsoType,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if dso_type != DsoType::KEEPALIVE {
+            return Ok(None);
+        }
+        Ok(Some(Self::from_millis(
+            u32::parse(parser)?,
+            u32::parse(parser)?,
+        )))
+    }
+}
+
+impl ComposeTlvData for Keepalive {
+    fn compose_len(&self) -> u16 {
+        2 * u32::COMPOSE_LEN
+    }
+
+    fn compose_tlv<Target: OctetsBuilder + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.inactivity_timeout.compose(target)?;
+        self.keepalive_interval.compose(target)
+    }
+}
+
+//------------ RetryDelay ----------------------------------------------------
+
+/// The data of the Retry Delay TLV.
+///
+/// A server uses this TLV to ask a client to close its session and not to
+/// reconnect before the given time has passed. As a primary TLV, it
+/// appears in unidirectional messages. As an additional TLV, it can be
+/// added to error responses to indicate when the client may retry.
+///
+/// The TLV is defined in section 7.2 of [RFC 8490].
+///
+/// [RFC 8490]: https://tools.ietf.org/html/rfc8490
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RetryDelay {
+    /// The delay in milliseconds.
+    delay: u32,
+}
+
+impl RetryDelay {
+    /// Creates a new value from a duration.
+    ///
+    /// Durations too large to be expressed are capped.
+    #[must_use]
+    pub fn new(delay: Duration) -> Self {
+        Self::from_millis(duration_to_millis(Some(delay)))
+    }
+
+    /// Creates a new value from a delay in milliseconds.
+    #[must_use]
+    pub fn from_millis(delay: u32) -> Self {
+        RetryDelay { delay }
+    }
+
+    /// Returns the delay.
+    #[must_use]
+    pub fn delay(self) -> Duration {
+        Duration::from_millis(self.delay.into())
+    }
+
+    /// Returns the delay in milliseconds.
+    #[must_use]
+    pub fn delay_millis(self) -> u32 {
+        self.delay
+    }
+}
+
+//--- TlvData, ParseTlvData, and ComposeTlvData
+
+impl TlvData for RetryDelay {
+    fn dso_type(&self) -> DsoType {
+        DsoType::RETRY_DELAY
+    }
+}
+
+impl<'a, Octs: AsRef<[u8]> + ?Sized> ParseTlvData<'a, Octs> for RetryDelay {
+    fn parse_tlv(
+        dso_type: DsoType,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if dso_type != DsoType::RETRY_DELAY {
+            return Ok(None);
+        }
+        u32::parse(parser).map(|delay| Some(Self::from_millis(delay)))
+    }
+}
+
+impl ComposeTlvData for RetryDelay {
+    fn compose_len(&self) -> u16 {
+        u32::COMPOSE_LEN
+    }
+
+    fn compose_tlv<Target: OctetsBuilder + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.delay.compose(target)
+    }
+}
+
+//------------ EncryptionPadding ---------------------------------------------
+
+/// The data of the Encryption Padding TLV.
+///
+/// This TLV is used to increase the size of a DSO message sent over an
+/// encrypted transport so that eavesdroppers can’t learn anything from the
+/// size. It is only ever an additional TLV and its content is ignored by
+/// the receiver.
+///
+/// When building a message, [`DsoBuilder::padding`] can be used to add
+/// padding without creating a value of this type.
+///
+/// The TLV is defined in section 7.3 of [RFC 8490].
+///
+/// [RFC 8490]: https://tools.ietf.org/html/rfc8490
+#[derive(Clone)]
+pub struct EncryptionPadding<Octs> {
+    /// The padding octets.
+    octets: Octs,
+}
+
+impl<Octs> EncryptionPadding<Octs> {
+    /// Creates a value from the padding octets.
+    ///
+    /// Returns an error if `octets` are longer than 65,535 octets.
+    pub fn from_octets(octets: Octs) -> Result<Self, LongTlvData>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        LongTlvData::check_len(octets.as_ref().len())?;
+        Ok(EncryptionPadding { octets })
+    }
+
+    /// Returns a reference to the padding octets.
+    pub fn as_octets(&self) -> &Octs {
+        &self.octets
+    }
+
+    /// Converts the value into the padding octets.
+    pub fn into_octets(self) -> Octs {
+        self.octets
+    }
+
+    /// Returns a slice of the padding octets.
+    pub fn as_slice(&self) -> &[u8]
+    where
+        Octs: AsRef<[u8]>,
+    {
+        self.octets.as_ref()
+    }
+}
+
+//--- TlvData, ParseTlvData, and ComposeTlvData
+
+impl<Octs> TlvData for EncryptionPadding<Octs> {
+    fn dso_type(&self) -> DsoType {
+        DsoType::ENCRYPTION_PADDING
+    }
+}
+
+impl<'a, Octs> ParseTlvData<'a, Octs> for EncryptionPadding<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_tlv(
+        dso_type: DsoType,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if dso_type != DsoType::ENCRYPTION_PADDING {
+            return Ok(None);
+        }
+        let octets = parser.parse_octets(parser.remaining())?;
+        Ok(Some(EncryptionPadding { octets }))
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeTlvData for EncryptionPadding<Octs> {
+    fn compose_len(&self) -> u16 {
+        self.octets
+            .as_ref()
+            .len()
+            .try_into()
+            .expect("long TLV data")
+    }
+
+    fn compose_tlv<Target: OctetsBuilder + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        target.append_slice(self.octets.as_ref())
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for EncryptionPadding<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("EncryptionPadding")
+            .field(&self.octets.as_ref().len())
+            .finish()
+    }
+}
+
+//------------ DsoBuilder ----------------------------------------------------
+
+/// Builds a DSO message.
+///
+/// A builder is created from a [`MessageBuilder`] via
+/// [`from_builder`][Self::from_builder]. This sets the opcode of the
+/// message to DSO. The header can be further modified via
+/// [`header_mut`][Self::header_mut]. In particular, the message ID needs
+/// to be set to a non-zero value for requests that expect a response.
+///
+/// TLVs are appended via [`push`][Self::push]. The first TLV pushed to a
+/// request becomes its primary TLV.
+#[derive(Clone, Debug)]
+pub struct DsoBuilder<Target> {
+    /// The target with the message.
+    target: Target,
+}
+
+impl<Target: Composer> DsoBuilder<Target> {
+    /// Creates a DSO builder from a message builder.
+    ///
+    /// The opcode of the message header is set to DSO.
+    pub fn from_builder(builder: MessageBuilder<Target>) -> Self {
+        let mut res = DsoBuilder {
+            target: builder.finish(),
+        };
+        res.header_mut().set_opcode(Opcode::DSO);
+        res
+    }
+
+    /// Returns the header of the message.
+    pub fn header(&self) -> Header {
+        *Header::for_message_slice(self.target.as_ref())
+    }
+
+    /// Returns a mutable reference to the header of the message.
+    pub fn header_mut(&mut self) -> &mut Header {
+        Header::for_message_slice_mut(self.target.as_mut())
+    }
+
+    /// Appends a TLV to the message.
+    pub fn push<Data: ComposeTlvData + ?Sized>(
+        &mut self,
+        data: &Data,
+    ) -> Result<(), Target::AppendError> {
+        self.push_raw(data.dso_type(), data.compose_len(), |target| {
+            data.compose_tlv(target)
+        })
+    }
+
+    /// Appends a raw TLV to the message.
+    ///
+    /// The TLV will have the given type and length. Its data is written
+    /// by the closure `op` which must write exactly `len` octets.
+    pub fn push_raw<F>(
+        &mut self,
+        dso_type: DsoType,
+        len: u16,
+        op: F,
+    ) -> Result<(), Target::AppendError>
+    where
+        F: FnOnce(&mut Target) -> Result<(), Target::AppendError>,
+    {
+        let pos = self.target.as_ref().len();
+        let res = dso_type
+            .compose(&mut self.target)
+            .and_then(|_| len.compose(&mut self.target))
+            .and_then(|_| op(&mut self.target));
+        if res.is_err() {
+            self.target.truncate(pos);
+        }
+        res
+    }
+
+    /// Appends an Encryption Padding TLV with `len` zero octets.
+    pub fn padding(&mut self, len: u16) -> Result<(), Target::AppendError> {
+        self.push_raw(DsoType::ENCRYPTION_PADDING, len, |target| {
+            (0..len).try_for_each(|_| 0u8.compose(target))
+        })
+    }
+
+    /// Returns a slice of the message assembled so far.
+    pub fn as_slice(&self) -> &[u8] {
+        self.target.as_ref()
+    }
+
+    /// Returns a reference to the underlying target.
+    pub fn as_target(&self) -> &Target {
+        &self.target
+    }
+
+    /// Finishes the message and returns the underlying target.
+    pub fn finish(self) -> Target {
+        self.target
+    }
+
+    /// Finishes the message and returns it as a DSO message.
+    pub fn into_message(self) -> DsoMessage<Target::Octets>
+    where
+        Target: FreezeBuilder,
+        Target::Octets: AsRef<[u8]>,
+    {
+        DsoMessage {
+            message: unsafe {
+                Message::from_octets_unchecked(self.target.freeze())
+            },
+        }
+    }
+}
+
+//============ Error Types ===================================================
+
+//------------ LongTlvData ---------------------------------------------------
+
+/// The TLV data was too long.
+#[derive(Clone, Copy, Debug)]
+pub struct LongTlvData(());
+
+impl LongTlvData {
+    /// Checks that a length is acceptable for TLV data.
+    fn check_len(len: usize) -> Result<(), Self> {
+        if len > usize::from(u16::MAX) {
+            Err(LongTlvData(()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl fmt::Display for LongTlvData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("TLV data too long")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LongTlvData {}
+
+//============ Helper Functions ==============================================
+
+/// Converts an optional duration into milliseconds with infinity.
+fn duration_to_millis(duration: Option<Duration>) -> u32 {
+    duration
+        .and_then(|duration| u32::try_from(duration.as_millis()).ok())
+        .unwrap_or(Keepalive::INFINITY)
+}
+
+/// Converts milliseconds with infinity into an optional duration.
+fn millis_to_duration(millis: u32) -> Option<Duration> {
+    if millis == Keepalive::INFINITY {
+        None
+    } else {
+        Some(Duration::from_millis(millis.into()))
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    use super::*;
+    use std::vec::Vec;
+
+    #[test]
+    fn build_and_parse() {
+        let mut msg = DsoBuilder::from_builder(MessageBuilder::new_vec());
+        msg.header_mut().set_id(12);
+        msg.push(&Keepalive::from_millis(15_000, Keepalive::INFINITY))
+            .unwrap();
+        msg.push(&RetryDelay::new(Duration::from_secs(5))).unwrap();
+        msg.padding(3).unwrap();
+        msg.push(&Tlv::new(DsoType::from_int(0xF000), b"foo").unwrap())
+            .unwrap();
+        let msg = msg.into_message();
+
+        assert_eq!(msg.header().opcode(), Opcode::DSO);
+        assert!(!msg.is_unidirectional());
+        assert_eq!(
+            &msg.as_message().as_slice()[12..],
+            b"\x00\x01\x00\x08\x00\x00\x3a\x98\xff\xff\xff\xff\
+              \x00\x02\x00\x04\x00\x00\x13\x88\
+              \x00\x03\x00\x03\x00\x00\x00\
+              \xf0\x00\x00\x03foo"
+        );
+
+        let tlvs = msg.tlvs().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(tlvs.len(), 4);
+        let keepalive = tlvs[0].parse_data::<Keepalive>().unwrap().unwrap();
+        assert_eq!(
+            keepalive.inactivity_timeout(),
+            Some(Duration::from_secs(15))
+        );
+        assert_eq!(keepalive.keepalive_interval(), None);
+        assert!(tlvs[0].parse_data::<RetryDelay>().unwrap().is_none());
+        assert_eq!(
+            tlvs[1].parse_data::<RetryDelay>().unwrap().unwrap().delay(),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            tlvs[2]
+                .parse_data::<EncryptionPadding<_>>()
+                .unwrap()
+                .unwrap()
+                .as_slice(),
+            b"\0\0\0"
+        );
+        assert_eq!(tlvs[3].dso_type(), DsoType::from_int(0xF000));
+        assert_eq!(tlvs[3].as_slice(), b"foo");
+    }
+
+    #[test]
+    fn reject_non_dso() {
+        let msg = MessageBuilder::new_vec().into_message();
+        assert!(DsoMessage::from_message(msg).is_err());
+
+        let mut msg = MessageBuilder::new_vec();
+        msg.header_mut().set_opcode(Opcode::DSO);
+        let mut msg = msg.question();
+        msg.push((crate::base::Name::root_slice(), crate::base::Rtype::A))
+            .unwrap();
+        assert!(DsoMessage::from_message(msg.into_message()).is_err());
+    }
+
+    #[test]
+    fn bad_tlvs() {
+        let mut msg = DsoBuilder::from_builder(MessageBuilder::new_vec());
+        msg.push_raw(DsoType::KEEPALIVE, 4, |target| 0u32.compose(target))
+            .unwrap();
+        msg.push(&RetryDelay::from_millis(0)).unwrap();
+        let msg = msg.into_message();
+        assert!(msg.is_unidirectional());
+        assert!(msg
+            .primary_tlv()
+            .unwrap()
+            .unwrap()
+            .parse_data::<Keepalive>()
+            .is_err());
+
+        // Truncated TLV.
+        let mut octets = msg.into_message().into_octets();
+        octets.pop();
+        let msg = DsoMessage::from_octets(octets).unwrap();
+        let mut tlvs = msg.tlvs();
+        assert!(tlvs.next().unwrap().is_ok());
+        assert!(tlvs.next().unwrap().is_err());
+        assert!(tlvs.next().is_none());
+    }
+}
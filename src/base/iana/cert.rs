@@ -0,0 +1,50 @@
+//! Certificate types.
+
+//------------ CertType ------------------------------------------------------
+
+int_enum! {
+    /// Certificate types.
+    ///
+    /// The certificate type of a CERT record specifies the format of the
+    /// certificate or CRL contained in the record.
+    ///
+    /// For the currently registered values see the [IANA registration].
+    /// This type is complete as of the registry update of 2006-03-28.
+    ///
+    /// [IANA registration]: https://www.iana.org/assignments/cert-rr-types/cert-rr-types.xhtml
+    =>
+    CertType, u16;
+
+    /// X.509 as per PKIX.
+    (PKIX => 1, "PKIX")
+
+    /// SPKI certificate.
+    (SPKI => 2, "SPKI")
+
+    /// OpenPGP packet.
+    (PGP => 3, "PGP")
+
+    /// The URL of an X.509 data object.
+    (IPKIX => 4, "IPKIX")
+
+    /// The URL of an SPKI certificate.
+    (ISPKI => 5, "ISPKI")
+
+    /// The fingerprint and URL of an OpenPGP packet.
+    (IPGP => 6, "IPGP")
+
+    /// Attribute certificate.
+    (ACPKIX => 7, "ACPKIX")
+
+    /// The URL of an attribute certificate.
+    (IACPKIX => 8, "IACPKIX")
+
+    /// URI private.
+    (URI => 253, "URI")
+
+    /// OID private.
+    (OID => 254, "OID")
+}
+
+int_enum_str_with_decimal!(CertType, u16, "unknown certificate type");
+int_enum_zonefile_fmt_with_decimal!(CertType);
@@ -56,6 +56,41 @@ int_enum! {
     (ANY => 0xFF, "*")
 }
 
+/// # Multicast DNS
+///
+/// Multicast DNS as defined in [RFC 6762] uses the top bit of the class
+/// field of questions and records as a flag. In questions, it requests a
+/// unicast response. In records, it is the cache-flush bit. The class
+/// itself is given by the remaining 15 bits.
+///
+/// [RFC 6762]: https://tools.ietf.org/html/rfc6762
+impl Class {
+    /// The bit used as a flag by multicast DNS.
+    const MDNS_BIT: u16 = 0x8000;
+
+    /// Returns whether the multicast DNS flag bit is set.
+    #[must_use]
+    pub const fn mdns_bit(self) -> bool {
+        self.0 & Self::MDNS_BIT != 0
+    }
+
+    /// Returns the class with the multicast DNS flag bit cleared.
+    #[must_use]
+    pub const fn without_mdns_bit(self) -> Self {
+        Class(self.0 & !Self::MDNS_BIT)
+    }
+
+    /// Returns the class with the multicast DNS flag bit set to `value`.
+    #[must_use]
+    pub const fn with_mdns_bit(self, value: bool) -> Self {
+        if value {
+            Class(self.0 | Self::MDNS_BIT)
+        } else {
+            self.without_mdns_bit()
+        }
+    }
+}
+
 int_enum_str_with_prefix!(Class, "CLASS", b"CLASS", u16, "unknown class");
 int_enum_zonefile_fmt_with_prefix!(Class, "CLASS");
 
@@ -84,4 +119,16 @@ mod test {
         assert_eq!(format!("{:?}", Class::IN), "Class::IN");
         assert_eq!(format!("{:?}", Class(69)), "Class(69)");
     }
+
+    #[test]
+    fn mdns_bit() {
+        use super::Class;
+
+        let class = Class::from_int(0x8001);
+        assert!(class.mdns_bit());
+        assert_eq!(class.without_mdns_bit(), Class::IN);
+        assert!(!Class::IN.mdns_bit());
+        assert_eq!(Class::IN.with_mdns_bit(true), class);
+        assert_eq!(class.with_mdns_bit(false), Class::IN);
+    }
 }
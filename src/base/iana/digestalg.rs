@@ -9,7 +9,7 @@ int_enum! {
     /// key digest in the record has been generated.
     ///
     /// For the currently registered values see the [IANA registration].
-    /// This type is complete as of the registry update of 2012-04-13.
+    /// This type is complete as of the registration of SM3 by RFC 9563.
     ///
     /// [IANA registration]: https://www.iana.org/assignments/ds-rr-types/ds-rr-types.xhtml#ds-rr-types-1
     =>
@@ -40,15 +40,50 @@ int_enum! {
     ///
     /// [RFC 6605]: https://tools.ietf.org/html/rfc6605
     (SHA384 => 4, "SHA-384")
+
+    /// Specifies that the GOST R 34.11-2012 hash function is used.
+    ///
+    /// Use of this hash function is described in [RFC 9558]. Implementing
+    /// the function is optional.
+    ///
+    /// [RFC 9558]: https://tools.ietf.org/html/rfc9558
+    (GOST12 => 5, "GOST R 34.11-2012")
+
+    /// Specifies that the SM3 hash function is used.
+    ///
+    /// Use of this hash function is described in [RFC 9563]. Implementing
+    /// the function is optional.
+    ///
+    /// [RFC 9563]: https://tools.ietf.org/html/rfc9563
+    (SM3 => 6, "SM3")
 }
 
-int_enum_str_decimal!(DigestAlg, u8);
+int_enum_str_mnemonic_or_decimal!(DigestAlg, u8, "unknown digest algorithm");
 int_enum_zonefile_fmt_decimal!(DigestAlg, "digest type");
 
 //============ Tests =========================================================
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_str_and_display() {
+        use super::DigestAlg;
+        use core::str::FromStr;
+        use std::string::ToString;
+
+        assert_eq!(
+            DigestAlg::from_str("SHA-256").unwrap(),
+            DigestAlg::SHA256
+        );
+        assert_eq!(DigestAlg::from_str("sm3").unwrap(), DigestAlg::SM3);
+        assert_eq!(DigestAlg::from_str("4").unwrap(), DigestAlg::SHA384);
+        assert!(DigestAlg::from_str("SHA256").is_err());
+
+        assert_eq!(DigestAlg::GOST12.to_string(), "GOST R 34.11-2012");
+        assert_eq!(DigestAlg::from_int(100).to_string(), "100");
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn ser_de() {
@@ -0,0 +1,79 @@
+//! DSO TLV types.
+
+//------------ DsoType -------------------------------------------------------
+
+int_enum! {
+    /// DNS Stateful Operations type codes.
+    ///
+    /// The body of a [DSO] message consists of a sequence of TLVs, each
+    /// starting with a 16 bit value specifying its type. The first TLV of a
+    /// request is the primary TLV and selects the operation requested.
+    ///
+    /// The currently assigned types can be found in the [IANA registry].
+    /// The type contains the values defined by RFC 8490 and RFC 8765.
+    ///
+    /// [DSO]: ../../dso/index.html
+    /// [IANA registry]: https://www.iana.org/assignments/dns-parameters/dns-parameters.xhtml#dso-type-codes
+    =>
+    DsoType, u16;
+
+    /// Keepalive (1).
+    ///
+    /// This TLV is used to establish a DSO session and to convey the
+    /// inactivity timeout and keepalive interval of the session.
+    ///
+    /// Defined in [RFC 8490].
+    ///
+    /// [RFC 8490]: https://tools.ietf.org/html/rfc8490
+    (KEEPALIVE => 1, "KeepAlive")
+
+    /// Retry Delay (2).
+    ///
+    /// A server uses this TLV to ask a client to close the session and not
+    /// reconnect for the given time.
+    ///
+    /// Defined in [RFC 8490].
+    ///
+    /// [RFC 8490]: https://tools.ietf.org/html/rfc8490
+    (RETRY_DELAY => 2, "RetryDelay")
+
+    /// Encryption Padding (3).
+    ///
+    /// This TLV is used to pad DSO messages sent over encrypted transports.
+    ///
+    /// Defined in [RFC 8490].
+    ///
+    /// [RFC 8490]: https://tools.ietf.org/html/rfc8490
+    (ENCRYPTION_PADDING => 3, "EncryptionPadding")
+
+    /// DNS Push Notification SUBSCRIBE (0x40).
+    ///
+    /// Defined in [RFC 8765].
+    ///
+    /// [RFC 8765]: https://tools.ietf.org/html/rfc8765
+    (SUBSCRIBE => 0x40, "SUBSCRIBE")
+
+    /// DNS Push Notification PUSH (0x41).
+    ///
+    /// Defined in [RFC 8765].
+    ///
+    /// [RFC 8765]: https://tools.ietf.org/html/rfc8765
+    (PUSH => 0x41, "PUSH")
+
+    /// DNS Push Notification UNSUBSCRIBE (0x42).
+    ///
+    /// Defined in [RFC 8765].
+    ///
+    /// [RFC 8765]: https://tools.ietf.org/html/rfc8765
+    (UNSUBSCRIBE => 0x42, "UNSUBSCRIBE")
+
+    /// DNS Push Notification RECONFIRM (0x43).
+    ///
+    /// Defined in [RFC 8765].
+    ///
+    /// [RFC 8765]: https://tools.ietf.org/html/rfc8765
+    (RECONFIRM => 0x43, "RECONFIRM")
+}
+
+int_enum_str_with_decimal!(DsoType, u16, "unknown DSO type");
+int_enum_zonefile_fmt_with_decimal!(DsoType);
@@ -150,6 +150,31 @@ int_enum! {
     /// its most recent zone being too old or having expired.
     (INVALID_DATA => 24, "Invalid Data")
 
+    /// The resolver found an RRSIG whose expiration time lies before its
+    /// inception time.
+    (SIGNATURE_EXPIRED_BEFORE_VALID => 25, "Signature Expired before Valid")
+
+    /// The query was answered too early, i.e., before the time an earlier
+    /// response told the client it should retry the query.
+    (TOO_EARLY => 26, "Too Early")
+
+    /// The NSEC3 RRset uses an iteration value the resolver does not
+    /// support, as specified in [RFC 9276].
+    ///
+    /// [RFC 9276]: https://tools.ietf.org/html/rfc9276
+    (UNSUPPORTED_NSEC3_ITERATIONS_VALUE => 27,
+        "Unsupported NSEC3 Iterations Value")
+
+    /// The server is unable to respond to the query according to the
+    /// policy requested by the client.
+    (UNABLE_TO_CONFORM_TO_POLICY => 28, "Unable to conform to policy")
+
+    /// The answer was synthesized from other data, e.g., from aggressive
+    /// use of the DNSSEC-validated cache as described in [RFC 8198].
+    ///
+    /// [RFC 8198]: https://tools.ietf.org/html/rfc8198
+    (SYNTHESIZED => 29, "Synthesized")
+
     /// The requested resource record type should not appear in a query.
     (INVALID_QUERY_TYPE => 30, "Invalid Query Type")
 }
@@ -0,0 +1,36 @@
+//! IPSECKEY algorithm types.
+
+//------------ IpseckeyAlg ---------------------------------------------------
+
+int_enum! {
+    /// IPSECKEY public key algorithms.
+    ///
+    /// The algorithm type identifies the public key’s cryptographic
+    /// algorithm and determines the format of the public key field. The
+    /// values are used by both IPSECKEY and HIP records.
+    ///
+    /// For the currently registered values see the [IANA registration].
+    /// This type is complete as of the registry update of 2023-03-14.
+    ///
+    /// [IANA registration]: https://www.iana.org/assignments/ipseckey-rr-parameters/ipseckey-rr-parameters.xhtml#ipseckey-rr-parameters-1
+    =>
+    IpseckeyAlg, u8;
+
+    /// No key is present.
+    (NONE => 0, "NONE")
+
+    /// A DSA key as defined in RFC 2536.
+    (DSA => 1, "DSA")
+
+    /// An RSA key as defined in RFC 3110.
+    (RSA => 2, "RSA")
+
+    /// An ECDSA key as defined in RFC 6605.
+    (ECDSA => 3, "ECDSA")
+
+    /// An EdDSA key as defined in RFC 9373.
+    (EDDSA => 4, "EdDSA")
+}
+
+int_enum_str_decimal!(IpseckeyAlg, u8);
+int_enum_zonefile_fmt_decimal!(IpseckeyAlg, "algorithm");
@@ -330,6 +330,73 @@ macro_rules! int_enum_str_with_decimal {
     };
 }
 
+/// Adds impls for `FromStr` and `Display` to the type given as first argument.
+///
+/// For `FromStr`, recognizes all mnemonics case-insensitively as well as a
+/// decimal number representing any value.
+///
+/// For `Display`, it will display the mnemonic or, for values without
+/// mnemonic, a decimal number.
+///
+/// If the `serde` feature is enabled, also adds implementation for
+/// `Serialize` and `Deserialize`, serializing values as their decimal values.
+macro_rules! int_enum_str_mnemonic_or_decimal {
+    ($ianatype:ident, $inttype:ident, $error:expr) => {
+        impl $ianatype {
+            #[must_use]
+            pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+                $ianatype::from_mnemonic(bytes).or_else(|| {
+                    core::str::from_utf8(bytes)
+                        .ok()
+                        .and_then(|r| r.parse().ok().map($ianatype::from_int))
+                })
+            }
+        }
+
+        impl core::str::FromStr for $ianatype {
+            type Err = FromStrError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                // We assume all mnemonics are always ASCII, so using
+                // the bytes representation of `s` is safe.
+                $ianatype::from_bytes(s.as_bytes()).ok_or(FromStrError(()))
+            }
+        }
+
+        impl core::fmt::Display for $ianatype {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                match self.to_mnemonic_str() {
+                    Some(m) => f.write_str(m),
+                    None => write!(f, "{}", self.to_int()),
+                }
+            }
+        }
+
+        scan_impl!($ianatype);
+
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $ianatype {
+            fn serialize<S: serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                self.to_int().serialize(serializer)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $ianatype {
+            fn deserialize<D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Self, D::Error> {
+                $inttype::deserialize(deserializer).map(Into::into)
+            }
+        }
+
+        from_str_error!($error);
+    };
+}
+
 /// Adds impls for `FromStr` and `Display` to the type given as first argument.
 ///
 /// For `FromStr` recognizes all defined mnemonics ignoring case. Additionally
@@ -20,5 +20,5 @@ int_enum! {
     (SHA1 => 1, "SHA-1")
 }
 
-int_enum_str_decimal!(Nsec3HashAlg, u8);
+int_enum_str_mnemonic_or_decimal!(Nsec3HashAlg, u8, "unknown hash algorithm");
 int_enum_zonefile_fmt_decimal!(Nsec3HashAlg, "hash algorithm");
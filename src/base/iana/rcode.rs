@@ -160,6 +160,16 @@ impl Rcode {
     ///
     /// [RFC 2136]: https://tools.ietf.org/html/rfc2136
     pub const NOTZONE: Self = Self(10);
+
+    /// DSO-TYPE not implemented.
+    ///
+    /// A DSO request contained a primary TLV type that the server does not
+    /// implement.
+    ///
+    /// Defined in [RFC 8490].
+    ///
+    /// [RFC 8490]: https://tools.ietf.org/html/rfc8490
+    pub const DSOTYPENI: Self = Self(11);
 }
 
 impl Rcode {
@@ -207,6 +217,7 @@ impl Rcode {
             Rcode::NXRRSET => Some(b"NXRRSET"),
             Rcode::NOTAUTH => Some(b"NOTAUTH"),
             Rcode::NOTZONE => Some(b"NOTZONE"),
+            Rcode::DSOTYPENI => Some(b"DSOTYPENI"),
             _ => None,
         }
     }
@@ -230,6 +241,7 @@ impl FromStr for Rcode {
             "NXRRSET" => Ok(Rcode::NXRRSET),
             "NOTAUTH" => Ok(Rcode::NOTAUTH),
             "NOTZONE" => Ok(Rcode::NOTZONE),
+            "DSOTYPENI" => Ok(Rcode::DSOTYPENI),
             _ => Err(()),
         }
     }
@@ -475,6 +487,16 @@ impl OptRcode {
     /// [RFC 2136]: https://tools.ietf.org/html/rfc2136
     pub const NOTZONE: Self = Self::from_rcode(Rcode::NOTZONE);
 
+    /// DSO-TYPE not implemented.
+    ///
+    /// A DSO request contained a primary TLV type that the server does not
+    /// implement.
+    ///
+    /// Defined in [RFC 8490].
+    ///
+    /// [RFC 8490]: https://tools.ietf.org/html/rfc8490
+    pub const DSOTYPENI: Self = Self::from_rcode(Rcode::DSOTYPENI);
+
     /// Bad OPT version.
     ///
     /// A name server does not implement the EDNS version requested in the
@@ -504,14 +526,14 @@ impl OptRcode {
     /// The rcode is valid if the upper four bits of `value` are all zero.
     #[must_use]
     pub const fn checked_from_int(value: u16) -> Option<OptRcode> {
-        if value & 0x0FFF != 0 {
+        if value & 0xF000 != 0 {
             None
         } else {
             Some(Self(value))
         }
     }
 
-    /// Creates an rcode from an integer, only considering the lower four bits.
+    /// Creates an rcode from an integer, only considering the lower 12 bits.
     ///
     /// This function will ignore the upper four bit of `value`.
     #[must_use]
@@ -587,6 +609,7 @@ impl OptRcode {
             OptRcode::NXRRSET => Some(b"NXRRSET"),
             OptRcode::NOTAUTH => Some(b"NOTAUTH"),
             OptRcode::NOTZONE => Some(b"NOTZONE"),
+            OptRcode::DSOTYPENI => Some(b"DSOTYPENI"),
             OptRcode::BADVERS => Some(b"BADVERS"),
             OptRcode::BADCOOKIE => Some(b"BADCOOKIE"),
             _ => None,
@@ -612,6 +635,7 @@ impl FromStr for OptRcode {
             "NXRRSET" => Ok(OptRcode::NXRRSET),
             "NOTAUTH" => Ok(OptRcode::NOTAUTH),
             "NOTZONE" => Ok(OptRcode::NOTZONE),
+            "DSOTYPENI" => Ok(OptRcode::DSOTYPENI),
             "BADVERS" => Ok(OptRcode::BADVERS),
             "BADCOOKIE" => Ok(OptRcode::BADCOOKIE),
             _ => Err(()),
@@ -802,6 +826,16 @@ int_enum! {
     /// [RFC 2136]: https://tools.ietf.org/html/rfc2136
     (NOTZONE => 10, "NOTZONE")
 
+    /// DSO-TYPE not implemented.
+    ///
+    /// A DSO request contained a primary TLV type that the server does not
+    /// implement.
+    ///
+    /// Defined in [RFC 8490].
+    ///
+    /// [RFC 8490]: https://tools.ietf.org/html/rfc8490
+    (DSOTYPENI => 11, "DSOTYPENI")
+
     /// TSIG signature failure.
     ///
     /// The TSIG signature fails to verify.
@@ -920,6 +954,19 @@ impl std::error::Error for InvalidRcode {}
 mod test {
     use super::*;
 
+    #[test]
+    fn optrcode_from_int() {
+        assert_eq!(OptRcode::checked_from_int(0), Some(OptRcode::NOERROR));
+        assert_eq!(OptRcode::checked_from_int(16), Some(OptRcode::BADVERS));
+        assert_eq!(
+            OptRcode::checked_from_int(0x0FFF).map(OptRcode::to_int),
+            Some(0x0FFF)
+        );
+        assert_eq!(OptRcode::checked_from_int(0x1000), None);
+        assert_eq!(OptRcode::try_from(23u16).ok(), Some(OptRcode::BADCOOKIE));
+        assert_eq!(OptRcode::masked_from_int(0x1017), OptRcode::BADCOOKIE);
+    }
+
     #[test]
     fn optrcode_parts() {
         // Define a macro to test the various functions involved in working
@@ -950,6 +997,7 @@ mod test {
         assert_opt_rcode_parts_eq!(OptRcode::NXRRSET, 0b000_0000, 0b1000);
         assert_opt_rcode_parts_eq!(OptRcode::NOTAUTH, 0b000_0000, 0b1001);
         assert_opt_rcode_parts_eq!(OptRcode::NOTZONE, 0b000_0000, 0b1010);
+        assert_opt_rcode_parts_eq!(OptRcode::DSOTYPENI, 0b000_0000, 0b1011);
         assert_opt_rcode_parts_eq!(OptRcode(15), 0b0000_0000, 0b1111);
         assert_opt_rcode_parts_eq!(OptRcode::BADVERS, 0b0000_0001, 0b0000);
         assert_opt_rcode_parts_eq!(OptRcode(17), 0b0000_0001, 0b0001);
@@ -420,6 +420,11 @@ int_enum! {
     /// See draft-durand-doa-over-dns.
     (DOA => 259, "DOA")
 
+    /// Automatic Multicast Tunneling Relay.
+    ///
+    /// See RFC 8777.
+    (AMTRELAY => 260, "AMTRELAY")
+
     /// DNSSEC trust authorities.
     (TA => 32768, "TA")
 
@@ -8,6 +8,7 @@ int_enum! {
     /// These numbers are used in various security related record types.
     ///
     /// For the currently registered values see the [IANA registration].
+    /// This type is complete as of the registration of SM2SM3 by RFC 9563.
     ///
     /// [IANA registration]: http://www.iana.org/assignments/dns-sec-alg-numbers/dns-sec-alg-numbers.xhtml#dns-sec-alg-numbers-1].
     =>
@@ -100,6 +101,17 @@ int_enum! {
     /// This algorithm is described in RFC 8080.
     (ED448 => 16, "ED448")
 
+    /// SM2 signing with SM3 hashing
+    ///
+    /// This algorithm is described in RFC 9563.
+    (SM2SM3 => 17, "SM2SM3")
+
+    /// GOST R 34.10-2012
+    ///
+    /// This algorithm is described in RFC 9558. It may be used for zone
+    /// signing only.
+    (ECC_GOST12 => 23, "ECC-GOST12")
+
     /// Reserved for Indirect Keys
     ///
     /// This value is reserved by RFC 4034.
@@ -116,5 +128,46 @@ int_enum! {
     (PRIVATEOID => 254, "PRIVATEOID")
 }
 
-int_enum_str_decimal!(SecAlg, u8);
+int_enum_str_mnemonic_or_decimal!(SecAlg, u8, "unknown algorithm");
 int_enum_zonefile_fmt_decimal!(SecAlg, "algorithm");
+
+//============ Tests =========================================================
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_str_and_display() {
+        use super::SecAlg;
+        use core::str::FromStr;
+        use std::string::ToString;
+
+        assert_eq!(SecAlg::from_str("RSASHA256").unwrap(), SecAlg::RSASHA256);
+        assert_eq!(SecAlg::from_str("ed25519").unwrap(), SecAlg::ED25519);
+        assert_eq!(
+            SecAlg::from_str("ECC-GOST12").unwrap(),
+            SecAlg::ECC_GOST12
+        );
+        assert_eq!(SecAlg::from_str("13").unwrap(), SecAlg::ECDSAP256SHA256);
+        assert_eq!(SecAlg::from_str("200").unwrap(), SecAlg::from_int(200));
+        assert!(SecAlg::from_str("RSASHA").is_err());
+        assert!(SecAlg::from_str("256").is_err());
+
+        assert_eq!(
+            SecAlg::RSASHA1_NSEC3_SHA1.to_string(),
+            "RSASHA1-NSEC3-SHA1"
+        );
+        assert_eq!(SecAlg::SM2SM3.to_string(), "SM2SM3");
+        assert_eq!(SecAlg::from_int(200).to_string(), "200");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn ser_de() {
+        use super::SecAlg;
+        use serde_test::{assert_tokens, Token};
+
+        assert_tokens(&SecAlg::ED25519, &[Token::U8(15)]);
+        assert_tokens(&SecAlg(200), &[Token::U8(200)]);
+    }
+}
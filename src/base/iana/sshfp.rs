@@ -0,0 +1,76 @@
+//! SSHFP algorithm and fingerprint type numbers.
+
+//------------ SshfpAlg ------------------------------------------------------
+
+int_enum! {
+    /// SSHFP public key algorithm numbers.
+    ///
+    /// These numbers are used in the SSHFP resource record to specify the
+    /// algorithm of the SSH public key the fingerprint was created from.
+    ///
+    /// For the currently registered values see the [IANA registration].
+    /// This type is complete as of the registry update of 2015-08-14.
+    ///
+    /// [IANA registration]: https://www.iana.org/assignments/dns-sshfp-rr-parameters/dns-sshfp-rr-parameters.xhtml#dns-sshfp-rr-parameters-1
+    =>
+    SshfpAlg, u8;
+
+    /// The RSA algorithm.
+    (RSA => 1, "RSA")
+
+    /// The DSA algorithm.
+    (DSA => 2, "DSA")
+
+    /// The ECDSA algorithm.
+    ///
+    /// Defined in [RFC 6594].
+    ///
+    /// [RFC 6594]: https://tools.ietf.org/html/rfc6594
+    (ECDSA => 3, "ECDSA")
+
+    /// The Ed25519 algorithm.
+    ///
+    /// Defined in [RFC 7479].
+    ///
+    /// [RFC 7479]: https://tools.ietf.org/html/rfc7479
+    (ED25519 => 4, "Ed25519")
+
+    /// The Ed448 algorithm.
+    ///
+    /// Defined in [RFC 8709].
+    ///
+    /// [RFC 8709]: https://tools.ietf.org/html/rfc8709
+    (ED448 => 6, "Ed448")
+}
+
+int_enum_str_decimal!(SshfpAlg, u8);
+int_enum_zonefile_fmt_decimal!(SshfpAlg, "algorithm");
+
+//------------ SshfpFpType ---------------------------------------------------
+
+int_enum! {
+    /// SSHFP fingerprint type numbers.
+    ///
+    /// These numbers are used in the SSHFP resource record to specify the
+    /// hash function used to create the fingerprint.
+    ///
+    /// For the currently registered values see the [IANA registration].
+    /// This type is complete as of the registry update of 2015-08-14.
+    ///
+    /// [IANA registration]: https://www.iana.org/assignments/dns-sshfp-rr-parameters/dns-sshfp-rr-parameters.xhtml#dns-sshfp-rr-parameters-2
+    =>
+    SshfpFpType, u8;
+
+    /// The fingerprint is the SHA-1 digest of the public key.
+    (SHA1 => 1, "SHA-1")
+
+    /// The fingerprint is the SHA-256 digest of the public key.
+    ///
+    /// Defined in [RFC 6594].
+    ///
+    /// [RFC 6594]: https://tools.ietf.org/html/rfc6594
+    (SHA256 => 2, "SHA-256")
+}
+
+int_enum_str_decimal!(SshfpFpType, u8);
+int_enum_zonefile_fmt_decimal!(SshfpFpType, "fingerprint type");
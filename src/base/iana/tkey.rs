@@ -0,0 +1,35 @@
+//! TKEY modes.
+
+//------------ TkeyMode ------------------------------------------------------
+
+int_enum! {
+    /// TKEY modes.
+    ///
+    /// The mode of a TKEY record specifies the scheme used for establishing
+    /// or deleting a shared secret key.
+    ///
+    /// For the currently registered values see the [IANA registration].
+    /// This type is complete as of the registry update of 2006-03-28.
+    ///
+    /// [IANA registration]: https://www.iana.org/assignments/tkey-modes/tkey-modes.xhtml
+    =>
+    TkeyMode, u16;
+
+    /// The key is assigned by the server.
+    (SERVER_ASSIGNMENT => 1, "server-assignment")
+
+    /// The key is established through a Diffie-Hellman exchange.
+    (DIFFIE_HELLMAN => 2, "diffie-hellman")
+
+    /// The key is established through a GSS-API negotiation.
+    (GSS_API => 3, "gss-api")
+
+    /// The key is assigned by the resolver.
+    (RESOLVER_ASSIGNMENT => 4, "resolver-assignment")
+
+    /// The key is deleted.
+    (KEY_DELETION => 5, "key-deletion")
+}
+
+int_enum_str_decimal!(TkeyMode, u16);
+int_enum_zonefile_fmt_decimal!(TkeyMode, "mode");
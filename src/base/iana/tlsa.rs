@@ -0,0 +1,113 @@
+//! TLSA certificate usages, selectors, and matching types.
+
+//------------ TlsaCertUsage -------------------------------------------------
+
+int_enum! {
+    /// TLSA certificate usages.
+    ///
+    /// The certificate usage of a TLSA record specifies how the association
+    /// data is to be matched against the certificate chain presented by a
+    /// server.
+    ///
+    /// For the currently registered values see the [IANA registration].
+    /// The mnemonics are those defined in [RFC 7218]. This type is complete
+    /// as of the registry update of 2014-04-30.
+    ///
+    /// [IANA registration]: https://www.iana.org/assignments/dane-parameters/dane-parameters.xhtml#certificate-usages
+    /// [RFC 7218]: https://tools.ietf.org/html/rfc7218
+    =>
+    TlsaCertUsage, u8;
+
+    /// CA constraint.
+    ///
+    /// The association data matches a trust anchor that must appear in the
+    /// certification path validated by regular PKIX path validation.
+    (PKIX_TA => 0, "PKIX-TA")
+
+    /// Service certificate constraint.
+    ///
+    /// The association data matches the end entity certificate which must
+    /// also pass regular PKIX path validation.
+    (PKIX_EE => 1, "PKIX-EE")
+
+    /// Trust anchor assertion.
+    ///
+    /// The association data matches a trust anchor which is used to
+    /// validate the end entity certificate.
+    (DANE_TA => 2, "DANE-TA")
+
+    /// Domain-issued certificate.
+    ///
+    /// The association data matches the end entity certificate. No PKIX
+    /// path validation takes place.
+    (DANE_EE => 3, "DANE-EE")
+
+    /// Reserved for private use.
+    (PRIVCERT => 255, "PrivCert")
+}
+
+int_enum_str_decimal!(TlsaCertUsage, u8);
+int_enum_zonefile_fmt_decimal!(TlsaCertUsage, "certificate usage");
+
+//------------ TlsaSelector --------------------------------------------------
+
+int_enum! {
+    /// TLSA selectors.
+    ///
+    /// The selector of a TLSA record specifies which part of the
+    /// certificate is matched against the association data.
+    ///
+    /// For the currently registered values see the [IANA registration].
+    /// The mnemonics are those defined in [RFC 7218]. This type is complete
+    /// as of the registry update of 2014-04-30.
+    ///
+    /// [IANA registration]: https://www.iana.org/assignments/dane-parameters/dane-parameters.xhtml#selectors
+    /// [RFC 7218]: https://tools.ietf.org/html/rfc7218
+    =>
+    TlsaSelector, u8;
+
+    /// The full certificate is used.
+    (CERT => 0, "Cert")
+
+    /// The DER-encoded SubjectPublicKeyInfo of the certificate is used.
+    (SPKI => 1, "SPKI")
+
+    /// Reserved for private use.
+    (PRIVSEL => 255, "PrivSel")
+}
+
+int_enum_str_decimal!(TlsaSelector, u8);
+int_enum_zonefile_fmt_decimal!(TlsaSelector, "selector");
+
+//------------ TlsaMatchingType ----------------------------------------------
+
+int_enum! {
+    /// TLSA matching types.
+    ///
+    /// The matching type of a TLSA record specifies how the data selected
+    /// from the certificate is presented in the association data.
+    ///
+    /// For the currently registered values see the [IANA registration].
+    /// The mnemonics are those defined in [RFC 7218]. This type is complete
+    /// as of the registry update of 2014-04-30.
+    ///
+    /// [IANA registration]: https://www.iana.org/assignments/dane-parameters/dane-parameters.xhtml#matching-types
+    /// [RFC 7218]: https://tools.ietf.org/html/rfc7218
+    =>
+    TlsaMatchingType, u8;
+
+    /// The selected data is included as is.
+    (FULL => 0, "Full")
+
+    /// The SHA-256 hash of the selected data is included.
+    (SHA2_256 => 1, "SHA2-256")
+
+    /// The SHA-512 hash of the selected data is included.
+    (SHA2_512 => 2, "SHA2-512")
+
+    /// Reserved for private use.
+    (PRIVMATCH => 255, "PrivMatch")
+}
+
+int_enum_str_decimal!(TlsaMatchingType, u8);
+int_enum_zonefile_fmt_decimal!(TlsaMatchingType, "matching type");
@@ -14,13 +14,14 @@ use super::dig_printer::DigPrinter;
 use super::header::{Header, HeaderCounts, HeaderSection};
 use super::iana::{Class, OptRcode, Rcode, Rtype};
 use super::message_builder::{AdditionalBuilder, AnswerBuilder, PushError};
-use super::name::ParsedName;
+use super::name::{ParsedName, ToName};
 use super::opt::{Opt, OptRecord};
 use super::question::Question;
 use super::rdata::{ParseAnyRecordData, ParseRecordData};
 use super::record::{ComposeRecord, ParsedRecord, Record};
 use super::wire::{Composer, ParseError};
 use crate::rdata::rfc1035::Cname;
+use crate::rdata::AllRecordData;
 use core::marker::PhantomData;
 use core::{fmt, mem};
 use octseq::{Octets, OctetsFrom, Parser};
@@ -440,6 +441,98 @@ impl<Octs: Octets + ?Sized> Message<Octs> {
         }
     }
 
+    /// Returns whether this is the answer to some other message with the
+    /// question names matching exactly.
+    ///
+    /// This is the same as [`is_answer`][Self::is_answer] except that the
+    /// names of the questions are compared octet by octet instead of
+    /// ignoring ASCII case. This allows checking that an answer preserved
+    /// the case of a query which had its question names randomized via
+    /// [`Name::randomize_case`] to guard against spoofed answers.
+    ///
+    /// [`Name::randomize_case`]: super::name::Name::randomize_case
+    pub fn is_answer_exact_case<Other: Octets + ?Sized>(
+        &self,
+        query: &Message<Other>,
+    ) -> bool {
+        if !self.is_answer(query) {
+            return false;
+        }
+        self.question()
+            .zip(query.question())
+            .all(|item| match item {
+                (Ok(left), Ok(right)) => {
+                    left.qname().composed_cmp(right.qname()).is_eq()
+                }
+                _ => false,
+            })
+    }
+
+    /// Returns whether two messages have the same content.
+    ///
+    /// Because of name compression, two messages with identical content can
+    /// have very different wire formats. This method therefore compares
+    /// the content of the messages rather than their octets. It ignores the
+    /// message ID and the order of records within an RRset, i.e., a
+    /// sequence of records with the same owner, class, and type. The RRsets
+    /// themselves have to appear in the same order and in the same
+    /// sections. Domain names are compared ignoring ASCII case.
+    ///
+    /// The TTLs of records are compared, too. Use
+    /// [`eq_semantic_ignore_ttl`][Self::eq_semantic_ignore_ttl] if they
+    /// should be ignored.
+    ///
+    /// If either message fails to parse, the messages are considered
+    /// different.
+    pub fn eq_semantic<Other: Octets + ?Sized>(
+        &self,
+        other: &Message<Other>,
+    ) -> bool {
+        self.eq_semantic_impl(other, true).unwrap_or(false)
+    }
+
+    /// Returns whether two messages have the same content ignoring TTLs.
+    ///
+    /// This is the same as [`eq_semantic`][Self::eq_semantic] except that
+    /// the TTLs of records are not compared. This is useful when comparing
+    /// answers that have spent different amounts of time in a cache. The
+    /// TTL field of an OPT record, which contains the extended response
+    /// code and flags, is still compared.
+    pub fn eq_semantic_ignore_ttl<Other: Octets + ?Sized>(
+        &self,
+        other: &Message<Other>,
+    ) -> bool {
+        self.eq_semantic_impl(other, false).unwrap_or(false)
+    }
+
+    /// Compares the content of two messages.
+    fn eq_semantic_impl<Other: Octets + ?Sized>(
+        &self,
+        other: &Message<Other>,
+        ttl: bool,
+    ) -> Result<bool, ParseError> {
+        let mut header = self.header();
+        header.set_id(0);
+        let mut other_header = other.header();
+        other_header.set_id(0);
+        if header != other_header
+            || self.header_counts() != other.header_counts()
+            || self.question() != other.question()
+        {
+            return Ok(false);
+        }
+        let mut section = Some(self.answer()?);
+        let mut other_section = Some(other.answer()?);
+        while let (Some(left), Some(right)) = (section, other_section) {
+            if !rrsets_eq(left, right, ttl)? {
+                return Ok(false);
+            }
+            section = left.next_section()?;
+            other_section = right.next_section()?;
+        }
+        Ok(true)
+    }
+
     /// Returns whether the message has a question that is either AXFR or
     /// IXFR.
     pub fn is_xfr(&self) -> bool {
@@ -661,6 +754,53 @@ impl<Octs: Octets + ?Sized> Message<Octs> {
             .map(|opt| opt.rcode(self.header()))
             .unwrap_or_else(|| self.header().rcode().into())
     }
+
+    /// Sets the extended rcode of the message.
+    ///
+    /// The lower four bits of the extended rcode are kept in the message
+    /// header while the upper eight bits are kept in the OPT record. The
+    /// method updates both. If the message doesn’t have an OPT record,
+    /// only rcodes that fit into the header can be set.
+    ///
+    /// Returns an error if the rcode needs an OPT record and there isn’t
+    /// one or if the additional section cannot be parsed. In this case,
+    /// the message is left unchanged.
+    pub fn set_opt_rcode(
+        &mut self,
+        rcode: OptRcode,
+    ) -> Result<(), SetRcodeError>
+    where
+        Octs: AsMut<[u8]>,
+    {
+        match self.opt_ext_rcode_pos()? {
+            Some(pos) => self.as_slice_mut()[pos] = rcode.ext(),
+            None if rcode.is_ext() => return Err(SetRcodeError::MissingOpt),
+            None => {}
+        }
+        self.header_mut().set_rcode(rcode.rcode());
+        Ok(())
+    }
+
+    /// Returns the position of the extended rcode octet of the OPT record.
+    ///
+    /// Returns `Ok(None)` if the message doesn’t have an OPT record.
+    pub(super) fn opt_ext_rcode_pos(
+        &self,
+    ) -> Result<Option<usize>, ParseError> {
+        let mut section = self.additional()?;
+        while let Some(record) = section.next() {
+            let record = record?;
+            if record.rtype() == Rtype::OPT {
+                // The extended rcode is the first octet of the TTL field
+                // which is followed by the two octets of the data length
+                // and the data itself.
+                return Ok(Some(
+                    section.pos() - usize::from(record.rdlen()) - 6,
+                ));
+            }
+        }
+        Ok(None)
+    }
 }
 
 /// # Printing
@@ -747,6 +887,266 @@ impl<Octs: AsRef<[u8]> + ?Sized> fmt::Debug for Message<Octs> {
     }
 }
 
+//------------ IndexedMessage ------------------------------------------------
+
+/// A message with an index of its sections and records.
+///
+/// Because records are of varying length, accessing a section of a
+/// [`Message`] requires walking over all the sections before it. If a
+/// message is accessed repeatedly, this work is done over and over again.
+///
+/// This type wraps a message and records the position of every record in
+/// an index the first time it is needed. After that, the sections of the
+/// message can be accessed directly via [`answer`][Self::answer],
+/// [`authority`][Self::authority], and [`additional`][Self::additional]
+/// and individual records can be picked via [`record`][Self::record].
+///
+/// Building the index checks that all records of the message can be
+/// parsed. If that fails, all methods accessing the record sections return
+/// the error encountered.
+///
+/// Everything else remains available via the `Deref` implementation for
+/// [`Message`].
+///
+/// ```
+/// use domain::base::{IndexedMessage, Message, MessageBuilder, Name, Rtype};
+/// use domain::rdata::A;
+///
+/// let mut msg = MessageBuilder::new_vec().question();
+/// msg.push((Name::vec_from_str("example.com").unwrap(), Rtype::A))
+///     .unwrap();
+/// let mut msg = msg.answer();
+/// msg.push(
+///     (Name::vec_from_str("example.com").unwrap(), 3600,
+///      A::from_octets(192, 0, 2, 1))
+/// ).unwrap();
+/// let msg = IndexedMessage::new(msg.into_message());
+///
+/// let mut answer = msg.answer().unwrap().limit_to::<A>();
+/// assert_eq!(
+///     answer.next().unwrap().unwrap().data(),
+///     &A::from_octets(192, 0, 2, 1)
+/// );
+/// assert!(msg.additional().unwrap().next().is_none());
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub struct IndexedMessage<Octs> {
+    /// The message.
+    message: Message<Octs>,
+
+    /// The index, built on first use.
+    index: std::sync::OnceLock<Result<MessageIndex, ParseError>>,
+}
+
+#[cfg(feature = "std")]
+impl<Octs> IndexedMessage<Octs> {
+    /// Creates a new indexed message from a message.
+    ///
+    /// The index is not built right away but only when it is first needed.
+    pub fn new(message: Message<Octs>) -> Self {
+        IndexedMessage {
+            message,
+            index: Default::default(),
+        }
+    }
+
+    /// Returns a reference to the underlying message.
+    pub fn as_message(&self) -> &Message<Octs> {
+        &self.message
+    }
+
+    /// Converts the indexed message into the underlying message.
+    pub fn into_message(self) -> Message<Octs> {
+        self.message
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Octs: Octets> IndexedMessage<Octs> {
+    /// Returns the answer section.
+    pub fn answer(&self) -> Result<RecordSection<'_, Octs>, ParseError> {
+        self.section(Section::Answer)
+    }
+
+    /// Returns the authority section.
+    pub fn authority(&self) -> Result<RecordSection<'_, Octs>, ParseError> {
+        self.section(Section::Authority)
+    }
+
+    /// Returns the additional section.
+    pub fn additional(&self) -> Result<RecordSection<'_, Octs>, ParseError> {
+        self.section(Section::Additional)
+    }
+
+    /// Returns the given record section.
+    pub fn section(
+        &self,
+        section: Section,
+    ) -> Result<RecordSection<'_, Octs>, ParseError> {
+        let pos = self.index()?.section_start(section);
+        Ok(RecordSection::new(self.parser_at(pos), section))
+    }
+
+    /// Returns the record with the given index in the given section.
+    ///
+    /// Returns `Ok(None)` if the section has fewer records.
+    pub fn record(
+        &self,
+        section: Section,
+        idx: usize,
+    ) -> Result<Option<ParsedRecord<'_, Octs>>, ParseError> {
+        let pos = match self.index()?.record_start(section, idx) {
+            Some(pos) => pos,
+            None => return Ok(None),
+        };
+        ParsedRecord::parse(&mut self.parser_at(pos)).map(Some)
+    }
+
+    /// Returns the index, building it if necessary.
+    fn index(&self) -> Result<&MessageIndex, ParseError> {
+        self.index
+            .get_or_init(|| MessageIndex::build(&self.message))
+            .as_ref()
+            .map_err(|err| *err)
+    }
+
+    /// Returns a parser for the message positioned at `pos`.
+    fn parser_at(&self, pos: usize) -> Parser<'_, Octs> {
+        let mut parser = Parser::from_ref(self.message.as_octets());
+        parser.seek(pos).expect("index points outside of message");
+        parser
+    }
+}
+
+//--- From
+
+#[cfg(feature = "std")]
+impl<Octs> From<Message<Octs>> for IndexedMessage<Octs> {
+    fn from(message: Message<Octs>) -> Self {
+        Self::new(message)
+    }
+}
+
+//--- Deref and AsRef
+
+#[cfg(feature = "std")]
+impl<Octs> core::ops::Deref for IndexedMessage<Octs> {
+    type Target = Message<Octs>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.message
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Octs> AsRef<Message<Octs>> for IndexedMessage<Octs> {
+    fn as_ref(&self) -> &Message<Octs> {
+        &self.message
+    }
+}
+
+//--- Debug
+
+#[cfg(feature = "std")]
+impl<Octs: AsRef<[u8]>> fmt::Debug for IndexedMessage<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("IndexedMessage")
+            .field(&self.message)
+            .finish()
+    }
+}
+
+//------------ MessageIndex --------------------------------------------------
+
+/// The index of an [`IndexedMessage`].
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+struct MessageIndex {
+    /// The start of each record of the three record sections.
+    ///
+    /// The last element is the end of the last record, so there always is
+    /// at least one element.
+    records: std::vec::Vec<usize>,
+
+    /// The index into `records` of the first authority record.
+    authority: usize,
+
+    /// The index into `records` of the first additional record.
+    additional: usize,
+}
+
+#[cfg(feature = "std")]
+impl MessageIndex {
+    /// Builds the index by walking over the message.
+    fn build<Octs: Octets + ?Sized>(
+        message: &Message<Octs>,
+    ) -> Result<Self, ParseError> {
+        let counts = message.header_counts();
+        let mut records = std::vec::Vec::with_capacity(
+            usize::from(counts.ancount())
+                + usize::from(counts.nscount())
+                + usize::from(counts.arcount())
+                + 1,
+        );
+        let mut starts = [0; 2];
+        let mut section = Some(message.answer()?);
+        while let Some(mut current) = section {
+            if let Some(idx) = match current.section {
+                Section::Answer => None,
+                Section::Authority => Some(0),
+                Section::Additional => Some(1),
+            } {
+                starts[idx] = records.len();
+            }
+            loop {
+                let pos = current.pos();
+                match current.skip_next() {
+                    Some(res) => {
+                        res?;
+                        records.push(pos);
+                    }
+                    None => break,
+                }
+            }
+            if current.section == Section::Additional {
+                records.push(current.pos());
+            }
+            section = current.next_section()?;
+        }
+        Ok(MessageIndex {
+            records,
+            authority: starts[0],
+            additional: starts[1],
+        })
+    }
+
+    /// Returns the range of `records` for a section.
+    fn section_range(&self, section: Section) -> core::ops::Range<usize> {
+        match section {
+            Section::Answer => 0..self.authority,
+            Section::Authority => self.authority..self.additional,
+            Section::Additional => self.additional..self.records.len() - 1,
+        }
+    }
+
+    /// Returns the start of a section.
+    fn section_start(&self, section: Section) -> usize {
+        self.records[self.section_range(section).start]
+    }
+
+    /// Returns the start of a record in a section if it exists.
+    fn record_start(&self, section: Section, idx: usize) -> Option<usize> {
+        let range = self.section_range(section);
+        let idx = range.start.checked_add(idx)?;
+        if idx < range.end {
+            Some(self.records[idx])
+        } else {
+            None
+        }
+    }
+}
+
 //------------ QuestionSection ----------------------------------------------
 
 /// An iterator over the question section of a DNS message.
@@ -1297,6 +1697,130 @@ where
         Some(record.into_any_record())
     }
 }
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Compares the records of two sections RRset by RRset.
+///
+/// The order of the RRsets has to be the same while the order of records
+/// within each RRset is ignored.
+fn rrsets_eq<Octs, Other>(
+    mut left: RecordSection<'_, Octs>,
+    mut right: RecordSection<'_, Other>,
+    ttl: bool,
+) -> Result<bool, ParseError>
+where
+    Octs: Octets + ?Sized,
+    Other: Octets + ?Sized,
+{
+    loop {
+        let (left_start, right_start) = (left, right);
+        let first = match (left.next(), right.next()) {
+            (None, None) => return Ok(true),
+            (Some(first), Some(other_first)) => {
+                let first = first?;
+                if !same_rrset(&first, &other_first?) {
+                    return Ok(false);
+                }
+                first
+            }
+            _ => return Ok(false),
+        };
+        let len = rrset_len(&mut left, &first)?;
+        if len != rrset_len(&mut right, &first)? {
+            return Ok(false);
+        }
+        for record in left_start.take(len) {
+            let record = record?;
+            if count_equal(left_start, len, &record, ttl)?
+                != count_equal(right_start, len, &record, ttl)?
+            {
+                return Ok(false);
+            }
+        }
+    }
+}
+
+/// Returns whether two records belong to the same RRset.
+fn same_rrset<Octs, Other>(
+    left: &ParsedRecord<'_, Octs>,
+    right: &ParsedRecord<'_, Other>,
+) -> bool
+where
+    Octs: Octets + ?Sized,
+    Other: Octets + ?Sized,
+{
+    left.rtype() == right.rtype()
+        && left.class() == right.class()
+        && left.owner() == right.owner()
+}
+
+/// Returns the length of the RRset started by `first`.
+///
+/// The section must be positioned right after the first record. It is
+/// advanced to the first record of the next RRset.
+fn rrset_len<Octs, Other>(
+    section: &mut RecordSection<'_, Octs>,
+    first: &ParsedRecord<'_, Other>,
+) -> Result<usize, ParseError>
+where
+    Octs: Octets + ?Sized,
+    Other: Octets + ?Sized,
+{
+    let mut len = 1;
+    loop {
+        let mut next = *section;
+        match next.next() {
+            Some(record) => {
+                if !same_rrset(first, &record?) {
+                    return Ok(len);
+                }
+                *section = next;
+                len += 1;
+            }
+            None => return Ok(len),
+        }
+    }
+}
+
+/// Returns how many of the first `len` records are equal to `record`.
+fn count_equal<Octs, Other>(
+    section: RecordSection<'_, Octs>,
+    len: usize,
+    record: &ParsedRecord<'_, Other>,
+    ttl: bool,
+) -> Result<usize, ParseError>
+where
+    Octs: Octets + ?Sized,
+    Other: Octets + ?Sized,
+{
+    let mut res = 0;
+    for item in section.take(len) {
+        if records_eq(record, &item?, ttl)? {
+            res += 1;
+        }
+    }
+    Ok(res)
+}
+
+/// Returns whether the TTL and data of two records are equal.
+fn records_eq<Octs, Other>(
+    left: &ParsedRecord<'_, Octs>,
+    right: &ParsedRecord<'_, Other>,
+    ttl: bool,
+) -> Result<bool, ParseError>
+where
+    Octs: Octets + ?Sized,
+    Other: Octets + ?Sized,
+{
+    if (ttl || left.rtype() == Rtype::OPT) && left.ttl() != right.ttl() {
+        return Ok(false);
+    }
+    let left = left.to_any_record::<AllRecordData<_, ParsedName<_>>>()?;
+    let right = right.to_any_record::<AllRecordData<_, ParsedName<_>>>()?;
+    Ok(left.data() == right.data())
+}
+
 //============ Error Types ===================================================
 
 //------------ ShortMessage --------------------------------------------------
@@ -1314,6 +1838,42 @@ impl fmt::Display for ShortMessage {
 #[cfg(feature = "std")]
 impl std::error::Error for ShortMessage {}
 
+//------------ SetRcodeError -------------------------------------------------
+
+/// An error occurred while setting the extended rcode of a message.
+#[derive(Clone, Copy, Debug)]
+pub enum SetRcodeError {
+    /// Parsing the message failed.
+    Parse(ParseError),
+
+    /// The rcode needs an OPT record but the message doesn’t have one.
+    MissingOpt,
+}
+
+//--- From
+
+impl From<ParseError> for SetRcodeError {
+    fn from(err: ParseError) -> Self {
+        SetRcodeError::Parse(err)
+    }
+}
+
+//--- Display and Error
+
+impl fmt::Display for SetRcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SetRcodeError::Parse(ref err) => err.fmt(f),
+            SetRcodeError::MissingOpt => {
+                f.write_str("extended rcode requires OPT record")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SetRcodeError {}
+
 //------------ CopyRecordsError ----------------------------------------------
 
 /// An error occurrd while copying records.
@@ -1364,7 +1924,7 @@ mod test {
     #[cfg(feature = "std")]
     use crate::base::name::Name;
     #[cfg(feature = "std")]
-    use crate::rdata::{AllRecordData, Ns};
+    use crate::rdata::Ns;
     #[cfg(feature = "std")]
     use std::vec::Vec;
 
@@ -1453,6 +2013,157 @@ mod test {
         assert!(msg.as_message().canonical_name().is_none());
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn is_answer_exact_case() {
+        let mut query = MessageBuilder::new_vec().question();
+        query.header_mut().set_id(12);
+        query
+            .push((Name::vec_from_str("wWw.ExaMPle.com").unwrap(), Rtype::A))
+            .unwrap();
+        let query = query.into_message();
+
+        let answer = MessageBuilder::new_vec()
+            .start_answer(&query, Rcode::NOERROR)
+            .unwrap()
+            .into_message();
+        assert!(answer.is_answer(&query));
+        assert!(answer.is_answer_exact_case(&query));
+
+        let mut answer = MessageBuilder::new_vec().question();
+        answer.header_mut().set_id(12);
+        answer.header_mut().set_qr(true);
+        answer
+            .push((Name::vec_from_str("www.example.com").unwrap(), Rtype::A))
+            .unwrap();
+        let answer = answer.into_message();
+        assert!(answer.is_answer(&query));
+        assert!(!answer.is_answer_exact_case(&query));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn eq_semantic() {
+        use crate::base::message_builder::StaticCompressor;
+        use crate::rdata::A;
+
+        fn build<Target: Composer>(
+            builder: MessageBuilder<Target>,
+            id: u16,
+            records: &[(&str, u32, [u8; 4])],
+        ) -> Target {
+            let mut msg = builder.question();
+            msg.header_mut().set_id(id);
+            msg.push((Name::vec_from_str("example.com").unwrap(), Rtype::A))
+                .unwrap();
+            let mut msg = msg.answer();
+            for (owner, ttl, addr) in records {
+                msg.push((
+                    Name::vec_from_str(owner).unwrap(),
+                    *ttl,
+                    A::new((*addr).into()),
+                ))
+                .unwrap();
+            }
+            msg.finish()
+        }
+
+        let compressed = build(
+            MessageBuilder::from_target(StaticCompressor::new(Vec::new()))
+                .unwrap(),
+            1,
+            &[
+                ("example.com", 3600, [192, 0, 2, 1]),
+                ("example.com", 3600, [192, 0, 2, 2]),
+                ("www.example.com", 60, [192, 0, 2, 3]),
+            ],
+        );
+        let compressed =
+            Message::from_octets(compressed.into_target()).unwrap();
+        let reordered = Message::from_octets(build(
+            MessageBuilder::new_vec(),
+            2,
+            &[
+                ("EXAMPLE.com", 3600, [192, 0, 2, 2]),
+                ("example.com", 3600, [192, 0, 2, 1]),
+                ("www.example.com", 60, [192, 0, 2, 3]),
+            ],
+        ))
+        .unwrap();
+        assert_ne!(compressed.as_slice().len(), reordered.as_slice().len());
+        assert!(compressed.eq_semantic(&reordered));
+        assert!(reordered.eq_semantic_ignore_ttl(&compressed));
+
+        let ttl = Message::from_octets(build(
+            MessageBuilder::new_vec(),
+            1,
+            &[
+                ("example.com", 3599, [192, 0, 2, 1]),
+                ("example.com", 3599, [192, 0, 2, 2]),
+                ("www.example.com", 59, [192, 0, 2, 3]),
+            ],
+        ))
+        .unwrap();
+        assert!(!compressed.eq_semantic(&ttl));
+        assert!(compressed.eq_semantic_ignore_ttl(&ttl));
+
+        let rrsets_reordered = Message::from_octets(build(
+            MessageBuilder::new_vec(),
+            1,
+            &[
+                ("www.example.com", 60, [192, 0, 2, 3]),
+                ("example.com", 3600, [192, 0, 2, 1]),
+                ("example.com", 3600, [192, 0, 2, 2]),
+            ],
+        ))
+        .unwrap();
+        assert!(!compressed.eq_semantic(&rrsets_reordered));
+
+        let duplicate = Message::from_octets(build(
+            MessageBuilder::new_vec(),
+            1,
+            &[
+                ("example.com", 3600, [192, 0, 2, 1]),
+                ("example.com", 3600, [192, 0, 2, 1]),
+                ("www.example.com", 60, [192, 0, 2, 3]),
+            ],
+        ))
+        .unwrap();
+        assert!(!compressed.eq_semantic(&duplicate));
+        assert!(!duplicate.eq_semantic(&compressed));
+
+        let mut flags = reordered.clone();
+        flags.header_mut().set_rd(true);
+        assert!(!compressed.eq_semantic(&flags));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn set_opt_rcode() {
+        let mut msg = MessageBuilder::new_vec().additional();
+        msg.opt(|opt| {
+            opt.set_rcode(OptRcode::BADVERS);
+            Ok(())
+        })
+        .unwrap();
+        let mut msg = msg.into_message();
+        assert_eq!(msg.opt_rcode(), OptRcode::BADVERS);
+        msg.set_opt_rcode(OptRcode::BADCOOKIE).unwrap();
+        assert_eq!(msg.opt_rcode(), OptRcode::BADCOOKIE);
+        msg.set_opt_rcode(OptRcode::REFUSED).unwrap();
+        assert_eq!(msg.opt_rcode(), OptRcode::REFUSED);
+        assert_eq!(msg.opt().unwrap().rcode(msg.header()), OptRcode::REFUSED);
+
+        let mut msg = get_test_message();
+        assert!(matches!(
+            msg.set_opt_rcode(OptRcode::BADVERS),
+            Err(SetRcodeError::MissingOpt)
+        ));
+        assert_eq!(msg.opt_rcode(), OptRcode::NOERROR);
+        msg.set_opt_rcode(OptRcode::SERVFAIL).unwrap();
+        assert_eq!(msg.header().rcode(), Rcode::SERVFAIL);
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn message_iterator() {
@@ -1491,4 +2202,46 @@ mod test {
             assert_eq!(0, msg.header_counts().arcount());
         }
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn indexed_message() {
+        let msg = IndexedMessage::new(get_test_message());
+        let plain = msg.as_message();
+        for section in
+            [Section::Answer, Section::Authority, Section::Additional]
+        {
+            let linear = plain
+                .iter()
+                .map(Result::unwrap)
+                .filter(|(_, s)| *s == section)
+                .map(|(record, _)| record)
+                .collect::<Vec<_>>();
+            let indexed = msg
+                .section(section)
+                .unwrap()
+                .map(Result::unwrap)
+                .collect::<Vec<_>>();
+            assert!(linear == indexed);
+            for (idx, record) in linear.iter().enumerate() {
+                let indexed = msg.record(section, idx).unwrap();
+                assert!(indexed.as_ref() == Some(record));
+            }
+            assert!(msg.record(section, linear.len()).unwrap().is_none());
+        }
+        assert_eq!(
+            msg.authority().unwrap().pos(),
+            plain.authority().unwrap().pos()
+        );
+        assert_eq!(msg.additional().unwrap().pos(), plain.as_slice().len());
+
+        // A broken record fails all section access.
+        let mut octets = get_test_message().into_octets();
+        let len = octets.len();
+        octets.truncate(len - 1);
+        let msg = IndexedMessage::new(Message::from_octets(octets).unwrap());
+        assert!(msg.answer().is_err());
+        assert!(msg.record(Section::Answer, 0).is_err());
+        assert!(msg.question().next().is_none());
+    }
 }
@@ -131,7 +131,7 @@ use super::header::{CountOverflow, Header, HeaderCounts, HeaderSection};
 #[cfg(feature = "rand")]
 use super::iana::Rtype;
 use super::iana::{OptRcode, OptionCode, Rcode};
-use super::message::Message;
+use super::message::{Message, SetRcodeError};
 use super::name::{Label, ToName};
 use super::opt::{ComposeOptData, OptHeader, OptRecord};
 use super::question::ComposeQuestion;
@@ -176,6 +176,11 @@ pub struct MessageBuilder<Target> {
     ///
     /// Defaults to usize::MAX.
     limit: usize,
+
+    /// The amount of space before the limit reserved for the OPT record.
+    ///
+    /// Defaults to 0.
+    reserved: usize,
 }
 
 /// # Creating Message Builders
@@ -197,6 +202,7 @@ impl<Target: OctetsBuilder + Truncate> MessageBuilder<Target> {
         Ok(MessageBuilder {
             target,
             limit: usize::MAX,
+            reserved: 0,
         })
     }
 }
@@ -238,12 +244,40 @@ impl MessageBuilder<StreamTarget<BytesMut>> {
 impl<Target: Composer> MessageBuilder<Target> {
     /// Starts creating an answer for the given message.
     ///
-    /// Specifically, this sets the ID, QR, OPCODE, RD, and RCODE fields
+    /// Specifically, this sets the ID, QR, OPCODE, RD, CD, and RCODE fields
     /// in the header and attempts to push the message’s questions to the
     /// builder.
     ///
     /// The method converts the message builder into an answer builder ready
     /// to receive the answer for the question.
+    ///
+    /// A push limit and a reservation for the OPT record set on the message
+    /// builder are kept. This allows building a response that is truncated
+    /// at RRset granularity yet still contains the OPT record:
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use domain::base::{Message, MessageBuilder, Name, Rtype};
+    /// use domain::base::iana::Rcode;
+    /// use domain::rdata::A;
+    ///
+    /// # let mut query = MessageBuilder::new_vec().question();
+    /// # query.push((Name::vec_from_str("example.com").unwrap(), Rtype::A))
+    /// #     .unwrap();
+    /// # let query = query.into_message();
+    /// let mut builder = MessageBuilder::new_vec();
+    /// builder.set_push_limit(512);
+    /// builder.reserve_opt(11);
+    /// let mut answer = builder.start_answer(&query, Rcode::NOERROR).unwrap();
+    /// let qname = query.sole_question().unwrap().into_qname();
+    /// let rrset = (1..=10).map(|i| (&qname, 3600, A::from_octets(192, 0, 2, i)));
+    /// let _ = answer.push_rrset(rrset);
+    /// let mut additional = answer.additional();
+    /// additional.opt(|opt| {
+    ///     opt.set_udp_payload_size(1232);
+    ///     Ok(())
+    /// }).unwrap();
+    /// ```
     pub fn start_answer<Octs: Octets + ?Sized>(
         mut self,
         msg: &Message<Octs>,
@@ -255,6 +289,7 @@ impl<Target: Composer> MessageBuilder<Target> {
             header.set_qr(true);
             header.set_opcode(msg.header().opcode());
             header.set_rd(msg.header().rd());
+            header.set_cd(msg.header().cd());
             header.set_rcode(rcode);
         }
         let mut builder = self.question();
@@ -279,6 +314,7 @@ impl<Target: Composer> MessageBuilder<Target> {
             header.set_qr(true);
             header.set_opcode(msg.header().opcode());
             header.set_rd(msg.header().rd());
+            header.set_cd(msg.header().cd());
             header.set_rcode(rcode);
         }
 
@@ -342,6 +378,25 @@ impl<Target: Composer> MessageBuilder<Target> {
             Some(self.limit)
         }
     }
+
+    /// Reserves space before the push limit for the OPT record.
+    ///
+    /// While a reservation is in place, pushing records will fail if they
+    /// would end within `len` octets of the push limit. Only
+    /// [`AdditionalBuilder::opt()`] may use the reserved space. Pushing the
+    /// OPT record releases the reservation.
+    ///
+    /// This allows a responder to always include the OPT record even if
+    /// it has to truncate the message. Since the reservation is relative to
+    /// the push limit, it has no effect if no limit is set.
+    pub fn reserve_opt(&mut self, len: usize) {
+        self.reserved = len;
+    }
+
+    /// Returns the amount of space currently reserved for the OPT record.
+    pub fn opt_reserve(&self) -> usize {
+        self.reserved
+    }
 }
 
 /// # Access to the Message Header
@@ -370,6 +425,42 @@ impl<Target: OctetsBuilder + AsMut<[u8]>> MessageBuilder<Target> {
     }
 }
 
+/// # Access to the Extended Rcode
+///
+impl<Target> MessageBuilder<Target>
+where
+    Target: OctetsBuilder + AsRef<[u8]> + AsMut<[u8]>,
+{
+    /// Returns the extended rcode of the message.
+    ///
+    /// The rcode is assembled from the message header and, if present, the
+    /// OPT record.
+    pub fn opt_rcode(&self) -> OptRcode {
+        self.as_message().opt_rcode()
+    }
+
+    /// Sets the extended rcode of the message.
+    ///
+    /// The method updates both the message header and, if present, the
+    /// OPT record. If the message doesn’t have an OPT record yet, only
+    /// rcodes that fit into the header can be set. Otherwise an error is
+    /// returned and the message is left unchanged. Use
+    /// [`OptBuilder::set_rcode`] to set the rcode when adding the OPT
+    /// record.
+    pub fn set_opt_rcode(
+        &mut self,
+        rcode: OptRcode,
+    ) -> Result<(), SetRcodeError> {
+        match self.as_message().opt_ext_rcode_pos()? {
+            Some(pos) => self.target.as_mut()[pos] = rcode.ext(),
+            None if rcode.is_ext() => return Err(SetRcodeError::MissingOpt),
+            None => {}
+        }
+        self.header_mut().set_rcode(rcode.rcode());
+        Ok(())
+    }
+}
+
 /// # Conversions
 ///
 impl<Target> MessageBuilder<Target> {
@@ -477,7 +568,7 @@ impl<Target: Composer> MessageBuilder<Target> {
         }
 
         let new_pos = self.target.as_ref().len();
-        if new_pos >= self.limit {
+        if new_pos >= self.limit.saturating_sub(self.reserved) {
             self.target.truncate(pos);
             return Err(PushError::ShortBuf);
         }
@@ -488,6 +579,56 @@ impl<Target: Composer> MessageBuilder<Target> {
         }
         Ok(())
     }
+
+    /// Returns a checkpoint for the current state of the message.
+    fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            pos: self.target.as_ref().len(),
+            counts: self.counts(),
+        }
+    }
+
+    /// Rolls the message back to the given checkpoint.
+    ///
+    /// The checkpoint must not be before `start`, the start of the current
+    /// section.
+    fn rollback(&mut self, start: usize, checkpoint: Checkpoint) {
+        assert!(
+            checkpoint.pos >= start
+                && checkpoint.pos <= self.target.as_ref().len(),
+            "checkpoint outside of the current section"
+        );
+        self.target.truncate(checkpoint.pos);
+        *self.counts_mut() = checkpoint.counts;
+    }
+
+    /// Pushes all records of an RRset or none at all.
+    ///
+    /// If any of the records cannot be pushed, all records of the set are
+    /// removed again. If this happened because the message is full and
+    /// `truncate` is `true`, the TC bit is set.
+    fn push_rrset<R, Push>(
+        &mut self,
+        start: usize,
+        truncate: bool,
+        rrset: impl IntoIterator<Item = R>,
+        mut push: Push,
+    ) -> Result<(), PushError>
+    where
+        Push: FnMut(&mut Self, R) -> Result<(), PushError>,
+    {
+        let checkpoint = self.checkpoint();
+        for record in rrset {
+            if let Err(err) = push(self, record) {
+                self.rollback(start, checkpoint);
+                if truncate && matches!(err, PushError::ShortBuf) {
+                    self.header_mut().set_tc(true);
+                }
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
 }
 
 //--- From
@@ -893,6 +1034,58 @@ impl<Target: Composer> AnswerBuilder<Target> {
             |counts| counts.inc_ancount(),
         )
     }
+
+    /// Returns a checkpoint for the current state of the answer section.
+    ///
+    /// Passing the checkpoint to [`rollback`][Self::rollback] later removes
+    /// all records added since and restores the header counts.
+    pub fn checkpoint(&self) -> Checkpoint {
+        self.builder.checkpoint()
+    }
+
+    /// Rolls the answer section back to a checkpoint.
+    ///
+    /// All records added after `checkpoint` was created are removed and the
+    /// header counts are restored. This allows, for instance, to drop
+    /// a complete RRset that turned out not to fit into the message.
+    ///
+    /// Rolling back invalidates all checkpoints created after `checkpoint`.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if the checkpoint lies before the start of the
+    /// answer section or after the current end of the message.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        self.builder.rollback(self.start, checkpoint)
+    }
+
+    /// Appends all records of an RRset to the answer section.
+    ///
+    /// Either all the records are added or, if any of them fails, none of
+    /// them. If the records do not fit into the message, the TC bit is
+    /// set in the header, marking the message as truncated as described in
+    /// [RFC 2181, section 9].
+    ///
+    /// The method thus allows truncating a message at RRset granularity.
+    /// Combined with a push limit (see
+    /// [`set_push_limit`][MessageBuilder::set_push_limit]) and possibly a
+    /// reservation for the OPT record (see
+    /// [`reserve_opt`][MessageBuilder::reserve_opt]), this can be used to
+    /// build responses to be sent over UDP.
+    ///
+    /// [RFC 2181, section 9]: https://tools.ietf.org/html/rfc2181#section-9
+    pub fn push_rrset(
+        &mut self,
+        rrset: impl IntoIterator<Item = impl ComposeRecord>,
+    ) -> Result<(), PushError> {
+        self.builder
+            .push_rrset(self.start, true, rrset, |builder, rr| {
+                builder.push(
+                    |target| rr.compose_record(target).map_err(Into::into),
+                    |counts| counts.inc_ancount(),
+                )
+            })
+    }
 }
 
 /// # Conversions
@@ -1145,6 +1338,54 @@ impl<Target: Composer> AuthorityBuilder<Target> {
             |counts| counts.inc_nscount(),
         )
     }
+
+    /// Returns a checkpoint for the current state of the authority section.
+    ///
+    /// Passing the checkpoint to [`rollback`][Self::rollback] later removes
+    /// all records added since and restores the header counts.
+    pub fn checkpoint(&self) -> Checkpoint {
+        self.answer.builder.checkpoint()
+    }
+
+    /// Rolls the authority section back to a checkpoint.
+    ///
+    /// All records added after `checkpoint` was created are removed and the
+    /// header counts are restored. This allows, for instance, to drop
+    /// a complete RRset that turned out not to fit into the message.
+    ///
+    /// Rolling back invalidates all checkpoints created after `checkpoint`.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if the checkpoint lies before the start of the
+    /// authority section or after the current end of the message.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        self.answer.builder.rollback(self.start, checkpoint)
+    }
+
+    /// Appends all records of an RRset to the authority section.
+    ///
+    /// Either all the records are added or, if any of them fails, none of
+    /// them. If the records do not fit into the message, the TC bit is
+    /// set in the header.
+    ///
+    /// See [`AnswerBuilder::push_rrset`] for more information.
+    pub fn push_rrset(
+        &mut self,
+        rrset: impl IntoIterator<Item = impl ComposeRecord>,
+    ) -> Result<(), PushError> {
+        self.answer.builder.push_rrset(
+            self.start,
+            true,
+            rrset,
+            |builder, rr| {
+                builder.push(
+                    |target| rr.compose_record(target).map_err(Into::into),
+                    |counts| counts.inc_nscount(),
+                )
+            },
+        )
+    }
 }
 
 /// # Conversions
@@ -1402,6 +1643,58 @@ impl<Target: Composer> AdditionalBuilder<Target> {
             |counts| counts.inc_arcount(),
         )
     }
+
+    /// Returns a checkpoint for the current state of the additional section.
+    ///
+    /// Passing the checkpoint to [`rollback`][Self::rollback] later removes
+    /// all records added since and restores the header counts.
+    pub fn checkpoint(&self) -> Checkpoint {
+        self.authority.answer.builder.checkpoint()
+    }
+
+    /// Rolls the additional section back to a checkpoint.
+    ///
+    /// All records added after `checkpoint` was created are removed and the
+    /// header counts are restored. This allows, for instance, to drop
+    /// a complete RRset that turned out not to fit into the message.
+    ///
+    /// Rolling back invalidates all checkpoints created after `checkpoint`.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if the checkpoint lies before the start of the
+    /// additional section or after the current end of the message.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        self.authority
+            .answer
+            .builder
+            .rollback(self.start, checkpoint)
+    }
+
+    /// Appends all records of an RRset to the additional section.
+    ///
+    /// Either all the records are added or, if any of them fails, none of
+    /// them. Unlike with the other sections, the TC bit is not set if the
+    /// records do not fit, since records in the additional section are
+    /// not required for a complete answer.
+    ///
+    /// See [`AnswerBuilder::push_rrset`] for more information.
+    pub fn push_rrset(
+        &mut self,
+        rrset: impl IntoIterator<Item = impl ComposeRecord>,
+    ) -> Result<(), PushError> {
+        self.authority.answer.builder.push_rrset(
+            self.start,
+            false,
+            rrset,
+            |builder, rr| {
+                builder.push(
+                    |target| rr.compose_record(target).map_err(Into::into),
+                    |counts| counts.inc_arcount(),
+                )
+            },
+        )
+    }
 }
 
 impl<Target: Composer> AdditionalBuilder<Target> {
@@ -1414,15 +1707,27 @@ impl<Target: Composer> AdditionalBuilder<Target> {
     /// The method will return whatever the closure returns. In addition, it
     /// will return an error if it failed to add the header of the OPT record.
     ///
+    /// The record may use the space reserved via
+    /// [`reserve_opt`][MessageBuilder::reserve_opt]. The reservation is
+    /// released if the record was added successfully and kept otherwise.
+    ///
     /// [`OptBuilder`]: struct.OptBuilder.html
     pub fn opt<F>(&mut self, op: F) -> Result<(), PushError>
     where
         F: FnOnce(&mut OptBuilder<Target>) -> Result<(), Target::AppendError>,
     {
-        self.authority.answer.builder.push(
-            |target| OptBuilder::new(target)?.build(op),
+        let builder = &mut self.authority.answer.builder;
+        let reserved = mem::replace(&mut builder.reserved, 0);
+        // Pushing fails if the message reaches the limit.
+        let limit = builder.limit.saturating_sub(1);
+        let res = builder.push(
+            |target| OptBuilder::new(target, limit)?.build(op),
             |counts| counts.inc_arcount(),
-        )
+        );
+        if res.is_err() {
+            builder.reserved = reserved;
+        }
+        res
     }
 }
 
@@ -1640,6 +1945,23 @@ where
     }
 }
 
+//------------ Checkpoint ----------------------------------------------------
+
+/// A saved state of a message builder.
+///
+/// A checkpoint can be acquired via the `checkpoint` method of the record
+/// section builders [`AnswerBuilder`], [`AuthorityBuilder`], and
+/// [`AdditionalBuilder`]. It can later be given to the `rollback` method of
+/// the same builder to remove everything that was added in the meantime.
+#[derive(Clone, Copy, Debug)]
+pub struct Checkpoint {
+    /// The length of the message when the checkpoint was created.
+    pos: usize,
+
+    /// The header counts when the checkpoint was created.
+    counts: HeaderCounts,
+}
+
 //------------ OptBuilder ----------------------------------------------------
 
 /// Builds an OPT record.
@@ -1652,14 +1974,21 @@ where
 pub struct OptBuilder<'a, Target: ?Sized> {
     start: usize,
     target: &'a mut Target,
+
+    /// The maximum length of the message including the OPT record.
+    limit: usize,
 }
 
 impl<'a, Target: Composer + ?Sized> OptBuilder<'a, Target> {
     /// Creates a new opt builder atop an additional builder.
-    fn new(target: &'a mut Target) -> Result<Self, ShortBuf> {
+    fn new(target: &'a mut Target, limit: usize) -> Result<Self, ShortBuf> {
         let start = target.as_ref().len();
         OptHeader::default().compose(target).map_err(Into::into)?;
-        Ok(OptBuilder { start, target })
+        Ok(OptBuilder {
+            start,
+            target,
+            limit,
+        })
     }
 
     fn build<F>(&mut self, op: F) -> Result<(), ShortBuf>
@@ -1805,6 +2134,15 @@ impl<'a, Target: Composer + ?Sized> OptBuilder<'a, Target> {
     pub fn as_target(&self) -> &Target {
         self.target
     }
+
+    /// Returns the maximum length the message may grow to.
+    ///
+    /// This is derived from the push limit of the message builder. If no
+    /// limit has been set, the value is effectively unlimited.
+    #[must_use]
+    pub fn max_message_len(&self) -> usize {
+        self.limit
+    }
 }
 
 //------------ StreamTarget --------------------------------------------------
@@ -1967,6 +2305,14 @@ where
     }
 }
 
+//------------ POINTER_LIMIT -------------------------------------------------
+
+/// The end of the part of a message that compression pointers can refer to.
+///
+/// Compression pointers contain a 14 bit offset from the start of the
+/// message, so only names starting before this position can be referred to.
+const POINTER_LIMIT: usize = 0x4000;
+
 //------------ StaticCompressor ----------------------------------------------
 
 /// A domain name compressor that doesn’t require an allocator.
@@ -2054,7 +2400,7 @@ impl<Target> StaticCompressor<Target> {
 
     /// Inserts the position of a new domain name if possible.
     fn insert(&mut self, pos: usize) -> bool {
-        if pos < 0xc000 && self.len < self.entries.len() {
+        if pos < POINTER_LIMIT && self.len < self.entries.len() {
             self.entries[self.len] = pos as u16;
             self.len += 1;
             true
@@ -2137,7 +2483,7 @@ impl<Target: Composer> Composer for StaticCompressor<Target> {
 impl<Target: Truncate> Truncate for StaticCompressor<Target> {
     fn truncate(&mut self, len: usize) {
         self.target.truncate(len);
-        if len < 0xC000 {
+        if len < POINTER_LIMIT {
             let len = len as u16;
             for i in 0..self.len {
                 if self.entries[i] >= len {
@@ -2190,6 +2536,9 @@ pub struct TreeCompressor<Target> {
 /// by adding this label to the name constructed so far.
 ///
 /// Each node also contains the position of that name in the message.
+///
+/// Labels are kept in lowercase, so names are compared ignoring ASCII case
+/// just like everywhere else.
 #[cfg(feature = "std")]
 #[derive(Clone, Debug, Default)]
 struct Node {
@@ -2202,6 +2551,13 @@ struct Node {
 
 #[cfg(feature = "std")]
 impl Node {
+    /// Returns the key for a label in the `parents` map.
+    fn key(label: &Label) -> Array<64> {
+        let mut key: Array<64> = label.as_ref().try_into().unwrap();
+        key.as_mut().make_ascii_lowercase();
+        key
+    }
+
     fn drop_above(&mut self, len: u16) {
         self.value = match self.value {
             Some(value) if value < len => Some(value),
@@ -2258,7 +2614,7 @@ impl<Target> TreeCompressor<Target> {
             if label.is_root() {
                 return node.value;
             }
-            node = node.parents.get(label.as_ref())?;
+            node = node.parents.get(&Node::key(label))?;
         }
         None
     }
@@ -2268,7 +2624,7 @@ impl<Target> TreeCompressor<Target> {
         name: N,
         pos: usize,
     ) -> bool {
-        if pos >= 0xC000 {
+        if pos >= POINTER_LIMIT {
             return false;
         }
         let pos = pos as u16;
@@ -2278,10 +2634,7 @@ impl<Target> TreeCompressor<Target> {
                 node.value = Some(pos);
                 break;
             }
-            node = node
-                .parents
-                .entry(label.as_ref().try_into().unwrap())
-                .or_default();
+            node = node.parents.entry(Node::key(label)).or_default();
         }
         true
     }
@@ -2365,7 +2718,7 @@ impl<Target: Composer> Composer for TreeCompressor<Target> {
 impl<Target: Composer> Truncate for TreeCompressor<Target> {
     fn truncate(&mut self, len: usize) {
         self.target.truncate(len);
-        if len < 0xC000 {
+        if len < POINTER_LIMIT {
             self.start.drop_above(len as u16)
         }
     }
@@ -2452,7 +2805,7 @@ struct HashEntry {
 impl HashEntry {
     /// Try constructing a [`HashEntry`].
     fn new(head: usize, tail: usize) -> Option<Self> {
-        if head < 0xC000 {
+        if head < POINTER_LIMIT {
             Some(Self {
                 head: head as u16,
                 tail: tail as u16,
@@ -2595,7 +2948,7 @@ impl<Target: Composer> Composer for HashCompressor<Target> {
 
             // Remember this label for future compression, if possible.
             //
-            // If some labels in this name pass the 0xC000 boundary point, then
+            // If some labels in this name pass the pointer limit, then
             // none of its remembered labels can be used (since they are looked
             // up from right to left, and the rightmost ones will fail first).
             // We could check more thoroughly for this, but it's not worth it.
@@ -2631,7 +2984,7 @@ impl<Target: Composer> Composer for HashCompressor<Target> {
 impl<Target: Composer> Truncate for HashCompressor<Target> {
     fn truncate(&mut self, len: usize) {
         self.target.truncate(len);
-        if len < 0xC000 {
+        if len < POINTER_LIMIT {
             self.names.retain(|name| name.head < len as u16);
         }
     }
@@ -2674,6 +3027,135 @@ mod test {
     use crate::rdata::{Ns, Soa, A};
     use core::str::FromStr;
 
+    #[test]
+    fn checkpoint_rollback() {
+        let name = Name::<Vec<u8>>::from_str("example.com").unwrap();
+        let other = Name::<Vec<u8>>::from_str("www.example.org").unwrap();
+        let mut msg =
+            MessageBuilder::from_target(StaticCompressor::new(Vec::new()))
+                .unwrap()
+                .answer();
+
+        msg.push((&name, 86400, A::from_octets(192, 0, 2, 1)))
+            .unwrap();
+        let len = msg.as_slice().len();
+        let checkpoint = msg.checkpoint();
+        msg.push((&other, 86400, A::from_octets(192, 0, 2, 2)))
+            .unwrap();
+        msg.push((&other, 86400, A::from_octets(192, 0, 2, 3)))
+            .unwrap();
+        assert_eq!(msg.counts().ancount(), 3);
+
+        msg.rollback(checkpoint);
+        assert_eq!(msg.counts().ancount(), 1);
+        assert_eq!(msg.as_slice().len(), len);
+
+        // The compressor must have forgotten about the dropped names.
+        msg.push((&name, 86400, A::from_octets(192, 0, 2, 4)))
+            .unwrap();
+        let mut msg = msg.authority();
+        let checkpoint = msg.checkpoint();
+        msg.push((&other, 86400, A::from_octets(192, 0, 2, 5)))
+            .unwrap();
+        msg.rollback(checkpoint);
+        let msg = msg.into_message();
+        assert_eq!(msg.header_counts().nscount(), 0);
+        let answer = msg.answer().unwrap().limit_to::<A>();
+        assert_eq!(
+            answer
+                .map(|record| record.unwrap().data().clone())
+                .collect::<Vec<_>>(),
+            [A::from_octets(192, 0, 2, 1), A::from_octets(192, 0, 2, 4)]
+        );
+    }
+
+    #[test]
+    fn start_answer() {
+        let mut query = MessageBuilder::new_vec();
+        query.header_mut().set_id(4711);
+        query.header_mut().set_rd(true);
+        query.header_mut().set_cd(true);
+        let mut query = query.question();
+        query
+            .push((
+                Name::<Vec<u8>>::from_str("example.com").unwrap(),
+                Rtype::A,
+            ))
+            .unwrap();
+        let query = query.into_message();
+
+        let mut builder = MessageBuilder::new_vec();
+        builder.reserve_opt(11);
+        let answer = builder.start_answer(&query, Rcode::NXDOMAIN).unwrap();
+        assert_eq!(answer.opt_reserve(), 11);
+        let answer = answer.into_message();
+        let header = answer.header();
+        assert_eq!(header.id(), 4711);
+        assert!(header.qr());
+        assert!(header.rd());
+        assert!(header.cd());
+        assert!(!header.ra());
+        assert_eq!(header.opcode(), query.header().opcode());
+        assert_eq!(header.rcode(), Rcode::NXDOMAIN);
+        assert_eq!(
+            answer.sole_question().unwrap(),
+            query.sole_question().unwrap()
+        );
+    }
+
+    #[test]
+    fn push_rrset() {
+        // Each of the records is 27 octets long.
+        let name = Name::<Vec<u8>>::from_str("example.com").unwrap();
+        let rrset =
+            |n| (1..=n).map(|i| (&name, 86400, A::from_octets(192, 0, 2, i)));
+
+        let mut msg = MessageBuilder::new_vec();
+        msg.set_push_limit(12 + 4 * 27 + 1);
+        msg.reserve_opt(11);
+        let mut msg = msg.answer();
+        msg.push_rrset(rrset(2)).unwrap();
+        assert!(!msg.header().tc());
+
+        // Doesn’t fit with the reservation for the OPT record.
+        assert!(msg.push_rrset(rrset(2)).is_err());
+        assert!(msg.header().tc());
+        assert_eq!(msg.counts().ancount(), 2);
+        assert_eq!(msg.as_slice().len(), 12 + 2 * 27);
+
+        // The additional section doesn’t touch the TC bit.
+        msg.header_mut().set_tc(false);
+        let mut msg = msg.additional();
+        msg.push_rrset(rrset(1)).unwrap();
+        assert!(msg.push_rrset(rrset(1)).is_err());
+        assert!(!msg.header().tc());
+        assert_eq!(msg.counts().arcount(), 1);
+
+        // A failed OPT record keeps the reservation.
+        assert!(msg.opt(|opt| opt.padding(100)).is_err());
+        assert_eq!(msg.opt_reserve(), 11);
+        assert_eq!(msg.counts().arcount(), 1);
+
+        // But the OPT record does fit.
+        msg.opt(|_| Ok(())).unwrap();
+        assert_eq!(msg.opt_reserve(), 0);
+        let msg = msg.into_message();
+        assert_eq!(msg.header_counts().arcount(), 2);
+        assert!(msg.opt().is_some());
+    }
+
+    #[test]
+    #[should_panic]
+    fn rollback_previous_section() {
+        let name = Name::<Vec<u8>>::from_str("example.com").unwrap();
+        let mut msg = MessageBuilder::new_vec().answer();
+        let checkpoint = msg.checkpoint();
+        msg.push((&name, 86400, A::from_octets(192, 0, 2, 1)))
+            .unwrap();
+        let mut msg = msg.authority();
+        msg.rollback(checkpoint);
+    }
+
     #[test]
     fn message_builder() {
         // Make a domain name we can use later on.
@@ -2802,6 +3284,31 @@ mod test {
         assert_eq!(opts.next(), Some(Ok(nsid)));
     }
 
+    #[test]
+    fn opt_rcode() {
+        let mut msg = MessageBuilder::new_vec().additional();
+        assert_eq!(msg.opt_rcode(), OptRcode::NOERROR);
+        msg.set_opt_rcode(OptRcode::NXDOMAIN).unwrap();
+        assert_eq!(msg.header().rcode(), Rcode::NXDOMAIN);
+        assert!(matches!(
+            msg.set_opt_rcode(OptRcode::BADCOOKIE),
+            Err(SetRcodeError::MissingOpt)
+        ));
+        assert_eq!(msg.opt_rcode(), OptRcode::NXDOMAIN);
+
+        msg.opt(|o| {
+            o.set_udp_payload_size(1232);
+            Ok(())
+        })
+        .unwrap();
+        msg.set_opt_rcode(OptRcode::BADCOOKIE).unwrap();
+        assert_eq!(msg.opt_rcode(), OptRcode::BADCOOKIE);
+        assert_eq!(msg.header().rcode(), Rcode::masked_from_int(7));
+        let msg = msg.into_message();
+        assert_eq!(msg.opt().unwrap().udp_payload_size(), 1232);
+        assert_eq!(msg.opt_rcode(), OptRcode::BADCOOKIE);
+    }
+
     fn create_compressed<T: Composer>(target: T) -> T
     where
         T::AppendError: fmt::Debug,
@@ -2951,4 +3458,83 @@ mod test {
         assert_eq!(45, actual.len(), "unexpected response size");
         assert_eq!(expect[..], actual, "unexpected response data");
     }
+
+    fn create_long<T: Composer>(target: T) -> T
+    where
+        T::AppendError: fmt::Debug,
+    {
+        let mut msg = MessageBuilder::from_target(target).unwrap().answer();
+        let mut i = 0;
+        while msg.as_slice().len() < POINTER_LIMIT {
+            let owner =
+                Name::<Vec<u8>>::from_str(&format!("n{i}.example")).unwrap();
+            msg.push((owner, 3600, A::from_octets(192, 0, 2, 1)))
+                .unwrap();
+            i += 1;
+        }
+        let late = Name::<Vec<u8>>::from_str("late.example.org").unwrap();
+        msg.push((&late, 3600, A::from_octets(192, 0, 2, 1)))
+            .unwrap();
+        msg.push((&late, 3600, A::from_octets(192, 0, 2, 2)))
+            .unwrap();
+        msg.finish()
+    }
+
+    fn check_long(msg: &[u8]) {
+        let late = Name::<Vec<u8>>::from_str("late.example.org").unwrap();
+        let msg = Message::from_octets(msg).unwrap();
+        let answer = msg.answer().unwrap();
+        let count = answer.count();
+        for record in answer.skip(count - 2) {
+            assert_eq!(record.unwrap().owner(), late);
+        }
+    }
+
+    #[test]
+    fn compress_pointer_limit() {
+        // Names starting beyond the 14 bit pointer range must not be used
+        // as the target of a pointer.
+        check_long(create_long(StaticCompressor::new(Vec::new())).as_ref());
+        check_long(create_long(TreeCompressor::new(Vec::new())).as_ref());
+        check_long(create_long(HashCompressor::new(Vec::new())).as_ref());
+    }
+
+    #[test]
+    fn compress_ignoring_case() {
+        fn create<T: Composer>(target: T) -> T
+        where
+            T::AppendError: fmt::Debug,
+        {
+            let mut msg =
+                MessageBuilder::from_target(target).unwrap().question();
+            msg.push((
+                Name::<Vec<u8>>::from_str("www.example.com").unwrap(),
+                Rtype::A,
+            ))
+            .unwrap();
+            let mut msg = msg.answer();
+            msg.push((
+                Name::<Vec<u8>>::from_str("WWW.Example.COM").unwrap(),
+                3600,
+                A::from_octets(192, 0, 2, 1),
+            ))
+            .unwrap();
+            msg.finish()
+        }
+
+        // Header, question, and the answer with a compressed owner.
+        let len = 12 + 17 + 4 + 2 + 10 + 4;
+        assert_eq!(
+            create(StaticCompressor::new(Vec::new())).as_ref().len(),
+            len
+        );
+        assert_eq!(
+            create(TreeCompressor::new(Vec::new())).as_ref().len(),
+            len
+        );
+        assert_eq!(
+            create(HashCompressor::new(Vec::new())).as_ref().len(),
+            len
+        );
+    }
 }
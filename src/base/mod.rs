@@ -55,6 +55,7 @@
 //! all of them in their module. These are:
 //!
 //! * [charstr] for DNS character strings,
+//! * [dso] for the TLVs of DNS Stateful Operations messages,
 //! * [header] for the header of DNS messages,
 //! * [name] for domain names,
 //! * [opt] for the record data of OPT records used in EDNS,
@@ -91,12 +92,15 @@ pub use self::charstr::CharStr;
 pub use self::cmp::CanonicalOrd;
 pub use self::header::{Header, HeaderCounts, HeaderSection};
 pub use self::iana::Rtype;
-pub use self::message::{Message, QuestionSection, RecordSection};
 #[cfg(feature = "std")]
-pub use self::message_builder::{HashCompressor, TreeCompressor};
+pub use self::message::IndexedMessage;
+pub use self::message::{Message, QuestionSection, RecordSection};
 pub use self::message_builder::{
-    MessageBuilder, RecordSectionBuilder, StaticCompressor, StreamTarget,
+    Checkpoint, MessageBuilder, RecordSectionBuilder, StaticCompressor,
+    StreamTarget,
 };
+#[cfg(feature = "std")]
+pub use self::message_builder::{HashCompressor, TreeCompressor};
 pub use self::name::{
     Name, NameBuilder, ParsedName, RelativeName, ToName, ToRelativeName,
 };
@@ -110,6 +114,7 @@ pub use self::serial::Serial;
 pub mod charstr;
 pub mod cmp;
 mod dig_printer;
+pub mod dso;
 pub mod header;
 pub mod iana;
 pub mod message;
@@ -3,10 +3,12 @@
 //! This is a private module. Its public types are re-exported by the parent.
 
 use super::super::cmp::CanonicalOrd;
-use super::super::net::IpAddr;
+use super::super::net::{IpAddr, Ipv4Addr};
 use super::super::scan::{Scanner, Symbol, SymbolCharsError, Symbols};
 use super::super::wire::{FormError, ParseError};
 use super::builder::{FromStrError, NameBuilder, PushError};
+#[cfg(feature = "std")]
+use super::idna::{self, IdnaError};
 use super::label::{Label, LabelTypeError, SplitLabelError};
 use super::relative::{NameIter, RelativeName};
 use super::traits::{FlattenInto, ToLabelIter, ToName};
@@ -23,6 +25,8 @@ use octseq::parse::Parser;
 #[cfg(feature = "serde")]
 use octseq::serde::{DeserializeOctets, SerializeOctets};
 #[cfg(feature = "std")]
+use std::string::String;
+#[cfg(feature = "std")]
 use std::vec::Vec;
 
 //------------ Name ----------------------------------------------------------
@@ -44,6 +48,14 @@ use std::vec::Vec;
 /// using [`to_string`] or by using its [`Display`] implementation (which
 /// performs no allocations).
 ///
+/// If you need a domain name that doesn’t allocate, use an [`Array`] with
+/// the maximum name length as the octets sequence, i.e.,
+/// `Name<Array<255>>`. Such a name keeps its wire format inline and works
+/// everywhere other names do. Since the array is large enough for any name,
+/// you can use [`ToName::try_to_name`] to convert any other name into one
+/// without having to worry about running out of space.
+///
+/// [`Array`]: octseq::array::Array
 /// [`FromStr`]: std::str::FromStr
 /// [`to_string`]: std::string::ToString::to_string
 /// [`ParsedName`]: crate::base::name::ParsedName
@@ -218,6 +230,193 @@ impl<Octs> Name<Octs> {
         }
         builder.into_name()
     }
+
+    /// Creates the name of a classless reverse zone for an IPv4 network.
+    ///
+    /// Reverse lookup for networks with a prefix longer than 24 bits can
+    /// be delegated as described in [RFC 2317]: The network gets its own
+    /// zone below the zone of its /24 network and the latter contains a
+    /// CNAME record for every address of the network pointing to the name
+    /// returned by [`reverse_classless_from_addr`] in the new zone.
+    ///
+    /// The zone is named after the first address of the network and the
+    /// prefix length separated by a slash. For instance, the zone for
+    /// 192.0.2.0/25 is `0/25.2.0.192.in-addr.arpa.` The slash is an
+    /// ordinary octet of the label and doesn’t need escaping in the
+    /// presentation format.
+    ///
+    /// Host bits set in `addr` are ignored. Returns an error if
+    /// `prefix_len` isn’t between 25 and 32.
+    ///
+    /// [RFC 2317]: https://tools.ietf.org/html/rfc2317
+    /// [`reverse_classless_from_addr`]: Self::reverse_classless_from_addr
+    pub fn reverse_classless_zone(
+        addr: Ipv4Addr,
+        prefix_len: u8,
+    ) -> Result<Self, ClasslessError>
+    where
+        Octs: FromBuilder,
+        <Octs as FromBuilder>::Builder: EmptyBuilder
+            + FreezeBuilder<Octets = Octs>
+            + AsRef<[u8]>
+            + AsMut<[u8]>,
+    {
+        let mut builder =
+            NameBuilder::<<Octs as FromBuilder>::Builder>::new();
+        Self::append_classless_zone(&mut builder, addr, prefix_len)?;
+        Ok(builder.into_name()?)
+    }
+
+    /// Creates the name for an IPv4 address in a classless reverse zone.
+    ///
+    /// This is the name of the address within the zone returned by
+    /// [`reverse_classless_zone`] for the network with the given prefix
+    /// length, e.g., `12.0/25.2.0.192.in-addr.arpa.` for 192.0.2.12/25.
+    /// It is the target of the CNAME record owned by the regular reverse
+    /// name of the address as returned by
+    /// [`reverse_from_addr`][Self::reverse_from_addr] and the owner of the
+    /// PTR record in the classless zone.
+    ///
+    /// Returns an error if `prefix_len` isn’t between 25 and 32.
+    ///
+    /// [`reverse_classless_zone`]: Self::reverse_classless_zone
+    pub fn reverse_classless_from_addr(
+        addr: Ipv4Addr,
+        prefix_len: u8,
+    ) -> Result<Self, ClasslessError>
+    where
+        Octs: FromBuilder,
+        <Octs as FromBuilder>::Builder: EmptyBuilder
+            + FreezeBuilder<Octets = Octs>
+            + AsRef<[u8]>
+            + AsMut<[u8]>,
+    {
+        let mut builder =
+            NameBuilder::<<Octs as FromBuilder>::Builder>::new();
+        builder.append_dec_u8_label(addr.octets()[3])?;
+        Self::append_classless_zone(&mut builder, addr, prefix_len)?;
+        Ok(builder.into_name()?)
+    }
+
+    /// Appends the labels of a classless reverse zone to a builder.
+    fn append_classless_zone<Builder>(
+        builder: &mut NameBuilder<Builder>,
+        addr: Ipv4Addr,
+        prefix_len: u8,
+    ) -> Result<(), ClasslessError>
+    where
+        Builder: OctetsBuilder + AsRef<[u8]> + AsMut<[u8]>,
+    {
+        if !(25..=32).contains(&prefix_len) {
+            return Err(ClasslessError::PrefixLen(prefix_len));
+        }
+        fn push_dec<Builder>(
+            builder: &mut NameBuilder<Builder>,
+            value: u8,
+        ) -> Result<(), PushError>
+        where
+            Builder: OctetsBuilder + AsRef<[u8]> + AsMut<[u8]>,
+        {
+            if value >= 100 {
+                builder.push(value / 100 + b'0')?;
+            }
+            if value >= 10 {
+                builder.push(value / 10 % 10 + b'0')?;
+            }
+            builder.push(value % 10 + b'0')
+        }
+
+        let [a, b, c, d] = addr.octets();
+        builder.end_label();
+        push_dec(builder, d & (0xFF << (32 - prefix_len)))?;
+        builder.push(b'/')?;
+        push_dec(builder, prefix_len)?;
+        builder.append_dec_u8_label(c)?;
+        builder.append_dec_u8_label(b)?;
+        builder.append_dec_u8_label(a)?;
+        builder.append_label(b"in-addr")?;
+        builder.append_label(b"arpa")?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Octs> Name<Octs> {
+    /// Creates a domain name from its Unicode form.
+    ///
+    /// Labels are separated by dots, including the ideographic full stop
+    /// and its fullwidth and halfwidth variants. Labels that contain
+    /// non-ASCII characters are mapped to lowercase and converted into their
+    /// ASCII compatible encoding with the `xn--` prefix. Labels that consist
+    /// of ASCII characters only are used as is. In particular, no escape
+    /// sequences are interpreted. If such a label is in ASCII compatible
+    /// encoding already, it has to be valid.
+    ///
+    /// This is a deliberately limited subset of the processing defined in
+    /// UTS #46:
+    ///
+    /// * Labels with non-ASCII characters are lowercased using the default
+    ///   Unicode lowercase mapping rather than the UTS #46 mapping table.
+    ///   ASCII-only labels keep their case, which doesn’t matter since
+    ///   domain names are compared ignoring ASCII case.
+    /// * There is no Unicode normalization.
+    /// * Labels with characters that normalization or the UTS #46 mapping
+    ///   would change, such as combining marks or fullwidth forms, are
+    ///   rejected. The check is conservative, so some names that full IDNA
+    ///   implementations accept are rejected, too.
+    ///
+    /// As with [`from_chars`][Self::from_chars], the name will always be
+    /// an absolute name whether `s` ends in a dot or not.
+    pub fn from_unicode(s: &str) -> Result<Self, IdnaError>
+    where
+        Octs: FromBuilder,
+        <Octs as FromBuilder>::Builder: EmptyBuilder
+            + FreezeBuilder<Octets = Octs>
+            + AsRef<[u8]>
+            + AsMut<[u8]>,
+    {
+        let mut builder = NameBuilder::<Octs::Builder>::new();
+
+        // A single dot is the root name which has no labels to append.
+        let labels = s.strip_suffix(idna::SEPARATORS).unwrap_or(s);
+        if s.is_empty() || !labels.is_empty() {
+            let mut label = Vec::new();
+            for part in labels.split(idna::SEPARATORS) {
+                label.clear();
+                idna::label_to_ascii(part, &mut label)?;
+                builder.append_label(&label)?;
+            }
+        }
+        builder.into_name().map_err(Into::into)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Octs: AsRef<[u8]> + ?Sized> Name<Octs> {
+    /// Returns the Unicode form of the domain name.
+    ///
+    /// Labels in ASCII compatible encoding are decoded while all other
+    /// labels are given in presentation format. As with the [`Display`]
+    /// implementation, there is no dot at the end of the name unless it is
+    /// the root name.
+    ///
+    /// Returns an error if a label with the `xn--` prefix does not contain
+    /// valid Punycode.
+    ///
+    /// [`Display`]: fmt::Display
+    pub fn to_unicode(&self) -> Result<String, IdnaError> {
+        if self.is_root() {
+            return Ok(".".into());
+        }
+        let mut res = String::new();
+        for label in self.iter().filter(|label| !label.is_root()) {
+            if !res.is_empty() {
+                res.push('.');
+            }
+            idna::label_to_unicode(label, &mut res)?;
+        }
+        Ok(res)
+    }
 }
 
 impl Name<[u8]> {
@@ -372,6 +571,30 @@ impl<Octs: ?Sized> Name<Octs> {
     {
         Label::make_slice_canonical(self.0.as_mut());
     }
+
+    /// Randomly changes the case of the ASCII letters in the domain name.
+    ///
+    /// Because name servers generally copy the question into their answers
+    /// as is, randomizing the case of a query name adds entropy to a query
+    /// that spoofed answers would have to guess, too. This is commonly known
+    /// as ‘DNS 0x20.’ Since names are compared ignoring ASCII case, the
+    /// answer needs to be checked with
+    /// [`Message::is_answer_exact_case`].
+    ///
+    /// [`Message::is_answer_exact_case`]: crate::base::Message::is_answer_exact_case
+    #[cfg(feature = "rand")]
+    pub fn randomize_case(&mut self)
+    where
+        Octs: AsMut<[u8]>,
+    {
+        // Length octets are at most 63 and thus never ASCII letters, so we
+        // can simply go over the whole slice.
+        for ch in self.0.as_mut() {
+            if ch.is_ascii_alphabetic() && ::rand::random() {
+                *ch ^= 0x20;
+            }
+        }
+    }
 }
 
 /// # Properties
@@ -397,13 +620,75 @@ impl<Octs: AsRef<[u8]> + ?Sized> Name<Octs> {
     pub fn fmt_with_dot(&self) -> impl fmt::Display + '_ {
         ToName::fmt_with_dot(self)
     }
+
+    /// Returns the IP address of a name used for reverse lookup.
+    ///
+    /// This is the inverse of [`reverse_from_addr`][Self::reverse_from_addr].
+    /// The name needs to consist of four decimal labels below
+    /// `in-addr.arpa.` for an IPv4 address or 32 single hex digit labels
+    /// below `ip6.arpa.` for an IPv6 address. Otherwise, `None` is returned.
+    pub fn reverse_to_addr(&self) -> Option<IpAddr> {
+        fn dec_u8(label: &Label) -> Option<u8> {
+            let slice = label.as_slice();
+            if slice.is_empty()
+                || slice.len() > 3
+                || (slice.len() > 1 && slice[0] == b'0')
+            {
+                return None;
+            }
+            let mut res = 0u16;
+            for &ch in slice {
+                if !ch.is_ascii_digit() {
+                    return None;
+                }
+                res = res * 10 + u16::from(ch - b'0');
+            }
+            u8::try_from(res).ok()
+        }
+
+        fn hex_digit(label: &Label) -> Option<u8> {
+            match *label.as_slice() {
+                [ch @ b'0'..=b'9'] => Some(ch - b'0'),
+                [ch @ b'a'..=b'f'] => Some(ch - b'a' + 10),
+                [ch @ b'A'..=b'F'] => Some(ch - b'A' + 10),
+                _ => None,
+            }
+        }
+
+        let mut labels = self.iter();
+        match self.label_count() {
+            7 if self.ends_with(
+                Name::from_slice(b"\x07in-addr\x04arpa\0").unwrap(),
+            ) =>
+            {
+                let mut addr = [0u8; 4];
+                for item in addr.iter_mut().rev() {
+                    *item = dec_u8(labels.next()?)?;
+                }
+                Some(addr.into())
+            }
+            35 if self.ends_with(
+                Name::from_slice(b"\x03ip6\x04arpa\0").unwrap(),
+            ) =>
+            {
+                let mut addr = [0u8; 16];
+                for item in addr.iter_mut().rev() {
+                    let low = hex_digit(labels.next()?)?;
+                    let high = hex_digit(labels.next()?)?;
+                    *item = high << 4 | low;
+                }
+                Some(addr.into())
+            }
+            _ => None,
+        }
+    }
 }
 
 /// # Working with Labels
 ///
 impl<Octs: AsRef<[u8]> + ?Sized> Name<Octs> {
     /// Returns an iterator over the labels of the domain name.
-    pub fn iter(&self) -> NameIter {
+    pub fn iter(&self) -> NameIter<'_> {
         NameIter::new(self.0.as_ref())
     }
 
@@ -639,6 +924,34 @@ impl<Octs: AsRef<[u8]> + ?Sized> Name<Octs> {
         }
     }
 
+    /// Splits the name into two after the given number of labels.
+    ///
+    /// Returns a pair of the relative name made up of the first `count`
+    /// labels and the absolute name made up of the remaining labels. If
+    /// `count` is zero, the left part is empty.
+    ///
+    /// Because the right part needs to contain at least the root label,
+    /// returns `None` if `count` is not less than the label count.
+    #[allow(clippy::type_complexity)]
+    pub fn split_at_label(
+        &self,
+        count: usize,
+    ) -> Option<(RelativeName<Octs::Range<'_>>, Name<Octs::Range<'_>>)>
+    where
+        Octs: Octets,
+    {
+        let mut mid = 0;
+        let mut labels = self.iter();
+        for _ in 0..count {
+            let label = labels.next()?;
+            if label.is_root() {
+                return None;
+            }
+            mid += usize::from(label.compose_len());
+        }
+        Some(self.split(mid))
+    }
+
     /// Truncates the name before `len`.
     ///
     /// Because truncating converts the name into a relative name, the method
@@ -702,6 +1015,44 @@ impl<Octs: AsRef<[u8]> + ?Sized> Name<Octs> {
             Err(self)
         }
     }
+
+    /// Returns whether the name is matched by a wildcard name.
+    ///
+    /// This implements the rules of [RFC 4592] for determining whether
+    /// `wildcard` is the source of synthesis for the name. The wildcard
+    /// needs to start with an asterisk label and the name needs to be
+    /// strictly below the remainder of the wildcard, its would-be closest
+    /// encloser.
+    ///
+    /// In addition, the wildcard only matches if there is no existing name
+    /// closer to the name than that closest encloser. Since this depends on
+    /// the content of the zone, the closure `exists` is asked whether each
+    /// of these closer names, including the name itself, exists. Note that
+    /// a name that owns no records but has names below it, a so-called
+    /// empty non-terminal, exists, too.
+    ///
+    /// [RFC 4592]: https://tools.ietf.org/html/rfc4592
+    pub fn matches_wildcard<N, F>(&self, wildcard: &N, mut exists: F) -> bool
+    where
+        Octs: Octets,
+        N: ToName + ?Sized,
+        F: FnMut(&Name<[u8]>) -> bool,
+    {
+        let mut encloser = wildcard.iter_labels();
+        if !encloser.next().is_some_and(Label::is_wildcard) {
+            return false;
+        }
+        let encloser_count = encloser.clone().count();
+        let self_count = self.label_count();
+        if self_count <= encloser_count
+            || !self.iter().rev().zip(encloser.rev()).all(|(l, r)| l == r)
+        {
+            return false;
+        }
+        self.iter_suffixes()
+            .take(self_count - encloser_count)
+            .all(|suffix| !exists(suffix.for_slice()))
+    }
 }
 
 impl<Octs> Name<Octs> {
@@ -922,16 +1273,20 @@ impl<Octs: AsRef<[u8]> + ?Sized> fmt::Display for Name<Octs> {
     /// This will produce the domain name in ‘common display format’ without
     /// the trailing dot with the exception of a root name which will be just
     /// a dot.
+    ///
+    /// Labels are escaped as described for the `Display` implementation of
+    /// [`Label`]. The alternate form `{:#}` is passed on to the labels.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if self.is_root() {
             return f.write_str(".");
         }
 
         let mut iter = self.iter();
-        write!(f, "{}", iter.next().unwrap())?;
+        iter.next().unwrap().fmt(f)?;
         for label in iter {
             if !label.is_root() {
-                write!(f, ".{}", label)?
+                f.write_str(".")?;
+                label.fmt(f)?;
             }
         }
         Ok(())
@@ -1220,6 +1575,42 @@ impl fmt::Display for NameError {
 #[cfg(feature = "std")]
 impl std::error::Error for NameError {}
 
+//------------ ClasslessError ------------------------------------------------
+
+/// A name for classless reverse delegation could not be created.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ClasslessError {
+    /// The prefix length is not between 25 and 32.
+    PrefixLen(u8),
+
+    /// Building the name failed.
+    Push(PushError),
+}
+
+//--- From
+
+impl From<PushError> for ClasslessError {
+    fn from(err: PushError) -> Self {
+        ClasslessError::Push(err)
+    }
+}
+
+//--- Display and Error
+
+impl fmt::Display for ClasslessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ClasslessError::PrefixLen(len) => {
+                write!(f, "invalid prefix length {len} for classless zone")
+            }
+            ClasslessError::Push(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ClasslessError {}
+
 //============ Testing =======================================================
 //
 // Some of the helper functions herein are resused by the tests of other
@@ -1368,6 +1759,101 @@ pub(crate) mod test {
             .unwrap()
         );
     }
+
+    #[test]
+    fn reverse_to_addr() {
+        type TestName = Name<octseq::array::Array<128>>;
+
+        for addr in [
+            IpAddr::from([192, 0, 2, 12]),
+            IpAddr::from([0, 10, 100, 255]),
+            IpAddr::from([
+                0x2001, 0xdb8, 0x1234, 0x0, 0x5678, 0x1, 0x9abc, 0xdef,
+            ]),
+        ] {
+            assert_eq!(
+                TestName::reverse_from_addr(addr).unwrap().reverse_to_addr(),
+                Some(addr)
+            );
+        }
+
+        assert_eq!(
+            TestName::from_str("12.2.0.192.IN-ADDR.ARPA")
+                .unwrap()
+                .reverse_to_addr(),
+            Some(IpAddr::from([192, 0, 2, 12]))
+        );
+        for name in [
+            "2.0.192.in-addr.arpa",
+            "1.12.2.0.192.in-addr.arpa",
+            "012.2.0.192.in-addr.arpa",
+            "256.2.0.192.in-addr.arpa",
+            "x.2.0.192.in-addr.arpa",
+            "12.2.0.192.ip6.arpa",
+            "12.2.0.192.in-addr.example",
+            "f.e.d.0.c.b.a.9.1.0.0.0.8.7.6.5.\
+             0.0.0.0.4.3.2.1.8.b.d.0.1.0.0.2.\
+             in-addr.arpa",
+            "f.e.d.0.c.b.a.9.1.0.0.0.8.7.6.5.\
+             0.0.0.0.4.3.2.1.8.b.d.0.1.0.0.g.\
+             ip6.arpa",
+            "fe.d.0.c.b.a.9.1.0.0.0.8.7.6.5.\
+             0.0.0.0.4.3.2.1.8.b.d.0.1.0.0.2.\
+             ip6.arpa",
+        ] {
+            assert_eq!(
+                TestName::from_str(name).unwrap().reverse_to_addr(),
+                None,
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn reverse_classless() {
+        type TestName = Name<octseq::array::Array<128>>;
+
+        let addr = Ipv4Addr::from([192, 0, 2, 140]);
+        let zone = TestName::reverse_classless_zone(addr, 25).unwrap();
+        assert_eq!(
+            zone,
+            TestName::from_str("128/25.2.0.192.in-addr.arpa").unwrap()
+        );
+        assert_eq!(zone.first().as_slice(), b"128/25");
+        #[cfg(feature = "std")]
+        {
+            use std::string::ToString;
+
+            // The slash isn’t escaped when displaying the name.
+            assert_eq!(zone.to_string(), "128/25.2.0.192.in-addr.arpa");
+        }
+
+        let name = TestName::reverse_classless_from_addr(addr, 25).unwrap();
+        assert_eq!(
+            name,
+            TestName::from_str("140.128/25.2.0.192.in-addr.arpa").unwrap()
+        );
+        assert!(name.ends_with(&zone));
+        assert_eq!(
+            TestName::reverse_classless_zone([192, 0, 2, 13].into(), 29)
+                .unwrap(),
+            TestName::from_str("8/29.2.0.192.in-addr.arpa").unwrap()
+        );
+        assert_eq!(
+            TestName::reverse_classless_zone([192, 0, 2, 13].into(), 32)
+                .unwrap(),
+            TestName::from_str("13/32.2.0.192.in-addr.arpa").unwrap()
+        );
+
+        for prefix_len in [0, 24, 33] {
+            assert_eq!(
+                TestName::reverse_classless_zone(addr, prefix_len),
+                Err(ClasslessError::PrefixLen(prefix_len))
+            );
+        }
+    }
+
     // `Name::from_chars` is covered in the `FromStr` test.
     //
     // No tests for the simple conversion methods because, well, simple.
@@ -1394,6 +1880,22 @@ pub(crate) mod test {
         );
     }
 
+    #[test]
+    #[cfg(all(feature = "std", feature = "rand"))]
+    fn randomize_case() {
+        let orig = Name::vec_from_str(&"abcdefghij.".repeat(20)).unwrap();
+        let mut name = orig.clone();
+        name.randomize_case();
+        assert_eq!(name, orig);
+        assert!(name.as_slice().eq_ignore_ascii_case(orig.as_slice()));
+        // The chance of all 200 letters staying lowercase is negligible.
+        assert_ne!(name.as_slice(), orig.as_slice());
+
+        let mut name = Name::vec_from_str("1-2.3_4.").unwrap();
+        name.randomize_case();
+        assert_eq!(name.as_slice(), b"\x031-2\x033_4\0");
+    }
+
     #[test]
     fn is_root() {
         assert!(Name::from_slice(b"\0").unwrap().is_root());
@@ -1754,6 +2256,27 @@ pub(crate) mod test {
         assert!(wecr.parent().is_none());
     }
 
+    #[test]
+    fn split_at_label() {
+        let wecr = Name::from_octets(b"\x03www\x07example\x03com\0".as_ref())
+            .unwrap();
+
+        let (left, right) = wecr.split_at_label(0).unwrap();
+        assert_eq!(left.as_slice(), b"");
+        assert_eq!(right.as_slice(), b"\x03www\x07example\x03com\0");
+
+        let (left, right) = wecr.split_at_label(2).unwrap();
+        assert_eq!(left.as_slice(), b"\x03www\x07example");
+        assert_eq!(right.as_slice(), b"\x03com\0");
+
+        let (left, right) = wecr.split_at_label(3).unwrap();
+        assert_eq!(left.as_slice(), b"\x03www\x07example\x03com");
+        assert_eq!(right.as_slice(), b"\0");
+
+        assert!(wecr.split_at_label(4).is_none());
+        assert!(Name::root_slice().split_at_label(1).is_none());
+    }
+
     #[test]
     fn strip_suffix() {
         let wecr = Name::from_octets(b"\x03www\x07example\x03com\0".as_ref())
@@ -1798,6 +2321,63 @@ pub(crate) mod test {
         );
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn matches_wildcard() {
+        // The example zone from section 2.2.1 of RFC 4592.
+        let existing = [
+            "example.",
+            "*.example.",
+            "host1.example.",
+            "_ssh._tcp.host1.example.",
+            "_tcp.host1.example.",
+            "_ssh._tcp.host2.example.",
+            "_tcp.host2.example.",
+            "host2.example.",
+            "subdel.example.",
+            "sub.*.example.",
+        ]
+        .map(|name| Name::<Vec<u8>>::from_str(name).unwrap());
+        let exists = |name: &Name<[u8]>| existing.iter().any(|n| n == name);
+        let wildcard = Name::<Vec<u8>>::from_str("*.example.").unwrap();
+        let check = |qname: &str| {
+            Name::<Vec<u8>>::from_str(qname)
+                .unwrap()
+                .matches_wildcard(&wildcard, exists)
+        };
+
+        // These are the queries from section 2.2.1 that are answered via
+        // the wildcard.
+        assert!(check("host3.example."));
+        assert!(check("foo.bar.example."));
+
+        // And these aren’t.
+        assert!(!check("host1.example."));
+        assert!(!check("sub.*.example."));
+        assert!(!check("_telnet._tcp.host1.example."));
+        assert!(!check("host.subdel.example."));
+        assert!(!check("ghost.*.example."));
+        assert!(!check("*.example."));
+        assert!(!check("example."));
+        assert!(!check("foo.example.com."));
+
+        // Without a zone, only the structure is checked.
+        let qname = Name::<Vec<u8>>::from_str("foo.bar.example.").unwrap();
+        assert!(qname.matches_wildcard(&wildcard, |_| false));
+        assert!(!qname.matches_wildcard(
+            &Name::<Vec<u8>>::from_str("foo.example.").unwrap(),
+            |_| false
+        ));
+        assert!(!qname.matches_wildcard(
+            &Name::<Vec<u8>>::from_str("*.bar.example.").unwrap(),
+            |_| true
+        ));
+        assert!(qname.matches_wildcard(
+            &Name::<Vec<u8>>::from_str("*.BAR.example.").unwrap(),
+            |_| false
+        ));
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn parse() {
@@ -1896,6 +2476,118 @@ pub(crate) mod test {
         );
     }
 
+    #[test]
+    fn array_name() {
+        use core::str::FromStr;
+        use octseq::array::Array;
+
+        let wecr = Name::<Array<255>>::from_str("www.example.com").unwrap();
+        assert_eq!(wecr.as_slice(), b"\x03www\x07example\x03com\0");
+
+        let ecr = Name::from_slice(b"\x07example\x03com\0").unwrap();
+        let chained: Name<Array<255>> = RelativeName::from_octets(b"\x03www")
+            .unwrap()
+            .chain(ecr)
+            .unwrap()
+            .try_to_name()
+            .unwrap();
+        assert_eq!(chained, wecr);
+
+        let mut target = Array::<255>::new();
+        wecr.compose(&mut target).unwrap();
+        assert_eq!(target.as_ref(), wecr.as_slice());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn display_from_str_round_trip() {
+        use core::str::FromStr;
+        use std::string::ToString;
+        use std::vec::Vec;
+
+        // All octet values don’t fit into a single name, so use two.
+        let octets = (0..=255u8).collect::<Vec<_>>();
+        for half in octets.chunks(128) {
+            let mut builder = NameBuilder::new_vec();
+            for label in half.chunks(Label::MAX_LEN) {
+                builder.append_label(label).unwrap();
+            }
+            let name = builder.into_name().unwrap();
+            assert_eq!(
+                Name::<Vec<u8>>::from_str(&name.to_string())
+                    .unwrap()
+                    .as_slice(),
+                name.as_slice()
+            );
+        }
+        assert_eq!(
+            Name::<Vec<u8>>::from_str(r"a\.b.c\\d.\000\255")
+                .unwrap()
+                .as_slice(),
+            b"\x03a.b\x03c\\d\x02\x00\xff\0"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn from_unicode() {
+        // The actual label conversion is tested in the idna module. Just
+        // check that the name is assembled correctly.
+        assert_eq!(
+            Name::<Vec<u8>>::from_unicode(".").unwrap().as_slice(),
+            b"\0"
+        );
+        assert_eq!(
+            Name::<Vec<u8>>::from_unicode("www.Bücher.example")
+                .unwrap()
+                .as_slice(),
+            b"\x03www\x0dxn--bcher-kva\x07example\0"
+        );
+        assert_eq!(
+            Name::<Vec<u8>>::from_unicode("bücher\u{3002}example\u{FF0E}")
+                .unwrap()
+                .as_slice(),
+            b"\x0dxn--bcher-kva\x07example\0"
+        );
+        // ASCII-only labels keep their case.
+        assert_eq!(
+            Name::<Vec<u8>>::from_unicode("WWW.example")
+                .unwrap()
+                .as_slice(),
+            b"\x03WWW\x07example\0"
+        );
+        // Input that would need normalization is rejected.
+        assert!(Name::<Vec<u8>>::from_unicode("bu\u{308}cher").is_err());
+        assert!(Name::<Vec<u8>>::from_unicode("").is_err());
+        assert!(Name::<Vec<u8>>::from_unicode("..").is_err());
+        assert!(Name::<Vec<u8>>::from_unicode("www..example").is_err());
+        assert!(Name::<Vec<u8>>::from_unicode(&"ü".repeat(60)).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn to_unicode() {
+        assert_eq!(Name::root_slice().to_unicode().unwrap(), ".");
+        assert_eq!(
+            Name::from_slice(b"\x03www\x0dxn--bcher-kva\x07example\0")
+                .unwrap()
+                .to_unicode()
+                .unwrap(),
+            "www.bücher.example"
+        );
+        assert_eq!(
+            Name::from_slice(b"\x03a.b\x0dXN--bcher-kva\0")
+                .unwrap()
+                .to_unicode()
+                .unwrap(),
+            "a\\.b.bücher"
+        );
+        assert!(Name::from_slice(b"\x07xn--b!c\0")
+            .unwrap()
+            .to_unicode()
+            .is_err());
+    }
+
     #[test]
     fn eq() {
         assert_eq!(
@@ -2011,6 +2703,12 @@ pub(crate) mod test {
         cmp(b"\0", ".", ".");
         cmp(b"\x03com\0", "com", "com.");
         cmp(b"\x07example\x03com\0", "example.com", "example.com.");
+        cmp(b"\x03a.b\x03c\\d\0", "a\\.b.c\\\\d", "a\\.b.c\\\\d.");
+        cmp(b"\x03a\xffb\x03c d\0", "a\\255b.c\\ d", "a\\255b.c\\ d.");
+
+        let name = Name::from_octets(b"\x03a.b\x03c d\0".as_ref()).unwrap();
+        assert_eq!(format!("{:#}", name), "a.b.c d");
+        assert_eq!(format!("{:#}", name.fmt_with_dot()), "a.b.c d.");
     }
 
     #[cfg(all(feature = "serde", feature = "std"))]
@@ -337,13 +337,11 @@ where
     ///
     /// Returns an error if appending would result in a name longer than 254
     /// bytes.
-    //
-    //  XXX NEEDS TESTS
     pub fn append_name<N: ToRelativeName>(
         &mut self,
         name: &N,
     ) -> Result<(), PushNameError> {
-        let head = self.head.take();
+        let head = self.head;
         self.end_label();
         if self.len() + usize::from(name.compose_len()) > 254 {
             self.head = head;
@@ -442,8 +440,6 @@ where
     /// If there currently is a label under construction, ends the label.
     /// Then adds the `origin` and transforms the name into a
     /// [`Name`].
-    //
-    //  XXX NEEDS TESTS
     pub fn append_origin<N: ToName>(
         mut self,
         origin: &N,
@@ -865,4 +861,58 @@ mod test {
             b"\x03www\x07example\x03com\x00"
         );
     }
+
+    #[test]
+    fn append_name() {
+        let ec = RelativeName::from_octets(b"\x07example\x03com".as_ref())
+            .unwrap();
+
+        let mut builder = NameBuilder::new_vec();
+        builder.append_label(b"www").unwrap();
+        builder.append_name(&ec).unwrap();
+        assert_eq!(builder.finish().as_slice(), b"\x03www\x07example\x03com");
+
+        // A label under construction is ended first.
+        let mut builder = NameBuilder::new_vec();
+        builder.append_slice(b"www").unwrap();
+        builder.append_name(&ec).unwrap();
+        assert!(!builder.in_label());
+        assert_eq!(builder.finish().as_slice(), b"\x03www\x07example\x03com");
+
+        let mut builder = NameBuilder::new_vec();
+        for _ in 0..24 {
+            builder.append_label(b"123456789").unwrap();
+        }
+        builder.append_slice(b"12").unwrap();
+        assert_eq!(builder.append_name(&ec), Err(PushNameError::LongName));
+        assert!(builder.in_label());
+        builder.push(b'3').unwrap();
+        assert_eq!(builder.len(), 244);
+    }
+
+    #[test]
+    fn append_origin() {
+        let ec =
+            Name::from_octets(b"\x07example\x03com\x00".as_ref()).unwrap();
+
+        let mut builder = NameBuilder::new_vec();
+        builder.append_slice(b"www").unwrap();
+        assert_eq!(
+            builder.append_origin(&ec).unwrap().as_slice(),
+            b"\x03www\x07example\x03com\x00"
+        );
+
+        let mut builder = NameBuilder::new_vec();
+        for _ in 0..25 {
+            builder.append_label(b"123456789").unwrap();
+        }
+        assert_eq!(
+            builder.clone().append_origin(&ec),
+            Err(PushNameError::LongName)
+        );
+        assert_eq!(
+            builder.append_origin(&Name::root_slice()).unwrap().len(),
+            251
+        );
+    }
 }
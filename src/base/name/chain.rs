@@ -3,13 +3,14 @@
 //! This is a private module. Its public types are re-exported by the parent
 //! crate.
 
+use super::super::cmp::CanonicalOrd;
 use super::super::scan::Scanner;
 use super::label::Label;
 use super::relative::NameIter;
 use super::traits::{FlattenInto, ToLabelIter, ToName, ToRelativeName};
 use super::uncertain::UncertainName;
 use super::Name;
-use core::{fmt, iter};
+use core::{cmp, fmt, hash, iter};
 use octseq::builder::{
     BuilderAppendError, EmptyBuilder, FreezeBuilder, FromBuilder,
 };
@@ -28,6 +29,10 @@ use octseq::builder::{
 /// A chain on an uncertain name is special in that the second name is only
 /// used if the uncertain name is relative.
 ///
+/// To turn a chain into a single owned name, use [`ToName::to_name`] or
+/// [`ToRelativeName::to_relative_name`] (or their fallible `try_` versions)
+/// which copy the labels into a new octets sequence in one go.
+///
 /// [`RelativeName::chain`]: super::RelativeName::chain
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -190,6 +195,64 @@ where
 {
 }
 
+//--- PartialEq, Eq, and Hash
+
+/// Compares the labels of the chain with those of any other name.
+///
+/// As with all name types, labels are compared ignoring ASCII case. An
+/// absolute chain can only be equal to an absolute name and a relative chain
+/// only to a relative name.
+impl<L, R, N> PartialEq<N> for Chain<L, R>
+where
+    Self: ToLabelIter,
+    N: ToLabelIter + ?Sized,
+{
+    fn eq(&self, other: &N) -> bool {
+        self.iter_labels().eq(other.iter_labels())
+    }
+}
+
+impl<L, R> Eq for Chain<L, R> where Self: ToLabelIter {}
+
+/// Hashes the chain in the same way as the other name types.
+///
+/// This makes it possible to use a chain to look up values in a hash map
+/// keyed by other names.
+impl<L, R> hash::Hash for Chain<L, R>
+where
+    Self: ToLabelIter,
+{
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        for label in self.iter_labels() {
+            label.hash(state)
+        }
+    }
+}
+
+//--- CanonicalOrd
+
+impl<L, R, N> CanonicalOrd<N> for Chain<L, R>
+where
+    L: ToRelativeName,
+    R: ToName,
+    N: ToName + ?Sized,
+{
+    fn canonical_cmp(&self, other: &N) -> cmp::Ordering {
+        self.name_cmp(other)
+    }
+}
+
+impl<Octets, R, N> CanonicalOrd<N> for Chain<UncertainName<Octets>, R>
+where
+    Octets: AsRef<[u8]>,
+    R: ToName,
+    N: ToName + ?Sized,
+{
+    fn canonical_cmp(&self, other: &N) -> cmp::Ordering {
+        self.name_cmp(other)
+    }
+}
+
 //--- FlattenInto
 
 impl<L, R, Target> FlattenInto<Name<Target>> for Chain<L, R>
@@ -382,8 +445,9 @@ impl std::error::Error for LongChainError {}
 #[cfg(feature = "std")]
 mod test {
     use super::*;
-    use crate::base::name::RelativeName;
+    use crate::base::name::{ParsedName, RelativeName};
     use octseq::builder::infallible;
+    use octseq::parse::Parser;
 
     /// Tests that `ToName` and `ToRelativeName` are implemented for the
     /// right types.
@@ -482,7 +546,8 @@ mod test {
     fn to_label_iter_impl() {
         fn check_impl<N: ToLabelIter>(name: N, labels: &[&[u8]]) {
             let labels = labels.iter().map(|s| Label::from_slice(s).unwrap());
-            assert!(name.iter_labels().eq(labels));
+            assert!(name.iter_labels().eq(labels.clone()));
+            assert!(name.iter_labels().rev().eq(labels.rev()));
             assert_eq!(
                 name.iter_labels().map(|l| l.compose_len()).sum::<u16>(),
                 name.compose_len()
@@ -529,6 +594,66 @@ mod test {
         );
     }
 
+    /// Tests that chains sort in canonical order.
+    #[test]
+    fn canonical_cmp() {
+        use core::cmp::Ordering;
+
+        // The names from section 6.1 of RFC 4034 split into a relative and
+        // an absolute part in various ways.
+        let example = Name::from_slice(b"\x07example\0").unwrap();
+        let a_example = Name::from_slice(b"\x01a\x07example\0").unwrap();
+        let names = [
+            RelativeName::empty_slice().chain(example).unwrap(),
+            RelativeName::from_slice(b"\x01a")
+                .unwrap()
+                .chain(example)
+                .unwrap(),
+            RelativeName::from_slice(b"\x08yljkjljk")
+                .unwrap()
+                .chain(a_example)
+                .unwrap(),
+            RelativeName::from_slice(b"\x01Z\x01a")
+                .unwrap()
+                .chain(example)
+                .unwrap(),
+            RelativeName::from_slice(b"\x04zABC")
+                .unwrap()
+                .chain(a_example)
+                .unwrap(),
+            RelativeName::from_slice(b"\x01z")
+                .unwrap()
+                .chain(example)
+                .unwrap(),
+            RelativeName::from_slice(b"\x01\x01\x01z")
+                .unwrap()
+                .chain(example)
+                .unwrap(),
+            RelativeName::from_slice(b"\x01*\x01z")
+                .unwrap()
+                .chain(example)
+                .unwrap(),
+            RelativeName::from_slice(b"\x01\xc8\x01z")
+                .unwrap()
+                .chain(example)
+                .unwrap(),
+        ];
+        for (i, left) in names.iter().enumerate() {
+            for (j, right) in names.iter().enumerate() {
+                assert_eq!(left.canonical_cmp(right), i.cmp(&j));
+                assert_eq!(left.canonical_cmp(&right.to_vec()), i.cmp(&j));
+            }
+        }
+
+        let uncertain = UncertainName::from(
+            RelativeName::from_slice(b"\x01Z").unwrap().to_vec(),
+        )
+        .chain(a_example)
+        .unwrap();
+        assert_eq!(uncertain.canonical_cmp(&names[3]), Ordering::Equal);
+        assert_eq!(uncertain.canonical_cmp(example), Ordering::Greater);
+    }
+
     /// Tests that composing works as expected.
     #[test]
     fn compose() {
@@ -674,4 +799,74 @@ mod test {
         cmp(uabs.clone().chain(root), "com", "com.");
         cmp(uabs.clone().chain(abs), "com", "com.");
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn eq_and_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        fn hash<T: Hash + ?Sized>(t: &T) -> u64 {
+            let mut s = DefaultHasher::new();
+            t.hash(&mut s);
+            s.finish()
+        }
+
+        let name = Name::from_octets(b"\x03www\x07example\x03com\0".as_ref())
+            .unwrap();
+        let chain = RelativeName::from_octets(b"\x03WWW".as_ref())
+            .unwrap()
+            .chain(
+                Name::from_octets(b"\x07exAMPle\x03com\0".as_ref()).unwrap(),
+            )
+            .unwrap();
+        let mut parser =
+            Parser::from_static(b"\x03com\0\x03www\x07example\xc0\0");
+        parser.advance(5).unwrap();
+        let parsed = ParsedName::parse(&mut parser).unwrap();
+
+        assert_eq!(chain, name);
+        assert_eq!(name, chain);
+        assert_eq!(chain, parsed);
+        assert_eq!(parsed, chain);
+        assert_eq!(hash(&chain), hash(&name));
+        assert_eq!(hash(&chain), hash(&parsed));
+
+        let rel = RelativeName::from_octets(b"\x03www".as_ref())
+            .unwrap()
+            .chain(
+                RelativeName::from_octets(b"\x07example".as_ref()).unwrap(),
+            )
+            .unwrap();
+        let rel_name =
+            RelativeName::from_octets(b"\x03www\x07example".as_ref())
+                .unwrap();
+        assert_eq!(rel, rel_name);
+        assert_eq!(hash(&rel), hash(&rel_name));
+        assert_ne!(rel, name);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn to_name() {
+        use std::vec::Vec;
+
+        let www = RelativeName::from_octets(b"\x03www".as_ref()).unwrap();
+        let example =
+            RelativeName::from_octets(b"\x07example".as_ref()).unwrap();
+        let com = Name::from_octets(b"\x03com\0".as_ref()).unwrap();
+
+        let name: Name<Vec<u8>> = www
+            .clone()
+            .chain(example.clone())
+            .unwrap()
+            .chain(com)
+            .unwrap()
+            .to_name();
+        assert_eq!(name.as_slice(), b"\x03www\x07example\x03com\0");
+
+        let rel: RelativeName<Vec<u8>> =
+            www.chain(example).unwrap().to_relative_name();
+        assert_eq!(rel.as_slice(), b"\x03www\x07example");
+    }
 }
@@ -0,0 +1,602 @@
+//! Internationalized domain names.
+//!
+//! This is a private module. Its public types are re-exported by the parent.
+//!
+//! The module implements the Punycode encoding defined in [RFC 3492] and
+//! uses it to convert labels between their Unicode form and their ASCII
+//! compatible encoding (ACE) form with the `xn--` prefix as used by IDNA.
+//!
+//! This is a deliberately limited subset of the processing described in
+//! [UTS #46]. Characters are mapped to lowercase using the default Unicode
+//! mapping and labels are checked for the hyphen restrictions and for
+//! invalid characters. There is neither the UTS #46 mapping table nor
+//! Unicode normalization. Instead, characters that either of them would
+//! change are rejected. This covers combining marks that normalization
+//! could compose with the preceding character and compatibility
+//! characters such as fullwidth forms. The check is conservative and based
+//! on ranges of characters, so it rejects some characters that full UTS #46
+//! processing accepts.
+//!
+//! [RFC 3492]: https://datatracker.ietf.org/doc/html/rfc3492
+//! [UTS #46]: https://www.unicode.org/reports/tr46/
+
+use super::builder::PushError;
+use super::label::Label;
+use core::cmp::Ordering;
+use core::fmt;
+use std::string::String;
+use std::vec::Vec;
+
+//------------ Label Conversion ----------------------------------------------
+
+/// The prefix marking a label in ASCII compatible encoding.
+const ACE_PREFIX: &str = "xn--";
+
+/// The characters that separate labels in the Unicode form of a name.
+///
+/// Besides the full stop, UTS #46 maps the ideographic full stop as well
+/// as the fullwidth and halfwidth variants to a label separator.
+pub(super) const SEPARATORS: &[char] =
+    &['.', '\u{3002}', '\u{FF0E}', '\u{FF61}'];
+
+/// Converts a label in Unicode form into its ASCII form.
+///
+/// Labels consisting only of ASCII characters are used as is. If they are
+/// in ACE form already, they have to contain valid Punycode that decodes
+/// into acceptable characters. Since these labels aren’t changed, they
+/// aren’t checked for unsupported characters. All other labels are checked for
+/// unsupported characters, mapped to lowercase, checked again, and
+/// encoded.
+///
+/// The ASCII form of the label is appended to `target`.
+pub(super) fn label_to_ascii(
+    label: &str,
+    target: &mut Vec<u8>,
+) -> Result<(), IdnaError> {
+    if label.is_empty() {
+        return Err(IdnaErrorEnum::EmptyLabel.into());
+    }
+    if label.is_ascii() {
+        if let Some(encoded) = strip_ace_prefix(label.as_bytes()) {
+            check_chars(&decode(encoded)?)?;
+        }
+        target.extend_from_slice(label.as_bytes());
+        return Ok(());
+    }
+
+    check_supported(&label.chars().collect::<Vec<_>>())?;
+    let chars: Vec<char> =
+        label.chars().flat_map(char::to_lowercase).collect();
+    check_chars(&chars)?;
+    target.extend_from_slice(ACE_PREFIX.as_bytes());
+    encode(&chars, target)
+}
+
+/// Converts a label into its Unicode form.
+///
+/// Labels in ACE form are decoded. All other labels are appended in their
+/// presentation format.
+pub(super) fn label_to_unicode(
+    label: &Label,
+    target: &mut String,
+) -> Result<(), IdnaError> {
+    match strip_ace_prefix(label.as_slice()) {
+        Some(encoded) => {
+            target.extend(decode(encoded)?);
+        }
+        None => {
+            use core::fmt::Write;
+
+            write!(target, "{}", label).expect("writing to string failed");
+        }
+    }
+    Ok(())
+}
+
+/// Returns the remainder of a label if it starts with the ACE prefix.
+fn strip_ace_prefix(label: &[u8]) -> Option<&[u8]> {
+    if label.len() > ACE_PREFIX.len()
+        && label[..ACE_PREFIX.len()]
+            .eq_ignore_ascii_case(ACE_PREFIX.as_bytes())
+    {
+        Some(&label[ACE_PREFIX.len()..])
+    } else {
+        None
+    }
+}
+
+/// Checks that the mapped characters of a label are acceptable.
+///
+/// Any ASCII characters need to be letters, digits, or hyphens. There must
+/// not be a hyphen at the start or end of the label or hyphens in both the
+/// third and fourth position. Non-ASCII characters must not be control or
+/// white space characters.
+fn check_chars(chars: &[char]) -> Result<(), IdnaError> {
+    if chars.first() == Some(&'-') || chars.last() == Some(&'-') {
+        return Err(IdnaErrorEnum::Hyphen.into());
+    }
+    if chars.get(2) == Some(&'-') && chars.get(3) == Some(&'-') {
+        return Err(IdnaErrorEnum::Hyphen.into());
+    }
+    for &ch in chars {
+        let good = if ch.is_ascii() {
+            ch.is_ascii_alphanumeric() || ch == '-'
+        } else {
+            !ch.is_control() && !ch.is_whitespace()
+        };
+        if !good {
+            return Err(IdnaErrorEnum::BadChar(ch).into());
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the characters of a label can be processed.
+///
+/// Returns an error for the first character that falls into one of the
+/// [`UNSUPPORTED`] ranges.
+fn check_supported(chars: &[char]) -> Result<(), IdnaError> {
+    for &ch in chars {
+        let unsupported = UNSUPPORTED
+            .binary_search_by(|&(start, end)| {
+                if end < ch {
+                    Ordering::Less
+                } else if start > ch {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .is_ok();
+        if unsupported {
+            return Err(IdnaErrorEnum::Unsupported(ch).into());
+        }
+    }
+    Ok(())
+}
+
+/// The ranges of characters that can’t be processed.
+///
+/// Either Unicode normalization or the UTS #46 mapping would change these
+/// characters in a way that differs from the default lowercase mapping.
+/// The ranges are sorted and don’t overlap.
+const UNSUPPORTED: &[(char, char)] = &[
+    // Latin-1 punctuation, symbols, and ordinal indicators.
+    ('\u{A0}', '\u{BF}'),
+    // Latin ligatures and letters mapped to several characters.
+    ('\u{132}', '\u{133}'),
+    ('\u{13F}', '\u{140}'),
+    ('\u{149}', '\u{149}'),
+    ('\u{17F}', '\u{17F}'),
+    ('\u{1C4}', '\u{1CC}'),
+    ('\u{1F1}', '\u{1F3}'),
+    // Modifier letters and combining diacritical marks.
+    ('\u{2B0}', '\u{36F}'),
+    // Greek signs, punctuation, and symbol variants.
+    ('\u{374}', '\u{37E}'),
+    ('\u{384}', '\u{387}'),
+    ('\u{3D0}', '\u{3D6}'),
+    ('\u{3F0}', '\u{3F5}'),
+    // Armenian ligature.
+    ('\u{587}', '\u{587}'),
+    // Arabic letters with a high hamza.
+    ('\u{675}', '\u{678}'),
+    // Indic letters decomposed by normalization.
+    ('\u{958}', '\u{95F}'),
+    ('\u{9DC}', '\u{9DD}'),
+    ('\u{9DF}', '\u{9DF}'),
+    ('\u{A33}', '\u{A33}'),
+    ('\u{A36}', '\u{A36}'),
+    ('\u{A59}', '\u{A5B}'),
+    ('\u{A5E}', '\u{A5E}'),
+    ('\u{B5C}', '\u{B5D}'),
+    // Thai and Lao vowels and ligatures mapped to several characters.
+    ('\u{E33}', '\u{E33}'),
+    ('\u{EB3}', '\u{EB3}'),
+    ('\u{EDC}', '\u{EDD}'),
+    // Tibetan letters and vowel signs decomposed by normalization.
+    ('\u{F0C}', '\u{F0C}'),
+    ('\u{F43}', '\u{F43}'),
+    ('\u{F4D}', '\u{F4D}'),
+    ('\u{F52}', '\u{F52}'),
+    ('\u{F57}', '\u{F57}'),
+    ('\u{F5C}', '\u{F5C}'),
+    ('\u{F69}', '\u{F69}'),
+    ('\u{F73}', '\u{F73}'),
+    ('\u{F75}', '\u{F79}'),
+    ('\u{F81}', '\u{F81}'),
+    ('\u{F93}', '\u{F93}'),
+    ('\u{F9D}', '\u{F9D}'),
+    ('\u{FA2}', '\u{FA2}'),
+    ('\u{FA7}', '\u{FA7}'),
+    ('\u{FAC}', '\u{FAC}'),
+    ('\u{FB9}', '\u{FB9}'),
+    // Georgian modifier letter.
+    ('\u{10FC}', '\u{10FC}'),
+    // Conjoining Hangul Jamo composed by normalization.
+    ('\u{1100}', '\u{11FF}'),
+    // Cherokee, mapped to uppercase by UTS #46.
+    ('\u{13A0}', '\u{13FF}'),
+    // Extended combining diacritical marks.
+    ('\u{1AB0}', '\u{1AFF}'),
+    // Cyrillic letter variants and Georgian Mtavruli.
+    ('\u{1C80}', '\u{1CBF}'),
+    // Phonetic modifier letters and combining marks.
+    ('\u{1D2C}', '\u{1DFF}'),
+    // Latin letters mapped to several characters.
+    ('\u{1E9A}', '\u{1E9B}'),
+    ('\u{1E9E}', '\u{1E9E}'),
+    // Greek letters with oxia and iota subscript.
+    ('\u{1F70}', '\u{1FFF}'),
+    // Punctuation, letterlike symbols, number forms, and other symbols.
+    ('\u{2000}', '\u{2BFF}'),
+    // CJK and Kangxi radicals.
+    ('\u{2E80}', '\u{2FFF}'),
+    // CJK symbols and punctuation.
+    ('\u{3000}', '\u{3004}'),
+    ('\u{3008}', '\u{303F}'),
+    // Combining Kana voiced sound marks and Kana digraphs.
+    ('\u{3099}', '\u{309C}'),
+    ('\u{309F}', '\u{309F}'),
+    ('\u{30FF}', '\u{30FF}'),
+    // Hangul compatibility Jamo, Kanbun, and enclosed CJK characters.
+    ('\u{3131}', '\u{319F}'),
+    ('\u{3200}', '\u{33FF}'),
+    // Conjoining Hangul Jamo extensions.
+    ('\u{A960}', '\u{A97F}'),
+    // Latin modifier letters and Cherokee small letters.
+    ('\u{AB5C}', '\u{AB5F}'),
+    ('\u{AB70}', '\u{ABBF}'),
+    ('\u{D7B0}', '\u{D7FF}'),
+    // Private use area, CJK compatibility ideographs, presentation forms,
+    // variation selectors, and halfwidth and fullwidth forms.
+    ('\u{E000}', '\u{FFFF}'),
+    // Mathematical alphanumeric symbols.
+    ('\u{1D400}', '\u{1D7FF}'),
+    // Enclosed alphanumeric and ideographic supplements.
+    ('\u{1F100}', '\u{1F2FF}'),
+    // CJK compatibility ideographs supplement.
+    ('\u{2F800}', '\u{2FA1F}'),
+    // Tags, variation selectors supplement, and private use planes.
+    ('\u{E0000}', '\u{10FFFF}'),
+];
+
+//------------ Punycode ------------------------------------------------------
+
+const BASE: u32 = 36;
+const TMIN: u32 = 1;
+const TMAX: u32 = 26;
+const SKEW: u32 = 38;
+const DAMP: u32 = 700;
+const INITIAL_BIAS: u32 = 72;
+const INITIAL_N: u32 = 0x80;
+
+/// Adapts the bias after a delta has been encoded or decoded.
+///
+/// This is the algorithm from section 6.1 of RFC 3492.
+fn adapt(delta: u32, numpoints: u32, firsttime: bool) -> u32 {
+    let mut delta = if firsttime { delta / DAMP } else { delta / 2 };
+    delta += delta / numpoints;
+    let mut k = 0;
+    while delta > ((BASE - TMIN) * TMAX) / 2 {
+        delta /= BASE - TMIN;
+        k += BASE;
+    }
+    k + (BASE - TMIN + 1) * delta / (delta + SKEW)
+}
+
+/// Returns the threshold for the digit at position `k`.
+fn threshold(k: u32, bias: u32) -> u32 {
+    if k <= bias {
+        TMIN
+    } else if k >= bias + TMAX {
+        TMAX
+    } else {
+        k - bias
+    }
+}
+
+/// Returns the ASCII character for a digit value.
+fn encode_digit(digit: u32) -> u8 {
+    match digit {
+        0..=25 => b'a' + digit as u8,
+        _ => b'0' + (digit - 26) as u8,
+    }
+}
+
+/// Returns the digit value of an ASCII character.
+fn decode_digit(ch: u8) -> Option<u32> {
+    match ch {
+        b'a'..=b'z' => Some(u32::from(ch - b'a')),
+        b'A'..=b'Z' => Some(u32::from(ch - b'A')),
+        b'0'..=b'9' => Some(u32::from(ch - b'0') + 26),
+        _ => None,
+    }
+}
+
+/// Encodes a sequence of characters into Punycode.
+///
+/// The encoded octets are appended to `target`. This is the algorithm from
+/// section 6.3 of RFC 3492.
+fn encode(input: &[char], target: &mut Vec<u8>) -> Result<(), IdnaError> {
+    let overflow = || IdnaError::from(IdnaErrorEnum::Overflow);
+
+    let mut basic = 0;
+    for &ch in input.iter().filter(|ch| ch.is_ascii()) {
+        target.push(ch as u8);
+        basic += 1;
+    }
+    if basic > 0 {
+        target.push(b'-');
+    }
+
+    let len = u32::try_from(input.len()).map_err(|_| overflow())?;
+    let mut n = INITIAL_N;
+    let mut delta = 0u32;
+    let mut bias = INITIAL_BIAS;
+    let mut handled = basic;
+    while handled < len {
+        let m = input
+            .iter()
+            .map(|&ch| u32::from(ch))
+            .filter(|&ch| ch >= n)
+            .min()
+            .ok_or_else(overflow)?;
+        delta = (m - n)
+            .checked_mul(handled + 1)
+            .and_then(|value| delta.checked_add(value))
+            .ok_or_else(overflow)?;
+        n = m;
+        for &ch in input {
+            let ch = u32::from(ch);
+            if ch < n {
+                delta = delta.checked_add(1).ok_or_else(overflow)?;
+            }
+            if ch == n {
+                let mut q = delta;
+                let mut k = BASE;
+                loop {
+                    let t = threshold(k, bias);
+                    if q < t {
+                        break;
+                    }
+                    target.push(encode_digit(t + (q - t) % (BASE - t)));
+                    q = (q - t) / (BASE - t);
+                    k += BASE;
+                }
+                target.push(encode_digit(q));
+                bias = adapt(delta, handled + 1, handled == basic);
+                delta = 0;
+                handled += 1;
+            }
+        }
+        delta = delta.checked_add(1).ok_or_else(overflow)?;
+        n += 1;
+    }
+    Ok(())
+}
+
+/// Decodes Punycode into a sequence of characters.
+///
+/// This is the algorithm from section 6.2 of RFC 3492.
+fn decode(input: &[u8]) -> Result<Vec<char>, IdnaError> {
+    let bad = || IdnaError::from(IdnaErrorEnum::BadPunycode);
+
+    let (mut output, extended) =
+        match input.iter().rposition(|&ch| ch == b'-') {
+            Some(pos) => {
+                let basic = &input[..pos];
+                if !basic.is_ascii() {
+                    return Err(bad());
+                }
+                (
+                    basic.iter().map(|&ch| char::from(ch)).collect(),
+                    &input[pos + 1..],
+                )
+            }
+            None => (Vec::new(), input),
+        };
+
+    let mut n = INITIAL_N;
+    let mut i = 0u32;
+    let mut bias = INITIAL_BIAS;
+    let mut extended = extended.iter();
+    while extended.len() > 0 {
+        let oldi = i;
+        let mut w = 1u32;
+        let mut k = BASE;
+        loop {
+            let digit = extended
+                .next()
+                .and_then(|&ch| decode_digit(ch))
+                .ok_or_else(bad)?;
+            i = digit
+                .checked_mul(w)
+                .and_then(|value| i.checked_add(value))
+                .ok_or_else(bad)?;
+            let t = threshold(k, bias);
+            if digit < t {
+                break;
+            }
+            w = w.checked_mul(BASE - t).ok_or_else(bad)?;
+            k += BASE;
+        }
+        let len = u32::try_from(output.len() + 1).map_err(|_| bad())?;
+        bias = adapt(i - oldi, len, oldi == 0);
+        n = n.checked_add(i / len).ok_or_else(bad)?;
+        i %= len;
+        let ch = char::from_u32(n).ok_or_else(bad)?;
+        if ch.is_ascii() {
+            return Err(bad());
+        }
+        output.insert(i as usize, ch);
+        i += 1;
+    }
+    Ok(output)
+}
+
+//============ Error Types ===================================================
+
+//------------ IdnaError -----------------------------------------------------
+
+/// An error happened while converting an internationalized domain name.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct IdnaError(IdnaErrorEnum);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum IdnaErrorEnum {
+    /// The name contained an empty label.
+    EmptyLabel,
+
+    /// A label started or ended with a hyphen or had hyphens in both the
+    /// third and fourth position.
+    Hyphen,
+
+    /// A label contained a character that isn’t allowed.
+    BadChar(char),
+
+    /// A label contained a character that can’t be processed.
+    Unsupported(char),
+
+    /// A label in ACE form didn’t contain valid Punycode.
+    BadPunycode,
+
+    /// A label was too long to be encoded.
+    Overflow,
+
+    /// Building the domain name failed.
+    Push(PushError),
+}
+
+//--- From
+
+impl From<IdnaErrorEnum> for IdnaError {
+    fn from(err: IdnaErrorEnum) -> Self {
+        Self(err)
+    }
+}
+
+impl From<PushError> for IdnaError {
+    fn from(err: PushError) -> Self {
+        Self(IdnaErrorEnum::Push(err))
+    }
+}
+
+//--- Display and Error
+
+impl fmt::Display for IdnaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            IdnaErrorEnum::EmptyLabel => f.write_str("empty label"),
+            IdnaErrorEnum::Hyphen => f.write_str("misplaced hyphen in label"),
+            IdnaErrorEnum::BadChar(ch) => {
+                write!(f, "invalid character {:?} in label", ch)
+            }
+            IdnaErrorEnum::Unsupported(ch) => {
+                write!(f, "unsupported character {:?} in label", ch)
+            }
+            IdnaErrorEnum::BadPunycode => f.write_str("invalid punycode"),
+            IdnaErrorEnum::Overflow => f.write_str("long label"),
+            IdnaErrorEnum::Push(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for IdnaError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn encode_str(s: &str) -> String {
+        let chars: Vec<char> = s.chars().collect();
+        let mut res = Vec::new();
+        encode(&chars, &mut res).unwrap();
+        String::from_utf8(res).unwrap()
+    }
+
+    fn decode_str(s: &str) -> String {
+        decode(s.as_bytes()).unwrap().into_iter().collect()
+    }
+
+    #[test]
+    fn punycode() {
+        // Samples from section 7.1 of RFC 3492 as well as some common
+        // examples.
+        let samples = [
+            ("bücher", "bcher-kva"),
+            ("münchen", "mnchen-3ya"),
+            ("españa", "espaa-rta"),
+            (
+                "\u{4ED6}\u{4EEC}\u{4E3A}\u{4EC0}\u{4E48}\u{4E0D}\u{8BF4}\
+                 \u{4E2D}\u{6587}",
+                "ihqwcrb4cv8a8dqg056pqjye",
+            ),
+            (
+                "3\u{5E74}B\u{7D44}\u{91D1}\u{516B}\u{5148}\u{751F}",
+                "3B-ww4c5e180e575a65lsy2b",
+            ),
+        ];
+        for (unicode, ascii) in samples {
+            assert_eq!(encode_str(unicode), ascii);
+            assert_eq!(decode_str(ascii), unicode);
+        }
+    }
+
+    #[test]
+    fn bad_punycode() {
+        assert!(decode(b"bcher-kv!").is_err());
+        assert!(decode("bü-kva".as_bytes()).is_err());
+        // The last digit doesn’t terminate the number.
+        assert!(decode(b"bcher-kv9").is_err());
+        assert!(decode(b"99999999999999").is_err());
+    }
+
+    #[test]
+    fn unsupported_ranges() {
+        for &(start, end) in UNSUPPORTED {
+            assert!(start <= end);
+        }
+        for pair in UNSUPPORTED.windows(2) {
+            assert!(pair[0].1 < pair[1].0);
+        }
+    }
+
+    #[test]
+    fn to_ascii() {
+        fn check(label: &str, expected: Result<&str, IdnaError>) {
+            let mut res = Vec::new();
+            assert_eq!(
+                label_to_ascii(label, &mut res)
+                    .map(|_| String::from_utf8(res).unwrap()),
+                expected.map(String::from)
+            );
+        }
+
+        check("example", Ok("example"));
+        check("_tcp", Ok("_tcp"));
+        check("Bücher", Ok("xn--bcher-kva"));
+        check("xn--bcher-kva", Ok("xn--bcher-kva"));
+        check("xn--bcher-kv!", Err(IdnaErrorEnum::BadPunycode.into()));
+        check("", Err(IdnaErrorEnum::EmptyLabel.into()));
+        check("-bücher", Err(IdnaErrorEnum::Hyphen.into()));
+        check("bü--cher", Err(IdnaErrorEnum::Hyphen.into()));
+        check("bü-", Err(IdnaErrorEnum::Hyphen.into()));
+        check("bü_cher", Err(IdnaErrorEnum::BadChar('_').into()));
+        check("bü\u{1680}", Err(IdnaErrorEnum::BadChar('\u{1680}').into()));
+        check("xn--b--xka", Err(IdnaErrorEnum::Hyphen.into()));
+        check("İstanbul", Ok("xn--istanbul-o0e"));
+        check("xn--istanbul-o0e", Ok("xn--istanbul-o0e"));
+        check(
+            "bu\u{308}cher",
+            Err(IdnaErrorEnum::Unsupported('\u{308}').into()),
+        );
+        check("ｂüｃｈｅｒ", Err(IdnaErrorEnum::Unsupported('ｂ').into()));
+        check(
+            "bü\u{2003}",
+            Err(IdnaErrorEnum::Unsupported('\u{2003}').into()),
+        );
+    }
+}
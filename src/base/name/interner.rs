@@ -0,0 +1,229 @@
+//! Interning of domain names.
+//!
+//! This is a private module. Its public types are re-exported by the parent
+//! module.
+
+use super::absolute::Name;
+use super::traits::ToName;
+use bytes::Bytes;
+use core::{borrow, fmt, hash};
+use octseq::array::Array;
+use std::collections::HashSet;
+
+//------------ InternedName --------------------------------------------------
+
+/// A domain name handed out by a [`NameInterner`].
+///
+/// This is a regular `Name<Bytes>`. Its octets are reference counted, so
+/// cloning the name is cheap and all clones share the same memory.
+pub type InternedName = Name<Bytes>;
+
+//------------ NameInterner --------------------------------------------------
+
+/// A pool of deduplicated domain names.
+///
+/// Applications that store a lot of domain names – such as the owner names
+/// of records in a cache – often end up with many copies of the same name.
+/// The interner keeps exactly one copy of each name and hands out
+/// [`InternedName`]s, reference counted handles to that copy.
+///
+/// Names are compared octet by octet when deduplicating. In particular, the
+/// interner preserves the case of names, so names that differ only in case
+/// are interned separately.
+///
+/// The interner keeps a reference to every name it has handed out. To
+/// release names that are not used anywhere else anymore, call
+/// [`purge`][Self::purge] every once in a while.
+#[derive(Clone, Default)]
+pub struct NameInterner {
+    /// The set of interned names.
+    names: HashSet<Key>,
+}
+
+impl NameInterner {
+    /// Creates a new, empty interner.
+    #[must_use]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns the interned version of the given name.
+    ///
+    /// If an identical name has been interned before, returns a handle to
+    /// it. Otherwise, adds a copy of the name to the interner.
+    pub fn intern<N: ToName + ?Sized>(&mut self, name: &N) -> InternedName {
+        with_flat_slice(name, |slice| {
+            if let Some(key) = self.names.get(slice) {
+                return key.0.clone();
+            }
+            let res = unsafe {
+                Name::from_octets_unchecked(Bytes::copy_from_slice(slice))
+            };
+            self.names.insert(Key(res.clone()));
+            res
+        })
+    }
+
+    /// Returns the interned version of the given name if there is one.
+    ///
+    /// Unlike [`intern`][Self::intern], this method does not add the name
+    /// if it hasn’t been interned yet.
+    #[must_use]
+    pub fn get<N: ToName + ?Sized>(&self, name: &N) -> Option<InternedName> {
+        with_flat_slice(name, |slice| {
+            self.names.get(slice).map(|key| key.0.clone())
+        })
+    }
+
+    /// Returns the number of names currently kept by the interner.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Returns whether the interner is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Removes all names that are not used outside of the interner.
+    pub fn purge(&mut self) {
+        self.names.retain(|key| !key.0.as_octets().is_unique())
+    }
+
+    /// Removes all names from the interner.
+    ///
+    /// Names previously handed out stay valid but will not be shared with
+    /// names interned in the future.
+    pub fn clear(&mut self) {
+        self.names.clear()
+    }
+
+    /// Returns an iterator over all names currently kept by the interner.
+    pub fn iter(&self) -> impl Iterator<Item = &InternedName> + '_ {
+        self.names.iter().map(|key| &key.0)
+    }
+}
+
+//--- Extend
+
+impl<N: ToName> Extend<N> for NameInterner {
+    fn extend<T: IntoIterator<Item = N>>(&mut self, iter: T) {
+        for name in iter {
+            self.intern(&name);
+        }
+    }
+}
+
+//--- Debug
+
+impl fmt::Debug for NameInterner {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+//------------ Key -----------------------------------------------------------
+
+/// An interned name as kept in the set.
+///
+/// Unlike names themselves, which compare and hash ignoring ASCII case,
+/// the key compares and hashes the exact octets of the name. It can be
+/// borrowed as the octets slice so we can look up names without creating
+/// a key first.
+#[derive(Clone)]
+struct Key(InternedName);
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_slice() == other.0.as_slice()
+    }
+}
+
+impl Eq for Key {}
+
+impl hash::Hash for Key {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.0.as_slice().hash(state)
+    }
+}
+
+impl borrow::Borrow<[u8]> for Key {
+    fn borrow(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Calls `op` with the uncompressed wire format of `name`.
+fn with_flat_slice<N: ToName + ?Sized, R>(
+    name: &N,
+    op: impl FnOnce(&[u8]) -> R,
+) -> R {
+    if let Some(slice) = name.as_flat_slice() {
+        return op(slice);
+    }
+    let mut buf = Array::<255>::new();
+    name.compose(&mut buf).expect("long domain name");
+    op(buf.as_ref())
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::name::{Chain, RelativeName};
+    use core::str::FromStr;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    #[test]
+    fn intern() {
+        let mut interner = NameInterner::new();
+        assert!(interner.is_empty());
+
+        let one = interner
+            .intern(&Name::<Vec<u8>>::from_str("www.example.com").unwrap());
+        let two = interner.intern(
+            &Chain::new(
+                RelativeName::from_slice(b"\x03www").unwrap(),
+                Name::from_slice(b"\x07example\x03com\0").unwrap(),
+            )
+            .unwrap(),
+        );
+        assert_eq!(one.as_octets().as_ptr(), two.as_octets().as_ptr());
+        assert_eq!(interner.len(), 1);
+
+        let upper = interner
+            .intern(&Name::<Vec<u8>>::from_str("WWW.example.com").unwrap());
+        assert_ne!(one.as_octets().as_ptr(), upper.as_octets().as_ptr());
+        assert_eq!(upper.to_string(), "WWW.example.com");
+        assert_eq!(interner.len(), 2);
+
+        assert!(interner
+            .get(&Name::<Vec<u8>>::from_str("example.com").unwrap())
+            .is_none());
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn purge() {
+        let mut interner = NameInterner::new();
+        let kept = interner
+            .intern(&Name::<Vec<u8>>::from_str("www.example.com").unwrap());
+        drop(
+            interner.intern(
+                &Name::<Vec<u8>>::from_str("mail.example.com").unwrap(),
+            ),
+        );
+        interner.purge();
+        assert_eq!(interner.len(), 1);
+        assert_eq!(
+            interner.get(&kept).unwrap().as_octets().as_ptr(),
+            kept.as_octets().as_ptr()
+        );
+    }
+}
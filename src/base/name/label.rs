@@ -190,7 +190,7 @@ impl Label {
     }
 
     /// Iterator over the octets of the label.
-    pub fn iter(&self) -> iter::Copied<slice::Iter<u8>> {
+    pub fn iter(&self) -> iter::Copied<slice::Iter<'_, u8>> {
         self.as_slice().iter().copied()
     }
 
@@ -205,7 +205,7 @@ impl Label {
     ///
     /// Panics if `start` is beyond the end of `slice`.
     #[must_use]
-    pub fn iter_slice(slice: &[u8], start: usize) -> SliceLabelsIter {
+    pub fn iter_slice(slice: &[u8], start: usize) -> SliceLabelsIter<'_> {
         SliceLabelsIter { slice, start }
     }
 
@@ -434,9 +434,20 @@ impl<'a> IntoIterator for &'a Label {
 //--- Display and Debug
 
 impl fmt::Display for Label {
+    /// Formats the label in presentation format.
+    ///
+    /// Dots, spaces, and backslashes are escaped by a preceding backslash
+    /// and all other octets outside of printable ASCII are escaped as a
+    /// backslash followed by their three-digit decimal value, as described
+    /// in RFC 1035, section 5.1.
+    ///
+    /// The alternate form, i.e., `{:#}`, does not escape dots, spaces, and
+    /// backslashes. Because this is ambiguous, it should only be used for
+    /// displaying labels to humans.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let alternate = f.alternate();
         for ch in self.iter() {
-            if ch == b' ' || ch == b'.' || ch == b'\\' {
+            if (ch == b' ' || ch == b'.' || ch == b'\\') && !alternate {
                 write!(f, "\\{}", ch as char)?;
             } else if !(0x20..0x7F).contains(&ch) {
                 write!(f, "\\{:03}", ch)?;
@@ -456,6 +467,38 @@ impl fmt::Debug for Label {
     }
 }
 
+//--- Serialize
+
+/// Serializes a label.
+///
+/// Human-readable formats receive the escaped presentation format, binary
+/// formats the content octets. There is no matching `Deserialize` impl as
+/// `Label` is a dynamically sized type that cannot be created by a
+/// deserializer. Instead, a serialized label can be deserialized into an
+/// [`OwnedLabel`]. In order for both to use the same format, the label is
+/// serialized as a newtype struct named `OwnedLabel`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Label {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        use octseq::serde::SerializeOctets;
+
+        if serializer.is_human_readable() {
+            serializer.serialize_newtype_struct(
+                "OwnedLabel",
+                &format_args!("{}", self),
+            )
+        } else {
+            serializer.serialize_newtype_struct(
+                "OwnedLabel",
+                &self.as_slice().as_serialized_octets(),
+            )
+        }
+    }
+}
+
 //------------ OwnedLabel ----------------------------------------------------
 
 /// An owned label.
@@ -1016,6 +1059,46 @@ mod test {
 
     // XXX OwnedLabel::from_str
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn display() {
+        let label = Label::from_slice(b"a.b\\c d\x00\x7f").unwrap();
+        assert_eq!(format!("{}", label), "a\\.b\\\\c\\ d\\000\\127");
+        assert_eq!(format!("{:#}", label), "a.b\\c d\\000\\127");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn label_ser() {
+        use serde_test::{assert_ser_tokens, Configure, Token};
+
+        let label = Label::from_slice(b"fo.").unwrap();
+        assert_ser_tokens(
+            &label.compact(),
+            &[
+                Token::NewtypeStruct { name: "OwnedLabel" },
+                Token::Bytes(b"fo."),
+            ],
+        );
+        assert_ser_tokens(
+            &label.readable(),
+            &[
+                Token::NewtypeStruct { name: "OwnedLabel" },
+                Token::Str("fo\\."),
+            ],
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn label_ser_owned_label_de() {
+        let label = Label::from_slice(b"fo.").unwrap();
+        let json = serde_json::to_string(label).unwrap();
+        assert_eq!(json, "\"fo\\\\.\"");
+        let owned: OwnedLabel = serde_json::from_str(&json).unwrap();
+        assert_eq!(owned.as_label(), label);
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn owned_label_ser_de() {
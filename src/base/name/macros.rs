@@ -0,0 +1,211 @@
+//! Macros for creating domain names at compile time.
+//!
+//! This is a private module. The macros are exported at the crate root and
+//! the helper functions they need are re-exported, hidden, by the parent
+//! module.
+
+//------------ name! ---------------------------------------------------------
+
+/// Creates a static absolute domain name from a string literal.
+///
+/// The macro takes a domain name in presentation format – with or without
+/// the final dot – and expands to a `Name<&'static [u8]>`. The name is
+/// converted into wire format during compilation, so an invalid name
+/// results in a compile time error rather than a panic at runtime. Because
+/// of this, the macro can be used to define constants.
+///
+/// The macro supports the escape sequences `\.`, `\\`, and `\DDD` just like
+/// the [`FromStr`] implementation of [`Name`]. Since there is no way to
+/// tell whether a name given without a final dot is meant to be relative,
+/// all names are considered absolute.
+///
+/// # Examples
+///
+/// ```
+/// use domain::base::name::Name;
+/// use domain::name;
+///
+/// const EXAMPLE: Name<&[u8]> = name!("www.example.com.");
+/// assert_eq!(EXAMPLE.as_slice(), b"\x03www\x07example\x03com\0");
+/// assert!(name!(".").is_root());
+/// ```
+///
+/// Invalid names are rejected:
+///
+/// ```compile_fail
+/// let _ = domain::name!("www..example.com");
+/// ```
+///
+/// [`FromStr`]: core::str::FromStr
+/// [`Name`]: crate::base::name::Name
+#[macro_export]
+macro_rules! name {
+    ($s:expr) => {{
+        const WIRE: ([u8; 255], usize) =
+            $crate::base::name::__name_to_wire($s);
+        const OCTETS: [u8; WIRE.1] =
+            $crate::base::name::__truncate_wire::<{ WIRE.1 }>(&WIRE.0);
+        unsafe {
+            $crate::base::name::Name::<&'static [u8]>::from_octets_unchecked(
+                &OCTETS,
+            )
+        }
+    }};
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Converts a domain name in presentation format into wire format.
+///
+/// Returns a buffer with the wire format and the length of the name in the
+/// buffer. Panics if the name is invalid.
+#[doc(hidden)]
+pub const fn name_to_wire(s: &str) -> ([u8; 255], usize) {
+    let s = s.as_bytes();
+    let mut buf = [0u8; 255];
+
+    if s.is_empty() {
+        panic!("empty domain name");
+    }
+    if s.len() == 1 && s[0] == b'.' {
+        return (buf, 1);
+    }
+
+    // The position of the length octet of the current label.
+    let mut start = 0;
+
+    // The position where the next octet is written.
+    let mut pos = 1;
+
+    let mut i = 0;
+    while i < s.len() {
+        let ch = s[i];
+        i += 1;
+        let octet = if ch == b'.' {
+            if pos == start + 1 {
+                panic!("empty label in domain name");
+            }
+            buf[start] = (pos - start - 1) as u8;
+            start = pos;
+            pos += 1;
+            continue;
+        } else if ch == b'\\' {
+            if i >= s.len() {
+                panic!("unexpected end of domain name");
+            }
+            let ch = s[i];
+            i += 1;
+            if ch.is_ascii_digit() {
+                if i + 2 > s.len()
+                    || !s[i].is_ascii_digit()
+                    || !s[i + 1].is_ascii_digit()
+                {
+                    panic!("illegal escape sequence in domain name");
+                }
+                let value = (ch - b'0') as u16 * 100
+                    + (s[i] - b'0') as u16 * 10
+                    + (s[i + 1] - b'0') as u16;
+                i += 2;
+                if value > 255 {
+                    panic!("illegal escape sequence in domain name");
+                }
+                value as u8
+            } else if ch.is_ascii_graphic() || ch == b' ' {
+                ch
+            } else {
+                panic!("illegal escape sequence in domain name");
+            }
+        } else if ch.is_ascii_graphic() {
+            ch
+        } else {
+            panic!("illegal character in domain name");
+        };
+        if pos - start > 63 {
+            panic!("long label in domain name");
+        }
+        if pos >= 254 {
+            panic!("long domain name");
+        }
+        buf[pos] = octet;
+        pos += 1;
+    }
+
+    // If the name didn’t end in a dot, end the last label. Either way, the
+    // root label is written by leaving its zero octet in place.
+    if pos > start + 1 {
+        buf[start] = (pos - start - 1) as u8;
+        pos += 1;
+    }
+    (buf, pos)
+}
+
+/// Returns the first `N` octets of a wire format buffer.
+#[doc(hidden)]
+pub const fn truncate_wire<const N: usize>(buf: &[u8; 255]) -> [u8; N] {
+    let mut res = [0u8; N];
+    let mut i = 0;
+    while i < N {
+        res[i] = buf[i];
+        i += 1;
+    }
+    res
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use crate::base::name::Name;
+
+    const WECR: Name<&[u8]> = name!("www.example.com");
+
+    #[test]
+    fn name() {
+        assert_eq!(name!(".").as_slice(), b"\0");
+        assert_eq!(WECR.as_slice(), b"\x03www\x07example\x03com\0");
+        assert_eq!(
+            name!("www.example.com.").as_slice(),
+            b"\x03www\x07example\x03com\0"
+        );
+        assert_eq!(
+            name!(r"a\.b.c\\d.\000\255\ ").as_slice(),
+            b"\x03a.b\x03c\\d\x03\x00\xff \0"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn name_to_wire() {
+        use super::name_to_wire;
+
+        fn wire(s: &str) -> Option<std::vec::Vec<u8>> {
+            std::panic::catch_unwind(|| {
+                let (buf, len) = name_to_wire(s);
+                buf[..len].to_vec()
+            })
+            .ok()
+        }
+
+        let longest = "a".repeat(63)
+            + "."
+            + &"b".repeat(63)
+            + "."
+            + &"c".repeat(63)
+            + "."
+            + &"d".repeat(61);
+        assert_eq!(wire(&longest).unwrap().len(), 255);
+        assert!(wire(&(longest + "d")).is_none());
+        assert_eq!(wire(&"a".repeat(63)).unwrap().len(), 65);
+        assert!(wire(&"a".repeat(64)).is_none());
+
+        assert!(wire("").is_none());
+        assert!(wire("..").is_none());
+        assert!(wire(".example").is_none());
+        assert!(wire("www..example").is_none());
+        assert!(wire("www example").is_none());
+        assert!(wire("bücher").is_none());
+        assert!(wire(r"a\").is_none());
+        assert!(wire(r"a\25").is_none());
+        assert!(wire(r"a\256").is_none());
+    }
+}
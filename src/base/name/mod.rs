@@ -40,8 +40,10 @@
 //! Unicode strings in labels using only ASCII characters. This encoding is
 //! called [_punycode_].
 //!
-//! This crate currently does not support conversion from and to IDN
-//! representations of domain names. This will be added in future versions.
+//! With the `std` feature, [`Name::from_unicode`] and [`Name::to_unicode`]
+//! convert between the Unicode form of a domain name and its encoded form.
+//! Note that they only perform a subset of the processing defined for IDNA.
+//! In particular, there is no Unicode normalization.
 //!
 //!
 //! ## Absolute, relative, and ‘uncertain’ domain names
@@ -86,24 +88,38 @@
 //! You can create a domain name value from its presentation format using the
 //! [`FromStr`] trait. Alternatively, the [`NameBuilder`] type allows you to
 //! construct a name from scratch by appending octets, slices, or complete
-//! labels.
+//! labels. Names known at compile time can be created with the [`name!`]
+//! macro.
+//!
+//! With the `std` and `bytes` features, the [`NameInterner`] can be used to
+//! deduplicate domain names when a large number of them is stored, e.g., in
+//! a cache.
 //!
 //! [`FromStr`]: std::str::FromStr
+//! [`name!`]: crate::name
 //! [_punycode_]: <https://datatracker.ietf.org/doc/html/rfc3492>
 
-pub use self::absolute::{Name, NameError};
+pub use self::absolute::{ClasslessError, Name, NameError, SuffixIter};
 pub use self::builder::{
     FromStrError, NameBuilder, PresentationError, PushError, PushNameError,
 };
 pub use self::chain::{Chain, ChainIter, LongChainError, UncertainChainIter};
+#[cfg(feature = "std")]
+pub use self::idna::IdnaError;
+#[cfg(all(feature = "bytes", feature = "std"))]
+pub use self::interner::{InternedName, NameInterner};
 pub use self::label::{
     Label, LabelTypeError, LongLabelError, OwnedLabel, SliceLabelsIter,
     SplitLabelError,
 };
+#[doc(hidden)]
+pub use self::macros::{
+    name_to_wire as __name_to_wire, truncate_wire as __truncate_wire,
+};
 pub use self::parsed::{ParsedName, ParsedNameIter, ParsedSuffixIter};
 pub use self::relative::{
     NameIter, RelativeFromStrError, RelativeName, RelativeNameError,
-    StripSuffixError,
+    RelativeSuffixIter, StripSuffixError,
 };
 pub use self::traits::{FlattenInto, ToLabelIter, ToName, ToRelativeName};
 pub use self::uncertain::UncertainName;
@@ -111,7 +127,12 @@ pub use self::uncertain::UncertainName;
 mod absolute;
 mod builder;
 mod chain;
+#[cfg(feature = "std")]
+mod idna;
+#[cfg(all(feature = "bytes", feature = "std"))]
+mod interner;
 mod label;
+mod macros;
 mod parsed;
 mod relative;
 mod traits;
@@ -80,7 +80,7 @@ impl<Octs> ParsedName<Octs> {
     }
 
     /// Returns a correctly positioned parser.
-    fn parser(&self) -> Parser<Octs>
+    fn parser(&self) -> Parser<'_, Octs>
     where
         Octs: AsRef<[u8]>,
     {
@@ -116,7 +116,7 @@ impl<'a, Octs: Octets + ?Sized> ParsedName<&'a Octs> {
 ///
 impl<Octs: AsRef<[u8]>> ParsedName<Octs> {
     /// Returns an iterator over the labels of the name.
-    pub fn iter(&self) -> ParsedNameIter {
+    pub fn iter(&self) -> ParsedNameIter<'_> {
         ParsedNameIter::new(self.octets.as_ref(), self.pos, self.name_len)
     }
 
@@ -125,7 +125,7 @@ impl<Octs: AsRef<[u8]>> ParsedName<Octs> {
     /// The returned iterator starts with the full name and then for each
     /// additional step returns a name with the left-most label stripped off
     /// until it reaches the root label.
-    pub fn iter_suffixes(&self) -> ParsedSuffixIter<Octs> {
+    pub fn iter_suffixes(&self) -> ParsedSuffixIter<'_, Octs> {
         ParsedSuffixIter::new(self)
     }
 
@@ -505,10 +505,11 @@ impl<Octs: AsRef<[u8]>> fmt::Display for ParsedName<Octs> {
     /// the trailing dot.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut iter = self.iter();
-        write!(f, "{}", iter.next().unwrap())?;
+        iter.next().unwrap().fmt(f)?;
         for label in iter {
             if !label.is_root() {
-                write!(f, ".{}", label)?
+                f.write_str(".")?;
+                label.fmt(f)?;
             }
         }
         Ok(())
@@ -523,13 +524,48 @@ impl<Octs: AsRef<[u8]>> fmt::Debug for ParsedName<Octs> {
 
 //--- Serialize
 
+/// Serializes the parsed name in the same way as [`Name`].
+///
+/// For human readable formats, the name is serialized in presentation
+/// format. Otherwise, its uncompressed wire format is used. In both cases,
+/// the result is wrapped in a newtype struct called `"Name"` so that it can
+/// be deserialized into a [`Name`].
 #[cfg(feature = "serde")]
 impl<Octs: AsRef<[u8]>> serde::Serialize for ParsedName<Octs> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.collect_str(self)
+        use octseq::serde::SerializeOctets;
+
+        if serializer.is_human_readable() {
+            if self.is_root() {
+                serializer.serialize_newtype_struct("Name", ".")
+            } else {
+                serializer.serialize_newtype_struct(
+                    "Name",
+                    &format_args!("{}", self),
+                )
+            }
+        } else if let Some(slice) = self.as_flat_slice() {
+            serializer.serialize_newtype_struct(
+                "Name",
+                &slice.as_serialized_octets(),
+            )
+        } else {
+            let mut buf = [0u8; 255];
+            let mut len = 0;
+            for label in self.iter() {
+                let label = label.as_slice();
+                buf[len] = label.len() as u8;
+                buf[len + 1..len + 1 + label.len()].copy_from_slice(label);
+                len += label.len() + 1;
+            }
+            serializer.serialize_newtype_struct(
+                "Name",
+                &buf[..len].as_serialized_octets(),
+            )
+        }
     }
 }
 
@@ -1046,7 +1082,7 @@ mod test {
             assert_eq!(parser.pos(), pos + len);
         }
 
-        fn p(slice: &[u8], pos: usize) -> Parser<[u8]> {
+        fn p(slice: &[u8], pos: usize) -> Parser<'_, [u8]> {
             let mut res = Parser::from_ref(slice);
             res.advance(pos).unwrap();
             res
@@ -1167,6 +1203,30 @@ mod test {
 
     // XXX TODO compose_canonical
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn ser() {
+        use serde_test::{assert_ser_tokens, Configure, Token};
+
+        for name in [name!(flat), name!(once), name!(twice)] {
+            assert_ser_tokens(
+                &name.compact(),
+                &[Token::NewtypeStruct { name: "Name" }, Token::Bytes(WECR)],
+            );
+            assert_ser_tokens(
+                &name.readable(),
+                &[
+                    Token::NewtypeStruct { name: "Name" },
+                    Token::Str("www.example.com"),
+                ],
+            );
+        }
+        assert_ser_tokens(
+            &name!(root).readable(),
+            &[Token::NewtypeStruct { name: "Name" }, Token::Str(".")],
+        );
+    }
+
     #[test]
     fn as_flat_slice() {
         assert_eq!(name!(root).as_flat_slice(), Some(b"\x00".as_ref()));
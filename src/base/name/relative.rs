@@ -352,10 +352,19 @@ impl<Octs: AsRef<[u8]> + ?Sized> RelativeName<Octs> {
 ///
 impl<Octs: AsRef<[u8]> + ?Sized> RelativeName<Octs> {
     /// Returns an iterator over the labels of the domain name.
-    pub fn iter(&self) -> NameIter {
+    pub fn iter(&self) -> NameIter<'_> {
         NameIter::new(self.0.as_ref())
     }
 
+    /// Returns an iterator over the suffixes of the name.
+    ///
+    /// The returned iterator starts with the full name and then for each
+    /// additional step returns a name with the left-most label stripped off
+    /// until it reaches the last label. The empty name is not included.
+    pub fn iter_suffixes(&self) -> RelativeSuffixIter<'_, Octs> {
+        RelativeSuffixIter::new(self)
+    }
+
     /// Returns the number of labels in the name.
     pub fn label_count(&self) -> usize {
         self.iter().count()
@@ -509,6 +518,28 @@ impl<Octs: AsRef<[u8]> + ?Sized> RelativeName<Octs> {
         }
     }
 
+    /// Splits the name into two after the given number of labels.
+    ///
+    /// Returns a pair of the name made up of the first `count` labels and
+    /// the name made up of the remaining labels. Either part may be empty.
+    ///
+    /// Returns `None` if `count` is greater than the label count.
+    #[allow(clippy::type_complexity)]
+    pub fn split_at_label(
+        &self,
+        count: usize,
+    ) -> Option<(RelativeName<Octs::Range<'_>>, RelativeName<Octs::Range<'_>>)>
+    where
+        Octs: Octets,
+    {
+        let mut mid = 0;
+        let mut labels = self.iter();
+        for _ in 0..count {
+            mid += usize::from(labels.next()?.compose_len());
+        }
+        Some(self.split(mid))
+    }
+
     /// Truncates the name to the given length.
     ///
     /// # Panics
@@ -948,6 +979,40 @@ impl DoubleEndedIterator for NameIter<'_> {
     }
 }
 
+//------------ RelativeSuffixIter --------------------------------------------
+
+/// An iterator over ever shorter suffixes of a relative domain name.
+#[derive(Clone)]
+pub struct RelativeSuffixIter<'a, Octs: ?Sized> {
+    name: &'a RelativeName<Octs>,
+    start: usize,
+}
+
+impl<'a, Octs: ?Sized> RelativeSuffixIter<'a, Octs> {
+    /// Creates a new iterator for `name`.
+    fn new(name: &'a RelativeName<Octs>) -> Self {
+        RelativeSuffixIter { name, start: 0 }
+    }
+}
+
+impl<'a, Octs: Octets + ?Sized> Iterator for RelativeSuffixIter<'a, Octs> {
+    type Item = RelativeName<Octs::Range<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let slice = self.name.as_slice();
+        if self.start >= slice.len() {
+            return None;
+        }
+        let res = unsafe {
+            RelativeName::from_octets_unchecked(
+                self.name.0.range(self.start..),
+            )
+        };
+        self.start += usize::from(slice[self.start]) + 1;
+        Some(res)
+    }
+}
+
 //============ Error Types ===================================================
 
 //------------ RelativeNameError --------------------------------------------
@@ -1626,6 +1691,43 @@ mod test {
         assert!(wec.parent().is_none());
     }
 
+    #[test]
+    fn iter_suffixes() {
+        assert!(RelativeName::empty_slice().iter_suffixes().next().is_none());
+        let wec =
+            RelativeName::from_octets(b"\x03www\x07example\x03com".as_ref())
+                .unwrap();
+        let mut iter = wec.iter_suffixes();
+        assert_eq!(
+            iter.next().unwrap().as_slice(),
+            b"\x03www\x07example\x03com"
+        );
+        assert_eq!(iter.next().unwrap().as_slice(), b"\x07example\x03com");
+        assert_eq!(iter.next().unwrap().as_slice(), b"\x03com");
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn split_at_label() {
+        let wec =
+            RelativeName::from_octets(b"\x03www\x07example\x03com".as_ref())
+                .unwrap();
+
+        let (left, right) = wec.split_at_label(0).unwrap();
+        assert_eq!(left.as_slice(), b"");
+        assert_eq!(right.as_slice(), b"\x03www\x07example\x03com");
+
+        let (left, right) = wec.split_at_label(1).unwrap();
+        assert_eq!(left.as_slice(), b"\x03www");
+        assert_eq!(right.as_slice(), b"\x07example\x03com");
+
+        let (left, right) = wec.split_at_label(3).unwrap();
+        assert_eq!(left.as_slice(), b"\x03www\x07example\x03com");
+        assert_eq!(right.as_slice(), b"");
+
+        assert!(wec.split_at_label(4).is_none());
+    }
+
     #[test]
     fn strip_suffix() {
         let wec =
@@ -78,6 +78,47 @@ pub trait ToLabelIter {
             }
         }
     }
+
+    /// Returns the labels of `self` that follow the prefix `base`.
+    ///
+    /// If `base` is a prefix of `self` as determined by
+    /// [`starts_with`][Self::starts_with], returns an iterator over the
+    /// remaining labels. Otherwise, returns `None`. Labels are compared
+    /// ignoring ASCII case.
+    fn labels_after_prefix<N: ToLabelIter + ?Sized>(
+        &self,
+        base: &N,
+    ) -> Option<Self::LabelIter<'_>> {
+        let mut self_iter = self.iter_labels();
+        for base_label in base.iter_labels() {
+            if self_iter.next()? != base_label {
+                return None;
+            }
+        }
+        Some(self_iter)
+    }
+
+    /// Returns the labels of `self` that precede the suffix `base`.
+    ///
+    /// If `base` is a suffix of `self` as determined by
+    /// [`ends_with`][Self::ends_with], returns an iterator over the
+    /// remaining labels. Otherwise, returns `None`. Labels are compared
+    /// ignoring ASCII case.
+    ///
+    /// This can be used to determine the part of a domain name below a
+    /// given apex.
+    fn labels_before_suffix<N: ToLabelIter + ?Sized>(
+        &self,
+        base: &N,
+    ) -> Option<Self::LabelIter<'_>> {
+        let mut self_iter = self.iter_labels();
+        for base_label in base.iter_labels().rev() {
+            if self_iter.next_back()? != base_label {
+                return None;
+            }
+        }
+        Some(self_iter)
+    }
 }
 
 impl<'r, N: ToLabelIter + ?Sized> ToLabelIter for &'r N {
@@ -216,7 +257,7 @@ pub trait ToName: ToLabelIter {
     /// [`as_flat_slice`]: ToName::as_flat_slice
     /// [`to_name`]: ToName::to_name
     #[cfg(feature = "std")]
-    fn to_cow(&self) -> Name<std::borrow::Cow<[u8]>> {
+    fn to_cow(&self) -> Name<std::borrow::Cow<'_, [u8]>> {
         let octets = self
             .as_flat_slice()
             .map(Cow::Borrowed)
@@ -369,9 +410,10 @@ where
         if first.is_root() {
             f.write_str(".")
         } else {
-            write!(f, "{}", first)?;
+            first.fmt(f)?;
             for label in labels {
-                write!(f, ".{}", label)?
+                f.write_str(".")?;
+                label.fmt(f)?;
             }
             Ok(())
         }
@@ -502,7 +544,7 @@ pub trait ToRelativeName: ToLabelIter {
     /// [`as_flat_slice`]: ToRelativeName::as_flat_slice
     /// [`to_relative_name`]: ToRelativeName::to_relative_name
     #[cfg(feature = "std")]
-    fn to_cow(&self) -> RelativeName<std::borrow::Cow<[u8]>> {
+    fn to_cow(&self) -> RelativeName<std::borrow::Cow<'_, [u8]>> {
         let octets = self
             .as_flat_slice()
             .map(Cow::Borrowed)
@@ -614,3 +656,69 @@ pub trait FlattenInto<Target>: Sized {
         infallible(self.try_flatten_into())
     }
 }
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    use super::*;
+    use crate::base::name::ParsedName;
+    use octseq::parse::Parser;
+    use std::vec::Vec;
+
+    fn labels<'a>(iter: impl Iterator<Item = &'a Label>) -> Vec<&'a [u8]> {
+        iter.map(Label::as_slice).collect()
+    }
+
+    #[test]
+    fn labels_after_prefix() {
+        let name = Name::from_slice(b"\x03www\x07example\x03com\0").unwrap();
+        let www = RelativeName::from_slice(b"\x03WWW").unwrap();
+        let ex = RelativeName::from_slice(b"\x07example").unwrap();
+
+        assert_eq!(
+            labels(name.labels_after_prefix(www).unwrap()),
+            [b"example".as_ref(), b"com", b""]
+        );
+        assert_eq!(
+            labels(
+                name.labels_after_prefix(RelativeName::empty_slice())
+                    .unwrap()
+            ),
+            [b"www".as_ref(), b"example", b"com", b""]
+        );
+        assert_eq!(
+            labels(name.labels_after_prefix(&name).unwrap()),
+            Vec::<&[u8]>::new()
+        );
+        assert!(name.labels_after_prefix(ex).is_none());
+        assert!(www.labels_after_prefix(&www.chain(ex).unwrap()).is_none());
+    }
+
+    #[test]
+    fn labels_before_suffix() {
+        // Use a compressed name to make sure this works for all name types.
+        let msg = b"\x07example\x03com\0\x03www\xc0\x00";
+        let mut parser = Parser::from_ref(msg.as_ref());
+        parser.advance(13).unwrap();
+        let name = ParsedName::parse(&mut parser).unwrap();
+        let apex = Name::from_slice(b"\x07EXAMPLE\x03com\0").unwrap();
+        let other = Name::from_slice(b"\x07example\x03net\0").unwrap();
+
+        assert_eq!(
+            labels(name.labels_before_suffix(apex).unwrap()),
+            [b"www".as_ref()]
+        );
+        assert_eq!(
+            labels(name.labels_before_suffix(Name::root_slice()).unwrap()),
+            [b"www".as_ref(), b"example", b"com"]
+        );
+        assert_eq!(
+            labels(name.labels_before_suffix(&name).unwrap()),
+            Vec::<&[u8]>::new()
+        );
+        assert!(name.labels_before_suffix(other).is_none());
+        assert!(apex.labels_before_suffix(&name).is_none());
+    }
+}
@@ -416,7 +416,8 @@ impl<Octets: AsRef<[u8]>> fmt::Display for UncertainName<Octets> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             UncertainName::Absolute(ref name) => {
-                write!(f, "{}.", name)
+                name.fmt(f)?;
+                f.write_str(".")
             }
             UncertainName::Relative(ref name) => name.fmt(f),
         }
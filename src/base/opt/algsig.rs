@@ -5,16 +5,18 @@
 //! options are defined in [RFC 6975].
 //!
 //! There are three options for three different purposes. However, the data
-//! for each of them is a sequence of security algorithms. The module only
+//! for each of them is a sequence of algorithm numbers. The module only
 //! defines one type [`Understood<Variant, Octs>`] which carries
 //! the specific variant as its first type parameter. Marker types and
 //! type aliases are defined for the three options [Dau], [Dhu], and [N3u]
 //! which specific the DNSSEC signature algorithms, DS hash algorithm, and
-//! NSEC3 hash algorithms understood by the client, respectively.
+//! NSEC3 hash algorithms understood by the client, respectively. The
+//! [`UnderstoodVariant`] trait implemented by the marker types provides the
+//! type of the algorithms for each option.
 //!
 //! [RFC 6975]: https://tools.ietf.org/html/rfc6975
 
-use super::super::iana::{OptionCode, SecAlg};
+use super::super::iana::{DigestAlg, Nsec3HashAlg, OptionCode, SecAlg};
 use super::super::message_builder::OptBuilder;
 use super::super::wire::{Compose, Composer, ParseError};
 use super::{
@@ -34,15 +36,16 @@ use core::marker::PhantomData;
 /// This type provides the option data for the three options DAU, DHU, and
 /// N3U which allow a client to specify the cryptographic algorithms it
 /// supports for DNSSEC signatures, DS hashes, and NSEC3 hashes respectively.
-/// Each of them contains a sequence of [`SecAlg`] values in wire format.
+/// Each of them contains a sequence of algorithm numbers in wire format,
+/// [`SecAlg`] for DAU, [`DigestAlg`] for DHU, and [`Nsec3HashAlg`] for N3U.
 ///
 /// Which exact option is to be used is specified via the `Variant` type
 /// argument. Three marker types `DauVariant`, `DhuVariant` and `N3uVariant`
 /// are defined with accompanying type aliases [`Dau`], [`Dhu`], and [`N3u`].
 ///
 /// You can create a new value from anything that can be turned into an
-/// iterator over [`SecAlg`] via the
-/// [`from_sec_algs`][Understood::from_sec_algs] associated function.
+/// iterator over the variant’s algorithm type via the
+/// [`from_algs`][Understood::from_algs] associated function.
 /// Once you have a value, you can iterate over the algorithms via the
 /// [`iter`][Understood::iter] method or use the [`IntoIterator`] implementation
 /// for a reference.
@@ -79,6 +82,58 @@ pub struct DhuVariant;
 #[derive(Clone, Copy, Debug)]
 pub struct N3uVariant;
 
+/// A type for the variant of an [`Understood`] option.
+///
+/// The trait provides the type of algorithm listed in the option. It is
+/// implemented by the marker types [`DauVariant`], [`DhuVariant`], and
+/// [`N3uVariant`].
+pub trait UnderstoodVariant {
+    /// The type of the algorithms listed in the option.
+    type Alg: Copy;
+
+    /// Converts an algorithm value into its number.
+    fn alg_to_int(alg: Self::Alg) -> u8;
+
+    /// Converts an algorithm number into its value.
+    fn alg_from_int(value: u8) -> Self::Alg;
+}
+
+impl UnderstoodVariant for DauVariant {
+    type Alg = SecAlg;
+
+    fn alg_to_int(alg: Self::Alg) -> u8 {
+        alg.to_int()
+    }
+
+    fn alg_from_int(value: u8) -> Self::Alg {
+        SecAlg::from_int(value)
+    }
+}
+
+impl UnderstoodVariant for DhuVariant {
+    type Alg = DigestAlg;
+
+    fn alg_to_int(alg: Self::Alg) -> u8 {
+        alg.to_int()
+    }
+
+    fn alg_from_int(value: u8) -> Self::Alg {
+        DigestAlg::from_int(value)
+    }
+}
+
+impl UnderstoodVariant for N3uVariant {
+    type Alg = Nsec3HashAlg;
+
+    fn alg_to_int(alg: Self::Alg) -> u8 {
+        alg.to_int()
+    }
+
+    fn alg_from_int(value: u8) -> Self::Alg {
+        Nsec3HashAlg::from_int(value)
+    }
+}
+
 /// A type alias for the DAU option.
 pub type Dau<Octs> = Understood<DauVariant, Octs>;
 
@@ -105,8 +160,8 @@ impl<Variant, Octs> Understood<Variant, Octs> {
     ///
     /// # Safety
     ///
-    /// The caller needs to make sure that the slice contains a sequence of
-    /// 16 bit values that is no longer than 65,535 octets.
+    /// The caller needs to make sure that the slice is no longer than
+    /// 65,535 octets.
     pub unsafe fn from_octets_unchecked(octets: Octs) -> Self {
         Understood {
             marker: PhantomData,
@@ -116,18 +171,19 @@ impl<Variant, Octs> Understood<Variant, Octs> {
 
     /// Creates a new value from a sequence of algorithms.
     ///
-    /// The operation will fail if the iterator returns more than 32,767
+    /// The operation will fail if the iterator returns more than 65,535
     /// algorithms.
-    pub fn from_sec_algs(
-        sec_algs: impl IntoIterator<Item = SecAlg>
+    pub fn from_algs(
+        algs: impl IntoIterator<Item = Variant::Alg>
     ) -> Result<Self, BuildDataError>
     where
+        Variant: UnderstoodVariant,
         Octs: FromBuilder,
         <Octs as FromBuilder>::Builder: EmptyBuilder
     {
         let mut octets = EmptyBuilder::empty();
-        for item in sec_algs {
-            item.compose(&mut octets)?;
+        for item in algs {
+            Variant::alg_to_int(item).compose(&mut octets)?;
         }
         let octets = Octs::from_builder(octets);
         LongOptData::check_len(octets.as_ref().len())?;
@@ -135,6 +191,21 @@ impl<Variant, Octs> Understood<Variant, Octs> {
     }
 }
 
+impl<Octs> Understood<DauVariant, Octs> {
+    /// Creates a new DAU value from a sequence of security algorithms.
+    ///
+    /// This is the same as [`from_algs`][Self::from_algs].
+    pub fn from_sec_algs(
+        sec_algs: impl IntoIterator<Item = SecAlg>
+    ) -> Result<Self, BuildDataError>
+    where
+        Octs: FromBuilder,
+        <Octs as FromBuilder>::Builder: EmptyBuilder
+    {
+        Self::from_algs(sec_algs)
+    }
+}
+
 impl<Variant> Understood<Variant, [u8]> {
     /// Creates a new value from an octets slice.
     ///
@@ -149,8 +220,8 @@ impl<Variant> Understood<Variant, [u8]> {
     ///
     /// # Safety
     ///
-    /// The caller needs to make sure that the slice contains a sequence of
-    /// 16 bit values that is no longer than 65,535 octets.
+    /// The caller needs to make sure that the slice is no longer than
+    /// 65,535 octets.
     #[must_use]
     pub unsafe fn from_slice_unchecked(slice: &[u8]) -> &Self {
         // SAFETY: Understood has repr(transparent)
@@ -158,11 +229,11 @@ impl<Variant> Understood<Variant, [u8]> {
     }
 
     /// Checks that a slice contains a correctly encoded value.
+    ///
+    /// Since each algorithm is a single octet, any slice is fine as long as
+    /// it isn’t too long.
     fn check_slice(slice: &[u8]) -> Result<(), ParseError> {
         LongOptData::check_len(slice.len())?;
-        if slice.len() % usize::from(u16::COMPOSE_LEN) != 0 {
-            return Err(ParseError::form_error("invalid understood data"))
-        }
         Ok(())
     }
 }
@@ -211,11 +282,12 @@ impl<Variant, Octs: ?Sized> Understood<Variant, Octs> {
     }
 
     /// Returns an iterator over the algorithms in the data.
-    pub fn iter(&self) -> SecAlgsIter
+    pub fn iter(&self) -> AlgsIter<'_, Variant>
     where
+        Variant: UnderstoodVariant,
         Octs: AsRef<[u8]>,
     {
-        SecAlgsIter::new(self.octets.as_ref())
+        AlgsIter::new(self.octets.as_ref())
     }
 }
 
@@ -375,10 +447,11 @@ where
 
 impl<'a, Variant, Octs> IntoIterator for &'a Understood<Variant, Octs>
 where
+    Variant: UnderstoodVariant,
     Octs: AsRef<[u8]> + ?Sized
 {
-    type Item = SecAlg;
-    type IntoIter = SecAlgsIter<'a>;
+    type Item = Variant::Alg;
+    type IntoIter = AlgsIter<'a, Variant>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
@@ -435,7 +508,12 @@ impl<Octs: AsRef<[u8]> + ?Sized> fmt::Debug for Understood<N3uVariant, Octs> {
 //--- Serialize
 
 #[cfg(feature = "serde")]
-impl<V, Octs: AsRef<[u8]>> serde::Serialize for Understood<V, Octs> {
+impl<V, Octs> serde::Serialize for Understood<V, Octs>
+where
+    V: UnderstoodVariant,
+    V::Alg: serde::Serialize,
+    Octs: AsRef<[u8]>,
+{
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer {
@@ -482,67 +560,67 @@ impl<Target: Composer> OptBuilder<'_, Target> {
     pub fn dau(
         &mut self, algs: &impl AsRef<[SecAlg]>,
     ) -> Result<(), BuildDataError> {
-        Ok(self.push_raw_option(
-            OptionCode::DAU,
-            u16::try_from(
-                algs.as_ref().len() * usize::from(SecAlg::COMPOSE_LEN)
-            ).map_err(|_| BuildDataError::LongOptData)?,
-            |octs| {
-                algs.as_ref().iter().try_for_each(|item| item.compose(octs))
-            },
-        )?)
+        self.push_understood::<DauVariant>(OptionCode::DAU, algs.as_ref())
     }
 
     /// Appends a DHU option.
     ///
     /// The DHU option lists the DS hash algorithms the requester supports.
     pub fn dhu(
-        &mut self, algs: &impl AsRef<[SecAlg]>,
+        &mut self, algs: &impl AsRef<[DigestAlg]>,
     ) -> Result<(), BuildDataError> {
-        Ok(self.push_raw_option(
-            OptionCode::DHU,
-            u16::try_from(
-                algs.as_ref().len() * usize::from(SecAlg::COMPOSE_LEN)
-            ).map_err(|_| BuildDataError::LongOptData)?,
-            |octs| {
-                algs.as_ref().iter().try_for_each(|item| item.compose(octs))
-            },
-        )?)
+        self.push_understood::<DhuVariant>(OptionCode::DHU, algs.as_ref())
     }
 
     /// Appends a N3U option.
     ///
     /// The N3U option lists the NSEC3 hash algorithms the requester supports.
     pub fn n3u(
-        &mut self, algs: &impl AsRef<[SecAlg]>,
+        &mut self, algs: &impl AsRef<[Nsec3HashAlg]>,
+    ) -> Result<(), BuildDataError> {
+        self.push_understood::<N3uVariant>(OptionCode::N3U, algs.as_ref())
+    }
+
+    /// Appends one of the three understood options.
+    fn push_understood<Variant: UnderstoodVariant>(
+        &mut self, code: OptionCode, algs: &[Variant::Alg],
     ) -> Result<(), BuildDataError> {
         Ok(self.push_raw_option(
-            OptionCode::N3U,
+            code,
             u16::try_from(
-                algs.as_ref().len() * usize::from(SecAlg::COMPOSE_LEN)
+                algs.len()
             ).map_err(|_| BuildDataError::LongOptData)?,
             |octs| {
-                algs.as_ref().iter().try_for_each(|item| item.compose(octs))
+                algs.iter().try_for_each(|item| {
+                    Variant::alg_to_int(*item).compose(octs)
+                })
             },
         )?)
     }
 }
 
-//------------ SecAlgsIter ---------------------------------------------------
+//------------ AlgsIter ------------------------------------------------------
+
+/// An iterator over the algorithms of an [`Understood`] option.
+pub struct AlgsIter<'a, Variant> {
+    /// The remaining algorithm numbers.
+    iter: slice::Iter<'a, u8>,
 
-pub struct SecAlgsIter<'a>(slice::Iter<'a, u8>);
+    /// A marker for the variant.
+    marker: PhantomData<Variant>,
+}
 
-impl<'a> SecAlgsIter<'a> {
+impl<'a, Variant> AlgsIter<'a, Variant> {
     fn new(slice: &'a [u8]) -> Self {
-        SecAlgsIter(slice.iter())
+        AlgsIter { iter: slice.iter(), marker: PhantomData }
     }
 }
 
-impl Iterator for SecAlgsIter<'_> {
-    type Item = SecAlg;
+impl<Variant: UnderstoodVariant> Iterator for AlgsIter<'_, Variant> {
+    type Item = Variant::Alg;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next().map(|x| SecAlg::from_int(*x))
+        self.iter.next().map(|x| Variant::alg_from_int(*x))
     }
 }
 
@@ -553,6 +631,7 @@ impl Iterator for SecAlgsIter<'_> {
 mod test {
     use super::*;
     use super::super::test::test_option_compose_parse;
+    use std::vec::Vec;
 
     #[test]
     #[allow(clippy::redundant_closure)] // lifetimes ...
@@ -562,4 +641,53 @@ mod test {
             |parser| Dau::parse(parser)
         );
     }
+
+    #[test]
+    fn algs() {
+        let dau = Dau::<Vec<u8>>::from_sec_algs([
+            SecAlg::RSASHA256, SecAlg::ECDSAP256SHA256, SecAlg::ED25519
+        ]).unwrap();
+        assert_eq!(dau.as_slice(), b"\x08\x0d\x0f");
+        assert_eq!(
+            dau.iter().collect::<Vec<_>>(),
+            [SecAlg::RSASHA256, SecAlg::ECDSAP256SHA256, SecAlg::ED25519]
+        );
+
+        let dhu = Dhu::from_octets(b"\x02\x04".as_ref()).unwrap();
+        assert_eq!(
+            dhu.iter().collect::<Vec<_>>(),
+            [DigestAlg::SHA256, DigestAlg::SHA384]
+        );
+
+        let n3u = N3u::<Vec<u8>>::from_algs([Nsec3HashAlg::SHA1]).unwrap();
+        assert_eq!(n3u.iter().collect::<Vec<_>>(), [Nsec3HashAlg::SHA1]);
+    }
+
+    #[test]
+    fn opt_builder() {
+        use crate::base::message_builder::MessageBuilder;
+        use crate::base::message::Message;
+
+        let mut msg = MessageBuilder::new_vec().additional();
+        msg.opt(|opt| {
+            opt.dau(&[SecAlg::ED25519]).unwrap();
+            opt.dhu(&[DigestAlg::SHA256]).unwrap();
+            opt.n3u(&[Nsec3HashAlg::SHA1]).unwrap();
+            Ok(())
+        }).unwrap();
+        let msg = Message::from_octets(msg.finish()).unwrap();
+        let opt = msg.opt().unwrap();
+        let opt = opt.opt();
+        assert_eq!(
+            opt.dau().unwrap().iter().collect::<Vec<_>>(), [SecAlg::ED25519]
+        );
+        assert_eq!(
+            opt.dhu().unwrap().iter().collect::<Vec<_>>(),
+            [DigestAlg::SHA256]
+        );
+        assert_eq!(
+            opt.n3u().unwrap().iter().collect::<Vec<_>>(),
+            [Nsec3HashAlg::SHA1]
+        );
+    }
 }
@@ -654,6 +654,95 @@ impl fmt::Display for StandardServerCookie {
 }
 
 
+//------------ CookieStore ---------------------------------------------------
+
+/// The cookie state of a client.
+///
+/// A client needs to use a different client cookie for every server it
+/// talks to and has to remember the server cookie it received from each of
+/// them. This type keeps track of both, keyed by the server’s IP address.
+///
+/// Before sending a request to a server, get the cookie to include from
+/// [`request_cookie`][Self::request_cookie]. When a response arrives, hand
+/// its cookie to [`update`][Self::update]. This checks that the response
+/// carries the client cookie sent to the server and learns the server
+/// cookie. If the server responded with a BADCOOKIE response code, the
+/// request can simply be repeated with a new cookie from
+/// [`request_cookie`][Self::request_cookie] which will now contain the
+/// server cookie.
+///
+/// If the client’s own IP address changes, all state should be dropped via
+/// [`clear`][Self::clear] as described in section 5.4 of [RFC 9018].
+///
+/// [RFC 9018]: https://tools.ietf.org/html/rfc9018
+#[cfg(all(feature = "std", feature = "rand"))]
+#[derive(Clone, Debug, Default)]
+pub struct CookieStore {
+    /// The cookies for each server.
+    servers: std::collections::HashMap<crate::base::net::IpAddr, Cookie>,
+}
+
+#[cfg(all(feature = "std", feature = "rand"))]
+impl CookieStore {
+    /// Creates a new, empty cookie store.
+    #[must_use]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns the cookie to include in a request to the given server.
+    ///
+    /// If there is no cookie for the server yet, creates a new random
+    /// client cookie for it.
+    pub fn request_cookie(
+        &mut self, server: crate::base::net::IpAddr
+    ) -> Cookie {
+        self.servers.entry(server).or_insert_with(
+            Cookie::create_initial
+        ).clone()
+    }
+
+    /// Processes the cookie received in a response from the given server.
+    ///
+    /// Returns `false` if the client cookie of `cookie` is not the one sent
+    /// to the server. In this case, the response must be discarded as
+    /// described in section 5.3 of [RFC 7873]. Otherwise, remembers the
+    /// server cookie, if there is one, for future requests and returns
+    /// `true`.
+    ///
+    /// [RFC 7873]: https://tools.ietf.org/html/rfc7873
+    pub fn update(
+        &mut self, server: crate::base::net::IpAddr, cookie: &Cookie
+    ) -> bool {
+        match self.servers.get_mut(&server) {
+            Some(known) if known.client() == cookie.client() => {
+                if cookie.server().is_some() {
+                    known.server = cookie.server.clone();
+                }
+                true
+            }
+            _ => false
+        }
+    }
+
+    /// Returns the cookie currently known for the given server.
+    #[must_use]
+    pub fn get(&self, server: crate::base::net::IpAddr) -> Option<&Cookie> {
+        self.servers.get(&server)
+    }
+
+    /// Forgets all state for the given server.
+    pub fn remove(&mut self, server: crate::base::net::IpAddr) {
+        self.servers.remove(&server);
+    }
+
+    /// Forgets all state.
+    pub fn clear(&mut self) {
+        self.servers.clear()
+    }
+}
+
+
 //============ Tests =========================================================
 
 #[cfg(test)]
@@ -805,5 +894,42 @@ mod test {
             );
         }
     }
-}
 
+    #[test]
+    #[cfg(all(feature = "std", feature = "rand"))]
+    fn cookie_store() {
+        use crate::base::net::{IpAddr, Ipv4Addr};
+
+        const SERVER_1: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+        const SERVER_2: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 2));
+
+        let mut store = CookieStore::new();
+        let first = store.request_cookie(SERVER_1);
+        assert!(first.server().is_none());
+        assert_eq!(store.request_cookie(SERVER_1), first);
+        assert_ne!(
+            store.request_cookie(SERVER_2).client(), first.client()
+        );
+
+        let server = ServerCookie::from_octets(b"01234567");
+        let response = Cookie::new(first.client(), Some(server.clone()));
+        assert!(store.update(SERVER_1, &response));
+        assert_eq!(store.request_cookie(SERVER_1), response);
+
+        // A response without a server cookie keeps the old one.
+        assert!(store.update(SERVER_1, &Cookie::new(first.client(), None)));
+        assert_eq!(store.get(SERVER_1), Some(&response));
+
+        // Wrong client cookies are rejected.
+        let spoofed = Cookie::new(
+            store.request_cookie(SERVER_2).client(), Some(server)
+        );
+        assert!(!store.update(SERVER_1, &spoofed));
+        assert!(!store.update(
+            IpAddr::V4(Ipv4Addr::new(192, 0, 2, 3)), &spoofed
+        ));
+
+        store.remove(SERVER_1);
+        assert_ne!(store.request_cookie(SERVER_1).client(), first.client());
+    }
+}
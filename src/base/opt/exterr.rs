@@ -85,13 +85,18 @@ impl<Octs> ExtendedError<Octs> {
         Ok(unsafe { Self::new_unchecked(code, text.map(Ok)) })
     }
 
+    /// Creates a new value from a code and a text given as a string slice.
+    ///
+    /// Returns an error if `text` is too long to fit into an option.
     pub fn new_with_str(code: ExtendedErrorCode, text: &str) ->
-	Result<Self, LongOptData>
-	where Octs: AsRef<[u8]> + FromBuilder,
-	<Octs as FromBuilder>::Builder: EmptyBuilder,
-	<<Octs as FromBuilder>::Builder as OctetsBuilder>::AppendError: Into<Infallible>,
+        Result<Self, LongOptData>
+    where
+        Octs: AsRef<[u8]> + FromBuilder,
+        <Octs as FromBuilder>::Builder: EmptyBuilder,
+        <<Octs as FromBuilder>::Builder as OctetsBuilder>::AppendError:
+            Into<Infallible>,
     {
-	Self::new(code, Some(Str::copy_from_str(text)))
+        Self::new(code, Some(Str::copy_from_str(text)))
     }
 
     /// Creates a new value without checking for the option length.
@@ -160,6 +165,100 @@ impl<Octs> ExtendedError<Octs> {
     }
 }
 
+/// # Common Errors
+///
+/// The following functions create values for commonly used error codes
+/// without a text. A text can be added via [`set_text`][Self::set_text].
+impl<Octs> ExtendedError<Octs> {
+    /// Creates a value for an error not covered by any other code.
+    ///
+    /// Since this code doesn’t carry any information by itself, [RFC 8914]
+    /// asks for a text to be included, so this function requires one.
+    ///
+    /// [RFC 8914]: https://tools.ietf.org/html/rfc8914
+    pub fn other(text: &str) -> Result<Self, LongOptData>
+    where
+        Octs: AsRef<[u8]> + FromBuilder,
+        <Octs as FromBuilder>::Builder: EmptyBuilder,
+        <<Octs as FromBuilder>::Builder as OctetsBuilder>::AppendError:
+            Into<Infallible>,
+    {
+        Self::new_with_str(ExtendedErrorCode::OTHER, text)
+    }
+
+    /// Creates a value for an answer served from stale cached data.
+    #[must_use]
+    pub fn stale_answer() -> Self {
+        ExtendedErrorCode::STALE_ANSWER.into()
+    }
+
+    /// Creates a value for a stale cached NXDOMAIN answer.
+    #[must_use]
+    pub fn stale_nxdomain_answer() -> Self {
+        ExtendedErrorCode::STALE_NXDOMAIN_ANSWER.into()
+    }
+
+    /// Creates a value for a failed DNSSEC validation.
+    #[must_use]
+    pub fn dnssec_bogus() -> Self {
+        ExtendedErrorCode::DNSSEC_BOGUS.into()
+    }
+
+    /// Creates a value for a DNSSEC validation that couldn’t be completed.
+    #[must_use]
+    pub fn dnssec_indeterminate() -> Self {
+        ExtendedErrorCode::DNSSEC_INDETERMINATE.into()
+    }
+
+    /// Creates a value for a query blocked by the server’s operator.
+    #[must_use]
+    pub fn blocked() -> Self {
+        ExtendedErrorCode::BLOCKED.into()
+    }
+
+    /// Creates a value for a query blocked due to an external requirement.
+    #[must_use]
+    pub fn censored() -> Self {
+        ExtendedErrorCode::CENSORED.into()
+    }
+
+    /// Creates a value for a query blocked at the client’s request.
+    #[must_use]
+    pub fn filtered() -> Self {
+        ExtendedErrorCode::FILTERED.into()
+    }
+
+    /// Creates a value for a query refused because of the client.
+    #[must_use]
+    pub fn prohibited() -> Self {
+        ExtendedErrorCode::PROHIBITED.into()
+    }
+
+    /// Creates a value for a server that isn’t able to answer yet.
+    #[must_use]
+    pub fn not_ready() -> Self {
+        ExtendedErrorCode::NOT_READY.into()
+    }
+
+    /// Creates a value for a server not authoritative for the query.
+    #[must_use]
+    pub fn not_authoritative() -> Self {
+        ExtendedErrorCode::NOT_AUTHORITATIVE.into()
+    }
+
+    /// Creates a value for a resolver that couldn’t reach any server.
+    #[must_use]
+    pub fn no_reachable_authority() -> Self {
+        ExtendedErrorCode::NO_REACHABLE_AUTHORITY.into()
+    }
+
+    /// Creates a value for a failure to talk to another server.
+    #[must_use]
+    pub fn network_error() -> Self {
+        ExtendedErrorCode::NETWORK_ERROR.into()
+    }
+}
+
 //--- From and TryFrom
 
 impl<Octs> From<ExtendedErrorCode> for ExtendedError<Octs> {
@@ -427,6 +526,26 @@ mod tests {
         assert!(ede.is_private());
     }
 
+    #[test]
+    fn common_errors() {
+        use std::vec::Vec;
+
+        let ede = ExtendedError::<Vec<u8>>::dnssec_bogus();
+        assert_eq!(ede.code(), ExtendedErrorCode::DNSSEC_BOGUS);
+        assert!(ede.text().is_none());
+        assert_eq!(
+            ExtendedError::<Vec<u8>>::stale_answer().code(),
+            ExtendedErrorCode::STALE_ANSWER
+        );
+
+        let ede = ExtendedError::<Vec<u8>>::other("oops").unwrap();
+        assert_eq!(ede.code(), ExtendedErrorCode::OTHER);
+        assert_eq!(ede.text_slice(), Some(b"oops".as_ref()));
+        assert!(
+            ExtendedError::<Vec<u8>>::other(&"x".repeat(65534)).is_err()
+        );
+    }
+
     #[test]
     fn display_lossy_octets() {
         use std::string::ToString;
@@ -6,7 +6,7 @@
 //!
 //! Note that his has nothing to do with the keepalive feature of TCP itself.
 //!
-//! This option is defined in [RFC 7829](https://tools.ietf.org/html/rfc7828).
+//! This option is defined in [RFC 7828](https://tools.ietf.org/html/rfc7828).
 
 use core::fmt;
 use core::time::Duration;
@@ -43,12 +43,42 @@ impl TcpKeepalive {
         TcpKeepalive(timeout)
     }
 
+    /// Creates the value a client includes in a query.
+    ///
+    /// Clients signal support for the option by including it without an
+    /// idle timeout in queries sent over TCP.
+    #[must_use]
+    pub fn request() -> Self {
+        TcpKeepalive(None)
+    }
+
+    /// Creates a value for a response from an idle timeout duration.
+    ///
+    /// Durations longer than allowed by the option are capped at the
+    /// maximum of 6553.5 seconds.
+    #[must_use]
+    pub fn response(timeout: Duration) -> Self {
+        TcpKeepalive(Some(
+            IdleTimeout::try_from(timeout).unwrap_or(IdleTimeout::MAX)
+        ))
+    }
+
     /// Returns the idle timeout.
     #[must_use]
     pub fn timeout(self) -> Option<IdleTimeout> {
         self.0
     }
 
+    /// Returns the idle timeout as a duration.
+    ///
+    /// Returns `None` if the option doesn’t carry a timeout. A timeout of
+    /// zero, which asks the client to close the connection, is returned as
+    /// `Some(Duration::ZERO)`.
+    #[must_use]
+    pub fn timeout_duration(self) -> Option<Duration> {
+        self.0.map(Into::into)
+    }
+
     /// Parses an option data value from its wire format.
     pub fn parse<Octs: AsRef<[u8]>>(
         parser: &mut Parser<Octs>
@@ -132,6 +162,10 @@ impl<Octs: Octets> Opt<Octs> {
 }
 
 impl<Target: Composer> OptBuilder<'_, Target> {
+    /// Appends an edns-tcp-keepalive option.
+    ///
+    /// Clients include the option without a timeout in their queries,
+    /// servers include the timeout they want to use in responses.
     pub fn tcp_keepalive(
         &mut self, timeout: Option<IdleTimeout>
     ) -> Result<(), Target::AppendError> {
@@ -152,6 +186,9 @@ impl<Target: Composer> OptBuilder<'_, Target> {
 pub struct IdleTimeout(u16);
 
 impl IdleTimeout {
+    /// The largest possible idle timeout of 6553.5 seconds.
+    pub const MAX: Self = IdleTimeout(u16::MAX);
+
     /// The length in octets of the wire format.
     const COMPOSE_LEN: u16 = 2;
 
@@ -255,5 +292,22 @@ mod test {
             |parser| TcpKeepalive::parse(parser)
         );
     }
+
+    #[test]
+    fn tcp_keepalive_duration() {
+        assert_eq!(TcpKeepalive::request().timeout_duration(), None);
+        assert_eq!(
+            TcpKeepalive::response(Duration::from_millis(1250)).timeout(),
+            Some(12.into())
+        );
+        assert_eq!(
+            TcpKeepalive::response(Duration::from_secs(7200)).timeout(),
+            Some(IdleTimeout::MAX)
+        );
+        assert_eq!(
+            TcpKeepalive::new(Some(0.into())).timeout_duration(),
+            Some(Duration::ZERO)
+        );
+    }
 }
 
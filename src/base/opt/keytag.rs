@@ -150,7 +150,7 @@ impl<Octs: ?Sized> KeyTag<Octs> {
     }
 
     /// Returns an iterator over the individual key tags.
-    pub fn iter(&self) -> KeyTagIter
+    pub fn iter(&self) -> KeyTagIter<'_>
     where Octs: AsRef<[u8]> {
         KeyTagIter(self.octets.as_ref())
     }
@@ -10,6 +10,7 @@
 use super::super::iana::OptionCode;
 use super::super::message_builder::OptBuilder;
 use super::super::wire::{Composer, ParseError};
+use crate::utils::base16;
 use super::{
     BuildDataError, LongOptData, Opt, OptData, ComposeOptData, ParseOptData
 };
@@ -143,6 +144,47 @@ impl<Octs: ?Sized> Nsid<Octs> {
     {
         unsafe { Nsid::from_slice_unchecked(self.octets.as_ref()) }
     }
+
+    /// Returns an object that displays the identifier in hexadecimal.
+    ///
+    /// Each octet is shown as two upper case hexadecimal digits without
+    /// any separators. This is the format [RFC 5001] requires user
+    /// interfaces to use.
+    ///
+    /// [RFC 5001]: https://tools.ietf.org/html/rfc5001
+    pub fn display_hex(&self) -> impl fmt::Display + '_
+    where
+        Octs: AsRef<[u8]>
+    {
+        base16::encode_display(self.as_slice())
+    }
+
+    /// Returns an object that displays the identifier as ASCII text.
+    ///
+    /// Many servers use printable text as their identifier. Octets that
+    /// are not printable ASCII characters are shown as a dot.
+    pub fn display_ascii(&self) -> impl fmt::Display + '_
+    where
+        Octs: AsRef<[u8]>
+    {
+        struct Display<'a>(&'a [u8]);
+
+        impl fmt::Display for Display<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                for &ch in self.0 {
+                    if ch.is_ascii_graphic() || ch == b' ' {
+                        fmt::Write::write_char(f, ch.into())?
+                    }
+                    else {
+                        f.write_str(".")?
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        Display(self.as_slice())
+    }
 }
 
 //--- OctetsFrom
@@ -215,7 +257,7 @@ impl<Octs: AsRef<[u8]> + ?Sized> fmt::Display for Nsid<Octs> {
         // | option as a sequence of hexadecimal digits, two digits per
         // | payload octet.
         for v in self.octets.as_ref() {
-            write!(f, "{:X} ", *v)?;
+            write!(f, "{:02X} ", *v)?;
         }
         if let Ok(s) = str::from_utf8(self.octets.as_ref()) {
             write!(f, "({})", s)?;
@@ -326,4 +368,15 @@ mod test {
             |parser| Nsid::parse(parser)
         );
     }
+
+    #[test]
+    fn nsid_display() {
+        use std::string::ToString;
+
+        let nsid = Nsid::from_octets(b"ns1\x0a\xff").unwrap();
+        assert_eq!(nsid.display_hex().to_string(), "6E73310AFF");
+        assert_eq!(nsid.display_ascii().to_string(), "ns1..");
+        assert_eq!(nsid.to_string(), "6E 73 31 0A FF ");
+        assert_eq!(Nsid::empty().display_hex().to_string(), "");
+    }
 }
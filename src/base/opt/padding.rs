@@ -7,6 +7,10 @@
 //! Since this option does not have any meaning for the receiver of a message,
 //! you should generally just use the [`OptBuilder::padding`] and
 //! [`OptBuilder::random_padding`] methods when constructing a message.
+//!
+//! The module also provides [`BlockPadding`], the padding policy
+//! recommended for encrypted transports, which can be applied via
+//! [`OptBuilder::block_padding`].
 
 use core::{borrow, fmt, str};
 use super::super::iana::OptionCode;
@@ -180,6 +184,7 @@ impl<Octs: AsRef<[u8]> + ?Sized> fmt::Debug for Padding<Octs> {
 //--- Extended OptBuilder
 
 impl<Target: Composer> OptBuilder<'_, Target> {
+    /// Appends a padding option with `len` zero octets.
     pub fn padding( &mut self, len: u16) -> Result<(), Target::AppendError> {
         self.push_raw_option(
             OptionCode::PADDING,
@@ -193,6 +198,7 @@ impl<Target: Composer> OptBuilder<'_, Target> {
         )
     }
 
+    /// Appends a padding option with `len` random octets.
     #[cfg(feature = "rand")]
     pub fn random_padding(
         &mut self, len: u16
@@ -208,6 +214,29 @@ impl<Target: Composer> OptBuilder<'_, Target> {
             }
         )
     }
+
+    /// Appends a padding option according to the given padding policy.
+    ///
+    /// The option is sized so that the complete message becomes a multiple
+    /// of the policy’s block size. If that would make the message longer
+    /// than the push limit of the message builder, the message is padded
+    /// to the limit instead. If not even an empty padding option fits, no
+    /// option is added.
+    ///
+    /// Since the length of the message is determined when this method is
+    /// called, it should be the last option added to the OPT record and the
+    /// OPT record should be the last record of the message, with the
+    /// exception of a TSIG record.
+    pub fn block_padding(
+        &mut self, policy: BlockPadding
+    ) -> Result<(), Target::AppendError> {
+        match policy.padding_len(
+            self.as_target().as_ref().len(), self.max_message_len()
+        ) {
+            Some(len) => self.padding(len),
+            None => Ok(())
+        }
+    }
 }
 
 
@@ -222,3 +251,110 @@ impl<Octs: AsRef<[u8]>> serde::Serialize for Padding<Octs> {
         self.octets.as_ref().serialize_octets(serializer)
     }
 }
+
+
+//------------ BlockPadding --------------------------------------------------
+
+/// The block-length padding policy.
+///
+/// With this policy, messages are padded to the nearest multiple of a fixed
+/// block size. It is the policy recommended by [RFC 8467] for
+/// encrypted transports, with a block size of 128 octets for queries and
+/// 468 octets for responses. These two values are available as the
+/// constants [`QUERY`][Self::QUERY] and [`RESPONSE`][Self::RESPONSE].
+///
+/// A policy is applied to a message via [`OptBuilder::block_padding`].
+///
+/// [RFC 8467]: https://tools.ietf.org/html/rfc8467
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BlockPadding {
+    /// The block size in octets.
+    block_size: u16,
+}
+
+impl BlockPadding {
+    /// The recommended policy for queries.
+    pub const QUERY: Self = BlockPadding { block_size: 128 };
+
+    /// The recommended policy for responses.
+    pub const RESPONSE: Self = BlockPadding { block_size: 468 };
+
+    /// Creates a new policy using the given block size.
+    ///
+    /// # Panics
+    ///
+    /// The function panics if `block_size` is zero.
+    #[must_use]
+    pub const fn new(block_size: u16) -> Self {
+        assert!(block_size > 0, "zero padding block size");
+        BlockPadding { block_size }
+    }
+
+    /// Returns the block size of the policy.
+    #[must_use]
+    pub const fn block_size(self) -> u16 {
+        self.block_size
+    }
+
+    /// Returns the length of the padding option data for a message.
+    ///
+    /// The `len` argument is the length of the message before adding the
+    /// padding option and `max_len` is the maximum length the message may
+    /// grow to. The returned length is that of the option data, i.e., it
+    /// does not include the four octets of the option’s code and length.
+    ///
+    /// Returns `None` if even an empty option would exceed `max_len`.
+    #[must_use]
+    pub fn padding_len(self, len: usize, max_len: usize) -> Option<u16> {
+        let len = len.checked_add(4)?;
+        if len > max_len {
+            return None
+        }
+        let block_size = usize::from(self.block_size);
+        let padded = match len % block_size {
+            0 => len,
+            rem => len.saturating_add(block_size - rem)
+        };
+        u16::try_from(padded.min(max_len) - len).ok()
+    }
+}
+
+
+//============ Testing =======================================================
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+    use crate::base::message_builder::MessageBuilder;
+    use crate::base::name::Name;
+    use crate::base::iana::Rtype;
+    use core::str::FromStr;
+    use std::vec::Vec;
+
+    #[test]
+    fn padding_len() {
+        assert_eq!(BlockPadding::QUERY.padding_len(60, 512), Some(64));
+        assert_eq!(BlockPadding::QUERY.padding_len(124, 512), Some(0));
+        assert_eq!(BlockPadding::QUERY.padding_len(125, 512), Some(127));
+        assert_eq!(BlockPadding::RESPONSE.padding_len(500, 512), Some(8));
+        assert_eq!(BlockPadding::RESPONSE.padding_len(508, 512), Some(0));
+        assert_eq!(BlockPadding::RESPONSE.padding_len(509, 512), None);
+    }
+
+    #[test]
+    fn block_padding() {
+        let mut msg = MessageBuilder::new_vec().question();
+        msg.push((
+            Name::<Vec<u8>>::from_str("www.example.com").unwrap(), Rtype::A
+        )).unwrap();
+        let mut msg = msg.additional();
+        msg.opt(|opt| opt.block_padding(BlockPadding::QUERY)).unwrap();
+        assert_eq!(msg.as_slice().len(), 128);
+
+        let mut msg = MessageBuilder::new_vec();
+        msg.set_push_limit(100);
+        let mut msg = msg.additional();
+        msg.opt(|opt| opt.block_padding(BlockPadding::QUERY)).unwrap();
+        assert_eq!(msg.as_slice().len(), 99);
+    }
+}
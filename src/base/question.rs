@@ -84,11 +84,43 @@ impl<N: ToName> Question<N> {
     }
 
     /// Returns the class of the question.
+    ///
+    /// The class is returned as it appears in the message. In multicast
+    /// DNS, its top bit is used as a flag. Use
+    /// [`mdns_qclass`][Self::mdns_qclass] to get the class without it.
     pub fn qclass(&self) -> Class {
         self.qclass
     }
 }
 
+/// # Multicast DNS
+///
+/// Multicast DNS as defined in [RFC 6762] uses the top bit of the class
+/// field of a question to request a unicast response. Use this bit only
+/// with multicast DNS: in regular DNS, it is just part of the class.
+///
+/// [RFC 6762]: https://tools.ietf.org/html/rfc6762
+impl<N> Question<N> {
+    /// Returns whether the question requests a unicast response.
+    ///
+    /// This is the QU bit of section 5.4 of [RFC 6762].
+    ///
+    /// [RFC 6762]: https://tools.ietf.org/html/rfc6762
+    pub fn unicast_response(&self) -> bool {
+        self.qclass.mdns_bit()
+    }
+
+    /// Sets whether the question requests a unicast response.
+    pub fn set_unicast_response(&mut self, value: bool) {
+        self.qclass = self.qclass.with_mdns_bit(value)
+    }
+
+    /// Returns the class of the question without the unicast response bit.
+    pub fn mdns_qclass(&self) -> Class {
+        self.qclass.without_mdns_bit()
+    }
+}
+
 /// # Parsing and Composing
 ///
 impl<Octs> Question<ParsedName<Octs>> {
@@ -292,9 +324,15 @@ impl<N: hash::Hash> hash::Hash for Question<N> {
 
 //--- Display and Debug
 
-impl<N: fmt::Display> fmt::Display for Question<N> {
+impl<N: ToName> fmt::Display for Question<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}.\t{}\t{}", self.qname, self.qtype, self.qclass)
+        write!(
+            f,
+            "{}\t{}\t{}",
+            self.qname.fmt_with_dot(),
+            self.qtype,
+            self.qclass
+        )
     }
 }
 
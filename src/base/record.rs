@@ -156,6 +156,28 @@ impl<Name, Data> Record<Name, Data> {
         self.class = class
     }
 
+    /// Returns whether the multicast DNS cache-flush bit is set.
+    ///
+    /// Multicast DNS uses the top bit of the class field of a record to
+    /// ask receivers to flush older records of the RRset from their cache
+    /// as described in section 10.2 of [RFC 6762]. In regular DNS, the bit
+    /// is just part of the class.
+    ///
+    /// [RFC 6762]: https://tools.ietf.org/html/rfc6762
+    pub fn cache_flush(&self) -> bool {
+        self.class.mdns_bit()
+    }
+
+    /// Sets the multicast DNS cache-flush bit.
+    pub fn set_cache_flush(&mut self, value: bool) {
+        self.class = self.class.with_mdns_bit(value)
+    }
+
+    /// Returns the record class without the multicast DNS cache-flush bit.
+    pub fn mdns_class(&self) -> Class {
+        self.class.without_mdns_bit()
+    }
+
     /// Returns the record’s time-to-live.
     pub fn ttl(&self) -> Ttl {
         self.ttl
@@ -213,14 +235,37 @@ impl<N: ToName, D: RecordData + ComposeRecordData> Record<N, D> {
         self.data.compose_len_rdata(target)
     }
 
+    /// Appends the canonical wire format of the record to `target`.
+    ///
+    /// The canonical form is defined in [RFC 4034, section 6.2]: The owner
+    /// name and, for the record types listed there, the domain names
+    /// embedded in the record data are lowercased. Names are never
+    /// compressed.
+    ///
+    /// [RFC 4034, section 6.2]: https://tools.ietf.org/html/rfc4034#section-6.2
     pub fn compose_canonical<Target: Composer + ?Sized>(
         &self,
         target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_canonical_with_ttl(self.ttl, target)
+    }
+
+    /// Appends the canonical wire format of the record with a given TTL.
+    ///
+    /// This is the form of the record that is signed by an RRSIG record.
+    /// In that case, `ttl` should be the original TTL from the RRSIG
+    /// record, as described in [RFC 4034, section 3.1.8.1].
+    ///
+    /// [RFC 4034, section 3.1.8.1]: https://tools.ietf.org/html/rfc4034#section-3.1.8.1
+    pub fn compose_canonical_with_ttl<Target: Composer + ?Sized>(
+        &self,
+        ttl: Ttl,
+        target: &mut Target,
     ) -> Result<(), Target::AppendError> {
         self.owner.compose_canonical(target)?;
         self.data.rtype().compose(target)?;
         self.class.compose(target)?;
-        self.ttl.compose(target)?;
+        ttl.compose(target)?;
         self.data.compose_canonical_len_rdata(target)
     }
 }
@@ -401,14 +446,14 @@ where
 
 impl<Name, Data> fmt::Display for Record<Name, Data>
 where
-    Name: fmt::Display,
+    Name: ToName,
     Data: RecordData + fmt::Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "{}. {} {} {} {}",
-            self.owner,
+            "{} {} {} {} {}",
+            self.owner.fmt_with_dot(),
             self.ttl.as_secs(),
             self.class,
             self.data.rtype(),
@@ -614,6 +659,18 @@ impl<Name> RecordHeader<Name> {
         self.class
     }
 
+    /// Returns whether the multicast DNS cache-flush bit is set.
+    ///
+    /// See [`Record::cache_flush`] for details.
+    pub fn cache_flush(&self) -> bool {
+        self.class.mdns_bit()
+    }
+
+    /// Returns the class without the multicast DNS cache-flush bit.
+    pub fn mdns_class(&self) -> Class {
+        self.class.without_mdns_bit()
+    }
+
     /// Returns the TTL of the record.
     pub fn ttl(&self) -> Ttl {
         self.ttl
@@ -1453,10 +1510,9 @@ impl Ttl {
     without modifying the original"]
     #[inline]
     pub const fn checked_div(self, rhs: u32) -> Option<Ttl> {
-        if rhs != 0 {
-            Some(Ttl(self.0 / rhs))
-        } else {
-            None
+        match self.0.checked_div(rhs) {
+            Some(secs) => Some(Ttl(secs)),
+            None => None,
         }
     }
 
@@ -1678,4 +1734,114 @@ mod test {
         assert_eq!(ds.owner(), ds_bytes.owner());
         assert_eq!(ds.data().digest(), ds_bytes.data().digest());
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn mdns_bits() {
+        use super::*;
+        use crate::base::iana::{Class, Rtype};
+        use crate::base::message::Message;
+        use crate::base::message_builder::MessageBuilder;
+        use crate::base::name::Name;
+        use crate::base::question::Question;
+        use crate::rdata::A;
+
+        let mut question = Question::new_in(Name::root_slice(), Rtype::A);
+        question.set_unicast_response(true);
+        let mut record = Record::new(
+            Name::root_slice(),
+            Class::IN,
+            Ttl::from_secs(120),
+            A::from_octets(192, 0, 2, 1),
+        );
+        record.set_cache_flush(true);
+
+        let mut msg = MessageBuilder::new_vec().question();
+        msg.push(question).unwrap();
+        let mut msg = msg.answer();
+        msg.push(record).unwrap();
+        let msg = Message::from_octets(msg.finish()).unwrap();
+
+        let question = msg.first_question().unwrap();
+        assert!(question.unicast_response());
+        assert_eq!(question.qclass(), Class::from_int(0x8001));
+        assert_eq!(question.mdns_qclass(), Class::IN);
+
+        let record = msg.answer().unwrap().next().unwrap().unwrap();
+        assert_eq!(record.class(), Class::from_int(0x8001));
+        let record = record.into_record::<A>().unwrap().unwrap();
+        assert!(record.cache_flush());
+        assert_eq!(record.mdns_class(), Class::IN);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn compose_canonical() {
+        use super::*;
+        use crate::base::iana::Class;
+        use crate::base::name::Name;
+        use crate::rdata::{Mx, Txt};
+        use core::str::FromStr;
+        use octseq::builder::infallible;
+        use std::vec::Vec;
+
+        let record = Record::new(
+            Name::<Vec<u8>>::from_str("Example.COM").unwrap(),
+            Class::IN,
+            Ttl::from_secs(3600),
+            Mx::new(10, Name::<Vec<u8>>::from_str("MX.example.com").unwrap()),
+        );
+        let mut buf = Vec::new();
+        infallible(record.compose_canonical(&mut buf));
+        assert_eq!(
+            buf.as_slice(),
+            b"\x07example\x03com\x00\x00\x0f\x00\x01\x00\x00\x0e\x10\
+              \x00\x12\x00\x0a\x02mx\x07example\x03com\x00"
+        );
+
+        let mut buf = Vec::new();
+        infallible(
+            record.compose_canonical_with_ttl(Ttl::from_secs(60), &mut buf),
+        );
+        assert_eq!(&buf[17..21], b"\x00\x00\x00\x3c");
+
+        // Names inside other record data keep their case.
+        let record = Record::new(
+            Name::<Vec<u8>>::from_str("example.com").unwrap(),
+            Class::IN,
+            Ttl::from_secs(3600),
+            Txt::<Vec<u8>>::build_from_slice(b"ABC").unwrap(),
+        );
+        let mut buf = Vec::new();
+        infallible(record.compose_canonical(&mut buf));
+        assert!(buf.ends_with(b"\x00\x04\x03ABC"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn display() {
+        use super::*;
+        use crate::base::iana::Class;
+        use crate::base::name::Name;
+        use crate::rdata::{Mx, Ns};
+        use core::str::FromStr;
+        use std::string::ToString;
+        use std::vec::Vec;
+
+        let record = Record::new(
+            Name::root_slice(),
+            Class::IN,
+            Ttl::from_secs(3600),
+            Ns::new(Name::<Vec<u8>>::from_str("ns.example").unwrap()),
+        );
+        assert_eq!(record.to_string(), ". 3600 IN NS ns.example.");
+
+        let record = Record::new(
+            Name::<Vec<u8>>::from_str("example.com").unwrap(),
+            Class::IN,
+            Ttl::from_secs(3600),
+            Mx::new(0, Name::root_slice()),
+        );
+        assert_eq!(record.to_string(), "example.com. 3600 IN MX 0 .");
+    }
 }
@@ -76,9 +76,13 @@ macro_rules! impl_scan_unsigned {
                     res = res.checked_mul(10).ok_or_else(|| {
                         S::Error::custom("decimal number overflow")
                     })?;
-                    res += ch.into_digit(10).map_err(|_| {
-                        S::Error::custom("expected decimal number")
-                    })? as $type;
+                    res = res
+                        .checked_add(ch.into_digit(10).map_err(|_| {
+                            S::Error::custom("expected decimal number")
+                        })? as $type)
+                        .ok_or_else(|| {
+                            S::Error::custom("decimal number overflow")
+                        })?;
                     Ok(())
                 })?;
                 Ok(res)
@@ -100,9 +104,11 @@ impl<S: Scanner> Scan<S> for Ttl {
             res = res
                 .checked_mul(10)
                 .ok_or_else(|| S::Error::custom("decimal number overflow"))?;
-            res += ch
-                .into_digit(10)
-                .map_err(|_| S::Error::custom("expected decimal number"))?;
+            res = res
+                .checked_add(ch.into_digit(10).map_err(|_| {
+                    S::Error::custom("expected decimal number")
+                })?)
+                .ok_or_else(|| S::Error::custom("decimal number overflow"))?;
             Ok(())
         })?;
         Ok(Ttl::from_secs(res))
@@ -283,7 +289,7 @@ declare_error_trait!(ScannerError: Sized + fmt::Debug + fmt::Display);
 #[cfg(feature = "std")]
 impl ScannerError for std::io::Error {
     fn custom(msg: &'static str) -> Self {
-        std::io::Error::new(std::io::ErrorKind::Other, msg)
+        std::io::Error::other(msg)
     }
 
     fn end_of_entry() -> Self {
@@ -294,11 +300,11 @@ impl ScannerError for std::io::Error {
     }
 
     fn short_buf() -> Self {
-        std::io::Error::new(std::io::ErrorKind::Other, ShortBuf)
+        std::io::Error::other(ShortBuf)
     }
 
     fn trailing_tokens() -> Self {
-        std::io::Error::new(std::io::ErrorKind::Other, "trailing data")
+        std::io::Error::other("trailing data")
     }
 }
 
@@ -1168,7 +1174,7 @@ impl std::error::Error for BadSymbol {}
 #[cfg(feature = "std")]
 impl From<BadSymbol> for std::io::Error {
     fn from(err: BadSymbol) -> Self {
-        std::io::Error::new(std::io::ErrorKind::Other, err)
+        std::io::Error::other(err)
     }
 }
 
@@ -21,8 +21,18 @@ pub trait Composer:
     /// optional. However, in order to be able to opt in, we need to know
     /// if we are dealing with a domain name that ought to be compressed.
     ///
+    /// Compression is only allowed for the owner name and question name and
+    /// for names in the record data of the well-known record types defined
+    /// in RFC 1035. As [RFC 3597] requires, names embedded in the record
+    /// data of all other record types must be appended uncompressed via
+    /// [`ToName::compose`] instead. Compression also is applied ignoring
+    /// ASCII case, so the appended name may end up with the case of a
+    /// previously appended name.
+    ///
     /// The trait provides a default implementation which simply appends the
     /// name uncompressed.
+    ///
+    /// [RFC 3597]: https://tools.ietf.org/html/rfc3597
     fn append_compressed_name<N: ToName + ?Sized>(
         &mut self,
         name: &N,
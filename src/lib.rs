@@ -33,6 +33,9 @@
 #![cfg_attr(not(feature = "resolv"), doc = "* resolv:")]
 //!   An asynchronous DNS resolver based on the
 //!   [Tokio](https://tokio.rs/) async runtime.
+#![cfg_attr(feature = "unstable-validate", doc = "* [sig0]:")]
+#![cfg_attr(not(feature = "unstable-validate"), doc = "* sig0:")]
+//!   Experimental support for SIG(0) transaction signatures.
 #![cfg_attr(feature = "unstable-sign", doc = "* [sign]:")]
 #![cfg_attr(not(feature = "unstable-sign"), doc = "* sign:")]
 //!   Experimental support for DNSSEC signing.
@@ -161,7 +164,13 @@
 //! * `unstable-validate`: basic DNSSEC validation support. This enables the
 #![cfg_attr(feature = "unstable-validate", doc = "  [validate]")]
 #![cfg_attr(not(feature = "unstable-validate"), doc = "  validate")]
-//!   module and currently also enables the `std` and `ring` features.
+//!   module and currently also enables the `std` feature. Verifying
+//!   signatures and calculating digests requires a cryptographic backend
+//!   (`ring` or `openssl`).
+//!   It also enables verifying SIG(0) signatures in the
+#![cfg_attr(feature = "unstable-validate", doc = "  [sig0]")]
+#![cfg_attr(not(feature = "unstable-validate"), doc = "  sig0")]
+//!   module. Creating them requires the `unstable-sign` feature.
 //! * `unstable-validator`: a DNSSEC validator, primarily the `validator`
 //!   and the `net::client::validator` modules.
 //! * `unstable-xfr`: zone transfer related functionality..
@@ -192,6 +201,7 @@ pub mod dep;
 pub mod net;
 pub mod rdata;
 pub mod resolv;
+pub mod sig0;
 pub mod sign;
 pub mod stelline;
 pub mod tsig;
@@ -10,7 +10,7 @@ use super::request::{
 use crate::base::iana::{Rcode, Rtype};
 use crate::base::message::Message;
 use crate::base::message_builder::StreamTarget;
-use crate::base::opt::{AllOptData, OptRecord, TcpKeepalive};
+use crate::base::opt::{OptRecord, TcpKeepalive};
 use crate::base::{ParsedName, Serial};
 use crate::rdata::AllRecordData;
 use crate::utils::config::DefMinMax;
@@ -865,13 +865,8 @@ where
         opts: &OptRecord<Octs>,
         status: &mut Status,
     ) {
-        // XXX This handles _all_ keepalive options. I think just using the
-        //     first option as returned by Opt::tcp_keepalive should be good
-        //     enough? -- M.
-        for option in opts.opt().iter().flatten() {
-            if let AllOptData::TcpKeepalive(tcpkeepalive) = option {
-                Self::handle_keepalive(tcpkeepalive, status);
-            }
+        if let Some(tcpkeepalive) = opts.opt().tcp_keepalive() {
+            Self::handle_keepalive(tcpkeepalive, status);
         }
     }
 
@@ -1041,10 +1036,10 @@ where
         if status.send_keepalive
             && match &mut req.msg {
                 ReqSingleMulti::Single(msg) => {
-                    msg.add_opt(&TcpKeepalive::new(None)).is_ok()
+                    msg.add_opt(&TcpKeepalive::request()).is_ok()
                 }
                 ReqSingleMulti::Multi(msg) => {
-                    msg.add_opt(&TcpKeepalive::new(None)).is_ok()
+                    msg.add_opt(&TcpKeepalive::request()).is_ok()
                 }
             }
         {
@@ -1066,9 +1061,8 @@ where
 
     /// Handle a received edns-tcp-keepalive option.
     fn handle_keepalive(opt_value: TcpKeepalive, status: &mut Status) {
-        if let Some(value) = opt_value.timeout() {
-            let value_dur = Duration::from(value);
-            status.idle_timeout = value_dur;
+        if let Some(value) = opt_value.timeout_duration() {
+            status.idle_timeout = value;
         }
     }
 
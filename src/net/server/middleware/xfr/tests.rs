@@ -32,7 +32,7 @@ use crate::net::server::service::{
     CallResult, Service, ServiceError, ServiceFeedback, ServiceResult,
 };
 use crate::rdata::{
-    Aaaa, AllRecordData, Cname, Mx, Ns, Soa, Txt, ZoneRecordData, A,
+    Aaaa, AllRecordData, Cname, Dname, Mx, Ns, Soa, Txt, ZoneRecordData, A,
 };
 use crate::tsig::{Algorithm, Key, KeyName};
 use crate::zonefile::inplace::Zonefile;
@@ -169,6 +169,62 @@ async fn axfr_name_compression_not_yet_supported() {
 #[tokio::test]
 async fn axfr_occluded_names() {
     // https://datatracker.ietf.org/doc/html/rfc5936#section-3.5
+    let zone = load_zone(
+        br#"
+example.com.         IN SOA ns.example.com. admin.example.com. (
+                              1 600 600 3600000 604800)
+                     IN NS  ns.example.com.
+ns.example.com.      IN A   192.0.2.1
+sub.example.com.     IN NS  ns.sub.example.com.
+ns.sub.example.com.  IN A   192.0.2.2
+www.sub.example.com. IN A   192.0.2.3
+old.example.com.     IN DNAME example.net.
+www.old.example.com. IN A   192.0.2.4
+    "#,
+    );
+
+    let req = mk_axfr_request(zone.apex_name(), ());
+
+    let res = do_preprocess(zone.clone(), &req).await.unwrap();
+
+    let ControlFlow::Break(mut stream) = res else {
+        panic!("AXFR failed");
+    };
+
+    let zone_soa = get_zone_soa(&zone).await;
+
+    // Names below the zone cut and the DNAME are occluded but still have
+    // to be transferred, each of them exactly once.
+    let mut expected_records: ExpectedRecords = vec![
+        (n("example.com"), zone_soa.clone().into()),
+        (n("example.com"), Ns::new(n("ns.example.com")).into()),
+        (n("ns.example.com"), A::new(p("192.0.2.1")).into()),
+        (
+            n("sub.example.com"),
+            Ns::new(n("ns.sub.example.com")).into(),
+        ),
+        (n("ns.sub.example.com"), A::new(p("192.0.2.2")).into()),
+        (n("www.sub.example.com"), A::new(p("192.0.2.3")).into()),
+        (n("old.example.com"), Dname::new(n("example.net")).into()),
+        (n("www.old.example.com"), A::new(p("192.0.2.4")).into()),
+        (n("example.com"), zone_soa.into()),
+    ];
+
+    let msg = stream.next().await.unwrap().unwrap();
+    assert!(matches!(
+        msg.feedback(),
+        Some(ServiceFeedback::BeginTransaction)
+    ));
+
+    let stream =
+        assert_stream_eq(req.message(), &mut stream, &mut expected_records)
+            .await;
+
+    let msg = stream.next().await.unwrap().unwrap();
+    assert!(matches!(
+        msg.feedback(),
+        Some(ServiceFeedback::EndTransaction)
+    ));
 }
 
 #[tokio::test]
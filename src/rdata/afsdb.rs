@@ -0,0 +1,272 @@
+//! Record data from [RFC 1183]: AFSDB records.
+//!
+//! Among others, this RFC defines the AFSDB record type.
+//!
+//! [RFC 1183]: https://tools.ietf.org/html/rfc1183
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::name::{FlattenInto, ParsedName, ToName};
+use crate::base::rdata::{ComposeRecordData, ParseRecordData, RecordData};
+use crate::base::scan::{Scan, Scanner};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use core::cmp::Ordering;
+use core::fmt;
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Afsdb ---------------------------------------------------------
+
+/// Afsdb record data.
+///
+/// The Afsdb record specifies the location of a server for an AFS cell or
+/// a DCE cell with the same name as the owner name. The kind of server is
+/// given via the subtype, see [`SUBTYPE_AFS`] and [`SUBTYPE_DCE`] for the
+/// defined values.
+///
+/// The Afsdb record type is defined in [RFC 1183, section 1][1].
+///
+/// [`SUBTYPE_AFS`]: Afsdb::SUBTYPE_AFS
+/// [`SUBTYPE_DCE`]: Afsdb::SUBTYPE_DCE
+/// [1]: https://tools.ietf.org/html/rfc1183#section-1
+#[derive(Clone, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Afsdb<N> {
+    subtype: u16,
+    hostname: N,
+}
+
+impl Afsdb<()> {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::AFSDB;
+
+    /// The subtype for an AFS version 3.0 volume location server.
+    pub const SUBTYPE_AFS: u16 = 1;
+
+    /// The subtype for a DCE authenticated name server.
+    pub const SUBTYPE_DCE: u16 = 2;
+}
+
+impl<N> Afsdb<N> {
+    /// Creates new Afsdb record data from the components.
+    pub fn new(subtype: u16, hostname: N) -> Self {
+        Afsdb { subtype, hostname }
+    }
+
+    /// The subtype of the server.
+    pub fn subtype(&self) -> u16 {
+        self.subtype
+    }
+
+    /// The name of the host running the server.
+    pub fn hostname(&self) -> &N {
+        &self.hostname
+    }
+
+    /// Converts the record data into the host name.
+    pub fn into_hostname(self) -> N {
+        self.hostname
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<N>>(
+        self,
+    ) -> Result<Afsdb<Target>, Target::Error> {
+        Ok(Afsdb::new(self.subtype, self.hostname.try_octets_into()?))
+    }
+
+    pub(super) fn flatten<TargetName>(
+        self,
+    ) -> Result<Afsdb<TargetName>, N::AppendError>
+    where
+        N: FlattenInto<TargetName>,
+    {
+        Ok(Afsdb::new(self.subtype, self.hostname.try_flatten_into()?))
+    }
+
+    pub fn scan<S: Scanner<Name = N>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error> {
+        Ok(Self::new(u16::scan(scanner)?, scanner.scan_name()?))
+    }
+}
+
+impl<Octs> Afsdb<ParsedName<Octs>> {
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized + 'a>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        Ok(Self::new(u16::parse(parser)?, ParsedName::parse(parser)?))
+    }
+}
+
+//--- OctetsFrom and FlattenInto
+
+impl<Name, SrcName> OctetsFrom<Afsdb<SrcName>> for Afsdb<Name>
+where
+    Name: OctetsFrom<SrcName>,
+{
+    type Error = Name::Error;
+
+    fn try_octets_from(source: Afsdb<SrcName>) -> Result<Self, Self::Error> {
+        Ok(Afsdb::new(
+            source.subtype,
+            Name::try_octets_from(source.hostname)?,
+        ))
+    }
+}
+
+impl<Name, TName> FlattenInto<Afsdb<TName>> for Afsdb<Name>
+where
+    Name: FlattenInto<TName>,
+{
+    type AppendError = Name::AppendError;
+
+    fn try_flatten_into(self) -> Result<Afsdb<TName>, Name::AppendError> {
+        self.flatten()
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<N, NN> PartialEq<Afsdb<NN>> for Afsdb<N>
+where
+    N: ToName,
+    NN: ToName,
+{
+    fn eq(&self, other: &Afsdb<NN>) -> bool {
+        self.subtype == other.subtype
+            && self.hostname.name_eq(&other.hostname)
+    }
+}
+
+impl<N: ToName> Eq for Afsdb<N> {}
+
+//--- PartialOrd, Ord, and CanonicalOrd
+
+impl<N, NN> PartialOrd<Afsdb<NN>> for Afsdb<N>
+where
+    N: ToName,
+    NN: ToName,
+{
+    fn partial_cmp(&self, other: &Afsdb<NN>) -> Option<Ordering> {
+        match self.subtype.partial_cmp(&other.subtype) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        Some(self.hostname.name_cmp(&other.hostname))
+    }
+}
+
+impl<N: ToName> Ord for Afsdb<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.subtype.cmp(&other.subtype) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.hostname.name_cmp(&other.hostname)
+    }
+}
+
+impl<N: ToName, NN: ToName> CanonicalOrd<Afsdb<NN>> for Afsdb<N> {
+    fn canonical_cmp(&self, other: &Afsdb<NN>) -> Ordering {
+        match self.subtype.cmp(&other.subtype) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.hostname.lowercase_composed_cmp(&other.hostname)
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<N> RecordData for Afsdb<N> {
+    fn rtype(&self) -> Rtype {
+        Afsdb::RTYPE
+    }
+}
+
+impl<'a, Octs: Octets + ?Sized> ParseRecordData<'a, Octs>
+    for Afsdb<ParsedName<Octs::Range<'a>>>
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Afsdb::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Name: ToName> ComposeRecordData for Afsdb<Name> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        // AFSDB records are not compressed.
+        Some(u16::COMPOSE_LEN + self.hostname.compose_len())
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.subtype.compose(target)?;
+        self.hostname.compose(target)
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.subtype.compose(target)?;
+        self.hostname.compose_canonical(target) // ... but are lowercased.
+    }
+}
+
+//--- Display
+
+impl<N: ToName> fmt::Display for Afsdb<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.subtype, self.hostname.fmt_with_dot())
+    }
+}
+
+//--- ZonefileFmt
+
+impl<N: ToName> ZonefileFmt for Afsdb<N> {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| {
+            p.write_token(self.subtype)?;
+            p.write_comment("subtype")?;
+            p.write_token(self.hostname.fmt_with_dot())
+        })
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::name::Name;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use core::str::FromStr;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn afsdb_compose_parse_scan() {
+        let rdata = Afsdb::<Name<Vec<u8>>>::new(
+            1,
+            Name::from_str("afs.example.com").unwrap(),
+        );
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Afsdb::parse(parser));
+        test_scan(&["1", "afs.example.com"], Afsdb::scan, &rdata);
+        assert_eq!(rdata.to_string(), "1 afs.example.com.");
+    }
+}
@@ -0,0 +1,664 @@
+//! Record data from [RFC 8777]: AMTRELAY records.
+//!
+//! This RFC defines the AMTRELAY record type.
+//!
+//! [RFC 8777]: https://tools.ietf.org/html/rfc8777
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::name::{FlattenInto, ParsedName, ToName};
+use crate::base::net::{Ipv4Addr, Ipv6Addr};
+use crate::base::rdata::{ComposeRecordData, ParseRecordData, RecordData};
+use crate::base::scan::{Scan, Scanner, ScannerError};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use core::cmp::Ordering;
+use core::str::FromStr;
+use core::{fmt, hash};
+use octseq::octets::{Octets, OctetsFrom};
+use octseq::parse::Parser;
+
+//------------ Amtrelay ------------------------------------------------------
+
+/// Amtrelay record data.
+///
+/// Amtrelay records advertise a relay for Automatic Multicast Tunneling
+/// (AMT) for the source network listed in the owner name. The record data
+/// contains the [relay][AmtrelayRelay], which can be absent, an IPv4 or
+/// IPv6 address, or a domain name, a precedence for choosing among
+/// multiple records, and the discovery optional flag, also known as the
+/// D-bit.
+///
+/// The Amtrelay record type is defined in [RFC 8777, section 4][1].
+///
+/// [1]: https://tools.ietf.org/html/rfc8777#section-4
+#[derive(Clone, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Amtrelay<Name> {
+    precedence: u8,
+    discovery_optional: bool,
+    relay: AmtrelayRelay<Name>,
+}
+
+impl Amtrelay<()> {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::AMTRELAY;
+
+    /// The bit of the D-bit in the second octet of the wire format.
+    const D_BIT: u8 = 0x80;
+}
+
+impl<Name> Amtrelay<Name> {
+    /// Creates new Amtrelay record data from its components.
+    pub fn new(
+        precedence: u8,
+        discovery_optional: bool,
+        relay: AmtrelayRelay<Name>,
+    ) -> Self {
+        Amtrelay {
+            precedence,
+            discovery_optional,
+            relay,
+        }
+    }
+
+    /// Returns the precedence of the record.
+    ///
+    /// Records with a lower value should be tried first.
+    pub fn precedence(&self) -> u8 {
+        self.precedence
+    }
+
+    /// Returns whether the discovery optional flag is set.
+    ///
+    /// If the flag is set, the relay may be used without first performing
+    /// AMT discovery. The flag is called the D-bit in the RFC.
+    pub fn discovery_optional(&self) -> bool {
+        self.discovery_optional
+    }
+
+    /// Returns the relay.
+    pub fn relay(&self) -> &AmtrelayRelay<Name> {
+        &self.relay
+    }
+
+    /// Converts the record data into the relay.
+    pub fn into_relay(self) -> AmtrelayRelay<Name> {
+        self.relay
+    }
+
+    /// Returns the second octet of the wire format.
+    ///
+    /// It contains the D-bit and the relay type.
+    fn type_octet(&self) -> u8 {
+        if self.discovery_optional {
+            Amtrelay::D_BIT | self.relay.relay_type()
+        } else {
+            self.relay.relay_type()
+        }
+    }
+
+    pub(super) fn convert_octets<TName: OctetsFrom<Name>>(
+        self,
+    ) -> Result<Amtrelay<TName>, TName::Error> {
+        Ok(Amtrelay::new(
+            self.precedence,
+            self.discovery_optional,
+            self.relay.try_map_name(TName::try_octets_from)?,
+        ))
+    }
+
+    pub(super) fn flatten<TName>(
+        self,
+    ) -> Result<Amtrelay<TName>, Name::AppendError>
+    where
+        Name: FlattenInto<TName>,
+    {
+        Ok(Amtrelay::new(
+            self.precedence,
+            self.discovery_optional,
+            self.relay.try_map_name(Name::try_flatten_into)?,
+        ))
+    }
+
+    pub fn scan<S: Scanner<Name = Name>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error> {
+        let precedence = u8::scan(scanner)?;
+        let discovery_optional = match u8::scan(scanner)? {
+            0 => false,
+            1 => true,
+            _ => return Err(S::Error::custom("expected D-bit of 0 or 1")),
+        };
+        let relay = match u8::scan(scanner)? {
+            AmtrelayRelay::<Name>::NONE => scanner.scan_ascii_str(|s| {
+                if s == "." {
+                    Ok(AmtrelayRelay::None)
+                } else {
+                    Err(S::Error::custom("expected '.' for no relay"))
+                }
+            })?,
+            AmtrelayRelay::<Name>::IPV4 => scanner.scan_ascii_str(|s| {
+                Ipv4Addr::from_str(s)
+                    .map(AmtrelayRelay::Ipv4)
+                    .map_err(|_| S::Error::custom("expected IPv4 address"))
+            })?,
+            AmtrelayRelay::<Name>::IPV6 => scanner.scan_ascii_str(|s| {
+                Ipv6Addr::from_str(s)
+                    .map(AmtrelayRelay::Ipv6)
+                    .map_err(|_| S::Error::custom("expected IPv6 address"))
+            })?,
+            AmtrelayRelay::<Name>::NAME => {
+                AmtrelayRelay::Name(scanner.scan_name()?)
+            }
+            _ => return Err(S::Error::custom("unknown relay type")),
+        };
+        Ok(Self::new(precedence, discovery_optional, relay))
+    }
+}
+
+impl<Octs> Amtrelay<ParsedName<Octs>> {
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized + 'a>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let precedence = u8::parse(parser)?;
+        let type_octet = u8::parse(parser)?;
+        let relay = match type_octet & !Amtrelay::D_BIT {
+            AmtrelayRelay::<()>::NONE => AmtrelayRelay::None,
+            AmtrelayRelay::<()>::IPV4 => {
+                AmtrelayRelay::Ipv4(Ipv4Addr::parse(parser)?)
+            }
+            AmtrelayRelay::<()>::IPV6 => {
+                AmtrelayRelay::Ipv6(Ipv6Addr::parse(parser)?)
+            }
+            AmtrelayRelay::<()>::NAME => {
+                AmtrelayRelay::Name(ParsedName::parse(parser)?)
+            }
+            _ => return Err(ParseError::form_error("unknown relay type")),
+        };
+        Ok(Self::new(
+            precedence,
+            type_octet & Amtrelay::D_BIT != 0,
+            relay,
+        ))
+    }
+}
+
+//--- OctetsFrom and FlattenInto
+
+impl<Name, SrcName> OctetsFrom<Amtrelay<SrcName>> for Amtrelay<Name>
+where
+    Name: OctetsFrom<SrcName>,
+{
+    type Error = Name::Error;
+
+    fn try_octets_from(
+        source: Amtrelay<SrcName>,
+    ) -> Result<Self, Self::Error> {
+        source.convert_octets()
+    }
+}
+
+impl<Name, TName> FlattenInto<Amtrelay<TName>> for Amtrelay<Name>
+where
+    Name: FlattenInto<TName>,
+{
+    type AppendError = Name::AppendError;
+
+    fn try_flatten_into(self) -> Result<Amtrelay<TName>, Name::AppendError> {
+        self.flatten()
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Name, OtherName> PartialEq<Amtrelay<OtherName>> for Amtrelay<Name>
+where
+    Name: ToName,
+    OtherName: ToName,
+{
+    fn eq(&self, other: &Amtrelay<OtherName>) -> bool {
+        self.precedence == other.precedence
+            && self.discovery_optional == other.discovery_optional
+            && self.relay.eq(&other.relay)
+    }
+}
+
+impl<Name: ToName> Eq for Amtrelay<Name> {}
+
+//--- PartialOrd, Ord, and CanonicalOrd
+
+impl<Name, OtherName> PartialOrd<Amtrelay<OtherName>> for Amtrelay<Name>
+where
+    Name: ToName,
+    OtherName: ToName,
+{
+    fn partial_cmp(&self, other: &Amtrelay<OtherName>) -> Option<Ordering> {
+        match self.precedence.partial_cmp(&other.precedence) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        match self
+            .discovery_optional
+            .partial_cmp(&other.discovery_optional)
+        {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        self.relay.partial_cmp(&other.relay)
+    }
+}
+
+impl<Name, OtherName> CanonicalOrd<Amtrelay<OtherName>> for Amtrelay<Name>
+where
+    Name: ToName,
+    OtherName: ToName,
+{
+    fn canonical_cmp(&self, other: &Amtrelay<OtherName>) -> Ordering {
+        match self.precedence.cmp(&other.precedence) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.type_octet().cmp(&other.type_octet()) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.relay.canonical_cmp(&other.relay)
+    }
+}
+
+impl<Name: ToName> Ord for Amtrelay<Name> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.precedence.cmp(&other.precedence) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.discovery_optional.cmp(&other.discovery_optional) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.relay.cmp(&other.relay)
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Name> RecordData for Amtrelay<Name> {
+    fn rtype(&self) -> Rtype {
+        Amtrelay::RTYPE
+    }
+}
+
+impl<'a, Octs: Octets + ?Sized> ParseRecordData<'a, Octs>
+    for Amtrelay<ParsedName<Octs::Range<'a>>>
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Amtrelay::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Name: ToName> ComposeRecordData for Amtrelay<Name> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(2 + self.relay.compose_len())
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.precedence.compose(target)?;
+        self.type_octet().compose(target)?;
+        self.relay.compose(target)
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Name: ToName> fmt::Display for Amtrelay<Name> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.precedence,
+            u8::from(self.discovery_optional),
+            self.relay.relay_type(),
+            self.relay
+        )
+    }
+}
+
+//--- ZonefileFmt
+
+impl<Name: ToName> ZonefileFmt for Amtrelay<Name> {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| {
+            p.write_token(self.precedence)?;
+            p.write_comment("precedence")?;
+            p.write_token(u8::from(self.discovery_optional))?;
+            p.write_comment("discovery optional")?;
+            p.write_token(self.relay.relay_type())?;
+            p.write_comment("relay type")?;
+            match self.relay {
+                AmtrelayRelay::None => p.write_token("."),
+                AmtrelayRelay::Ipv4(addr) => p.write_token(addr),
+                AmtrelayRelay::Ipv6(addr) => p.write_token(addr),
+                AmtrelayRelay::Name(ref name) => {
+                    p.write_token(name.fmt_with_dot())
+                }
+            }
+        })
+    }
+}
+
+//------------ AmtrelayRelay -------------------------------------------------
+
+/// The relay of an AMTRELAY record.
+///
+/// In wire format, the type of the relay is given by a separate field
+/// which determines the encoding of the relay itself. The encoding is the
+/// same as that of the gateway of an IPSECKEY record.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AmtrelayRelay<Name> {
+    /// There is no relay.
+    None,
+
+    /// The relay is given as an IPv4 address.
+    Ipv4(Ipv4Addr),
+
+    /// The relay is given as an IPv6 address.
+    Ipv6(Ipv6Addr),
+
+    /// The relay is given as a domain name.
+    ///
+    /// The name is never compressed.
+    Name(Name),
+}
+
+impl<Name> AmtrelayRelay<Name> {
+    /// The relay type value for no relay.
+    pub const NONE: u8 = 0;
+
+    /// The relay type value for an IPv4 address.
+    pub const IPV4: u8 = 1;
+
+    /// The relay type value for an IPv6 address.
+    pub const IPV6: u8 = 2;
+
+    /// The relay type value for a domain name.
+    pub const NAME: u8 = 3;
+
+    /// Returns the value of the relay type field for the relay.
+    pub fn relay_type(&self) -> u8 {
+        match self {
+            AmtrelayRelay::None => Self::NONE,
+            AmtrelayRelay::Ipv4(_) => Self::IPV4,
+            AmtrelayRelay::Ipv6(_) => Self::IPV6,
+            AmtrelayRelay::Name(_) => Self::NAME,
+        }
+    }
+
+    /// Converts the name of the relay using the given function.
+    fn try_map_name<TName, E>(
+        self,
+        op: impl FnOnce(Name) -> Result<TName, E>,
+    ) -> Result<AmtrelayRelay<TName>, E> {
+        Ok(match self {
+            AmtrelayRelay::None => AmtrelayRelay::None,
+            AmtrelayRelay::Ipv4(addr) => AmtrelayRelay::Ipv4(addr),
+            AmtrelayRelay::Ipv6(addr) => AmtrelayRelay::Ipv6(addr),
+            AmtrelayRelay::Name(name) => AmtrelayRelay::Name(op(name)?),
+        })
+    }
+}
+
+impl<Name: ToName> AmtrelayRelay<Name> {
+    /// Returns the length of the wire format of the relay.
+    pub fn compose_len(&self) -> u16 {
+        match self {
+            AmtrelayRelay::None => 0,
+            AmtrelayRelay::Ipv4(_) => 4,
+            AmtrelayRelay::Ipv6(_) => 16,
+            AmtrelayRelay::Name(name) => name.compose_len(),
+        }
+    }
+
+    /// Appends the wire format of the relay to a target.
+    pub fn compose<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        match self {
+            AmtrelayRelay::None => Ok(()),
+            AmtrelayRelay::Ipv4(addr) => addr.compose(target),
+            AmtrelayRelay::Ipv6(addr) => addr.compose(target),
+            AmtrelayRelay::Name(name) => name.compose(target),
+        }
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Name, OtherName> PartialEq<AmtrelayRelay<OtherName>>
+    for AmtrelayRelay<Name>
+where
+    Name: ToName,
+    OtherName: ToName,
+{
+    fn eq(&self, other: &AmtrelayRelay<OtherName>) -> bool {
+        match (self, other) {
+            (AmtrelayRelay::None, AmtrelayRelay::None) => true,
+            (AmtrelayRelay::Ipv4(left), AmtrelayRelay::Ipv4(right)) => {
+                left == right
+            }
+            (AmtrelayRelay::Ipv6(left), AmtrelayRelay::Ipv6(right)) => {
+                left == right
+            }
+            (AmtrelayRelay::Name(left), AmtrelayRelay::Name(right)) => {
+                left.name_eq(right)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<Name: ToName> Eq for AmtrelayRelay<Name> {}
+
+//--- PartialOrd, Ord, and CanonicalOrd
+
+impl<Name, OtherName> PartialOrd<AmtrelayRelay<OtherName>>
+    for AmtrelayRelay<Name>
+where
+    Name: ToName,
+    OtherName: ToName,
+{
+    fn partial_cmp(
+        &self,
+        other: &AmtrelayRelay<OtherName>,
+    ) -> Option<Ordering> {
+        Some(match (self, other) {
+            (AmtrelayRelay::Ipv4(left), AmtrelayRelay::Ipv4(right)) => {
+                left.octets().cmp(&right.octets())
+            }
+            (AmtrelayRelay::Ipv6(left), AmtrelayRelay::Ipv6(right)) => {
+                left.octets().cmp(&right.octets())
+            }
+            (AmtrelayRelay::Name(left), AmtrelayRelay::Name(right)) => {
+                left.name_cmp(right)
+            }
+            _ => self.relay_type().cmp(&other.relay_type()),
+        })
+    }
+}
+
+impl<Name: ToName> Ord for AmtrelayRelay<Name> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (AmtrelayRelay::Ipv4(left), AmtrelayRelay::Ipv4(right)) => {
+                left.octets().cmp(&right.octets())
+            }
+            (AmtrelayRelay::Ipv6(left), AmtrelayRelay::Ipv6(right)) => {
+                left.octets().cmp(&right.octets())
+            }
+            (AmtrelayRelay::Name(left), AmtrelayRelay::Name(right)) => {
+                left.name_cmp(right)
+            }
+            _ => self.relay_type().cmp(&other.relay_type()),
+        }
+    }
+}
+
+impl<Name, OtherName> CanonicalOrd<AmtrelayRelay<OtherName>>
+    for AmtrelayRelay<Name>
+where
+    Name: ToName,
+    OtherName: ToName,
+{
+    fn canonical_cmp(&self, other: &AmtrelayRelay<OtherName>) -> Ordering {
+        match (self, other) {
+            (AmtrelayRelay::Ipv4(left), AmtrelayRelay::Ipv4(right)) => {
+                left.octets().cmp(&right.octets())
+            }
+            (AmtrelayRelay::Ipv6(left), AmtrelayRelay::Ipv6(right)) => {
+                left.octets().cmp(&right.octets())
+            }
+            (AmtrelayRelay::Name(left), AmtrelayRelay::Name(right)) => {
+                left.composed_cmp(right)
+            }
+            _ => self.relay_type().cmp(&other.relay_type()),
+        }
+    }
+}
+
+//--- Hash
+
+impl<Name: hash::Hash> hash::Hash for AmtrelayRelay<Name> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        match self {
+            AmtrelayRelay::None => 0u8.hash(state),
+            AmtrelayRelay::Ipv4(addr) => {
+                1u8.hash(state);
+                addr.octets().hash(state)
+            }
+            AmtrelayRelay::Ipv6(addr) => {
+                2u8.hash(state);
+                addr.octets().hash(state)
+            }
+            AmtrelayRelay::Name(name) => {
+                3u8.hash(state);
+                name.hash(state)
+            }
+        }
+    }
+}
+
+//--- Display
+
+impl<Name: ToName> fmt::Display for AmtrelayRelay<Name> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AmtrelayRelay::None => f.write_str("."),
+            AmtrelayRelay::Ipv4(addr) => addr.fmt(f),
+            AmtrelayRelay::Ipv6(addr) => addr.fmt(f),
+            AmtrelayRelay::Name(name) => name.fmt_with_dot().fmt(f),
+        }
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::name::Name;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    type TestAmtrelay = Amtrelay<Name<Vec<u8>>>;
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn amtrelay_compose_parse_scan() {
+        // The examples from RFC 8777, section 5.
+        let cases: [(TestAmtrelay, &[&str], &str); 4] = [
+            (
+                Amtrelay::new(10, false, AmtrelayRelay::None),
+                &["10", "0", "0", "."],
+                "10 0 0 .",
+            ),
+            (
+                Amtrelay::new(
+                    10,
+                    false,
+                    AmtrelayRelay::Ipv4(Ipv4Addr::new(203, 0, 113, 15)),
+                ),
+                &["10", "0", "1", "203.0.113.15"],
+                "10 0 1 203.0.113.15",
+            ),
+            (
+                Amtrelay::new(
+                    10,
+                    false,
+                    AmtrelayRelay::Ipv6(
+                        Ipv6Addr::from_str("2001:db8::15").unwrap(),
+                    ),
+                ),
+                &["10", "0", "2", "2001:db8::15"],
+                "10 0 2 2001:db8::15",
+            ),
+            (
+                Amtrelay::new(
+                    10,
+                    true,
+                    AmtrelayRelay::Name(
+                        Name::vec_from_str("amtrelays.example.com").unwrap(),
+                    ),
+                ),
+                &["10", "1", "3", "amtrelays.example.com."],
+                "10 1 3 amtrelays.example.com.",
+            ),
+        ];
+        for (rdata, tokens, display) in cases {
+            test_rdlen(&rdata);
+            test_compose_parse(&rdata, |parser| Amtrelay::parse(parser));
+            test_scan(tokens, Amtrelay::scan, &rdata);
+            assert_eq!(rdata.to_string(), display);
+        }
+    }
+
+    #[test]
+    fn amtrelay_wire_format() {
+        let mut buf = Vec::new();
+        TestAmtrelay::new(
+            10,
+            true,
+            AmtrelayRelay::Ipv4(Ipv4Addr::new(203, 0, 113, 15)),
+        )
+        .compose_rdata(&mut buf)
+        .unwrap();
+        assert_eq!(buf, b"\x0a\x81\xcb\x00\x71\x0f");
+
+        let wire = b"\x0a\x84";
+        assert!(
+            Amtrelay::parse(&mut Parser::from_ref(wire.as_ref())).is_err()
+        );
+    }
+}
@@ -0,0 +1,781 @@
+//! Record data from [RFC 3123]: APL records.
+//!
+//! This RFC defines the APL record type.
+//!
+//! [RFC 3123]: https://tools.ietf.org/html/rfc3123
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scanner, ScannerError};
+use crate::base::wire::{Composer, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+#[cfg(feature = "bytes")]
+use bytes::BytesMut;
+use core::cmp::Ordering;
+use core::str::FromStr;
+use core::{fmt, hash};
+use octseq::builder::{EmptyBuilder, FreezeBuilder, OctetsBuilder, ShortBuf};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Apl -----------------------------------------------------------
+
+/// APL record data.
+///
+/// APL records contain a list of address prefixes. Each [item][AplItem]
+/// of the list consists of an IPv4 or IPv6 address prefix and a flag
+/// indicating whether the prefix is negated, i.e., excluded from the list.
+///
+/// The type holds the list in its wire format. In this format, trailing
+/// zero octets of the address are omitted. Only the address families for
+/// IPv4 and IPv6 defined in the RFC are supported. You can iterate over
+/// the items via [`iter`][Self::iter] and create new record data from
+/// items via [`AplBuilder`].
+///
+/// The APL record type is defined in [RFC 3123, section 4].
+///
+/// # Presentation format
+///
+/// In presentation format, the items are given as white-space separated
+/// words of the form `[!]afi:address/prefix` where the optional `!` marks
+/// a negated item, `afi` is the address family, i.e., 1 for IPv4 and 2 for
+/// IPv6, `address` is the IP address, and `prefix` is the length of the
+/// prefix in bits. For example, `1:192.168.32.0/21 !1:192.168.38.0/28`.
+///
+/// [RFC 3123, section 4]: https://tools.ietf.org/html/rfc3123#section-4
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Apl<Octs> {
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "octseq::serde::SerializeOctets::serialize_octets",
+            deserialize_with = "octseq::serde::DeserializeOctets::deserialize_octets",
+            bound(
+                serialize = "Octs: octseq::serde::SerializeOctets",
+                deserialize = "Octs: octseq::serde::DeserializeOctets<'de>",
+            )
+        )
+    )]
+    data: Octs,
+}
+
+impl Apl<()> {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::APL;
+}
+
+impl<Octs> Apl<Octs> {
+    /// Creates new APL record data from its wire format.
+    ///
+    /// Returns an error if `data` does not contain a correctly encoded
+    /// list of items or is longer than 65,535 octets.
+    pub fn from_octets(data: Octs) -> Result<Self, AplError>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        LongRecordData::check_len(data.as_ref().len())?;
+        Self::check_slice(data.as_ref())?;
+        Ok(unsafe { Self::from_octets_unchecked(data) })
+    }
+
+    /// Creates new APL record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that `data` contains a correctly encoded
+    /// list of items and is at most 65,535 octets long.
+    pub unsafe fn from_octets_unchecked(data: Octs) -> Self {
+        Apl { data }
+    }
+
+    /// Returns a reference to the wire format of the record data.
+    pub fn data(&self) -> &Octs {
+        &self.data
+    }
+
+    /// Returns a slice of the wire format of the record data.
+    pub fn as_slice(&self) -> &[u8]
+    where
+        Octs: AsRef<[u8]>,
+    {
+        self.data.as_ref()
+    }
+
+    /// Returns whether the list of items is empty.
+    pub fn is_empty(&self) -> bool
+    where
+        Octs: AsRef<[u8]>,
+    {
+        self.data.as_ref().is_empty()
+    }
+
+    /// Returns an iterator over the items of the list.
+    pub fn iter(&self) -> AplIter<'_>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        AplIter {
+            parser: Parser::from_ref(self.data.as_ref()),
+        }
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Apl<Target>, Target::Error> {
+        Ok(unsafe {
+            Apl::from_octets_unchecked(self.data.try_octets_into()?)
+        })
+    }
+
+    pub(super) fn flatten<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Apl<Target>, Target::Error> {
+        self.convert_octets()
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        let data = parser.parse_octets(parser.remaining())?;
+        Self::check_slice(data.as_ref())
+            .map_err(|err| ParseError::form_error(err.as_str()))?;
+        Ok(unsafe { Self::from_octets_unchecked(data) })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        let mut builder = AplBuilder::from_builder(scanner.octets_builder()?);
+        while scanner.continues() {
+            let item = scanner.scan_ascii_str(|s| {
+                AplItem::from_str(s)
+                    .map_err(|err| S::Error::custom(err.as_str()))
+            })?;
+            builder
+                .push(&item)
+                .map_err(|err| S::Error::custom(err.as_str()))?;
+        }
+        Ok(builder.finish())
+    }
+
+    /// Checks that a slice contains a correctly encoded list of items.
+    fn check_slice(slice: &[u8]) -> Result<(), AplError> {
+        let mut parser = Parser::from_ref(slice);
+        while parser.remaining() > 0 {
+            AplItem::parse(&mut parser)?;
+        }
+        Ok(())
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Apl<SrcOcts>> for Apl<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Apl<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Apl::from_octets_unchecked(Octs::try_octets_from(source.data)?)
+        })
+    }
+}
+
+//--- IntoIterator
+
+impl<'a, Octs: AsRef<[u8]>> IntoIterator for &'a Apl<Octs> {
+    type Item = AplItem;
+    type IntoIter = AplIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Apl<Other>> for Apl<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Apl<Other>) -> bool {
+        self.data.as_ref().eq(other.data.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Apl<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Apl<Other>> for Apl<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Apl<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Apl<Other>> for Apl<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Apl<Other>) -> Ordering {
+        self.data.as_ref().cmp(other.data.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Apl<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Apl<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.data.as_ref().hash(state)
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Apl<Octs> {
+    fn rtype(&self) -> Rtype {
+        Apl::RTYPE
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Apl<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Apl::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Apl<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            u16::try_from(self.data.as_ref().len())
+                .expect("long APL record data"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        target.append_slice(self.data.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Apl<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, item) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            item.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Apl<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Apl(")?;
+        f.debug_list().entries(self.iter()).finish()?;
+        f.write_str(")")
+    }
+}
+
+//--- ZonefileFmt
+
+impl<Octs: AsRef<[u8]>> ZonefileFmt for Apl<Octs> {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| {
+            for item in self.iter() {
+                p.write_token(item)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+//------------ AplItem -------------------------------------------------------
+
+/// A single item of an APL record.
+///
+/// An item consists of an address prefix given as an IP address and a
+/// prefix length in bits and a flag whether the prefix is negated.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AplItem {
+    /// Whether the prefix is negated.
+    negation: bool,
+
+    /// The address of the prefix.
+    addr: IpAddr,
+
+    /// The length of the prefix in bits.
+    prefix_len: u8,
+}
+
+impl AplItem {
+    /// The address family number for IPv4.
+    pub const FAMILY_IPV4: u16 = 1;
+
+    /// The address family number for IPv6.
+    pub const FAMILY_IPV6: u16 = 2;
+
+    /// Creates a new item from its components.
+    ///
+    /// Returns an error if the prefix is longer than the address.
+    pub fn new(
+        negation: bool,
+        addr: IpAddr,
+        prefix_len: u8,
+    ) -> Result<Self, AplError> {
+        let max = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        if prefix_len > max {
+            return Err(AplError::LongPrefix);
+        }
+        Ok(AplItem {
+            negation,
+            addr,
+            prefix_len,
+        })
+    }
+
+    /// Returns whether the prefix is negated.
+    pub fn negation(&self) -> bool {
+        self.negation
+    }
+
+    /// Returns the address family of the item.
+    pub fn family(&self) -> u16 {
+        match self.addr {
+            IpAddr::V4(_) => Self::FAMILY_IPV4,
+            IpAddr::V6(_) => Self::FAMILY_IPV6,
+        }
+    }
+
+    /// Returns the address of the prefix.
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the length of the prefix in bits.
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns the address octets with trailing zeros removed.
+    fn afdpart(&self, buf: &mut [u8; 16]) -> usize {
+        let len = match self.addr {
+            IpAddr::V4(addr) => {
+                buf[..4].copy_from_slice(&addr.octets());
+                4
+            }
+            IpAddr::V6(addr) => {
+                buf.copy_from_slice(&addr.octets());
+                16
+            }
+        };
+        buf[..len]
+            .iter()
+            .rposition(|&octet| octet != 0)
+            .map(|pos| pos + 1)
+            .unwrap_or(0)
+    }
+
+    /// Returns the length of the wire format of the item.
+    pub fn compose_len(&self) -> usize {
+        4 + self.afdpart(&mut [0; 16])
+    }
+
+    /// Appends the wire format of the item to a target.
+    pub fn compose<Target: OctetsBuilder + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        let mut buf = [0; 16];
+        let len = self.afdpart(&mut buf);
+        target.append_slice(&self.family().to_be_bytes())?;
+        target.append_slice(&[
+            self.prefix_len,
+            // There are at most 16 octets, so the `as` is fine.
+            if self.negation { 0x80 } else { 0 } | len as u8,
+        ])?;
+        target.append_slice(&buf[..len])
+    }
+
+    /// Parses an item from the beginning of a parser.
+    fn parse<Octs: AsRef<[u8]> + ?Sized>(
+        parser: &mut Parser<Octs>,
+    ) -> Result<Self, AplError> {
+        let mut head = [0u8; 4];
+        parser
+            .parse_buf(&mut head)
+            .map_err(|_| AplError::ShortInput)?;
+        let family = u16::from_be_bytes([head[0], head[1]]);
+        let prefix_len = head[2];
+        let negation = head[3] & 0x80 != 0;
+        let len = usize::from(head[3] & 0x7F);
+        let mut buf = [0u8; 16];
+        let (addr, max) = match family {
+            Self::FAMILY_IPV4 => {
+                if len > 4 {
+                    return Err(AplError::LongAfdPart);
+                }
+                parser
+                    .parse_buf(&mut buf[..len])
+                    .map_err(|_| AplError::ShortInput)?;
+                (
+                    IpAddr::V4(Ipv4Addr::new(buf[0], buf[1], buf[2], buf[3])),
+                    32,
+                )
+            }
+            Self::FAMILY_IPV6 => {
+                if len > 16 {
+                    return Err(AplError::LongAfdPart);
+                }
+                parser
+                    .parse_buf(&mut buf[..len])
+                    .map_err(|_| AplError::ShortInput)?;
+                (IpAddr::V6(Ipv6Addr::from(buf)), 128)
+            }
+            _ => return Err(AplError::UnknownFamily),
+        };
+        if prefix_len > max {
+            return Err(AplError::LongPrefix);
+        }
+        Ok(AplItem {
+            negation,
+            addr,
+            prefix_len,
+        })
+    }
+}
+
+//--- FromStr
+
+impl FromStr for AplItem {
+    type Err = AplError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negation, s) = match s.strip_prefix('!') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+        let (family, s) = s.split_once(':').ok_or(AplError::InvalidFormat)?;
+        let (addr, prefix_len) =
+            s.split_once('/').ok_or(AplError::InvalidFormat)?;
+        let prefix_len =
+            u8::from_str(prefix_len).map_err(|_| AplError::InvalidFormat)?;
+        let addr = match u16::from_str(family) {
+            Ok(Self::FAMILY_IPV4) => IpAddr::V4(
+                Ipv4Addr::from_str(addr)
+                    .map_err(|_| AplError::InvalidFormat)?,
+            ),
+            Ok(Self::FAMILY_IPV6) => IpAddr::V6(
+                Ipv6Addr::from_str(addr)
+                    .map_err(|_| AplError::InvalidFormat)?,
+            ),
+            Ok(_) => return Err(AplError::UnknownFamily),
+            Err(_) => return Err(AplError::InvalidFormat),
+        };
+        Self::new(negation, addr, prefix_len)
+    }
+}
+
+//--- Display
+
+impl fmt::Display for AplItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.negation {
+            f.write_str("!")?;
+        }
+        write!(f, "{}:", self.family())?;
+        match self.addr {
+            IpAddr::V4(addr) => addr.fmt(f)?,
+            IpAddr::V6(addr) => addr.fmt(f)?,
+        }
+        write!(f, "/{}", self.prefix_len)
+    }
+}
+
+//------------ AplIter -------------------------------------------------------
+
+/// An iterator over the items of APL record data.
+///
+/// The iterator is returned by [`Apl::iter`].
+#[derive(Clone, Debug)]
+pub struct AplIter<'a> {
+    parser: Parser<'a, [u8]>,
+}
+
+impl<'a> Iterator for AplIter<'a> {
+    type Item = AplItem;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.parser.remaining() == 0 {
+            return None;
+        }
+        // The content has been checked upon creation. Only serde
+        // deserialization can sneak in invalid data, in which case we
+        // simply stop.
+        AplItem::parse(&mut self.parser).ok()
+    }
+}
+
+//------------ AplBuilder ----------------------------------------------------
+
+/// Iteratively build APL record data.
+///
+/// This type allows building APL record data by starting with an empty
+/// list and pushing items to it.
+#[derive(Clone, Debug)]
+pub struct AplBuilder<Builder> {
+    builder: Builder,
+}
+
+impl<Builder: OctetsBuilder + EmptyBuilder> AplBuilder<Builder> {
+    /// Creates a new, empty APL builder.
+    #[must_use]
+    pub fn new() -> Self {
+        AplBuilder {
+            builder: Builder::empty(),
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl AplBuilder<BytesMut> {
+    /// Creates a new, empty APL builder using `BytesMut`.
+    pub fn new_bytes() -> Self {
+        Self::new()
+    }
+}
+
+impl<Builder: OctetsBuilder + AsRef<[u8]>> AplBuilder<Builder> {
+    /// Creates a new APL builder atop an existing, empty octets builder.
+    fn from_builder(builder: Builder) -> Self {
+        AplBuilder { builder }
+    }
+
+    /// Appends an item to the list.
+    ///
+    /// The method will return an error if appending the item would result
+    /// in exceeding the record data length limit or the underlying builder
+    /// runs out of space.
+    pub fn push(&mut self, item: &AplItem) -> Result<(), AplError> {
+        LongRecordData::check_append_len(
+            self.builder.as_ref().len(),
+            item.compose_len(),
+        )?;
+        item.compose(&mut self.builder)
+            .map_err(|_| AplError::ShortBuf)
+    }
+
+    /// Finishes the builder and returns the record data.
+    pub fn finish(self) -> Apl<Builder::Octets>
+    where
+        Builder: FreezeBuilder,
+    {
+        unsafe { Apl::from_octets_unchecked(self.builder.freeze()) }
+    }
+}
+
+//--- Default
+
+impl<Builder: OctetsBuilder + EmptyBuilder> Default for AplBuilder<Builder> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//============ Error Types ===================================================
+
+//------------ AplError ------------------------------------------------------
+
+/// An error happened while creating APL record data or one of its items.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AplError {
+    /// The data ended in the middle of an item.
+    ShortInput,
+
+    /// An item used an address family other than IPv4 or IPv6.
+    UnknownFamily,
+
+    /// The prefix length was longer than the address.
+    LongPrefix,
+
+    /// The address part was longer than the address.
+    LongAfdPart,
+
+    /// The presentation format of an item was invalid.
+    InvalidFormat,
+
+    /// The record data was longer than 65,535 octets.
+    LongRecordData,
+
+    /// The octets builder ran out of space.
+    ShortBuf,
+}
+
+impl AplError {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AplError::ShortInput => "short APL item",
+            AplError::UnknownFamily => "unknown APL address family",
+            AplError::LongPrefix => "APL prefix exceeds address length",
+            AplError::LongAfdPart => {
+                "APL address part exceeds address length"
+            }
+            AplError::InvalidFormat => "invalid APL item",
+            AplError::LongRecordData => "record data exceeds 65,535 bytes",
+            AplError::ShortBuf => "buffer size exceeded",
+        }
+    }
+}
+
+impl From<LongRecordData> for AplError {
+    fn from(_: LongRecordData) -> Self {
+        AplError::LongRecordData
+    }
+}
+
+impl From<ShortBuf> for AplError {
+    fn from(_: ShortBuf) -> Self {
+        AplError::ShortBuf
+    }
+}
+
+impl fmt::Display for AplError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AplError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn apl_compose_parse_scan() {
+        let mut builder = AplBuilder::<Vec<u8>>::new();
+        for item in ["1:192.168.32.0/21", "!1:192.168.38.0/28", "2:ff00::/8"]
+        {
+            builder.push(&AplItem::from_str(item).unwrap()).unwrap();
+        }
+        let rdata = builder.finish();
+        assert_eq!(
+            rdata.as_slice(),
+            b"\x00\x01\x15\x03\xc0\xa8\x20\
+              \x00\x01\x1c\x83\xc0\xa8\x26\
+              \x00\x02\x08\x01\xff"
+        );
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Apl::parse(parser));
+        test_scan(
+            &["1:192.168.32.0/21", "!1:192.168.38.0/28", "2:ff00::/8"],
+            Apl::scan,
+            &rdata,
+        );
+        assert_eq!(
+            rdata.to_string(),
+            "1:192.168.32.0/21 !1:192.168.38.0/28 2:ff00::/8"
+        );
+    }
+
+    #[test]
+    fn apl_empty() {
+        let rdata = Apl::from_octets(Vec::new()).unwrap();
+        assert!(rdata.is_empty());
+        assert_eq!(rdata.iter().count(), 0);
+
+        let item = AplItem::from_str("1:0.0.0.0/0").unwrap();
+        assert_eq!(item.compose_len(), 4);
+    }
+
+    #[test]
+    fn apl_invalid() {
+        assert_eq!(
+            AplItem::from_str("1:10.0.0.0/33"),
+            Err(AplError::LongPrefix)
+        );
+        assert_eq!(
+            AplItem::from_str("3:10.0.0.0/8"),
+            Err(AplError::UnknownFamily)
+        );
+        assert_eq!(
+            AplItem::from_str("10.0.0.0/8"),
+            Err(AplError::InvalidFormat)
+        );
+        assert_eq!(
+            Apl::from_octets(b"\x00\x01\x08\x05\x0a\0\0\0\0".as_ref()),
+            Err(AplError::LongAfdPart)
+        );
+        assert_eq!(
+            Apl::from_octets(b"\x00\x01\x08\x02\x0a".as_ref()),
+            Err(AplError::ShortInput)
+        );
+        assert_eq!(
+            Apl::from_octets(b"\x00\x03\x08\x01\x0a".as_ref()),
+            Err(AplError::UnknownFamily)
+        );
+    }
+}
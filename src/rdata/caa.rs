@@ -0,0 +1,509 @@
+//! Record data from [RFC 8659]: CAA records.
+//!
+//! This RFC defines the CAA record type.
+//!
+//! [RFC 8659]: https://tools.ietf.org/html/rfc8659
+
+use crate::base::charstr::CharStr;
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scan, Scanner, ScannerError, Symbol};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use core::cmp::Ordering;
+use core::{fmt, hash};
+#[cfg(feature = "serde")]
+use octseq::builder::{EmptyBuilder, FromBuilder};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Caa -----------------------------------------------------------
+
+/// Caa record data.
+///
+/// Caa records allow the holder of a domain name to specify which
+/// certification authorities are authorized to issue certificates for the
+/// domain. Each record consists of a flags octet, a property tag, and the
+/// value of that property.
+///
+/// The tag is a non-empty sequence of at most 255 ASCII letters and digits.
+/// The meaning of the value depends on the tag. The tags `issue`,
+/// `issuewild`, and `iodef` are defined in RFC 8659 itself.
+///
+/// The Caa record type is defined in [RFC 8659, section 4][1].
+///
+/// [1]: https://tools.ietf.org/html/rfc8659#section-4
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "Octs: AsRef<[u8]> + octseq::serde::SerializeOctets",
+        deserialize = "Octs: \
+                FromBuilder \
+                + octseq::serde::DeserializeOctets<'de>, \
+            <Octs as FromBuilder>::Builder: AsRef<[u8]> + EmptyBuilder ",
+    ))
+)]
+pub struct Caa<Octs> {
+    flags: u8,
+    tag: CharStr<Octs>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "octseq::serde::SerializeOctets::serialize_octets",
+            deserialize_with = "octseq::serde::DeserializeOctets::deserialize_octets",
+        )
+    )]
+    value: Octs,
+}
+
+impl Caa<()> {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::CAA;
+
+    /// The issuer critical flag.
+    ///
+    /// If this flag is set, a certification authority must not issue
+    /// a certificate if it doesn’t understand the property tag.
+    pub const CRITICAL: u8 = 0x80;
+}
+
+impl<Octs> Caa<Octs> {
+    /// Creates new Caa record data from its components.
+    ///
+    /// Returns an error if the tag is empty or contains anything other than
+    /// ASCII letters and digits or if the record data would be too long.
+    pub fn new(
+        flags: u8,
+        tag: CharStr<Octs>,
+        value: Octs,
+    ) -> Result<Self, CaaError>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Self::check_tag(tag.as_slice())?;
+        LongRecordData::check_len(
+            usize::from(tag.compose_len()) + 1 + value.as_ref().len(),
+        )?;
+        Ok(unsafe { Self::new_unchecked(flags, tag, value) })
+    }
+
+    /// Creates new Caa record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that the tag is valid and that the
+    /// record data is not longer than 65,535 octets.
+    pub unsafe fn new_unchecked(
+        flags: u8,
+        tag: CharStr<Octs>,
+        value: Octs,
+    ) -> Self {
+        Caa { flags, tag, value }
+    }
+
+    /// Returns the flags octet.
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// Returns whether the issuer critical flag is set.
+    pub fn is_critical(&self) -> bool {
+        self.flags & Caa::CRITICAL != 0
+    }
+
+    /// Returns the property tag.
+    pub fn tag(&self) -> &CharStr<Octs> {
+        &self.tag
+    }
+
+    /// Returns the property value.
+    pub fn value(&self) -> &Octs {
+        &self.value
+    }
+
+    /// Checks that a tag is non-empty and only contains letters and digits.
+    fn check_tag(tag: &[u8]) -> Result<(), CaaError> {
+        if tag.is_empty() || !tag.iter().all(u8::is_ascii_alphanumeric) {
+            Err(CaaError::InvalidTag)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(in crate::rdata) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Caa<Target>, Target::Error> {
+        Ok(Caa {
+            flags: self.flags,
+            tag: self.tag.try_octets_into()?,
+            value: self.value.try_octets_into()?,
+        })
+    }
+
+    pub(in crate::rdata) fn flatten<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Caa<Target>, Target::Error> {
+        self.convert_octets()
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        let flags = u8::parse(parser)?;
+        let tag = CharStr::parse(parser)?;
+        Self::check_tag(tag.as_slice())
+            .map_err(|_| ParseError::form_error("invalid CAA tag"))?;
+        let value = parser.parse_octets(parser.remaining())?;
+        Ok(Caa { flags, tag, value })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        let flags = u8::scan(scanner)?;
+        let tag = scanner.scan_charstr()?;
+        let value = scanner.scan_octets()?;
+        Self::new(flags, tag, value)
+            .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Caa<SrcOcts>> for Caa<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Caa<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(Caa {
+            flags: source.flags,
+            tag: CharStr::try_octets_from(source.tag)?,
+            value: Octs::try_octets_from(source.value)?,
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Caa<Other>> for Caa<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Caa<Other>) -> bool {
+        self.flags == other.flags
+            && self.tag.eq(&other.tag)
+            && self.value.as_ref() == other.value.as_ref()
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Caa<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Caa<Other>> for Caa<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Caa<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Caa<Other>> for Caa<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Caa<Other>) -> Ordering {
+        match self.flags.cmp(&other.flags) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.tag.canonical_cmp(&other.tag) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.value.as_ref().cmp(other.value.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Caa<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Caa<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.flags.hash(state);
+        self.tag.hash(state);
+        self.value.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Caa<Octs> {
+    fn rtype(&self) -> Rtype {
+        Caa::RTYPE
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Caa<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Caa::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Caa<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            u16::try_from(self.value.as_ref().len())
+                .expect("long CAA rdata")
+                .checked_add(self.tag.compose_len() + 1)
+                .expect("long CAA rdata"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.flags.compose(target)?;
+        self.tag.compose(target)?;
+        target.append_slice(self.value.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Caa<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.flags,
+            self.tag.display_unquoted(),
+            DisplayValue(self.value.as_ref())
+        )
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Caa<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Caa")
+            .field("flags", &self.flags)
+            .field("tag", &self.tag)
+            .field("value", &DisplayValue(self.value.as_ref()))
+            .finish()
+    }
+}
+
+//--- ZonefileFmt
+
+impl<Octs: AsRef<[u8]>> ZonefileFmt for Caa<Octs> {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| {
+            p.write_token(self.flags)?;
+            p.write_comment(format_args!(
+                "flags (critical: {})",
+                self.is_critical()
+            ))?;
+            p.write_token(self.tag.display_unquoted())?;
+            p.write_token(DisplayValue(self.value.as_ref()))
+        })
+    }
+}
+
+//------------ DisplayValue --------------------------------------------------
+
+/// Helper struct for displaying the value as a quoted string.
+struct DisplayValue<'a>(&'a [u8]);
+
+impl fmt::Display for DisplayValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("\"")?;
+        for &ch in self.0 {
+            fmt::Display::fmt(&Symbol::quoted_from_octet(ch), f)?;
+        }
+        f.write_str("\"")
+    }
+}
+
+impl fmt::Debug for DisplayValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+//============ Error Types ===================================================
+
+//------------ CaaError ------------------------------------------------------
+
+/// Caa record data could not be created from the given components.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CaaError {
+    /// The tag was empty or contained characters other than letters and
+    /// digits.
+    InvalidTag,
+
+    /// The record data would have been too long.
+    LongRecordData,
+}
+
+impl CaaError {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CaaError::InvalidTag => "invalid CAA tag",
+            CaaError::LongRecordData => "record data too long",
+        }
+    }
+}
+
+impl From<LongRecordData> for CaaError {
+    fn from(_: LongRecordData) -> Self {
+        CaaError::LongRecordData
+    }
+}
+
+impl fmt::Display for CaaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CaaError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    fn caa(flags: u8, tag: &str, value: &str) -> Caa<Vec<u8>> {
+        Caa::new(flags, tag.parse().unwrap(), value.as_bytes().into())
+            .unwrap()
+    }
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn caa_compose_parse_scan() {
+        let rdata = caa(0, "issue", "ca.example.net");
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Caa::parse(parser));
+        test_scan(&["0", "issue", "ca.example.net"], Caa::scan, &rdata);
+
+        let rdata = caa(128, "tbs", "Unknown");
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Caa::parse(parser));
+        test_scan(&["128", "tbs", "Unknown"], Caa::scan, &rdata);
+    }
+
+    #[test]
+    fn caa_scan_invalid_flags() {
+        use crate::base::scan::IterScanner;
+
+        fn scan_err(input: &[&str]) {
+            let mut scanner = IterScanner::<_, Vec<u8>>::new(
+                input
+                    .iter()
+                    .map(|s| std::string::String::from(*s))
+                    .collect::<Vec<_>>(),
+            );
+            assert!(Caa::scan(&mut scanner).is_err(), "{:?}", input);
+        }
+
+        scan_err(&["256", "issue", "x"]);
+        scan_err(&["1000", "issue", "x"]);
+    }
+
+    #[test]
+    fn caa_wire() {
+        // RFC 8659, section 4.1.1.
+        let rdata = caa(0, "issue", "ca.example.net");
+        let mut buf = Vec::new();
+        rdata.compose_rdata(&mut buf).unwrap();
+        assert_eq!(buf, b"\x00\x05issueca.example.net");
+    }
+
+    #[test]
+    fn caa_critical() {
+        assert!(!caa(0, "issue", "ca.example.net").is_critical());
+        assert!(caa(128, "tbs", "Unknown").is_critical());
+        assert!(caa(129, "tbs", "Unknown").is_critical());
+    }
+
+    #[test]
+    fn caa_invalid_tag() {
+        let new = |tag: &str| {
+            Caa::new(0, tag.parse().unwrap(), Vec::new()).map(|_| ())
+        };
+        assert_eq!(new("issue"), Ok(()));
+        assert_eq!(new("Issue2"), Ok(()));
+        assert_eq!(new(""), Err(CaaError::InvalidTag));
+        assert_eq!(new("issue-wild"), Err(CaaError::InvalidTag));
+        assert_eq!(new("iss ue"), Err(CaaError::InvalidTag));
+
+        assert!(
+            Caa::parse(&mut Parser::from_ref(b"\x00\x00".as_ref())).is_err()
+        );
+        assert!(Caa::parse(&mut Parser::from_ref(b"\x00\x02a-".as_ref()))
+            .is_err());
+    }
+
+    #[test]
+    fn caa_display() {
+        assert_eq!(
+            caa(0, "issue", "ca.example.net; account=230123").to_string(),
+            r#"0 issue "ca.example.net; account=230123""#
+        );
+        assert_eq!(
+            caa(0, "iodef", "mailto:\"security\"@example.com").to_string(),
+            r#"0 iodef "mailto:\"security\"@example.com""#
+        );
+    }
+}
@@ -1,6 +1,10 @@
 //! Record data from [RFC 7344]: CDS and CDNSKEY records.
 //!
+//! The special record data used to request the deletion of the DS records
+//! of a zone is defined in [RFC 8078].
+//!
 //! [RFC 7344]: https://tools.ietf.org/html/rfc7344
+//! [RFC 8078]: https://tools.ietf.org/html/rfc8078
 use crate::base::cmp::CanonicalOrd;
 use crate::base::iana::{DigestAlg, Rtype, SecAlg};
 use crate::base::rdata::{
@@ -93,6 +97,41 @@ impl<Octs> Cdnskey<Octs> {
         }
     }
 
+    /// Creates the CDNSKEY record data requesting DS deletion.
+    ///
+    /// This is the record data `0 3 0 AA==` defined in [RFC 8078,
+    /// section 4]. It signals to the parent that all DS records for the
+    /// child zone should be removed.
+    ///
+    /// [RFC 8078, section 4]: https://tools.ietf.org/html/rfc8078#section-4
+    #[must_use]
+    pub fn delete() -> Self
+    where
+        Octs: From<&'static [u8]>,
+    {
+        unsafe {
+            Cdnskey::new_unchecked(
+                0,
+                3,
+                SecAlg::DELETE,
+                b"\0".as_ref().into(),
+            )
+        }
+    }
+
+    /// Returns whether this is the record data requesting DS deletion.
+    ///
+    /// See [`delete`][Self::delete] for details.
+    pub fn is_delete(&self) -> bool
+    where
+        Octs: AsRef<[u8]>,
+    {
+        self.flags == 0
+            && self.protocol == 3
+            && self.algorithm == SecAlg::DELETE
+            && self.public_key.as_ref() == b"\0"
+    }
+
     pub fn flags(&self) -> u16 {
         self.flags
     }
@@ -309,7 +348,13 @@ impl<Octs: AsRef<[u8]>> ComposeRecordData for Cdnskey<Octs> {
 
 impl<Octs: AsRef<[u8]>> fmt::Display for Cdnskey<Octs> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} {} {} ", self.flags, self.protocol, self.algorithm)?;
+        write!(
+            f,
+            "{} {} {} ",
+            self.flags,
+            self.protocol,
+            self.algorithm.to_int()
+        )?;
         base64::display(&self.public_key, f)
     }
 }
@@ -420,6 +465,41 @@ impl<Octs> Cds<Octs> {
         }
     }
 
+    /// Creates the CDS record data requesting DS deletion.
+    ///
+    /// This is the record data `0 0 0 00` defined in [RFC 8078,
+    /// section 4]. It signals to the parent that all DS records for the
+    /// child zone should be removed.
+    ///
+    /// [RFC 8078, section 4]: https://tools.ietf.org/html/rfc8078#section-4
+    #[must_use]
+    pub fn delete() -> Self
+    where
+        Octs: From<&'static [u8]>,
+    {
+        unsafe {
+            Cds::new_unchecked(
+                0,
+                SecAlg::DELETE,
+                DigestAlg::from_int(0),
+                b"\0".as_ref().into(),
+            )
+        }
+    }
+
+    /// Returns whether this is the record data requesting DS deletion.
+    ///
+    /// See [`delete`][Self::delete] for details.
+    pub fn is_delete(&self) -> bool
+    where
+        Octs: AsRef<[u8]>,
+    {
+        self.key_tag == 0
+            && self.algorithm == SecAlg::DELETE
+            && self.digest_type == DigestAlg::from_int(0)
+            && self.digest.as_ref() == b"\0"
+    }
+
     pub fn key_tag(&self) -> u16 {
         self.key_tag
     }
@@ -654,7 +734,9 @@ impl<Octs: AsRef<[u8]>> fmt::Display for Cds<Octs> {
         write!(
             f,
             "{} {} {} ",
-            self.key_tag, self.algorithm, self.digest_type
+            self.key_tag,
+            self.algorithm.to_int(),
+            self.digest_type.to_int()
         )?;
         for ch in self.digest.as_ref() {
             write!(f, "{:02x}", ch)?
@@ -717,6 +799,17 @@ mod test {
         test_scan(&["10", "11", "5", "a2V5"], Cdnskey::scan, &rdata);
     }
 
+    #[test]
+    fn cdnskey_delete() {
+        let rdata = Cdnskey::<&[u8]>::delete();
+        assert!(rdata.is_delete());
+        test_scan(&["0", "3", "0", "AA=="], Cdnskey::scan, &rdata);
+        assert!(!Cdnskey::new(0, 3, SecAlg::DELETE, b"").unwrap().is_delete());
+        assert!(!Cdnskey::new(256, 3, SecAlg::DELETE, b"\0")
+            .unwrap()
+            .is_delete());
+    }
+
     //--- Cds
 
     #[test]
@@ -728,4 +821,17 @@ mod test {
         test_compose_parse(&rdata, |parser| Cds::parse(parser));
         test_scan(&["10", "5", "2", "6b6579"], Cds::scan, &rdata);
     }
+
+    #[test]
+    fn cds_delete() {
+        let rdata = Cds::<&[u8]>::delete();
+        assert!(rdata.is_delete());
+        test_scan(&["0", "0", "0", "00"], Cds::scan, &rdata);
+        assert!(!Cds::new(0, SecAlg::DELETE, DigestAlg::from_int(0), b"")
+            .unwrap()
+            .is_delete());
+        assert!(!Cds::new(0, SecAlg::RSASHA1, DigestAlg::SHA1, b"\0")
+            .unwrap()
+            .is_delete());
+    }
 }
@@ -0,0 +1,443 @@
+//! Record data from [RFC 4398]: CERT records.
+//!
+//! This RFC defines the CERT record type.
+//!
+//! [RFC 4398]: https://tools.ietf.org/html/rfc4398
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::{CertType, Rtype, SecAlg};
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scan, Scanner, ScannerError};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use crate::utils::base64;
+use core::cmp::Ordering;
+use core::{fmt, hash};
+#[cfg(feature = "serde")]
+use octseq::builder::{EmptyBuilder, FromBuilder, OctetsBuilder};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Cert ----------------------------------------------------------
+
+/// Cert record data.
+///
+/// Cert records store certificates and certificate revocation lists in the
+/// DNS. The certificate type describes the format of the certificate. The
+/// key tag and algorithm identify the public key the certificate is for in
+/// the same way as for DNSSEC records. A key tag and algorithm of zero
+/// means that they are unknown.
+///
+/// In presentation format, the certificate is given in base64.
+///
+/// The Cert record type is defined in [RFC 4398, section 2][1].
+///
+/// [1]: https://tools.ietf.org/html/rfc4398#section-2
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs: FromBuilder + octseq::serde::DeserializeOctets<'de>,
+            <Octs as FromBuilder>::Builder:
+                OctetsBuilder + EmptyBuilder,
+        ",
+    ))
+)]
+pub struct Cert<Octs> {
+    cert_type: CertType,
+    key_tag: u16,
+    algorithm: SecAlg,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base64::serde")
+    )]
+    certificate: Octs,
+}
+
+impl Cert<()> {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::CERT;
+}
+
+impl<Octs> Cert<Octs> {
+    /// Creates new Cert record data from its components.
+    ///
+    /// Returns an error if the record data would be too long.
+    pub fn new(
+        cert_type: CertType,
+        key_tag: u16,
+        algorithm: SecAlg,
+        certificate: Octs,
+    ) -> Result<Self, LongRecordData>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        LongRecordData::check_len(
+            usize::from(
+                CertType::COMPOSE_LEN
+                    + u16::COMPOSE_LEN
+                    + SecAlg::COMPOSE_LEN,
+            )
+            .checked_add(certificate.as_ref().len())
+            .expect("long certificate"),
+        )?;
+        Ok(unsafe {
+            Cert::new_unchecked(cert_type, key_tag, algorithm, certificate)
+        })
+    }
+
+    /// Creates new Cert record data for a DER-encoded X.509 certificate.
+    ///
+    /// The certificate type will be [`CertType::PKIX`] and both the key
+    /// tag and the algorithm will be zero, marking them as unknown. The
+    /// content of `der` is not checked.
+    ///
+    /// Returns an error if the record data would be too long.
+    pub fn from_der(der: Octs) -> Result<Self, LongRecordData>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Self::new(CertType::PKIX, 0, SecAlg::from_int(0), der)
+    }
+
+    /// Creates new Cert record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that wire format representation of the
+    /// record data is at most 65,535 octets long.
+    pub unsafe fn new_unchecked(
+        cert_type: CertType,
+        key_tag: u16,
+        algorithm: SecAlg,
+        certificate: Octs,
+    ) -> Self {
+        Cert {
+            cert_type,
+            key_tag,
+            algorithm,
+            certificate,
+        }
+    }
+
+    /// Returns the format of the certificate.
+    pub fn cert_type(&self) -> CertType {
+        self.cert_type
+    }
+
+    /// Returns the key tag of the certified key.
+    pub fn key_tag(&self) -> u16 {
+        self.key_tag
+    }
+
+    /// Returns the algorithm of the certified key.
+    pub fn algorithm(&self) -> SecAlg {
+        self.algorithm
+    }
+
+    /// Returns the certificate or CRL.
+    pub fn certificate(&self) -> &Octs {
+        &self.certificate
+    }
+
+    /// Converts the record data into the certificate or CRL.
+    pub fn into_certificate(self) -> Octs {
+        self.certificate
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Cert<Target>, Target::Error> {
+        Ok(unsafe {
+            Cert::new_unchecked(
+                self.cert_type,
+                self.key_tag,
+                self.algorithm,
+                self.certificate.try_octets_into()?,
+            )
+        })
+    }
+
+    pub(super) fn flatten<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Cert<Target>, Target::Error> {
+        self.convert_octets()
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let len = match parser.remaining().checked_sub(5) {
+            Some(len) => len,
+            None => return Err(ParseError::ShortInput),
+        };
+        Ok(unsafe {
+            Self::new_unchecked(
+                CertType::parse(parser)?,
+                u16::parse(parser)?,
+                SecAlg::parse(parser)?,
+                parser.parse_octets(len)?,
+            )
+        })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Self::new(
+            CertType::scan(scanner)?,
+            u16::scan(scanner)?,
+            SecAlg::scan(scanner)?,
+            scanner.convert_entry(base64::SymbolConverter::new())?,
+        )
+        .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Cert<SrcOcts>> for Cert<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Cert<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Cert::new_unchecked(
+                source.cert_type,
+                source.key_tag,
+                source.algorithm,
+                Octs::try_octets_from(source.certificate)?,
+            )
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Cert<Other>> for Cert<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Cert<Other>) -> bool {
+        self.cert_type == other.cert_type
+            && self.key_tag == other.key_tag
+            && self.algorithm == other.algorithm
+            && self.certificate.as_ref().eq(other.certificate.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Cert<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Cert<Other>> for Cert<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Cert<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Cert<Other>> for Cert<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Cert<Other>) -> Ordering {
+        match self.cert_type.cmp(&other.cert_type) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.key_tag.cmp(&other.key_tag) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.algorithm.cmp(&other.algorithm) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.certificate.as_ref().cmp(other.certificate.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Cert<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Cert<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.cert_type.hash(state);
+        self.key_tag.hash(state);
+        self.algorithm.hash(state);
+        self.certificate.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Cert<Octs> {
+    fn rtype(&self) -> Rtype {
+        Cert::RTYPE
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Cert<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Cert::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Cert<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            u16::checked_add(
+                CertType::COMPOSE_LEN
+                    + u16::COMPOSE_LEN
+                    + SecAlg::COMPOSE_LEN,
+                self.certificate
+                    .as_ref()
+                    .len()
+                    .try_into()
+                    .expect("long certificate"),
+            )
+            .expect("long certificate"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.cert_type.compose(target)?;
+        self.key_tag.compose(target)?;
+        self.algorithm.compose(target)?;
+        target.append_slice(self.certificate.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Cert<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} ",
+            self.cert_type.to_int(),
+            self.key_tag,
+            self.algorithm.to_int()
+        )?;
+        base64::display(&self.certificate, f)
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Cert<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Cert")
+            .field("cert_type", &self.cert_type)
+            .field("key_tag", &self.key_tag)
+            .field("algorithm", &self.algorithm)
+            .field("certificate", &self.certificate.as_ref())
+            .finish()
+    }
+}
+
+//--- ZonefileFmt
+
+impl<Octs: AsRef<[u8]>> ZonefileFmt for Cert<Octs> {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| {
+            p.write_show(self.cert_type)?;
+            p.write_token(self.key_tag)?;
+            p.write_comment("key tag")?;
+            p.write_show(self.algorithm)?;
+            p.write_token(base64::encode_display(&self.certificate))
+        })
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn cert_compose_parse_scan() {
+        let rdata = Cert::new(
+            CertType::PGP,
+            12345,
+            SecAlg::RSASHA256,
+            b"certificate".to_vec(),
+        )
+        .unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Cert::parse(parser));
+        test_scan(
+            &["3", "12345", "8", "Y2VydGlmaWNhdGU="],
+            Cert::scan,
+            &rdata,
+        );
+        test_scan(
+            &["pgp", "12345", "RSASHA256", "Y2VydGlm", "aWNhdGU="],
+            Cert::scan,
+            &rdata,
+        );
+        assert_eq!(rdata.to_string(), "3 12345 8 Y2VydGlmaWNhdGU=");
+    }
+
+    #[test]
+    fn cert_from_der() {
+        let der = Vec::from(b"\x30\x03\x02\x01\x00".as_ref());
+        let rdata = Cert::from_der(der.clone()).unwrap();
+        assert_eq!(rdata.cert_type(), CertType::PKIX);
+        assert_eq!(rdata.key_tag(), 0);
+        assert_eq!(rdata.algorithm().to_int(), 0);
+        assert_eq!(rdata.certificate(), &der);
+        assert_eq!(rdata.to_string(), "1 0 0 MAMCAQA=");
+    }
+}
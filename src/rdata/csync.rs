@@ -0,0 +1,360 @@
+//! Record data from [RFC 7477]: CSYNC records.
+//!
+//! This RFC defines the CSYNC record type.
+//!
+//! [RFC 7477]: https://tools.ietf.org/html/rfc7477
+
+use super::dnssec::RtypeBitmap;
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::rdata::{ComposeRecordData, ParseRecordData, RecordData};
+use crate::base::scan::{Scan, Scanner};
+use crate::base::serial::Serial;
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use core::cmp::Ordering;
+use core::{fmt, hash};
+#[cfg(feature = "serde")]
+use octseq::builder::{EmptyBuilder, FromBuilder, OctetsBuilder, Truncate};
+use octseq::octets::{Octets, OctetsFrom};
+use octseq::parse::Parser;
+
+//------------ Csync ---------------------------------------------------------
+
+/// Csync record data.
+///
+/// Csync records are published by a child zone to request that a parental
+/// agent copies the records of the types listed in the type bitmap from
+/// the child to the delegation in the parent.
+///
+/// The flags modify how the request is processed. If the immediate flag is
+/// set, the parental agent may process the request right away. Otherwise
+/// it has to wait until it can verify the request with DNSSEC. If the
+/// soaminimum flag is set, the records must only be used if the serial of
+/// the child's SOA record is at least the serial given in the record.
+///
+/// The Csync record type is defined in [RFC 7477, section 2][1].
+///
+/// [1]: https://tools.ietf.org/html/rfc7477#section-2
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs: FromBuilder + octseq::serde::DeserializeOctets<'de>,
+            <Octs as FromBuilder>::Builder:
+                OctetsBuilder + EmptyBuilder + Truncate
+                + AsRef<[u8]> + AsMut<[u8]>,
+        ",
+    ))
+)]
+pub struct Csync<Octs> {
+    serial: Serial,
+    flags: u16,
+    types: RtypeBitmap<Octs>,
+}
+
+impl Csync<()> {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::CSYNC;
+
+    /// The flag requesting immediate processing.
+    pub const IMMEDIATE: u16 = 0x0001;
+
+    /// The flag requesting a minimum SOA serial.
+    pub const SOAMINIMUM: u16 = 0x0002;
+}
+
+impl<Octs> Csync<Octs> {
+    /// Creates new Csync record data from its components.
+    pub fn new(serial: Serial, flags: u16, types: RtypeBitmap<Octs>) -> Self {
+        Csync {
+            serial,
+            flags,
+            types,
+        }
+    }
+
+    /// Returns the minimum SOA serial of the child zone.
+    ///
+    /// The value is only relevant if the soaminimum flag is set.
+    pub fn serial(&self) -> Serial {
+        self.serial
+    }
+
+    /// Returns the raw flags.
+    pub fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    /// Returns whether the immediate flag is set.
+    pub fn immediate(&self) -> bool {
+        self.flags & Csync::IMMEDIATE != 0
+    }
+
+    /// Returns whether the soaminimum flag is set.
+    pub fn soa_minimum(&self) -> bool {
+        self.flags & Csync::SOAMINIMUM != 0
+    }
+
+    /// Returns the record types to be synchronized.
+    pub fn types(&self) -> &RtypeBitmap<Octs> {
+        &self.types
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Csync<Target>, Target::Error> {
+        Ok(Csync::new(
+            self.serial,
+            self.flags,
+            self.types.convert_octets()?,
+        ))
+    }
+
+    pub(super) fn flatten<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Csync<Target>, Target::Error> {
+        self.convert_octets()
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Ok(Self::new(
+            Serial::parse(parser)?,
+            u16::parse(parser)?,
+            RtypeBitmap::parse(parser)?,
+        ))
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error> {
+        Ok(Self::new(
+            Serial::scan(scanner)?,
+            u16::scan(scanner)?,
+            RtypeBitmap::scan(scanner)?,
+        ))
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Csync<SrcOcts>> for Csync<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Csync<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(Csync::new(
+            source.serial,
+            source.flags,
+            RtypeBitmap::try_octets_from(source.types)?,
+        ))
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Csync<Other>> for Csync<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Csync<Other>) -> bool {
+        self.serial == other.serial
+            && self.flags == other.flags
+            && self.types == other.types
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Csync<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Csync<Other>> for Csync<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Csync<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Csync<Other>> for Csync<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Csync<Other>) -> Ordering {
+        match self.serial.canonical_cmp(&other.serial) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.flags.cmp(&other.flags) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.types.as_slice().cmp(other.types.as_slice())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Csync<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Csync<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.serial.hash(state);
+        self.flags.hash(state);
+        self.types.hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Csync<Octs> {
+    fn rtype(&self) -> Rtype {
+        Csync::RTYPE
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Csync<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Csync::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Csync<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            (Serial::COMPOSE_LEN + u16::COMPOSE_LEN)
+                .checked_add(self.types.compose_len())
+                .expect("long type bitmap"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.serial.compose(target)?;
+        self.flags.compose(target)?;
+        self.types.compose(target)
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Csync<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.serial, self.flags, self.types)
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Csync<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Csync")
+            .field("serial", &self.serial)
+            .field("flags", &self.flags)
+            .field("types", &self.types)
+            .finish()
+    }
+}
+
+//--- ZonefileFmt
+
+impl<Octs: AsRef<[u8]>> ZonefileFmt for Csync<Octs> {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| {
+            p.write_token(self.serial)?;
+            p.write_comment("serial")?;
+            p.write_token(self.flags)?;
+            p.write_comment(format_args!(
+                "flags:{}{}{}",
+                if self.immediate() { " immediate" } else { "" },
+                if self.soa_minimum() {
+                    " soaminimum"
+                } else {
+                    ""
+                },
+                if self.flags == 0 { " <none>" } else { "" },
+            ))?;
+            p.write_show(&self.types)
+        })
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use crate::rdata::dnssec::RtypeBitmapBuilder;
+    use std::string::ToString;
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn csync_compose_parse_scan() {
+        // RFC 7477, section 2.2.
+        let mut types = RtypeBitmapBuilder::new_vec();
+        types.add(Rtype::A).unwrap();
+        types.add(Rtype::NS).unwrap();
+        types.add(Rtype::AAAA).unwrap();
+        let rdata = Csync::new(Serial(66), 3, types.finalize());
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Csync::parse(parser));
+        test_scan(&["66", "3", "A", "NS", "AAAA"], Csync::scan, &rdata);
+        assert_eq!(rdata.to_string(), "66 3 A NS AAAA");
+        assert!(rdata.immediate());
+        assert!(rdata.soa_minimum());
+    }
+
+    #[test]
+    fn csync_flags() {
+        let types = RtypeBitmapBuilder::new_vec().finalize();
+        let rdata = Csync::new(Serial(1), Csync::IMMEDIATE, types.clone());
+        assert!(rdata.immediate());
+        assert!(!rdata.soa_minimum());
+        let rdata = Csync::new(Serial(1), Csync::SOAMINIMUM, types);
+        assert!(!rdata.immediate());
+        assert!(rdata.soa_minimum());
+    }
+}
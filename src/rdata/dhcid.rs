@@ -0,0 +1,429 @@
+//! Record data from [RFC 4701]: DHCID records.
+//!
+//! This RFC defines the DHCID record type.
+//!
+//! [RFC 4701]: https://tools.ietf.org/html/rfc4701
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+#[cfg(feature = "ring")]
+use crate::base::name::ToName;
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scanner, ScannerError};
+use crate::base::wire::{Composer, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use crate::utils::base64;
+use core::cmp::Ordering;
+use core::{fmt, hash};
+#[cfg(feature = "ring")]
+use octseq::builder::ShortBuf;
+#[cfg(any(feature = "ring", feature = "serde"))]
+use octseq::builder::{EmptyBuilder, FromBuilder, OctetsBuilder};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Dhcid ---------------------------------------------------------
+
+/// Dhcid record data.
+///
+/// Dhcid records associate a DHCP client’s identity with a domain name.
+/// This allows DHCP servers updating the DNS on behalf of their clients to
+/// detect conflicts between clients using the same name.
+///
+/// The record data consists of a two octet identifier type, a one octet
+/// digest type, and a digest over the client identity and the domain name.
+/// It is, however, treated as opaque by DNS and given in base64 in
+/// presentation format. Therefore, this type simply wraps the record data.
+/// Its components can be accessed via the [`identifier_type`],
+/// [`digest_type`], and [`digest`] methods. Record data for a client can be
+/// created through [`from_identifier`] if the `ring` feature is enabled.
+///
+/// The Dhcid record type is defined in [RFC 4701, section 3][1].
+///
+/// [`identifier_type`]: Dhcid::identifier_type
+/// [`digest_type`]: Dhcid::digest_type
+/// [`digest`]: Dhcid::digest
+/// [`from_identifier`]: Dhcid::from_identifier
+/// [1]: https://tools.ietf.org/html/rfc4701#section-3
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs: FromBuilder + octseq::serde::DeserializeOctets<'de>,
+            <Octs as FromBuilder>::Builder:
+                OctetsBuilder + EmptyBuilder,
+        ",
+    ))
+)]
+pub struct Dhcid<Octs> {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base64::serde")
+    )]
+    data: Octs,
+}
+
+impl Dhcid<()> {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::DHCID;
+
+    /// The identifier type for the `chaddr` of a DHCPv4 client.
+    ///
+    /// The identifier consists of the `htype` octet followed by the
+    /// `chaddr` field of the DHCPv4 message.
+    pub const IDENTIFIER_CHADDR: u16 = 0;
+
+    /// The identifier type for a DHCPv4 client identifier option.
+    ///
+    /// The identifier consists of the data octets of the option, i.e., the
+    /// type and client identifier fields.
+    pub const IDENTIFIER_CLIENT_ID: u16 = 1;
+
+    /// The identifier type for a DUID.
+    ///
+    /// The identifier consists of the DHCP unique identifier of a DHCPv6
+    /// client or a DHCPv4 client using a DUID-based client identifier.
+    pub const IDENTIFIER_DUID: u16 = 2;
+
+    /// The digest type for SHA-256.
+    pub const DIGEST_SHA256: u8 = 1;
+}
+
+impl<Octs> Dhcid<Octs> {
+    /// Creates new Dhcid record data from its wire format.
+    ///
+    /// The content of `data` is not checked beyond its length. Returns an
+    /// error if the record data would be too long.
+    pub fn new(data: Octs) -> Result<Self, LongRecordData>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        LongRecordData::check_len(data.as_ref().len())?;
+        Ok(unsafe { Dhcid::new_unchecked(data) })
+    }
+
+    /// Creates new Dhcid record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that wire format representation of the
+    /// record data is at most 65,535 octets long.
+    pub unsafe fn new_unchecked(data: Octs) -> Self {
+        Dhcid { data }
+    }
+
+    /// Creates the Dhcid record data for a DHCP client.
+    ///
+    /// The client is identified by an identifier of the given type, one of
+    /// the `IDENTIFIER_*` constants defined on `Dhcid<()>`, and the domain
+    /// name `fqdn` it uses. The digest is calculated using SHA-256 over
+    /// the identifier followed by the name in canonical wire format as
+    /// described in [RFC 4701, section 3.5].
+    ///
+    /// Returns an error if the octets builder runs out of space.
+    ///
+    /// [RFC 4701, section 3.5]: https://tools.ietf.org/html/rfc4701#section-3.5
+    #[cfg(feature = "ring")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+    pub fn from_identifier<N: ToName + ?Sized>(
+        identifier_type: u16,
+        identifier: &[u8],
+        fqdn: &N,
+    ) -> Result<Self, ShortBuf>
+    where
+        Octs: FromBuilder,
+        <Octs as FromBuilder>::Builder: EmptyBuilder,
+    {
+        let mut name = octseq::array::Array::<255>::new();
+        fqdn.compose_canonical(&mut name).expect("long domain name");
+        let mut context = ring::digest::Context::new(&ring::digest::SHA256);
+        context.update(identifier);
+        context.update(name.as_ref());
+
+        let mut data = <Octs as FromBuilder>::Builder::empty();
+        data.append_slice(&identifier_type.to_be_bytes())
+            .map_err(Into::into)?;
+        data.append_slice(&[Dhcid::DIGEST_SHA256])
+            .map_err(Into::into)?;
+        data.append_slice(context.finish().as_ref())
+            .map_err(Into::into)?;
+        Ok(unsafe { Dhcid::new_unchecked(Octs::from_builder(data)) })
+    }
+
+    /// Returns the wire format of the record data.
+    pub fn data(&self) -> &Octs {
+        &self.data
+    }
+
+    /// Returns the identifier type.
+    ///
+    /// Returns `None` if the record data is too short.
+    pub fn identifier_type(&self) -> Option<u16>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        match self.data.as_ref() {
+            [high, low, ..] => Some(u16::from_be_bytes([*high, *low])),
+            _ => None,
+        }
+    }
+
+    /// Returns the digest type.
+    ///
+    /// Returns `None` if the record data is too short.
+    pub fn digest_type(&self) -> Option<u8>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        self.data.as_ref().get(2).copied()
+    }
+
+    /// Returns the digest.
+    ///
+    /// Returns `None` if the record data is too short.
+    pub fn digest(&self) -> Option<&[u8]>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        self.data.as_ref().get(3..)
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Dhcid<Target>, Target::Error> {
+        Ok(unsafe { Dhcid::new_unchecked(self.data.try_octets_into()?) })
+    }
+
+    pub(super) fn flatten<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Dhcid<Target>, Target::Error> {
+        self.convert_octets()
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let len = parser.remaining();
+        Ok(unsafe { Self::new_unchecked(parser.parse_octets(len)?) })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Self::new(scanner.convert_entry(base64::SymbolConverter::new())?)
+            .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Dhcid<SrcOcts>> for Dhcid<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Dhcid<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Dhcid::new_unchecked(Octs::try_octets_from(source.data)?)
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Dhcid<Other>> for Dhcid<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Dhcid<Other>) -> bool {
+        self.data.as_ref().eq(other.data.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Dhcid<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Dhcid<Other>> for Dhcid<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Dhcid<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Dhcid<Other>> for Dhcid<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Dhcid<Other>) -> Ordering {
+        self.data.as_ref().cmp(other.data.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Dhcid<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Dhcid<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.data.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Dhcid<Octs> {
+    fn rtype(&self) -> Rtype {
+        Dhcid::RTYPE
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Dhcid<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Dhcid::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Dhcid<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(self.data.as_ref().len().try_into().expect("long DHCID"))
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        target.append_slice(self.data.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Dhcid<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        base64::display(&self.data, f)
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Dhcid<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Dhcid")
+            .field("data", &self.data.as_ref())
+            .finish()
+    }
+}
+
+//--- ZonefileFmt
+
+impl<Octs: AsRef<[u8]>> ZonefileFmt for Dhcid<Octs> {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| p.write_token(base64::encode_display(&self.data)))
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn dhcid_compose_parse_scan() {
+        let rdata = Dhcid::new(b"\x00\x02\x01digest".to_vec()).unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Dhcid::parse(parser));
+        test_scan(&["AAIBZGlnZXN0"], Dhcid::scan, &rdata);
+        assert_eq!(rdata.to_string(), "AAIBZGlnZXN0");
+        assert_eq!(rdata.identifier_type(), Some(Dhcid::IDENTIFIER_DUID));
+        assert_eq!(rdata.digest_type(), Some(Dhcid::DIGEST_SHA256));
+        assert_eq!(rdata.digest(), Some(b"digest".as_ref()));
+
+        let short = Dhcid::new(b"\x00".to_vec()).unwrap();
+        assert_eq!(short.identifier_type(), None);
+        assert_eq!(short.digest(), None);
+    }
+
+    #[cfg(feature = "ring")]
+    #[test]
+    fn dhcid_from_identifier() {
+        use crate::base::Name;
+        use core::str::FromStr;
+        use std::vec::Vec;
+
+        // RFC 4701, section 3.6.
+        let cases: [(u16, &[u8], &str, &str); 3] = [
+            (
+                Dhcid::IDENTIFIER_DUID,
+                b"\x00\x01\x00\x06\x41\x2d\xf1\x66\x01\x02\x03\x04\x05\x06",
+                "chi6.example.com",
+                "AAIBY2/AuCccgoJbsaxcQc9TUapptP69lOjxfNuVAA2kjEA=",
+            ),
+            (
+                Dhcid::IDENTIFIER_CLIENT_ID,
+                b"\x01\x07\x08\x09\x0a\x0b\x0c",
+                "chi.example.com",
+                "AAEBOSD+XR3Os/0LozeXVqcNc7FwCfQdWL3b/NaiUDlW2No=",
+            ),
+            (
+                Dhcid::IDENTIFIER_CHADDR,
+                b"\x01\x01\x02\x03\x04\x05\x06",
+                "client.example.com",
+                "AAABxLmlskllE0MVjd57zHcWmEH3pCQ6VytcKD//7es/deY=",
+            ),
+        ];
+        for (id_type, id, fqdn, expected) in cases {
+            let rdata = Dhcid::<Vec<u8>>::from_identifier(
+                id_type,
+                id,
+                &Name::<Vec<u8>>::from_str(fqdn).unwrap(),
+            )
+            .unwrap();
+            assert_eq!(rdata.to_string(), expected);
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use crate::base::cmp::CanonicalOrd;
-use crate::base::name::{ParsedName, ToName};
+use crate::base::name::{
+    Name, NameBuilder, ParsedName, PushError, PushNameError, ToName,
+};
 use crate::base::wire::ParseError;
 use core::cmp::Ordering;
 use core::str::FromStr;
 use core::{fmt, hash};
+use octseq::builder::{EmptyBuilder, FromBuilder};
 use octseq::octets::{Octets, OctetsFrom};
 use octseq::parse::Parser;
 
@@ -19,6 +22,105 @@ name_type_canonical! {
     (Dname, DNAME, dname, into_dname)
 }
 
+impl<N: ToName> Dname<N> {
+    /// Synthesizes the name a query name is redirected to.
+    ///
+    /// Given the `owner` of the DNAME record and a `qname` below it, the
+    /// function replaces the `owner` suffix of `qname` with the target of
+    /// the record as described in [RFC 6672, section 2.2]. For instance,
+    /// with a DNAME record at `example.com` pointing to `example.net`,
+    /// the name `www.example.com` is redirected to `www.example.net`.
+    ///
+    /// Returns an error if `qname` is not strictly below `owner` or if the
+    /// synthesized name would exceed the maximum length of a domain name.
+    /// In the latter case, a server has to respond with YXDOMAIN.
+    ///
+    /// [RFC 6672, section 2.2]: https://tools.ietf.org/html/rfc6672#section-2.2
+    pub fn synthesize<Octs, Owner, Qname>(
+        &self,
+        owner: &Owner,
+        qname: &Qname,
+    ) -> Result<Name<Octs>, DnameSynthError>
+    where
+        Octs: FromBuilder,
+        <Octs as FromBuilder>::Builder:
+            EmptyBuilder + AsRef<[u8]> + AsMut<[u8]>,
+        Owner: ToName + ?Sized,
+        Qname: ToName + ?Sized,
+    {
+        if !qname.ends_with(owner) || qname.name_eq(owner) {
+            return Err(DnameSynthError::NotBelowOwner);
+        }
+        let prefix_len =
+            qname.iter_labels().count() - owner.iter_labels().count();
+        let mut builder = NameBuilder::<Octs::Builder>::new();
+        for label in qname.iter_labels().take(prefix_len) {
+            builder.append_label(label.as_slice())?;
+        }
+        Ok(builder.append_origin(self.dname())?)
+    }
+}
+
+//============ Error Types ==================================================
+
+//------------ DnameSynthError ----------------------------------------------
+
+/// A name could not be synthesized from a DNAME record.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DnameSynthError {
+    /// The query name was not strictly below the owner of the record.
+    NotBelowOwner,
+
+    /// The synthesized name would exceed the limit of 255 bytes.
+    LongName,
+
+    /// The buffer is too short to contain the synthesized name.
+    ShortBuf,
+}
+
+impl DnameSynthError {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DnameSynthError::NotBelowOwner => {
+                "query name not below DNAME owner"
+            }
+            DnameSynthError::LongName => "long domain name",
+            DnameSynthError::ShortBuf => "buffer size exceeded",
+        }
+    }
+}
+
+impl From<PushError> for DnameSynthError {
+    fn from(err: PushError) -> Self {
+        match err {
+            // Labels taken from an existing name can’t be too long.
+            PushError::LongLabel | PushError::LongName => {
+                DnameSynthError::LongName
+            }
+            PushError::ShortBuf => DnameSynthError::ShortBuf,
+        }
+    }
+}
+
+impl From<PushNameError> for DnameSynthError {
+    fn from(err: PushNameError) -> Self {
+        match err {
+            PushNameError::LongName => DnameSynthError::LongName,
+            PushNameError::ShortBuf => DnameSynthError::ShortBuf,
+        }
+    }
+}
+
+impl fmt::Display for DnameSynthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DnameSynthError {}
+
 //============ Testing ======================================================
 
 #[cfg(test)]
@@ -30,6 +132,7 @@ mod test {
         test_compose_parse, test_rdlen, test_scan,
     };
     use core::str::FromStr;
+    use std::format;
     use std::vec::Vec;
 
     #[test]
@@ -49,4 +152,43 @@ mod test {
         test_compose_parse(&rdata, |parser| Dname::parse(parser));
         test_scan(&["www.example.com"], Dname::scan, &rdata);
     }
+
+    #[test]
+    fn dname_synthesize() {
+        let rdata = Dname::new(Name::vec_from_str("example.net").unwrap());
+        let owner = Name::vec_from_str("Example.com").unwrap();
+        assert_eq!(
+            rdata.synthesize::<Vec<u8>, _, _>(
+                &owner,
+                &Name::vec_from_str("www.sub.example.com").unwrap()
+            ),
+            Ok(Name::vec_from_str("www.sub.example.net").unwrap())
+        );
+        assert_eq!(
+            rdata.synthesize::<Vec<u8>, _, _>(&owner, &owner),
+            Err(DnameSynthError::NotBelowOwner)
+        );
+        assert_eq!(
+            rdata.synthesize::<Vec<u8>, _, _>(
+                &owner,
+                &Name::vec_from_str("www.example.org").unwrap()
+            ),
+            Err(DnameSynthError::NotBelowOwner)
+        );
+
+        // A query name of 254 octets becomes too long with a target that
+        // is four octets longer than the owner.
+        let label = "a".repeat(63);
+        let qname = Name::vec_from_str(&format!(
+            "{label}.{label}.{label}.{}.example.com",
+            "a".repeat(48)
+        ))
+        .unwrap();
+        let long = Dname::new(Name::vec_from_str("example.network").unwrap());
+        assert!(rdata.synthesize::<Vec<u8>, _, _>(&owner, &qname).is_ok());
+        assert_eq!(
+            long.synthesize::<Vec<u8>, _, _>(&owner, &qname),
+            Err(DnameSynthError::LongName)
+        );
+    }
 }
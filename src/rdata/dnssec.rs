@@ -16,6 +16,8 @@ use crate::base::wire::{Compose, Composer, FormError, Parse, ParseError};
 use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
 use crate::base::Ttl;
 use crate::utils::{base16, base64};
+#[cfg(feature = "unstable-validate")]
+use crate::validate::DnskeyExt;
 use core::cmp::Ordering;
 use core::convert::TryInto;
 use core::{cmp, fmt, hash, str};
@@ -171,6 +173,12 @@ impl<Octs> Dnskey<Octs> {
     }
 
     /// Returns the key tag for this DNSKEY data.
+    ///
+    /// The key tag is calculated as described in [RFC 4034, appendix B].
+    /// For keys using the historic RSA/MD5 algorithm, it is taken from the
+    /// public key itself as described in appendix B.1.
+    ///
+    /// [RFC 4034, appendix B]: https://tools.ietf.org/html/rfc4034#appendix-B
     pub fn key_tag(&self) -> u16
     where
         Octs: AsRef<[u8]>,
@@ -414,7 +422,13 @@ impl<Octs: AsRef<[u8]>> ComposeRecordData for Dnskey<Octs> {
 
 impl<Octs: AsRef<[u8]>> fmt::Display for Dnskey<Octs> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} {} {} ", self.flags, self.protocol, self.algorithm)?;
+        write!(
+            f,
+            "{} {} {} ",
+            self.flags,
+            self.protocol,
+            self.algorithm.to_int()
+        )?;
         base64::display(&self.public_key, f)
     }
 }
@@ -1347,20 +1361,20 @@ impl<Octs: AsRef<[u8]>, Name: ToName> Rrsig<Octs, Name> {
 impl<Octs, Name> fmt::Display for Rrsig<Octs, Name>
 where
     Octs: AsRef<[u8]>,
-    Name: fmt::Display,
+    Name: ToName,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "{} {} {} {} {} {} {} {}. ",
+            "{} {} {} {} {} {} {} {} ",
             self.type_covered,
-            self.algorithm,
+            self.algorithm.to_int(),
             self.labels,
             self.original_ttl.as_secs(),
             self.expiration,
             self.inception,
             self.key_tag,
-            self.signer_name
+            self.signer_name.fmt_with_dot()
         )?;
         base64::display(&self.signature, f)
     }
@@ -1674,10 +1688,10 @@ where
 impl<Octs, Name> fmt::Display for Nsec<Octs, Name>
 where
     Octs: AsRef<[u8]>,
-    Name: fmt::Display,
+    Name: ToName,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}. {}", self.next_name, self.types)
+        write!(f, "{} {}", self.next_name.fmt_with_dot(), self.types)
     }
 }
 
@@ -1860,6 +1874,70 @@ impl<Octs> Ds<Octs> {
     }
 }
 
+#[cfg(feature = "unstable-validate")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable-validate")))]
+impl<Octs> Ds<Octs> {
+    /// Creates the DS record data for a DNSKEY.
+    ///
+    /// The digest is calculated over the canonical form of the DNSKEY’s
+    /// owner name, `owner`, and its record data as described in
+    /// [RFC 4034, section 5.1.4]. Only SHA-256 and SHA-384 are supported
+    /// for `digest_type`. SHA-1 must not be used for generating DS records
+    /// anymore according to [RFC 8624].
+    ///
+    /// [RFC 4034, section 5.1.4]: https://tools.ietf.org/html/rfc4034#section-5.1.4
+    /// [RFC 8624]: https://tools.ietf.org/html/rfc8624
+    pub fn from_dnskey<N, KOcts>(
+        owner: &N,
+        dnskey: &Dnskey<KOcts>,
+        digest_type: DigestAlg,
+    ) -> Result<Self, DsDigestError>
+    where
+        N: ToName + ?Sized,
+        KOcts: AsRef<[u8]>,
+        Octs: FromBuilder,
+        <Octs as FromBuilder>::Builder: EmptyBuilder,
+    {
+        if !matches!(digest_type, DigestAlg::SHA256 | DigestAlg::SHA384) {
+            return Err(DsDigestError::UnsupportedAlgorithm);
+        }
+        let digest = DnskeyExt::digest(dnskey, &owner, digest_type)
+            .map_err(|_| DsDigestError::UnsupportedAlgorithm)?;
+        let mut builder = <Octs as FromBuilder>::Builder::with_capacity(
+            digest.as_ref().len(),
+        );
+        builder
+            .append_slice(digest.as_ref())
+            .map_err(|_| DsDigestError::ShortBuf)?;
+        Ok(unsafe {
+            Ds::new_unchecked(
+                dnskey.key_tag(),
+                dnskey.algorithm(),
+                digest_type,
+                builder.freeze(),
+            )
+        })
+    }
+
+    /// Returns whether the DS record data refers to a DNSKEY.
+    ///
+    /// The DNSKEY’s owner name, `owner`, is the same as that of the DS
+    /// record. In addition to SHA-256 and SHA-384, SHA-1 digests are
+    /// accepted as validators still need to support them. If the digest
+    /// type is not supported, returns `false`.
+    pub fn matches<N, KOcts>(&self, owner: &N, dnskey: &Dnskey<KOcts>) -> bool
+    where
+        N: ToName + ?Sized,
+        KOcts: AsRef<[u8]>,
+        Octs: AsRef<[u8]>,
+    {
+        self.key_tag == dnskey.key_tag()
+            && self.algorithm == dnskey.algorithm()
+            && DnskeyExt::digest(dnskey, &owner, self.digest_type)
+                .is_ok_and(|digest| digest.as_ref() == self.digest.as_ref())
+    }
+}
+
 //--- OctetsFrom
 
 impl<Octs, SrcOcts> OctetsFrom<Ds<SrcOcts>> for Ds<Octs>
@@ -2022,7 +2100,9 @@ impl<Octs: AsRef<[u8]>> fmt::Display for Ds<Octs> {
         write!(
             f,
             "{} {} {} ",
-            self.key_tag, self.algorithm, self.digest_type
+            self.key_tag,
+            self.algorithm.to_int(),
+            self.digest_type.to_int()
         )?;
         for ch in self.digest.as_ref() {
             write!(f, "{:02x}", ch)?
@@ -2132,7 +2212,7 @@ impl<Octs: AsRef<[u8]>> RtypeBitmap<Octs> {
         self.0.as_ref()
     }
 
-    pub fn iter(&self) -> RtypeBitmapIter {
+    pub fn iter(&self) -> RtypeBitmapIter<'_> {
         RtypeBitmapIter::new(self.0.as_ref())
     }
 
@@ -2728,6 +2808,35 @@ impl fmt::Display for IllegalSignatureTime {
 #[cfg(feature = "std")]
 impl std::error::Error for IllegalSignatureTime {}
 
+//------------ DsDigestError -------------------------------------------------
+
+/// DS record data could not be created from a DNSKEY.
+#[cfg(feature = "unstable-validate")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable-validate")))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DsDigestError {
+    /// The digest algorithm is not supported.
+    UnsupportedAlgorithm,
+
+    /// The octets builder didn’t have enough space for the digest.
+    ShortBuf,
+}
+
+#[cfg(feature = "unstable-validate")]
+impl fmt::Display for DsDigestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnsupportedAlgorithm => {
+                f.write_str("unsupported digest algorithm")
+            }
+            Self::ShortBuf => f.write_str("buffer size exceeded"),
+        }
+    }
+}
+
+#[cfg(feature = "unstable-validate")]
+impl std::error::Error for DsDigestError {}
+
 //============ Test ==========================================================
 
 #[cfg(test)]
@@ -2825,6 +2934,107 @@ mod test {
         test_scan(&["10", "5", "2", "6b6579"], Ds::scan, &rdata);
     }
 
+    #[test]
+    #[cfg(all(
+        feature = "unstable-validate",
+        any(feature = "ring", feature = "openssl")
+    ))]
+    fn ds_from_dnskey() {
+        // RFC 4509, section 2.3 (SHA-256) and RFC 6605, section 6.2
+        // (SHA-384).
+        for (owner, dnskey, ds) in [
+            (
+                "dskey.example.com.",
+                (
+                    256,
+                    SecAlg::RSASHA1,
+                    "AQOeiiR0GOMYkDshWoSKz9XzfwJr1AYtsmx3TGkJaNXVbfi/\
+                     2pHm822aJ5iI9BMzNXxeYCmZDRD99WYwYqUSdjMmmAphXdvx\
+                     egXd/M5+X7OrzKBaMbCVdFLUUh6DhweJBjEVv5f2wwjM9Xzc\
+                     nOf+EPbtG9DMBmADjFDc2w/rljwvFw==",
+                ),
+                (
+                    60485,
+                    DigestAlg::SHA256,
+                    "D4B7D520E7BB5F0F67674A0CCEB1E3E0\
+                     614B93C4F9E99B8383F6A1E4469DA50A",
+                ),
+            ),
+            (
+                "example.net.",
+                (
+                    257,
+                    SecAlg::ECDSAP384SHA384,
+                    "xKYaNhWdGOfJ+nPrL8/arkwf2EY3MDJ+SErKivBVSum1w/egsXvSADtN\
+                     Jhyem5RCOpgQ6K8X1DRSEkrbYQ+OB+v8/uX45NBwY8rp65F6Glur8I/m\
+                     lVNgF6W/qTI37m40",
+                ),
+                (
+                    10771,
+                    DigestAlg::SHA384,
+                    "72d7b62976ce06438e9c0bf319013cf801f09ecc84b8d7e9\
+                     495f27e305c6a9b0563a9b5f4d288405c3008a946df983d6",
+                ),
+            ),
+        ] {
+            let upper =
+                Name::<Vec<u8>>::from_str(&owner.to_ascii_uppercase())
+                    .unwrap();
+            let owner = Name::<Vec<u8>>::from_str(owner).unwrap();
+            let dnskey = Dnskey::new(
+                dnskey.0,
+                3,
+                dnskey.1,
+                base64::decode::<Vec<u8>>(dnskey.2).unwrap(),
+            )
+            .unwrap();
+            let expected = Ds::new(
+                ds.0,
+                dnskey.algorithm(),
+                ds.1,
+                base16::decode_vec(ds.2).unwrap(),
+            )
+            .unwrap();
+
+            let ds =
+                Ds::<Vec<u8>>::from_dnskey(&owner, &dnskey, ds.1).unwrap();
+            assert_eq!(ds, expected);
+            assert!(ds.matches(&owner, &dnskey));
+
+            // The owner name is compared case-insensitively.
+            assert!(ds.matches(&upper, &dnskey));
+            let other =
+                Name::<Vec<u8>>::from_str("other.example.").unwrap();
+            assert!(!ds.matches(&other, &dnskey));
+        }
+    }
+
+    #[test]
+    #[cfg(all(
+        feature = "unstable-validate",
+        any(feature = "ring", feature = "openssl")
+    ))]
+    fn ds_from_dnskey_errors() {
+        let owner = Name::<Vec<u8>>::from_str("example.").unwrap();
+        let dnskey = Dnskey::new(257, 3, SecAlg::ED25519, [0u8; 32]).unwrap();
+        for digest_type in [DigestAlg::SHA1, DigestAlg::GOST] {
+            assert_eq!(
+                Ds::<Vec<u8>>::from_dnskey(&owner, &dnskey, digest_type),
+                Err(DsDigestError::UnsupportedAlgorithm)
+            );
+        }
+
+        // SHA-1 digests are still accepted when checking.
+        let ds = Ds::new(
+            dnskey.key_tag(),
+            SecAlg::ED25519,
+            DigestAlg::SHA1,
+            [0u8; 20],
+        )
+        .unwrap();
+        assert!(!ds.matches(&owner, &dnskey));
+    }
+
     //--- RtypeBitmape
 
     #[test]
@@ -2960,6 +3170,12 @@ mod test {
             .key_tag(),
             18698
         );
+
+        // RSA/MD5 keys too short for a key tag.
+        assert_eq!(
+            Dnskey::new(257, 3, SecAlg::RSAMD5, b"ab").unwrap().key_tag(),
+            0
+        );
     }
 
     #[test]
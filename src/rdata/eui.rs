@@ -0,0 +1,352 @@
+//! Record data from [RFC 7043]: EUI48 and EUI64 records.
+//!
+//! This RFC defines the EUI48 and EUI64 record types which store 48 bit
+//! and 64 bit extended unique identifiers, respectively. In presentation
+//! format, the identifiers are written as groups of two hexadecimal digits
+//! separated by hyphens, e.g., `00-00-5e-00-53-2a`.
+//!
+//! [RFC 7043]: https://tools.ietf.org/html/rfc7043
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::rdata::{ComposeRecordData, ParseRecordData, RecordData};
+use crate::base::scan::{Scanner, ScannerError};
+use crate::base::wire::{Composer, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use core::cmp::Ordering;
+use core::convert::Infallible;
+use core::str::FromStr;
+use core::{fmt, str};
+use octseq::octets::OctetsFrom;
+use octseq::parse::Parser;
+
+//------------ eui_type! -----------------------------------------------------
+
+/// Defines a record data type for an EUI of the given length.
+macro_rules! eui_type {
+    (
+        $(#[$attr:meta])*
+        ( $target:ident, $rtype:ident, $len:expr, $name:expr )
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize)
+        )]
+        pub struct $target {
+            addr: [u8; $len],
+        }
+
+        impl $target {
+            /// The rtype of this record data type.
+            pub(crate) const RTYPE: Rtype = Rtype::$rtype;
+
+            /// The length of the identifier in octets.
+            pub const LEN: usize = $len;
+        }
+
+        impl $target {
+            /// Creates new record data from the octets of the identifier.
+            #[must_use]
+            pub fn new(addr: [u8; $len]) -> Self {
+                $target { addr }
+            }
+
+            /// Returns the octets of the identifier.
+            #[must_use]
+            pub fn addr(&self) -> [u8; $len] {
+                self.addr
+            }
+
+            /// Sets the octets of the identifier.
+            pub fn set_addr(&mut self, addr: [u8; $len]) {
+                self.addr = addr
+            }
+
+            pub(super) fn convert_octets<E>(self) -> Result<Self, E> {
+                Ok(self)
+            }
+
+            pub(super) fn flatten<E>(self) -> Result<Self, E> {
+                Ok(self)
+            }
+
+            /// Parses the record data from wire format.
+            ///
+            /// The parser must contain exactly the octets of the
+            /// identifier. Any other length is rejected.
+            pub fn parse<Octs: AsRef<[u8]> + ?Sized>(
+                parser: &mut Parser<Octs>,
+            ) -> Result<Self, ParseError> {
+                if parser.remaining() != $len {
+                    return Err(ParseError::form_error(concat!(
+                        "invalid ", $name, " length"
+                    )));
+                }
+                let mut addr = [0u8; $len];
+                parser.parse_buf(&mut addr)?;
+                Ok(Self::new(addr))
+            }
+
+            /// Scans the record data from presentation format.
+            pub fn scan<S: Scanner>(
+                scanner: &mut S,
+            ) -> Result<Self, S::Error> {
+                let token = scanner.scan_octets()?;
+                str::from_utf8(token.as_ref())
+                    .ok()
+                    .and_then(|token| Self::from_str(token).ok())
+                    .ok_or_else(|| {
+                        S::Error::custom(concat!("expected ", $name))
+                    })
+            }
+        }
+
+        //--- From and FromStr
+
+        impl From<[u8; $len]> for $target {
+            fn from(addr: [u8; $len]) -> Self {
+                Self::new(addr)
+            }
+        }
+
+        impl From<$target> for [u8; $len] {
+            fn from(data: $target) -> Self {
+                data.addr
+            }
+        }
+
+        impl FromStr for $target {
+            type Err = ParseEuiError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                parse_eui(s).map(Self::new)
+            }
+        }
+
+        //--- OctetsFrom
+
+        impl OctetsFrom<$target> for $target {
+            type Error = Infallible;
+
+            fn try_octets_from(source: $target) -> Result<Self, Self::Error> {
+                Ok(source)
+            }
+        }
+
+        //--- CanonicalOrd
+
+        impl CanonicalOrd for $target {
+            fn canonical_cmp(&self, other: &Self) -> Ordering {
+                self.cmp(other)
+            }
+        }
+
+        //--- RecordData, ParseRecordData, ComposeRecordData
+
+        impl RecordData for $target {
+            fn rtype(&self) -> Rtype {
+                Self::RTYPE
+            }
+        }
+
+        impl<'a, Octs: AsRef<[u8]> + ?Sized> ParseRecordData<'a, Octs>
+            for $target
+        {
+            fn parse_rdata(
+                rtype: Rtype,
+                parser: &mut Parser<'a, Octs>,
+            ) -> Result<Option<Self>, ParseError> {
+                if rtype == Self::RTYPE {
+                    Self::parse(parser).map(Some)
+                } else {
+                    Ok(None)
+                }
+            }
+        }
+
+        impl ComposeRecordData for $target {
+            fn rdlen(&self, _compress: bool) -> Option<u16> {
+                Some($len)
+            }
+
+            fn compose_rdata<Target: Composer + ?Sized>(
+                &self,
+                target: &mut Target,
+            ) -> Result<(), Target::AppendError> {
+                target.append_slice(&self.addr)
+            }
+
+            fn compose_canonical_rdata<Target: Composer + ?Sized>(
+                &self,
+                target: &mut Target,
+            ) -> Result<(), Target::AppendError> {
+                self.compose_rdata(target)
+            }
+        }
+
+        //--- Display
+
+        impl fmt::Display for $target {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt_eui(&self.addr, f)
+            }
+        }
+
+        //--- ZonefileFmt
+
+        impl ZonefileFmt for $target {
+            fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+                p.write_token(self)
+            }
+        }
+
+        //--- AsRef and AsMut
+
+        impl AsRef<[u8]> for $target {
+            fn as_ref(&self) -> &[u8] {
+                &self.addr
+            }
+        }
+
+        impl AsMut<[u8]> for $target {
+            fn as_mut(&mut self) -> &mut [u8] {
+                &mut self.addr
+            }
+        }
+    };
+}
+
+//------------ Eui48 ---------------------------------------------------------
+
+eui_type! {
+    /// EUI48 record data.
+    ///
+    /// The record data contains a 48 bit extended unique identifier, such
+    /// as an Ethernet MAC address.
+    ///
+    /// The EUI48 record type is defined in [RFC 7043, section 3].
+    ///
+    /// [RFC 7043, section 3]: https://tools.ietf.org/html/rfc7043#section-3
+    (Eui48, EUI48, 6, "EUI48")
+}
+
+//------------ Eui64 ---------------------------------------------------------
+
+eui_type! {
+    /// EUI64 record data.
+    ///
+    /// The record data contains a 64 bit extended unique identifier.
+    ///
+    /// The EUI64 record type is defined in [RFC 7043, section 4].
+    ///
+    /// [RFC 7043, section 4]: https://tools.ietf.org/html/rfc7043#section-4
+    (Eui64, EUI64, 8, "EUI64")
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Parses the presentation format of an EUI of exactly `N` octets.
+fn parse_eui<const N: usize>(s: &str) -> Result<[u8; N], ParseEuiError> {
+    let mut res = [0u8; N];
+    let mut groups = s.split('-');
+    for octet in res.iter_mut() {
+        let group = groups.next().ok_or(ParseEuiError)?.as_bytes();
+        if group.len() != 2 {
+            return Err(ParseEuiError);
+        }
+        *octet = (hex_digit(group[0])? << 4) | hex_digit(group[1])?;
+    }
+    if groups.next().is_some() {
+        return Err(ParseEuiError);
+    }
+    Ok(res)
+}
+
+/// Converts a single hexadecimal digit into its value.
+fn hex_digit(ch: u8) -> Result<u8, ParseEuiError> {
+    match ch {
+        b'0'..=b'9' => Ok(ch - b'0'),
+        b'a'..=b'f' => Ok(ch - b'a' + 10),
+        b'A'..=b'F' => Ok(ch - b'A' + 10),
+        _ => Err(ParseEuiError),
+    }
+}
+
+/// Formats an EUI as hyphen-separated groups of two hexadecimal digits.
+fn fmt_eui(addr: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
+    for (i, octet) in addr.iter().enumerate() {
+        if i > 0 {
+            f.write_str("-")?;
+        }
+        write!(f, "{:02x}", octet)?;
+    }
+    Ok(())
+}
+
+//============ Error Types ===================================================
+
+//------------ ParseEuiError -------------------------------------------------
+
+/// A string could not be converted into an EUI.
+///
+/// The string must consist of exactly as many groups of two hexadecimal
+/// digits separated by hyphens as the identifier has octets.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ParseEuiError;
+
+impl fmt::Display for ParseEuiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid EUI")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseEuiError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+
+    #[test]
+    fn eui48_compose_parse_scan() {
+        let rdata = Eui48::new([0x00, 0x00, 0x5e, 0x00, 0x53, 0x2a]);
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, Eui48::parse);
+        test_scan(&["00-00-5e-00-53-2a"], Eui48::scan, &rdata);
+        assert_eq!(rdata.to_string(), "00-00-5e-00-53-2a");
+        assert_eq!(Eui48::from_str("00-00-5E-00-53-2A"), Ok(rdata));
+    }
+
+    #[test]
+    fn eui64_compose_parse_scan() {
+        let rdata =
+            Eui64::new([0x00, 0x00, 0x5e, 0xef, 0x10, 0x00, 0x00, 0x2a]);
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, Eui64::parse);
+        test_scan(&["00-00-5e-ef-10-00-00-2a"], Eui64::scan, &rdata);
+        assert_eq!(rdata.to_string(), "00-00-5e-ef-10-00-00-2a");
+    }
+
+    #[test]
+    fn eui_invalid() {
+        assert!(Eui48::from_str("00-00-5e-00-53").is_err());
+        assert!(Eui48::from_str("00-00-5e-00-53-2a-00").is_err());
+        assert!(Eui48::from_str("00-00-5e-00-53-2").is_err());
+        assert!(Eui48::from_str("00:00:5e:00:53:2a").is_err());
+        assert!(Eui48::from_str("00-00-5e-00-53-2g").is_err());
+        assert!(Eui64::from_str("00-00-5e-00-53-2a").is_err());
+
+        let octets = [0u8; 7];
+        assert!(Eui48::parse(&mut Parser::from_ref(octets.as_ref())).is_err());
+        assert!(Eui64::parse(&mut Parser::from_ref(octets.as_ref())).is_err());
+    }
+}
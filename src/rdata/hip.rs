@@ -0,0 +1,626 @@
+//! Record data from [RFC 8005]: HIP records.
+//!
+//! This RFC defines the HIP record type.
+//!
+//! [RFC 8005]: https://tools.ietf.org/html/rfc8005
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::{IpseckeyAlg, Rtype};
+use crate::base::name::{Name, ToName};
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scanner, ScannerError};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use crate::utils::{base16, base64};
+use core::cmp::Ordering;
+use core::{fmt, hash};
+use octseq::builder::FreezeBuilder;
+#[cfg(feature = "serde")]
+use octseq::builder::{EmptyBuilder, FromBuilder, OctetsBuilder};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Hip -----------------------------------------------------------
+
+/// Hip record data.
+///
+/// Hip records publish the host identity tag (HIT) and host identity, i.e.,
+/// the public key, of a host that uses the Host Identity Protocol. In
+/// addition, they can list the rendezvous servers through which the host
+/// can be reached.
+///
+/// The rendezvous servers are a sequence of domain names filling the
+/// remainder of the record data. They must not be compressed. They are
+/// kept in wire format and can be accessed via the [`servers`] method.
+///
+/// The Hip record type is defined in [RFC 8005, section 5][1].
+///
+/// [`servers`]: Hip::servers
+/// [1]: https://tools.ietf.org/html/rfc8005#section-5
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs: FromBuilder + octseq::serde::DeserializeOctets<'de>,
+            <Octs as FromBuilder>::Builder:
+                OctetsBuilder + EmptyBuilder,
+        ",
+    ))
+)]
+pub struct Hip<Octs> {
+    algorithm: IpseckeyAlg,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base16::serde")
+    )]
+    hit: Octs,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base64::serde")
+    )]
+    public_key: Octs,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "octseq::serde::SerializeOctets::serialize_octets",
+            deserialize_with = "octseq::serde::DeserializeOctets::deserialize_octets",
+        )
+    )]
+    servers: Octs,
+}
+
+impl Hip<()> {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::HIP;
+}
+
+impl<Octs> Hip<Octs> {
+    /// Creates new Hip record data from its components.
+    ///
+    /// The `servers` must contain a sequence of uncompressed domain names
+    /// in wire format. It may be empty if there are no rendezvous servers.
+    ///
+    /// Returns an error if the HIT is longer than 255 octets, the public
+    /// key is longer than 65,535 octets, `servers` does not contain a
+    /// valid sequence of names, or the record data would be too long.
+    pub fn new(
+        algorithm: IpseckeyAlg,
+        hit: Octs,
+        public_key: Octs,
+        servers: Octs,
+    ) -> Result<Self, HipError>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        if hit.as_ref().len() > usize::from(u8::MAX) {
+            return Err(HipError::LongHit);
+        }
+        if public_key.as_ref().len() > usize::from(u16::MAX) {
+            return Err(HipError::LongPublicKey);
+        }
+        if check_servers(servers.as_ref()).is_err() {
+            return Err(HipError::InvalidServers);
+        }
+        LongRecordData::check_len(
+            4 + hit.as_ref().len()
+                + public_key.as_ref().len()
+                + servers.as_ref().len(),
+        )?;
+        Ok(unsafe {
+            Self::new_unchecked(algorithm, hit, public_key, servers)
+        })
+    }
+
+    /// Creates new Hip record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that the HIT is at most 255 octets long,
+    /// that `servers` contains a sequence of uncompressed domain names, and
+    /// that the wire format representation of the record data is at most
+    /// 65,535 octets long.
+    pub unsafe fn new_unchecked(
+        algorithm: IpseckeyAlg,
+        hit: Octs,
+        public_key: Octs,
+        servers: Octs,
+    ) -> Self {
+        Hip {
+            algorithm,
+            hit,
+            public_key,
+            servers,
+        }
+    }
+
+    /// Returns the algorithm of the public key.
+    pub fn algorithm(&self) -> IpseckeyAlg {
+        self.algorithm
+    }
+
+    /// Returns the host identity tag.
+    pub fn hit(&self) -> &Octs {
+        &self.hit
+    }
+
+    /// Returns the public key, i.e., the host identity.
+    pub fn public_key(&self) -> &Octs {
+        &self.public_key
+    }
+
+    /// Returns the wire format of the rendezvous servers.
+    pub fn servers_slice(&self) -> &[u8]
+    where
+        Octs: AsRef<[u8]>,
+    {
+        self.servers.as_ref()
+    }
+
+    /// Returns an iterator over the rendezvous servers.
+    pub fn servers(&self) -> RendezvousServers<'_>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        RendezvousServers {
+            parser: Parser::from_ref(self.servers.as_ref()),
+        }
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Hip<Target>, Target::Error> {
+        Ok(unsafe {
+            Hip::new_unchecked(
+                self.algorithm,
+                self.hit.try_octets_into()?,
+                self.public_key.try_octets_into()?,
+                self.servers.try_octets_into()?,
+            )
+        })
+    }
+
+    pub(super) fn flatten<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Hip<Target>, Target::Error> {
+        self.convert_octets()
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        let hit_len = u8::parse(parser)?;
+        let algorithm = IpseckeyAlg::parse(parser)?;
+        let key_len = u16::parse(parser)?;
+        let hit = parser.parse_octets(usize::from(hit_len))?;
+        let public_key = parser.parse_octets(usize::from(key_len))?;
+        let servers = parser.parse_octets(parser.remaining())?;
+        check_servers(servers.as_ref())?;
+        Ok(unsafe {
+            Self::new_unchecked(algorithm, hit, public_key, servers)
+        })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        let algorithm = IpseckeyAlg::scan(scanner)?;
+        let hit = scanner.convert_token(base16::SymbolConverter::new())?;
+        let public_key =
+            scanner.convert_token(base64::SymbolConverter::new())?;
+        let mut servers = scanner.octets_builder()?;
+        while scanner.continues() {
+            scanner
+                .scan_name()?
+                .compose(&mut servers)
+                .map_err(|_| S::Error::short_buf())?;
+        }
+        Self::new(algorithm, hit, public_key, servers.freeze())
+            .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+/// Checks that `servers` contains a sequence of uncompressed names.
+fn check_servers(servers: &[u8]) -> Result<(), ParseError> {
+    let mut parser = Parser::from_ref(servers);
+    while parser.remaining() > 0 {
+        Name::parse(&mut parser)?;
+    }
+    Ok(())
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Hip<SrcOcts>> for Hip<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Hip<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Hip::new_unchecked(
+                source.algorithm,
+                Octs::try_octets_from(source.hit)?,
+                Octs::try_octets_from(source.public_key)?,
+                Octs::try_octets_from(source.servers)?,
+            )
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Hip<Other>> for Hip<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Hip<Other>) -> bool {
+        self.algorithm == other.algorithm
+            && self.hit.as_ref().eq(other.hit.as_ref())
+            && self.public_key.as_ref().eq(other.public_key.as_ref())
+            && self.servers.as_ref().eq(other.servers.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Hip<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Hip<Other>> for Hip<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Hip<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Hip<Other>> for Hip<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Hip<Other>) -> Ordering {
+        // The rendezvous servers are not converted to lowercase in the
+        // canonical form, so we can simply compare the wire format in
+        // order -- which starts with the two lengths.
+        match self.hit.as_ref().len().cmp(&other.hit.as_ref().len()) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.algorithm.cmp(&other.algorithm) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self
+            .public_key
+            .as_ref()
+            .len()
+            .cmp(&other.public_key.as_ref().len())
+        {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.hit.as_ref().cmp(other.hit.as_ref()) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.public_key.as_ref().cmp(other.public_key.as_ref()) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.servers.as_ref().cmp(other.servers.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Hip<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Hip<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.algorithm.hash(state);
+        self.hit.as_ref().hash(state);
+        self.public_key.as_ref().hash(state);
+        self.servers.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Hip<Octs> {
+    fn rtype(&self) -> Rtype {
+        Hip::RTYPE
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Hip<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Hip::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Hip<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            u16::try_from(
+                4 + self.hit.as_ref().len()
+                    + self.public_key.as_ref().len()
+                    + self.servers.as_ref().len(),
+            )
+            .expect("long HIP record data"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        u8::try_from(self.hit.as_ref().len())
+            .expect("long HIT")
+            .compose(target)?;
+        self.algorithm.compose(target)?;
+        u16::try_from(self.public_key.as_ref().len())
+            .expect("long public key")
+            .compose(target)?;
+        target.append_slice(self.hit.as_ref())?;
+        target.append_slice(self.public_key.as_ref())?;
+        target.append_slice(self.servers.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Hip<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ", self.algorithm)?;
+        base16::display(&self.hit, f)?;
+        f.write_str(" ")?;
+        base64::display(&self.public_key, f)?;
+        for server in self.servers() {
+            write!(f, " {}", server.fmt_with_dot())?;
+        }
+        Ok(())
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Hip<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Hip")
+            .field("algorithm", &self.algorithm)
+            .field("hit", &self.hit.as_ref())
+            .field("public_key", &self.public_key.as_ref())
+            .field("servers", &self.servers.as_ref())
+            .finish()
+    }
+}
+
+//--- ZonefileFmt
+
+impl<Octs: AsRef<[u8]>> ZonefileFmt for Hip<Octs> {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| {
+            p.write_show(self.algorithm)?;
+            p.write_token(base16::encode_display(&self.hit))?;
+            p.write_token(base64::encode_display(&self.public_key))?;
+            for server in self.servers() {
+                p.write_token(server.fmt_with_dot())?;
+            }
+            Ok(())
+        })
+    }
+}
+
+//------------ RendezvousServers ---------------------------------------------
+
+/// An iterator over the rendezvous servers of a HIP record.
+///
+/// The iterator is returned by [`Hip::servers`].
+#[derive(Clone, Debug)]
+pub struct RendezvousServers<'a> {
+    parser: Parser<'a, [u8]>,
+}
+
+impl<'a> Iterator for RendezvousServers<'a> {
+    type Item = Name<&'a [u8]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.parser.remaining() == 0 {
+            return None;
+        }
+        // The content has been checked upon creation. Only serde
+        // deserialization can sneak in invalid data, in which case we
+        // simply stop.
+        match Name::parse(&mut self.parser) {
+            Ok(name) => Some(name),
+            Err(_) => {
+                self.parser.advance_to_end();
+                None
+            }
+        }
+    }
+}
+
+//============ Error Types ===================================================
+
+//------------ HipError ------------------------------------------------------
+
+/// An error happened while constructing HIP record data.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HipError {
+    /// The HIT was longer than 255 octets.
+    LongHit,
+
+    /// The public key was longer than 65,535 octets.
+    LongPublicKey,
+
+    /// The rendezvous servers were not a sequence of uncompressed names.
+    InvalidServers,
+
+    /// The record data was longer than 65,535 octets.
+    LongRecordData,
+}
+
+impl HipError {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HipError::LongHit => "HIT exceeds 255 bytes",
+            HipError::LongPublicKey => "public key exceeds 65,535 bytes",
+            HipError::InvalidServers => "invalid rendezvous servers",
+            HipError::LongRecordData => "record data exceeds 65,535 bytes",
+        }
+    }
+}
+
+impl From<LongRecordData> for HipError {
+    fn from(_: LongRecordData) -> Self {
+        HipError::LongRecordData
+    }
+}
+
+impl fmt::Display for HipError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HipError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    fn servers() -> Vec<u8> {
+        b"\x03rvs\x07example\x03com\x00\x04rvs2\x07example\x03com\x00".into()
+    }
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn hip_compose_parse_scan() {
+        let rdata = Hip::new(
+            IpseckeyAlg::RSA,
+            b"\x20\x01\x00\x10".to_vec(),
+            b"public key".to_vec(),
+            servers(),
+        )
+        .unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Hip::parse(parser));
+        test_scan(
+            &[
+                "2",
+                "20010010",
+                "cHVibGljIGtleQ==",
+                "rvs.example.com.",
+                "rvs2.example.com.",
+            ],
+            Hip::scan,
+            &rdata,
+        );
+        assert_eq!(
+            rdata.to_string(),
+            "2 20010010 cHVibGljIGtleQ== \
+             rvs.example.com. rvs2.example.com."
+        );
+        assert_eq!(
+            rdata
+                .servers()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>(),
+            ["rvs.example.com", "rvs2.example.com"]
+        );
+    }
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn hip_without_servers() {
+        let rdata = Hip::new(
+            IpseckeyAlg::ECDSA,
+            b"\x20\x01".to_vec(),
+            b"key".to_vec(),
+            Vec::new(),
+        )
+        .unwrap();
+        test_compose_parse(&rdata, |parser| Hip::parse(parser));
+        test_scan(&["3", "2001", "a2V5"], Hip::scan, &rdata);
+        assert_eq!(rdata.servers().count(), 0);
+    }
+
+    #[test]
+    fn hip_invalid_servers() {
+        assert_eq!(
+            Hip::new(
+                IpseckeyAlg::RSA,
+                b"\x20\x01".to_vec(),
+                b"key".to_vec(),
+                b"\x03rvs\xc0\x0c".to_vec(),
+            ),
+            Err(HipError::InvalidServers)
+        );
+        let wire = b"\x02\x02\x00\x03\x20\x01key\x03rvs\xc0\x0c";
+        assert!(Hip::parse(&mut Parser::from_ref(wire.as_ref())).is_err());
+
+        // Invalid servers that sneaked in stop the iteration.
+        let rdata = unsafe {
+            Hip::new_unchecked(
+                IpseckeyAlg::RSA,
+                b"\x20\x01".to_vec(),
+                b"key".to_vec(),
+                b"\x03rvs\x00\x03rvs\xc0\x0c\x00".to_vec(),
+            )
+        };
+        let mut servers = rdata.servers();
+        assert_eq!(servers.next().unwrap().to_string(), "rvs");
+        assert!(servers.next().is_none());
+        assert!(servers.next().is_none());
+        assert_eq!(rdata.to_string(), "2 2001 a2V5 rvs.");
+    }
+}
@@ -0,0 +1,688 @@
+//! Record data from [RFC 6742]: NID, L32, L64, and LP records.
+//!
+//! This RFC defines the record types used by the Identifier-Locator
+//! Network Protocol (ILNP). The 64 bit node identifiers and locators of the
+//! NID and L64 record types are written as four groups of four hexadecimal
+//! digits separated by colons in presentation format, e.g.,
+//! `0014:4fff:ff20:ee64`.
+//!
+//! [RFC 6742]: https://tools.ietf.org/html/rfc6742
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::name::{FlattenInto, ParsedName, ToName};
+use crate::base::net::Ipv4Addr;
+use crate::base::rdata::{ComposeRecordData, ParseRecordData, RecordData};
+use crate::base::scan::{Scan, Scanner, ScannerError};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use core::cmp::Ordering;
+use core::convert::Infallible;
+use core::str::FromStr;
+use core::{fmt, str};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ ilnp64_type! --------------------------------------------------
+
+/// Defines a record data type for a preference and a 64 bit value.
+macro_rules! ilnp64_type {
+    (
+        $(#[$attr:meta])*
+        ( $target:ident, $rtype:ident, $field:ident, $name:expr )
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize)
+        )]
+        pub struct $target {
+            preference: u16,
+            $field: u64,
+        }
+
+        impl $target {
+            /// The rtype of this record data type.
+            pub(crate) const RTYPE: Rtype = Rtype::$rtype;
+        }
+
+        impl $target {
+            /// Creates new record data from its components.
+            #[must_use]
+            pub fn new(preference: u16, $field: u64) -> Self {
+                $target { preference, $field }
+            }
+
+            /// Returns the preference of this record.
+            ///
+            /// Lower values are preferred.
+            #[must_use]
+            pub fn preference(&self) -> u16 {
+                self.preference
+            }
+
+            #[doc = concat!("Returns the ", $name, ".")]
+            #[must_use]
+            pub fn $field(&self) -> u64 {
+                self.$field
+            }
+
+            pub(super) fn convert_octets<E>(self) -> Result<Self, E> {
+                Ok(self)
+            }
+
+            pub(super) fn flatten<E>(self) -> Result<Self, E> {
+                Ok(self)
+            }
+
+            pub fn parse<Octs: AsRef<[u8]> + ?Sized>(
+                parser: &mut Parser<Octs>,
+            ) -> Result<Self, ParseError> {
+                Ok(Self::new(u16::parse(parser)?, u64::parse(parser)?))
+            }
+
+            pub fn scan<S: Scanner>(
+                scanner: &mut S,
+            ) -> Result<Self, S::Error> {
+                let preference = u16::scan(scanner)?;
+                let token = scanner.scan_octets()?;
+                let value = str::from_utf8(token.as_ref())
+                    .ok()
+                    .and_then(parse_ilnp64)
+                    .ok_or_else(|| {
+                        S::Error::custom(concat!("expected ", $name))
+                    })?;
+                Ok(Self::new(preference, value))
+            }
+        }
+
+        //--- OctetsFrom
+
+        impl OctetsFrom<$target> for $target {
+            type Error = Infallible;
+
+            fn try_octets_from(source: $target) -> Result<Self, Self::Error> {
+                Ok(source)
+            }
+        }
+
+        //--- CanonicalOrd
+
+        impl CanonicalOrd for $target {
+            fn canonical_cmp(&self, other: &Self) -> Ordering {
+                self.cmp(other)
+            }
+        }
+
+        //--- RecordData, ParseRecordData, ComposeRecordData
+
+        impl RecordData for $target {
+            fn rtype(&self) -> Rtype {
+                Self::RTYPE
+            }
+        }
+
+        impl<'a, Octs: AsRef<[u8]> + ?Sized> ParseRecordData<'a, Octs>
+            for $target
+        {
+            fn parse_rdata(
+                rtype: Rtype,
+                parser: &mut Parser<'a, Octs>,
+            ) -> Result<Option<Self>, ParseError> {
+                if rtype == Self::RTYPE {
+                    Self::parse(parser).map(Some)
+                } else {
+                    Ok(None)
+                }
+            }
+        }
+
+        impl ComposeRecordData for $target {
+            fn rdlen(&self, _compress: bool) -> Option<u16> {
+                Some(u16::COMPOSE_LEN + u64::COMPOSE_LEN)
+            }
+
+            fn compose_rdata<Target: Composer + ?Sized>(
+                &self,
+                target: &mut Target,
+            ) -> Result<(), Target::AppendError> {
+                self.preference.compose(target)?;
+                self.$field.compose(target)
+            }
+
+            fn compose_canonical_rdata<Target: Composer + ?Sized>(
+                &self,
+                target: &mut Target,
+            ) -> Result<(), Target::AppendError> {
+                self.compose_rdata(target)
+            }
+        }
+
+        //--- Display
+
+        impl fmt::Display for $target {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{} {}", self.preference, Ilnp64(self.$field))
+            }
+        }
+
+        //--- ZonefileFmt
+
+        impl ZonefileFmt for $target {
+            fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+                p.block(|p| {
+                    p.write_token(self.preference)?;
+                    p.write_comment("preference")?;
+                    p.write_token(Ilnp64(self.$field))
+                })
+            }
+        }
+    };
+}
+
+//------------ Nid -----------------------------------------------------------
+
+ilnp64_type! {
+    /// Nid record data.
+    ///
+    /// The Nid record provides a 64 bit node identifier for the owner
+    /// name.
+    ///
+    /// The Nid record type is defined in [RFC 6742, section 2.1][1].
+    ///
+    /// [1]: https://tools.ietf.org/html/rfc6742#section-2.1
+    (Nid, NID, node_id, "node identifier")
+}
+
+//------------ L64 -----------------------------------------------------------
+
+ilnp64_type! {
+    /// L64 record data.
+    ///
+    /// The L64 record provides a 64 bit locator, i.e., the routing prefix
+    /// of an IPv6 network, for the owner name.
+    ///
+    /// The L64 record type is defined in [RFC 6742, section 2.3][1].
+    ///
+    /// [1]: https://tools.ietf.org/html/rfc6742#section-2.3
+    (L64, L64, locator, "locator")
+}
+
+//------------ L32 -----------------------------------------------------------
+
+/// L32 record data.
+///
+/// The L32 record provides a 32 bit locator, i.e., an IPv4 network, for
+/// the owner name. In presentation format, the locator is written like an
+/// IPv4 address.
+///
+/// The L32 record type is defined in [RFC 6742, section 2.2][1].
+///
+/// [1]: https://tools.ietf.org/html/rfc6742#section-2.2
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct L32 {
+    preference: u16,
+    locator: Ipv4Addr,
+}
+
+impl L32 {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::L32;
+}
+
+impl L32 {
+    /// Creates new L32 record data from its components.
+    #[must_use]
+    pub fn new(preference: u16, locator: Ipv4Addr) -> Self {
+        L32 {
+            preference,
+            locator,
+        }
+    }
+
+    /// Returns the preference of this record.
+    ///
+    /// Lower values are preferred.
+    #[must_use]
+    pub fn preference(&self) -> u16 {
+        self.preference
+    }
+
+    /// Returns the locator.
+    #[must_use]
+    pub fn locator(&self) -> Ipv4Addr {
+        self.locator
+    }
+
+    pub(super) fn convert_octets<E>(self) -> Result<Self, E> {
+        Ok(self)
+    }
+
+    pub(super) fn flatten<E>(self) -> Result<Self, E> {
+        Ok(self)
+    }
+
+    pub fn parse<Octs: AsRef<[u8]> + ?Sized>(
+        parser: &mut Parser<Octs>,
+    ) -> Result<Self, ParseError> {
+        Ok(Self::new(u16::parse(parser)?, Ipv4Addr::parse(parser)?))
+    }
+
+    pub fn scan<S: Scanner>(scanner: &mut S) -> Result<Self, S::Error> {
+        let preference = u16::scan(scanner)?;
+        let token = scanner.scan_octets()?;
+        let locator = str::from_utf8(token.as_ref())
+            .ok()
+            .and_then(|token| Ipv4Addr::from_str(token).ok())
+            .ok_or_else(|| S::Error::custom("expected locator"))?;
+        Ok(Self::new(preference, locator))
+    }
+}
+
+//--- OctetsFrom
+
+impl OctetsFrom<L32> for L32 {
+    type Error = Infallible;
+
+    fn try_octets_from(source: L32) -> Result<Self, Self::Error> {
+        Ok(source)
+    }
+}
+
+//--- CanonicalOrd
+
+impl CanonicalOrd for L32 {
+    fn canonical_cmp(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl RecordData for L32 {
+    fn rtype(&self) -> Rtype {
+        L32::RTYPE
+    }
+}
+
+impl<'a, Octs: AsRef<[u8]> + ?Sized> ParseRecordData<'a, Octs> for L32 {
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == L32::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl ComposeRecordData for L32 {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(u16::COMPOSE_LEN + 4)
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.preference.compose(target)?;
+        target.append_slice(&self.locator.octets())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl fmt::Display for L32 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.preference, self.locator)
+    }
+}
+
+//--- ZonefileFmt
+
+impl ZonefileFmt for L32 {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| {
+            p.write_token(self.preference)?;
+            p.write_comment("preference")?;
+            p.write_token(self.locator)
+        })
+    }
+}
+
+//------------ Lp ------------------------------------------------------------
+
+/// Lp record data.
+///
+/// The Lp record points to a domain name that has L32 or L64 records. It
+/// is used to provide one level of indirection for the locators of a
+/// node. The domain name is never compressed.
+///
+/// The Lp record type is defined in [RFC 6742, section 2.4][1].
+///
+/// [1]: https://tools.ietf.org/html/rfc6742#section-2.4
+#[derive(Clone, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lp<N> {
+    preference: u16,
+    fqdn: N,
+}
+
+impl Lp<()> {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::LP;
+}
+
+impl<N> Lp<N> {
+    /// Creates new Lp record data from the components.
+    pub fn new(preference: u16, fqdn: N) -> Self {
+        Lp { preference, fqdn }
+    }
+
+    /// Returns the preference of this record.
+    ///
+    /// Lower values are preferred.
+    pub fn preference(&self) -> u16 {
+        self.preference
+    }
+
+    /// Returns the domain name that has the locator records.
+    pub fn fqdn(&self) -> &N {
+        &self.fqdn
+    }
+
+    /// Converts the record data into the domain name.
+    pub fn into_fqdn(self) -> N {
+        self.fqdn
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<N>>(
+        self,
+    ) -> Result<Lp<Target>, Target::Error> {
+        Ok(Lp::new(self.preference, self.fqdn.try_octets_into()?))
+    }
+
+    pub(super) fn flatten<TargetName>(
+        self,
+    ) -> Result<Lp<TargetName>, N::AppendError>
+    where
+        N: FlattenInto<TargetName>,
+    {
+        Ok(Lp::new(self.preference, self.fqdn.try_flatten_into()?))
+    }
+
+    pub fn scan<S: Scanner<Name = N>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error> {
+        Ok(Self::new(u16::scan(scanner)?, scanner.scan_name()?))
+    }
+}
+
+impl<Octs> Lp<ParsedName<Octs>> {
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized + 'a>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        Ok(Self::new(u16::parse(parser)?, ParsedName::parse(parser)?))
+    }
+}
+
+//--- OctetsFrom and FlattenInto
+
+impl<Name, SrcName> OctetsFrom<Lp<SrcName>> for Lp<Name>
+where
+    Name: OctetsFrom<SrcName>,
+{
+    type Error = Name::Error;
+
+    fn try_octets_from(source: Lp<SrcName>) -> Result<Self, Self::Error> {
+        Ok(Lp::new(
+            source.preference,
+            Name::try_octets_from(source.fqdn)?,
+        ))
+    }
+}
+
+impl<Name, TName> FlattenInto<Lp<TName>> for Lp<Name>
+where
+    Name: FlattenInto<TName>,
+{
+    type AppendError = Name::AppendError;
+
+    fn try_flatten_into(self) -> Result<Lp<TName>, Name::AppendError> {
+        self.flatten()
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<N, NN> PartialEq<Lp<NN>> for Lp<N>
+where
+    N: ToName,
+    NN: ToName,
+{
+    fn eq(&self, other: &Lp<NN>) -> bool {
+        self.preference == other.preference && self.fqdn.name_eq(&other.fqdn)
+    }
+}
+
+impl<N: ToName> Eq for Lp<N> {}
+
+//--- PartialOrd, Ord, and CanonicalOrd
+
+impl<N, NN> PartialOrd<Lp<NN>> for Lp<N>
+where
+    N: ToName,
+    NN: ToName,
+{
+    fn partial_cmp(&self, other: &Lp<NN>) -> Option<Ordering> {
+        match self.preference.partial_cmp(&other.preference) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        Some(self.fqdn.name_cmp(&other.fqdn))
+    }
+}
+
+impl<N: ToName> Ord for Lp<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.preference.cmp(&other.preference) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.fqdn.name_cmp(&other.fqdn)
+    }
+}
+
+impl<N: ToName, NN: ToName> CanonicalOrd<Lp<NN>> for Lp<N> {
+    fn canonical_cmp(&self, other: &Lp<NN>) -> Ordering {
+        match self.preference.cmp(&other.preference) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.fqdn.composed_cmp(&other.fqdn)
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<N> RecordData for Lp<N> {
+    fn rtype(&self) -> Rtype {
+        Lp::RTYPE
+    }
+}
+
+impl<'a, Octs: Octets + ?Sized> ParseRecordData<'a, Octs>
+    for Lp<ParsedName<Octs::Range<'a>>>
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Lp::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Name: ToName> ComposeRecordData for Lp<Name> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        // LP records are not compressed.
+        Some(u16::COMPOSE_LEN + self.fqdn.compose_len())
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.preference.compose(target)?;
+        self.fqdn.compose(target)
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        // ... and not lowercased either.
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<N: ToName> fmt::Display for Lp<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.preference, self.fqdn.fmt_with_dot())
+    }
+}
+
+//--- ZonefileFmt
+
+impl<N: ToName> ZonefileFmt for Lp<N> {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| {
+            p.write_token(self.preference)?;
+            p.write_comment("preference")?;
+            p.write_token(self.fqdn.fmt_with_dot())
+        })
+    }
+}
+
+//------------ Ilnp64 --------------------------------------------------------
+
+/// Helper for displaying a 64 bit node identifier or locator.
+struct Ilnp64(u64);
+
+impl fmt::Display for Ilnp64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:04x}:{:04x}:{:04x}:{:04x}",
+            self.0 >> 48,
+            (self.0 >> 32) & 0xffff,
+            (self.0 >> 16) & 0xffff,
+            self.0 & 0xffff
+        )
+    }
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Parses the presentation format of a 64 bit node identifier or locator.
+///
+/// The value must consist of exactly four groups of one to four hexadecimal
+/// digits separated by colons.
+fn parse_ilnp64(s: &str) -> Option<u64> {
+    let mut res = 0u64;
+    let mut groups = s.split(':');
+    for _ in 0..4 {
+        let group = groups.next()?;
+        if group.is_empty()
+            || group.len() > 4
+            || !group.bytes().all(|ch| ch.is_ascii_hexdigit())
+        {
+            return None;
+        }
+        res = (res << 16) | u64::from(u16::from_str_radix(group, 16).ok()?);
+    }
+    if groups.next().is_some() {
+        return None;
+    }
+    Some(res)
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::name::Name;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    #[test]
+    fn nid_compose_parse_scan() {
+        let rdata = Nid::new(10, 0x0014_4fff_ff20_ee64);
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, Nid::parse);
+        test_scan(&["10", "0014:4fff:ff20:ee64"], Nid::scan, &rdata);
+        test_scan(&["10", "14:4FFF:FF20:EE64"], Nid::scan, &rdata);
+        assert_eq!(rdata.to_string(), "10 0014:4fff:ff20:ee64");
+    }
+
+    #[test]
+    fn l32_compose_parse_scan() {
+        let rdata = L32::new(10, Ipv4Addr::new(10, 1, 2, 0));
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, L32::parse);
+        test_scan(&["10", "10.1.2.0"], L32::scan, &rdata);
+        assert_eq!(rdata.to_string(), "10 10.1.2.0");
+    }
+
+    #[test]
+    fn l64_compose_parse_scan() {
+        let rdata = L64::new(10, 0x2001_0db8_1140_1000);
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, L64::parse);
+        test_scan(&["10", "2001:0DB8:1140:1000"], L64::scan, &rdata);
+        assert_eq!(rdata.to_string(), "10 2001:0db8:1140:1000");
+    }
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn lp_compose_parse_scan() {
+        let rdata = Lp::<Name<Vec<u8>>>::new(
+            10,
+            Name::from_str("l64-subnet1.example.com").unwrap(),
+        );
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Lp::parse(parser));
+        test_scan(&["10", "l64-subnet1.example.com"], Lp::scan, &rdata);
+        assert_eq!(rdata.to_string(), "10 l64-subnet1.example.com.");
+    }
+
+    #[test]
+    fn ilnp64_invalid() {
+        assert_eq!(parse_ilnp64("0014:4fff:ff20"), None);
+        assert_eq!(parse_ilnp64("0014:4fff:ff20:ee64:0000"), None);
+        assert_eq!(parse_ilnp64("0014:4fff::ee64"), None);
+        assert_eq!(parse_ilnp64("0014:4fff:ff20:ee640"), None);
+        assert_eq!(parse_ilnp64("0014:4fff:ff20:+e64"), None);
+    }
+}
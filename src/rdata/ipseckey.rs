@@ -0,0 +1,833 @@
+//! Record data from [RFC 4025]: IPSECKEY records.
+//!
+//! This RFC defines the IPSECKEY record type.
+//!
+//! [RFC 4025]: https://tools.ietf.org/html/rfc4025
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::{IpseckeyAlg, Rtype};
+use crate::base::name::{FlattenInto, ParsedName, ToName};
+use crate::base::net::{Ipv4Addr, Ipv6Addr};
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scan, Scanner, ScannerError};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use crate::utils::base64;
+use core::cmp::Ordering;
+use core::str::FromStr;
+use core::{fmt, hash};
+#[cfg(feature = "serde")]
+use octseq::builder::{EmptyBuilder, FromBuilder, OctetsBuilder};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Ipseckey ------------------------------------------------------
+
+/// Ipseckey record data.
+///
+/// Ipseckey records publish a public key to be used for establishing IPsec
+/// security associations with a host. Besides the key and its algorithm,
+/// the record data contains the [gateway][IpseckeyGateway] through which
+/// the host can be reached, which can be absent, an IPv4 or IPv6 address,
+/// or a domain name, and a precedence for choosing among multiple
+/// records.
+///
+/// The Ipseckey record type is defined in [RFC 4025, section 2][1].
+///
+/// [1]: https://tools.ietf.org/html/rfc4025#section-2
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>,
+            Name: serde::Serialize,
+        ",
+        deserialize = "
+            Octs: FromBuilder + octseq::serde::DeserializeOctets<'de>,
+            <Octs as FromBuilder>::Builder:
+                OctetsBuilder + EmptyBuilder,
+            Name: serde::Deserialize<'de>,
+        ",
+    ))
+)]
+pub struct Ipseckey<Octs, Name> {
+    precedence: u8,
+    algorithm: IpseckeyAlg,
+    gateway: IpseckeyGateway<Name>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base64::serde")
+    )]
+    public_key: Octs,
+}
+
+impl Ipseckey<(), ()> {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::IPSECKEY;
+}
+
+impl<Octs, Name> Ipseckey<Octs, Name> {
+    /// Creates new Ipseckey record data from its components.
+    ///
+    /// Returns an error if the record data would be too long.
+    pub fn new(
+        precedence: u8,
+        algorithm: IpseckeyAlg,
+        gateway: IpseckeyGateway<Name>,
+        public_key: Octs,
+    ) -> Result<Self, LongRecordData>
+    where
+        Octs: AsRef<[u8]>,
+        Name: ToName,
+    {
+        LongRecordData::check_len(
+            3 + usize::from(gateway.compose_len())
+                .checked_add(public_key.as_ref().len())
+                .expect("long public key"),
+        )?;
+        Ok(unsafe {
+            Self::new_unchecked(precedence, algorithm, gateway, public_key)
+        })
+    }
+
+    /// Creates new Ipseckey record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that wire format representation of the
+    /// record data is at most 65,535 octets long.
+    pub unsafe fn new_unchecked(
+        precedence: u8,
+        algorithm: IpseckeyAlg,
+        gateway: IpseckeyGateway<Name>,
+        public_key: Octs,
+    ) -> Self {
+        Ipseckey {
+            precedence,
+            algorithm,
+            gateway,
+            public_key,
+        }
+    }
+
+    /// Returns the precedence of the record.
+    ///
+    /// Records with a lower value should be tried first.
+    pub fn precedence(&self) -> u8 {
+        self.precedence
+    }
+
+    /// Returns the algorithm of the public key.
+    pub fn algorithm(&self) -> IpseckeyAlg {
+        self.algorithm
+    }
+
+    /// Returns the gateway.
+    pub fn gateway(&self) -> &IpseckeyGateway<Name> {
+        &self.gateway
+    }
+
+    /// Returns the public key.
+    ///
+    /// The key may be empty, in which case the algorithm should be
+    /// [`IpseckeyAlg::NONE`].
+    pub fn public_key(&self) -> &Octs {
+        &self.public_key
+    }
+
+    pub(super) fn convert_octets<TOcts, TName>(
+        self,
+    ) -> Result<Ipseckey<TOcts, TName>, TOcts::Error>
+    where
+        TOcts: OctetsFrom<Octs>,
+        TName: OctetsFrom<Name, Error = TOcts::Error>,
+    {
+        Ok(unsafe {
+            Ipseckey::new_unchecked(
+                self.precedence,
+                self.algorithm,
+                self.gateway.try_map_name(TName::try_octets_from)?,
+                self.public_key.try_octets_into()?,
+            )
+        })
+    }
+
+    pub(super) fn flatten<TOcts, TName>(
+        self,
+    ) -> Result<Ipseckey<TOcts, TName>, TOcts::Error>
+    where
+        TOcts: OctetsFrom<Octs>,
+        Name: FlattenInto<TName, AppendError = TOcts::Error>,
+    {
+        Ok(unsafe {
+            Ipseckey::new_unchecked(
+                self.precedence,
+                self.algorithm,
+                self.gateway.try_map_name(Name::try_flatten_into)?,
+                self.public_key.try_octets_into()?,
+            )
+        })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs, Name = Name>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+        Name: ToName,
+    {
+        let precedence = u8::scan(scanner)?;
+        let gateway_type = u8::scan(scanner)?;
+        let algorithm = IpseckeyAlg::scan(scanner)?;
+        let gateway = match gateway_type {
+            IpseckeyGateway::<Name>::NONE => scanner.scan_ascii_str(|s| {
+                if s == "." {
+                    Ok(IpseckeyGateway::None)
+                } else {
+                    Err(S::Error::custom("expected '.' for no gateway"))
+                }
+            })?,
+            IpseckeyGateway::<Name>::IPV4 => scanner.scan_ascii_str(|s| {
+                Ipv4Addr::from_str(s)
+                    .map(IpseckeyGateway::Ipv4)
+                    .map_err(|_| S::Error::custom("expected IPv4 address"))
+            })?,
+            IpseckeyGateway::<Name>::IPV6 => scanner.scan_ascii_str(|s| {
+                Ipv6Addr::from_str(s)
+                    .map(IpseckeyGateway::Ipv6)
+                    .map_err(|_| S::Error::custom("expected IPv6 address"))
+            })?,
+            IpseckeyGateway::<Name>::NAME => {
+                IpseckeyGateway::Name(scanner.scan_name()?)
+            }
+            _ => return Err(S::Error::custom("unknown gateway type")),
+        };
+        Self::new(
+            precedence,
+            algorithm,
+            gateway,
+            scanner.convert_entry(base64::SymbolConverter::new())?,
+        )
+        .map_err(|_| S::Error::custom("record data too long"))
+    }
+}
+
+impl<Octs> Ipseckey<Octs, ParsedName<Octs>> {
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized + 'a>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let precedence = u8::parse(parser)?;
+        let gateway_type = u8::parse(parser)?;
+        let algorithm = IpseckeyAlg::parse(parser)?;
+        let gateway = match gateway_type {
+            IpseckeyGateway::<()>::NONE => IpseckeyGateway::None,
+            IpseckeyGateway::<()>::IPV4 => {
+                IpseckeyGateway::Ipv4(Ipv4Addr::parse(parser)?)
+            }
+            IpseckeyGateway::<()>::IPV6 => {
+                IpseckeyGateway::Ipv6(Ipv6Addr::parse(parser)?)
+            }
+            IpseckeyGateway::<()>::NAME => {
+                IpseckeyGateway::Name(ParsedName::parse(parser)?)
+            }
+            _ => return Err(ParseError::form_error("unknown gateway type")),
+        };
+        let len = parser.remaining();
+        let public_key = parser.parse_octets(len)?;
+        Ok(unsafe {
+            Self::new_unchecked(precedence, algorithm, gateway, public_key)
+        })
+    }
+}
+
+//--- OctetsFrom and FlattenInto
+
+impl<Octs, SrcOcts, Name, SrcName> OctetsFrom<Ipseckey<SrcOcts, SrcName>>
+    for Ipseckey<Octs, Name>
+where
+    Octs: OctetsFrom<SrcOcts>,
+    Name: OctetsFrom<SrcName, Error = Octs::Error>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(
+        source: Ipseckey<SrcOcts, SrcName>,
+    ) -> Result<Self, Self::Error> {
+        source.convert_octets()
+    }
+}
+
+impl<Octs, TOcts, Name, TName> FlattenInto<Ipseckey<TOcts, TName>>
+    for Ipseckey<Octs, Name>
+where
+    TOcts: OctetsFrom<Octs>,
+    Name: FlattenInto<TName, AppendError = TOcts::Error>,
+{
+    type AppendError = TOcts::Error;
+
+    fn try_flatten_into(
+        self,
+    ) -> Result<Ipseckey<TOcts, TName>, TOcts::Error> {
+        self.flatten()
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, OtherOcts, Name, OtherName>
+    PartialEq<Ipseckey<OtherOcts, OtherName>> for Ipseckey<Octs, Name>
+where
+    Octs: AsRef<[u8]>,
+    OtherOcts: AsRef<[u8]>,
+    Name: ToName,
+    OtherName: ToName,
+{
+    fn eq(&self, other: &Ipseckey<OtherOcts, OtherName>) -> bool {
+        self.precedence == other.precedence
+            && self.algorithm == other.algorithm
+            && self.gateway.eq(&other.gateway)
+            && self.public_key.as_ref().eq(other.public_key.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>, Name: ToName> Eq for Ipseckey<Octs, Name> {}
+
+//--- PartialOrd, Ord, and CanonicalOrd
+
+impl<Octs, OtherOcts, Name, OtherName>
+    PartialOrd<Ipseckey<OtherOcts, OtherName>> for Ipseckey<Octs, Name>
+where
+    Octs: AsRef<[u8]>,
+    OtherOcts: AsRef<[u8]>,
+    Name: ToName,
+    OtherName: ToName,
+{
+    fn partial_cmp(
+        &self,
+        other: &Ipseckey<OtherOcts, OtherName>,
+    ) -> Option<Ordering> {
+        match self.precedence.partial_cmp(&other.precedence) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        match self.algorithm.partial_cmp(&other.algorithm) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        match self.gateway.partial_cmp(&other.gateway) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        self.public_key
+            .as_ref()
+            .partial_cmp(other.public_key.as_ref())
+    }
+}
+
+impl<Octs, OtherOcts, Name, OtherName>
+    CanonicalOrd<Ipseckey<OtherOcts, OtherName>> for Ipseckey<Octs, Name>
+where
+    Octs: AsRef<[u8]>,
+    OtherOcts: AsRef<[u8]>,
+    Name: ToName,
+    OtherName: ToName,
+{
+    fn canonical_cmp(
+        &self,
+        other: &Ipseckey<OtherOcts, OtherName>,
+    ) -> Ordering {
+        match self.precedence.cmp(&other.precedence) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self
+            .gateway
+            .gateway_type()
+            .cmp(&other.gateway.gateway_type())
+        {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.algorithm.cmp(&other.algorithm) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.gateway.canonical_cmp(&other.gateway) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.public_key.as_ref().cmp(other.public_key.as_ref())
+    }
+}
+
+impl<Octs, Name> Ord for Ipseckey<Octs, Name>
+where
+    Octs: AsRef<[u8]>,
+    Name: ToName,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.precedence.cmp(&other.precedence) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.algorithm.cmp(&other.algorithm) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.gateway.cmp(&other.gateway) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.public_key.as_ref().cmp(other.public_key.as_ref())
+    }
+}
+
+//--- Hash
+
+impl<Octs, Name> hash::Hash for Ipseckey<Octs, Name>
+where
+    Octs: AsRef<[u8]>,
+    Name: hash::Hash,
+{
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.precedence.hash(state);
+        self.algorithm.hash(state);
+        self.gateway.hash(state);
+        self.public_key.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs, Name> RecordData for Ipseckey<Octs, Name> {
+    fn rtype(&self) -> Rtype {
+        Ipseckey::RTYPE
+    }
+}
+
+impl<'a, Octs: Octets + ?Sized> ParseRecordData<'a, Octs>
+    for Ipseckey<Octs::Range<'a>, ParsedName<Octs::Range<'a>>>
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Ipseckey::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs, Name> ComposeRecordData for Ipseckey<Octs, Name>
+where
+    Octs: AsRef<[u8]>,
+    Name: ToName,
+{
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            (3 + self.gateway.compose_len())
+                .checked_add(
+                    u16::try_from(self.public_key.as_ref().len())
+                        .expect("long public key"),
+                )
+                .expect("long public key"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.precedence.compose(target)?;
+        self.gateway.gateway_type().compose(target)?;
+        self.algorithm.compose(target)?;
+        self.gateway.compose(target)?;
+        target.append_slice(self.public_key.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs, Name> fmt::Display for Ipseckey<Octs, Name>
+where
+    Octs: AsRef<[u8]>,
+    Name: ToName,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.precedence,
+            self.gateway.gateway_type(),
+            self.algorithm,
+            self.gateway
+        )?;
+        if !self.public_key.as_ref().is_empty() {
+            f.write_str(" ")?;
+            base64::display(&self.public_key, f)?;
+        }
+        Ok(())
+    }
+}
+
+//--- Debug
+
+impl<Octs, Name> fmt::Debug for Ipseckey<Octs, Name>
+where
+    Octs: AsRef<[u8]>,
+    Name: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Ipseckey")
+            .field("precedence", &self.precedence)
+            .field("algorithm", &self.algorithm)
+            .field("gateway", &self.gateway)
+            .field("public_key", &self.public_key.as_ref())
+            .finish()
+    }
+}
+
+//--- ZonefileFmt
+
+impl<Octs, Name> ZonefileFmt for Ipseckey<Octs, Name>
+where
+    Octs: AsRef<[u8]>,
+    Name: ToName,
+{
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| {
+            p.write_token(self.precedence)?;
+            p.write_comment("precedence")?;
+            p.write_token(self.gateway.gateway_type())?;
+            p.write_comment("gateway type")?;
+            p.write_show(self.algorithm)?;
+            match self.gateway {
+                IpseckeyGateway::None => p.write_token(".")?,
+                IpseckeyGateway::Ipv4(addr) => p.write_token(addr)?,
+                IpseckeyGateway::Ipv6(addr) => p.write_token(addr)?,
+                IpseckeyGateway::Name(ref name) => {
+                    p.write_token(name.fmt_with_dot())?
+                }
+            }
+            p.write_comment("gateway")?;
+            if !self.public_key.as_ref().is_empty() {
+                p.write_token(base64::encode_display(&self.public_key))?;
+            }
+            Ok(())
+        })
+    }
+}
+
+//------------ IpseckeyGateway -----------------------------------------------
+
+/// The gateway of an IPSECKEY record.
+///
+/// In wire format, the type of the gateway is given by a separate field
+/// which determines the encoding of the gateway itself.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IpseckeyGateway<Name> {
+    /// There is no gateway.
+    None,
+
+    /// The gateway is given as an IPv4 address.
+    Ipv4(Ipv4Addr),
+
+    /// The gateway is given as an IPv6 address.
+    Ipv6(Ipv6Addr),
+
+    /// The gateway is given as a domain name.
+    ///
+    /// The name is never compressed.
+    Name(Name),
+}
+
+impl<Name> IpseckeyGateway<Name> {
+    /// The gateway type value for no gateway.
+    pub const NONE: u8 = 0;
+
+    /// The gateway type value for an IPv4 address.
+    pub const IPV4: u8 = 1;
+
+    /// The gateway type value for an IPv6 address.
+    pub const IPV6: u8 = 2;
+
+    /// The gateway type value for a domain name.
+    pub const NAME: u8 = 3;
+
+    /// Returns the value of the gateway type field for the gateway.
+    pub fn gateway_type(&self) -> u8 {
+        match self {
+            IpseckeyGateway::None => Self::NONE,
+            IpseckeyGateway::Ipv4(_) => Self::IPV4,
+            IpseckeyGateway::Ipv6(_) => Self::IPV6,
+            IpseckeyGateway::Name(_) => Self::NAME,
+        }
+    }
+
+    /// Converts the name of the gateway using the given function.
+    fn try_map_name<TName, E>(
+        self,
+        op: impl FnOnce(Name) -> Result<TName, E>,
+    ) -> Result<IpseckeyGateway<TName>, E> {
+        Ok(match self {
+            IpseckeyGateway::None => IpseckeyGateway::None,
+            IpseckeyGateway::Ipv4(addr) => IpseckeyGateway::Ipv4(addr),
+            IpseckeyGateway::Ipv6(addr) => IpseckeyGateway::Ipv6(addr),
+            IpseckeyGateway::Name(name) => IpseckeyGateway::Name(op(name)?),
+        })
+    }
+}
+
+impl<Name: ToName> IpseckeyGateway<Name> {
+    /// Returns the length of the wire format of the gateway.
+    pub fn compose_len(&self) -> u16 {
+        match self {
+            IpseckeyGateway::None => 0,
+            IpseckeyGateway::Ipv4(_) => 4,
+            IpseckeyGateway::Ipv6(_) => 16,
+            IpseckeyGateway::Name(name) => name.compose_len(),
+        }
+    }
+
+    /// Appends the wire format of the gateway to a target.
+    pub fn compose<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        match self {
+            IpseckeyGateway::None => Ok(()),
+            IpseckeyGateway::Ipv4(addr) => addr.compose(target),
+            IpseckeyGateway::Ipv6(addr) => addr.compose(target),
+            IpseckeyGateway::Name(name) => name.compose(target),
+        }
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Name, OtherName> PartialEq<IpseckeyGateway<OtherName>>
+    for IpseckeyGateway<Name>
+where
+    Name: ToName,
+    OtherName: ToName,
+{
+    fn eq(&self, other: &IpseckeyGateway<OtherName>) -> bool {
+        match (self, other) {
+            (IpseckeyGateway::None, IpseckeyGateway::None) => true,
+            (IpseckeyGateway::Ipv4(left), IpseckeyGateway::Ipv4(right)) => {
+                left == right
+            }
+            (IpseckeyGateway::Ipv6(left), IpseckeyGateway::Ipv6(right)) => {
+                left == right
+            }
+            (IpseckeyGateway::Name(left), IpseckeyGateway::Name(right)) => {
+                left.name_eq(right)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<Name: ToName> Eq for IpseckeyGateway<Name> {}
+
+//--- PartialOrd, Ord, and CanonicalOrd
+
+impl<Name, OtherName> PartialOrd<IpseckeyGateway<OtherName>>
+    for IpseckeyGateway<Name>
+where
+    Name: ToName,
+    OtherName: ToName,
+{
+    fn partial_cmp(
+        &self,
+        other: &IpseckeyGateway<OtherName>,
+    ) -> Option<Ordering> {
+        Some(match (self, other) {
+            (IpseckeyGateway::Ipv4(left), IpseckeyGateway::Ipv4(right)) => {
+                left.octets().cmp(&right.octets())
+            }
+            (IpseckeyGateway::Ipv6(left), IpseckeyGateway::Ipv6(right)) => {
+                left.octets().cmp(&right.octets())
+            }
+            (IpseckeyGateway::Name(left), IpseckeyGateway::Name(right)) => {
+                left.name_cmp(right)
+            }
+            _ => self.gateway_type().cmp(&other.gateway_type()),
+        })
+    }
+}
+
+impl<Name: ToName> Ord for IpseckeyGateway<Name> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (IpseckeyGateway::Ipv4(left), IpseckeyGateway::Ipv4(right)) => {
+                left.octets().cmp(&right.octets())
+            }
+            (IpseckeyGateway::Ipv6(left), IpseckeyGateway::Ipv6(right)) => {
+                left.octets().cmp(&right.octets())
+            }
+            (IpseckeyGateway::Name(left), IpseckeyGateway::Name(right)) => {
+                left.name_cmp(right)
+            }
+            _ => self.gateway_type().cmp(&other.gateway_type()),
+        }
+    }
+}
+
+impl<Name, OtherName> CanonicalOrd<IpseckeyGateway<OtherName>>
+    for IpseckeyGateway<Name>
+where
+    Name: ToName,
+    OtherName: ToName,
+{
+    fn canonical_cmp(&self, other: &IpseckeyGateway<OtherName>) -> Ordering {
+        match (self, other) {
+            (IpseckeyGateway::Ipv4(left), IpseckeyGateway::Ipv4(right)) => {
+                left.octets().cmp(&right.octets())
+            }
+            (IpseckeyGateway::Ipv6(left), IpseckeyGateway::Ipv6(right)) => {
+                left.octets().cmp(&right.octets())
+            }
+            (IpseckeyGateway::Name(left), IpseckeyGateway::Name(right)) => {
+                left.composed_cmp(right)
+            }
+            _ => self.gateway_type().cmp(&other.gateway_type()),
+        }
+    }
+}
+
+//--- Hash
+
+impl<Name: hash::Hash> hash::Hash for IpseckeyGateway<Name> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        match self {
+            IpseckeyGateway::None => 0u8.hash(state),
+            IpseckeyGateway::Ipv4(addr) => {
+                1u8.hash(state);
+                addr.octets().hash(state)
+            }
+            IpseckeyGateway::Ipv6(addr) => {
+                2u8.hash(state);
+                addr.octets().hash(state)
+            }
+            IpseckeyGateway::Name(name) => {
+                3u8.hash(state);
+                name.hash(state)
+            }
+        }
+    }
+}
+
+//--- Display
+
+impl<Name: ToName> fmt::Display for IpseckeyGateway<Name> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IpseckeyGateway::None => f.write_str("."),
+            IpseckeyGateway::Ipv4(addr) => addr.fmt(f),
+            IpseckeyGateway::Ipv6(addr) => addr.fmt(f),
+            IpseckeyGateway::Name(name) => name.fmt_with_dot().fmt(f),
+        }
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::name::Name;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    type TestIpseckey = Ipseckey<Vec<u8>, Name<Vec<u8>>>;
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn ipseckey_compose_parse_scan() {
+        // The examples from RFC 4025, section 3.1, with a shorter key.
+        let cases: [(TestIpseckey, &[&str], &str); 4] = [
+            (
+                Ipseckey::new(
+                    10,
+                    IpseckeyAlg::RSA,
+                    IpseckeyGateway::Ipv4(Ipv4Addr::new(192, 0, 2, 38)),
+                    b"key".to_vec(),
+                )
+                .unwrap(),
+                &["10", "1", "2", "192.0.2.38", "a2V5"],
+                "10 1 2 192.0.2.38 a2V5",
+            ),
+            (
+                Ipseckey::new(
+                    10,
+                    IpseckeyAlg::NONE,
+                    IpseckeyGateway::None,
+                    Vec::new(),
+                )
+                .unwrap(),
+                &["10", "0", "0", "."],
+                "10 0 0 .",
+            ),
+            (
+                Ipseckey::new(
+                    10,
+                    IpseckeyAlg::RSA,
+                    IpseckeyGateway::Name(
+                        Name::vec_from_str("mygateway.example.com").unwrap(),
+                    ),
+                    b"key".to_vec(),
+                )
+                .unwrap(),
+                &["10", "3", "2", "mygateway.example.com.", "a2V5"],
+                "10 3 2 mygateway.example.com. a2V5",
+            ),
+            (
+                Ipseckey::new(
+                    10,
+                    IpseckeyAlg::RSA,
+                    IpseckeyGateway::Ipv6(
+                        Ipv6Addr::from_str("2001:0DB8:0:8002::2000:1")
+                            .unwrap(),
+                    ),
+                    b"key".to_vec(),
+                )
+                .unwrap(),
+                &["10", "2", "2", "2001:0DB8:0:8002::2000:1", "a2V5"],
+                "10 2 2 2001:db8:0:8002::2000:1 a2V5",
+            ),
+        ];
+        for (rdata, tokens, display) in cases {
+            test_rdlen(&rdata);
+            test_compose_parse(&rdata, |parser| Ipseckey::parse(parser));
+            test_scan(tokens, Ipseckey::scan, &rdata);
+            assert_eq!(rdata.to_string(), display);
+        }
+    }
+
+    #[test]
+    fn ipseckey_invalid_gateway_type() {
+        let wire = b"\x0a\x04\x02key";
+        assert!(
+            Ipseckey::parse(&mut Parser::from_ref(wire.as_ref())).is_err()
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! Record data from [RFC 2230]: KX records.
+//!
+//! This RFC defines the KX record type.
+//!
+//! [RFC 2230]: https://tools.ietf.org/html/rfc2230
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::name::{FlattenInto, ParsedName, ToName};
+use crate::base::rdata::{ComposeRecordData, ParseRecordData, RecordData};
+use crate::base::scan::{Scan, Scanner};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use core::cmp::Ordering;
+use core::fmt;
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Kx ------------------------------------------------------------
+
+/// Kx record data.
+///
+/// The Kx record specifies a host willing to act as a key exchanger for
+/// the owner name. It is similar to the Mx record but, unlike it, its
+/// target name is never compressed.
+///
+/// The Kx record type is defined in [RFC 2230, section 3.1][1].
+///
+/// [1]: https://tools.ietf.org/html/rfc2230#section-3.1
+#[derive(Clone, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Kx<N> {
+    preference: u16,
+    exchanger: N,
+}
+
+impl Kx<()> {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::KX;
+}
+
+impl<N> Kx<N> {
+    /// Creates new Kx record data from the components.
+    pub fn new(preference: u16, exchanger: N) -> Self {
+        Kx {
+            preference,
+            exchanger,
+        }
+    }
+
+    /// The preference for this record.
+    ///
+    /// Defines an order if there are several Kx records for the same owner.
+    /// Lower values are preferred.
+    pub fn preference(&self) -> u16 {
+        self.preference
+    }
+
+    /// The name of the host that is the key exchanger.
+    pub fn exchanger(&self) -> &N {
+        &self.exchanger
+    }
+
+    /// Converts the record data into the key exchanger.
+    pub fn into_exchanger(self) -> N {
+        self.exchanger
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<N>>(
+        self,
+    ) -> Result<Kx<Target>, Target::Error> {
+        Ok(Kx::new(self.preference, self.exchanger.try_octets_into()?))
+    }
+
+    pub(super) fn flatten<TargetName>(
+        self,
+    ) -> Result<Kx<TargetName>, N::AppendError>
+    where
+        N: FlattenInto<TargetName>,
+    {
+        Ok(Kx::new(self.preference, self.exchanger.try_flatten_into()?))
+    }
+
+    pub fn scan<S: Scanner<Name = N>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error> {
+        Ok(Self::new(u16::scan(scanner)?, scanner.scan_name()?))
+    }
+}
+
+impl<Octs> Kx<ParsedName<Octs>> {
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized + 'a>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        Ok(Self::new(u16::parse(parser)?, ParsedName::parse(parser)?))
+    }
+}
+
+//--- OctetsFrom and FlattenInto
+
+impl<Name, SrcName> OctetsFrom<Kx<SrcName>> for Kx<Name>
+where
+    Name: OctetsFrom<SrcName>,
+{
+    type Error = Name::Error;
+
+    fn try_octets_from(source: Kx<SrcName>) -> Result<Self, Self::Error> {
+        Ok(Kx::new(
+            source.preference,
+            Name::try_octets_from(source.exchanger)?,
+        ))
+    }
+}
+
+impl<Name, TName> FlattenInto<Kx<TName>> for Kx<Name>
+where
+    Name: FlattenInto<TName>,
+{
+    type AppendError = Name::AppendError;
+
+    fn try_flatten_into(self) -> Result<Kx<TName>, Name::AppendError> {
+        self.flatten()
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<N, NN> PartialEq<Kx<NN>> for Kx<N>
+where
+    N: ToName,
+    NN: ToName,
+{
+    fn eq(&self, other: &Kx<NN>) -> bool {
+        self.preference == other.preference
+            && self.exchanger.name_eq(&other.exchanger)
+    }
+}
+
+impl<N: ToName> Eq for Kx<N> {}
+
+//--- PartialOrd, Ord, and CanonicalOrd
+
+impl<N, NN> PartialOrd<Kx<NN>> for Kx<N>
+where
+    N: ToName,
+    NN: ToName,
+{
+    fn partial_cmp(&self, other: &Kx<NN>) -> Option<Ordering> {
+        match self.preference.partial_cmp(&other.preference) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        Some(self.exchanger.name_cmp(&other.exchanger))
+    }
+}
+
+impl<N: ToName> Ord for Kx<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.preference.cmp(&other.preference) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.exchanger.name_cmp(&other.exchanger)
+    }
+}
+
+impl<N: ToName, NN: ToName> CanonicalOrd<Kx<NN>> for Kx<N> {
+    fn canonical_cmp(&self, other: &Kx<NN>) -> Ordering {
+        match self.preference.cmp(&other.preference) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.exchanger.lowercase_composed_cmp(&other.exchanger)
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<N> RecordData for Kx<N> {
+    fn rtype(&self) -> Rtype {
+        Kx::RTYPE
+    }
+}
+
+impl<'a, Octs: Octets + ?Sized> ParseRecordData<'a, Octs>
+    for Kx<ParsedName<Octs::Range<'a>>>
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Kx::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Name: ToName> ComposeRecordData for Kx<Name> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        // KX records are not compressed.
+        Some(u16::COMPOSE_LEN + self.exchanger.compose_len())
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.preference.compose(target)?;
+        self.exchanger.compose(target)
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.preference.compose(target)?;
+        self.exchanger.compose_canonical(target) // ... but are lowercased.
+    }
+}
+
+//--- Display
+
+impl<N: ToName> fmt::Display for Kx<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.preference, self.exchanger.fmt_with_dot())
+    }
+}
+
+//--- ZonefileFmt
+
+impl<N: ToName> ZonefileFmt for Kx<N> {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| {
+            p.write_token(self.preference)?;
+            p.write_comment("preference")?;
+            p.write_token(self.exchanger.fmt_with_dot())
+        })
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::name::Name;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use core::str::FromStr;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn kx_compose_parse_scan() {
+        let rdata = Kx::<Name<Vec<u8>>>::new(
+            10,
+            Name::from_str("kx.example.com").unwrap(),
+        );
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Kx::parse(parser));
+        test_scan(&["10", "kx.example.com"], Kx::scan, &rdata);
+        assert_eq!(rdata.to_string(), "10 kx.example.com.");
+    }
+}
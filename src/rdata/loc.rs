@@ -0,0 +1,667 @@
+//! Record data from [RFC 1876]: LOC records.
+//!
+//! This RFC defines the LOC record type.
+//!
+//! [RFC 1876]: https://tools.ietf.org/html/rfc1876
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::rdata::{ComposeRecordData, ParseRecordData, RecordData};
+use crate::base::scan::{Scanner, ScannerError};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use core::cmp::Ordering;
+use core::convert::Infallible;
+use core::fmt;
+use octseq::octets::OctetsFrom;
+use octseq::parse::Parser;
+
+//------------ Loc -----------------------------------------------------------
+
+/// Loc record data.
+///
+/// Loc records convey the geographical location of a host or network. The
+/// location is given as latitude, longitude, and altitude together with
+/// the diameter of a sphere enclosing the entity and the horizontal and
+/// vertical precision of the location.
+///
+/// The latitude and longitude are given in thousandths of an arc second
+/// offset by 2<sup>31</sup> so that this value represents the equator and
+/// prime meridian, respectively. The altitude is given in centimeters above
+/// a base of 100,000 meters below the reference spheroid used by GPS. The
+/// size and precisions are represented by [`LocPrecision`].
+///
+/// The presentation format uses degrees, minutes, and seconds for the
+/// latitude and longitude and meters for everything else.
+///
+/// The Loc record type is defined in [RFC 1876, section 2][1]. Only
+/// version 0 of the record data exists.
+///
+/// [1]: https://tools.ietf.org/html/rfc1876#section-2
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Loc {
+    size: LocPrecision,
+    horiz_pre: LocPrecision,
+    vert_pre: LocPrecision,
+    latitude: u32,
+    longitude: u32,
+    altitude: u32,
+}
+
+impl Loc {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::LOC;
+
+    /// The raw value of the equator and prime meridian.
+    pub const EQUATOR: u32 = 1 << 31;
+
+    /// The raw value of the altitude of the reference spheroid.
+    pub const REFERENCE_ALTITUDE: u32 = 10_000_000;
+
+    /// The only version currently defined.
+    const VERSION: u8 = 0;
+
+    /// The maximum latitude in thousandths of an arc second.
+    const MAX_LATITUDE: u32 = 90 * 3_600_000;
+
+    /// The maximum longitude in thousandths of an arc second.
+    const MAX_LONGITUDE: u32 = 180 * 3_600_000;
+}
+
+impl Loc {
+    /// Creates new Loc record data from its raw components.
+    #[must_use]
+    pub fn new(
+        size: LocPrecision,
+        horiz_pre: LocPrecision,
+        vert_pre: LocPrecision,
+        latitude: u32,
+        longitude: u32,
+        altitude: u32,
+    ) -> Self {
+        Loc {
+            size,
+            horiz_pre,
+            vert_pre,
+            latitude,
+            longitude,
+            altitude,
+        }
+    }
+
+    /// Returns the diameter of a sphere enclosing the entity.
+    #[must_use]
+    pub fn size(&self) -> LocPrecision {
+        self.size
+    }
+
+    /// Returns the horizontal precision of the location.
+    #[must_use]
+    pub fn horiz_pre(&self) -> LocPrecision {
+        self.horiz_pre
+    }
+
+    /// Returns the vertical precision of the location.
+    #[must_use]
+    pub fn vert_pre(&self) -> LocPrecision {
+        self.vert_pre
+    }
+
+    /// Returns the raw latitude.
+    #[must_use]
+    pub fn latitude(&self) -> u32 {
+        self.latitude
+    }
+
+    /// Returns the raw longitude.
+    #[must_use]
+    pub fn longitude(&self) -> u32 {
+        self.longitude
+    }
+
+    /// Returns the raw altitude.
+    #[must_use]
+    pub fn altitude(&self) -> u32 {
+        self.altitude
+    }
+
+    /// Returns the latitude in thousandths of an arc second.
+    ///
+    /// Positive values are north of the equator, negative values south.
+    #[must_use]
+    pub fn latitude_mas(&self) -> i64 {
+        i64::from(self.latitude) - i64::from(Self::EQUATOR)
+    }
+
+    /// Returns the longitude in thousandths of an arc second.
+    ///
+    /// Positive values are east of the prime meridian, negative values
+    /// west.
+    #[must_use]
+    pub fn longitude_mas(&self) -> i64 {
+        i64::from(self.longitude) - i64::from(Self::EQUATOR)
+    }
+
+    /// Returns the altitude in centimeters above the reference spheroid.
+    #[must_use]
+    pub fn altitude_cm(&self) -> i64 {
+        i64::from(self.altitude) - i64::from(Self::REFERENCE_ALTITUDE)
+    }
+
+    pub(in crate::rdata) fn convert_octets<E>(self) -> Result<Self, E> {
+        Ok(self)
+    }
+
+    pub(in crate::rdata) fn flatten<E>(self) -> Result<Self, E> {
+        Ok(self)
+    }
+
+    pub fn parse<Octs: AsRef<[u8]> + ?Sized>(
+        parser: &mut Parser<Octs>,
+    ) -> Result<Self, ParseError> {
+        if u8::parse(parser)? != Self::VERSION {
+            return Err(ParseError::form_error("unsupported LOC version"));
+        }
+        Ok(Self::new(
+            LocPrecision::parse(parser)?,
+            LocPrecision::parse(parser)?,
+            LocPrecision::parse(parser)?,
+            u32::parse(parser)?,
+            u32::parse(parser)?,
+            u32::parse(parser)?,
+        ))
+    }
+
+    pub fn scan<S: Scanner>(scanner: &mut S) -> Result<Self, S::Error> {
+        let latitude =
+            Self::scan_coord(scanner, b'N', b'S', Self::MAX_LATITUDE)?;
+        let longitude =
+            Self::scan_coord(scanner, b'E', b'W', Self::MAX_LONGITUDE)?;
+
+        let altitude = scanner.scan_ascii_str(|s| {
+            let (neg, s) = match s.strip_prefix('-') {
+                Some(s) => (true, s),
+                None => (false, s),
+            };
+            let cm = parse_meters(s)
+                .and_then(|cm| i64::try_from(cm).ok())
+                .map(|cm| if neg { -cm } else { cm })
+                .and_then(|cm| {
+                    u32::try_from(cm + i64::from(Self::REFERENCE_ALTITUDE))
+                        .ok()
+                });
+            cm.ok_or_else(|| S::Error::custom("invalid LOC altitude"))
+        })?;
+
+        let mut res = Self::new(
+            LocPrecision::DEFAULT_SIZE,
+            LocPrecision::DEFAULT_HORIZ_PRE,
+            LocPrecision::DEFAULT_VERT_PRE,
+            latitude,
+            longitude,
+            altitude,
+        );
+        for field in [&mut res.size, &mut res.horiz_pre, &mut res.vert_pre] {
+            if !scanner.continues() {
+                break;
+            }
+            *field = LocPrecision::scan(scanner)?;
+        }
+        Ok(res)
+    }
+
+    /// Scans degrees, optional minutes and seconds, and the hemisphere.
+    ///
+    /// Returns the raw value for a coordinate of at most `max` thousandths
+    /// of an arc second in either direction. The `pos` and `neg` letters
+    /// mark the positive and negative hemisphere, respectively.
+    fn scan_coord<S: Scanner>(
+        scanner: &mut S,
+        pos: u8,
+        neg: u8,
+        max: u32,
+    ) -> Result<u32, S::Error> {
+        // The factors for degrees, minutes, and seconds. Only seconds may
+        // have a fraction.
+        const FACTORS: [u64; 3] = [3_600_000, 60_000, 1_000];
+
+        let mut value = 0u32;
+        let mut idx = 0;
+        loop {
+            let negative = scanner.scan_ascii_str(|s| {
+                let ch = match s.as_bytes() {
+                    [ch] if idx > 0 => ch.to_ascii_uppercase(),
+                    _ => 0,
+                };
+                if ch == pos {
+                    return Ok(Some(false));
+                }
+                if ch == neg {
+                    return Ok(Some(true));
+                }
+                let factor = FACTORS.get(idx).ok_or_else(|| {
+                    S::Error::custom("expected LOC hemisphere")
+                })?;
+                let item = parse_decimal(s, 3)
+                    .filter(|&item| idx == 2 || item % 1_000 == 0)
+                    .filter(|&item| idx == 0 || item < 60_000)
+                    .map(|item| item / 1_000 * factor + item % 1_000)
+                    .and_then(|item| u32::try_from(item).ok())
+                    .and_then(|item| value.checked_add(item))
+                    .ok_or_else(|| {
+                        S::Error::custom("invalid LOC coordinate")
+                    })?;
+                value = item;
+                idx += 1;
+                Ok(None)
+            })?;
+            if let Some(negative) = negative {
+                if value > max {
+                    return Err(S::Error::custom("invalid LOC coordinate"));
+                }
+                return Ok(if negative {
+                    Self::EQUATOR - value
+                } else {
+                    Self::EQUATOR + value
+                });
+            }
+        }
+    }
+}
+
+//--- OctetsFrom
+
+impl OctetsFrom<Loc> for Loc {
+    type Error = Infallible;
+
+    fn try_octets_from(source: Loc) -> Result<Self, Self::Error> {
+        Ok(source)
+    }
+}
+
+//--- CanonicalOrd
+
+impl CanonicalOrd for Loc {
+    fn canonical_cmp(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl RecordData for Loc {
+    fn rtype(&self) -> Rtype {
+        Loc::RTYPE
+    }
+}
+
+impl<'a, Octs: AsRef<[u8]> + ?Sized> ParseRecordData<'a, Octs> for Loc {
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Loc::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl ComposeRecordData for Loc {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(16)
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        Self::VERSION.compose(target)?;
+        self.size.compose(target)?;
+        self.horiz_pre.compose(target)?;
+        self.vert_pre.compose(target)?;
+        self.latitude.compose(target)?;
+        self.longitude.compose(target)?;
+        self.altitude.compose(target)
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl fmt::Display for Loc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {}",
+            DisplayCoord(self.latitude_mas(), b'N', b'S'),
+            DisplayCoord(self.longitude_mas(), b'E', b'W'),
+            DisplayAltitude(self.altitude_cm()),
+            self.size,
+            self.horiz_pre,
+            self.vert_pre,
+        )
+    }
+}
+
+//--- ZonefileFmt
+
+impl ZonefileFmt for Loc {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| {
+            p.write_token(DisplayCoord(self.latitude_mas(), b'N', b'S'))?;
+            p.write_comment("latitude")?;
+            p.write_token(DisplayCoord(self.longitude_mas(), b'E', b'W'))?;
+            p.write_comment("longitude")?;
+            p.write_token(DisplayAltitude(self.altitude_cm()))?;
+            p.write_comment("altitude")?;
+            p.write_token(self.size)?;
+            p.write_comment("size")?;
+            p.write_token(self.horiz_pre)?;
+            p.write_comment("horizontal precision")?;
+            p.write_token(self.vert_pre)?;
+            p.write_comment("vertical precision")
+        })
+    }
+}
+
+//------------ LocPrecision --------------------------------------------------
+
+/// A size or precision of Loc record data.
+///
+/// The value is a length in centimeters expressed as a mantissa and a power
+/// of ten, each of them between 0 and 9. On the wire, it is encoded in a
+/// single octet with the mantissa in the upper and the exponent in the
+/// lower four bits.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LocPrecision(u8);
+
+impl LocPrecision {
+    /// The default size of one meter.
+    pub const DEFAULT_SIZE: Self = LocPrecision(0x12);
+
+    /// The default horizontal precision of 10,000 meters.
+    pub const DEFAULT_HORIZ_PRE: Self = LocPrecision(0x16);
+
+    /// The default vertical precision of 10 meters.
+    pub const DEFAULT_VERT_PRE: Self = LocPrecision(0x13);
+
+    /// Creates a value from its wire format octet.
+    ///
+    /// Returns `None` if the mantissa or exponent is larger than 9.
+    #[must_use]
+    pub fn from_octet(octet: u8) -> Option<Self> {
+        if octet >> 4 > 9 || octet & 0x0F > 9 {
+            None
+        } else {
+            Some(LocPrecision(octet))
+        }
+    }
+
+    /// Creates a value from a length in centimeters.
+    ///
+    /// Since only the most significant digit of the length can be
+    /// represented, the length is rounded down. Returns `None` if the
+    /// length is larger than 90,000,000 meters.
+    #[must_use]
+    pub fn from_cm(cm: u64) -> Option<Self> {
+        if cm > 9_000_000_000 {
+            return None;
+        }
+        let mut exponent = 0;
+        let mut mantissa = cm;
+        while mantissa > 9 {
+            mantissa /= 10;
+            exponent += 1;
+        }
+        Some(LocPrecision((mantissa as u8) << 4 | exponent))
+    }
+
+    /// Returns the wire format octet.
+    #[must_use]
+    pub fn to_octet(self) -> u8 {
+        self.0
+    }
+
+    /// Returns the length in centimeters.
+    #[must_use]
+    pub fn to_cm(self) -> u64 {
+        u64::from(self.0 >> 4) * 10u64.pow(u32::from(self.0 & 0x0F))
+    }
+
+    pub fn parse<Octs: AsRef<[u8]> + ?Sized>(
+        parser: &mut Parser<Octs>,
+    ) -> Result<Self, ParseError> {
+        Self::from_octet(u8::parse(parser)?)
+            .ok_or_else(|| ParseError::form_error("invalid LOC precision"))
+    }
+
+    pub fn scan<S: Scanner>(scanner: &mut S) -> Result<Self, S::Error> {
+        scanner.scan_ascii_str(|s| {
+            parse_meters(s)
+                .and_then(Self::from_cm)
+                .ok_or_else(|| S::Error::custom("invalid LOC precision"))
+        })
+    }
+
+    pub fn compose<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.0.compose(target)
+    }
+}
+
+//--- Display
+
+impl fmt::Display for LocPrecision {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cm = self.to_cm();
+        write!(f, "{}.{:02}m", cm / 100, cm % 100)
+    }
+}
+
+//------------ DisplayCoord --------------------------------------------------
+
+/// Helper struct for displaying a latitude or longitude.
+struct DisplayCoord(i64, u8, u8);
+
+impl fmt::Display for DisplayCoord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = self.0.unsigned_abs();
+        write!(
+            f,
+            "{} {} {}.{:03} {}",
+            value / 3_600_000,
+            value / 60_000 % 60,
+            value / 1_000 % 60,
+            value % 1_000,
+            char::from(if self.0 < 0 { self.2 } else { self.1 })
+        )
+    }
+}
+
+//------------ DisplayAltitude -----------------------------------------------
+
+/// Helper struct for displaying an altitude.
+struct DisplayAltitude(i64);
+
+impl fmt::Display for DisplayAltitude {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = self.0.unsigned_abs();
+        write!(
+            f,
+            "{}{}.{:02}m",
+            if self.0 < 0 { "-" } else { "" },
+            value / 100,
+            value % 100
+        )
+    }
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Parses an unsigned decimal number with up to `digits` fractional digits.
+///
+/// Returns the number multiplied by 10 to the power of `digits`.
+fn parse_decimal(s: &str, digits: u32) -> Option<u64> {
+    let (int, frac) = match s.split_once('.') {
+        Some((int, frac)) => (int, frac),
+        None => (s, ""),
+    };
+    if int.is_empty()
+        || frac.len() > digits as usize
+        || !int.bytes().all(|ch| ch.is_ascii_digit())
+        || !frac.bytes().all(|ch| ch.is_ascii_digit())
+    {
+        return None;
+    }
+    let mut res = int.parse::<u64>().ok()?.checked_mul(10u64.pow(digits))?;
+    let mut factor = 10u64.pow(digits);
+    for ch in frac.bytes() {
+        factor /= 10;
+        res = res.checked_add(u64::from(ch - b'0') * factor)?;
+    }
+    Some(res)
+}
+
+/// Parses a length in meters with an optional unit into centimeters.
+fn parse_meters(s: &str) -> Option<u64> {
+    parse_decimal(s.strip_suffix(['m', 'M']).unwrap_or(s), 2)
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+
+    #[test]
+    fn loc_compose_parse_scan() {
+        // RFC 1876, section 4: cambridge-net.kei.com.
+        let rdata = Loc::new(
+            LocPrecision::from_cm(3000).unwrap(),
+            LocPrecision::DEFAULT_HORIZ_PRE,
+            LocPrecision::DEFAULT_VERT_PRE,
+            Loc::EQUATOR + 42 * 3_600_000 + 21 * 60_000 + 54_000,
+            Loc::EQUATOR - (71 * 3_600_000 + 6 * 60_000 + 18_000),
+            Loc::REFERENCE_ALTITUDE - 2400,
+        );
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, Loc::parse);
+        test_scan(
+            &["42", "21", "54", "N", "71", "06", "18", "W", "-24m", "30m"],
+            Loc::scan,
+            &rdata,
+        );
+        assert_eq!(
+            rdata.to_string(),
+            "42 21 54.000 N 71 6 18.000 W -24.00m 30.00m 10000.00m 10.00m"
+        );
+    }
+
+    #[test]
+    fn loc_scan() {
+        // RFC 1876, section 4: loiosh.kei.com.
+        test_scan(
+            &["42", "21", "43.952", "N", "71", "5", "6.344", "W", "-24m"],
+            Loc::scan,
+            &Loc::new(
+                LocPrecision::DEFAULT_SIZE,
+                LocPrecision::DEFAULT_HORIZ_PRE,
+                LocPrecision::DEFAULT_VERT_PRE,
+                Loc::EQUATOR + 42 * 3_600_000 + 21 * 60_000 + 43_952,
+                Loc::EQUATOR - (71 * 3_600_000 + 5 * 60_000 + 6_344),
+                Loc::REFERENCE_ALTITUDE - 2400,
+            ),
+        );
+
+        // RFC 1876, section 4: pipex.net.
+        test_scan(
+            &["52", "14", "05", "N", "00", "08", "50", "E", "10m"],
+            Loc::scan,
+            &Loc::new(
+                LocPrecision::DEFAULT_SIZE,
+                LocPrecision::DEFAULT_HORIZ_PRE,
+                LocPrecision::DEFAULT_VERT_PRE,
+                Loc::EQUATOR + 52 * 3_600_000 + 14 * 60_000 + 5_000,
+                Loc::EQUATOR + 8 * 60_000 + 50_000,
+                Loc::REFERENCE_ALTITUDE + 1000,
+            ),
+        );
+
+        // Degrees only, all precisions.
+        test_scan(
+            &["32", "S", "116", "E", "10", "1m", "2.5m", "3"],
+            Loc::scan,
+            &Loc::new(
+                LocPrecision::from_cm(100).unwrap(),
+                LocPrecision::from_cm(200).unwrap(),
+                LocPrecision::from_cm(300).unwrap(),
+                Loc::EQUATOR - 32 * 3_600_000,
+                Loc::EQUATOR + 116 * 3_600_000,
+                Loc::REFERENCE_ALTITUDE + 1000,
+            ),
+        );
+    }
+
+    #[test]
+    fn loc_scan_invalid() {
+        use crate::base::scan::IterScanner;
+        use std::vec::Vec;
+
+        fn scan_err(input: &[&str]) {
+            let mut scanner = IterScanner::<_, Vec<u8>>::new(
+                input
+                    .iter()
+                    .map(|s| std::string::String::from(*s))
+                    .collect::<Vec<_>>(),
+            );
+            assert!(Loc::scan(&mut scanner).is_err(), "{:?}", input);
+        }
+
+        scan_err(&["91", "N", "0", "E", "0m"]);
+        scan_err(&["0", "N", "181", "E", "0m"]);
+        scan_err(&["10", "60", "N", "0", "E", "0m"]);
+        scan_err(&["10", "1.5", "N", "0", "E", "0m"]);
+        scan_err(&["10", "1", "1", "1", "N", "0", "E", "0m"]);
+        scan_err(&["10", "N", "0", "E"]);
+        scan_err(&["10", "N", "0", "E", "-100000.01m"]);
+        scan_err(&["10", "N", "0", "E", "0m", "90000001m"]);
+    }
+
+    #[test]
+    fn loc_precision() {
+        assert_eq!(LocPrecision::DEFAULT_SIZE.to_cm(), 100);
+        assert_eq!(LocPrecision::DEFAULT_HORIZ_PRE.to_cm(), 1_000_000);
+        assert_eq!(LocPrecision::DEFAULT_VERT_PRE.to_cm(), 1_000);
+        assert_eq!(LocPrecision::from_cm(0).unwrap().to_octet(), 0x00);
+        assert_eq!(LocPrecision::from_cm(3000).unwrap().to_octet(), 0x33);
+        assert_eq!(LocPrecision::from_cm(3999).unwrap().to_octet(), 0x33);
+        assert_eq!(
+            LocPrecision::from_cm(9_000_000_000).unwrap().to_octet(),
+            0x99
+        );
+        assert_eq!(LocPrecision::from_cm(10_000_000_000), None);
+        assert_eq!(LocPrecision::from_octet(0xA0), None);
+        assert_eq!(LocPrecision::from_octet(0x0A), None);
+        assert!(Loc::parse(&mut Parser::from_ref(
+            b"\x01\x12\x16\x13\x80\0\0\0\x80\0\0\0\0\x98\x96\x80".as_ref()
+        ))
+        .is_err());
+    }
+}
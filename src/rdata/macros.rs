@@ -454,7 +454,7 @@ macro_rules! rdata_types {
         impl<O, N> fmt::Display for ZoneRecordData<O, N>
         where
             O: AsRef<[u8]>,
-            N: fmt::Display
+            N: ToName
         {
             fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
                 match *self {
@@ -1068,7 +1068,7 @@ macro_rules! rdata_types {
         //--- Display and Debug
 
         impl<O, N> fmt::Display for AllRecordData<O, N>
-        where O: Octets, N: fmt::Display {
+        where O: Octets, N: ToName {
             fn fmt(
                 &self, f: &mut fmt::Formatter
             ) -> fmt::Result {
@@ -1339,9 +1339,9 @@ macro_rules! name_type_base {
 
         //--- Display
 
-        impl<N: fmt::Display> fmt::Display for $target<N> {
+        impl<N: ToName> fmt::Display for $target<N> {
             fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                write!(f, "{}.", self.$field)
+                self.$field.fmt_with_dot().fmt(f)
             }
         }
 
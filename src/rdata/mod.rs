@@ -86,12 +86,47 @@ rdata_types! {
             Aaaa,
         }
     }
+    afsdb::{
+        zone {
+            Afsdb<N>,
+        }
+    }
+    amtrelay::{
+        zone {
+            Amtrelay<N>,
+        }
+    }
+    apl::{
+        zone {
+            Apl<O>,
+        }
+    }
+    caa::{
+        zone {
+            Caa<O>,
+        }
+    }
     cds::{
         zone {
             Cdnskey<O>,
             Cds<O>,
         }
     }
+    cert::{
+        zone {
+            Cert<O>,
+        }
+    }
+    csync::{
+        zone {
+            Csync<O>,
+        }
+    }
+    dhcid::{
+        zone {
+            Dhcid<O>,
+        }
+    }
     dname::{
         zone {
             Dname<N>,
@@ -105,6 +140,40 @@ rdata_types! {
             Ds<O>,
         }
     }
+    eui::{
+        zone {
+            Eui48,
+            Eui64,
+        }
+    }
+    hip::{
+        zone {
+            Hip<O>,
+        }
+    }
+    ilnp::{
+        zone {
+            Nid,
+            L32,
+            L64,
+            Lp<N>,
+        }
+    }
+    ipseckey::{
+        zone {
+            Ipseckey<O, N>,
+        }
+    }
+    kx::{
+        zone {
+            Kx<N>,
+        }
+    }
+    loc::{
+        zone {
+            Loc,
+        }
+    }
     naptr::{
         zone {
             Naptr<O, N>,
@@ -116,22 +185,57 @@ rdata_types! {
             Nsec3param<O>,
         }
     }
+    openpgpkey::{
+        zone {
+            Openpgpkey<O>,
+        }
+    }
+    rp::{
+        zone {
+            Rp<N>,
+        }
+    }
+    smimea::{
+        zone {
+            Smimea<O>,
+        }
+    }
     srv::{
         zone {
             Srv<N>,
         }
     }
+    sshfp::{
+        zone {
+            Sshfp<O>,
+        }
+    }
     svcb::{
-        pseudo {
+        zone {
             Svcb<O, N>,
             Https<O, N>,
         }
     }
+    tkey::{
+        pseudo {
+            Tkey<O, N>,
+        }
+    }
+    tlsa::{
+        zone {
+            Tlsa<O>,
+        }
+    }
     tsig::{
         pseudo {
             Tsig<O, N>,
         }
     }
+    uri::{
+        zone {
+            Uri<O>,
+        }
+    }
     zonemd::{
         zone {
             Zonemd<O>,
@@ -446,18 +446,18 @@ where
 impl<Octs, Name> core::fmt::Display for Naptr<Octs, Name>
 where
     Octs: AsRef<[u8]>,
-    Name: fmt::Display,
+    Name: ToName,
 {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(
             f,
-            "{} {} {} {} {} {}.",
+            "{} {} {} {} {} {}",
             self.order,
             self.preference,
             self.flags.display_quoted(),
             self.services.display_quoted(),
             self.regexp.display_quoted(),
-            self.replacement
+            self.replacement.fmt_with_dot()
         )
     }
 }
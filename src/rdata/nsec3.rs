@@ -351,10 +351,16 @@ impl<Octs: AsRef<[u8]>> fmt::Display for Nsec3<Octs> {
         write!(
             f,
             "{} {} {} {} ",
-            self.hash_algorithm, self.flags, self.iterations, self.salt
+            self.hash_algorithm.to_int(),
+            self.flags,
+            self.iterations,
+            self.salt
         )?;
         base32::display_hex(&self.next_owner, f)?;
-        write!(f, " {}", self.types)
+        if !self.types.as_slice().is_empty() {
+            write!(f, " {}", self.types)?;
+        }
+        Ok(())
     }
 }
 
@@ -670,7 +676,10 @@ impl<Octs: AsRef<[u8]>> fmt::Display for Nsec3param<Octs> {
         write!(
             f,
             "{} {} {} {}",
-            self.hash_algorithm, self.flags, self.iterations, self.salt
+            self.hash_algorithm.to_int(),
+            self.flags,
+            self.iterations,
+            self.salt
         )
     }
 }
@@ -0,0 +1,360 @@
+//! Record data from [RFC 7929]: OPENPGPKEY records.
+//!
+//! This RFC defines the OPENPGPKEY record type.
+//!
+//! [RFC 7929]: https://tools.ietf.org/html/rfc7929
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+#[cfg(feature = "ring")]
+use crate::base::name::{Name, NameBuilder, PushNameError, ToName};
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scanner, ScannerError};
+use crate::base::wire::{Composer, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use crate::utils::base64;
+use core::cmp::Ordering;
+use core::{fmt, hash};
+#[cfg(feature = "ring")]
+use octseq::builder::FreezeBuilder;
+#[cfg(feature = "serde")]
+use octseq::builder::FromBuilder;
+#[cfg(any(feature = "ring", feature = "serde"))]
+use octseq::builder::{EmptyBuilder, OctetsBuilder};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Openpgpkey ----------------------------------------------------
+
+/// Openpgpkey record data.
+///
+/// Openpgpkey records publish the OpenPGP transferable public key for an
+/// email address. The records are found at an owner name derived from the
+/// local part of the email address, which can be created via
+/// [`Openpgpkey::owner_name`].
+///
+/// In presentation format, the key is given in base64.
+///
+/// The Openpgpkey record type is defined in [RFC 7929, section 2][1].
+///
+/// [1]: https://tools.ietf.org/html/rfc7929#section-2
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs: FromBuilder + octseq::serde::DeserializeOctets<'de>,
+            <Octs as FromBuilder>::Builder:
+                OctetsBuilder + EmptyBuilder,
+        ",
+    ))
+)]
+pub struct Openpgpkey<Octs> {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base64::serde")
+    )]
+    key: Octs,
+}
+
+impl Openpgpkey<()> {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::OPENPGPKEY;
+
+    /// The label following the hashed local part in the owner name.
+    pub const OWNER_LABEL: &'static [u8] = b"_openpgpkey";
+
+    /// Returns the owner name label for the local part of an email address.
+    ///
+    /// The label consists of the first 28 octets of the SHA-256 digest of
+    /// the local part in lowercase hex. The local part is used as is,
+    /// without any canonicalization.
+    #[cfg(feature = "ring")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+    pub fn owner_hash_label(local_part: &[u8]) -> [u8; 56] {
+        // RFC 8162 uses the very same label for SMIMEA.
+        super::Smimea::owner_hash_label(local_part)
+    }
+
+    /// Returns the owner name of Openpgpkey records for an email address.
+    ///
+    /// The email address is given via its local part and its domain. The
+    /// owner name consists of the [hash label][Self::owner_hash_label] for
+    /// the local part, the [`OWNER_LABEL`][Self::OWNER_LABEL] and the
+    /// domain.
+    #[cfg(feature = "ring")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+    pub fn owner_name<Builder, N>(
+        local_part: &[u8],
+        domain: &N,
+    ) -> Result<Name<Builder::Octets>, PushNameError>
+    where
+        Builder: OctetsBuilder
+            + EmptyBuilder
+            + FreezeBuilder
+            + AsRef<[u8]>
+            + AsMut<[u8]>,
+        N: ToName,
+    {
+        // Both labels are short enough, so the only possible error is
+        // running out of buffer.
+        let mut builder = NameBuilder::<Builder>::new();
+        builder
+            .append_label(&Self::owner_hash_label(local_part))
+            .map_err(|_| PushNameError::ShortBuf)?;
+        builder
+            .append_label(Self::OWNER_LABEL)
+            .map_err(|_| PushNameError::ShortBuf)?;
+        builder.append_origin(domain)
+    }
+}
+
+impl<Octs> Openpgpkey<Octs> {
+    /// Creates new Openpgpkey record data from the public key.
+    ///
+    /// Returns an error if the record data would be too long.
+    pub fn new(key: Octs) -> Result<Self, LongRecordData>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        LongRecordData::check_len(key.as_ref().len())?;
+        Ok(unsafe { Openpgpkey::new_unchecked(key) })
+    }
+
+    /// Creates new Openpgpkey record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that wire format representation of the
+    /// record data is at most 65,535 octets long.
+    pub unsafe fn new_unchecked(key: Octs) -> Self {
+        Openpgpkey { key }
+    }
+
+    /// Returns the OpenPGP transferable public key.
+    pub fn key(&self) -> &Octs {
+        &self.key
+    }
+
+    /// Converts the record data into the public key.
+    pub fn into_key(self) -> Octs {
+        self.key
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Openpgpkey<Target>, Target::Error> {
+        Ok(unsafe { Openpgpkey::new_unchecked(self.key.try_octets_into()?) })
+    }
+
+    pub(super) fn flatten<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Openpgpkey<Target>, Target::Error> {
+        self.convert_octets()
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let len = parser.remaining();
+        Ok(unsafe { Self::new_unchecked(parser.parse_octets(len)?) })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Self::new(scanner.convert_entry(base64::SymbolConverter::new())?)
+            .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Openpgpkey<SrcOcts>> for Openpgpkey<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(
+        source: Openpgpkey<SrcOcts>,
+    ) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Openpgpkey::new_unchecked(Octs::try_octets_from(source.key)?)
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Openpgpkey<Other>> for Openpgpkey<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Openpgpkey<Other>) -> bool {
+        self.key.as_ref().eq(other.key.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Openpgpkey<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Openpgpkey<Other>> for Openpgpkey<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Openpgpkey<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Openpgpkey<Other>> for Openpgpkey<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Openpgpkey<Other>) -> Ordering {
+        self.key.as_ref().cmp(other.key.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Openpgpkey<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Openpgpkey<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.key.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Openpgpkey<Octs> {
+    fn rtype(&self) -> Rtype {
+        Openpgpkey::RTYPE
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Openpgpkey<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Openpgpkey::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Openpgpkey<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(self.key.as_ref().len().try_into().expect("long key"))
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        target.append_slice(self.key.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Openpgpkey<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        base64::display(&self.key, f)
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Openpgpkey<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Openpgpkey")
+            .field("key", &self.key.as_ref())
+            .finish()
+    }
+}
+
+//--- ZonefileFmt
+
+impl<Octs: AsRef<[u8]>> ZonefileFmt for Openpgpkey<Octs> {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| p.write_token(base64::encode_display(&self.key)))
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn openpgpkey_compose_parse_scan() {
+        let rdata = Openpgpkey::new(b"public key".to_vec()).unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Openpgpkey::parse(parser));
+        test_scan(&["cHVibGljIGtleQ=="], Openpgpkey::scan, &rdata);
+        test_scan(&["cHVibGlj", "IGtleQ=="], Openpgpkey::scan, &rdata);
+        assert_eq!(rdata.to_string(), "cHVibGljIGtleQ==");
+    }
+
+    #[cfg(feature = "ring")]
+    #[test]
+    fn openpgpkey_owner_name() {
+        use crate::base::Name;
+        use core::str::FromStr;
+        use std::vec::Vec;
+
+        // RFC 7929, section 3.
+        assert_eq!(
+            Openpgpkey::owner_name::<Vec<u8>, _>(
+                b"hugh",
+                &Name::<Vec<u8>>::from_str("example.com").unwrap()
+            )
+            .unwrap(),
+            Name::<Vec<u8>>::from_str(
+                "c93f1e400f26708f98cb19d936620da35eec8f72e57f9eec01c1afd6.\
+                 _openpgpkey.example.com"
+            )
+            .unwrap()
+        );
+    }
+}
@@ -239,9 +239,14 @@ impl<Name: ToName> ComposeRecordData for Minfo<Name> {
 
 //--- Display
 
-impl<N: fmt::Display> fmt::Display for Minfo<N> {
+impl<N: ToName> fmt::Display for Minfo<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}. {}.", self.rmailbx, self.emailbx)
+        write!(
+            f,
+            "{} {}",
+            self.rmailbx.fmt_with_dot(),
+            self.emailbx.fmt_with_dot()
+        )
     }
 }
 
@@ -222,9 +222,9 @@ impl<Name: ToName> ComposeRecordData for Mx<Name> {
 
 //--- Display
 
-impl<N: fmt::Display> fmt::Display for Mx<N> {
+impl<N: ToName> fmt::Display for Mx<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} {}.", self.preference, self.exchange)
+        write!(f, "{} {}", self.preference, self.exchange.fmt_with_dot())
     }
 }
 
@@ -388,13 +388,13 @@ impl<Name: ToName> Soa<Name> {
 
 //--- Display
 
-impl<N: fmt::Display> fmt::Display for Soa<N> {
+impl<N: ToName> fmt::Display for Soa<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "{}. {}. {} {} {} {} {}",
-            self.mname,
-            self.rname,
+            "{} {} {} {} {} {} {}",
+            self.mname.fmt_with_dot(),
+            self.rname.fmt_with_dot(),
             self.serial,
             self.refresh.as_secs(),
             self.retry.as_secs(),
@@ -205,14 +205,14 @@ impl<Octs: AsRef<[u8]> + ?Sized> Txt<Octs> {
     /// Returns an iterator over the character strings as slices.
     ///
     /// The returned iterator will always return at least one octets slice.
-    pub fn iter(&self) -> TxtIter {
+    pub fn iter(&self) -> TxtIter<'_> {
         TxtIter(self.iter_charstrs())
     }
 
     /// Returns an iterator over the character strings.
     ///
     /// The returned iterator will always return at least one octets slice.
-    pub fn iter_charstrs(&self) -> TxtCharStrIter {
+    pub fn iter_charstrs(&self) -> TxtCharStrIter<'_> {
         TxtCharStrIter(Parser::from_ref(self.0.as_ref()))
     }
 
@@ -0,0 +1,282 @@
+//! Record data from [RFC 1183]: RP records.
+//!
+//! Among others, this RFC defines the RP record type.
+//!
+//! [RFC 1183]: https://tools.ietf.org/html/rfc1183
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::name::{FlattenInto, ParsedName, ToName};
+use crate::base::rdata::{ComposeRecordData, ParseRecordData, RecordData};
+use crate::base::scan::Scanner;
+use crate::base::wire::{Composer, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use core::cmp::Ordering;
+use core::fmt;
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Rp ------------------------------------------------------------
+
+/// Rp record data.
+///
+/// The Rp record specifies the person responsible for the owner name. It
+/// consists of the mailbox of that person and the name of a domain that
+/// has Txt records with further information.
+///
+/// The Rp record type is defined in [RFC 1183, section 2.2][1].
+///
+/// [1]: https://tools.ietf.org/html/rfc1183#section-2.2
+#[derive(Clone, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rp<N> {
+    mbox: N,
+    txt: N,
+}
+
+impl Rp<()> {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::RP;
+}
+
+impl<N> Rp<N> {
+    /// Creates a new Rp record data from the components.
+    pub fn new(mbox: N, txt: N) -> Self {
+        Rp { mbox, txt }
+    }
+
+    /// The mailbox of the responsible person.
+    ///
+    /// The first label of the domain name contains the local part of the
+    /// mailbox. If this domain name is the root, no mailbox is available.
+    pub fn mbox(&self) -> &N {
+        &self.mbox
+    }
+
+    /// The domain name for Txt records with further information.
+    ///
+    /// If this domain name is the root, no such records exist.
+    pub fn txt(&self) -> &N {
+        &self.txt
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<N>>(
+        self,
+    ) -> Result<Rp<Target>, Target::Error> {
+        Ok(Rp::new(
+            self.mbox.try_octets_into()?,
+            self.txt.try_octets_into()?,
+        ))
+    }
+
+    pub(super) fn flatten<TargetName>(
+        self,
+    ) -> Result<Rp<TargetName>, N::AppendError>
+    where
+        N: FlattenInto<TargetName>,
+    {
+        Ok(Rp::new(
+            self.mbox.try_flatten_into()?,
+            self.txt.try_flatten_into()?,
+        ))
+    }
+
+    pub fn scan<S: Scanner<Name = N>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error> {
+        Ok(Self::new(scanner.scan_name()?, scanner.scan_name()?))
+    }
+}
+
+impl<Octs> Rp<ParsedName<Octs>> {
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        Ok(Self::new(
+            ParsedName::parse(parser)?,
+            ParsedName::parse(parser)?,
+        ))
+    }
+}
+
+//--- OctetsFrom and FlattenInto
+
+impl<Name, SrcName> OctetsFrom<Rp<SrcName>> for Rp<Name>
+where
+    Name: OctetsFrom<SrcName>,
+{
+    type Error = Name::Error;
+
+    fn try_octets_from(source: Rp<SrcName>) -> Result<Self, Self::Error> {
+        Ok(Rp::new(
+            Name::try_octets_from(source.mbox)?,
+            Name::try_octets_from(source.txt)?,
+        ))
+    }
+}
+
+impl<Name, TName> FlattenInto<Rp<TName>> for Rp<Name>
+where
+    Name: FlattenInto<TName>,
+{
+    type AppendError = Name::AppendError;
+
+    fn try_flatten_into(self) -> Result<Rp<TName>, Name::AppendError> {
+        self.flatten()
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<N, NN> PartialEq<Rp<NN>> for Rp<N>
+where
+    N: ToName,
+    NN: ToName,
+{
+    fn eq(&self, other: &Rp<NN>) -> bool {
+        self.mbox.name_eq(&other.mbox) && self.txt.name_eq(&other.txt)
+    }
+}
+
+impl<N: ToName> Eq for Rp<N> {}
+
+//--- PartialOrd, Ord, and CanonicalOrd
+
+impl<N, NN> PartialOrd<Rp<NN>> for Rp<N>
+where
+    N: ToName,
+    NN: ToName,
+{
+    fn partial_cmp(&self, other: &Rp<NN>) -> Option<Ordering> {
+        match self.mbox.name_cmp(&other.mbox) {
+            Ordering::Equal => {}
+            other => return Some(other),
+        }
+        Some(self.txt.name_cmp(&other.txt))
+    }
+}
+
+impl<N: ToName> Ord for Rp<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.mbox.name_cmp(&other.mbox) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.txt.name_cmp(&other.txt)
+    }
+}
+
+impl<N: ToName, NN: ToName> CanonicalOrd<Rp<NN>> for Rp<N> {
+    fn canonical_cmp(&self, other: &Rp<NN>) -> Ordering {
+        match self.mbox.lowercase_composed_cmp(&other.mbox) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.txt.lowercase_composed_cmp(&other.txt)
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<N> RecordData for Rp<N> {
+    fn rtype(&self) -> Rtype {
+        Rp::RTYPE
+    }
+}
+
+impl<'a, Octs: Octets + ?Sized> ParseRecordData<'a, Octs>
+    for Rp<ParsedName<Octs::Range<'a>>>
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rp::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Name: ToName> ComposeRecordData for Rp<Name> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        // RP records are not compressed.
+        Some(self.mbox.compose_len() + self.txt.compose_len())
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.mbox.compose(target)?;
+        self.txt.compose(target)
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        // ... but are lowercased.
+        self.mbox.compose_canonical(target)?;
+        self.txt.compose_canonical(target)
+    }
+}
+
+//--- Display
+
+impl<N: ToName> fmt::Display for Rp<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            self.mbox.fmt_with_dot(),
+            self.txt.fmt_with_dot()
+        )
+    }
+}
+
+//--- ZonefileFmt
+
+impl<N: ToName> ZonefileFmt for Rp<N> {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| {
+            p.write_token(self.mbox.fmt_with_dot())?;
+            p.write_comment("mailbox")?;
+            p.write_token(self.txt.fmt_with_dot())?;
+            p.write_comment("TXT domain")
+        })
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::name::Name;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use core::str::FromStr;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn rp_compose_parse_scan() {
+        let rdata = Rp::<Name<Vec<u8>>>::new(
+            Name::from_str("admin.example.com").unwrap(),
+            Name::from_str("info.example.com").unwrap(),
+        );
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Rp::parse(parser));
+        test_scan(
+            &["admin.example.com", "info.example.com"],
+            Rp::scan,
+            &rdata,
+        );
+        assert_eq!(rdata.to_string(), "admin.example.com. info.example.com.");
+    }
+}
@@ -0,0 +1,553 @@
+//! Record data from [RFC 8162]: SMIMEA records.
+//!
+//! This RFC defines the SMIMEA record type.
+//!
+//! [RFC 8162]: https://tools.ietf.org/html/rfc8162
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::{
+    Rtype, TlsaCertUsage, TlsaMatchingType, TlsaSelector,
+};
+#[cfg(feature = "ring")]
+use crate::base::name::{Name, NameBuilder, PushNameError, ToName};
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scanner, ScannerError};
+use crate::base::wire::{Composer, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use crate::utils::base16;
+use core::cmp::Ordering;
+use core::{fmt, hash};
+#[cfg(feature = "ring")]
+use octseq::builder::FreezeBuilder;
+#[cfg(feature = "serde")]
+use octseq::builder::FromBuilder;
+#[cfg(any(feature = "ring", feature = "serde"))]
+use octseq::builder::{EmptyBuilder, OctetsBuilder};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Smimea --------------------------------------------------------
+
+/// Smimea record data.
+///
+/// Smimea records associate an S/MIME certificate with an email address.
+/// The record data has the same format as that of [Tlsa][super::Tlsa]
+/// records. The records are found at an owner name derived from the local
+/// part of the email address, which can be created via
+/// [`Smimea::owner_name`].
+///
+/// The Smimea record type is defined in [RFC 8162, section 2][1].
+///
+/// [1]: https://tools.ietf.org/html/rfc8162#section-2
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs: FromBuilder + octseq::serde::DeserializeOctets<'de>,
+            <Octs as FromBuilder>::Builder:
+                OctetsBuilder + EmptyBuilder,
+        ",
+    ))
+)]
+pub struct Smimea<Octs> {
+    cert_usage: TlsaCertUsage,
+    selector: TlsaSelector,
+    matching_type: TlsaMatchingType,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base16::serde")
+    )]
+    data: Octs,
+}
+
+impl Smimea<()> {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::SMIMEA;
+
+    /// The label following the hashed local part in the owner name.
+    pub const OWNER_LABEL: &'static [u8] = b"_smimecert";
+
+    /// Returns the owner name label for the local part of an email address.
+    ///
+    /// The label consists of the first 28 octets of the SHA-256 digest of
+    /// the local part in lowercase hex. The local part is used as is,
+    /// without any canonicalization.
+    #[cfg(feature = "ring")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+    pub fn owner_hash_label(local_part: &[u8]) -> [u8; 56] {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+
+        let digest = ring::digest::digest(&ring::digest::SHA256, local_part);
+        let mut res = [0u8; 56];
+        for (octet, chunk) in digest.as_ref().iter().zip(res.chunks_mut(2)) {
+            chunk[0] = HEX[usize::from(octet >> 4)];
+            chunk[1] = HEX[usize::from(octet & 0x0F)];
+        }
+        res
+    }
+
+    /// Returns the owner name of Smimea records for an email address.
+    ///
+    /// The email address is given via its local part and its domain. The
+    /// owner name consists of the [hash label][Self::owner_hash_label] for
+    /// the local part, the [`OWNER_LABEL`][Self::OWNER_LABEL] and the
+    /// domain.
+    #[cfg(feature = "ring")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+    pub fn owner_name<Builder, N>(
+        local_part: &[u8],
+        domain: &N,
+    ) -> Result<Name<Builder::Octets>, PushNameError>
+    where
+        Builder: OctetsBuilder
+            + EmptyBuilder
+            + FreezeBuilder
+            + AsRef<[u8]>
+            + AsMut<[u8]>,
+        N: ToName,
+    {
+        // Both labels are short enough, so the only possible error is
+        // running out of buffer.
+        let mut builder = NameBuilder::<Builder>::new();
+        builder
+            .append_label(&Self::owner_hash_label(local_part))
+            .map_err(|_| PushNameError::ShortBuf)?;
+        builder
+            .append_label(Self::OWNER_LABEL)
+            .map_err(|_| PushNameError::ShortBuf)?;
+        builder.append_origin(domain)
+    }
+}
+
+impl<Octs> Smimea<Octs> {
+    /// Creates new Smimea record data from its components.
+    ///
+    /// Returns an error if the record data would be too long.
+    pub fn new(
+        cert_usage: TlsaCertUsage,
+        selector: TlsaSelector,
+        matching_type: TlsaMatchingType,
+        data: Octs,
+    ) -> Result<Self, LongRecordData>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        LongRecordData::check_len(
+            usize::from(
+                TlsaCertUsage::COMPOSE_LEN
+                    + TlsaSelector::COMPOSE_LEN
+                    + TlsaMatchingType::COMPOSE_LEN,
+            )
+            .checked_add(data.as_ref().len())
+            .expect("long association data"),
+        )?;
+        Ok(unsafe {
+            Smimea::new_unchecked(cert_usage, selector, matching_type, data)
+        })
+    }
+
+    /// Creates new Smimea record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that wire format representation of the
+    /// record data is at most 65,535 octets long.
+    pub unsafe fn new_unchecked(
+        cert_usage: TlsaCertUsage,
+        selector: TlsaSelector,
+        matching_type: TlsaMatchingType,
+        data: Octs,
+    ) -> Self {
+        Smimea {
+            cert_usage,
+            selector,
+            matching_type,
+            data,
+        }
+    }
+
+    /// Returns how the association is to be verified.
+    pub fn cert_usage(&self) -> TlsaCertUsage {
+        self.cert_usage
+    }
+
+    /// Returns which part of the certificate is matched.
+    pub fn selector(&self) -> TlsaSelector {
+        self.selector
+    }
+
+    /// Returns how the selected part is presented in the association data.
+    pub fn matching_type(&self) -> TlsaMatchingType {
+        self.matching_type
+    }
+
+    /// Returns the certificate association data.
+    pub fn data(&self) -> &Octs {
+        &self.data
+    }
+
+    /// Converts the record data into the certificate association data.
+    pub fn into_data(self) -> Octs {
+        self.data
+    }
+
+    /// Returns whether the record data matches a certificate.
+    ///
+    /// The certificate is given in its DER encoding. The selector and
+    /// matching type are used in the same way as for TLSA records, see
+    /// [`Tlsa::matches`][super::tlsa::Tlsa::matches] for details. The
+    /// certificate usage isn’t considered.
+    #[cfg(feature = "ring")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+    pub fn matches(&self, cert: &[u8]) -> bool
+    where
+        Octs: AsRef<[u8]>,
+    {
+        super::tlsa::association_matches(
+            self.selector,
+            self.matching_type,
+            self.data.as_ref(),
+            cert,
+        )
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Smimea<Target>, Target::Error> {
+        Ok(unsafe {
+            Smimea::new_unchecked(
+                self.cert_usage,
+                self.selector,
+                self.matching_type,
+                self.data.try_octets_into()?,
+            )
+        })
+    }
+
+    pub(super) fn flatten<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Smimea<Target>, Target::Error> {
+        self.convert_octets()
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let len = match parser.remaining().checked_sub(3) {
+            Some(len) => len,
+            None => return Err(ParseError::ShortInput),
+        };
+        Ok(unsafe {
+            Self::new_unchecked(
+                TlsaCertUsage::parse(parser)?,
+                TlsaSelector::parse(parser)?,
+                TlsaMatchingType::parse(parser)?,
+                parser.parse_octets(len)?,
+            )
+        })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Self::new(
+            TlsaCertUsage::scan(scanner)?,
+            TlsaSelector::scan(scanner)?,
+            TlsaMatchingType::scan(scanner)?,
+            scanner.convert_entry(base16::SymbolConverter::new())?,
+        )
+        .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Smimea<SrcOcts>> for Smimea<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Smimea<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Smimea::new_unchecked(
+                source.cert_usage,
+                source.selector,
+                source.matching_type,
+                Octs::try_octets_from(source.data)?,
+            )
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Smimea<Other>> for Smimea<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Smimea<Other>) -> bool {
+        self.cert_usage == other.cert_usage
+            && self.selector == other.selector
+            && self.matching_type == other.matching_type
+            && self.data.as_ref().eq(other.data.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Smimea<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Smimea<Other>> for Smimea<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Smimea<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Smimea<Other>> for Smimea<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Smimea<Other>) -> Ordering {
+        match self.cert_usage.cmp(&other.cert_usage) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.selector.cmp(&other.selector) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.matching_type.cmp(&other.matching_type) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.data.as_ref().cmp(other.data.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Smimea<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Smimea<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.cert_usage.hash(state);
+        self.selector.hash(state);
+        self.matching_type.hash(state);
+        self.data.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Smimea<Octs> {
+    fn rtype(&self) -> Rtype {
+        Smimea::RTYPE
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Smimea<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Smimea::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Smimea<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            u16::checked_add(
+                TlsaCertUsage::COMPOSE_LEN
+                    + TlsaSelector::COMPOSE_LEN
+                    + TlsaMatchingType::COMPOSE_LEN,
+                self.data
+                    .as_ref()
+                    .len()
+                    .try_into()
+                    .expect("long association data"),
+            )
+            .expect("long association data"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.cert_usage.compose(target)?;
+        self.selector.compose(target)?;
+        self.matching_type.compose(target)?;
+        target.append_slice(self.data.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Smimea<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} ",
+            self.cert_usage, self.selector, self.matching_type
+        )?;
+        for ch in self.data.as_ref() {
+            write!(f, "{:02x}", ch)?
+        }
+        Ok(())
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Smimea<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Smimea")
+            .field("cert_usage", &self.cert_usage)
+            .field("selector", &self.selector)
+            .field("matching_type", &self.matching_type)
+            .field("data", &self.data.as_ref())
+            .finish()
+    }
+}
+
+//--- ZonefileFmt
+
+impl<Octs: AsRef<[u8]>> ZonefileFmt for Smimea<Octs> {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| {
+            p.write_show(self.cert_usage)?;
+            p.write_show(self.selector)?;
+            p.write_show(self.matching_type)?;
+            p.write_token(base16::encode_display(&self.data))
+        })
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use crate::utils::base16::decode;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    // RFC 6698, section 2.3, reused for SMIMEA.
+    const DIGEST: &str =
+        "d2abde240d7cd3ee6b4b28c54df034b97983a1d16e8a410e4561cb106618e971";
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn smimea_compose_parse_scan() {
+        let rdata = Smimea::new(
+            TlsaCertUsage::PKIX_TA,
+            TlsaSelector::CERT,
+            TlsaMatchingType::SHA2_256,
+            decode::<Vec<u8>>(DIGEST).unwrap(),
+        )
+        .unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Smimea::parse(parser));
+        test_scan(&["0", "0", "1", DIGEST], Smimea::scan, &rdata);
+        test_scan(
+            &["0", "0", "1", &DIGEST[..32], &DIGEST[32..]],
+            Smimea::scan,
+            &rdata,
+        );
+        assert_eq!(rdata.to_string(), format!("0 0 1 {}", DIGEST));
+    }
+
+    #[cfg(feature = "ring")]
+    #[test]
+    fn smimea_matches() {
+        use crate::rdata::tlsa::test::{CERT, SPKI};
+
+        let cert = decode::<Vec<u8>>(CERT).unwrap();
+        let spki = decode::<Vec<u8>>(SPKI).unwrap();
+        let digest = ring::digest::digest(&ring::digest::SHA256, &spki);
+        let rdata = Smimea::new(
+            TlsaCertUsage::DANE_EE,
+            TlsaSelector::SPKI,
+            TlsaMatchingType::SHA2_256,
+            digest.as_ref(),
+        )
+        .unwrap();
+        assert!(rdata.matches(&cert));
+        assert!(!rdata.matches(&spki));
+
+        let rdata = Smimea::new(
+            TlsaCertUsage::DANE_EE,
+            TlsaSelector::CERT,
+            TlsaMatchingType::FULL,
+            cert.as_slice(),
+        )
+        .unwrap();
+        assert!(rdata.matches(&cert));
+        assert!(!rdata.matches(&cert[1..]));
+    }
+
+    #[cfg(feature = "ring")]
+    #[test]
+    fn smimea_owner_name() {
+        use crate::base::Name;
+        use core::str::FromStr;
+
+        // RFC 8162, section 3.
+        assert_eq!(
+            &Smimea::owner_hash_label(b"hugh"),
+            b"c93f1e400f26708f98cb19d936620da35eec8f72e57f9eec01c1afd6"
+        );
+        assert_eq!(
+            Smimea::owner_name::<Vec<u8>, _>(
+                b"hugh",
+                &Name::<Vec<u8>>::from_str("example.com").unwrap()
+            )
+            .unwrap(),
+            Name::<Vec<u8>>::from_str(
+                "c93f1e400f26708f98cb19d936620da35eec8f72e57f9eec01c1afd6.\
+                 _smimecert.example.com"
+            )
+            .unwrap()
+        );
+    }
+}
@@ -111,6 +111,46 @@ impl<Octs> Srv<ParsedName<Octs>> {
     }
 }
 
+#[cfg(feature = "rand")]
+impl<N: ToName> Srv<N> {
+    /// Orders a set of SRV records for use by a client.
+    ///
+    /// Reorders the records in `records` into the order in which the
+    /// targets should be tried as described in [RFC 2782]: Records are
+    /// grouped by priority with lower values first. Within each group,
+    /// records are picked by weighted random selection.
+    ///
+    /// Records with the root name as their target are moved to the end
+    /// and excluded from the returned slice. A single such record signals
+    /// that the service is decidedly not available, in which case the
+    /// returned slice is empty.
+    ///
+    /// [RFC 2782]: https://tools.ietf.org/html/rfc2782
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    pub fn order_targets(records: &mut [Self]) -> &mut [Self] {
+        Self::order_targets_with_rng(records, &mut rand::thread_rng())
+    }
+
+    /// Orders a set of SRV records using the given random number generator.
+    ///
+    /// This is the same as [`order_targets`][Self::order_targets] but
+    /// uses `rng` for the weighted selection.
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    pub fn order_targets_with_rng<'a, R: rand::Rng + ?Sized>(
+        records: &'a mut [Self],
+        rng: &mut R,
+    ) -> &'a mut [Self] {
+        records.sort_unstable_by_key(|srv| srv.target.compose_len() == 1);
+        let len = records
+            .iter()
+            .position(|srv| srv.target.compose_len() == 1)
+            .unwrap_or(records.len());
+        let records = &mut records[..len];
+        order_by_priority(records, |srv| (srv.priority, srv.weight), rng);
+        records
+    }
+}
+
 //--- OctetsFrom and FlattenInto
 
 impl<Name, SrcName> OctetsFrom<Srv<SrcName>> for Srv<Name>
@@ -273,12 +313,15 @@ impl<Name: ToName> Srv<Name> {
 
 //--- Display
 
-impl<N: fmt::Display> fmt::Display for Srv<N> {
+impl<N: ToName> fmt::Display for Srv<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
             "{} {} {} {}",
-            self.priority, self.weight, self.port, self.target
+            self.priority,
+            self.weight,
+            self.port,
+            self.target.fmt_with_dot()
         )
     }
 }
@@ -299,6 +342,64 @@ impl<N: ToName> ZonefileFmt for Srv<N> {
     }
 }
 
+//------------ Helper Functions ----------------------------------------------
+
+/// Orders items by priority and weight as described in RFC 2782.
+///
+/// The closure `key` returns the priority and weight of an item. Items are
+/// sorted by ascending priority. Within each priority group, the items are
+/// then picked in turn with a probability proportional to their weight.
+/// Items with weight zero sort first in the group so they have a small
+/// chance of being picked first, too.
+#[cfg(feature = "rand")]
+pub(crate) fn order_by_priority<T, R: rand::Rng + ?Sized>(
+    items: &mut [T],
+    key: impl Fn(&T) -> (u16, u16),
+    rng: &mut R,
+) {
+    items.sort_unstable_by_key(&key);
+    let mut items = items;
+    while !items.is_empty() {
+        let priority = key(&items[0]).0;
+        let len = items
+            .iter()
+            .position(|item| key(item).0 != priority)
+            .unwrap_or(items.len());
+        let (group, tail) = items.split_at_mut(len);
+        order_by_weight(group, |item| key(item).1, rng);
+        items = tail;
+    }
+}
+
+/// Orders the items of a single priority group by weighted selection.
+///
+/// Expects items with weight zero at the beginning of `items`.
+#[cfg(feature = "rand")]
+fn order_by_weight<T, R: rand::Rng + ?Sized>(
+    items: &mut [T],
+    weight: impl Fn(&T) -> u16,
+    rng: &mut R,
+) {
+    let mut weight_sum: u32 =
+        items.iter().map(|item| u32::from(weight(item))).sum();
+    for start in 0..items.len() {
+        let pick = rng.gen_range(0..=weight_sum);
+        let mut sum = 0;
+        let mut chosen = items.len() - 1;
+        for (i, item) in items.iter().enumerate().skip(start) {
+            sum += u32::from(weight(item));
+            if sum >= pick {
+                chosen = i;
+                break;
+            }
+        }
+        weight_sum -= u32::from(weight(&items[chosen]));
+        // Rotating rather than swapping keeps the remaining items in order
+        // and thus the zero weight ones at the beginning.
+        items[start..=chosen].rotate_right(1);
+    }
+}
+
 //============ Testing ======================================================
 
 #[cfg(test)]
@@ -310,6 +411,7 @@ mod test {
         test_compose_parse, test_rdlen, test_scan,
     };
     use core::str::FromStr;
+    use std::string::ToString;
     use std::vec::Vec;
 
     #[test]
@@ -324,5 +426,70 @@ mod test {
         test_rdlen(&rdata);
         test_compose_parse(&rdata, |parser| Srv::parse(parser));
         test_scan(&["10", "11", "12", "example.com."], Srv::scan, &rdata);
+        assert_eq!(rdata.to_string(), "10 11 12 example.com.");
+    }
+
+    #[cfg(feature = "rand")]
+    fn srv(priority: u16, weight: u16, target: &str) -> Srv<Name<Vec<u8>>> {
+        Srv::new(priority, weight, 0, Name::from_str(target).unwrap())
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn order_targets() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(2782);
+        for _ in 0..100 {
+            let mut records = [
+                srv(20, 0, "d.example."),
+                srv(10, 60, "a.example."),
+                srv(30, 0, "."),
+                srv(10, 0, "b.example."),
+                srv(20, 10, "c.example."),
+                srv(10, 20, "e.example."),
+            ];
+            let ordered = Srv::order_targets_with_rng(&mut records, &mut rng);
+            assert_eq!(ordered.len(), 5);
+            let prios: Vec<_> =
+                ordered.iter().map(|s| s.priority()).collect();
+            assert_eq!(prios, [10, 10, 10, 20, 20]);
+            assert!(ordered.iter().all(|s| !s.target().is_root()));
+        }
+
+        let mut records = [srv(0, 0, ".")];
+        assert!(Srv::order_targets(&mut records).is_empty());
+        let mut records: [Srv<Name<Vec<u8>>>; 0] = [];
+        assert!(Srv::order_targets(&mut records).is_empty());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn order_targets_weighted() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        // With weights 90 and 10 and the zero weight record, the first
+        // should come first about 90% of the time.
+        let mut rng = StdRng::seed_from_u64(2782);
+        let mut first = [0usize; 3];
+        for _ in 0..1000 {
+            let mut records = [
+                srv(10, 10, "b.example."),
+                srv(10, 0, "c.example."),
+                srv(10, 90, "a.example."),
+            ];
+            let ordered = Srv::order_targets_with_rng(&mut records, &mut rng);
+            let idx = match ordered[0].weight() {
+                90 => 0,
+                10 => 1,
+                _ => 2,
+            };
+            first[idx] += 1;
+        }
+        assert!(first[0] > 850 && first[0] < 950, "{:?}", first);
+        assert!(first[1] > 50 && first[1] < 150, "{:?}", first);
+        assert!(first[2] < 30, "{:?}", first);
     }
 }
@@ -0,0 +1,525 @@
+//! Record data from [RFC 4255]: SSHFP records.
+//!
+//! This RFC defines the SSHFP record type.
+//!
+//! [RFC 4255]: https://tools.ietf.org/html/rfc4255
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::{Rtype, SshfpAlg, SshfpFpType};
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scanner, ScannerError};
+use crate::base::wire::{Composer, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use crate::utils::base16;
+use core::cmp::Ordering;
+use core::{fmt, hash};
+#[cfg(any(feature = "ring", feature = "serde"))]
+use octseq::builder::{EmptyBuilder, FromBuilder, OctetsBuilder};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Sshfp ---------------------------------------------------------
+
+/// Sshfp record data.
+///
+/// Sshfp records publish the fingerprint of an SSH host key under the
+/// domain name of the host. This allows clients to verify the host key
+/// presented by a server via DNS. The record data consists of the
+/// algorithm of the public key, the type of the fingerprint, i.e., the
+/// hash function used to create it, and the fingerprint itself.
+///
+/// The Sshfp record type is defined in [RFC 4255, section 3][1].
+///
+/// [1]: https://tools.ietf.org/html/rfc4255#section-3
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs: FromBuilder + octseq::serde::DeserializeOctets<'de>,
+            <Octs as FromBuilder>::Builder:
+                OctetsBuilder + EmptyBuilder,
+        ",
+    ))
+)]
+pub struct Sshfp<Octs> {
+    algorithm: SshfpAlg,
+    fp_type: SshfpFpType,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base16::serde")
+    )]
+    fingerprint: Octs,
+}
+
+impl Sshfp<()> {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::SSHFP;
+}
+
+impl<Octs> Sshfp<Octs> {
+    /// Creates new Sshfp record data from its components.
+    ///
+    /// Returns an error if the record data would be too long.
+    pub fn new(
+        algorithm: SshfpAlg,
+        fp_type: SshfpFpType,
+        fingerprint: Octs,
+    ) -> Result<Self, LongRecordData>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        LongRecordData::check_len(
+            usize::from(SshfpAlg::COMPOSE_LEN + SshfpFpType::COMPOSE_LEN)
+                .checked_add(fingerprint.as_ref().len())
+                .expect("long fingerprint"),
+        )?;
+        Ok(unsafe { Sshfp::new_unchecked(algorithm, fp_type, fingerprint) })
+    }
+
+    /// Creates new Sshfp record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that wire format representation of the
+    /// record data is at most 65,535 octets long.
+    pub unsafe fn new_unchecked(
+        algorithm: SshfpAlg,
+        fp_type: SshfpFpType,
+        fingerprint: Octs,
+    ) -> Self {
+        Sshfp {
+            algorithm,
+            fp_type,
+            fingerprint,
+        }
+    }
+
+    /// Creates new Sshfp record data for an SSH public key.
+    ///
+    /// The key needs to be given in the SSH wire format as used in the
+    /// base 64 encoded part of the `authorized_keys` and `known_hosts`
+    /// files. The algorithm is determined from the key type name at the
+    /// start of the key. The fingerprint is calculated using the hash
+    /// function given by `fp_type`.
+    ///
+    /// Returns an error if the key is malformed, if its type isn’t one
+    /// of the known key types, or if the fingerprint type isn’t
+    /// supported.
+    #[cfg(feature = "ring")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+    pub fn from_public_key(
+        key: &[u8],
+        fp_type: SshfpFpType,
+    ) -> Result<Self, SshfpKeyError>
+    where
+        Octs: FromBuilder,
+        <Octs as FromBuilder>::Builder: OctetsBuilder + EmptyBuilder,
+    {
+        let algorithm = Self::key_algorithm(key)?;
+        let digest = ring::digest::digest(
+            match fp_type {
+                SshfpFpType::SHA1 => &ring::digest::SHA1_FOR_LEGACY_USE_ONLY,
+                SshfpFpType::SHA256 => &ring::digest::SHA256,
+                _ => return Err(SshfpKeyError::UnsupportedFpType),
+            },
+            key,
+        );
+        let mut fingerprint = <Octs as FromBuilder>::Builder::empty();
+        fingerprint
+            .append_slice(digest.as_ref())
+            .map_err(|_| SshfpKeyError::ShortBuf)?;
+        Ok(unsafe {
+            Sshfp::new_unchecked(
+                algorithm,
+                fp_type,
+                Octs::from_builder(fingerprint),
+            )
+        })
+    }
+
+    /// Determines the algorithm from the key type name of an SSH key.
+    #[cfg(feature = "ring")]
+    fn key_algorithm(key: &[u8]) -> Result<SshfpAlg, SshfpKeyError> {
+        let mut parser = Parser::from_ref(key);
+        let len = parser
+            .parse_u32_be()
+            .map_err(|_| SshfpKeyError::InvalidKey)?;
+        let name = usize::try_from(len)
+            .ok()
+            .and_then(|len| parser.peek(len).ok())
+            .ok_or(SshfpKeyError::InvalidKey)?;
+        match name {
+            b"ssh-rsa" => Ok(SshfpAlg::RSA),
+            b"ssh-dss" => Ok(SshfpAlg::DSA),
+            b"ecdsa-sha2-nistp256"
+            | b"ecdsa-sha2-nistp384"
+            | b"ecdsa-sha2-nistp521" => Ok(SshfpAlg::ECDSA),
+            b"ssh-ed25519" => Ok(SshfpAlg::ED25519),
+            b"ssh-ed448" => Ok(SshfpAlg::ED448),
+            _ => Err(SshfpKeyError::UnknownKeyType),
+        }
+    }
+
+    /// Returns the algorithm of the public key.
+    pub fn algorithm(&self) -> SshfpAlg {
+        self.algorithm
+    }
+
+    /// Returns the type of the fingerprint.
+    pub fn fp_type(&self) -> SshfpFpType {
+        self.fp_type
+    }
+
+    /// Returns the fingerprint.
+    pub fn fingerprint(&self) -> &Octs {
+        &self.fingerprint
+    }
+
+    /// Converts the record data into the fingerprint.
+    pub fn into_fingerprint(self) -> Octs {
+        self.fingerprint
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Sshfp<Target>, Target::Error> {
+        Ok(unsafe {
+            Sshfp::new_unchecked(
+                self.algorithm,
+                self.fp_type,
+                self.fingerprint.try_octets_into()?,
+            )
+        })
+    }
+
+    pub(super) fn flatten<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Sshfp<Target>, Target::Error> {
+        self.convert_octets()
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let len = match parser.remaining().checked_sub(2) {
+            Some(len) => len,
+            None => return Err(ParseError::ShortInput),
+        };
+        Ok(unsafe {
+            Self::new_unchecked(
+                SshfpAlg::parse(parser)?,
+                SshfpFpType::parse(parser)?,
+                parser.parse_octets(len)?,
+            )
+        })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Self::new(
+            SshfpAlg::scan(scanner)?,
+            SshfpFpType::scan(scanner)?,
+            scanner.convert_entry(base16::SymbolConverter::new())?,
+        )
+        .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Sshfp<SrcOcts>> for Sshfp<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Sshfp<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Sshfp::new_unchecked(
+                source.algorithm,
+                source.fp_type,
+                Octs::try_octets_from(source.fingerprint)?,
+            )
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Sshfp<Other>> for Sshfp<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Sshfp<Other>) -> bool {
+        self.algorithm == other.algorithm
+            && self.fp_type == other.fp_type
+            && self.fingerprint.as_ref().eq(other.fingerprint.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Sshfp<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Sshfp<Other>> for Sshfp<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Sshfp<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Sshfp<Other>> for Sshfp<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Sshfp<Other>) -> Ordering {
+        match self.algorithm.cmp(&other.algorithm) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.fp_type.cmp(&other.fp_type) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.fingerprint.as_ref().cmp(other.fingerprint.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Sshfp<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Sshfp<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.algorithm.hash(state);
+        self.fp_type.hash(state);
+        self.fingerprint.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Sshfp<Octs> {
+    fn rtype(&self) -> Rtype {
+        Sshfp::RTYPE
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Sshfp<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Sshfp::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Sshfp<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            u16::checked_add(
+                SshfpAlg::COMPOSE_LEN + SshfpFpType::COMPOSE_LEN,
+                self.fingerprint
+                    .as_ref()
+                    .len()
+                    .try_into()
+                    .expect("long fingerprint"),
+            )
+            .expect("long fingerprint"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.algorithm.compose(target)?;
+        self.fp_type.compose(target)?;
+        target.append_slice(self.fingerprint.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Sshfp<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} ", self.algorithm, self.fp_type)?;
+        for ch in self.fingerprint.as_ref() {
+            write!(f, "{:02x}", ch)?
+        }
+        Ok(())
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Sshfp<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Sshfp")
+            .field("algorithm", &self.algorithm)
+            .field("fp_type", &self.fp_type)
+            .field("fingerprint", &self.fingerprint.as_ref())
+            .finish()
+    }
+}
+
+//--- ZonefileFmt
+
+impl<Octs: AsRef<[u8]>> ZonefileFmt for Sshfp<Octs> {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| {
+            p.write_show(self.algorithm)?;
+            p.write_show(self.fp_type)?;
+            p.write_token(base16::encode_display(&self.fingerprint))
+        })
+    }
+}
+
+//============ Error Types ===================================================
+
+//------------ SshfpKeyError -------------------------------------------------
+
+/// Sshfp record data could not be created from an SSH public key.
+#[cfg(feature = "ring")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SshfpKeyError {
+    /// The key was not in SSH wire format.
+    InvalidKey,
+
+    /// The key type is not one of the known types.
+    UnknownKeyType,
+
+    /// The fingerprint type is not supported.
+    UnsupportedFpType,
+
+    /// The octets builder for the fingerprint was too short.
+    ShortBuf,
+}
+
+#[cfg(feature = "ring")]
+impl fmt::Display for SshfpKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            SshfpKeyError::InvalidKey => "invalid SSH public key",
+            SshfpKeyError::UnknownKeyType => "unknown SSH key type",
+            SshfpKeyError::UnsupportedFpType => {
+                "unsupported fingerprint type"
+            }
+            SshfpKeyError::ShortBuf => "short buffer",
+        })
+    }
+}
+
+#[cfg(all(feature = "ring", feature = "std"))]
+impl std::error::Error for SshfpKeyError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use crate::utils::base16::decode;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    // RFC 4255, section 3.3.
+    const FINGERPRINT: &str = "123456789abcdef67890123456789abcdef67890";
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn sshfp_compose_parse_scan() {
+        let rdata = Sshfp::new(
+            SshfpAlg::DSA,
+            SshfpFpType::SHA1,
+            decode::<Vec<u8>>(FINGERPRINT).unwrap(),
+        )
+        .unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Sshfp::parse(parser));
+        test_scan(&["2", "1", FINGERPRINT], Sshfp::scan, &rdata);
+        assert_eq!(rdata.to_string(), format!("2 1 {}", FINGERPRINT));
+    }
+
+    #[cfg(feature = "ring")]
+    #[test]
+    fn sshfp_from_public_key() {
+        use crate::utils::base64;
+
+        // Generated with `ssh-keygen -t ed25519` and fingerprinted with
+        // `ssh-keygen -r`.
+        let key: Vec<u8> = base64::decode(
+            "AAAAC3NzaC1lZDI1NTE5AAAAIIaDUISl3bh+SjxMnVitL3dy+p+J7ePuXILqfZkjTiuF",
+        )
+        .unwrap();
+        assert_eq!(
+            Sshfp::<Vec<u8>>::from_public_key(&key, SshfpFpType::SHA1)
+                .unwrap()
+                .to_string(),
+            "4 1 c6b642275da67452515bc08eea3676033ff03c2c"
+        );
+        assert_eq!(
+            Sshfp::<Vec<u8>>::from_public_key(&key, SshfpFpType::SHA256)
+                .unwrap()
+                .to_string(),
+            "4 2 357cbd69dd27b5651d781ac36a7ac86502884ab1d31c547f23da2a414ee12990"
+        );
+
+        assert_eq!(
+            Sshfp::<Vec<u8>>::from_public_key(
+                b"\x00\x00\x00\x07ssh-foo",
+                SshfpFpType::SHA1
+            ),
+            Err(SshfpKeyError::UnknownKeyType)
+        );
+        assert_eq!(
+            Sshfp::<Vec<u8>>::from_public_key(
+                b"\x00\x00\x00\x08ssh-rsa",
+                SshfpFpType::SHA1
+            ),
+            Err(SshfpKeyError::InvalidKey)
+        );
+        assert_eq!(
+            Sshfp::<Vec<u8>>::from_public_key(&key, SshfpFpType::from_int(3)),
+            Err(SshfpKeyError::UnsupportedFpType)
+        );
+    }
+}
@@ -1,7 +1,7 @@
 //! Record data for SVCB/HTTPS records.
 //!
-//! Service binding records are an IETF standard currently in development as
-//! [draft-ietf-dnsop-svcb-https](https://datatracker.ietf.org/doc/draft-ietf-dnsop-svcb-https/).
+//! Service binding records are defined in
+//! [RFC 9460](https://tools.ietf.org/html/rfc9460).
 //! They provide clients with information for accessing a service in one
 //! place rather than via multiple records.
 //!
@@ -27,9 +27,14 @@
 //! A new sequence of values can be constructed using the [`SvcParamsBuilder`]
 //! type.
 //!
+//! Record data can also be read from its presentation format via
+//! [`SvcbRdata::scan`] which converts the parameters through
+//! [`SvcParams::scan`].
+//!
 pub use self::params::{
     SvcParams, ValueIter, UnknownSvcParam,
     SvcParamValue, ParseSvcParamValue, ComposeSvcParamValue,
+    SvcParamRegistry,
     SvcParamsBuilder,
     SvcParamsError, LongSvcParam, PushError
 };
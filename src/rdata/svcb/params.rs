@@ -10,7 +10,7 @@
 use super::value::AllValues;
 use crate::base::cmp::CanonicalOrd;
 use crate::base::iana::SvcParamKey;
-use crate::base::scan::Symbol;
+use crate::base::scan::{Scanner, ScannerError, Symbol};
 use crate::base::wire::{Compose, Parse, ParseError};
 use crate::base::zonefile_fmt::{
     self, Formatter, ZonefileFmt,
@@ -18,7 +18,9 @@ use crate::base::zonefile_fmt::{
 use core::cmp::Ordering;
 use core::marker::PhantomData;
 use core::{cmp, fmt, hash, mem};
-use octseq::builder::{EmptyBuilder, FromBuilder, OctetsBuilder, ShortBuf};
+use octseq::builder::{
+    EmptyBuilder, FreezeBuilder, FromBuilder, OctetsBuilder, ShortBuf,
+};
 use octseq::octets::{Octets, OctetsFrom, OctetsInto};
 use octseq::parse::{Parser, ShortInput};
 
@@ -190,6 +192,151 @@ impl<Octs: AsRef<[u8]>> SvcParams<Octs> {
     }
 }
 
+impl<Octs> SvcParams<Octs> {
+    /// Scans a parameter sequence from its presentation format.
+    ///
+    /// The parameters are taken from all the remaining tokens of the entry.
+    /// Each parameter is given as `key=value` or, if it doesn’t have a
+    /// value, just as `key`. Keys are either the registered names or of the
+    /// form `keyNNNNN` with the decimal value of the key. The value can be
+    /// quoted, in which case it may also appear as a separate token directly
+    /// following the equals sign as in `alpn="h2,h3"`.
+    ///
+    /// The values of the parameters defined in [RFC 9460] are converted into
+    /// their wire format according to the rules given there. Values for all
+    /// other keys are used as is. The parameters can be given in any order
+    /// but each key may only appear once. All keys listed in the mandatory
+    /// parameter need to be present and the no-default-alpn parameter
+    /// requires the alpn parameter.
+    ///
+    /// Parameters beyond those can be supported through
+    /// [`scan_with`][Self::scan_with].
+    ///
+    /// [RFC 9460]: https://tools.ietf.org/html/rfc9460
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where Octs: AsRef<[u8]> {
+        Self::scan_with::<(), S>(scanner)
+    }
+
+    /// Scans a parameter sequence using additional parameters.
+    ///
+    /// This works like [`scan`][Self::scan] but first consults the
+    /// registry `R` for the names of keys and the presentation format of
+    /// values. See [`SvcParamRegistry`] for details.
+    pub fn scan_with<R: SvcParamRegistry, S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where Octs: AsRef<[u8]> {
+        let mut builder = SvcParamsBuilder {
+            octets: scanner.octets_builder()?,
+        };
+        while scanner.continues() {
+            let mut raw = scanner.octets_builder()?;
+            let (key, has_value) = Self::scan_raw_param::<R, _>(
+                scanner, &mut raw
+            )?;
+            let mut value = scanner.octets_builder()?;
+            super::value::scan_value::<R, _, _>(
+                key, has_value.then_some(raw.as_ref()), &mut value
+            )?;
+            let len = u16::try_from(value.as_ref().len()).map_err(|_| {
+                S::Error::custom("long SvcParam value")
+            })?;
+            builder.push_raw(key, len, |target| {
+                target.append_slice(value.as_ref())
+            }).map_err(|err| match err {
+                PushError::DuplicateKey => {
+                    S::Error::custom("duplicate SvcParam key")
+                }
+                PushError::ShortBuf => S::Error::short_buf(),
+            })?;
+        }
+        let mut target = scanner.octets_builder()?;
+        builder.freeze_into(&mut target).map_err(|_| S::Error::short_buf())?;
+        let res = unsafe { Self::from_octets_unchecked(target.freeze()) };
+        res.for_slice().check_scanned()?;
+        Ok(res)
+    }
+
+    /// Scans the key and the unprocessed value of a single parameter.
+    ///
+    /// Appends the value to `value` and returns the key and whether there
+    /// was a value at all.
+    fn scan_raw_param<R: SvcParamRegistry, S: Scanner>(
+        scanner: &mut S,
+        value: &mut S::OctetsBuilder,
+    ) -> Result<(SvcParamKey, bool), S::Error> {
+        // The longest registered key name is “no-default-alpn” but a
+        // registry may use longer names.
+        let mut key = [0u8; MAX_KEY_NAME_LEN];
+        let mut key_len = 0;
+        let mut has_value = false;
+        scanner.scan_symbols(|symbol| {
+            if has_value {
+                let ch = symbol.into_octet().map_err(|_| {
+                    S::Error::custom("invalid SvcParam value")
+                })?;
+                value.append_slice(&[ch]).map_err(|_| S::Error::short_buf())
+            }
+            else if symbol == Symbol::Char('=') {
+                has_value = true;
+                Ok(())
+            }
+            else {
+                match (symbol.into_ascii(), key.get_mut(key_len)) {
+                    (Ok(ch), Some(slot)) => {
+                        *slot = ch;
+                        key_len += 1;
+                        Ok(())
+                    }
+                    _ => Err(S::Error::custom("unknown SvcParam key"))
+                }
+            }
+        })?;
+        let key = key_from_name::<R>(&key[..key_len]).ok_or_else(|| {
+            S::Error::custom("unknown SvcParam key")
+        })?;
+
+        // A quoted value is a token of its own following the equals sign
+        // without any white space.
+        if has_value && value.as_ref().is_empty()
+            && scanner.continues() && !scanner.has_space()
+        {
+            scanner.scan_symbols(|symbol| {
+                let ch = symbol.into_octet().map_err(|_| {
+                    S::Error::custom("invalid SvcParam value")
+                })?;
+                value.append_slice(&[ch]).map_err(|_| S::Error::short_buf())
+            })?;
+        }
+        Ok((key, has_value))
+    }
+}
+
+impl SvcParams<[u8]> {
+    /// Checks the relationships between scanned parameters.
+    fn check_scanned<Error: ScannerError>(&self) -> Result<(), Error> {
+        let has_key = |key| {
+            self.iter::<UnknownSvcParam<_>>().any(|item| {
+                item.map(|item| item.key() == key).unwrap_or(false)
+            })
+        };
+        if let Some(mandatory) = self.mandatory() {
+            if !mandatory.iter().all(has_key) {
+                return Err(Error::custom("missing mandatory SvcParam"))
+            }
+        }
+        if has_key(SvcParamKey::NO_DEFAULT_ALPN)
+            && !has_key(SvcParamKey::ALPN)
+        {
+            return Err(Error::custom("no-default-alpn without alpn"))
+        }
+        Ok(())
+    }
+}
+
 impl<Octs: ?Sized> SvcParams<Octs> {
     /// Returns a reference to the underlying octets sequence.
     pub fn as_octets(&self) -> &Octs {
@@ -236,12 +383,12 @@ impl<Octs: AsRef<[u8]> + ?Sized> SvcParams<Octs> {
     }
 
     /// Returns an iterator over all values accepted by `Value`.
-    pub fn iter<Value>(&self) -> ValueIter<Octs, Value> {
+    pub fn iter<Value>(&self) -> ValueIter<'_, Octs, Value> {
         ValueIter::new(self.as_octets())
     }
 
     /// Returns an iterator over all values.
-    pub fn iter_all(&self) -> ValueIter<Octs, AllValues<Octs>>
+    pub fn iter_all(&self) -> ValueIter<'_, Octs, AllValues<Octs>>
     where Octs: Sized {
         self.iter()
     }
@@ -333,8 +480,22 @@ where
 
 //--- Display and Debug
 
-impl<Octs: Octets + ?Sized> fmt::Display for SvcParams<Octs> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl<Octs: AsRef<[u8]> + ?Sized> SvcParams<Octs> {
+    /// Returns a displayable value using additional parameters.
+    ///
+    /// The parameter sequence is displayed like via its `Display`
+    /// implementation, except that the registry `R` is given a chance to
+    /// format each parameter first. See [`SvcParamRegistry`] for details.
+    pub fn display_with<'a, R: SvcParamRegistry + 'a>(
+        &'a self
+    ) -> impl fmt::Display + 'a {
+        DisplayWith { params: self, marker: PhantomData::<R> }
+    }
+
+    /// Formats the parameter sequence using additional parameters.
+    fn fmt_with<R: SvcParamRegistry>(
+        &self, f: &mut fmt::Formatter
+    ) -> fmt::Result {
         let mut parser = Parser::from_ref(self.as_slice());
         let mut first = true;
         while parser.remaining() > 0 {
@@ -353,6 +514,20 @@ impl<Octs: Octets + ?Sized> fmt::Display for SvcParams<Octs> {
             else {
                 f.write_str(" ")?;
             }
+            if let Some(res) = R::fmt_param(
+                key, parser.peek_all(), f
+            ) {
+                res?;
+                continue
+            }
+            if key == SvcParamKey::MANDATORY {
+                if let Ok(value) = super::value::Mandatory::from_slice(
+                    parser.peek_all()
+                ) {
+                    value.fmt_with::<R>(f)?;
+                    continue
+                }
+            }
             write!(
                 f, "{}", super::value::AllValues::parse_any(key, &mut parser)
             )?;
@@ -361,7 +536,13 @@ impl<Octs: Octets + ?Sized> fmt::Display for SvcParams<Octs> {
     }
 }
 
-impl<Octs: Octets + ?Sized> fmt::Debug for SvcParams<Octs> {
+impl<Octs: AsRef<[u8]> + ?Sized> fmt::Display for SvcParams<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with::<()>(f)
+    }
+}
+
+impl<Octs: AsRef<[u8]> + ?Sized> fmt::Debug for SvcParams<Octs> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_tuple("SvcParams")
             .field(&format_args!("{}", self))
@@ -371,7 +552,7 @@ impl<Octs: Octets + ?Sized> fmt::Debug for SvcParams<Octs> {
 
 //--- ZonefileFmt
 
-impl<Octs: Octets + ?Sized> ZonefileFmt for SvcParams<Octs> {
+impl<Octs: AsRef<[u8]> + ?Sized> ZonefileFmt for SvcParams<Octs> {
     fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
         p.block(|p| {
             let mut parser = Parser::from_ref(self.as_slice());
@@ -393,6 +574,24 @@ impl<Octs: Octets + ?Sized> ZonefileFmt for SvcParams<Octs> {
     }
 }
 
+//------------ DisplayWith ---------------------------------------------------
+
+/// A parameter sequence displayed with additional parameters.
+struct DisplayWith<'a, Octs: ?Sized, R> {
+    params: &'a SvcParams<Octs>,
+    marker: PhantomData<R>,
+}
+
+impl<Octs, R> fmt::Display for DisplayWith<'_, Octs, R>
+where
+    Octs: AsRef<[u8]> + ?Sized,
+    R: SvcParamRegistry,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.params.fmt_with::<R>(f)
+    }
+}
+
 //------------ ValueIter -----------------------------------------------------
 
 /// An iterator over the values in a parameter sequence.
@@ -489,6 +688,107 @@ pub trait ComposeSvcParamValue: SvcParamValue {
     ) -> Result<(), Target::AppendError>;
 }
 
+//------------ SvcParamRegistry ----------------------------------------------
+
+/// Additional service binding parameters for the presentation format.
+///
+/// The presentation format of the parameters known to this crate is built
+/// into [`SvcParams::scan`] and the `Display` implementation of
+/// [`SvcParams`]. All other parameters have to be given with a key of the
+/// form `keyNNNNN` and their values are taken as is, with the exception of
+/// escape sequences.
+///
+/// A type implementing this trait can be used with
+/// [`SvcParams::scan_with`] and [`SvcParams::display_with`] to provide
+/// names and value formats for additional keys, for instance for keys from
+/// the private use range. The methods of the trait are consulted before
+/// the built-in conversions, so a registry can also change the handling
+/// of a known key. All methods have a default implementation that doesn’t
+/// handle any keys.
+///
+/// Access to the wire format of additional parameters works through the
+/// [`SvcParamValue`], [`ParseSvcParamValue`], and
+/// [`ComposeSvcParamValue`] traits.
+///
+/// The unit type `()` is a registry without any additional parameters.
+pub trait SvcParamRegistry {
+    /// Returns the key for a key name.
+    ///
+    /// The method should return `None` for all names it doesn’t know. Key
+    /// names are limited to 63 characters.
+    fn key_from_name(name: &[u8]) -> Option<SvcParamKey> {
+        let _ = name;
+        None
+    }
+
+    /// Converts a value from presentation format into wire format.
+    ///
+    /// The value is provided through `value`. It is `None` if the
+    /// parameter was given without a value. Escape sequences have already
+    /// been resolved.
+    ///
+    /// If the registry handles values of `key`, the method should append
+    /// the wire format of the value to `target` and return `Ok(true)`. It
+    /// should return an error if the value is invalid. Otherwise, it must
+    /// return `Ok(false)` without modifying `target`.
+    fn scan_value<Target, Error>(
+        key: SvcParamKey,
+        value: Option<&[u8]>,
+        target: &mut Target,
+    ) -> Result<bool, Error>
+    where
+        Target: OctetsBuilder + ?Sized,
+        Error: ScannerError,
+    {
+        let _ = (key, value, target);
+        Ok(false)
+    }
+
+    /// Writes the presentation format of a parameter.
+    ///
+    /// The parameter is given through its key and the wire format of its
+    /// value. If the registry handles the key, the method should write the
+    /// complete parameter, i.e., the key and, if appropriate, an equals
+    /// sign and the value, and return the result. Otherwise, it must return
+    /// `None` without writing anything.
+    fn fmt_param(
+        key: SvcParamKey,
+        value: &[u8],
+        f: &mut fmt::Formatter,
+    ) -> Option<fmt::Result> {
+        let _ = (key, value, f);
+        None
+    }
+
+    /// Writes the presentation format of a key.
+    ///
+    /// This is used for the keys listed in the value of the mandatory
+    /// parameter so that a key name accepted by
+    /// [`key_from_name`][Self::key_from_name] is also displayed. If the
+    /// registry has a name for `key`, the method should write it and return
+    /// the result. Otherwise, it must return `None` without writing
+    /// anything.
+    fn fmt_key(
+        key: SvcParamKey,
+        f: &mut fmt::Formatter,
+    ) -> Option<fmt::Result> {
+        let _ = (key, f);
+        None
+    }
+}
+
+impl SvcParamRegistry for () { }
+
+/// The maximum length of a key name in presentation format.
+const MAX_KEY_NAME_LEN: usize = 63;
+
+/// Returns the key for a key name using a registry.
+pub(super) fn key_from_name<R: SvcParamRegistry>(
+    name: &[u8]
+) -> Option<SvcParamKey> {
+    R::key_from_name(name).or_else(|| SvcParamKey::from_bytes(name))
+}
+
 //------------ UnknownSvcParam -----------------------------------------------
 
 /// A service binding parameter value in its raw form.
@@ -862,6 +1162,19 @@ impl<Octs> SvcParamsBuilder<Octs> {
         <Target as FromBuilder>::Builder: OctetsBuilder + EmptyBuilder
     {
         let mut target = <Target as FromBuilder>::Builder::empty();
+        self.freeze_into(&mut target)?;
+        Ok(unsafe {
+            SvcParams::from_octets_unchecked(
+                Target::from_builder(target)
+            )
+        })
+    }
+
+    /// Appends the sorted values to an octets builder.
+    fn freeze_into<Target: OctetsBuilder + ?Sized>(
+        &self, target: &mut Target
+    ) -> Result<(), Target::AppendError>
+    where Octs: AsRef<[u8]> {
         if !self.octets.as_ref().is_empty() {
             let mut parser = Parser::from_ref(self.octets.as_ref());
             loop {
@@ -874,14 +1187,10 @@ impl<Octs> SvcParamsBuilder<Octs> {
                 let pos = usize::try_from(pos).unwrap();
                 parser.seek(pos).unwrap();
                 let param = UnknownSvcParam::parse_param(&mut parser).unwrap();
-                param.compose_param(&mut target)?;
+                param.compose_param(target)?;
             }
         }
-        Ok(unsafe {
-            SvcParams::from_octets_unchecked(
-                Target::from_builder(target)
-            )
-        })
+        Ok(())
     }
 }
 
@@ -890,6 +1199,7 @@ impl<Octs> SvcParamsBuilder<Octs> {
 //------------ SvcParamsError -----------------------------------------------
 
 /// An octets sequence was not a valid service bindings parameter sequence.
+#[derive(Clone, Copy, Debug)]
 pub struct SvcParamsError(ParseError);
 
 impl From<ShortInput> for SvcParamsError {
@@ -1132,7 +1442,7 @@ mod test {
         alpn_builder.push("h3-19").unwrap();
         assert_eq!("alpn=h2,h3-19", format!("{}", alpn_builder.freeze()));
 
-        assert_eq!("nodefaultalpn", format!("{}", value::NoDefaultAlpn));
+        assert_eq!("no-default-alpn", format!("{}", value::NoDefaultAlpn));
 
         assert_eq!(
             "ech",
@@ -1152,6 +1462,117 @@ mod test {
         );
     }
 
+    //--- Registry
+
+    /// A registry adding a private use parameter with a decimal octet.
+    #[cfg(feature = "std")]
+    struct Flavor;
+
+    #[cfg(feature = "std")]
+    impl Flavor {
+        const KEY: SvcParamKey = SvcParamKey::from_int(
+            SvcParamKey::PRIVATE_RANGE_BEGIN
+        );
+    }
+
+    #[cfg(feature = "std")]
+    impl SvcParamRegistry for Flavor {
+        fn key_from_name(name: &[u8]) -> Option<SvcParamKey> {
+            (name == b"flavor").then_some(Self::KEY)
+        }
+
+        fn scan_value<Target, Error>(
+            key: SvcParamKey,
+            value: Option<&[u8]>,
+            target: &mut Target,
+        ) -> Result<bool, Error>
+        where
+            Target: OctetsBuilder + ?Sized,
+            Error: ScannerError,
+        {
+            if key != Self::KEY {
+                return Ok(false)
+            }
+            let value = value.and_then(|value| {
+                core::str::from_utf8(value).ok()?.parse::<u8>().ok()
+            }).ok_or_else(|| Error::custom("invalid flavor SvcParam"))?;
+            target.append_slice(&[value]).map_err(|_| Error::short_buf())?;
+            Ok(true)
+        }
+
+        fn fmt_param(
+            key: SvcParamKey,
+            value: &[u8],
+            f: &mut fmt::Formatter,
+        ) -> Option<fmt::Result> {
+            match (key, value) {
+                (Self::KEY, [value]) => Some(write!(f, "flavor={}", value)),
+                _ => None,
+            }
+        }
+
+        fn fmt_key(
+            key: SvcParamKey,
+            f: &mut fmt::Formatter,
+        ) -> Option<fmt::Result> {
+            (key == Self::KEY).then(|| f.write_str("flavor"))
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn scan_params<R: SvcParamRegistry>(
+        tokens: &[&str]
+    ) -> Result<SvcParams<std::vec::Vec<u8>>, crate::base::scan::StrError> {
+        use crate::base::scan::IterScanner;
+        use std::string::String;
+        use std::vec::Vec;
+
+        let mut scanner = IterScanner::<_, Vec<u8>>::new(
+            tokens.iter().map(|s| String::from(*s)).collect::<Vec<_>>()
+        );
+        SvcParams::scan_with::<R, _>(&mut scanner)
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn registry() {
+        let params = scan_params::<Flavor>(
+            &["flavor=12", "port=53", "mandatory=flavor"]
+        ).unwrap();
+        assert_eq!(
+            params.as_slice(),
+            b"\x00\x00\x00\x02\xff\x00\
+              \x00\x03\x00\x02\x00\x35\
+              \xff\x00\x00\x01\x0c"
+        );
+        assert_eq!(
+            format!("{}", params.display_with::<Flavor>()),
+            "mandatory=flavor port=53 flavor=12"
+        );
+        assert_eq!(
+            scan_params::<Flavor>(
+                &["mandatory=flavor", "port=53", "flavor=12"]
+            ).unwrap(),
+            params
+        );
+
+        // Without the registry, the parameter round-trips as an unknown
+        // parameter.
+        assert_eq!(
+            format!("{}", params),
+            "mandatory=key65280 port=53 key65280=\\012"
+        );
+        assert_eq!(
+            scan_params::<()>(
+                &["key65280=\\012", "port=53", "mandatory=key65280"]
+            ).unwrap(),
+            params
+        );
+
+        assert!(scan_params::<Flavor>(&["flavor=cherry"]).is_err());
+        assert!(scan_params::<()>(&["flavor=12"]).is_err());
+    }
+
     //--- Builder
 
     #[test]
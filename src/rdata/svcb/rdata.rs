@@ -2,13 +2,14 @@
 //!
 //! This is a private module. It’s public types are re-exported by the
 //! parent.
-use super::SvcParams;
+use super::{SvcParamRegistry, SvcParams};
 use crate::base::cmp::CanonicalOrd;
 use crate::base::iana::Rtype;
 use crate::base::name::{FlattenInto, ParsedName, ToName};
 use crate::base::rdata::{
     ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
 };
+use crate::base::scan::{Scan, Scanner, ScannerError};
 use crate::base::wire::{Compose, Composer, Parse, ParseError};
 use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
 use core::marker::PhantomData;
@@ -56,7 +57,7 @@ use octseq::parse::Parser;
 ///
 /// Note that the above is a wholy inadequate summary of service bindings
 /// records. For accurate details, see
-/// [draft-ietf-dnsop-svcb-https](https://datatracker.ietf.org/doc/html/draft-ietf-dnsop-svcb-https/).
+/// [RFC 9460](https://tools.ietf.org/html/rfc9460).
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(
@@ -157,6 +158,36 @@ impl<Variant, Octs, Name> SvcbRdata<Variant, Octs, Name> {
     }
 }
 
+impl<Variant, Octs: AsRef<[u8]>, Name: ToName> SvcbRdata<Variant, Octs, Name> {
+    /// Scans service bindings record data from its presentation format.
+    ///
+    /// The priority and target name are followed by the service
+    /// parameters in the format described with [`SvcParams::scan`].
+    pub fn scan<S: Scanner<Octets = Octs, Name = Name>>(
+        scanner: &mut S
+    ) -> Result<Self, S::Error> {
+        Self::scan_with::<(), S>(scanner)
+    }
+
+    /// Scans service bindings record data using additional parameters.
+    ///
+    /// This works like [`scan`][Self::scan] but uses
+    /// [`SvcParams::scan_with`] with the registry `R` for the service
+    /// parameters.
+    pub fn scan_with<R, S>(scanner: &mut S) -> Result<Self, S::Error>
+    where
+        R: SvcParamRegistry,
+        S: Scanner<Octets = Octs, Name = Name>,
+    {
+        let priority = u16::scan(scanner)?;
+        let target = scanner.scan_name()?;
+        let params = SvcParams::scan_with::<R, S>(scanner)?;
+        Self::new(priority, target, params).map_err(|_| {
+            S::Error::custom("long record data")
+        })
+    }
+}
+
 impl<Variant, Octs: AsRef<[u8]>> SvcbRdata<Variant, Octs, ParsedName<Octs>> {
     /// Parses service bindings record data from its wire format.
     pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized + 'a>(
@@ -441,17 +472,21 @@ where Self: RecordData, Octs: AsRef<[u8]>, Name: ToName {
 
 impl<Variant, Octs, Name> fmt::Display for SvcbRdata<Variant, Octs, Name>
 where
-    Octs: Octets,
-    Name: fmt::Display,
+    Octs: AsRef<[u8]>,
+    Name: ToName,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} {}. {}", self.priority, self.target, self.params)
+        write!(f, "{} {}", self.priority, self.target.fmt_with_dot())?;
+        if !self.params.is_empty() {
+            write!(f, " {}", self.params)?;
+        }
+        Ok(())
     }
 }
 
 impl<Variant, Octs, Name> fmt::Debug for SvcbRdata<Variant, Octs, Name>
 where
-    Octs: Octets,
+    Octs: AsRef<[u8]>,
     Name: fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -467,7 +502,7 @@ where
 
 impl<Variant, Octs, Name> ZonefileFmt for SvcbRdata<Variant, Octs, Name>
 where
-    Octs: Octets,
+    Octs: AsRef<[u8]>,
     Name: ToName,
 {
     fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
@@ -575,5 +610,106 @@ mod test {
         svcb_builder.compose_rdata(&mut buf).unwrap();
         assert_eq!(rdata.as_ref(), buf.as_ref());
     }
+
+    #[cfg(all(feature = "std", feature = "bytes"))]
+    #[test]
+    fn scan() {
+        use crate::base::rdata::test::test_scan;
+        use std::vec::Vec;
+
+        type SvcbVec = Svcb<Vec<u8>, Name<Vec<u8>>>;
+
+        fn svcb(priority: u16, target: &str, params: &[u8]) -> SvcbVec {
+            Svcb::new(
+                priority,
+                Name::from_str(target).unwrap(),
+                SvcParams::from_octets(params.into()).unwrap(),
+            ).unwrap()
+        }
+
+        test_scan(
+            &["0", "foo.example.com."],
+            Svcb::scan,
+            &svcb(0, "foo.example.com.", b""),
+        );
+        test_scan(
+            &[
+                "16", "foo.example.org.",
+                "alpn=h2,h3-19", "mandatory=ipv4hint,alpn",
+                "ipv4hint=192.0.2.1",
+            ],
+            Svcb::scan,
+            &svcb(
+                16, "foo.example.org.",
+                b"\x00\x00\x00\x04\x00\x01\x00\x04\
+                  \x00\x01\x00\x09\x02h2\x05h3-19\
+                  \x00\x04\x00\x04\xc0\x00\x02\x01"
+            ),
+        );
+        test_scan(
+            &["1", "foo.example.org.", "alpn=", r"f\\\\oo\\,bar,h2"],
+            Svcb::scan,
+            &svcb(
+                1, "foo.example.org.",
+                b"\x00\x01\x00\x0c\x08f\\oo,bar\x02h2"
+            ),
+        );
+        test_scan(
+            &[
+                "1", "example.com.", "key667=hello", "port=53",
+                "no-default-alpn", "alpn=h2"
+            ],
+            Svcb::scan,
+            &svcb(
+                1, "example.com.",
+                b"\x00\x01\x00\x03\x02h2\x00\x02\x00\x00\
+                  \x00\x03\x00\x02\x00\x35\
+                  \x02\x9b\x00\x05hello"
+            ),
+        );
+
+        fn scan_err(input: &[&str]) {
+            use crate::base::scan::IterScanner;
+            let mut scanner = IterScanner::<_, Vec<u8>>::new(
+                input.iter().map(|s| std::string::String::from(*s))
+                    .collect::<Vec<_>>()
+            );
+            assert!(SvcbVec::scan(&mut scanner).is_err());
+        }
+
+        scan_err(&["1", ".", "port=53", "port=54"]);
+        scan_err(&["1", ".", "mandatory=port"]);
+        scan_err(&["1", ".", "mandatory=mandatory"]);
+        scan_err(&["1", ".", "mandatory=port,port", "port=53"]);
+        scan_err(&["1", ".", "no-default-alpn"]);
+        scan_err(&["1", ".", "alpn"]);
+        scan_err(&["1", ".", "frobnicate=1"]);
+    }
+
+    #[cfg(all(feature = "std", feature = "bytes"))]
+    #[test]
+    fn alpn_display_scan() {
+        use crate::base::rdata::test::test_scan;
+        use std::string::ToString;
+        use std::vec::Vec;
+
+        let rdata = Svcb::new(
+            1,
+            Name::<Vec<u8>>::from_str("foo.example.org.").unwrap(),
+            SvcParams::from_octets(Vec::from(
+                b"\x00\x01\x00\x0f\x03a,b\x03x\"y\x03z\\\xe9\x02h2".as_ref()
+            )).unwrap(),
+        ).unwrap();
+        let text = rdata.to_string();
+        assert_eq!(
+            text,
+            r#"1 foo.example.org. alpn=a\\,b,x\"y,z\\\\\233,h2"#
+        );
+        test_scan(
+            &text.split(' ').collect::<Vec<_>>(),
+            Svcb::scan,
+            &rdata,
+        );
+    }
 }
 
@@ -1,9 +1,12 @@
 use super::{
     ComposeSvcParamValue, LongSvcParam, PushError, ParseSvcParamValue,
-    SvcParamsBuilder, SvcParams, SvcParamValue, UnknownSvcParam,
+    SvcParamsBuilder, SvcParams, SvcParamRegistry, SvcParamValue,
+    UnknownSvcParam,
 };
+use super::params::key_from_name;
 use crate::base::iana::SvcParamKey;
 use crate::base::net::{Ipv4Addr, Ipv6Addr};
+use crate::base::scan::{ConvertSymbols, ScannerError, Symbol};
 use crate::base::wire::{Compose, Parse, ParseError};
 use crate::utils::base64;
 use octseq::builder::{
@@ -13,7 +16,6 @@ use octseq::octets::{Octets, OctetsFrom};
 use octseq::parse::Parser;
 use octseq::str::Str;
 use core::{fmt, hash, mem, str};
-use core::fmt::Write as _;
 use core::str::FromStr;
 
 //============ AllValues =====================================================
@@ -493,11 +495,22 @@ impl<Octs: Octets + ?Sized> Iterator for MandatoryIter<'_, Octs> {
 
 impl<Octs: Octets + ?Sized> fmt::Display for Mandatory<Octs> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with::<()>(f)
+    }
+}
+
+impl<Octs: Octets + ?Sized> Mandatory<Octs> {
+    /// Formats the value using the key names of a registry.
+    pub(super) fn fmt_with<R: SvcParamRegistry>(
+        &self, f: &mut fmt::Formatter
+    ) -> fmt::Result {
         for (i, v) in self.iter().enumerate() {
-            if i == 0 {
-                write!(f, "mandatory={}", v)?;
-            } else {
-                write!(f, ",{}", v)?;
+            f.write_str(if i == 0 { "mandatory=" } else { "," })?;
+            if let Some(res) = R::fmt_key(v, f) {
+                res?;
+            }
+            else {
+                write!(f, "{}", v)?;
             }
         }
         Ok(())
@@ -631,8 +644,13 @@ impl<Octs: Octets + ?Sized> fmt::Display for Alpn<Octs> {
             } else {
                 f.write_str(",")?;
             }
-            for ch in v.as_ref() {
-                f.write_char(*ch as char)?;
+            // Commas and backslashes are escaped for the value list
+            // first and then everything for the zone file.
+            for &ch in v.as_ref() {
+                if ch == b',' || ch == b'\\' {
+                    Symbol::SimpleEscape(b'\\').fmt(f)?;
+                }
+                Symbol::from_octet(ch).fmt(f)?;
             }
         }
         Ok(())
@@ -805,7 +823,7 @@ impl ComposeSvcParamValue for NoDefaultAlpn {
 
 impl fmt::Display for NoDefaultAlpn {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str("nodefaultalpn")
+        f.write_str("no-default-alpn")
     }
 }
 
@@ -1443,6 +1461,197 @@ impl<Octs: OctetsBuilder + AsRef<[u8]> + AsMut<[u8]>> SvcParamsBuilder<Octs> {
     }
 }
 
+//============ Scanning ======================================================
+
+/// Converts a value from presentation format into wire format.
+///
+/// The `value` is the value part of a parameter in presentation format
+/// after all escape sequences of the zone file format have been resolved.
+/// It is `None` if the parameter was given without a value. The wire format
+/// of the value is appended to `target`.
+///
+/// The registry `R` is consulted first. Values for keys unknown to it and
+/// to us are taken as is.
+pub(super) fn scan_value<R, Target, Error>(
+    key: SvcParamKey,
+    value: Option<&[u8]>,
+    target: &mut Target,
+) -> Result<(), Error>
+where
+    R: SvcParamRegistry,
+    Target: OctetsBuilder + ?Sized,
+    Error: ScannerError,
+{
+    if R::scan_value(key, value, target)? {
+        return Ok(())
+    }
+    let short_buf = |_| Error::short_buf();
+    match key {
+        SvcParamKey::MANDATORY => {
+            scan_mandatory::<R, _, _>(required_value(value)?, target)
+        }
+        SvcParamKey::ALPN => {
+            scan_alpn(required_value(value)?, target)
+        }
+        SvcParamKey::NO_DEFAULT_ALPN => {
+            match value {
+                None | Some(b"") => Ok(()),
+                Some(_) => Err(Error::custom("unexpected SvcParam value")),
+            }
+        }
+        SvcParamKey::PORT => {
+            let port = u16::from_str(
+                value_str(required_value(value)?)?
+            ).map_err(|_| Error::custom("invalid port SvcParam"))?;
+            port.compose(target).map_err(short_buf)
+        }
+        SvcParamKey::IPV4HINT => {
+            for item in value_str(required_value(value)?)?.split(',') {
+                let addr = Ipv4Addr::from_str(item).map_err(|_| {
+                    Error::custom("invalid ipv4hint SvcParam")
+                })?;
+                target.append_slice(&addr.octets()).map_err(short_buf)?;
+            }
+            Ok(())
+        }
+        SvcParamKey::IPV6HINT => {
+            for item in value_str(required_value(value)?)?.split(',') {
+                let addr = Ipv6Addr::from_str(item).map_err(|_| {
+                    Error::custom("invalid ipv6hint SvcParam")
+                })?;
+                target.append_slice(&addr.octets()).map_err(short_buf)?;
+            }
+            Ok(())
+        }
+        SvcParamKey::ECH => {
+            let mut convert = base64::SymbolConverter::new();
+            for &ch in value.unwrap_or_default() {
+                let data = ConvertSymbols::<_, Error>::process_symbol(
+                    &mut convert, Symbol::Char(ch.into())
+                )?;
+                if let Some(data) = data {
+                    target.append_slice(data).map_err(short_buf)?;
+                }
+            }
+            ConvertSymbols::<Symbol, Error>::process_tail(&mut convert)?;
+            Ok(())
+        }
+        SvcParamKey::DOHPATH => {
+            let value = value.unwrap_or_default();
+            DohPath::check_slice(value).map_err(|_| {
+                Error::custom("invalid dohpath SvcParam")
+            })?;
+            target.append_slice(value).map_err(short_buf)
+        }
+        _ => {
+            target.append_slice(value.unwrap_or_default()).map_err(short_buf)
+        }
+    }
+}
+
+/// Converts the value of the mandatory parameter.
+///
+/// The keys can be given in any order but the wire format requires them
+/// to be sorted. Since we don’t have an allocator, we sort them by
+/// repeatedly selecting the smallest key not yet written.
+fn scan_mandatory<R, Target, Error>(
+    value: &[u8], target: &mut Target,
+) -> Result<(), Error>
+where
+    R: SvcParamRegistry,
+    Target: OctetsBuilder + ?Sized,
+    Error: ScannerError,
+{
+    let mut count = 0;
+    for item in value.split(|&ch| ch == b',') {
+        match key_from_name::<R>(item) {
+            Some(SvcParamKey::MANDATORY) => {
+                return Err(Error::custom("mandatory SvcParam lists itself"))
+            }
+            Some(_) => count += 1,
+            None => return Err(Error::custom("invalid mandatory SvcParam")),
+        }
+    }
+
+    let mut last = None;
+    for _ in 0..count {
+        let next = value.split(|&ch| ch == b',').filter_map(|item| {
+            key_from_name::<R>(item)
+        }).filter(|key| {
+            last.map(|last| *key > last).unwrap_or(true)
+        }).min();
+        match next {
+            Some(key) => {
+                key.compose(target).map_err(|_| Error::short_buf())?;
+                last = Some(key);
+            }
+            None => {
+                return Err(Error::custom("duplicate key in mandatory SvcParam"))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Converts the value of the alpn parameter.
+///
+/// The value is a comma separated list of protocol identifiers. A comma
+/// or backslash that is part of an identifier needs to be escaped with a
+/// backslash.
+fn scan_alpn<Target, Error>(
+    value: &[u8], target: &mut Target,
+) -> Result<(), Error>
+where
+    Target: OctetsBuilder + ?Sized,
+    Error: ScannerError,
+{
+    let mut buf = [0u8; 255];
+    let mut len = 0;
+    let mut chars = value.iter().copied();
+    loop {
+        let ch = chars.next();
+        if matches!(ch, None | Some(b',')) {
+            if len == 0 {
+                return Err(Error::custom("empty protocol in alpn SvcParam"))
+            }
+            (len as u8).compose(target).map_err(|_| Error::short_buf())?;
+            target.append_slice(&buf[..len]).map_err(|_| Error::short_buf())?;
+            len = 0;
+            if ch.is_none() {
+                return Ok(())
+            }
+            continue
+        }
+        let ch = match ch {
+            Some(b'\\') => chars.next().ok_or_else(|| {
+                Error::custom("invalid alpn SvcParam")
+            })?,
+            Some(ch) => ch,
+            None => unreachable!(),
+        };
+        if len == buf.len() {
+            return Err(Error::custom("long protocol in alpn SvcParam"))
+        }
+        buf[len] = ch;
+        len += 1;
+    }
+}
+
+/// Returns the value or an error if there is none.
+fn required_value<Error: ScannerError>(
+    value: Option<&[u8]>
+) -> Result<&[u8], Error> {
+    match value {
+        Some(value) if !value.is_empty() => Ok(value),
+        _ => Err(Error::custom("missing SvcParam value")),
+    }
+}
+
+/// Returns the value as a string.
+fn value_str<Error: ScannerError>(value: &[u8]) -> Result<&str, Error> {
+    str::from_utf8(value).map_err(|_| Error::custom("invalid SvcParam value"))
+}
+
 //============ BuildValueError ===============================================
 
 //------------ BuildValueError -----------------------------------------------
@@ -0,0 +1,630 @@
+//! Record data from [RFC 2930]: TKEY records.
+//!
+//! This RFC defines the TKEY record type used for establishing and deleting
+//! shared secret keys for TSIG.
+//!
+//! [RFC 2930]: https://tools.ietf.org/html/rfc2930
+
+use core::cmp::Ordering;
+use core::{fmt, hash};
+
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::{Rtype, TkeyMode, TsigRcode};
+use crate::base::name::{FlattenInto, ParsedName, ToName};
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use crate::rdata::dnssec::Timestamp;
+use crate::utils::base64;
+
+//------------ Tkey ----------------------------------------------------------
+
+/// TKEY record data.
+///
+/// TKEY records are exchanged in queries and responses to establish or
+/// delete a shared secret key for use with TSIG. The record data contains
+/// the name of the algorithm the key is to be used with, the validity
+/// period of the key, the [mode][TkeyMode] of key establishment, an
+/// error code, and the key data as well as additional data whose meaning
+/// depends on the mode.
+///
+/// TKEY records are meta records and never appear in zone files.
+///
+/// The TKEY record type is defined in [RFC 2930, section 2].
+///
+/// [RFC 2930, section 2]: https://tools.ietf.org/html/rfc2930#section-2
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tkey<Octs, Name> {
+    /// The algorithm of the key as a domain name.
+    algorithm: Name,
+
+    /// The start of the validity period of the key.
+    inception: Timestamp,
+
+    /// The end of the validity period of the key.
+    expiration: Timestamp,
+
+    /// The key establishment mode.
+    mode: TkeyMode,
+
+    /// The error code.
+    error: TsigRcode,
+
+    /// The key data.
+    ///
+    /// In wire format, consists of a unsigned 16 bit integer containing the
+    /// length followed by that many octets of actual key data.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "octseq::serde::SerializeOctets::serialize_octets",
+            deserialize_with = "octseq::serde::DeserializeOctets::deserialize_octets",
+            bound(
+                serialize = "Octs: octseq::serde::SerializeOctets",
+                deserialize = "Octs: octseq::serde::DeserializeOctets<'de>",
+            )
+        )
+    )]
+    key: Octs,
+
+    /// Other data.
+    ///
+    /// This is currently unused. In wire format, it is encoded as a
+    /// unsigned 16 bit integer followed by that many octets.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "octseq::serde::SerializeOctets::serialize_octets",
+            deserialize_with = "octseq::serde::DeserializeOctets::deserialize_octets",
+            bound(
+                serialize = "Octs: octseq::serde::SerializeOctets",
+                deserialize = "Octs: octseq::serde::DeserializeOctets<'de>",
+            )
+        )
+    )]
+    other: Octs,
+}
+
+impl Tkey<(), ()> {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::TKEY;
+}
+
+impl<O, N> Tkey<O, N> {
+    /// Creates new TKEY record data from its components.
+    ///
+    /// See the access methods for an explanation of these components. The
+    /// function will return an error if the wire format length of the
+    /// record would exceed 65,535 octets.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        algorithm: N,
+        inception: Timestamp,
+        expiration: Timestamp,
+        mode: TkeyMode,
+        error: TsigRcode,
+        key: O,
+        other: O,
+    ) -> Result<Self, LongRecordData>
+    where
+        O: AsRef<[u8]>,
+        N: ToName,
+    {
+        LongRecordData::check_len(
+            4 // inception
+            + 4 // expiration
+            + 2 // mode
+            + 2 // error
+            + 2 // key size
+            + 2 // other size
+            + usize::from(algorithm.compose_len())
+                .checked_add(key.as_ref().len())
+                .expect("long key")
+                .checked_add(other.as_ref().len())
+                .expect("long TKEY"),
+        )?;
+        Ok(unsafe {
+            Tkey::new_unchecked(
+                algorithm, inception, expiration, mode, error, key, other,
+            )
+        })
+    }
+
+    /// Creates new TKEY record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that the wire format length of the
+    /// created record will not exceed 65,535 octets.
+    #[allow(clippy::too_many_arguments)]
+    pub unsafe fn new_unchecked(
+        algorithm: N,
+        inception: Timestamp,
+        expiration: Timestamp,
+        mode: TkeyMode,
+        error: TsigRcode,
+        key: O,
+        other: O,
+    ) -> Self {
+        Tkey {
+            algorithm,
+            inception,
+            expiration,
+            mode,
+            error,
+            key,
+            other,
+        }
+    }
+
+    /// Returns a reference to the algorithm name.
+    ///
+    /// As with TSIG, the algorithm the key is to be used with is encoded
+    /// as a domain name.
+    pub fn algorithm(&self) -> &N {
+        &self.algorithm
+    }
+
+    /// Returns the start of the validity period of the key.
+    pub fn inception(&self) -> Timestamp {
+        self.inception
+    }
+
+    /// Returns the end of the validity period of the key.
+    pub fn expiration(&self) -> Timestamp {
+        self.expiration
+    }
+
+    /// Returns the key establishment mode.
+    pub fn mode(&self) -> TkeyMode {
+        self.mode
+    }
+
+    /// Returns the error code.
+    ///
+    /// In queries, this is always zero.
+    pub fn error(&self) -> TsigRcode {
+        self.error
+    }
+
+    /// Returns a reference to the key data.
+    ///
+    /// The content of the key data depends on the mode.
+    pub fn key(&self) -> &O {
+        &self.key
+    }
+
+    /// Returns an octet slice containing the key data.
+    pub fn key_slice(&self) -> &[u8]
+    where
+        O: AsRef<[u8]>,
+    {
+        self.key.as_ref()
+    }
+
+    /// Returns a reference to the other data.
+    pub fn other(&self) -> &O {
+        &self.other
+    }
+
+    pub(super) fn convert_octets<TOcts, TName>(
+        self,
+    ) -> Result<Tkey<TOcts, TName>, TOcts::Error>
+    where
+        TOcts: OctetsFrom<O>,
+        TName: OctetsFrom<N, Error = TOcts::Error>,
+    {
+        Ok(unsafe {
+            Tkey::new_unchecked(
+                self.algorithm.try_octets_into()?,
+                self.inception,
+                self.expiration,
+                self.mode,
+                self.error,
+                self.key.try_octets_into()?,
+                self.other.try_octets_into()?,
+            )
+        })
+    }
+
+    pub(super) fn flatten<TOcts, TName>(
+        self,
+    ) -> Result<Tkey<TOcts, TName>, TOcts::Error>
+    where
+        TOcts: OctetsFrom<O>,
+        N: FlattenInto<TName, AppendError = TOcts::Error>,
+    {
+        Ok(unsafe {
+            Tkey::new_unchecked(
+                self.algorithm.try_flatten_into()?,
+                self.inception,
+                self.expiration,
+                self.mode,
+                self.error,
+                self.key.try_octets_into()?,
+                self.other.try_octets_into()?,
+            )
+        })
+    }
+}
+
+impl<Octs> Tkey<Octs, ParsedName<Octs>> {
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized + 'a>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let algorithm = ParsedName::parse(parser)?;
+        let inception = Timestamp::parse(parser)?;
+        let expiration = Timestamp::parse(parser)?;
+        let mode = TkeyMode::parse(parser)?;
+        let error = TsigRcode::parse(parser)?;
+        let key_len = u16::parse(parser)?;
+        let key = parser.parse_octets(usize::from(key_len))?;
+        let other_len = u16::parse(parser)?;
+        let other = parser.parse_octets(usize::from(other_len))?;
+        Ok(unsafe {
+            Tkey::new_unchecked(
+                algorithm, inception, expiration, mode, error, key, other,
+            )
+        })
+    }
+}
+
+//--- OctetsFrom and FlattenInto
+
+impl<Octs, SrcOctets, Name, SrcName> OctetsFrom<Tkey<SrcOctets, SrcName>>
+    for Tkey<Octs, Name>
+where
+    Octs: OctetsFrom<SrcOctets>,
+    Name: OctetsFrom<SrcName>,
+    Octs::Error: From<Name::Error>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(
+        source: Tkey<SrcOctets, SrcName>,
+    ) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Tkey::new_unchecked(
+                Name::try_octets_from(source.algorithm)?,
+                source.inception,
+                source.expiration,
+                source.mode,
+                source.error,
+                Octs::try_octets_from(source.key)?,
+                Octs::try_octets_from(source.other)?,
+            )
+        })
+    }
+}
+
+impl<Octs, TOcts, Name, TName> FlattenInto<Tkey<TOcts, TName>>
+    for Tkey<Octs, Name>
+where
+    TOcts: OctetsFrom<Octs>,
+    Name: FlattenInto<TName, AppendError = TOcts::Error>,
+{
+    type AppendError = TOcts::Error;
+
+    fn try_flatten_into(
+        self,
+    ) -> Result<Tkey<TOcts, TName>, Self::AppendError> {
+        self.flatten()
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<O, OO, N, NN> PartialEq<Tkey<OO, NN>> for Tkey<O, N>
+where
+    O: AsRef<[u8]>,
+    OO: AsRef<[u8]>,
+    N: ToName,
+    NN: ToName,
+{
+    fn eq(&self, other: &Tkey<OO, NN>) -> bool {
+        self.algorithm.name_eq(&other.algorithm)
+            && self.inception == other.inception
+            && self.expiration == other.expiration
+            && self.mode == other.mode
+            && self.error == other.error
+            && self.key.as_ref().eq(other.key.as_ref())
+            && self.other.as_ref().eq(other.other.as_ref())
+    }
+}
+
+impl<O: AsRef<[u8]>, N: ToName> Eq for Tkey<O, N> {}
+
+//--- PartialOrd, Ord, and CanonicalOrd
+
+impl<O, OO, N, NN> PartialOrd<Tkey<OO, NN>> for Tkey<O, N>
+where
+    O: AsRef<[u8]>,
+    OO: AsRef<[u8]>,
+    N: ToName,
+    NN: ToName,
+{
+    fn partial_cmp(&self, other: &Tkey<OO, NN>) -> Option<Ordering> {
+        match self.algorithm.name_cmp(&other.algorithm) {
+            Ordering::Equal => {}
+            other => return Some(other),
+        }
+        match self.inception.into_int().cmp(&other.inception.into_int()) {
+            Ordering::Equal => {}
+            other => return Some(other),
+        }
+        match self.expiration.into_int().cmp(&other.expiration.into_int()) {
+            Ordering::Equal => {}
+            other => return Some(other),
+        }
+        match self.mode.partial_cmp(&other.mode) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        match self.error.partial_cmp(&other.error) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        match self.key.as_ref().partial_cmp(other.key.as_ref()) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        self.other.as_ref().partial_cmp(other.other.as_ref())
+    }
+}
+
+impl<O: AsRef<[u8]>, N: ToName> Ord for Tkey<O, N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.algorithm.name_cmp(&other.algorithm) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.inception.into_int().cmp(&other.inception.into_int()) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.expiration.into_int().cmp(&other.expiration.into_int()) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.mode.cmp(&other.mode) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.error.cmp(&other.error) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.key.as_ref().cmp(other.key.as_ref()) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.other.as_ref().cmp(other.other.as_ref())
+    }
+}
+
+impl<O, OO, N, NN> CanonicalOrd<Tkey<OO, NN>> for Tkey<O, N>
+where
+    O: AsRef<[u8]>,
+    OO: AsRef<[u8]>,
+    N: ToName,
+    NN: ToName,
+{
+    fn canonical_cmp(&self, other: &Tkey<OO, NN>) -> Ordering {
+        match self.algorithm.composed_cmp(&other.algorithm) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.inception.canonical_cmp(&other.inception) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.expiration.canonical_cmp(&other.expiration) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.mode.cmp(&other.mode) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.error.cmp(&other.error) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.key.as_ref().len().cmp(&other.key.as_ref().len()) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.key.as_ref().cmp(other.key.as_ref()) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.other.as_ref().len().cmp(&other.other.as_ref().len()) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.other.as_ref().cmp(other.other.as_ref())
+    }
+}
+
+//--- Hash
+
+impl<O: AsRef<[u8]>, N: hash::Hash> hash::Hash for Tkey<O, N> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.algorithm.hash(state);
+        self.inception.into_int().hash(state);
+        self.expiration.into_int().hash(state);
+        self.mode.hash(state);
+        self.error.hash(state);
+        self.key.as_ref().hash(state);
+        self.other.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<O, N> RecordData for Tkey<O, N> {
+    fn rtype(&self) -> Rtype {
+        Tkey::RTYPE
+    }
+}
+
+impl<'a, Octs: Octets + ?Sized> ParseRecordData<'a, Octs>
+    for Tkey<Octs::Range<'a>, ParsedName<Octs::Range<'a>>>
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Tkey::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>, Name: ToName> ComposeRecordData for Tkey<Octs, Name> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            4 // inception
+            + 4 // expiration
+            + 2 // mode
+            + 2 // error
+            + 2 // key size
+            + 2 // other size
+            + self
+                .algorithm
+                .compose_len()
+                .checked_add(
+                    u16::try_from(self.key.as_ref().len()).expect("long key"),
+                )
+                .expect("long key")
+                .checked_add(
+                    u16::try_from(self.other.as_ref().len())
+                        .expect("long TKEY"),
+                )
+                .expect("long TKEY"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.algorithm.compose(target)?;
+        self.inception.compose(target)?;
+        self.expiration.compose(target)?;
+        self.mode.compose(target)?;
+        self.error.compose(target)?;
+        u16::try_from(self.key.as_ref().len())
+            .expect("long key")
+            .compose(target)?;
+        target.append_slice(self.key.as_ref())?;
+        u16::try_from(self.other.as_ref().len())
+            .expect("long TKEY")
+            .compose(target)?;
+        target.append_slice(self.other.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display and Debug
+
+impl<O: AsRef<[u8]>, N: ToName> fmt::Display for Tkey<O, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} ",
+            self.algorithm.fmt_with_dot(),
+            self.inception,
+            self.expiration,
+            self.mode,
+            self.error
+        )?;
+        base64::display(&self.key, f)?;
+        f.write_str(" \"")?;
+        base64::display(&self.other, f)?;
+        f.write_str("\"")
+    }
+}
+
+impl<O: AsRef<[u8]>, N: fmt::Debug> fmt::Debug for Tkey<O, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Tkey")
+            .field("algorithm", &self.algorithm)
+            .field("inception", &self.inception)
+            .field("expiration", &self.expiration)
+            .field("mode", &self.mode)
+            .field("error", &self.error)
+            .field("key", &self.key.as_ref())
+            .field("other", &self.other.as_ref())
+            .finish()
+    }
+}
+
+//--- ZonefileFmt
+
+impl<O: AsRef<[u8]>, N: ToName> ZonefileFmt for Tkey<O, N> {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| {
+            p.write_token(self.algorithm.fmt_with_dot())?;
+            p.write_comment("algorithm")?;
+            p.write_show(self.inception)?;
+            p.write_comment("inception")?;
+            p.write_show(self.expiration)?;
+            p.write_comment("expiration")?;
+            p.write_show(self.mode)?;
+            p.write_token(self.error)?;
+            p.write_comment("error")?;
+            p.write_token(base64::encode_display(&self.key))?;
+            p.write_comment("key")?;
+            p.write_token(format_args!(
+                "\"{}\"",
+                base64::encode_display(&self.other)
+            ))
+        })
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::name::Name;
+    use crate::base::rdata::test::{test_compose_parse, test_rdlen};
+    use core::str::FromStr;
+    use std::vec::Vec;
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn tkey_compose_parse() {
+        let rdata = Tkey::new(
+            Name::<Vec<u8>>::from_str("gss-tsig.").unwrap(),
+            Timestamp::from(1_700_000_000),
+            Timestamp::from(1_700_086_400),
+            TkeyMode::GSS_API,
+            TsigRcode::NOERROR,
+            b"token".to_vec(),
+            Vec::new(),
+        )
+        .unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Tkey::parse(parser));
+        assert_eq!(rdata.mode(), TkeyMode::GSS_API);
+        assert_eq!(rdata.key_slice(), b"token");
+    }
+}
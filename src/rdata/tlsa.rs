@@ -0,0 +1,636 @@
+//! Record data from [RFC 6698]: TLSA records.
+//!
+//! This RFC defines the TLSA record type.
+//!
+//! [RFC 6698]: https://tools.ietf.org/html/rfc6698
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::{
+    Rtype, TlsaCertUsage, TlsaMatchingType, TlsaSelector,
+};
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scanner, ScannerError};
+use crate::base::wire::{Composer, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use crate::utils::base16;
+use core::cmp::Ordering;
+use core::{fmt, hash};
+#[cfg(feature = "serde")]
+use octseq::builder::{EmptyBuilder, FromBuilder, OctetsBuilder};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Tlsa ----------------------------------------------------------
+
+/// Tlsa record data.
+///
+/// Tlsa records associate a TLS server certificate or public key with the
+/// domain name where the record is found. The certificate usage describes
+/// how the association is to be verified, the selector determines which
+/// part of the certificate is matched, and the matching type specifies how
+/// that part is presented in the certificate association data.
+///
+/// The Tlsa record type is defined in [RFC 6698, section 2][1].
+///
+/// [1]: https://tools.ietf.org/html/rfc6698#section-2
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs: FromBuilder + octseq::serde::DeserializeOctets<'de>,
+            <Octs as FromBuilder>::Builder:
+                OctetsBuilder + EmptyBuilder,
+        ",
+    ))
+)]
+pub struct Tlsa<Octs> {
+    cert_usage: TlsaCertUsage,
+    selector: TlsaSelector,
+    matching_type: TlsaMatchingType,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base16::serde")
+    )]
+    data: Octs,
+}
+
+impl Tlsa<()> {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::TLSA;
+}
+
+impl<Octs> Tlsa<Octs> {
+    /// Creates new Tlsa record data from its components.
+    ///
+    /// Returns an error if the record data would be too long.
+    pub fn new(
+        cert_usage: TlsaCertUsage,
+        selector: TlsaSelector,
+        matching_type: TlsaMatchingType,
+        data: Octs,
+    ) -> Result<Self, LongRecordData>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        LongRecordData::check_len(
+            usize::from(
+                TlsaCertUsage::COMPOSE_LEN
+                    + TlsaSelector::COMPOSE_LEN
+                    + TlsaMatchingType::COMPOSE_LEN,
+            )
+            .checked_add(data.as_ref().len())
+            .expect("long association data"),
+        )?;
+        Ok(unsafe {
+            Tlsa::new_unchecked(cert_usage, selector, matching_type, data)
+        })
+    }
+
+    /// Creates new Tlsa record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that wire format representation of the
+    /// record data is at most 65,535 octets long.
+    pub unsafe fn new_unchecked(
+        cert_usage: TlsaCertUsage,
+        selector: TlsaSelector,
+        matching_type: TlsaMatchingType,
+        data: Octs,
+    ) -> Self {
+        Tlsa {
+            cert_usage,
+            selector,
+            matching_type,
+            data,
+        }
+    }
+
+    /// Returns how the association is to be verified.
+    pub fn cert_usage(&self) -> TlsaCertUsage {
+        self.cert_usage
+    }
+
+    /// Returns which part of the certificate is matched.
+    pub fn selector(&self) -> TlsaSelector {
+        self.selector
+    }
+
+    /// Returns how the selected part is presented in the association data.
+    pub fn matching_type(&self) -> TlsaMatchingType {
+        self.matching_type
+    }
+
+    /// Returns the certificate association data.
+    pub fn data(&self) -> &Octs {
+        &self.data
+    }
+
+    /// Converts the record data into the certificate association data.
+    pub fn into_data(self) -> Octs {
+        self.data
+    }
+
+    /// Returns whether the record data matches a certificate.
+    ///
+    /// The certificate is given in its DER encoding. Depending on the
+    /// selector, either the full certificate or its SubjectPublicKeyInfo is
+    /// compared with the certificate association data. Depending on the
+    /// matching type, this compares the selected data itself or its SHA-256
+    /// or SHA-512 digest.
+    ///
+    /// Returns `false` if the selector or matching type is unknown or if the
+    /// SubjectPublicKeyInfo can’t be found in the certificate. The
+    /// certificate usage isn’t considered. Checking it is up to the caller.
+    #[cfg(feature = "ring")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+    pub fn matches(&self, cert: &[u8]) -> bool
+    where
+        Octs: AsRef<[u8]>,
+    {
+        association_matches(
+            self.selector,
+            self.matching_type,
+            self.data.as_ref(),
+            cert,
+        )
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Tlsa<Target>, Target::Error> {
+        Ok(unsafe {
+            Tlsa::new_unchecked(
+                self.cert_usage,
+                self.selector,
+                self.matching_type,
+                self.data.try_octets_into()?,
+            )
+        })
+    }
+
+    pub(super) fn flatten<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Tlsa<Target>, Target::Error> {
+        self.convert_octets()
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let len = match parser.remaining().checked_sub(3) {
+            Some(len) => len,
+            None => return Err(ParseError::ShortInput),
+        };
+        Ok(unsafe {
+            Self::new_unchecked(
+                TlsaCertUsage::parse(parser)?,
+                TlsaSelector::parse(parser)?,
+                TlsaMatchingType::parse(parser)?,
+                parser.parse_octets(len)?,
+            )
+        })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Self::new(
+            TlsaCertUsage::scan(scanner)?,
+            TlsaSelector::scan(scanner)?,
+            TlsaMatchingType::scan(scanner)?,
+            scanner.convert_entry(base16::SymbolConverter::new())?,
+        )
+        .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Tlsa<SrcOcts>> for Tlsa<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Tlsa<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Tlsa::new_unchecked(
+                source.cert_usage,
+                source.selector,
+                source.matching_type,
+                Octs::try_octets_from(source.data)?,
+            )
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Tlsa<Other>> for Tlsa<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Tlsa<Other>) -> bool {
+        self.cert_usage == other.cert_usage
+            && self.selector == other.selector
+            && self.matching_type == other.matching_type
+            && self.data.as_ref().eq(other.data.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Tlsa<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Tlsa<Other>> for Tlsa<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Tlsa<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Tlsa<Other>> for Tlsa<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Tlsa<Other>) -> Ordering {
+        match self.cert_usage.cmp(&other.cert_usage) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.selector.cmp(&other.selector) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.matching_type.cmp(&other.matching_type) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.data.as_ref().cmp(other.data.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Tlsa<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Tlsa<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.cert_usage.hash(state);
+        self.selector.hash(state);
+        self.matching_type.hash(state);
+        self.data.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Tlsa<Octs> {
+    fn rtype(&self) -> Rtype {
+        Tlsa::RTYPE
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Tlsa<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Tlsa::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Tlsa<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            u16::checked_add(
+                TlsaCertUsage::COMPOSE_LEN
+                    + TlsaSelector::COMPOSE_LEN
+                    + TlsaMatchingType::COMPOSE_LEN,
+                self.data
+                    .as_ref()
+                    .len()
+                    .try_into()
+                    .expect("long association data"),
+            )
+            .expect("long association data"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.cert_usage.compose(target)?;
+        self.selector.compose(target)?;
+        self.matching_type.compose(target)?;
+        target.append_slice(self.data.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Tlsa<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} ",
+            self.cert_usage, self.selector, self.matching_type
+        )?;
+        for ch in self.data.as_ref() {
+            write!(f, "{:02x}", ch)?
+        }
+        Ok(())
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Tlsa<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Tlsa")
+            .field("cert_usage", &self.cert_usage)
+            .field("selector", &self.selector)
+            .field("matching_type", &self.matching_type)
+            .field("data", &self.data.as_ref())
+            .finish()
+    }
+}
+
+//--- ZonefileFmt
+
+impl<Octs: AsRef<[u8]>> ZonefileFmt for Tlsa<Octs> {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| {
+            p.write_show(self.cert_usage)?;
+            p.write_show(self.selector)?;
+            p.write_show(self.matching_type)?;
+            p.write_token(base16::encode_display(&self.data))
+        })
+    }
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Returns whether certificate association data matches a certificate.
+///
+/// This is shared with the Smimea record type which uses the same fields.
+#[cfg(feature = "ring")]
+pub(super) fn association_matches(
+    selector: TlsaSelector,
+    matching_type: TlsaMatchingType,
+    data: &[u8],
+    cert: &[u8],
+) -> bool {
+    use ring::digest;
+
+    let selected = match selector {
+        TlsaSelector::CERT => cert,
+        TlsaSelector::SPKI => match subject_public_key_info(cert) {
+            Some(spki) => spki,
+            None => return false,
+        },
+        _ => return false,
+    };
+    match matching_type {
+        TlsaMatchingType::FULL => selected == data,
+        TlsaMatchingType::SHA2_256 => {
+            digest::digest(&digest::SHA256, selected).as_ref() == data
+        }
+        TlsaMatchingType::SHA2_512 => {
+            digest::digest(&digest::SHA512, selected).as_ref() == data
+        }
+        _ => false,
+    }
+}
+
+/// Returns the DER encoded SubjectPublicKeyInfo of a certificate.
+///
+/// Following [RFC 5280, section 4.1], the SubjectPublicKeyInfo is the
+/// element of the TBSCertificate sequence after the optional version, the
+/// serial number, the signature algorithm, the issuer, the validity, and
+/// the subject.
+///
+/// [RFC 5280, section 4.1]: https://tools.ietf.org/html/rfc5280#section-4.1
+#[cfg(feature = "ring")]
+fn subject_public_key_info(cert: &[u8]) -> Option<&[u8]> {
+    const SEQUENCE: u8 = 0x30;
+    const VERSION: u8 = 0xA0;
+
+    let (tag, cert, _) = der_element(cert)?;
+    if tag != SEQUENCE {
+        return None;
+    }
+    let (tag, tbs, _) = der_element(cert)?;
+    if tag != SEQUENCE {
+        return None;
+    }
+    let mut tbs = tbs;
+    if tbs.first() == Some(&VERSION) {
+        tbs = der_element(tbs)?.2;
+    }
+    for _ in 0..5 {
+        tbs = der_element(tbs)?.2;
+    }
+    let (tag, _, rest) = der_element(tbs)?;
+    if tag != SEQUENCE {
+        return None;
+    }
+    // The result includes the tag and length octets of the element.
+    Some(&tbs[..tbs.len() - rest.len()])
+}
+
+/// Splits off the first DER element of `data`.
+///
+/// Returns the tag, the content, and the remaining data. Only tags that
+/// fit into a single octet are supported.
+#[cfg(feature = "ring")]
+fn der_element(data: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, data) = data.split_first()?;
+    if tag & 0x1F == 0x1F {
+        return None;
+    }
+    let (&first, mut data) = data.split_first()?;
+    let len = if first < 0x80 {
+        usize::from(first)
+    } else {
+        let count = usize::from(first & 0x7F);
+        if count == 0 || count > core::mem::size_of::<usize>() {
+            return None;
+        }
+        if data.len() < count {
+            return None;
+        }
+        let (octets, rest) = data.split_at(count);
+        data = rest;
+        octets
+            .iter()
+            .fold(0, |len, &octet| (len << 8) | usize::from(octet))
+    };
+    if data.len() < len {
+        return None;
+    }
+    let (content, rest) = data.split_at(len);
+    Some((tag, content, rest))
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+pub(crate) mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use crate::utils::base16::decode;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    // RFC 6698, section 2.3.
+    const DIGEST: &str =
+        "d2abde240d7cd3ee6b4b28c54df034b97983a1d16e8a410e4561cb106618e971";
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn tlsa_compose_parse_scan() {
+        let rdata = Tlsa::new(
+            TlsaCertUsage::PKIX_TA,
+            TlsaSelector::CERT,
+            TlsaMatchingType::SHA2_256,
+            decode::<Vec<u8>>(DIGEST).unwrap(),
+        )
+        .unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Tlsa::parse(parser));
+        test_scan(&["0", "0", "1", DIGEST], Tlsa::scan, &rdata);
+        test_scan(
+            &["0", "0", "1", &DIGEST[..32], &DIGEST[32..]],
+            Tlsa::scan,
+            &rdata,
+        );
+        assert_eq!(rdata.to_string(), format!("0 0 1 {}", DIGEST));
+    }
+
+    // A self-signed Ed25519 certificate for example.com.
+    #[cfg(feature = "ring")]
+    pub(crate) const CERT: &str = "\
+        308201403081f3a00302010202147e92fdc4375757904fde1705c9385db98ec1\
+        3b8d300506032b657030163114301206035504030c0b6578616d706c652e636f\
+        6d301e170d3236313031343139333832305a170d333631303131313933383230\
+        5a30163114301206035504030c0b6578616d706c652e636f6d302a300506032b\
+        6570032100a4cb18049147d1f9573094b194b186da6adc203f097cb6ccccf0e8\
+        a19c33325ca3533051301d0603551d0e0416041464ffc8ebf0ba953c1f4eacb5\
+        7f6e9c57fdda0153301f0603551d2304183016801464ffc8ebf0ba953c1f4eac\
+        b57f6e9c57fdda0153300f0603551d130101ff040530030101ff300506032b65\
+        700341007fd358486a9df23659ffeca1144380efd2f79c88b3a5b65a197672d0\
+        00ed585379d53b0ac0b6c371fe123ddc13e98cc6163d2ce0fdd9f0acf35d0d8d\
+        f262360d";
+
+    // The certificate’s SubjectPublicKeyInfo.
+    #[cfg(feature = "ring")]
+    pub(crate) const SPKI: &str = "\
+        302a300506032b6570032100a4cb18049147d1f9573094b194b186da6adc203f\
+        097cb6ccccf0e8a19c33325c";
+
+    #[test]
+    #[cfg(feature = "ring")]
+    fn tlsa_matches() {
+        fn check(
+            selector: TlsaSelector,
+            matching_type: TlsaMatchingType,
+            data: &str,
+        ) -> bool {
+            Tlsa::new(
+                TlsaCertUsage::DANE_EE,
+                selector,
+                matching_type,
+                decode::<Vec<u8>>(data).unwrap(),
+            )
+            .unwrap()
+            .matches(&decode::<Vec<u8>>(CERT).unwrap())
+        }
+
+        assert_eq!(
+            subject_public_key_info(&decode::<Vec<u8>>(CERT).unwrap()),
+            Some(decode::<Vec<u8>>(SPKI).unwrap().as_slice())
+        );
+
+        assert!(check(TlsaSelector::CERT, TlsaMatchingType::FULL, CERT));
+        assert!(check(TlsaSelector::SPKI, TlsaMatchingType::FULL, SPKI));
+        assert!(check(
+            TlsaSelector::CERT,
+            TlsaMatchingType::SHA2_256,
+            "14ace03c98686abf1cf0afffa7a2e6d9f1d31a3ea3c7c276f225591f66ff51f6"
+        ));
+        assert!(check(
+            TlsaSelector::SPKI,
+            TlsaMatchingType::SHA2_256,
+            "24dac56b99cfed6bb27cb8b05b71699afba1d81fb5f4b2402a17d3cb7f49d6b2"
+        ));
+        assert!(check(
+            TlsaSelector::SPKI,
+            TlsaMatchingType::SHA2_512,
+            "9fc9a25fbdfdfed8d3974a9b4ae0caed0ceb2fbbe410f385e45ab0f3b4cd299f\
+             27c59db18c0000a2f6805ffb999f9013fb3cc2157ed59b03292cc546ea4209b2"
+        ));
+
+        // The digest of the certificate doesn’t match the public key.
+        assert!(!check(
+            TlsaSelector::SPKI,
+            TlsaMatchingType::SHA2_256,
+            "14ace03c98686abf1cf0afffa7a2e6d9f1d31a3ea3c7c276f225591f66ff51f6"
+        ));
+        assert!(!check(TlsaSelector::CERT, TlsaMatchingType::FULL, SPKI));
+        assert!(!check(
+            TlsaSelector::from_int(2),
+            TlsaMatchingType::FULL,
+            CERT
+        ));
+        assert!(!check(
+            TlsaSelector::CERT,
+            TlsaMatchingType::from_int(3),
+            CERT
+        ));
+
+        // Truncated certificates don’t have a SubjectPublicKeyInfo.
+        let cert = decode::<Vec<u8>>(CERT).unwrap();
+        for len in 0..cert.len() {
+            assert_eq!(subject_public_key_info(&cert[..len]), None);
+        }
+    }
+}
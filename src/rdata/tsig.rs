@@ -578,12 +578,14 @@ impl<Octs: AsRef<[u8]>, Name: ToName> ComposeRecordData
 
 //--- Display and Debug
 
-impl<O: AsRef<[u8]>, N: fmt::Display> fmt::Display for Tsig<O, N> {
+impl<O: AsRef<[u8]>, N: ToName> fmt::Display for Tsig<O, N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "{}. {} {} ",
-            self.algorithm, self.time_signed, self.fudge
+            "{} {} {} ",
+            self.algorithm.fmt_with_dot(),
+            self.time_signed,
+            self.fudge
         )?;
         base64::display(&self.mac, f)?;
         write!(f, " {} {} \"", self.original_id, self.error)?;
@@ -0,0 +1,456 @@
+//! Record data from [RFC 7553]: URI records.
+//!
+//! This RFC defines the URI record type.
+//!
+//! [RFC 7553]: https://tools.ietf.org/html/rfc7553
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scan, Scanner, ScannerError, Symbol};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use crate::base::zonefile_fmt::{self, Formatter, ZonefileFmt};
+use core::cmp::Ordering;
+use core::{fmt, hash};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Uri -----------------------------------------------------------
+
+/// Uri record data.
+///
+/// Uri records map a domain name to a URI. Similarly to [Srv][super::Srv]
+/// records, there can be multiple records for a name with the priority and
+/// weight determining the order in which they should be tried.
+///
+/// The target URI is not a domain name but the remainder of the record
+/// data. Unlike a character string, it has no length octet and can be
+/// longer than 255 octets. It must not be empty. In presentation format, it
+/// is given as a quoted string.
+///
+/// The Uri record type is defined in [RFC 7553, section 4][1].
+///
+/// [1]: https://tools.ietf.org/html/rfc7553#section-4
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Uri<Octs> {
+    priority: u16,
+    weight: u16,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "octseq::serde::SerializeOctets::serialize_octets",
+            deserialize_with = "octseq::serde::DeserializeOctets::deserialize_octets",
+            bound(
+                serialize = "Octs: octseq::serde::SerializeOctets",
+                deserialize = "Octs: octseq::serde::DeserializeOctets<'de>",
+            )
+        )
+    )]
+    target: Octs,
+}
+
+impl Uri<()> {
+    /// The rtype of this record data type.
+    pub(crate) const RTYPE: Rtype = Rtype::URI;
+}
+
+impl<Octs> Uri<Octs> {
+    /// Creates new Uri record data from its components.
+    ///
+    /// Returns an error if the target is empty or if the record data would
+    /// be too long.
+    pub fn new(
+        priority: u16,
+        weight: u16,
+        target: Octs,
+    ) -> Result<Self, UriError>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        if target.as_ref().is_empty() {
+            return Err(UriError::EmptyTarget);
+        }
+        LongRecordData::check_len(
+            usize::from(u16::COMPOSE_LEN + u16::COMPOSE_LEN)
+                .checked_add(target.as_ref().len())
+                .expect("long target"),
+        )?;
+        Ok(unsafe { Self::new_unchecked(priority, weight, target) })
+    }
+
+    /// Creates new Uri record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that the target is not empty and that
+    /// the record data is not longer than 65,535 octets.
+    pub unsafe fn new_unchecked(
+        priority: u16,
+        weight: u16,
+        target: Octs,
+    ) -> Self {
+        Uri {
+            priority,
+            weight,
+            target,
+        }
+    }
+
+    /// Returns the priority of the target.
+    ///
+    /// Clients should try the targets with the lowest priority first.
+    pub fn priority(&self) -> u16 {
+        self.priority
+    }
+
+    /// Returns the weight of the target.
+    ///
+    /// Targets with the same priority should be chosen with a probability
+    /// proportional to their weight.
+    pub fn weight(&self) -> u16 {
+        self.weight
+    }
+
+    /// Returns the target URI.
+    pub fn target(&self) -> &Octs {
+        &self.target
+    }
+
+    /// Converts the record data into the target URI.
+    pub fn into_target(self) -> Octs {
+        self.target
+    }
+
+    pub(in crate::rdata) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Uri<Target>, Target::Error> {
+        Ok(Uri {
+            priority: self.priority,
+            weight: self.weight,
+            target: self.target.try_octets_into()?,
+        })
+    }
+
+    pub(in crate::rdata) fn flatten<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Uri<Target>, Target::Error> {
+        self.convert_octets()
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let priority = u16::parse(parser)?;
+        let weight = u16::parse(parser)?;
+        if parser.remaining() == 0 {
+            return Err(ParseError::form_error("empty URI target"));
+        }
+        let target = parser.parse_octets(parser.remaining())?;
+        Ok(Uri {
+            priority,
+            weight,
+            target,
+        })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        let priority = u16::scan(scanner)?;
+        let weight = u16::scan(scanner)?;
+        let target = scanner.scan_octets()?;
+        Self::new(priority, weight, target)
+            .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Uri<SrcOcts>> for Uri<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Uri<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(Uri {
+            priority: source.priority,
+            weight: source.weight,
+            target: Octs::try_octets_from(source.target)?,
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Uri<Other>> for Uri<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Uri<Other>) -> bool {
+        self.priority == other.priority
+            && self.weight == other.weight
+            && self.target.as_ref() == other.target.as_ref()
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Uri<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Uri<Other>> for Uri<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Uri<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Uri<Other>> for Uri<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Uri<Other>) -> Ordering {
+        match self.priority.cmp(&other.priority) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.weight.cmp(&other.weight) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.target.as_ref().cmp(other.target.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Uri<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Uri<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.priority.hash(state);
+        self.weight.hash(state);
+        self.target.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Uri<Octs> {
+    fn rtype(&self) -> Rtype {
+        Uri::RTYPE
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Uri<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Uri::RTYPE {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Uri<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            u16::try_from(self.target.as_ref().len())
+                .expect("long URI rdata")
+                .checked_add(u16::COMPOSE_LEN + u16::COMPOSE_LEN)
+                .expect("long URI rdata"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.priority.compose(target)?;
+        self.weight.compose(target)?;
+        target.append_slice(self.target.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Uri<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.priority,
+            self.weight,
+            DisplayTarget(self.target.as_ref())
+        )
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Uri<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Uri")
+            .field("priority", &self.priority)
+            .field("weight", &self.weight)
+            .field("target", &DisplayTarget(self.target.as_ref()))
+            .finish()
+    }
+}
+
+//--- ZonefileFmt
+
+impl<Octs: AsRef<[u8]>> ZonefileFmt for Uri<Octs> {
+    fn fmt(&self, p: &mut impl Formatter) -> zonefile_fmt::Result {
+        p.block(|p| {
+            p.write_token(self.priority)?;
+            p.write_comment("priority")?;
+            p.write_token(self.weight)?;
+            p.write_comment("weight")?;
+            p.write_token(DisplayTarget(self.target.as_ref()))
+        })
+    }
+}
+
+//------------ DisplayTarget -------------------------------------------------
+
+/// Helper struct for displaying the target as a quoted string.
+struct DisplayTarget<'a>(&'a [u8]);
+
+impl fmt::Display for DisplayTarget<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("\"")?;
+        for &ch in self.0 {
+            fmt::Display::fmt(&Symbol::quoted_from_octet(ch), f)?;
+        }
+        f.write_str("\"")
+    }
+}
+
+impl fmt::Debug for DisplayTarget<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+//============ Error Types ===================================================
+
+//------------ UriError ------------------------------------------------------
+
+/// Uri record data could not be created from the given components.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UriError {
+    /// The target was empty.
+    EmptyTarget,
+
+    /// The record data would have been too long.
+    LongRecordData,
+}
+
+impl UriError {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UriError::EmptyTarget => "empty URI target",
+            UriError::LongRecordData => "record data too long",
+        }
+    }
+}
+
+impl From<LongRecordData> for UriError {
+    fn from(_: LongRecordData) -> Self {
+        UriError::LongRecordData
+    }
+}
+
+impl fmt::Display for UriError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UriError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn uri_compose_parse_scan() {
+        // RFC 7553, section 4.
+        let rdata =
+            Uri::new(10, 1, b"ftp://ftp1.example.com/public".to_vec())
+                .unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Uri::parse(parser));
+        test_scan(
+            &["10", "1", "ftp://ftp1.example.com/public"],
+            Uri::scan,
+            &rdata,
+        );
+        assert_eq!(
+            rdata.to_string(),
+            "10 1 \"ftp://ftp1.example.com/public\""
+        );
+    }
+
+    #[test]
+    #[allow(clippy::redundant_closure)] // lifetimes ...
+    fn uri_long_target() {
+        // The target is not a character string and may exceed 255 octets.
+        let target = [b'a'; 300].to_vec();
+        let rdata = Uri::new(1, 1, target).unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Uri::parse(parser));
+    }
+
+    #[test]
+    fn uri_empty_target() {
+        assert_eq!(
+            Uri::new(1, 1, Vec::<u8>::new()).unwrap_err(),
+            UriError::EmptyTarget
+        );
+        assert!(Uri::parse(&mut Parser::from_ref(
+            b"\x00\x01\x00\x01".as_ref()
+        ))
+        .is_err());
+    }
+}
@@ -0,0 +1,638 @@
+//! Answering queries from a hosts table.
+//!
+//! Most systems keep a static table of host names and their addresses in
+//! the file `/etc/hosts`. The system’s resolver consults this table before
+//! sending queries to the DNS. This module provides [`Hosts`], a parser
+//! for and representation of such a table, and [`HostsResolver`], a
+//! resolver that answers address queries and reverse queries from the
+//! system’s table and an in-memory table of overrides and passes all other
+//! queries on to an upstream resolver.
+//!
+//! Since the answers of a [`HostsResolver`] are ordinary DNS messages, the
+//! lookups of the [lookup] module work with it just like with any other
+//! resolver. When wrapped around a [`StubResolver`], host and address
+//! lookups behave like the system’s `getaddrinfo` with the common
+//! `hosts: files dns` configuration:
+//!
+//! ```no_run
+//! use domain::base::Name;
+//! use domain::resolv::lookup::lookup_host;
+//! use domain::resolv::{HostsResolver, StubResolver};
+//! use std::str::FromStr;
+//!
+//! # async fn run() -> Result<(), std::io::Error> {
+//! let stub = StubResolver::new();
+//! let mut resolver = HostsResolver::new(&stub);
+//! resolver.overrides_mut().insert(
+//!     "192.0.2.1".parse().unwrap(),
+//!     Name::from_str("test.example").unwrap(),
+//!     [],
+//! );
+//!
+//! let name = Name::<Vec<u8>>::from_str("test.example").unwrap();
+//! let found = lookup_host(&resolver, &name).await?;
+//! assert_eq!(
+//!     found.iter().collect::<Vec<_>>(),
+//!     ["192.0.2.1".parse::<std::net::IpAddr>().unwrap()]
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [lookup]: super::lookup
+//! [`StubResolver`]: super::StubResolver
+
+use crate::base::iana::{Class, Rtype};
+use crate::base::message::Message;
+use crate::base::message_builder::{AnswerBuilder, MessageBuilder};
+use crate::base::name::{Name, ToName};
+use crate::base::question::Question;
+use crate::rdata::{Aaaa, Cname, Ptr, A};
+use crate::resolv::resolver::{Resolver, SearchNames};
+use bytes::{Bytes, BytesMut};
+use std::collections::HashMap;
+use std::future::Future;
+use std::io::{self, BufRead, Read};
+use std::net::IpAddr;
+use std::path::Path;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::task::{Context, Poll};
+use std::vec::Vec;
+use std::{fs, slice};
+
+//------------ HostName ------------------------------------------------------
+
+/// The type of the host names kept in a hosts table.
+pub type HostName = Name<Bytes>;
+
+//------------ Hosts ---------------------------------------------------------
+
+/// A table of host names and their addresses.
+///
+/// The table is modeled after the `/etc/hosts` file: it maps host names to
+/// a set of addresses and a canonical name. Each address is given together
+/// with a canonical name and any number of aliases. If the same host name
+/// appears with more than one address, all of them are kept and the
+/// canonical name is the one given with the first address.
+///
+/// You can get the system’s table via [`Hosts::system`], parse your own
+/// through [`parse`][Self::parse] or [`parse_file`][Self::parse_file],
+/// or add entries with [`insert`][Self::insert].
+#[derive(Clone, Debug, Default)]
+pub struct Hosts {
+    /// The entry for each host name, including aliases.
+    names: HashMap<HostName, HostEntry>,
+
+    /// The canonical host names for the reverse name of each address.
+    reverse: HashMap<HostName, Vec<HostName>>,
+}
+
+/// # Management
+///
+impl Hosts {
+    /// Creates a new, empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the system’s hosts table.
+    ///
+    /// This parses `/etc/hosts` and returns an empty table if that fails.
+    ///
+    /// XXX This currently only works for Unix-y systems.
+    pub fn system() -> Self {
+        Self::from_file("/etc/hosts").unwrap_or_default()
+    }
+
+    /// Creates a table from the content of a file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, io::Error> {
+        let mut res = Self::new();
+        res.parse_file(path)?;
+        Ok(res)
+    }
+
+    /// Adds an address for a host.
+    ///
+    /// The address is added to the entries of `canonical` and all of
+    /// `aliases`. Names that don’t have an entry yet will receive
+    /// `canonical` as their canonical name.
+    pub fn insert(
+        &mut self,
+        addr: IpAddr,
+        canonical: HostName,
+        aliases: impl IntoIterator<Item = HostName>,
+    ) {
+        for name in aliases.into_iter().chain(Some(canonical.clone())) {
+            let entry = self
+                .names
+                .entry(name)
+                .or_insert_with(|| HostEntry::new(canonical.clone()));
+            if !entry.addrs.contains(&addr) {
+                entry.addrs.push(addr);
+            }
+        }
+        let names = self.reverse.entry(reverse_name(addr)).or_default();
+        if !names.contains(&canonical) {
+            names.push(canonical);
+        }
+    }
+
+    /// Removes all entries from the table.
+    pub fn clear(&mut self) {
+        self.names.clear();
+        self.reverse.clear();
+    }
+
+    /// Returns whether the table is empty.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Returns the entry for a host name.
+    pub fn lookup_name(&self, name: &impl ToName) -> Option<&HostEntry> {
+        self.names.get(&name.to_name::<Bytes>())
+    }
+
+    /// Returns the canonical host names of an address.
+    pub fn lookup_addr(&self, addr: IpAddr) -> &[HostName] {
+        self.reverse
+            .get(&reverse_name(addr))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// # Parsing Hosts Files
+///
+impl Hosts {
+    /// Parses entries from a file.
+    pub fn parse_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<(), io::Error> {
+        let mut file = fs::File::open(path)?;
+        self.parse(&mut file)
+    }
+
+    /// Parses entries from a reader.
+    ///
+    /// The format is that of the `/etc/hosts` file. Each line contains an
+    /// address followed by the canonical host name and optionally a number
+    /// of aliases, all separated by white space. Everything following a
+    /// `#` is a comment.
+    ///
+    /// Like glibc, the method ignores lines that cannot be parsed.
+    pub fn parse<R: Read>(
+        &mut self,
+        reader: &mut R,
+    ) -> Result<(), io::Error> {
+        for line in io::BufReader::new(reader).lines() {
+            let line = line?;
+            let line = match line.split_once('#') {
+                Some((line, _)) => line,
+                None => line.as_str(),
+            };
+
+            let mut words = line.split_whitespace();
+            let addr = match words.next().map(IpAddr::from_str) {
+                Some(Ok(addr)) => addr,
+                _ => continue,
+            };
+            let names = match words
+                .map(HostName::from_str)
+                .collect::<Result<Vec<_>, _>>()
+            {
+                Ok(names) => names,
+                Err(_) => continue,
+            };
+            let mut names = names.into_iter();
+            if let Some(canonical) = names.next() {
+                self.insert(addr, canonical, names);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// # Answering Questions
+///
+impl Hosts {
+    /// Returns the answer to a question if the table has one.
+    ///
+    /// A, AAAA, and PTR questions of class IN are answered if the table has
+    /// an entry for the question’s name. Address questions for an alias
+    /// are answered with a CNAME record for the canonical name followed by
+    /// the addresses of the requested type, which may be none.
+    fn answer<N: ToName>(
+        &self,
+        question: &Question<N>,
+    ) -> Option<Message<Bytes>> {
+        if question.qclass() != Class::IN {
+            return None;
+        }
+        let qname = question.qname();
+        match question.qtype() {
+            Rtype::A | Rtype::AAAA => {
+                let entry = self.lookup_name(qname)?;
+                let mut answer = answer_builder(question);
+                if !entry.canonical.name_eq(qname) {
+                    answer
+                        .push((qname, TTL, Cname::new(&entry.canonical)))
+                        .expect("push should not fail");
+                }
+                for addr in &entry.addrs {
+                    match (question.qtype(), addr) {
+                        (Rtype::A, IpAddr::V4(addr)) => answer.push((
+                            &entry.canonical,
+                            TTL,
+                            A::new(*addr),
+                        )),
+                        (Rtype::AAAA, IpAddr::V6(addr)) => answer.push((
+                            &entry.canonical,
+                            TTL,
+                            Aaaa::new(*addr),
+                        )),
+                        _ => Ok(()),
+                    }
+                    .expect("push should not fail");
+                }
+                Some(answer.into_message())
+            }
+            Rtype::PTR => {
+                let names = self.reverse.get(&qname.to_name::<Bytes>())?;
+                let mut answer = answer_builder(question);
+                for name in names {
+                    answer
+                        .push((qname, TTL, Ptr::new(name)))
+                        .expect("push should not fail");
+                }
+                Some(answer.into_message())
+            }
+            _ => None,
+        }
+    }
+}
+
+//------------ HostEntry -----------------------------------------------------
+
+/// The entry of a host name in a hosts table.
+#[derive(Clone, Debug)]
+pub struct HostEntry {
+    /// The canonical name of the host.
+    canonical: HostName,
+
+    /// The addresses of the host.
+    addrs: Vec<IpAddr>,
+}
+
+impl HostEntry {
+    /// Creates a new entry without addresses.
+    fn new(canonical: HostName) -> Self {
+        HostEntry {
+            canonical,
+            addrs: Vec::new(),
+        }
+    }
+
+    /// Returns the canonical name of the host.
+    pub fn canonical_name(&self) -> &HostName {
+        &self.canonical
+    }
+
+    /// Returns the addresses of the host in the order they were added.
+    pub fn addrs(&self) -> &[IpAddr] {
+        &self.addrs
+    }
+
+    /// Returns an iterator over the addresses of the host.
+    pub fn iter(&self) -> slice::Iter<'_, IpAddr> {
+        self.addrs.iter()
+    }
+}
+
+//------------ HostsResolver -------------------------------------------------
+
+/// A resolver that consults hosts tables before asking another resolver.
+///
+/// The resolver keeps two [`Hosts`] tables, one that normally contains the
+/// system’s hosts file and one with overrides that starts out empty. A, AAAA,
+/// and PTR queries for host names and addresses present in the overrides
+/// are answered from there. Otherwise, if they are present in the hosts
+/// table, they are answered from that table. All other queries are passed
+/// on to the upstream resolver.
+///
+/// A host name’s entry in a table is used for all address queries for the
+/// name, even if it doesn’t have any addresses of the requested type. This
+/// keeps address lookups from mixing data from different sources.
+///
+/// The answers produced from the tables carry a TTL of zero.
+#[derive(Clone, Debug)]
+pub struct HostsResolver<R> {
+    /// The resolver to ask if the tables don’t have an answer.
+    upstream: R,
+
+    /// The hosts table.
+    hosts: Hosts,
+
+    /// The table of overrides.
+    overrides: Hosts,
+}
+
+impl<R> HostsResolver<R> {
+    /// Creates a new resolver using the system’s hosts table.
+    pub fn new(upstream: R) -> Self {
+        Self::with_hosts(upstream, Hosts::system())
+    }
+
+    /// Creates a new resolver using the given hosts table.
+    pub fn with_hosts(upstream: R, hosts: Hosts) -> Self {
+        HostsResolver {
+            upstream,
+            hosts,
+            overrides: Hosts::new(),
+        }
+    }
+
+    /// Returns a reference to the upstream resolver.
+    pub fn upstream(&self) -> &R {
+        &self.upstream
+    }
+
+    /// Returns a reference to the hosts table.
+    pub fn hosts(&self) -> &Hosts {
+        &self.hosts
+    }
+
+    /// Replaces the hosts table.
+    ///
+    /// This can be used to pick up changes to the hosts file.
+    pub fn set_hosts(&mut self, hosts: Hosts) {
+        self.hosts = hosts
+    }
+
+    /// Returns a reference to the table of overrides.
+    pub fn overrides(&self) -> &Hosts {
+        &self.overrides
+    }
+
+    /// Returns a mutable reference to the table of overrides.
+    pub fn overrides_mut(&mut self) -> &mut Hosts {
+        &mut self.overrides
+    }
+
+    /// Returns the answer to a question from the tables if there is one.
+    fn answer<N: ToName>(
+        &self,
+        question: &Question<N>,
+    ) -> Option<Message<Bytes>> {
+        self.overrides
+            .answer(question)
+            .or_else(|| self.hosts.answer(question))
+    }
+}
+
+//--- Resolver
+
+impl<R> Resolver for HostsResolver<R>
+where
+    R: Resolver<Octets = Bytes>,
+    R::Answer: From<Message<Bytes>> + Send + Unpin,
+    R::Query: Unpin,
+{
+    type Octets = Bytes;
+    type Answer = R::Answer;
+    type Query = HostsQuery<R>;
+
+    fn query<N, Q>(&self, question: Q) -> Self::Query
+    where
+        N: ToName,
+        Q: Into<Question<N>>,
+    {
+        let question = question.into();
+        match self.answer(&question) {
+            Some(answer) => HostsQuery::Hosts(Some(answer.into())),
+            None => HostsQuery::Upstream(self.upstream.query(question)),
+        }
+    }
+}
+
+//--- SearchNames
+
+impl<R: SearchNames> SearchNames for HostsResolver<R> {
+    type Name = R::Name;
+    type Iter = R::Iter;
+
+    fn search_iter(&self) -> Self::Iter {
+        self.upstream.search_iter()
+    }
+}
+
+//------------ HostsQuery ----------------------------------------------------
+
+/// The future of a query of a [`HostsResolver`].
+pub enum HostsQuery<R: Resolver> {
+    /// The query was answered from a hosts table.
+    ///
+    /// The answer is taken out when the future resolves.
+    Hosts(Option<R::Answer>),
+
+    /// The query was passed on to the upstream resolver.
+    Upstream(R::Query),
+}
+
+impl<R> Future for HostsQuery<R>
+where
+    R: Resolver,
+    R::Answer: Unpin,
+    R::Query: Unpin,
+{
+    type Output = Result<R::Answer, io::Error>;
+
+    fn poll(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Self::Output> {
+        match *self {
+            HostsQuery::Hosts(ref mut answer) => Poll::Ready(Ok(answer
+                .take()
+                .expect("polled a resolved HostsQuery"))),
+            HostsQuery::Upstream(ref mut query) => Pin::new(query).poll(cx),
+        }
+    }
+}
+
+//------------ Helpers -------------------------------------------------------
+
+/// The TTL of the records answered from a hosts table.
+const TTL: u32 = 0;
+
+/// Returns the reverse name for an address.
+fn reverse_name(addr: IpAddr) -> HostName {
+    Name::reverse_from_addr(addr).expect("address domain name too long")
+}
+
+/// Starts building the answer to a question.
+fn answer_builder<N: ToName>(
+    question: &Question<N>,
+) -> AnswerBuilder<BytesMut> {
+    let mut message = MessageBuilder::new_bytes();
+    message.header_mut().set_qr(true);
+    message.header_mut().set_rd(true);
+    message.header_mut().set_ra(true);
+    let mut message = message.question();
+    message.push(question).expect("push should not fail");
+    message.answer()
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::iana::Rcode;
+    use crate::resolv::lookup::{lookup_addr, lookup_host};
+    use crate::resolv::stub::Answer;
+    use std::future::{ready, Ready};
+    use std::string::ToString;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const HOSTS: &str = "\
+        # The usual suspects.\n\
+        127.0.0.1   localhost\n\
+        ::1         localhost ip6-localhost # IPv6, too.\n\
+        \n\
+        192.0.2.1   host.example   www.example\n\
+        192.0.2.2   host.example\n\
+        2001:db8::1 host.example\n\
+        192.0.2.3   \n\
+        bogus       other.example\n\
+    ";
+
+    /// An upstream resolver that counts queries and knows nothing.
+    #[derive(Default)]
+    struct Upstream(AtomicUsize);
+
+    impl Resolver for &Upstream {
+        type Octets = Bytes;
+        type Answer = Answer;
+        type Query = Ready<Result<Answer, io::Error>>;
+
+        fn query<N, Q>(&self, question: Q) -> Self::Query
+        where
+            N: ToName,
+            Q: Into<Question<N>>,
+        {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            let mut answer = answer_builder(&question.into());
+            answer.header_mut().set_rcode(Rcode::NXDOMAIN);
+            ready(Ok(answer.into_message().into()))
+        }
+    }
+
+    fn name(s: &str) -> HostName {
+        HostName::from_str(s).unwrap()
+    }
+
+    fn hosts() -> Hosts {
+        let mut hosts = Hosts::new();
+        hosts.parse(&mut io::Cursor::new(HOSTS)).unwrap();
+        hosts
+    }
+
+    #[test]
+    fn parse_hosts() {
+        let hosts = hosts();
+
+        let entry = hosts.lookup_name(&name("www.example")).unwrap();
+        assert_eq!(entry.canonical_name(), &name("host.example"));
+        assert_eq!(entry.addrs(), ["192.0.2.1".parse::<IpAddr>().unwrap()]);
+
+        let entry = hosts.lookup_name(&name("HOST.example")).unwrap();
+        assert_eq!(
+            entry.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            ["192.0.2.1", "192.0.2.2", "2001:db8::1"]
+        );
+
+        let entry = hosts.lookup_name(&name("ip6-localhost")).unwrap();
+        assert_eq!(entry.canonical_name(), &name("localhost"));
+        assert_eq!(entry.addrs(), ["::1".parse::<IpAddr>().unwrap()]);
+        assert_eq!(
+            hosts.lookup_name(&name("localhost")).unwrap().addrs().len(),
+            2
+        );
+
+        assert!(hosts.lookup_name(&name("other.example")).is_none());
+        assert_eq!(
+            hosts.lookup_addr("192.0.2.1".parse().unwrap()),
+            [name("host.example")]
+        );
+        assert!(hosts.lookup_addr("192.0.2.3".parse().unwrap()).is_empty());
+    }
+
+    #[tokio::test]
+    async fn lookups() {
+        let upstream = Upstream::default();
+        let mut resolver = HostsResolver::with_hosts(&upstream, hosts());
+
+        let found =
+            lookup_host(&resolver, name("www.example")).await.unwrap();
+        assert_eq!(found.canonical_name().to_string(), "host.example");
+        assert_eq!(
+            found
+                .iter()
+                .map(|addr| addr.to_string())
+                .collect::<Vec<_>>(),
+            ["192.0.2.1"]
+        );
+        let found =
+            lookup_host(&resolver, name("host.example")).await.unwrap();
+        assert_eq!(
+            found
+                .iter()
+                .map(|addr| addr.to_string())
+                .collect::<Vec<_>>(),
+            ["2001:db8::1", "192.0.2.1", "192.0.2.2"]
+        );
+
+        let found = lookup_addr(&resolver, "2001:db8::1".parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(
+            found
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>(),
+            ["host.example"]
+        );
+        assert_eq!(upstream.0.load(Ordering::Relaxed), 0);
+
+        // Unknown names and other record types go upstream.
+        let found = lookup_host(&resolver, name("unknown.example"))
+            .await
+            .unwrap();
+        assert!(found.is_empty());
+        assert_eq!(upstream.0.load(Ordering::Relaxed), 2);
+        let answer = resolver.query((name("host.example"), Rtype::MX)).await;
+        assert_eq!(answer.unwrap().header().rcode(), Rcode::NXDOMAIN);
+        assert_eq!(upstream.0.load(Ordering::Relaxed), 3);
+
+        // Overrides take precedence.
+        resolver.overrides_mut().insert(
+            "192.0.2.10".parse().unwrap(),
+            name("www.example"),
+            [],
+        );
+        let found =
+            lookup_host(&resolver, name("www.example")).await.unwrap();
+        assert_eq!(found.canonical_name().to_string(), "www.example");
+        assert_eq!(
+            found
+                .iter()
+                .map(|addr| addr.to_string())
+                .collect::<Vec<_>>(),
+            ["192.0.2.10"]
+        );
+        assert_eq!(upstream.0.load(Ordering::Relaxed), 3);
+    }
+}
@@ -19,7 +19,8 @@ type Octets128 = octseq::array::Array<128>;
 ///
 /// The function will query DNS using the resolver represented by `resolv`.
 /// It will query DNS only and not consider any other database the system
-/// may have.
+/// may have unless the resolver does so itself, as the
+/// [`HostsResolver`][crate::resolv::HostsResolver] does for the hosts file.
 ///
 /// The value returned upon success can be turned into an iterator over
 /// host names via its `iter()` method. This is due to lifetime issues.
@@ -5,12 +5,12 @@ use crate::base::iana::{Class, Rtype};
 use crate::base::message::Message;
 use crate::base::name::{Name, ToName, ToRelativeName};
 use crate::base::wire::ParseError;
+use crate::rdata::srv::order_by_priority;
 use crate::rdata::{Aaaa, Srv, A};
 use crate::resolv::resolver::Resolver;
 use core::fmt;
 use futures_util::stream::{self, Stream, StreamExt};
 use octseq::octets::Octets;
-use rand::distributions::{Distribution, Uniform};
 use std::net::{IpAddr, SocketAddr};
 use std::vec::Vec;
 use std::{io, mem, ops};
@@ -231,47 +231,11 @@ impl FoundSrvs {
     }
 
     fn reorder_items(items: &mut [SrvItem]) {
-        // First, reorder by priority and weight, effectively
-        // grouping by priority, with weight 0 records at the beginning of
-        // each group.
-        items.sort_by_key(|k| (k.priority(), k.weight()));
-
-        // Find each group and reorder them using reorder_by_weight
-        let mut current_prio = 0;
-        let mut weight_sum = 0;
-        let mut first_index = 0;
-        for i in 0..items.len() {
-            if current_prio != items[i].priority() {
-                current_prio = items[i].priority();
-                Self::reorder_by_weight(
-                    &mut items[first_index..i],
-                    weight_sum,
-                );
-                weight_sum = 0;
-                first_index = i;
-            }
-            weight_sum += u32::from(items[i].weight());
-        }
-        Self::reorder_by_weight(&mut items[first_index..], weight_sum);
-    }
-
-    /// Reorders items in a priority level based on their weight
-    fn reorder_by_weight(items: &mut [SrvItem], weight_sum: u32) {
-        let mut rng = rand::thread_rng();
-        let mut weight_sum = weight_sum;
-        for i in 0..items.len() {
-            let range = Uniform::new(0, weight_sum + 1);
-            let mut sum: u32 = 0;
-            let pick = range.sample(&mut rng);
-            for j in 0..items.len() {
-                sum += u32::from(items[j].weight());
-                if sum >= pick {
-                    weight_sum -= u32::from(items[j].weight());
-                    items.swap(i, j);
-                    break;
-                }
-            }
-        }
+        order_by_priority(
+            items,
+            |item| (item.priority(), item.weight()),
+            &mut rand::thread_rng(),
+        );
     }
 }
 
@@ -19,6 +19,12 @@
 //!    See the [stub] module for more information on how to use the stub
 //!    resolver.
 //!
+//! *  [`HostsResolver`] answers address and reverse queries from the
+//!    system’s hosts file and a table of overrides and passes all other
+//!    queries on to another resolver.
+//!
+//!    See the [hosts] module for more information.
+//!
 //! The lookups implemented by the crate are generic over the particular
 //! resolver, so you can pick the resolver most suitable for your own
 //! application or even implement your own specialised resolver. All
@@ -26,16 +32,20 @@
 //! convenience, they are also available as methods on the [`Resolver`]
 //! trait.
 //!
+//! [hosts]: hosts/index.html
 //! [lookup]: lookup/index.html
 //! [stub]: stub/index.html
 //! [`Resolver`]: resolver/trait.Resolver.html
+//! [`HostsResolver`]: hosts/struct.HostsResolver.html
 //! [`StubResolver`]: stub/struct.StubResolver.html
 #![cfg(feature = "resolv")]
 #![cfg_attr(docsrs, doc(cfg(feature = "resolv")))]
 
+pub use self::hosts::HostsResolver;
 pub use self::resolver::Resolver;
 pub use self::stub::StubResolver;
 
+pub mod hosts;
 pub mod lookup;
 pub mod resolver;
 pub mod stub;
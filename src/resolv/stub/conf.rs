@@ -14,10 +14,10 @@ use std::cmp::Ordering;
 use std::default::Default;
 use std::io::Read;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::slice::SliceIndex;
 use std::str::{self, FromStr, SplitWhitespace};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 use std::vec::Vec;
 use std::{convert, error, fmt, fs, io, ops};
 
@@ -34,8 +34,12 @@ pub struct ResolvOptions {
     /// Search list for host-name lookup.
     pub search: SearchList,
 
-    // TODO Sortlist
-    // sortlist: ??
+    /// Address ranges for sorting the results of host-name lookups.
+    ///
+    /// This option is not currently implemented. It is only relevant for
+    /// `lookup_host()`.
+    pub sortlist: Vec<SortListEntry>,
+
     /// Number of dots before an initial absolute query is made.
     pub ndots: usize,
 
@@ -159,6 +163,14 @@ pub struct ResolvOptions {
     /// `search` and `ndots` fields govern resolution of relative names of
     /// all kinds.
     pub no_tld_query: bool,
+
+    /// Set the authentic data bit in queries.
+    ///
+    /// This signals that the upstream resolver is trusted to perform DNSSEC
+    /// validation and that the AD bit of its responses is meaningful.
+    ///
+    /// This option is implemented by the query.
+    pub trust_ad: bool,
 }
 
 impl Default for ResolvOptions {
@@ -166,7 +178,7 @@ impl Default for ResolvOptions {
         ResolvOptions {
             // non-flags:
             search: SearchList::new(),
-            //sortlist,
+            sortlist: Vec::new(),
             ndots: 1,
             timeout: Duration::new(5, 0),
             attempts: 2,
@@ -192,6 +204,7 @@ impl Default for ResolvOptions {
             single_request: false,
             single_request_reopen: false,
             no_tld_query: false,
+            trust_ad: false,
         }
     }
 }
@@ -341,6 +354,17 @@ impl ResolvConf {
 /// # Parsing Configuration File
 ///
 impl ResolvConf {
+    /// Creates a finalized configuration from a file.
+    ///
+    /// Unlike [`ResolvConf::default`], this returns an error if the file
+    /// cannot be read or parsed.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let mut res = Self::new();
+        res.parse_file(path)?;
+        res.finalize();
+        Ok(res)
+    }
+
     /// Parses the configuration from a file.
     pub fn parse_file<P: AsRef<Path>>(
         &mut self,
@@ -373,8 +397,7 @@ impl ResolvConf {
                 Some("nameserver") => self.parse_nameserver(words)?,
                 Some("domain") => self.parse_domain(words)?,
                 Some("search") => self.parse_search(words)?,
-                Some("sortlist") => { /* TODO: self.parse_sortlist(words)? */
-                }
+                Some("sortlist") => self.parse_sortlist(words)?,
                 Some("options") => self.parse_options(words)?,
                 _ => return Err(Error::ParseError),
             }
@@ -420,14 +443,18 @@ impl ResolvConf {
         Ok(())
     }
 
-    /*
     fn parse_sortlist(
         &mut self,
-        _words: SplitWhitespace
+        words: SplitWhitespace,
     ) -> Result<(), Error> {
-        // XXX TODO
+        // Like glibc, we skip entries we don’t understand rather than
+        // rejecting the whole file.
+        self.options.sortlist = words
+            .filter_map(|word| SortListEntry::from_str(word).ok())
+            .take(SortListEntry::MAX_ENTRIES)
+            .collect();
+        Ok(())
     }
-    */
 
     fn parse_options(&mut self, words: SplitWhitespace) -> Result<(), Error> {
         for word in words {
@@ -453,6 +480,7 @@ impl ResolvConf {
                 }
                 ("no-tld-query", None) => self.options.no_tld_query = true,
                 ("use-vc", None) => self.options.use_vc = true,
+                ("trust-ad", None) => self.options.trust_ad = true,
                 // Ignore unknown or misformated options.
                 _ => {}
             }
@@ -502,6 +530,13 @@ impl fmt::Display for ResolvConf {
             }
             Ordering::Less => {}
         }
+        if !self.options.sortlist.is_empty() {
+            "sortlist".fmt(f)?;
+            for entry in &self.options.sortlist {
+                write!(f, " {}", entry)?;
+            }
+            "\n".fmt(f)?;
+        }
 
         // Collect options so we only print them if there are any non-default
         // ones.
@@ -572,6 +607,9 @@ impl fmt::Display for ResolvConf {
         if self.options.no_tld_query {
             options.push("no-tld-query".into())
         }
+        if self.options.trust_ad {
+            options.push("trust-ad".into())
+        }
 
         if !options.is_empty() {
             "options".fmt(f)?;
@@ -585,6 +623,169 @@ impl fmt::Display for ResolvConf {
     }
 }
 
+//------------ ConfWatcher ---------------------------------------------------
+
+/// Keeps track of changes to a configuration file.
+///
+/// The watcher remembers the modification time and size of the file when
+/// it was last loaded. Calling [`reload`][Self::reload] regularly, for
+/// instance before starting a new batch of queries, will return a new
+/// configuration whenever the file has changed since.
+///
+/// Since a [`StubResolver`][super::StubResolver] cannot be reconfigured,
+/// a new resolver needs to be created from the new configuration.
+#[derive(Clone, Debug)]
+pub struct ConfWatcher {
+    /// The path of the configuration file.
+    path: PathBuf,
+
+    /// The modification time and size when the file was last loaded.
+    ///
+    /// This is `None` if the file hasn’t been loaded yet or didn’t exist.
+    state: Option<(SystemTime, u64)>,
+}
+
+impl ConfWatcher {
+    /// Creates a new watcher for the file at the given path.
+    ///
+    /// The file is not loaded yet, so the first call to
+    /// [`reload`][Self::reload] will load it.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        ConfWatcher {
+            path: path.into(),
+            state: None,
+        }
+    }
+
+    /// Creates a new watcher for the system’s `/etc/resolv.conf`.
+    pub fn system() -> Self {
+        Self::new("/etc/resolv.conf")
+    }
+
+    /// Returns the path of the watched file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns whether the file has changed since it was last loaded.
+    ///
+    /// A file that cannot be accessed is considered changed if it was
+    /// accessible when last loaded.
+    pub fn has_changed(&self) -> bool {
+        self.current_state() != self.state
+    }
+
+    /// Loads the configuration from the file.
+    ///
+    /// The returned configuration has been finalized.
+    pub fn load(&mut self) -> Result<ResolvConf, Error> {
+        let state = self.current_state();
+        let res = ResolvConf::from_file(&self.path)?;
+        self.state = state;
+        Ok(res)
+    }
+
+    /// Loads the configuration from the file if it has changed.
+    ///
+    /// Returns `Ok(None)` if the file has not changed since it was last
+    /// loaded successfully.
+    pub fn reload(&mut self) -> Result<Option<ResolvConf>, Error> {
+        if self.state.is_some() && !self.has_changed() {
+            return Ok(None);
+        }
+        self.load().map(Some)
+    }
+
+    /// Returns the modification time and size of the file.
+    fn current_state(&self) -> Option<(SystemTime, u64)> {
+        let metadata = fs::metadata(&self.path).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+}
+
+//------------ SortListEntry -------------------------------------------------
+
+/// An entry of the sort list.
+///
+/// The entry describes an IPv4 network through an address and a netmask.
+/// Addresses found by a host-name lookup that are part of a network listed
+/// earlier in the sort list should be preferred.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SortListEntry {
+    /// The address of the network.
+    pub addr: Ipv4Addr,
+
+    /// The netmask of the network.
+    pub mask: Ipv4Addr,
+}
+
+impl SortListEntry {
+    /// The maximum number of entries in a sort list.
+    ///
+    /// Additional entries in a configuration file are ignored.
+    pub const MAX_ENTRIES: usize = 10;
+
+    /// Creates a new entry from an address and a netmask.
+    pub fn new(addr: Ipv4Addr, mask: Ipv4Addr) -> Self {
+        SortListEntry { addr, mask }
+    }
+
+    /// Creates a new entry using the natural netmask of the address.
+    ///
+    /// This is the netmask of the address’s class in the long obsolete
+    /// classful addressing which is what glibc uses if the netmask is
+    /// missing.
+    pub fn natural(addr: Ipv4Addr) -> Self {
+        let mask = match addr.octets()[0] {
+            0..=127 => Ipv4Addr::new(255, 0, 0, 0),
+            128..=191 => Ipv4Addr::new(255, 255, 0, 0),
+            _ => Ipv4Addr::new(255, 255, 255, 0),
+        };
+        Self::new(addr, mask)
+    }
+
+    /// Returns whether the given address is part of the network.
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        match addr {
+            IpAddr::V4(addr) => {
+                let mask = u32::from(self.mask);
+                u32::from(addr) & mask == u32::from(self.addr) & mask
+            }
+            IpAddr::V6(_) => false,
+        }
+    }
+}
+
+//--- FromStr
+
+impl FromStr for SortListEntry {
+    type Err = Error;
+
+    /// Parses an entry in the form `address[/netmask]`.
+    ///
+    /// As with glibc, the address and netmask can also be separated by an
+    /// ampersand.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(['/', '&']) {
+            Some((addr, mask)) => Ok(Self::new(
+                addr.parse().map_err(|_| Error::ParseError)?,
+                mask.parse().map_err(|_| Error::ParseError)?,
+            )),
+            None => {
+                Ok(Self::natural(s.parse().map_err(|_| Error::ParseError)?))
+            }
+        }
+    }
+}
+
+//--- Display
+
+impl fmt::Display for SortListEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.mask)
+    }
+}
+
 //------------ SearchSuffix --------------------------------------------------
 
 pub type SearchSuffix = Name<SmallVec<[u8; 24]>>;
@@ -745,4 +946,104 @@ mod test {
         assert!(conf.options.use_vc);
         assert_eq!(conf.options.ndots, 122);
     }
+
+    #[test]
+    fn parse_options_and_sortlist() {
+        let mut conf = ResolvConf::new();
+        let data = "domain example.com\n\
+                    search example.net example.org\n\
+                    sortlist 130.155.160.0/255.255.240.0 130.155.0.0\n\
+                    options timeout:3 attempts:4 rotate edns0 trust-ad\n"
+            .to_string();
+        conf.parse(&mut io::Cursor::new(data)).unwrap();
+        conf.finalize();
+
+        assert_eq!(conf.options.search.len(), 3);
+        assert_eq!(conf.options.timeout, Duration::from_secs(3));
+        assert_eq!(conf.options.attempts, 4);
+        assert!(conf.options.rotate);
+        assert!(conf.options.use_edns0);
+        assert!(conf.options.trust_ad);
+        assert_eq!(
+            conf.options.sortlist,
+            [
+                SortListEntry::new(
+                    Ipv4Addr::new(130, 155, 160, 0),
+                    Ipv4Addr::new(255, 255, 240, 0)
+                ),
+                SortListEntry::new(
+                    Ipv4Addr::new(130, 155, 0, 0),
+                    Ipv4Addr::new(255, 255, 0, 0)
+                ),
+            ]
+        );
+        assert!(conf.options.sortlist[0]
+            .contains(Ipv4Addr::new(130, 155, 175, 1).into()));
+        assert!(!conf.options.sortlist[0]
+            .contains(Ipv4Addr::new(130, 155, 176, 1).into()));
+
+        // Defaults are filled in.
+        assert_eq!(
+            conf.servers[0].addr,
+            SocketAddr::from((Ipv4Addr::LOCALHOST, 53))
+        );
+        assert_eq!(conf.servers[0].request_timeout, Duration::from_secs(3));
+
+        let display = conf.to_string();
+        assert!(display.contains(
+            "sortlist 130.155.160.0/255.255.240.0 130.155.0.0/255.255.0.0\n"
+        ));
+        assert!(display.contains("trust-ad"));
+    }
+
+    #[test]
+    fn parse_bad_sortlist() {
+        // Entries we don’t understand are skipped and don’t affect the
+        // rest of the file.
+        let mut conf = ResolvConf::new();
+        conf.parse(&mut io::Cursor::new(
+            "sortlist 10.0.0.0/8 130.155.0.0/foo 130.155.160.0&255.255.240.0\n\
+             nameserver 192.0.2.53\n",
+        ))
+        .unwrap();
+        conf.finalize();
+
+        assert_eq!(
+            conf.options.sortlist,
+            [SortListEntry::new(
+                Ipv4Addr::new(130, 155, 160, 0),
+                Ipv4Addr::new(255, 255, 240, 0)
+            )]
+        );
+        assert_eq!(conf.servers.len(), 1);
+        assert_eq!(
+            conf.servers[0].addr,
+            SocketAddr::from((Ipv4Addr::new(192, 0, 2, 53), 53))
+        );
+    }
+
+    #[test]
+    fn watch_conf() {
+        let path = std::env::temp_dir()
+            .join(format!("domain-resolv-{}.conf", std::process::id()));
+        fs::write(&path, "nameserver 192.0.2.1\n").unwrap();
+
+        let mut watcher = ConfWatcher::new(&path);
+        assert!(watcher.has_changed());
+        let conf = watcher.reload().unwrap().unwrap();
+        assert_eq!(conf.servers.len(), 1);
+        assert!(!watcher.has_changed());
+        assert!(watcher.reload().unwrap().is_none());
+
+        fs::write(&path, "nameserver 192.0.2.1\nnameserver 192.0.2.2\n")
+            .unwrap();
+        assert!(watcher.has_changed());
+        let conf = watcher.reload().unwrap().unwrap();
+        assert_eq!(conf.servers.len(), 2);
+        assert!(watcher.reload().unwrap().is_none());
+
+        fs::remove_file(&path).unwrap();
+        assert!(watcher.has_changed());
+        assert!(watcher.reload().is_err());
+    }
 }
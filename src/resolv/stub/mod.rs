@@ -66,13 +66,15 @@ pub mod conf;
 ///
 /// If you want to run a single query or lookup on a resolver synchronously,
 /// you can do so simply by using the [`run`] or [`run_with_conf`] associated
-/// functions.
+/// functions. For repeated queries, the blocking methods such as
+/// [`query_blocking`] reuse the resolver and its connections.
 ///
 /// [`new`]: #method.new
 /// [`from_conf`]: #method.from_conf
 /// [`query`]: #method.query
 /// [`run`]: #method.run
 /// [`run_with_conf`]: #method.run_with_conf
+/// [`query_blocking`]: #method.query_blocking
 #[derive(Debug)]
 pub struct StubResolver {
     transport: Mutex<Option<redundant::Connection<RequestMessage<Vec<u8>>>>>,
@@ -81,6 +83,10 @@ pub struct StubResolver {
     options: ResolvOptions,
 
     servers: Vec<ServerConf>,
+
+    /// The runtime used by the blocking methods.
+    #[cfg(feature = "resolv-sync")]
+    runtime: std::sync::OnceLock<runtime::Runtime>,
 }
 
 impl StubResolver {
@@ -96,6 +102,8 @@ impl StubResolver {
             options: conf.options,
 
             servers: conf.servers,
+            #[cfg(feature = "resolv-sync")]
+            runtime: Default::default(),
         }
     }
 
@@ -123,7 +131,7 @@ impl StubResolver {
         question: Q,
     ) -> Result<Answer, io::Error> {
         Query::new(self)?
-            .run(Query::create_message(question.into()))
+            .run(Query::create_message(&self.options, question.into()))
             .await
     }
 
@@ -301,6 +309,53 @@ impl StubResolver {
             .build()?;
         runtime.block_on(op(resolver))
     }
+
+    /// Synchronously answers a question.
+    ///
+    /// This is the blocking variant of [`query`][Self::query] for
+    /// applications that don’t use async code otherwise. The first call of
+    /// a blocking method starts a single-threaded runtime that is kept for
+    /// all later calls, so the connections to the servers stay open
+    /// between queries. Because the connections are driven by the runtime
+    /// that first used them, a resolver should either only be used through
+    /// the blocking methods or only from async code.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if called from within an async runtime.
+    pub fn query_blocking<N: ToName, Q: Into<Question<N>>>(
+        &self,
+        question: Q,
+    ) -> Result<Answer, io::Error> {
+        self.blocking_runtime()?.block_on(self.query(question))
+    }
+
+    /// Synchronously looks up the addresses of a host.
+    ///
+    /// This is the blocking variant of [`lookup_host`][Self::lookup_host].
+    /// See [`query_blocking`][Self::query_blocking] for how the blocking
+    /// methods work.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if called from within an async runtime.
+    pub fn lookup_host_blocking(
+        &self,
+        qname: impl ToName,
+    ) -> Result<FoundHosts<&Self>, io::Error> {
+        self.blocking_runtime()?.block_on(self.lookup_host(qname))
+    }
+
+    /// Returns the runtime for the blocking methods, starting it if needed.
+    fn blocking_runtime(&self) -> Result<&runtime::Runtime, io::Error> {
+        if let Some(runtime) = self.runtime.get() {
+            return Ok(runtime);
+        }
+        let runtime = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        Ok(self.runtime.get_or_init(|| runtime))
+    }
 }
 
 impl Default for StubResolver {
@@ -320,7 +375,7 @@ impl<'a> Resolver for &'a StubResolver {
         N: ToName,
         Q: Into<Question<N>>,
     {
-        let message = Query::create_message(question.into());
+        let message = Query::create_message(&self.options, question.into());
         Box::pin(self.query_message(message))
     }
 }
@@ -394,10 +449,14 @@ impl<'a> Query<'a> {
         }
     }
 
-    fn create_message(question: Question<impl ToName>) -> QueryMessage {
+    fn create_message(
+        options: &ResolvOptions,
+        question: Question<impl ToName>,
+    ) -> QueryMessage {
         let mut message = MessageBuilder::from_target(Default::default())
             .expect("MessageBuilder should not fail");
-        message.header_mut().set_rd(true);
+        message.header_mut().set_rd(options.recurse);
+        message.header_mut().set_ad(options.trust_ad);
         let mut message = message.question();
         message.push(question).expect("push should not fail");
         message.additional()
@@ -523,3 +582,98 @@ impl Iterator for SearchIter<'_> {
         }
     }
 }
+
+//============ Testing =======================================================
+
+#[cfg(all(test, feature = "resolv-sync"))]
+mod test {
+    use super::*;
+    use crate::base::iana::{Class, Rtype};
+    use crate::base::Name;
+    use crate::net::client::request::GetResponse;
+    use crate::rdata::A;
+    use std::net::Ipv4Addr;
+    use std::str::FromStr;
+    use std::sync::atomic::AtomicUsize;
+
+    /// A connection answering A queries with 192.0.2.1 and everything else
+    /// with NODATA.
+    #[derive(Debug, Default)]
+    struct MockConnection {
+        /// The number of requests received.
+        count: Arc<AtomicUsize>,
+    }
+
+    impl SendRequest<RequestMessage<Vec<u8>>> for MockConnection {
+        fn send_request(
+            &self,
+            request_msg: RequestMessage<Vec<u8>>,
+        ) -> Box<dyn GetResponse + Send + Sync> {
+            self.count.fetch_add(1, Ordering::Relaxed);
+            let query = request_msg.to_message().unwrap();
+            let question = query.sole_question().unwrap();
+            let mut answer = MessageBuilder::new_bytes()
+                .start_answer(&query, Rcode::NOERROR)
+                .unwrap();
+            if question.qtype() == Rtype::A {
+                answer
+                    .push((
+                        question.qname(),
+                        Class::IN,
+                        60,
+                        A::new(Ipv4Addr::new(192, 0, 2, 1)),
+                    ))
+                    .unwrap();
+            }
+            Box::new(MockResponse(answer.into_message()))
+        }
+    }
+
+    /// The response of a [`MockConnection`].
+    #[derive(Debug)]
+    struct MockResponse(Message<Bytes>);
+
+    impl GetResponse for MockResponse {
+        fn get_response(
+            &mut self,
+        ) -> Pin<
+            Box<
+                dyn Future<Output = Result<Message<Bytes>, Error>>
+                    + Send
+                    + Sync
+                    + '_,
+            >,
+        > {
+            let answer = self.0.clone();
+            Box::pin(async move { Ok(answer) })
+        }
+    }
+
+    #[test]
+    fn blocking() {
+        let resolver = StubResolver::from_conf(ResolvConf::new());
+        let connection = MockConnection::default();
+        let count = connection.count.clone();
+        resolver
+            .blocking_runtime()
+            .unwrap()
+            .block_on(resolver.add_connection(Box::new(connection)));
+        let name = Name::<Vec<u8>>::from_str("www.example.com").unwrap();
+
+        // Repeated queries reuse the runtime and the connections.
+        for _ in 0..2 {
+            let answer = resolver.query_blocking((&name, Rtype::A)).unwrap();
+            assert_eq!(answer.header().rcode(), Rcode::NOERROR);
+            assert_eq!(answer.header_counts().ancount(), 1);
+        }
+
+        let hosts = resolver.lookup_host_blocking(&name).unwrap();
+        assert_eq!(
+            hosts.iter().collect::<Vec<_>>(),
+            [IpAddr::from([192, 0, 2, 1])]
+        );
+
+        // Two queries plus one each for A and AAAA.
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+}
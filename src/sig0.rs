@@ -0,0 +1,477 @@
+//! Support for SIG(0) transaction signatures.
+//!
+//! **This module is experimental and likely to change significantly.**
+//!
+//! SIG(0), defined in [RFC 2931], authenticates a complete DNS message
+//! using public key cryptography. The sender calculates a signature over
+//! the message with its private key and appends it to the additional
+//! section of the message as a SIG record with a type covered of zero. The
+//! receiver verifies the signature with the sender’s public key which it
+//! has obtained in some way, typically from a KEY or DNSKEY record.
+//!
+//! Unlike [TSIG], SIG(0) doesn’t require the two parties to share a secret.
+//! It also doesn’t keep any state between messages, so there are only two
+//! functions: [`sign`] appends a SIG(0) record to a message under
+//! construction and [`verify_with`] checks the SIG(0) record of a received
+//! message using a cryptographic backend. With the `ring` feature, [`verify`]
+//! does the same using the Ring backend.
+//!
+//! When signing or verifying a response, the request it answers can be
+//! provided. It is then included in the signature which binds the response
+//! to the request.
+//!
+//! Verifying signatures requires the `unstable-validate` feature, creating
+//! signatures additionally requires the `unstable-sign` feature.
+//!
+//! [RFC 2931]: https://tools.ietf.org/html/rfc2931
+//! [TSIG]: https://tools.ietf.org/html/rfc8945
+#![cfg(feature = "unstable-validate")]
+#![cfg_attr(docsrs, doc(cfg(feature = "unstable-validate")))]
+
+use crate::base::header::HeaderCounts;
+use crate::base::iana::{Rtype, SecAlg};
+use crate::base::message::Message;
+use crate::base::name::{ParsedName, ToName};
+use crate::base::rdata::{ParseRecordData, RecordData};
+use crate::base::record::Ttl;
+use crate::base::wire::{Compose, Composer, ParseError};
+use crate::rdata::dnssec::Timestamp;
+use crate::rdata::Rrsig;
+use crate::validate::{Key, RrsigExt, VerifyRaw};
+#[cfg(feature = "unstable-sign")]
+use crate::{
+    base::iana::Class,
+    base::message_builder::{AdditionalBuilder, PushError},
+    base::name::Name,
+    base::rdata::{LongRecordData, UnknownRecordData},
+    sign::{SignError, SignRaw, SigningKey},
+};
+use octseq::builder::infallible;
+use octseq::{Octets, Parser};
+use std::vec::Vec;
+use std::{error, fmt};
+
+//------------ sign ----------------------------------------------------------
+
+/// Signs a message with SIG(0).
+///
+/// The function takes a complete message in the form of an additional
+/// builder, calculates the signature over it using `key`, and appends the
+/// signature as a SIG record to the additional section. No further records
+/// must be added to the message afterwards.
+///
+/// If the message is a response, the request it answers should be given
+/// via `request` in the exact form it was received, i.e., including its
+/// own SIG(0) record, if any.
+///
+/// The signature is valid from `inception` until `expiration`. Since
+/// SIG(0) signatures are only meant to protect a single exchange, the
+/// validity period should be short – [RFC 2931] suggests five minutes.
+///
+/// [RFC 2931]: https://tools.ietf.org/html/rfc2931
+#[cfg(feature = "unstable-sign")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable-sign")))]
+pub fn sign<Target, Octs, Inner>(
+    message: &mut AdditionalBuilder<Target>,
+    key: &SigningKey<Octs, Inner>,
+    request: Option<&[u8]>,
+    inception: Timestamp,
+    expiration: Timestamp,
+) -> Result<(), SigningError>
+where
+    Target: Composer,
+    Octs: AsRef<[u8]>,
+    Inner: SignRaw,
+{
+    let mut rdata = Vec::new();
+    infallible(compose_rdata_prefix(
+        &mut rdata,
+        key.algorithm(),
+        expiration,
+        inception,
+        key.public_key().key_tag(),
+        key.owner(),
+    ));
+
+    let mut data = rdata.clone();
+    if let Some(request) = request {
+        data.extend_from_slice(request);
+    }
+    data.extend_from_slice(message.as_slice());
+    let signature = key.raw_secret_key().sign_raw(&data)?;
+    rdata.extend_from_slice(signature.as_ref());
+
+    message.push((
+        Name::root_slice(),
+        Class::ANY,
+        Ttl::ZERO,
+        UnknownRecordData::from_octets(Rtype::SIG, rdata)?,
+    ))?;
+    Ok(())
+}
+
+//------------ verify --------------------------------------------------------
+
+/// Verifies the SIG(0) signature of a message.
+///
+/// The function checks that the last record of the additional section of
+/// `message` is a SIG(0) record created by `key` that is valid at `now`,
+/// and that its signature covers the message.
+///
+/// If the message is a response, the request it answers has to be given
+/// via `request` in the exact form it was sent, i.e., including its own
+/// SIG(0) record, if any.
+///
+/// The function only checks the signature. It does not remove the SIG(0)
+/// record from the message.
+///
+/// This uses the Ring backend. Use [`verify_with`] to choose a different
+/// one.
+#[cfg(feature = "ring")]
+pub fn verify<Octs: Octets + ?Sized>(
+    message: &Message<Octs>,
+    request: Option<&[u8]>,
+    key: &Key<impl AsRef<[u8]>>,
+    now: Timestamp,
+) -> Result<(), ValidationError> {
+    verify_with(message, request, key, now, &crate::validate::ring::Verifier)
+}
+
+/// Verifies the SIG(0) signature of a message using the given backend.
+///
+/// This is identical to [`verify`] but uses `verifier` for the
+/// cryptographic operation.
+pub fn verify_with<Octs: Octets + ?Sized>(
+    message: &Message<Octs>,
+    request: Option<&[u8]>,
+    key: &Key<impl AsRef<[u8]>>,
+    now: Timestamp,
+    verifier: &impl VerifyRaw,
+) -> Result<(), ValidationError> {
+    let (sig, start) = find_sig(message)?;
+    if sig.type_covered() != Rtype::from_int(0) {
+        return Err(ValidationError::Invalid);
+    }
+    if sig.algorithm() != key.algorithm()
+        || sig.key_tag() != key.key_tag()
+        || !sig.signer_name().name_eq(key.owner())
+    {
+        return Err(ValidationError::KeyMismatch);
+    }
+    if now < sig.inception() {
+        return Err(ValidationError::Premature);
+    }
+    if now > sig.expiration() {
+        return Err(ValidationError::Expired);
+    }
+
+    let mut data = Vec::new();
+    infallible(compose_rdata_prefix(
+        &mut data,
+        sig.algorithm(),
+        sig.expiration(),
+        sig.inception(),
+        sig.key_tag(),
+        sig.signer_name(),
+    ));
+    if let Some(request) = request {
+        data.extend_from_slice(request);
+    }
+    let header_start = data.len();
+    data.extend_from_slice(&message.as_slice()[..start]);
+    HeaderCounts::for_message_slice_mut(&mut data[header_start..])
+        .dec_arcount();
+
+    sig.verify_signed_data_with(&key.to_dnskey(), &data, verifier)
+        .map_err(|_| ValidationError::BadSig)
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Composes the SIG record data without the signature.
+fn compose_rdata_prefix<Target: Composer + ?Sized>(
+    target: &mut Target,
+    algorithm: SecAlg,
+    expiration: Timestamp,
+    inception: Timestamp,
+    key_tag: u16,
+    signer_name: &impl ToName,
+) -> Result<(), Target::AppendError> {
+    Rtype::from_int(0).compose(target)?;
+    algorithm.compose(target)?;
+    0u8.compose(target)?;
+    Ttl::ZERO.compose(target)?;
+    expiration.compose(target)?;
+    inception.compose(target)?;
+    key_tag.compose(target)?;
+    signer_name.compose_canonical(target)
+}
+
+/// Returns the SIG(0) record of a message and the index of its start.
+///
+/// Checks that the SIG(0) record is the last record in the additional
+/// section.
+#[allow(clippy::type_complexity)]
+fn find_sig<Octs: Octets + ?Sized>(
+    message: &Message<Octs>,
+) -> Result<
+    (Rrsig<Octs::Range<'_>, ParsedName<Octs::Range<'_>>>, usize),
+    ValidationError,
+> {
+    let mut section = message
+        .additional()
+        .map_err(|_| ValidationError::ParseError)?;
+    loop {
+        let start = section.pos();
+        let Some(record) = section.next() else {
+            return Err(ValidationError::Missing);
+        };
+        let record = record
+            .map_err(|_| ValidationError::ParseError)?
+            .into_record::<Sig<_, _>>()
+            .map_err(|_| ValidationError::Invalid)?;
+        if let Some(record) = record {
+            if section.next().is_some() {
+                return Err(ValidationError::Position);
+            }
+            return Ok((record.into_data().0, start));
+        }
+    }
+}
+
+//------------ Sig -----------------------------------------------------------
+
+/// The record data of a SIG record.
+///
+/// SIG records share their record data format with RRSIG records. This
+/// type allows parsing them as such.
+struct Sig<Octs, Name>(Rrsig<Octs, Name>);
+
+impl<Octs, Name> RecordData for Sig<Octs, Name> {
+    fn rtype(&self) -> Rtype {
+        Rtype::SIG
+    }
+}
+
+impl<'a, Octs: Octets + ?Sized> ParseRecordData<'a, Octs>
+    for Sig<Octs::Range<'a>, ParsedName<Octs::Range<'a>>>
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::SIG {
+            Rrsig::parse(parser).map(|sig| Some(Sig(sig)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+//------------ SigningError --------------------------------------------------
+
+/// An error happened while signing a message.
+#[cfg(feature = "unstable-sign")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable-sign")))]
+#[derive(Clone, Debug)]
+pub enum SigningError {
+    /// The signature could not be created.
+    Sign(SignError),
+
+    /// The signature was too long for the record data.
+    LongRecordData,
+
+    /// The SIG record could not be added to the message.
+    Push(PushError),
+}
+
+//--- From
+
+#[cfg(feature = "unstable-sign")]
+impl From<SignError> for SigningError {
+    fn from(err: SignError) -> Self {
+        SigningError::Sign(err)
+    }
+}
+
+#[cfg(feature = "unstable-sign")]
+impl From<LongRecordData> for SigningError {
+    fn from(_: LongRecordData) -> Self {
+        SigningError::LongRecordData
+    }
+}
+
+#[cfg(feature = "unstable-sign")]
+impl From<PushError> for SigningError {
+    fn from(err: PushError) -> Self {
+        SigningError::Push(err)
+    }
+}
+
+//--- Display and Error
+
+#[cfg(feature = "unstable-sign")]
+impl fmt::Display for SigningError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SigningError::Sign(err) => err.fmt(f),
+            SigningError::LongRecordData => f.write_str("signature too long"),
+            SigningError::Push(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "unstable-sign")]
+impl error::Error for SigningError {}
+
+//------------ ValidationError -----------------------------------------------
+
+/// An error happened while verifying the SIG(0) signature of a message.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ValidationError {
+    /// The message could not be parsed.
+    ParseError,
+
+    /// The message does not contain a SIG(0) record.
+    Missing,
+
+    /// The SIG(0) record is not the last record of the message.
+    Position,
+
+    /// The SIG(0) record is invalid.
+    Invalid,
+
+    /// The signature was not created by the given key.
+    KeyMismatch,
+
+    /// The signature is not valid yet.
+    Premature,
+
+    /// The signature has expired.
+    Expired,
+
+    /// The signature does not match the message.
+    BadSig,
+}
+
+//--- Display and Error
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ValidationError::ParseError => "malformed message",
+            ValidationError::Missing => "missing SIG(0) record",
+            ValidationError::Position => "SIG(0) record not last",
+            ValidationError::Invalid => "invalid SIG(0) record",
+            ValidationError::KeyMismatch => "SIG(0) key mismatch",
+            ValidationError::Premature => "SIG(0) signature not yet valid",
+            ValidationError::Expired => "SIG(0) signature expired",
+            ValidationError::BadSig => "bad SIG(0) signature",
+        })
+    }
+}
+
+impl error::Error for ValidationError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "unstable-sign")]
+mod test {
+    use super::*;
+    use crate::base::message_builder::MessageBuilder;
+    use crate::sign::{common, GenerateParams};
+    use core::str::FromStr;
+
+    type TestKey = SigningKey<Vec<u8>, common::KeyPair>;
+
+    fn key() -> TestKey {
+        let (sec, public) =
+            common::generate(GenerateParams::Ed25519).unwrap();
+        let pair = common::KeyPair::from_bytes(&sec, &public).unwrap();
+        SigningKey::new(
+            Name::from_str("client.example.com").unwrap(),
+            512,
+            pair,
+        )
+    }
+
+    fn signed(
+        key: &TestKey,
+        request: Option<&[u8]>,
+        id: u16,
+    ) -> Message<Vec<u8>> {
+        let mut msg = MessageBuilder::new_vec();
+        msg.header_mut().set_id(id);
+        let mut msg = msg.question();
+        msg.push((Name::vec_from_str("example.com").unwrap(), Rtype::A))
+            .unwrap();
+        let mut msg = msg.additional();
+        sign(&mut msg, key, request, 1000.into(), 2000.into()).unwrap();
+        msg.into_message()
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        let key = key();
+        let public = key.public_key();
+        let msg = signed(&key, None, 12);
+        assert_eq!(msg.header_counts().arcount(), 1);
+        assert_eq!(verify(&msg, None, &public, 1500.into()), Ok(()));
+        assert_eq!(
+            verify(&msg, None, &public, 999.into()),
+            Err(ValidationError::Premature)
+        );
+        assert_eq!(
+            verify(&msg, None, &public, 2001.into()),
+            Err(ValidationError::Expired)
+        );
+
+        let mut tampered = msg.into_octets();
+        tampered[1] = 13;
+        let tampered = Message::from_octets(tampered).unwrap();
+        assert_eq!(
+            verify(&tampered, None, &public, 1500.into()),
+            Err(ValidationError::BadSig)
+        );
+
+        // A key with a different key tag. Random keys can share a tag, so
+        // generate new ones until the tags differ.
+        let other = loop {
+            let other = self::key();
+            if other.public_key().key_tag() != public.key_tag() {
+                break other;
+            }
+        };
+        assert_eq!(
+            verify(&tampered, None, &other.public_key(), 1500.into()),
+            Err(ValidationError::KeyMismatch)
+        );
+    }
+
+    #[test]
+    fn sign_and_verify_response() {
+        let key = key();
+        let public = key.public_key();
+        let request = signed(&key, None, 12);
+        let response = signed(&key, Some(request.as_slice()), 12);
+        assert_eq!(
+            verify(&response, Some(request.as_slice()), &public, 1500.into()),
+            Ok(())
+        );
+        assert_eq!(
+            verify(&response, None, &public, 1500.into()),
+            Err(ValidationError::BadSig)
+        );
+    }
+
+    #[test]
+    fn verify_unsigned() {
+        let key = key();
+        let msg = MessageBuilder::new_vec().into_message();
+        assert_eq!(
+            verify(&msg, None, &key.public_key(), 1500.into()),
+            Err(ValidationError::Missing)
+        );
+    }
+}
@@ -0,0 +1,316 @@
+//! Reading and writing key files.
+//!
+//! BIND and most other DNSSEC tools store a key in a pair of files sharing
+//! the base name `K<zone>+<alg>+<tag>`, where `<zone>` is the owner name of
+//! the key including the final dot, `<alg>` the algorithm number padded to
+//! three digits, and `<tag>` the key tag padded to five digits.  The public
+//! key is kept in the `.key` file as a DNSKEY record (see [`Key`]), the
+//! secret key in the `.private` file (see [`SecretKeyBytes`]).
+//!
+//! New keys can be generated with [`common::generate()`] or the `generate()`
+//! function of one of the other cryptographic backends.
+//!
+//! ```no_run
+//! # use domain::base::Name;
+//! # use domain::sign::{common, keyfile, GenerateParams};
+//! # use domain::validate::Key;
+//! // Generate a new key signing key and store it in the current directory.
+//! let (secret, public) = common::generate(GenerateParams::Ed25519).unwrap();
+//! let owner: Name<Vec<u8>> = "example.org.".parse().unwrap();
+//! let public = Key::new(owner, 257, public);
+//! let base = keyfile::write(".", &public, &secret).unwrap();
+//!
+//! // Load it again.
+//! let (public, secret) = keyfile::read(&base).unwrap();
+//! ```
+//!
+//! [`common::generate()`]: super::common::generate()
+
+use core::fmt;
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::string::String;
+use std::vec::Vec;
+
+use crate::validate::{Key, ParseDnskeyTextError, RsaPublicKeyBytes};
+
+use super::{BindFormatError, PublicKeyBytes, SecretKeyBytes};
+
+//------------ Functions -----------------------------------------------------
+
+/// Returns the base name of the key files for a key.
+///
+/// The name has the form `K<zone>+<alg>+<tag>` and doesn’t include the
+/// `.key` or `.private` extension.
+pub fn base_name<Octs: AsRef<[u8]>>(key: &Key<Octs>) -> String {
+    format!(
+        "K{}+{:03}+{:05}",
+        key.owner().fmt_with_dot(),
+        key.algorithm().to_int(),
+        key.key_tag()
+    )
+}
+
+/// Reads a key from its key files.
+///
+/// The argument is the path of the key files without extension, as
+/// returned by [`write`]. The public key is read from the file with the
+/// extension `.key` appended, the secret key from the file with the
+/// extension `.private` appended.
+///
+/// Returns an error if either file can’t be read or parsed, or if the
+/// two keys don’t use the same algorithm.
+pub fn read(
+    base: impl AsRef<Path>,
+) -> Result<(Key<Vec<u8>>, SecretKeyBytes), KeyFileError> {
+    let base = base.as_ref();
+    let public = fs::read_to_string(with_extension(base, ".key"))?;
+    let public = Key::parse_from_bind(&public)?;
+    let secret = fs::read_to_string(with_extension(base, ".private"))?;
+    let secret = SecretKeyBytes::parse_from_bind(&secret)?;
+    if !keys_match(public.raw_public_key(), &secret) {
+        return Err(KeyFileError::KeyMismatch);
+    }
+    Ok((public, secret))
+}
+
+/// Writes a key into key files in the given directory.
+///
+/// The files are named as described by [`base_name`]. On success, returns
+/// the path of the key files without extension, which can be passed to
+/// [`read`].
+///
+/// Existing files are never overwritten and an error is returned instead.
+/// On Unix systems, the `.private` file is only readable and writable by
+/// its owner.
+pub fn write<Octs: AsRef<[u8]>>(
+    dir: impl AsRef<Path>,
+    public: &Key<Octs>,
+    secret: &SecretKeyBytes,
+) -> Result<PathBuf, KeyFileError> {
+    if !keys_match(public.raw_public_key(), secret) {
+        return Err(KeyFileError::KeyMismatch);
+    }
+
+    let base = dir.as_ref().join(base_name(public));
+    let private = with_extension(&base, ".private");
+    write_new(&private, 0o600, |file| {
+        write!(file, "{}", secret.display_as_bind())
+    })?;
+    let res = write_new(&with_extension(&base, ".key"), 0o644, |file| {
+        write!(file, "{}", public.display_as_bind())
+    });
+    if let Err(err) = res {
+        // Don’t leave a secret key without its public key behind.
+        let _ = fs::remove_file(&private);
+        return Err(err.into());
+    }
+    Ok(base)
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Appends an extension to a path.
+///
+/// The base name of key files contains dots, so we can’t use
+/// `Path::with_extension`.
+fn with_extension(base: &Path, ext: &str) -> PathBuf {
+    let mut path = OsString::from(base);
+    path.push(ext);
+    path.into()
+}
+
+/// Creates a new file which must not exist yet.
+fn create_new(path: &Path, _mode: u32) -> Result<File, io::Error> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(_mode);
+    }
+    options.open(path)
+}
+
+/// Creates a new file which must not exist yet and writes to it.
+///
+/// If `op` fails, the file is removed again so that a later attempt isn’t
+/// refused because of a partial file.
+fn write_new(
+    path: &Path,
+    mode: u32,
+    op: impl FnOnce(&mut File) -> Result<(), io::Error>,
+) -> Result<(), io::Error> {
+    let mut file = create_new(path, mode)?;
+    if let Err(err) = op(&mut file) {
+        drop(file);
+        let _ = fs::remove_file(path);
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// Returns whether a public and a secret key belong together.
+///
+/// Only RSA secret keys contain their public key, so for the other
+/// algorithms this only checks that the algorithms are the same.
+fn keys_match(public: &PublicKeyBytes, secret: &SecretKeyBytes) -> bool {
+    match (public, secret) {
+        (PublicKeyBytes::RsaSha256(p), SecretKeyBytes::RsaSha256(s)) => {
+            *p == RsaPublicKeyBytes::from(s)
+        }
+        (public, secret) => public.algorithm() == secret.algorithm(),
+    }
+}
+
+//============ Error Types ===================================================
+
+//------------ KeyFileError --------------------------------------------------
+
+/// An error happened while reading or writing key files.
+#[derive(Debug)]
+pub enum KeyFileError {
+    /// Reading or writing a file failed.
+    Io(io::Error),
+
+    /// The public key file could not be parsed.
+    PublicKey(ParseDnskeyTextError),
+
+    /// The secret key file could not be parsed.
+    SecretKey(BindFormatError),
+
+    /// The public and secret key don’t belong together.
+    KeyMismatch,
+}
+
+//--- From
+
+impl From<io::Error> for KeyFileError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ParseDnskeyTextError> for KeyFileError {
+    fn from(err: ParseDnskeyTextError) -> Self {
+        Self::PublicKey(err)
+    }
+}
+
+impl From<BindFormatError> for KeyFileError {
+    fn from(err: BindFormatError) -> Self {
+        Self::SecretKey(err)
+    }
+}
+
+//--- Display, Error
+
+impl fmt::Display for KeyFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => err.fmt(f),
+            Self::PublicKey(err) => write!(f, "invalid public key: {}", err),
+            Self::SecretKey(err) => write!(f, "invalid secret key: {}", err),
+            Self::KeyMismatch => {
+                f.write_str("public and secret key don’t match")
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeyFileError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod tests {
+    use std::string::ToString;
+
+    use super::*;
+    use crate::sign::{common, GenerateParams};
+
+    #[test]
+    fn read_test_keys() {
+        for name in [
+            "Ktest.+008+60616",
+            "Ktest.+013+42253",
+            "Ktest.+014+33566",
+            "Ktest.+015+56037",
+            "Ktest.+016+07379",
+        ] {
+            let path = Path::new("test-data/dnssec-keys").join(name);
+            let (public, secret) = read(&path).unwrap();
+            assert_eq!(base_name(&public), name);
+            assert_eq!(public.algorithm(), secret.algorithm());
+        }
+    }
+
+    #[test]
+    fn write_and_read() {
+        let dir = std::env::temp_dir()
+            .join(format!("domain-keyfile-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let (secret, public) =
+            common::generate(GenerateParams::EcdsaP256Sha256).unwrap();
+        let owner = "example.org.".parse().unwrap();
+        let public = Key::<Vec<u8>>::new(owner, 257, public);
+        let base = write(&dir, &public, &secret).unwrap();
+        assert_eq!(
+            base.file_name().unwrap().to_str().unwrap(),
+            base_name(&public)
+        );
+        assert!(base_name(&public).starts_with("Kexample.org.+013+"));
+
+        let (read_public, read_secret) = read(&base).unwrap();
+        assert_eq!(read_public, public);
+        assert_eq!(
+            read_secret.display_as_bind().to_string(),
+            secret.display_as_bind().to_string()
+        );
+
+        // Existing files aren’t overwritten.
+        assert!(matches!(
+            write(&dir, &public, &secret),
+            Err(KeyFileError::Io(_))
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn failed_write_removes_file() {
+        let dir = std::env::temp_dir()
+            .join(format!("domain-keyfile-failed-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Kexample.org.+013+00000.private");
+
+        assert!(write_new(&path, 0o600, |file| {
+            file.write_all(b"partial")?;
+            Err(io::Error::other("write failed"))
+        })
+        .is_err());
+        assert!(!path.exists());
+
+        // A retry isn’t refused because of the failed attempt.
+        write_new(&path, 0o600, |file| file.write_all(b"complete")).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "complete");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn mismatched_keys() {
+        let (secret, _) = common::generate(GenerateParams::Ed25519).unwrap();
+        let (_, public) =
+            common::generate(GenerateParams::EcdsaP256Sha256).unwrap();
+        let owner = "example.org.".parse().unwrap();
+        let public = Key::<Vec<u8>>::new(owner, 257, public);
+        assert!(matches!(
+            write(std::env::temp_dir(), &public, &secret),
+            Err(KeyFileError::KeyMismatch)
+        ));
+    }
+}
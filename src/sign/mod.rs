@@ -15,11 +15,14 @@
 //!
 //! # Example Usage
 //!
-//! At the moment, only "low-level" signing is supported.
+//! RRsets are signed with [`SigningKey::sign_rrset()`], which produces the
+//! RRSIG record covering the RRset.  Arbitrary byte sequences can also be
+//! signed with the raw secret key.
 //!
 //! ```
 //! # use domain::sign::*;
-//! # use domain::base::Name;
+//! # use domain::base::{iana::Class, Name, Record, Ttl};
+//! # use domain::rdata::{A, Rrsig};
 //! // Generate a new Ed25519 key.
 //! let params = GenerateParams::Ed25519;
 //! let (sec_bytes, pub_bytes) = common::generate(params).unwrap();
@@ -30,12 +33,24 @@
 //! // Associate the key with important metadata.
 //! let owner: Name<Vec<u8>> = "www.example.org.".parse().unwrap();
 //! let flags = 257; // key signing key
-//! let key = SigningKey::new(owner, flags, key_pair);
+//! let key = SigningKey::new(owner.clone(), flags, key_pair);
 //!
 //! // Access the public key (with metadata).
 //! let pub_key = key.public_key();
 //! println!("{:?}", pub_key);
 //!
+//! // Sign an RRset of the zone.
+//! let rrset = [Record::new(
+//!     owner.clone(),
+//!     Class::IN,
+//!     Ttl::from_secs(3600),
+//!     A::from_octets(192, 0, 2, 1),
+//! )];
+//! let rrsig: Record<_, Rrsig<Vec<u8>, _>> = key
+//!     .sign_rrset(&rrset, &owner, 1_700_000_000.into(), 1_600_000_000.into())
+//!     .unwrap();
+//! println!("{}", rrsig);
+//!
 //! // Sign arbitrary byte sequences with the key.
 //! let sig = key.raw_secret_key().sign_raw(b"Hello, World!").unwrap();
 //! println!("{:?}", sig);
@@ -109,6 +124,9 @@
 //! used by a variety of tools for storing DNSSEC keys on disk.  See the
 //! type-level documentation for a specification of the format.
 //!
+//! The [`keyfile`] module reads and writes complete keys from and to the
+//! pair of `.key` and `.private` files used by BIND.
+//!
 //! # Key Sets and Key Lifetime
 //! The [`keyset`] module provides a way to keep track of the collection of
 //! keys that are used to sign a particular zone. In addition, the lifetime
@@ -119,20 +137,33 @@
 #![cfg_attr(docsrs, doc(cfg(feature = "unstable-sign")))]
 
 use core::fmt;
+use std::{boxed::Box, vec::Vec};
 
 use crate::{
-    base::{iana::SecAlg, Name},
-    validate,
+    base::{
+        cmp::CanonicalOrd,
+        iana::{Rtype, SecAlg},
+        name::ToName,
+        rdata::{ComposeRecordData, RecordData},
+        Name, Record,
+    },
+    rdata::{
+        dnssec::{ProtoRrsig, Timestamp},
+        Rrsig,
+    },
+    validate::{self, RrsigExt},
 };
 
 pub use crate::validate::{PublicKeyBytes, RsaPublicKeyBytes, Signature};
 
 mod bytes;
-pub use self::bytes::{RsaSecretKeyBytes, SecretKeyBytes};
+pub use self::bytes::{BindFormatError, RsaSecretKeyBytes, SecretKeyBytes};
 
 pub mod common;
+pub mod keyfile;
 pub mod keyset;
 pub mod openssl;
+pub mod records;
 pub mod ring;
 
 //----------- SigningKey -----------------------------------------------------
@@ -256,7 +287,93 @@ impl<Octs, Inner: SignRaw> SigningKey<Octs, Inner> {
     }
 }
 
-// TODO: Conversion to and from key files
+//--- Signing
+
+impl<Octs: AsRef<[u8]>, Inner: SignRaw> SigningKey<Octs, Inner> {
+    /// Signs an RRset.
+    ///
+    /// Creates the RRSIG record covering the records in `rrset` which must
+    /// all have the same owner name, class, and record type. The records
+    /// don’t need to be in any particular order, they are put into
+    /// canonical form as described in [RFC 4034, section 6] before
+    /// signing.
+    ///
+    /// The signature is valid from `inception` until `expiration`. Its
+    /// signer name is `apex`, the apex of the zone containing the RRset,
+    /// which must also be the owner name of the key. The original TTL of
+    /// the signature as well as the TTL of the RRSIG record are taken from
+    /// the first record of the RRset.
+    ///
+    /// Note that it is up to the caller to decide which RRsets need to be
+    /// signed. In particular, only authoritative data and the DS and NSEC
+    /// RRsets at delegation points must be signed.
+    ///
+    /// [RFC 4034, section 6]: https://tools.ietf.org/html/rfc4034#section-6
+    pub fn sign_rrset<N, D, SigOcts, ApexName>(
+        &self,
+        rrset: &[Record<N, D>],
+        apex: &ApexName,
+        expiration: Timestamp,
+        inception: Timestamp,
+    ) -> Result<Record<N, Rrsig<SigOcts, ApexName>>, SignRrsetError>
+    where
+        N: ToName + Clone,
+        D: RecordData + CanonicalOrd + ComposeRecordData,
+        SigOcts: From<Box<[u8]>> + AsRef<[u8]>,
+        ApexName: ToName + Clone,
+    {
+        let first = rrset.first().ok_or(SignRrsetError::InvalidRrset)?;
+        let (owner, class, rtype) =
+            (first.owner(), first.class(), first.rtype());
+        if rtype == Rtype::RRSIG
+            || rrset.iter().any(|rr| {
+                !rr.owner().name_eq(owner)
+                    || rr.class() != class
+                    || rr.rtype() != rtype
+            })
+        {
+            return Err(SignRrsetError::InvalidRrset);
+        }
+        if !owner.ends_with(apex) {
+            return Err(SignRrsetError::OutOfZone);
+        }
+        if !self.owner.name_eq(apex) || !self.is_zone_signing_key() {
+            return Err(SignRrsetError::UnsuitableKey);
+        }
+
+        let rrsig = ProtoRrsig::new(
+            rtype,
+            self.algorithm(),
+            owner.rrsig_label_count(),
+            first.ttl(),
+            expiration,
+            inception,
+            self.public_key().key_tag(),
+            apex.clone(),
+        );
+
+        // The signed data consists of the RRSIG RDATA without the
+        // signature followed by the records in canonical form and order.
+        let mut records: Vec<_> = rrset.iter().collect();
+        let mut signed_data = Vec::new();
+        rrsig
+            .clone()
+            .into_rrsig(&[][..])
+            .expect("empty signature")
+            .signed_data(&mut signed_data, &mut records)
+            .expect("infallible");
+        let signature = self.inner.sign_raw(&signed_data)?;
+
+        Ok(Record::new(
+            owner.clone(),
+            class,
+            first.ttl(),
+            rrsig
+                .into_rrsig(SigOcts::from(signature.into()))
+                .expect("long signature"),
+        ))
+    }
+}
 
 //----------- SignRaw --------------------------------------------------------
 
@@ -419,3 +536,183 @@ impl fmt::Display for SignError {
 }
 
 impl std::error::Error for SignError {}
+
+//----------- SignRrsetError -------------------------------------------------
+
+/// An RRset could not be signed.
+#[derive(Clone, Debug)]
+pub enum SignRrsetError {
+    /// The records are empty, don’t form a single RRset, or are RRSIGs.
+    InvalidRrset,
+
+    /// The owner name of the RRset is not within the zone.
+    OutOfZone,
+
+    /// The key is not a zone key of the zone.
+    UnsuitableKey,
+
+    /// The cryptographic backend failed to create the signature.
+    Sign(SignError),
+}
+
+//--- From
+
+impl From<SignError> for SignRrsetError {
+    fn from(err: SignError) -> Self {
+        Self::Sign(err)
+    }
+}
+
+//--- Display, Error
+
+impl fmt::Display for SignRrsetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRrset => f.write_str("invalid RRset"),
+            Self::OutOfZone => f.write_str("RRset is not within the zone"),
+            Self::UnsuitableKey => f.write_str("key is not a zone key"),
+            Self::Sign(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for SignRrsetError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, vec::Vec};
+
+    use crate::{
+        base::{iana::Class, Ttl},
+        rdata::A,
+        validate::{validate_rrset, Key, ValidationStatus},
+    };
+
+    use super::*;
+
+    const KEYS: &[(SecAlg, u16)] = &[
+        (SecAlg::RSASHA256, 60616),
+        (SecAlg::ECDSAP256SHA256, 42253),
+        (SecAlg::ECDSAP384SHA384, 33566),
+        (SecAlg::ED25519, 56037),
+    ];
+
+    fn signing_key(
+        algorithm: SecAlg,
+        key_tag: u16,
+    ) -> SigningKey<Vec<u8>, common::KeyPair> {
+        let name = format!("test.+{:03}+{:05}", algorithm.to_int(), key_tag);
+
+        let path = format!("test-data/dnssec-keys/K{}.private", name);
+        let data = std::fs::read_to_string(path).unwrap();
+        let sec_key = SecretKeyBytes::parse_from_bind(&data).unwrap();
+
+        let path = format!("test-data/dnssec-keys/K{}.key", name);
+        let data = std::fs::read_to_string(path).unwrap();
+        let pub_key = Key::<Vec<u8>>::parse_from_bind(&data).unwrap();
+
+        let key_pair =
+            common::KeyPair::from_bytes(&sec_key, pub_key.raw_public_key())
+                .unwrap();
+        SigningKey::new(pub_key.owner().clone(), pub_key.flags(), key_pair)
+    }
+
+    fn rrset(owner: &str) -> Vec<Record<Name<Vec<u8>>, A>> {
+        let owner = Name::from_str(owner).unwrap();
+        ["192.0.2.2", "192.0.2.1"]
+            .into_iter()
+            .map(|addr| {
+                Record::new(
+                    owner.clone(),
+                    Class::IN,
+                    Ttl::from_secs(3600),
+                    A::from_str(addr).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sign_rrset() {
+        let apex = Name::<Vec<u8>>::from_str("test.").unwrap();
+        let (expiration, inception) = (1_700_000_000.into(), 1_600_000_000);
+        for &(algorithm, key_tag) in KEYS {
+            let key = signing_key(algorithm, key_tag);
+            let rrset = rrset("www.test.");
+            let rrsig: Record<_, Rrsig<Vec<u8>, _>> = key
+                .sign_rrset(&rrset, &apex, expiration, inception.into())
+                .unwrap();
+
+            assert_eq!(rrsig.owner(), rrset[0].owner());
+            assert_eq!(rrsig.ttl(), Ttl::from_secs(3600));
+            assert_eq!(rrsig.data().type_covered(), Rtype::A);
+            assert_eq!(rrsig.data().algorithm(), algorithm);
+            assert_eq!(rrsig.data().labels(), 2);
+            assert_eq!(rrsig.data().key_tag(), key_tag);
+            assert_eq!(rrsig.data().signer_name(), &apex);
+
+            // The signature must validate.
+            let dnskey = key.public_key().to_dnskey();
+            let status = validate_rrset(
+                &rrset,
+                &[rrsig],
+                &apex,
+                &[dnskey],
+                Timestamp::from(1_650_000_000),
+                &validate::ring::Verifier,
+            );
+            assert_eq!(status, ValidationStatus::Secure);
+        }
+    }
+
+    #[test]
+    fn sign_rrset_wildcard() {
+        let apex = Name::<Vec<u8>>::from_str("test.").unwrap();
+        let key = signing_key(SecAlg::ED25519, 56037);
+        let rrsig: Record<_, Rrsig<Vec<u8>, _>> = key
+            .sign_rrset(
+                &rrset("*.test."),
+                &apex,
+                1_700_000_000.into(),
+                1_600_000_000.into(),
+            )
+            .unwrap();
+        assert_eq!(rrsig.data().labels(), 1);
+    }
+
+    #[test]
+    fn sign_rrset_errors() {
+        let apex = Name::<Vec<u8>>::from_str("test.").unwrap();
+        let key = signing_key(SecAlg::ED25519, 56037);
+        let sign = |rrset: &[Record<Name<Vec<u8>>, A>], apex| {
+            key.sign_rrset::<_, _, Vec<u8>, _>(
+                rrset,
+                apex,
+                1_700_000_000.into(),
+                1_600_000_000.into(),
+            )
+        };
+
+        assert!(matches!(
+            sign(&[], &apex),
+            Err(SignRrsetError::InvalidRrset)
+        ));
+        let mut mixed = rrset("www.test.");
+        mixed.extend(rrset("mail.test."));
+        assert!(matches!(
+            sign(&mixed, &apex),
+            Err(SignRrsetError::InvalidRrset)
+        ));
+        assert!(matches!(
+            sign(&rrset("www.example."), &apex),
+            Err(SignRrsetError::OutOfZone)
+        ));
+        let child = Name::from_str("www.test.").unwrap();
+        assert!(matches!(
+            sign(&rrset("www.test."), &child),
+            Err(SignRrsetError::UnsuitableKey)
+        ));
+    }
+}
@@ -1,21 +1,49 @@
-//! Actual signing.
+//! Signing entire zones.
+//!
+//! Signing a zone means adding DNSKEY records for the keys of the zone,
+//! the NSEC or NSEC3 chain that allows proving the non-existence of names
+//! and record types, and RRSIG records for all authoritative RRsets. The
+//! [`sign_zone`] function does all of this in one go, using the keys and
+//! signature validity described by a [`SigningConfig`].
+//!
+//! The individual steps are available on [`SortedRecords`], which keeps
+//! records in the canonical order they need to be processed in.
+
+use core::cmp::Ordering;
+use std::boxed::Box;
+use std::collections::HashSet;
+use std::vec::Vec;
+use std::{fmt, io, slice};
+
+use octseq::builder::{EmptyBuilder, FromBuilder, OctetsBuilder, Truncate};
 
-use super::key::SigningKey;
 use crate::base::cmp::CanonicalOrd;
-use crate::base::iana::{Class, Rtype};
-use crate::base::name::ToName;
+use crate::base::iana::{Class, DigestAlg, Nsec3HashAlg, Rtype};
+use crate::base::name::{Name, NameBuilder, ToName};
 use crate::base::rdata::{ComposeRecordData, RecordData};
 use crate::base::record::Record;
 use crate::base::Ttl;
-use crate::rdata::dnssec::{ProtoRrsig, RtypeBitmap, Timestamp};
-use crate::rdata::{Dnskey, Ds, Nsec, Rrsig};
-use octseq::builder::{EmptyBuilder, FromBuilder, OctetsBuilder, Truncate};
-use std::vec::Vec;
-use std::{fmt, io, slice};
+use crate::rdata::dnssec::{RtypeBitmap, Timestamp};
+use crate::rdata::nsec3::{Nsec3Salt, OwnerHash};
+use crate::rdata::{
+    Dnskey, Ds, Nsec, Nsec3, Nsec3param, Rrsig, ZoneRecordData,
+};
+use crate::utils::base32;
+use crate::validate::{DigestError, Nsec3HashError, Nsec3paramExt};
+
+use super::{SignRaw, SignRrsetError, SigningKey};
 
 //------------ SortedRecords -------------------------------------------------
 
 /// A collection of resource records sorted for signing.
+///
+/// The records are kept in the canonical order defined in [RFC 4034,
+/// section 6] and duplicates are dropped when inserting. Iterating over the
+/// collection, either record by record via [`iter`][Self::iter] or grouped
+/// via [`families`][Self::families] and [`rrsets`][Self::rrsets], visits the
+/// records in this order.
+///
+/// [RFC 4034, section 6]: https://tools.ietf.org/html/rfc4034#section-6
 #[derive(Clone)]
 pub struct SortedRecords<N, D> {
     records: Vec<Record<N, D>>,
@@ -45,15 +73,35 @@ impl<N, D> SortedRecords<N, D> {
         }
     }
 
-    pub fn families(&self) -> RecordsIter<N, D> {
+    /// Returns an iterator over the records in canonical order.
+    pub fn iter(&self) -> slice::Iter<'_, Record<N, D>> {
+        self.records.iter()
+    }
+
+    /// Returns the number of records in the collection.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns whether the collection is empty.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Converts the collection into a vec of records in canonical order.
+    pub fn into_vec(self) -> Vec<Record<N, D>> {
+        self.records
+    }
+
+    pub fn families(&self) -> RecordsIter<'_, N, D> {
         RecordsIter::new(&self.records)
     }
 
-    pub fn rrsets(&self) -> RrsetIter<N, D> {
+    pub fn rrsets(&self) -> RrsetIter<'_, N, D> {
         RrsetIter::new(&self.records)
     }
 
-    pub fn find_soa(&self) -> Option<Rrset<N, D>>
+    pub fn find_soa(&self) -> Option<Rrset<'_, N, D>>
     where
         N: ToName,
         D: RecordData,
@@ -61,23 +109,54 @@ impl<N, D> SortedRecords<N, D> {
         self.rrsets().find(|rrset| rrset.rtype() == Rtype::SOA)
     }
 
+    /// Signs the authoritative RRsets of a zone with a key.
+    ///
+    /// The zone is identified by its `apex`. Records before the apex or
+    /// out of the zone are ignored. Of the records at or below a zone cut,
+    /// only the DS and NSEC RRsets at the cut are signed. Existing RRSIG
+    /// records are never signed.
+    ///
+    /// Returns the RRSIG records in the order of the RRsets they cover.
     #[allow(clippy::type_complexity)]
-    pub fn sign<Octets, Key, ApexName>(
+    pub fn sign<Octs, Inner, SigOcts, ApexName>(
         &self,
         apex: &FamilyName<ApexName>,
         expiration: Timestamp,
         inception: Timestamp,
-        key: Key,
-    ) -> Result<Vec<Record<N, Rrsig<Octets, ApexName>>>, Key::Error>
+        key: &SigningKey<Octs, Inner>,
+    ) -> Result<Vec<Record<N, Rrsig<SigOcts, ApexName>>>, SignRrsetError>
     where
         N: ToName + Clone,
-        D: RecordData + ComposeRecordData,
-        Key: SigningKey,
-        Octets: From<Key::Signature> + AsRef<[u8]>,
+        D: RecordData + CanonicalOrd + ComposeRecordData,
+        Octs: AsRef<[u8]>,
+        Inner: SignRaw,
+        SigOcts: From<Box<[u8]>> + AsRef<[u8]>,
         ApexName: ToName + Clone,
+    {
+        self.signable_rrsets(apex)
+            .into_iter()
+            .map(|rrset| {
+                key.sign_rrset(
+                    rrset.slice,
+                    apex.owner(),
+                    expiration,
+                    inception,
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the RRsets of a zone that need to be signed.
+    fn signable_rrsets<ApexName>(
+        &self,
+        apex: &FamilyName<ApexName>,
+    ) -> Vec<Rrset<'_, N, D>>
+    where
+        N: ToName + Clone,
+        D: RecordData,
+        ApexName: ToName,
     {
         let mut res = Vec::new();
-        let mut buf = Vec::new();
 
         // The owner name of a zone cut if we currently are at or below one.
         let mut cut: Option<FamilyName<N>> = None;
@@ -102,14 +181,11 @@ impl<N, D> SortedRecords<N, D> {
                 }
             }
 
-            // A copy of the family name. We’ll need it later.
-            let name = family.family_name().cloned();
-
             // If this family is the parent side of a zone cut, we keep the
             // family name for later. This also means below that if
             // `cut.is_some()` we are at the parent side of a zone.
             cut = if family.is_zone_cut(apex) {
-                Some(name.clone())
+                Some(family.family_name().cloned())
             } else {
                 None
             };
@@ -130,36 +206,10 @@ impl<N, D> SortedRecords<N, D> {
                         continue;
                     }
                 }
-
-                // Create the signature.
-                buf.clear();
-                let rrsig = ProtoRrsig::new(
-                    rrset.rtype(),
-                    key.algorithm()?,
-                    name.owner().rrsig_label_count(),
-                    rrset.ttl(),
-                    expiration,
-                    inception,
-                    key.key_tag()?,
-                    apex.owner().clone(),
-                );
-                rrsig.compose_canonical(&mut buf).unwrap();
-                for record in rrset.iter() {
-                    record.compose_canonical(&mut buf).unwrap();
-                }
-
-                // Create and push the RRSIG record.
-                res.push(Record::new(
-                    name.owner().clone(),
-                    name.class(),
-                    rrset.ttl(),
-                    rrsig
-                        .into_rrsig(key.sign(&buf)?.into())
-                        .expect("long signature"),
-                ));
+                res.push(rrset);
             }
         }
-        Ok(res)
+        res
     }
 
     pub fn nsecs<Octets, ApexName>(
@@ -247,9 +297,162 @@ impl<N, D> SortedRecords<N, D> {
         res
     }
 
+    /// Generates the NSEC3 chain of a zone.
+    ///
+    /// The NSEC3 records are created as described in [RFC 5155, section
+    /// 7.1] using the hash algorithm, iterations, and salt of `params`.
+    /// There is a record for each authoritative name, each delegation, and
+    /// each empty non-terminal. If `opt_out` is set, delegations without a
+    /// DS record as well as the empty non-terminals only leading to them
+    /// are left out and all records have the Opt-Out flag set.
+    ///
+    /// If an NSEC3PARAM record is published, it needs to be part of the
+    /// records already so that it is included in the type bitmap of the
+    /// apex.
+    ///
+    /// Returns the NSEC3 records ordered by their hashed owner names or an
+    /// error if hashing fails or two names of the zone have the same hash.
+    ///
+    /// [RFC 5155, section 7.1]: https://tools.ietf.org/html/rfc5155#section-7.1
+    #[allow(clippy::type_complexity)]
+    pub fn nsec3s<Octets, ApexName>(
+        &self,
+        apex: &FamilyName<ApexName>,
+        ttl: Ttl,
+        params: &Nsec3param<Octets>,
+        opt_out: bool,
+    ) -> Result<Vec<Record<N, Nsec3<Octets>>>, SignZoneError>
+    where
+        N: ToName + Clone + From<Name<Octets>>,
+        D: RecordData,
+        Octets: AsRef<[u8]> + Clone + From<Box<[u8]>> + FromBuilder,
+        Octets::Builder: EmptyBuilder + Truncate + AsRef<[u8]> + AsMut<[u8]>,
+        <Octets::Builder as OctetsBuilder>::AppendError: fmt::Debug,
+        ApexName: ToName,
+    {
+        // The original owner names and their type bitmaps. Empty
+        // non-terminals have an empty bitmap.
+        let mut names = Vec::new();
+        let mut seen = HashSet::new();
+
+        // The owner name of a zone cut if we currently are at or below one.
+        let mut cut: Option<FamilyName<N>> = None;
+
+        let mut families = self.families();
+
+        // Since the records are ordered, the first family is the apex --
+        // we can skip everything before that.
+        families.skip_before(apex);
+
+        for family in families {
+            // If the owner is out of zone, we have moved out of our zone and
+            // are done.
+            if !family.is_in_zone(apex) {
+                break;
+            }
+
+            // If the family is below a zone cut, we must ignore it.
+            if let Some(ref cut) = cut {
+                if family.owner().ends_with(cut.owner()) {
+                    continue;
+                }
+            }
+
+            // If this family is the parent side of a zone cut, we keep the
+            // family name for later. This also means below that if
+            // `cut.is_some()` we are at the parent side of a zone.
+            cut = if family.is_zone_cut(apex) {
+                Some(family.family_name().cloned())
+            } else {
+                None
+            };
+
+            // Delegations without a DS record are insecure. They aren’t
+            // signed and, with opt-out, don’t get an NSEC3 record.
+            let insecure = cut.is_some()
+                && !family.rrsets().any(|rrset| rrset.rtype() == Rtype::DS);
+            if insecure && opt_out {
+                continue;
+            }
+
+            // Since ancestors are ordered before their descendants, any
+            // ancestor we haven’t seen yet is an empty non-terminal.
+            let owner = family.owner().to_vec();
+            let mut parent = owner.parent().map(|name| name.to_vec());
+            while let Some(name) = parent {
+                if name.name_eq(apex.owner())
+                    || !name.ends_with(apex.owner())
+                    || seen.contains(&name)
+                {
+                    break;
+                }
+                parent = name.parent().map(|name| name.to_vec());
+                seen.insert(name.clone());
+                names.push((
+                    name,
+                    RtypeBitmap::<Octets>::builder().finalize(),
+                ));
+            }
+
+            let mut bitmap = RtypeBitmap::<Octets>::builder();
+            if !insecure {
+                bitmap.add(Rtype::RRSIG).unwrap();
+            }
+            for rrset in family.rrsets() {
+                bitmap.add(rrset.rtype()).unwrap()
+            }
+            seen.insert(owner.clone());
+            names.push((owner, bitmap.finalize()));
+        }
+
+        let mut hashes = Vec::with_capacity(names.len());
+        for (name, bitmap) in names {
+            let hash = params.hash_name(&name)?;
+            let label = base32::encode_string_hex(&hash).to_ascii_lowercase();
+            let mut builder = NameBuilder::new_vec();
+            builder
+                .append_label(label.as_bytes())
+                .map_err(|_| Nsec3HashError::LongName)?;
+            let owner = builder
+                .append_origin(apex.owner())
+                .map_err(|_| Nsec3HashError::LongName)?;
+            hashes.push((hash, owner, bitmap));
+        }
+        hashes
+            .sort_by(|left, right| left.0.as_slice().cmp(right.0.as_slice()));
+        if hashes.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+            return Err(SignZoneError::Nsec3HashCollision);
+        }
+
+        let mut res = Vec::with_capacity(hashes.len());
+        for (idx, (_, owner, bitmap)) in hashes.iter().enumerate() {
+            let next = &hashes[(idx + 1) % hashes.len()].0;
+            let owner =
+                Name::from_octets(Octets::from(owner.as_slice().into()))
+                    .expect("valid name");
+            res.push(Record::new(
+                owner.into(),
+                apex.class(),
+                ttl,
+                Nsec3::new(
+                    params.hash_algorithm(),
+                    u8::from(opt_out),
+                    params.iterations(),
+                    params.salt().clone(),
+                    OwnerHash::from_octets(Octets::from(
+                        next.as_slice().into(),
+                    ))
+                    .expect("short hash"),
+                    bitmap.clone(),
+                ),
+            ));
+        }
+        Ok(res)
+    }
+
     pub fn write<W>(&self, target: &mut W) -> Result<(), io::Error>
     where
-        N: fmt::Display,
+        N: ToName + fmt::Display,
         D: RecordData + fmt::Display,
         W: io::Write,
     {
@@ -273,6 +476,7 @@ where
 {
     fn from(mut src: Vec<Record<N, D>>) -> Self {
         src.sort_by(CanonicalOrd::canonical_cmp);
+        src.dedup_by(|a, b| a.canonical_cmp(b) == Ordering::Equal);
         SortedRecords { records: src }
     }
 }
@@ -303,6 +507,24 @@ where
     }
 }
 
+impl<'a, N, D> IntoIterator for &'a SortedRecords<N, D> {
+    type Item = &'a Record<N, D>;
+    type IntoIter = slice::Iter<'a, Record<N, D>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.records.iter()
+    }
+}
+
+impl<N, D> IntoIterator for SortedRecords<N, D> {
+    type Item = Record<N, D>;
+    type IntoIter = std::vec::IntoIter<Record<N, D>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.records.into_iter()
+    }
+}
+
 //------------ Family --------------------------------------------------------
 
 /// A set of records with the same owner name and class.
@@ -382,32 +604,42 @@ impl<N> FamilyName<N> {
         Record::new(self.owner.clone(), self.class, ttl, data)
     }
 
-    pub fn dnskey<K: SigningKey, Octets: From<K::Octets>>(
+    /// Creates the DNSKEY record for a key.
+    pub fn dnskey<Octs, KeyOcts, Inner>(
         &self,
         ttl: Ttl,
-        key: K,
-    ) -> Result<Record<N, Dnskey<Octets>>, K::Error>
+        key: &SigningKey<KeyOcts, Inner>,
+    ) -> Record<N, Dnskey<Octs>>
     where
         N: Clone,
+        Octs: From<Box<[u8]>>,
+        KeyOcts: AsRef<[u8]>,
+        Inner: SignRaw,
     {
-        key.dnskey()
-            .map(|dnskey| self.clone().into_record(ttl, dnskey.convert()))
+        self.clone()
+            .into_record(ttl, key.public_key().to_dnskey().convert())
     }
 
-    pub fn ds<K: SigningKey>(
+    /// Creates the DS record for a key using the given digest algorithm.
+    #[allow(clippy::type_complexity)]
+    pub fn ds<KeyOcts, Inner>(
         &self,
         ttl: Ttl,
-        key: K,
-    ) -> Result<Record<N, Ds<K::Octets>>, K::Error>
+        key: &SigningKey<KeyOcts, Inner>,
+        algorithm: DigestAlg,
+    ) -> Result<Record<N, Ds<Box<[u8]>>>, DigestError>
     where
-        N: ToName + Clone,
+        N: Clone,
+        KeyOcts: AsRef<[u8]>,
+        Inner: SignRaw,
     {
-        key.ds(&self.owner)
+        key.public_key()
+            .digest(algorithm)
             .map(|ds| self.clone().into_record(ttl, ds))
     }
 }
 
-impl<'a, N: Clone> FamilyName<&'a N> {
+impl<N: Clone> FamilyName<&N> {
     pub fn cloned(&self) -> FamilyName<N> {
         FamilyName {
             owner: (*self.owner).clone(),
@@ -493,10 +725,9 @@ impl<'a, N, D> RecordsIter<'a, N, D> {
         N: ToName,
     {
         while let Some(first) = self.slice.first() {
-            if first.class() != apex.class() {
-                continue;
-            }
-            if apex == first || first.owner().ends_with(apex.owner()) {
+            if first.class() == apex.class()
+                && (apex == first || first.owner().ends_with(apex.owner()))
+            {
                 break;
             }
             self.slice = &self.slice[1..]
@@ -512,10 +743,7 @@ where
     type Item = Family<'a, N, D>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let first = match self.slice.first() {
-            Some(first) => first,
-            None => return None,
-        };
+        let first = self.slice.first()?;
         let mut end = 1;
         while let Some(record) = self.slice.get(end) {
             if !record.owner().name_eq(first.owner())
@@ -552,10 +780,7 @@ where
     type Item = Rrset<'a, N, D>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let first = match self.slice.first() {
-            Some(first) => first,
-            None => return None,
-        };
+        let first = self.slice.first()?;
         let mut end = 1;
         while let Some(record) = self.slice.get(end) {
             if !record.owner().name_eq(first.owner())
@@ -593,10 +818,7 @@ where
     type Item = Rrset<'a, N, D>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let first = match self.slice.first() {
-            Some(first) => first,
-            None => return None,
-        };
+        let first = self.slice.first()?;
         let mut end = 1;
         while let Some(record) = self.slice.get(end) {
             if record.rtype() != first.rtype() {
@@ -609,3 +831,787 @@ where
         Some(Rrset::new(res))
     }
 }
+
+//------------ KeyRole -------------------------------------------------------
+
+/// The role of a key when signing a zone.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyRole {
+    /// A key signing key.
+    ///
+    /// The key only signs the DNSKEY RRset at the apex of the zone.
+    Ksk,
+
+    /// A zone signing key.
+    ///
+    /// The key signs all RRsets except for the DNSKEY RRset at the apex.
+    Zsk,
+
+    /// A combined signing key.
+    ///
+    /// The key signs all RRsets.
+    Csk,
+}
+
+impl KeyRole {
+    /// Returns whether a key of this role signs the apex DNSKEY RRset.
+    fn signs_dnskeys(self) -> bool {
+        matches!(self, KeyRole::Ksk | KeyRole::Csk)
+    }
+
+    /// Returns whether a key of this role signs all other RRsets.
+    fn signs_zone(self) -> bool {
+        matches!(self, KeyRole::Zsk | KeyRole::Csk)
+    }
+}
+
+//------------ Nsec3Config ---------------------------------------------------
+
+/// The parameters for using NSEC3 when signing a zone.
+///
+/// The hash algorithm is always SHA-1, the only one defined. The default
+/// config uses no additional iterations, an empty salt, and no opt-out as
+/// recommended by [RFC 9276].
+///
+/// [RFC 9276]: https://tools.ietf.org/html/rfc9276
+#[derive(Clone)]
+pub struct Nsec3Config {
+    /// The number of additional iterations of the hash function.
+    iterations: u16,
+
+    /// The salt added to the names before hashing.
+    salt: Nsec3Salt<Vec<u8>>,
+
+    /// Whether insecure delegations are left out of the NSEC3 chain.
+    opt_out: bool,
+}
+
+impl Nsec3Config {
+    /// Creates a new config from the iterations and salt.
+    pub fn new(iterations: u16, salt: Nsec3Salt<Vec<u8>>) -> Self {
+        Nsec3Config {
+            iterations,
+            salt,
+            opt_out: false,
+        }
+    }
+
+    /// Sets whether insecure delegations are left out of the NSEC3 chain.
+    ///
+    /// Opt-out is defined in [RFC 5155, section 6]. It is useful for zones
+    /// with many delegations of which only few are signed.
+    ///
+    /// [RFC 5155, section 6]: https://tools.ietf.org/html/rfc5155#section-6
+    pub fn set_opt_out(&mut self, opt_out: bool) {
+        self.opt_out = opt_out
+    }
+
+    /// Returns the number of additional iterations of the hash function.
+    pub fn iterations(&self) -> u16 {
+        self.iterations
+    }
+
+    /// Returns the salt.
+    pub fn salt(&self) -> &Nsec3Salt<Vec<u8>> {
+        &self.salt
+    }
+
+    /// Returns whether insecure delegations are left out.
+    pub fn opt_out(&self) -> bool {
+        self.opt_out
+    }
+
+    /// Returns the NSEC3PARAM record data for the config.
+    fn nsec3param<Octs>(&self) -> Nsec3param<Octs>
+    where
+        Octs: AsRef<[u8]> + From<Box<[u8]>>,
+    {
+        Nsec3param::new(
+            Nsec3HashAlg::SHA1,
+            0,
+            self.iterations,
+            Nsec3Salt::from_octets(Octs::from(self.salt.as_slice().into()))
+                .expect("short salt"),
+        )
+    }
+}
+
+impl Default for Nsec3Config {
+    fn default() -> Self {
+        Self::new(0, Nsec3Salt::empty())
+    }
+}
+
+//------------ SigningConfig -------------------------------------------------
+
+/// The keys and signature validity to use when signing a zone.
+///
+/// The signatures created by [`sign_zone`] are valid from the inception
+/// time until the expiration time. To avoid all signatures of a zone
+/// expiring at the same moment and having to be renewed together, a
+/// jitter can be set via [`set_jitter`][Self::set_jitter]. Up to that many
+/// seconds are then subtracted from the expiration time of each signature.
+/// The amount subtracted is derived from the owner name and record type of
+/// the signed RRset, so signing the same zone again results in the same
+/// expiration times.
+///
+/// By default, the NSEC chain is used for authenticated denial of
+/// existence. NSEC3 can be used instead via [`set_nsec3`][Self::set_nsec3].
+pub struct SigningConfig<'a, Octs, Inner: SignRaw> {
+    /// The keys to sign with and their roles.
+    keys: Vec<(&'a SigningKey<Octs, Inner>, KeyRole)>,
+
+    /// The start of the validity period of the signatures.
+    inception: Timestamp,
+
+    /// The end of the validity period of the signatures.
+    expiration: Timestamp,
+
+    /// The maximum number of seconds to subtract from the expiration.
+    jitter: u32,
+
+    /// The NSEC3 parameters if NSEC3 is used instead of NSEC.
+    nsec3: Option<Nsec3Config>,
+}
+
+impl<'a, Octs, Inner: SignRaw> SigningConfig<'a, Octs, Inner> {
+    /// Creates a new config without any keys and without jitter.
+    pub fn new(inception: Timestamp, expiration: Timestamp) -> Self {
+        SigningConfig {
+            keys: Vec::new(),
+            inception,
+            expiration,
+            jitter: 0,
+            nsec3: None,
+        }
+    }
+
+    /// Adds a key to sign with in the given role.
+    pub fn add_key(
+        &mut self,
+        key: &'a SigningKey<Octs, Inner>,
+        role: KeyRole,
+    ) {
+        self.keys.push((key, role))
+    }
+
+    /// Sets the maximum number of seconds to subtract from the expiration.
+    ///
+    /// When signing, the jitter is limited to the length of the validity
+    /// period so that no signature expires before its inception.
+    pub fn set_jitter(&mut self, jitter: u32) {
+        self.jitter = jitter
+    }
+
+    /// Sets the NSEC3 parameters or switches back to NSEC if `None`.
+    pub fn set_nsec3(&mut self, nsec3: Option<Nsec3Config>) {
+        self.nsec3 = nsec3
+    }
+
+    /// Returns the keys and their roles.
+    pub fn keys(&self) -> &[(&'a SigningKey<Octs, Inner>, KeyRole)] {
+        &self.keys
+    }
+
+    /// Returns the start of the validity period of the signatures.
+    pub fn inception(&self) -> Timestamp {
+        self.inception
+    }
+
+    /// Returns the end of the validity period of the signatures.
+    pub fn expiration(&self) -> Timestamp {
+        self.expiration
+    }
+
+    /// Returns the maximum number of seconds subtracted from the expiration.
+    pub fn jitter(&self) -> u32 {
+        self.jitter
+    }
+
+    /// Returns the NSEC3 parameters if NSEC3 is used.
+    pub fn nsec3(&self) -> Option<&Nsec3Config> {
+        self.nsec3.as_ref()
+    }
+
+    /// Returns the expiration time for the signature of an RRset.
+    fn expiration_for(&self, owner: &impl ToName, rtype: Rtype) -> Timestamp {
+        if self.jitter == 0 {
+            return self.expiration;
+        }
+
+        // A 32 bit FNV-1a hash of the canonical owner name and the type.
+        let mut hash: u32 = 0x811c_9dc5;
+        let mut add = |octet: u8| {
+            hash ^= u32::from(octet);
+            hash = hash.wrapping_mul(0x0100_0193);
+        };
+        for label in owner.iter_labels() {
+            add(label.len() as u8);
+            label
+                .as_slice()
+                .iter()
+                .for_each(|octet| add(octet.to_ascii_lowercase()));
+        }
+        rtype.to_int().to_be_bytes().into_iter().for_each(add);
+
+        let window = self
+            .expiration
+            .into_int()
+            .wrapping_sub(self.inception.into_int());
+        let jitter = self.jitter.min(window);
+        let offset = u64::from(hash) % (u64::from(jitter) + 1);
+        Timestamp::from(
+            self.expiration.into_int().wrapping_sub(offset as u32),
+        )
+    }
+}
+
+//------------ sign_zone -----------------------------------------------------
+
+/// Signs a zone.
+///
+/// The records of the zone are sorted into canonical order. Any RRSIG,
+/// NSEC, NSEC3, and NSEC3PARAM records already present are dropped since
+/// they are replaced by the new signatures and the new NSEC or NSEC3 chain.
+/// The apex of the zone is the owner of the SOA record.
+///
+/// The DNSKEY records for all keys of `config` are added at the apex with
+/// the TTL of the SOA record. Next, the NSEC chain is generated or, if
+/// `config` has NSEC3 parameters, the NSEC3PARAM record is added at the
+/// apex and the NSEC3 chain is generated via
+/// [`SortedRecords::nsec3s`]. Following [RFC 9077], the TTL of these
+/// records is the lesser of the TTL of the SOA record and its minimum
+/// field. Finally, all authoritative RRsets
+/// as well as the DS and NSEC RRsets at delegations are signed: the apex
+/// DNSKEY RRset by all keys with the [`Ksk`][KeyRole::Ksk] or
+/// [`Csk`][KeyRole::Csk] roles and all other RRsets by all keys with the
+/// [`Zsk`][KeyRole::Zsk] or [`Csk`][KeyRole::Csk] roles.
+///
+/// Returns the signed zone or an error if the zone has no SOA record, if
+/// the config lacks keys for signing either kind of RRset, or if hashing or
+/// signing fails.
+///
+/// [RFC 9077]: https://tools.ietf.org/html/rfc9077
+#[allow(clippy::type_complexity)]
+pub fn sign_zone<N, Octs, KeyOcts, Inner>(
+    records: impl IntoIterator<Item = Record<N, ZoneRecordData<Octs, N>>>,
+    config: &SigningConfig<KeyOcts, Inner>,
+) -> Result<SortedRecords<N, ZoneRecordData<Octs, N>>, SignZoneError>
+where
+    N: ToName + CanonicalOrd + Clone + From<Name<Octs>>,
+    Octs: AsRef<[u8]> + Clone + From<Box<[u8]>> + FromBuilder,
+    Octs::Builder: EmptyBuilder + Truncate + AsRef<[u8]> + AsMut<[u8]>,
+    <Octs::Builder as OctetsBuilder>::AppendError: fmt::Debug,
+    KeyOcts: AsRef<[u8]>,
+    Inner: SignRaw,
+{
+    if !config.keys.iter().any(|(_, role)| role.signs_dnskeys())
+        || !config.keys.iter().any(|(_, role)| role.signs_zone())
+    {
+        return Err(SignZoneError::MissingKeys);
+    }
+
+    let mut records: SortedRecords<_, _> = records
+        .into_iter()
+        .filter(|record| {
+            !matches!(
+                record.rtype(),
+                Rtype::RRSIG | Rtype::NSEC | Rtype::NSEC3 | Rtype::NSEC3PARAM
+            )
+        })
+        .collect();
+
+    let (apex, soa_ttl, nsec_ttl) = {
+        let soa = records.find_soa().ok_or(SignZoneError::MissingSoa)?;
+        let minimum = match soa.first().data() {
+            ZoneRecordData::Soa(data) => data.minimum(),
+            _ => return Err(SignZoneError::InvalidSoa),
+        };
+        (
+            soa.family_name().cloned(),
+            soa.ttl(),
+            soa.ttl().min(minimum),
+        )
+    };
+
+    for (key, _) in &config.keys {
+        let _ = records.insert(Record::from_record(
+            apex.dnskey::<Octs, _, _>(soa_ttl, key),
+        ));
+    }
+
+    match config.nsec3 {
+        Some(ref nsec3) => {
+            let params = nsec3.nsec3param::<Octs>();
+            let _ = records.insert(Record::from_record(
+                apex.clone().into_record(nsec_ttl, params.clone()),
+            ));
+            let nsec3s =
+                records.nsec3s(&apex, nsec_ttl, &params, nsec3.opt_out)?;
+            records.extend(nsec3s.into_iter().map(Record::from_record));
+        }
+        None => {
+            let nsecs = records.nsecs::<Octs, _>(&apex, nsec_ttl);
+            records.extend(nsecs.into_iter().map(Record::from_record));
+        }
+    }
+
+    let mut rrsigs = Vec::new();
+    for rrset in records.signable_rrsets(&apex) {
+        let is_apex_dnskey =
+            rrset.rtype() == Rtype::DNSKEY && rrset.family_name() == apex;
+        let expiration = config.expiration_for(rrset.owner(), rrset.rtype());
+        for (key, role) in &config.keys {
+            let signs = if is_apex_dnskey {
+                role.signs_dnskeys()
+            } else {
+                role.signs_zone()
+            };
+            if !signs {
+                continue;
+            }
+            let rrsig: Record<N, Rrsig<Octs, N>> = key.sign_rrset(
+                rrset.slice,
+                apex.owner(),
+                expiration,
+                config.inception,
+            )?;
+            rrsigs.push(Record::from_record(rrsig));
+        }
+    }
+    records.extend(rrsigs);
+
+    Ok(records)
+}
+
+//------------ SignZoneError -------------------------------------------------
+
+/// A zone could not be signed.
+#[derive(Clone, Debug)]
+pub enum SignZoneError {
+    /// The zone has no SOA record.
+    MissingSoa,
+
+    /// The SOA record of the zone doesn’t contain SOA record data.
+    InvalidSoa,
+
+    /// There are no keys for signing the DNSKEY RRset or the other RRsets.
+    MissingKeys,
+
+    /// The NSEC3 hash of a name could not be calculated.
+    Nsec3Hash(Nsec3HashError),
+
+    /// Two names of the zone have the same NSEC3 hash.
+    Nsec3HashCollision,
+
+    /// Signing an RRset failed.
+    Sign(SignRrsetError),
+}
+
+//--- From
+
+impl From<Nsec3HashError> for SignZoneError {
+    fn from(err: Nsec3HashError) -> Self {
+        Self::Nsec3Hash(err)
+    }
+}
+
+impl From<SignRrsetError> for SignZoneError {
+    fn from(err: SignRrsetError) -> Self {
+        Self::Sign(err)
+    }
+}
+
+//--- Display, Error
+
+impl fmt::Display for SignZoneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSoa => f.write_str("zone has no SOA record"),
+            Self::InvalidSoa => f.write_str("invalid SOA record"),
+            Self::MissingKeys => {
+                f.write_str("missing key signing or zone signing key")
+            }
+            Self::Nsec3Hash(err) => err.fmt(f),
+            Self::Nsec3HashCollision => f.write_str("NSEC3 hash collision"),
+            Self::Sign(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for SignZoneError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::string::{String, ToString};
+
+    use super::*;
+    use crate::base::rdata::UnknownRecordData;
+    use crate::rdata::{Ns, Soa, A};
+    use crate::sign::{common, GenerateParams};
+    use crate::validate::{self, validate_rrset, ValidationStatus};
+
+    type Data = ZoneRecordData<Vec<u8>, Name<Vec<u8>>>;
+    type Signed = SortedRecords<Name<Vec<u8>>, Data>;
+
+    fn name(s: &str) -> Name<Vec<u8>> {
+        Name::from_str(s).unwrap()
+    }
+
+    fn record(
+        owner: &str,
+        data: impl Into<Data>,
+    ) -> Record<Name<Vec<u8>>, Data> {
+        Record::new(name(owner), Class::IN, Ttl::from_secs(3600), data.into())
+    }
+
+    fn zone() -> Vec<Record<Name<Vec<u8>>, Data>> {
+        vec![
+            record("www.test.", A::from_str("192.0.2.2").unwrap()),
+            record(
+                "test.",
+                Soa::new(
+                    name("ns.test."),
+                    name("admin.test."),
+                    1.into(),
+                    Ttl::from_secs(1800),
+                    Ttl::from_secs(900),
+                    Ttl::from_secs(604800),
+                    Ttl::from_secs(300),
+                ),
+            ),
+            record("test.", Ns::new(name("ns.test."))),
+            record("ns.test.", A::from_str("192.0.2.1").unwrap()),
+            record("sub.test.", Ns::new(name("ns.sub.test."))),
+            record("ns.sub.test.", A::from_str("192.0.2.3").unwrap()),
+        ]
+    }
+
+    fn key(flags: u16) -> SigningKey<Vec<u8>, common::KeyPair> {
+        let (sec, public) =
+            common::generate(GenerateParams::Ed25519).unwrap();
+        let pair = common::KeyPair::from_bytes(&sec, &public).unwrap();
+        SigningKey::new(name("test."), flags, pair)
+    }
+
+    /// Checks that all RRsets that need to be are signed by the right key.
+    ///
+    /// Returns the expiration times of the signatures.
+    fn check_signatures(
+        signed: &Signed,
+        ksk: &SigningKey<Vec<u8>, common::KeyPair>,
+        zsk: &SigningKey<Vec<u8>, common::KeyPair>,
+        unsigned: &[(&str, Rtype)],
+    ) -> Vec<u32> {
+        let apex = name("test.");
+        let dnskeys: Vec<_> = signed
+            .iter()
+            .filter_map(|record| match record.data() {
+                ZoneRecordData::Dnskey(dnskey) => Some(dnskey.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(dnskeys.len(), 2);
+
+        let mut expirations = Vec::new();
+        for rrset in signed.rrsets() {
+            if rrset.rtype() == Rtype::RRSIG {
+                continue;
+            }
+            let rrsigs: Vec<_> = signed
+                .iter()
+                .filter_map(|record| match record.data() {
+                    ZoneRecordData::Rrsig(rrsig)
+                        if record.owner() == rrset.owner()
+                            && rrsig.type_covered() == rrset.rtype() =>
+                    {
+                        Some(Record::new(
+                            record.owner().clone(),
+                            record.class(),
+                            record.ttl(),
+                            rrsig.clone(),
+                        ))
+                    }
+                    _ => None,
+                })
+                .collect();
+
+            let owner = rrset.owner().to_string();
+            if unsigned.contains(&(owner.as_str(), rrset.rtype())) {
+                assert!(rrsigs.is_empty(), "{} {}", owner, rrset.rtype());
+                continue;
+            }
+            assert_eq!(rrsigs.len(), 1, "{} {}", owner, rrset.rtype());
+            let rrsig = rrsigs[0].data();
+            if rrset.rtype() == Rtype::DNSKEY {
+                assert_eq!(rrsig.key_tag(), ksk.public_key().key_tag());
+            } else {
+                assert_eq!(rrsig.key_tag(), zsk.public_key().key_tag());
+            }
+            expirations.push(rrsig.expiration().into_int());
+
+            let status = validate_rrset(
+                rrset.slice,
+                &rrsigs,
+                &apex,
+                &dnskeys,
+                Timestamp::from(1_650_000_000),
+                &validate::ring::Verifier,
+            );
+            assert_eq!(status, ValidationStatus::Secure);
+        }
+        expirations
+    }
+
+    /// Returns the original owner names and types of the NSEC3 records.
+    ///
+    /// The NSEC3 records need to form a single chain.
+    fn nsec3_names(signed: &Signed, config: &Nsec3Config) -> Vec<String> {
+        let candidates = [
+            "test.",
+            "b.test.",
+            "a.b.test.",
+            "ns.test.",
+            "sec.test.",
+            "sub.test.",
+            "www.test.",
+        ];
+        let params = config.nsec3param::<Vec<u8>>();
+        let apex = name("test.");
+        let mut res = Vec::new();
+        let mut next = None;
+        for record in signed {
+            let nsec3 = match record.data() {
+                ZoneRecordData::Nsec3(nsec3) => nsec3,
+                _ => continue,
+            };
+            assert_eq!(nsec3.opt_out(), config.opt_out());
+            assert_eq!(nsec3.iterations(), config.iterations());
+            assert_eq!(nsec3.salt().as_slice(), config.salt().as_slice());
+            assert_eq!(record.ttl(), Ttl::from_secs(300));
+            if let Some(next) = next.take() {
+                assert_eq!(record.owner(), &next);
+            }
+            let mut builder = NameBuilder::new_vec();
+            builder
+                .append_label(
+                    base32::encode_string_hex(nsec3.next_owner())
+                        .to_ascii_lowercase()
+                        .as_bytes(),
+                )
+                .unwrap();
+            next = Some(builder.append_origin(&apex).unwrap());
+            let original = candidates
+                .iter()
+                .find(|candidate| {
+                    params.hashed_owner_name(&name(candidate), &apex).unwrap()
+                        == *record.owner()
+                })
+                .unwrap();
+            res.push(std::format!("{} {}", original, nsec3.types()));
+        }
+        // The chain wraps around to the first record.
+        assert_eq!(
+            signed
+                .iter()
+                .find(|record| record.rtype() == Rtype::NSEC3)
+                .unwrap()
+                .owner(),
+            &next.unwrap()
+        );
+        res.sort();
+        res
+    }
+
+    #[test]
+    fn sign_zone() {
+        let ksk = key(257);
+        let zsk = key(256);
+        let mut config =
+            SigningConfig::new(1_600_000_000.into(), 1_700_000_000.into());
+        config.add_key(&ksk, KeyRole::Ksk);
+        config.add_key(&zsk, KeyRole::Zsk);
+        config.set_jitter(86400);
+
+        let signed = super::sign_zone(zone(), &config).unwrap();
+
+        // The NSEC chain skips the glue below the delegation.
+        let nsecs: Vec<_> = signed
+            .iter()
+            .filter_map(|record| match record.data() {
+                ZoneRecordData::Nsec(nsec) => Some(std::format!(
+                    "{} {} {}",
+                    record.owner(),
+                    record.ttl().as_secs(),
+                    nsec.next_name()
+                )),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            nsecs,
+            [
+                "test 300 ns.test",
+                "ns.test 300 sub.test",
+                "sub.test 300 www.test",
+                "www.test 300 test",
+            ]
+        );
+
+        let mut expirations = check_signatures(
+            &signed,
+            &ksk,
+            &zsk,
+            &[("sub.test", Rtype::NS), ("ns.sub.test", Rtype::A)],
+        );
+        assert!(expirations.iter().all(|&expiration| {
+            (1_700_000_000 - 86400..=1_700_000_000).contains(&expiration)
+        }));
+        expirations.sort_unstable();
+        expirations.dedup();
+        assert!(expirations.len() > 1);
+
+        // Signing again replaces the previous signatures and NSEC chain.
+        let resigned =
+            super::sign_zone(signed.clone().into_vec(), &config).unwrap();
+        assert_eq!(
+            resigned.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            signed.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn sign_zone_long_jitter() {
+        let ksk = key(257);
+        let mut config =
+            SigningConfig::new(1_600_000_000.into(), 1_600_000_100.into());
+        config.add_key(&ksk, KeyRole::Csk);
+        config.set_jitter(86400);
+
+        let signed = super::sign_zone(zone(), &config).unwrap();
+        let expirations: Vec<_> = signed
+            .iter()
+            .filter_map(|record| match record.data() {
+                ZoneRecordData::Rrsig(rrsig) => {
+                    Some(rrsig.expiration().into_int())
+                }
+                _ => None,
+            })
+            .collect();
+        assert!(!expirations.is_empty());
+        assert!(expirations.iter().all(|&expiration| {
+            (1_600_000_000..=1_600_000_100).contains(&expiration)
+        }));
+    }
+
+    #[test]
+    fn sign_zone_nsec3() {
+        let ksk = key(257);
+        let zsk = key(256);
+        let mut config =
+            SigningConfig::new(1_600_000_000.into(), 1_700_000_000.into());
+        config.add_key(&ksk, KeyRole::Ksk);
+        config.add_key(&zsk, KeyRole::Zsk);
+
+        let mut records = zone();
+        records.push(record("a.b.test.", A::from_str("192.0.2.4").unwrap()));
+        records.push(record("sec.test.", Ns::new(name("ns.sub.test."))));
+        records.push(record(
+            "sec.test.",
+            Ds::new(
+                1,
+                crate::base::iana::SecAlg::ED25519,
+                DigestAlg::SHA256,
+                std::vec![0; 32],
+            )
+            .unwrap(),
+        ));
+        let unsigned = [
+            ("sub.test", Rtype::NS),
+            ("ns.sub.test", Rtype::A),
+            ("sec.test", Rtype::NS),
+        ];
+
+        // Without opt-out, there is a record for every name including the
+        // empty non-terminal and the insecure delegation.
+        let nsec3 = Nsec3Config::new(5, Nsec3Salt::from_str("CAFE").unwrap());
+        config.set_nsec3(Some(nsec3.clone()));
+        let signed = super::sign_zone(records.clone(), &config).unwrap();
+        assert!(!signed.iter().any(|record| record.rtype() == Rtype::NSEC));
+        assert_eq!(
+            signed
+                .iter()
+                .filter(|record| record.rtype() == Rtype::NSEC3PARAM)
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["test. 300 IN NSEC3PARAM 1 0 5 CAFE"]
+        );
+        assert_eq!(
+            nsec3_names(&signed, &nsec3),
+            [
+                "a.b.test. A RRSIG",
+                "b.test. ",
+                "ns.test. A RRSIG",
+                "sec.test. NS DS RRSIG",
+                "sub.test. NS",
+                "test. NS SOA RRSIG DNSKEY NSEC3PARAM",
+                "www.test. A RRSIG",
+            ]
+        );
+        check_signatures(&signed, &ksk, &zsk, &unsigned);
+
+        // With opt-out, the insecure delegation is left out.
+        let mut nsec3 = Nsec3Config::default();
+        nsec3.set_opt_out(true);
+        config.set_nsec3(Some(nsec3.clone()));
+        let signed = super::sign_zone(records, &config).unwrap();
+        assert_eq!(
+            nsec3_names(&signed, &nsec3),
+            [
+                "a.b.test. A RRSIG",
+                "b.test. ",
+                "ns.test. A RRSIG",
+                "sec.test. NS DS RRSIG",
+                "test. NS SOA RRSIG DNSKEY NSEC3PARAM",
+                "www.test. A RRSIG",
+            ]
+        );
+        check_signatures(&signed, &ksk, &zsk, &unsigned);
+    }
+
+    #[test]
+    fn sign_zone_errors() {
+        let ksk = key(257);
+        let mut config =
+            SigningConfig::new(1_600_000_000.into(), 1_700_000_000.into());
+        config.add_key(&ksk, KeyRole::Ksk);
+        assert!(matches!(
+            super::sign_zone(zone(), &config),
+            Err(SignZoneError::MissingKeys)
+        ));
+
+        config.add_key(&ksk, KeyRole::Csk);
+        let mut records = zone();
+        records.retain(|record| record.rtype() != Rtype::SOA);
+        assert!(matches!(
+            super::sign_zone(records, &config),
+            Err(SignZoneError::MissingSoa)
+        ));
+
+        let mut records = zone();
+        records.retain(|record| record.rtype() != Rtype::SOA);
+        records.push(record(
+            "test.",
+            Data::Unknown(
+                UnknownRecordData::from_octets(Rtype::SOA, vec![0]).unwrap(),
+            ),
+        ));
+        assert!(matches!(
+            super::sign_zone(records, &config),
+            Err(SignZoneError::InvalidSoa)
+        ));
+    }
+}
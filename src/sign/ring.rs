@@ -368,7 +368,7 @@ mod tests {
     use crate::{
         base::iana::SecAlg,
         sign::{GenerateParams, SecretKeyBytes, SignRaw},
-        validate::Key,
+        validate::{self, AlgorithmError, Key, VerifyRaw},
     };
 
     use super::KeyPair;
@@ -437,7 +437,15 @@ mod tests {
 
             let key = KeyPair::from_bytes(&gen_key, pub_key, rng).unwrap();
 
-            let _ = key.sign_raw(b"Hello, World!").unwrap();
+            let sig = key.sign_raw(b"Hello, World!").unwrap();
+
+            let verifier = validate::ring::Verifier;
+            let res =
+                verifier.verify_raw(pub_key, b"Hello, World!", sig.as_ref());
+            assert_eq!(res, Ok(()));
+            let res =
+                verifier.verify_raw(pub_key, b"Hello, World?", sig.as_ref());
+            assert_eq!(res, Err(AlgorithmError::BadSig));
         }
     }
 }
@@ -0,0 +1,710 @@
+//! Checking proofs of non-existence.
+//!
+//! Authoritative servers prove that a name or an RRset doesn’t exist via
+//! NSEC records as described in [RFC 4035, section 5.4] or via NSEC3 records
+//! as described in [RFC 5155, section 8]. The functions in this module
+//! check whether a set of such records proves what a response claims.
+//!
+//! [`nsec_denial`] and [`nsec3_denial`] check the records of a negative
+//! response, i.e., an NXDOMAIN or NODATA response. [`nsec_wildcard`] and
+//! [`nsec3_wildcard`] check that an answer synthesized from a wildcard is
+//! correct, i.e., that the query name itself doesn’t exist.
+//!
+//! The functions don’t check the signatures of the records. This needs to
+//! be done before, for instance via [`validate_rrset`]. Likewise, limits on
+//! the number of NSEC3 iterations as recommended by [RFC 9276] need to be
+//! enforced by the caller.
+//!
+//! [`validate_rrset`]: super::validate_rrset
+//! [RFC 4035, section 5.4]: https://tools.ietf.org/html/rfc4035#section-5.4
+//! [RFC 5155, section 8]: https://tools.ietf.org/html/rfc5155#section-8
+//! [RFC 9276]: https://tools.ietf.org/html/rfc9276
+
+use core::cmp::Ordering;
+use core::str;
+use std::vec::Vec;
+
+use crate::base::iana::{Nsec3HashAlg, Rtype};
+use crate::base::name::{Name, ToName};
+use crate::base::{Record, RelativeName};
+use crate::rdata::dnssec::RtypeBitmap;
+use crate::rdata::nsec3::OwnerHash;
+use crate::rdata::{Nsec, Nsec3};
+
+use super::nsec3_hash;
+
+//------------ Denial --------------------------------------------------------
+
+/// What a set of NSEC or NSEC3 records proves.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Denial {
+    /// The query name doesn’t exist.
+    NxDomain,
+
+    /// The query name exists but has no records of the query type.
+    ///
+    /// The name may also exist as an empty non-terminal.
+    NoData,
+
+    /// The query name doesn’t exist and neither do records of the query
+    /// type at the wildcard matching it.
+    WildcardNoData,
+
+    /// The query name doesn’t exist, so a wildcard may be expanded for it.
+    Wildcard,
+
+    /// The query name is covered by an NSEC3 record with the opt-out flag.
+    ///
+    /// There may be an unsigned delegation for the name, so the response
+    /// is insecure rather than secure.
+    OptOut,
+
+    /// The records don’t prove the claim.
+    Unproven,
+}
+
+//------------ NSEC ----------------------------------------------------------
+
+/// Checks the NSEC records of a negative response.
+///
+/// Returns [`Denial::NoData`] if the records show that `qname` exists but
+/// has no records of type `qtype`, [`Denial::NxDomain`] if they show that
+/// `qname` and the wildcard that would match it don’t exist, or
+/// [`Denial::WildcardNoData`] if `qname` doesn’t exist and neither do
+/// records of type `qtype` at the wildcard. Otherwise, returns
+/// [`Denial::Unproven`].
+pub fn nsec_denial<N, Octs, Next>(
+    qname: &impl ToName,
+    qtype: Rtype,
+    nsecs: &[Record<N, Nsec<Octs, Next>>],
+) -> Denial
+where
+    N: ToName,
+    Octs: AsRef<[u8]>,
+    Next: ToName,
+{
+    // An NSEC record for the name itself can prove NODATA.
+    if let Some(rr) = nsecs.iter().find(|rr| rr.owner().name_eq(qname)) {
+        return if is_nodata(rr.data().types(), qtype) {
+            Denial::NoData
+        } else {
+            Denial::Unproven
+        };
+    }
+
+    let Some(covering) = nsecs.iter().find(|rr| nsec_covers(rr, qname))
+    else {
+        return Denial::Unproven;
+    };
+
+    // If the next name is below the query name, the query name is an empty
+    // non-terminal.
+    let next = covering.data().next_name();
+    if next.ends_with(qname) {
+        return Denial::NoData;
+    }
+
+    // The closest encloser is the longest ancestor of the query name that
+    // is an ancestor of the owner or next name of the covering record.
+    let qname = qname.to_vec();
+    let labels = common_labels(&qname, covering.owner())
+        .max(common_labels(&qname, next));
+    let Some(wildcard) = suffix(&qname, labels).and_then(wildcard) else {
+        return Denial::Unproven;
+    };
+    if let Some(rr) = nsecs.iter().find(|rr| rr.owner().name_eq(&wildcard)) {
+        return if is_nodata(rr.data().types(), qtype) {
+            Denial::WildcardNoData
+        } else {
+            Denial::Unproven
+        };
+    }
+    if nsecs.iter().any(|rr| nsec_covers(rr, &wildcard)) {
+        Denial::NxDomain
+    } else {
+        Denial::Unproven
+    }
+}
+
+/// Checks the NSEC records for an answer synthesized from a wildcard.
+///
+/// `closest_encloser` is the name the wildcard was expanded from without
+/// its asterisk label. It can be derived from the RRSIG of the answer via
+/// [`RrsigExt::wildcard_closest_encloser`].
+///
+/// Returns [`Denial::Wildcard`] if the records show that there is no closer
+/// match for `qname`. Otherwise, returns [`Denial::Unproven`].
+///
+/// [`RrsigExt::wildcard_closest_encloser`]: super::RrsigExt::wildcard_closest_encloser
+pub fn nsec_wildcard<N, Octs, Next>(
+    qname: &impl ToName,
+    closest_encloser: &impl ToName,
+    nsecs: &[Record<N, Nsec<Octs, Next>>],
+) -> Denial
+where
+    N: ToName,
+    Octs: AsRef<[u8]>,
+    Next: ToName,
+{
+    let Some(next_closer) = next_closer(qname, closest_encloser) else {
+        return Denial::Unproven;
+    };
+
+    // The next closer name and everything below it must not exist.
+    if nsecs.iter().any(|rr| {
+        nsec_covers(rr, &next_closer)
+            && !rr.data().next_name().ends_with(&next_closer)
+    }) {
+        Denial::Wildcard
+    } else {
+        Denial::Unproven
+    }
+}
+
+/// Returns whether an NSEC record covers a name.
+///
+/// The name needs to be strictly between the owner name and the next name
+/// in canonical order. An NSEC record can’t cover names below its owner if
+/// that is a delegation point or has a DNAME record.
+fn nsec_covers<N, Octs, Next>(
+    rr: &Record<N, Nsec<Octs, Next>>,
+    name: &impl ToName,
+) -> bool
+where
+    N: ToName,
+    Octs: AsRef<[u8]>,
+    Next: ToName,
+{
+    let (owner, next) = (rr.owner(), rr.data().next_name());
+    if name.ends_with(owner) && !is_ancestor(rr.data().types()) {
+        return false;
+    }
+    if owner.name_cmp(name) != Ordering::Less {
+        return false;
+    }
+    if owner.name_cmp(next) == Ordering::Less {
+        name.name_cmp(next) == Ordering::Less
+    } else {
+        // The last NSEC record of a zone points back to the apex.
+        name.ends_with(next)
+    }
+}
+
+//------------ NSEC3 ---------------------------------------------------------
+
+/// Checks the NSEC3 records of a negative response.
+///
+/// The NSEC3 records need to belong to `zone`. Records for other zones,
+/// with an unsupported hash algorithm, or with different parameters than
+/// the first usable record are ignored.
+///
+/// Returns [`Denial::NoData`] if the records show that `qname` exists but
+/// has no records of type `qtype`, [`Denial::NxDomain`] if they show that
+/// `qname` and the wildcard that would match it don’t exist, or
+/// [`Denial::WildcardNoData`] if `qname` doesn’t exist and neither do
+/// records of type `qtype` at the wildcard. If `qname` is covered by an
+/// NSEC3 record with the opt-out flag, returns [`Denial::OptOut`].
+/// Otherwise, returns [`Denial::Unproven`].
+pub fn nsec3_denial<N, Octs>(
+    qname: &impl ToName,
+    qtype: Rtype,
+    zone: &impl ToName,
+    nsec3s: &[Record<N, Nsec3<Octs>>],
+) -> Denial
+where
+    N: ToName,
+    Octs: AsRef<[u8]>,
+{
+    let set = Nsec3Set::new(zone, nsec3s);
+
+    // An NSEC3 record for the name itself can prove NODATA.
+    if let Some(nsec3) = set.matching(qname) {
+        return if is_nodata(nsec3.types(), qtype) {
+            Denial::NoData
+        } else {
+            Denial::Unproven
+        };
+    }
+
+    let qname = qname.to_vec();
+    let Some((closest_encloser, covering)) = set.closest_encloser(&qname)
+    else {
+        return Denial::Unproven;
+    };
+    if covering.opt_out() {
+        return Denial::OptOut;
+    }
+
+    let Some(wildcard) = wildcard(closest_encloser) else {
+        return Denial::Unproven;
+    };
+    if let Some(nsec3) = set.matching(&wildcard) {
+        return if is_nodata(nsec3.types(), qtype) {
+            Denial::WildcardNoData
+        } else {
+            Denial::Unproven
+        };
+    }
+    if set.covering(&wildcard).is_some() {
+        Denial::NxDomain
+    } else {
+        Denial::Unproven
+    }
+}
+
+/// Checks the NSEC3 records for an answer synthesized from a wildcard.
+///
+/// `closest_encloser` is the name the wildcard was expanded from without
+/// its asterisk label. It can be derived from the RRSIG of the answer via
+/// [`RrsigExt::wildcard_closest_encloser`]. The records are selected as
+/// described for [`nsec3_denial`].
+///
+/// Returns [`Denial::Wildcard`] if the records show that there is no closer
+/// match for `qname`, or [`Denial::OptOut`] if the closer match is covered
+/// by an NSEC3 record with the opt-out flag. Otherwise, returns
+/// [`Denial::Unproven`].
+///
+/// [`RrsigExt::wildcard_closest_encloser`]: super::RrsigExt::wildcard_closest_encloser
+pub fn nsec3_wildcard<N, Octs>(
+    qname: &impl ToName,
+    closest_encloser: &impl ToName,
+    zone: &impl ToName,
+    nsec3s: &[Record<N, Nsec3<Octs>>],
+) -> Denial
+where
+    N: ToName,
+    Octs: AsRef<[u8]>,
+{
+    let Some(next_closer) = next_closer(qname, closest_encloser) else {
+        return Denial::Unproven;
+    };
+    match Nsec3Set::new(zone, nsec3s).covering(&next_closer) {
+        Some(nsec3) if nsec3.opt_out() => Denial::OptOut,
+        Some(_) => Denial::Wildcard,
+        None => Denial::Unproven,
+    }
+}
+
+//------------ Nsec3Set ------------------------------------------------------
+
+/// The usable NSEC3 records of a response with their owner hashes.
+struct Nsec3Set<'a, Octs> {
+    /// The records and their owner hashes.
+    records: Vec<(OwnerHash<Vec<u8>>, &'a Nsec3<Octs>)>,
+}
+
+impl<'a, Octs: AsRef<[u8]>> Nsec3Set<'a, Octs> {
+    /// Selects the usable records.
+    fn new<N: ToName>(
+        zone: &impl ToName,
+        nsec3s: &'a [Record<N, Nsec3<Octs>>],
+    ) -> Self {
+        let mut records: Vec<(_, &Nsec3<Octs>)> = Vec::new();
+        for rr in nsec3s {
+            let nsec3 = rr.data();
+            if nsec3.hash_algorithm() != Nsec3HashAlg::SHA1 {
+                continue;
+            }
+            if let Some((_, first)) = records.first() {
+                if nsec3.iterations() != first.iterations()
+                    || nsec3.salt().as_slice() != first.salt().as_slice()
+                {
+                    continue;
+                }
+            }
+            if let Some(hash) = owner_hash(rr.owner(), zone) {
+                records.push((hash, nsec3));
+            }
+        }
+        Nsec3Set { records }
+    }
+
+    /// Returns the hash of a name.
+    fn hash(&self, name: &impl ToName) -> Option<OwnerHash<Vec<u8>>> {
+        let (_, first) = self.records.first()?;
+        nsec3_hash(
+            name,
+            first.hash_algorithm(),
+            first.iterations(),
+            first.salt(),
+        )
+        .ok()
+    }
+
+    /// Returns the record matching a name.
+    fn matching(&self, name: &impl ToName) -> Option<&'a Nsec3<Octs>> {
+        let hash = self.hash(name)?;
+        self.records
+            .iter()
+            .find(|(owner, _)| owner.as_slice() == hash.as_slice())
+            .map(|(_, nsec3)| *nsec3)
+    }
+
+    /// Returns the record covering a name.
+    fn covering(&self, name: &impl ToName) -> Option<&'a Nsec3<Octs>> {
+        let hash = self.hash(name)?;
+        let hash = hash.as_slice();
+        self.records
+            .iter()
+            .find(|(owner, nsec3)| {
+                let (owner, next) =
+                    (owner.as_slice(), nsec3.next_owner().as_slice());
+                if owner < next {
+                    owner < hash && hash < next
+                } else {
+                    // The last record wraps around to the first.
+                    owner < hash || hash < next
+                }
+            })
+            .map(|(_, nsec3)| *nsec3)
+    }
+
+    /// Finds the closest encloser of a name that doesn’t exist.
+    ///
+    /// This is the closest encloser proof of [RFC 5155, section 8.3]:
+    /// The closest encloser is the longest ancestor of `name` with a
+    /// matching record, and the next closer name needs to be covered by a
+    /// record. Returns the closest encloser and the covering record.
+    ///
+    /// [RFC 5155, section 8.3]: https://tools.ietf.org/html/rfc5155#section-8.3
+    fn closest_encloser<'n>(
+        &self,
+        name: &'n Name<Vec<u8>>,
+    ) -> Option<(Name<&'n [u8]>, &'a Nsec3<Octs>)> {
+        let mut next_closer = None;
+        for candidate in name.iter_suffixes() {
+            if let Some(nsec3) = self.matching(&candidate) {
+                if !is_ancestor(nsec3.types()) {
+                    return None;
+                }
+                let covering = self.covering(&next_closer?)?;
+                return Some((candidate, covering));
+            }
+            next_closer = Some(candidate);
+        }
+        None
+    }
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Returns whether a bitmap proves that a record type doesn’t exist.
+///
+/// The type must not exist and neither must a CNAME. The parent side of a
+/// delegation can only prove the absence of DS records, while the child
+/// side can’t.
+fn is_nodata(types: &RtypeBitmap<impl AsRef<[u8]>>, qtype: Rtype) -> bool {
+    if types.contains(qtype) || types.contains(Rtype::CNAME) {
+        return false;
+    }
+    if types.contains(Rtype::NS) && !types.contains(Rtype::SOA) {
+        qtype == Rtype::DS
+    } else {
+        qtype != Rtype::DS || !types.contains(Rtype::SOA)
+    }
+}
+
+/// Returns whether names below a record’s owner may exist in the zone.
+///
+/// This isn’t the case at delegation points and for DNAME records.
+fn is_ancestor(types: &RtypeBitmap<impl AsRef<[u8]>>) -> bool {
+    !types.contains(Rtype::DNAME)
+        && (!types.contains(Rtype::NS) || types.contains(Rtype::SOA))
+}
+
+/// Returns the hash from the owner name of an NSEC3 record.
+///
+/// The owner name must consist of the hash label directly below the zone.
+fn owner_hash(
+    owner: &impl ToName,
+    zone: &impl ToName,
+) -> Option<OwnerHash<Vec<u8>>> {
+    let mut labels = owner.iter_labels();
+    let label = labels.next()?;
+    if !labels.eq(zone.iter_labels()) {
+        return None;
+    }
+    str::from_utf8(label.as_slice()).ok()?.parse().ok()
+}
+
+/// Returns the next closer name of a name below a closest encloser.
+///
+/// This is the ancestor of `name`, or `name` itself, with one label more
+/// than `closest_encloser`.
+fn next_closer(
+    name: &impl ToName,
+    closest_encloser: &impl ToName,
+) -> Option<Name<Vec<u8>>> {
+    if !name.ends_with(closest_encloser) || name.name_eq(closest_encloser) {
+        return None;
+    }
+    let labels = closest_encloser.iter_labels().count() + 1;
+    suffix(&name.to_vec(), labels).map(|name| name.to_vec())
+}
+
+/// Returns the number of labels two names have in common at their end.
+fn common_labels(left: &impl ToName, right: &impl ToName) -> usize {
+    left.iter_labels()
+        .rev()
+        .zip(right.iter_labels().rev())
+        .take_while(|(left, right)| left == right)
+        .count()
+}
+
+/// Returns the ancestor of a name with the given number of labels.
+fn suffix(name: &Name<Vec<u8>>, labels: usize) -> Option<Name<&[u8]>> {
+    name.iter_suffixes()
+        .find(|name| name.label_count() == labels)
+}
+
+/// Returns the wildcard name directly below a name.
+fn wildcard(name: Name<&[u8]>) -> Option<Name<Vec<u8>>> {
+    RelativeName::wildcard_vec()
+        .chain(name)
+        .ok()
+        .map(|name| name.to_vec())
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(any(feature = "ring", feature = "openssl"))]
+mod test {
+    use super::*;
+    use crate::base::iana::Class;
+    use crate::base::Ttl;
+    use crate::rdata::dnssec::RtypeBitmapBuilder;
+    use crate::rdata::nsec3::Nsec3Salt;
+    use crate::rdata::Nsec3param;
+    use crate::validate::Nsec3paramExt;
+    use core::str::FromStr;
+    use std::vec;
+
+    type Nsecs = Vec<Record<Name<Vec<u8>>, Nsec<Vec<u8>, Name<Vec<u8>>>>>;
+    type Nsec3s = Vec<Record<Name<Vec<u8>>, Nsec3<Vec<u8>>>>;
+
+    fn name(s: &str) -> Name<Vec<u8>> {
+        Name::from_str(s).unwrap()
+    }
+
+    fn bitmap(types: &[Rtype]) -> RtypeBitmap<Vec<u8>> {
+        let mut builder = RtypeBitmapBuilder::new_vec();
+        for rtype in types {
+            builder.add(*rtype).unwrap();
+        }
+        builder.finalize()
+    }
+
+    /// The names of the test zone in canonical order and their types.
+    ///
+    /// `c.example.` and `w.example.` are empty non-terminals and
+    /// `sub.example.` is an unsigned delegation.
+    fn zone() -> Vec<(&'static str, Vec<Rtype>)> {
+        vec![
+            ("example.", vec![Rtype::SOA, Rtype::NS, Rtype::DNSKEY]),
+            ("a.example.", vec![Rtype::A]),
+            ("b.c.example.", vec![Rtype::A]),
+            ("sub.example.", vec![Rtype::NS]),
+            ("*.w.example.", vec![Rtype::MX]),
+        ]
+    }
+
+    fn nsecs() -> Nsecs {
+        let zone = zone();
+        zone.iter()
+            .enumerate()
+            .map(|(i, (owner, types))| {
+                let next = zone[(i + 1) % zone.len()].0;
+                let mut types = types.clone();
+                types.extend([Rtype::RRSIG, Rtype::NSEC]);
+                Record::new(
+                    name(owner),
+                    Class::IN,
+                    Ttl::from_secs(3600),
+                    Nsec::new(name(next), bitmap(&types)),
+                )
+            })
+            .collect()
+    }
+
+    fn nsec3s(opt_out: bool) -> Nsec3s {
+        let param = Nsec3param::new(
+            Nsec3HashAlg::SHA1,
+            0,
+            1,
+            Nsec3Salt::from_octets(vec![0xaa, 0xbb, 0xcc, 0xdd]).unwrap(),
+        );
+        let apex = name("example.");
+        let mut zone = zone();
+        zone.extend([("c.example.", vec![]), ("w.example.", vec![])]);
+        if opt_out {
+            zone.retain(|(owner, _)| *owner != "sub.example.");
+        }
+        let mut hashes: Vec<_> = zone
+            .into_iter()
+            .map(|(owner, types)| {
+                let owner = name(owner);
+                (param.hash_name(&owner).unwrap(), owner, types)
+            })
+            .collect();
+        hashes
+            .sort_by(|left, right| left.0.as_slice().cmp(right.0.as_slice()));
+        (0..hashes.len())
+            .map(|i| {
+                let (_, owner, types) = &hashes[i];
+                let (next, _, _) = &hashes[(i + 1) % hashes.len()];
+                let mut types = types.clone();
+                if !types.is_empty() {
+                    types.push(Rtype::RRSIG);
+                }
+                Record::new(
+                    param.hashed_owner_name(owner, &apex).unwrap(),
+                    Class::IN,
+                    Ttl::from_secs(3600),
+                    Nsec3::new(
+                        Nsec3HashAlg::SHA1,
+                        opt_out.into(),
+                        param.iterations(),
+                        param.salt().clone(),
+                        next.clone(),
+                        bitmap(&types),
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn nsec() {
+        let nsecs = nsecs();
+        for (qname, qtype, res) in [
+            ("a.example.", Rtype::MX, Denial::NoData),
+            ("a.example.", Rtype::A, Denial::Unproven),
+            ("a.example.", Rtype::CNAME, Denial::NoData),
+            ("c.example.", Rtype::A, Denial::NoData),
+            ("b.example.", Rtype::A, Denial::NxDomain),
+            ("x.example.", Rtype::A, Denial::NxDomain),
+            ("x.w.example.", Rtype::A, Denial::WildcardNoData),
+            ("x.w.example.", Rtype::MX, Denial::Unproven),
+            ("sub.example.", Rtype::DS, Denial::NoData),
+            ("sub.example.", Rtype::A, Denial::Unproven),
+            ("foo.sub.example.", Rtype::A, Denial::Unproven),
+            ("example.", Rtype::DS, Denial::Unproven),
+        ] {
+            assert_eq!(
+                nsec_denial(&name(qname), qtype, &nsecs),
+                res,
+                "{qname} {qtype}"
+            );
+        }
+
+        // The proof is incomplete without the NSEC for the wildcard.
+        assert_eq!(
+            nsec_denial(&name("b.example."), Rtype::A, &nsecs[1..]),
+            Denial::Unproven
+        );
+    }
+
+    #[test]
+    fn nsec_wildcard_answer() {
+        let nsecs = nsecs();
+        let (qname, ce) = (name("x.w.example."), name("w.example."));
+        assert_eq!(nsec_wildcard(&qname, &ce, &nsecs), Denial::Wildcard);
+        assert_eq!(
+            nsec_wildcard(&name("x.y.w.example."), &ce, &nsecs),
+            Denial::Wildcard
+        );
+        assert_eq!(
+            nsec_wildcard(&name("a.example."), &name("example."), &nsecs),
+            Denial::Unproven
+        );
+        assert_eq!(
+            nsec_wildcard(&name("b.c.example."), &name("example."), &nsecs),
+            Denial::Unproven
+        );
+        assert_eq!(nsec_wildcard(&ce, &ce, &nsecs), Denial::Unproven);
+    }
+
+    #[test]
+    fn nsec3() {
+        let nsec3s = nsec3s(false);
+        let zone = name("example.");
+        for (qname, qtype, res) in [
+            ("a.example.", Rtype::MX, Denial::NoData),
+            ("a.example.", Rtype::A, Denial::Unproven),
+            ("c.example.", Rtype::A, Denial::NoData),
+            ("b.example.", Rtype::A, Denial::NxDomain),
+            ("x.example.", Rtype::A, Denial::NxDomain),
+            ("x.w.example.", Rtype::A, Denial::WildcardNoData),
+            ("x.w.example.", Rtype::MX, Denial::Unproven),
+            ("sub.example.", Rtype::DS, Denial::NoData),
+            ("sub.example.", Rtype::A, Denial::Unproven),
+            ("foo.sub.example.", Rtype::A, Denial::Unproven),
+            ("example.", Rtype::DS, Denial::Unproven),
+        ] {
+            assert_eq!(
+                nsec3_denial(&name(qname), qtype, &zone, &nsec3s),
+                res,
+                "{qname} {qtype}"
+            );
+        }
+
+        // Records of other zones are ignored.
+        assert_eq!(
+            nsec3_denial(
+                &name("a.example."),
+                Rtype::MX,
+                &name("a."),
+                &nsec3s
+            ),
+            Denial::Unproven
+        );
+    }
+
+    #[test]
+    fn nsec3_wildcard_answer() {
+        let nsec3s = nsec3s(false);
+        let zone = name("example.");
+        assert_eq!(
+            nsec3_wildcard(
+                &name("x.w.example."),
+                &name("w.example."),
+                &zone,
+                &nsec3s
+            ),
+            Denial::Wildcard
+        );
+        assert_eq!(
+            nsec3_wildcard(
+                &name("a.example."),
+                &name("example."),
+                &zone,
+                &nsec3s
+            ),
+            Denial::Unproven
+        );
+    }
+
+    #[test]
+    fn nsec3_opt_out() {
+        let nsec3s = nsec3s(true);
+        let zone = name("example.");
+        assert_eq!(
+            nsec3_denial(&name("sub.example."), Rtype::DS, &zone, &nsec3s),
+            Denial::OptOut
+        );
+        assert_eq!(
+            nsec3_wildcard(
+                &name("sub.example."),
+                &name("example."),
+                &zone,
+                &nsec3s
+            ),
+            Denial::OptOut
+        );
+        assert_eq!(
+            nsec3_denial(&name("a.example."), Rtype::MX, &zone, &nsec3s),
+            Denial::NoData
+        );
+    }
+}
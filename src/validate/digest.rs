@@ -0,0 +1,217 @@
+//! Message digests.
+//!
+//! DS records and NSEC3 hashes are based on message digests. These are
+//! calculated using Ring if the `ring` feature is enabled or using OpenSSL
+//! if only the `openssl` feature is enabled. If neither is enabled, no
+//! digest algorithms are supported.
+
+use core::fmt;
+
+use crate::base::iana::DigestAlg;
+
+//----------- DigestContext --------------------------------------------------
+
+/// A context for calculating a message digest.
+pub(super) struct DigestContext(backend::Context);
+
+impl DigestContext {
+    /// Creates a new context for the given algorithm.
+    ///
+    /// Returns `None` if the algorithm isn’t supported.
+    pub fn new(algorithm: DigestAlg) -> Option<Self> {
+        if !Self::supports(algorithm) {
+            return None;
+        }
+        backend::new(algorithm).map(Self)
+    }
+
+    /// Returns whether an algorithm is supported.
+    pub fn supports(algorithm: DigestAlg) -> bool {
+        backend::SUPPORTED
+            && matches!(
+                algorithm,
+                DigestAlg::SHA1 | DigestAlg::SHA256 | DigestAlg::SHA384
+            )
+    }
+
+    /// Adds data to the digest.
+    pub fn update(&mut self, data: &[u8]) {
+        backend::update(&mut self.0, data)
+    }
+
+    /// Finishes calculating the digest.
+    pub fn finish(self) -> Digest {
+        backend::finish(self.0)
+    }
+}
+
+//----------- Digest ---------------------------------------------------------
+
+/// The result of a message digest calculation.
+#[derive(Clone, Copy)]
+pub struct Digest {
+    /// The buffer holding the digest.
+    ///
+    /// This is large enough for the longest supported digest, SHA-384.
+    buf: [u8; 48],
+
+    /// The length of the digest.
+    len: usize,
+}
+
+impl Digest {
+    /// Creates a digest from a slice.
+    #[allow(dead_code)] // Unused without a backend.
+    fn from_slice(slice: &[u8]) -> Self {
+        let mut buf = [0u8; 48];
+        buf[..slice.len()].copy_from_slice(slice);
+        Digest {
+            buf,
+            len: slice.len(),
+        }
+    }
+
+    /// Returns the digest as a slice.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+//--- AsRef
+
+impl AsRef<[u8]> for Digest {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+//--- PartialEq and Eq
+
+impl PartialEq for Digest {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for Digest {}
+
+//--- Debug
+
+impl fmt::Debug for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Digest").field(&self.as_slice()).finish()
+    }
+}
+
+//----------- Backends -------------------------------------------------------
+
+#[cfg(feature = "ring")]
+mod backend {
+    use ring::digest;
+
+    use super::Digest;
+    use crate::base::iana::DigestAlg;
+
+    pub type Context = digest::Context;
+
+    pub const SUPPORTED: bool = true;
+
+    pub fn new(algorithm: DigestAlg) -> Option<Context> {
+        let algorithm = match algorithm {
+            DigestAlg::SHA1 => &digest::SHA1_FOR_LEGACY_USE_ONLY,
+            DigestAlg::SHA256 => &digest::SHA256,
+            DigestAlg::SHA384 => &digest::SHA384,
+            _ => return None,
+        };
+        Some(Context::new(algorithm))
+    }
+
+    pub fn update(context: &mut Context, data: &[u8]) {
+        context.update(data)
+    }
+
+    pub fn finish(context: Context) -> Digest {
+        Digest::from_slice(context.finish().as_ref())
+    }
+}
+
+#[cfg(all(feature = "openssl", not(feature = "ring")))]
+mod backend {
+    use openssl::hash::{Hasher, MessageDigest};
+
+    use super::Digest;
+    use crate::base::iana::DigestAlg;
+
+    pub type Context = Hasher;
+
+    pub const SUPPORTED: bool = true;
+
+    pub fn new(algorithm: DigestAlg) -> Option<Context> {
+        let algorithm = match algorithm {
+            DigestAlg::SHA1 => MessageDigest::sha1(),
+            DigestAlg::SHA256 => MessageDigest::sha256(),
+            DigestAlg::SHA384 => MessageDigest::sha384(),
+            _ => return None,
+        };
+        Hasher::new(algorithm).ok()
+    }
+
+    pub fn update(context: &mut Context, data: &[u8]) {
+        context.update(data).expect("updating digest failed")
+    }
+
+    pub fn finish(mut context: Context) -> Digest {
+        Digest::from_slice(
+            &context.finish().expect("finishing digest failed"),
+        )
+    }
+}
+
+#[cfg(not(any(feature = "ring", feature = "openssl")))]
+mod backend {
+    use super::Digest;
+    use crate::base::iana::DigestAlg;
+
+    /// Without a backend, a context can never be created.
+    pub enum Context {}
+
+    pub const SUPPORTED: bool = false;
+
+    pub fn new(_algorithm: DigestAlg) -> Option<Context> {
+        None
+    }
+
+    pub fn update(context: &mut Context, _data: &[u8]) {
+        match *context {}
+    }
+
+    pub fn finish(context: Context) -> Digest {
+        match context {}
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(any(feature = "ring", feature = "openssl"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sha256() {
+        let mut context = DigestContext::new(DigestAlg::SHA256).unwrap();
+        context.update(b"a");
+        context.update(b"bc");
+        assert_eq!(
+            context.finish().as_slice(),
+            b"\xba\x78\x16\xbf\x8f\x01\xcf\xea\x41\x41\x40\xde\x5d\xae\x22\x23\
+              \xb0\x03\x61\xa3\x96\x17\x7a\x9c\xb4\x10\xff\x61\xf2\x00\x15\xad"
+        );
+    }
+
+    #[test]
+    fn unsupported() {
+        assert!(DigestContext::new(DigestAlg::GOST).is_none());
+        assert!(!DigestContext::supports(DigestAlg::GOST));
+    }
+}
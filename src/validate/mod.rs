@@ -1,28 +1,56 @@
 //! DNSSEC validation.
 //!
 //! **This module is experimental and likely to change significantly.**
+//!
+//! # Cryptography
+//!
+//! Signatures are verified by a cryptographic backend implementing
+//! [`VerifyRaw`].  This crate provides backends based on Ring and OpenSSL,
+//! gated on the `ring` and `openssl` features, respectively.  Each backend
+//! module exposes a `Verifier` type.  Functions that don't take a backend
+//! explicitly use the Ring backend and are only available with the `ring`
+//! feature.
+//!
+//! Message digests, used for DS records and NSEC3 hashes, are calculated
+//! with Ring if the `ring` feature is enabled and with OpenSSL otherwise.
+//! If neither feature is enabled, no digest algorithms are supported.
+//!
+//! Users can bring their own cryptography by implementing [`VerifyRaw`] for
+//! their own type.
 #![cfg(feature = "unstable-validate")]
 #![cfg_attr(docsrs, doc(cfg(feature = "unstable-validate")))]
 
 use crate::base::cmp::CanonicalOrd;
-use crate::base::iana::{Class, DigestAlg, SecAlg};
+use crate::base::iana::{
+    Class, DigestAlg, ExtendedErrorCode, Nsec3HashAlg, SecAlg,
+};
 use crate::base::name::Name;
-use crate::base::name::ToName;
+use crate::base::name::{NameBuilder, ToName};
 use crate::base::rdata::{ComposeRecordData, RecordData};
 use crate::base::record::Record;
 use crate::base::scan::{IterScanner, Scanner};
 use crate::base::wire::{Compose, Composer};
 use crate::base::zonefile_fmt::{DisplayKind, ZonefileFmt};
 use crate::base::Rtype;
-use crate::rdata::{Dnskey, Ds, Rrsig};
+use crate::rdata::dnssec::Timestamp;
+use crate::rdata::nsec3::{Nsec3Salt, OwnerHash};
+use crate::rdata::{Dnskey, Ds, Nsec3param, Rrsig};
+use crate::utils::base32;
 use bytes::Bytes;
 use octseq::builder::with_infallible;
 use octseq::{EmptyBuilder, FromBuilder};
-use ring::{digest, signature};
 use std::boxed::Box;
 use std::vec::Vec;
 use std::{error, fmt};
 
+pub mod denial;
+mod digest;
+pub mod openssl;
+pub mod ring;
+
+pub use self::digest::Digest;
+use self::digest::DigestContext;
+
 //----------- Key ------------------------------------------------------------
 
 /// A DNSSEC key for a particular zone.
@@ -182,12 +210,8 @@ impl<Octs> Key<Octs> {
     where
         Octs: AsRef<[u8]>,
     {
-        let mut context = ring::digest::Context::new(match algorithm {
-            DigestAlg::SHA1 => &ring::digest::SHA1_FOR_LEGACY_USE_ONLY,
-            DigestAlg::SHA256 => &ring::digest::SHA256,
-            DigestAlg::SHA384 => &ring::digest::SHA384,
-            _ => return Err(DigestError::UnsupportedAlgorithm),
-        });
+        let mut context = DigestContext::new(algorithm)
+            .ok_or(DigestError::UnsupportedAlgorithm)?;
 
         // Add the owner name.
         if self
@@ -470,7 +494,7 @@ impl PublicKeyBytes {
     }
 
     /// Compute a digest of this public key.
-    fn digest(&self, context: &mut ring::digest::Context) {
+    fn digest(&self, context: &mut DigestContext) {
         match self {
             Self::RsaSha1(k)
             | Self::RsaSha1Nsec3Sha1(k)
@@ -562,25 +586,21 @@ impl PublicKeyBytes {
 
 impl PartialEq for PublicKeyBytes {
     fn eq(&self, other: &Self) -> bool {
-        use ring::constant_time::verify_slices_are_equal;
-
         match (self, other) {
             (Self::RsaSha1(a), Self::RsaSha1(b)) => a == b,
             (Self::RsaSha1Nsec3Sha1(a), Self::RsaSha1Nsec3Sha1(b)) => a == b,
             (Self::RsaSha256(a), Self::RsaSha256(b)) => a == b,
             (Self::RsaSha512(a), Self::RsaSha512(b)) => a == b,
             (Self::EcdsaP256Sha256(a), Self::EcdsaP256Sha256(b)) => {
-                verify_slices_are_equal(&**a, &**b).is_ok()
+                constant_time_eq(&**a, &**b)
             }
             (Self::EcdsaP384Sha384(a), Self::EcdsaP384Sha384(b)) => {
-                verify_slices_are_equal(&**a, &**b).is_ok()
+                constant_time_eq(&**a, &**b)
             }
             (Self::Ed25519(a), Self::Ed25519(b)) => {
-                verify_slices_are_equal(&**a, &**b).is_ok()
-            }
-            (Self::Ed448(a), Self::Ed448(b)) => {
-                verify_slices_are_equal(&**a, &**b).is_ok()
+                constant_time_eq(&**a, &**b)
             }
+            (Self::Ed448(a), Self::Ed448(b)) => constant_time_eq(&**a, &**b),
             _ => false,
         }
     }
@@ -648,7 +668,7 @@ impl RsaPublicKeyBytes {
     }
 
     /// Compute a digest of this public key.
-    fn digest(&self, context: &mut ring::digest::Context) {
+    fn digest(&self, context: &mut DigestContext) {
         // Encode the exponent length.
         if let Ok(exp_len) = u8::try_from(self.e.len()) {
             context.update(&[exp_len]);
@@ -728,15 +748,25 @@ impl RsaPublicKeyBytes {
 
 impl PartialEq for RsaPublicKeyBytes {
     fn eq(&self, other: &Self) -> bool {
-        use ring::constant_time::verify_slices_are_equal;
-
-        verify_slices_are_equal(&self.n, &other.n).is_ok()
-            && verify_slices_are_equal(&self.e, &other.e).is_ok()
+        constant_time_eq(&self.n, &other.n)
+            && constant_time_eq(&self.e, &other.e)
     }
 }
 
 impl Eq for RsaPublicKeyBytes {}
 
+/// Compares two byte slices in constant time.
+///
+/// The time taken only depends on the lengths of the slices, not on their
+/// content.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b).fold(0u8, |diff, (a, b)| diff | (a ^ b));
+    core::hint::black_box(diff) == 0
+}
+
 //----------- Signature ------------------------------------------------------
 
 /// A cryptographic signature.
@@ -821,6 +851,58 @@ impl From<Signature> for Box<[u8]> {
     }
 }
 
+//----------- VerifyRaw ------------------------------------------------------
+
+/// Low-level signature verification.
+///
+/// This is the interface to the cryptographic backend used for validation.
+/// Implementations only perform the public key operation; everything DNSSEC
+/// specific, such as assembling the signed data or checking the validity
+/// period of a signature, is handled by the caller.
+pub trait VerifyRaw {
+    /// Returns whether signatures of the given algorithm can be verified.
+    ///
+    /// Records signed only with unsupported algorithms are treated as
+    /// insecure rather than bogus, as described in [RFC 4035, section 5.2].
+    ///
+    /// [RFC 4035, section 5.2]: https://tools.ietf.org/html/rfc4035#section-5.2
+    fn supports_algorithm(&self, algorithm: SecAlg) -> bool;
+
+    /// Verifies the signature over the given data.
+    ///
+    /// The signature is given in the format used by RRSIG records, which is
+    /// the same as described for [`Signature`].  The algorithm is the one of
+    /// `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AlgorithmError::Unsupported`] if the backend can't use the
+    /// key's algorithm or size, [`AlgorithmError::InvalidData`] if the key or
+    /// signature is malformed, and [`AlgorithmError::BadSig`] if the
+    /// signature doesn't match.
+    fn verify_raw(
+        &self,
+        key: &PublicKeyBytes,
+        signed_data: &[u8],
+        signature: &[u8],
+    ) -> Result<(), AlgorithmError>;
+}
+
+impl<T: VerifyRaw + ?Sized> VerifyRaw for &T {
+    fn supports_algorithm(&self, algorithm: SecAlg) -> bool {
+        (**self).supports_algorithm(algorithm)
+    }
+
+    fn verify_raw(
+        &self,
+        key: &PublicKeyBytes,
+        signed_data: &[u8],
+        signature: &[u8],
+    ) -> Result<(), AlgorithmError> {
+        (**self).verify_raw(key, signed_data, signature)
+    }
+}
+
 //------------ Dnskey --------------------------------------------------------
 
 /// Extensions for DNSKEY record type.
@@ -847,7 +929,7 @@ pub trait DnskeyExt {
         &self,
         name: &N,
         algorithm: DigestAlg,
-    ) -> Result<digest::Digest, AlgorithmError>;
+    ) -> Result<Digest, AlgorithmError>;
 }
 
 impl<Octets> DnskeyExt for Dnskey<Octets>
@@ -876,34 +958,129 @@ where
         &self,
         name: &N,
         algorithm: DigestAlg,
-    ) -> Result<digest::Digest, AlgorithmError> {
+    ) -> Result<Digest, AlgorithmError> {
         let mut buf: Vec<u8> = Vec::new();
         with_infallible(|| {
             name.compose_canonical(&mut buf)?;
             self.compose_canonical_rdata(&mut buf)
         });
 
-        let mut ctx = match algorithm {
-            DigestAlg::SHA1 => {
-                digest::Context::new(&digest::SHA1_FOR_LEGACY_USE_ONLY)
-            }
-            DigestAlg::SHA256 => digest::Context::new(&digest::SHA256),
-            DigestAlg::SHA384 => digest::Context::new(&digest::SHA384),
-            _ => {
-                return Err(AlgorithmError::Unsupported);
-            }
-        };
+        let mut ctx = DigestContext::new(algorithm)
+            .ok_or(AlgorithmError::Unsupported)?;
 
         ctx.update(&buf);
         Ok(ctx.finish())
     }
 }
 
-// This needs to match the digests supported in digest.
+/// Returns whether a digest algorithm is supported.
+///
+/// This depends on the cryptographic backends that are enabled. Without
+/// the `ring` or `openssl` feature, no digest algorithms are supported.
 pub fn supported_digest(d: &DigestAlg) -> bool {
-    *d == DigestAlg::SHA1
-        || *d == DigestAlg::SHA256
-        || *d == DigestAlg::SHA384
+    DigestContext::supports(*d)
+}
+
+//------------ Nsec3param ----------------------------------------------------
+
+/// Extensions for the NSEC3PARAM record type.
+pub trait Nsec3paramExt {
+    /// Calculates the NSEC3 hash of a name using these parameters.
+    ///
+    /// See [`nsec3_hash`] for details.
+    fn hash_name(
+        &self,
+        name: &impl ToName,
+    ) -> Result<OwnerHash<Vec<u8>>, Nsec3HashError>;
+
+    /// Returns the owner name of the NSEC3 record for a name.
+    ///
+    /// This is the NSEC3 hash of `name` using these parameters in
+    /// lowercase Base 32 encoding with extended hex alphabet as a single
+    /// label prepended to `apex` as described in [RFC 5155, section 3].
+    ///
+    /// [RFC 5155, section 3]: https://tools.ietf.org/html/rfc5155#section-3
+    fn hashed_owner_name(
+        &self,
+        name: &impl ToName,
+        apex: &impl ToName,
+    ) -> Result<Name<Vec<u8>>, Nsec3HashError>;
+}
+
+impl<Octs: AsRef<[u8]>> Nsec3paramExt for Nsec3param<Octs> {
+    fn hash_name(
+        &self,
+        name: &impl ToName,
+    ) -> Result<OwnerHash<Vec<u8>>, Nsec3HashError> {
+        nsec3_hash(
+            name,
+            self.hash_algorithm(),
+            self.iterations(),
+            self.salt(),
+        )
+    }
+
+    fn hashed_owner_name(
+        &self,
+        name: &impl ToName,
+        apex: &impl ToName,
+    ) -> Result<Name<Vec<u8>>, Nsec3HashError> {
+        let hash = self.hash_name(name)?;
+        let label = base32::encode_string_hex(&hash).to_ascii_lowercase();
+        let mut builder = NameBuilder::new_vec();
+        builder
+            .append_label(label.as_bytes())
+            .map_err(|_| Nsec3HashError::LongName)?;
+        builder
+            .append_origin(apex)
+            .map_err(|_| Nsec3HashError::LongName)
+    }
+}
+
+/// Calculates the NSEC3 hash of a name.
+///
+/// The hash is defined in [RFC 5155, section 5] as
+///
+/// ```text
+/// IH(salt, x, 0) = H(x || salt)
+/// IH(salt, x, k) = H(IH(salt, x, k-1) || salt), if k > 0
+/// ```
+///
+/// where the hash of the name is `IH(salt, name, iterations)` over the
+/// canonical wire format of the name. The only hash algorithm defined is
+/// SHA-1. For all other algorithms, an error is returned. An error is also
+/// returned if no cryptographic backend providing SHA-1 is enabled.
+///
+/// [RFC 5155, section 5]: https://tools.ietf.org/html/rfc5155#section-5
+pub fn nsec3_hash(
+    name: &impl ToName,
+    algorithm: Nsec3HashAlg,
+    iterations: u16,
+    salt: &Nsec3Salt<impl AsRef<[u8]>>,
+) -> Result<OwnerHash<Vec<u8>>, Nsec3HashError> {
+    if algorithm != Nsec3HashAlg::SHA1 {
+        return Err(Nsec3HashError::UnsupportedAlgorithm);
+    }
+    let new_context = || {
+        DigestContext::new(DigestAlg::SHA1)
+            .ok_or(Nsec3HashError::UnsupportedAlgorithm)
+    };
+
+    let mut buf = Vec::new();
+    name.compose_canonical(&mut buf).expect("infallible");
+    let mut ctx = new_context()?;
+    ctx.update(&buf);
+    ctx.update(salt.as_slice());
+    let mut hash = ctx.finish();
+
+    for _ in 0..iterations {
+        let mut ctx = new_context()?;
+        ctx.update(hash.as_ref());
+        ctx.update(salt.as_slice());
+        hash = ctx.finish();
+    }
+
+    Ok(OwnerHash::from_octets(hash.as_ref().to_vec()).expect("short hash"))
 }
 
 //------------ Rrsig ---------------------------------------------------------
@@ -959,10 +1136,30 @@ pub trait RrsigExt {
     ///    provides a list of algorithm types and provides pointers to the
     ///    documents that define each algorithm's use.
     /// ```
+    ///
+    /// This uses the Ring backend. Use
+    /// [`verify_signed_data_with`][Self::verify_signed_data_with] to choose
+    /// a different one.
+    #[cfg(feature = "ring")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
     fn verify_signed_data(
         &self,
         dnskey: &Dnskey<impl AsRef<[u8]>>,
         signed_data: &impl AsRef<[u8]>,
+    ) -> Result<(), AlgorithmError> {
+        self.verify_signed_data_with(dnskey, signed_data, &ring::Verifier)
+    }
+
+    /// Attempt to authenticate the signed data using the given backend.
+    ///
+    /// This is identical to
+    /// [`verify_signed_data`][Self::verify_signed_data] but uses `verifier`
+    /// for the cryptographic operation.
+    fn verify_signed_data_with(
+        &self,
+        dnskey: &Dnskey<impl AsRef<[u8]>>,
+        signed_data: &impl AsRef<[u8]>,
+        verifier: &impl VerifyRaw,
     ) -> Result<(), AlgorithmError>;
 }
 
@@ -1015,14 +1212,13 @@ impl<Octets: AsRef<[u8]>, TN: ToName> RrsigExt for Rrsig<Octets, TN> {
                     Some(name) => name.compose_canonical(buf)?,
                     None => fqdn.compose_canonical(buf)?,
                 };
+                rr.rtype().compose(buf)?;
+                rr.class().compose(buf)?;
+                self.original_ttl().compose(buf)?;
+                rr.data().compose_canonical_len_rdata(buf)?;
             } else {
-                fqdn.compose_canonical(buf)?;
+                rr.compose_canonical_with_ttl(self.original_ttl(), buf)?;
             }
-
-            rr.rtype().compose(buf)?;
-            rr.class().compose(buf)?;
-            self.original_ttl().compose(buf)?;
-            rr.data().compose_canonical_len_rdata(buf)?;
         }
         Ok(())
     }
@@ -1061,121 +1257,440 @@ impl<Octets: AsRef<[u8]>, TN: ToName> RrsigExt for Rrsig<Octets, TN> {
         }
     }
 
-    fn verify_signed_data(
+    fn verify_signed_data_with(
         &self,
         dnskey: &Dnskey<impl AsRef<[u8]>>,
         signed_data: &impl AsRef<[u8]>,
+        verifier: &impl VerifyRaw,
     ) -> Result<(), AlgorithmError> {
-        let signature = self.signature().as_ref();
-        let signed_data = signed_data.as_ref();
-
         // Caller needs to ensure that the signature matches the key, but enforce the algorithm match
         if self.algorithm() != dnskey.algorithm() {
             return Err(AlgorithmError::InvalidData);
         }
+        if !verifier.supports_algorithm(self.algorithm()) {
+            return Err(AlgorithmError::Unsupported);
+        }
 
-        // Note: Canonicalize the algorithm, otherwise matching named variants against Int(_) is not going to work
-        let sec_alg = SecAlg::from_int(self.algorithm().to_int());
-        match sec_alg {
-            SecAlg::RSASHA1
-            | SecAlg::RSASHA1_NSEC3_SHA1
-            | SecAlg::RSASHA256
-            | SecAlg::RSASHA512 => {
-                let (algorithm, min_bytes) = match sec_alg {
-                    SecAlg::RSASHA1 | SecAlg::RSASHA1_NSEC3_SHA1 => (
-                        &signature::RSA_PKCS1_1024_8192_SHA1_FOR_LEGACY_USE_ONLY,
-                        1024 / 8,
-                    ),
-                    SecAlg::RSASHA256 => (
-                        &signature::RSA_PKCS1_1024_8192_SHA256_FOR_LEGACY_USE_ONLY,
-                        1024 / 8,
-                    ),
-                    SecAlg::RSASHA512 => (
-                        &signature::RSA_PKCS1_1024_8192_SHA512_FOR_LEGACY_USE_ONLY,
-                        1024 / 8,
-                    ),
-                    _ => unreachable!(),
-                };
-
-                // The key isn't available in either PEM or DER, so use the
-                // direct RSA verifier.
-                let (e, n) = rsa_exponent_modulus(dnskey, min_bytes)?;
-                let public_key =
-                    signature::RsaPublicKeyComponents { n: &n, e: &e };
-                public_key
-                    .verify(algorithm, signed_data, signature)
-                    .map_err(|_| AlgorithmError::BadSig)
+        let key = PublicKeyBytes::from_dnskey_format(
+            dnskey.algorithm(),
+            dnskey.public_key().as_ref(),
+        )
+        .map_err(|err| match err {
+            FromDnskeyError::UnsupportedAlgorithm
+            | FromDnskeyError::UnsupportedProtocol => {
+                AlgorithmError::Unsupported
             }
-            SecAlg::ECDSAP256SHA256 | SecAlg::ECDSAP384SHA384 => {
-                let algorithm = match sec_alg {
-                    SecAlg::ECDSAP256SHA256 => {
-                        &signature::ECDSA_P256_SHA256_FIXED
-                    }
-                    SecAlg::ECDSAP384SHA384 => {
-                        &signature::ECDSA_P384_SHA384_FIXED
-                    }
-                    _ => unreachable!(),
-                };
+            FromDnskeyError::InvalidKey => AlgorithmError::InvalidData,
+        })?;
+        verifier.verify_raw(
+            &key,
+            signed_data.as_ref(),
+            self.signature().as_ref(),
+        )
+    }
+}
+
+// This needs to match the algorithms supported by the default backend used
+// in verify_signed_data.
+#[cfg(feature = "ring")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+pub fn supported_algorithm(a: &SecAlg) -> bool {
+    ring::Verifier.supports_algorithm(*a)
+}
 
-                // Add 0x4 identifier to the ECDSA pubkey as expected by ring.
-                let public_key = dnskey.public_key().as_ref();
-                let mut key = Vec::with_capacity(public_key.len() + 1);
-                key.push(0x4);
-                key.extend_from_slice(public_key);
+//------------ RRset Validation ----------------------------------------------
 
-                signature::UnparsedPublicKey::new(algorithm, &key)
-                    .verify(signed_data, signature)
-                    .map_err(|_| AlgorithmError::BadSig)
-            }
-            SecAlg::ED25519 => {
-                let key = dnskey.public_key();
-                signature::UnparsedPublicKey::new(&signature::ED25519, &key)
-                    .verify(signed_data, signature)
-                    .map_err(|_| AlgorithmError::BadSig)
+/// Validates an RRset using a set of trusted keys.
+///
+/// The RRset is given via its records in `rrset` which must all have the
+/// same owner name, class, and record type. The signatures found in
+/// `rrsigs` are checked against the DNSKEYs in `keys` which are considered
+/// to be the trusted apex keys of `zone`. Signatures covering a different
+/// record type or having a different owner name are ignored, so `rrsigs`
+/// can contain all the RRSIG records for the owner name. Signature validity
+/// windows are checked against `now`. Signatures are verified using the
+/// cryptographic backend `verifier`.
+///
+/// This follows [RFC 4035, section 5.3]: The RRset is secure if at least
+/// one signature by one of the keys can be verified. If there are no keys
+/// at all, the RRset is bogus. If none of the keys uses an algorithm
+/// supported by `verifier`, the RRset is insecure as there is no supported
+/// authentication path. Otherwise, the RRset is bogus and the reason refers
+/// to the signature that made it the furthest through the checks.
+///
+/// If the signature has fewer labels than the owner name, the RRset was
+/// synthesized from a wildcard. In this case, the caller still needs to
+/// check that the original query name does not exist. The
+/// [`RrsigExt::wildcard_closest_encloser`] method provides the name of
+/// the closest encloser for that check.
+///
+/// [RFC 4035, section 5.3]: https://tools.ietf.org/html/rfc4035#section-5.3
+pub fn validate_rrset<N, D>(
+    rrset: &[Record<N, D>],
+    rrsigs: &[Record<impl ToName, Rrsig<impl AsRef<[u8]>, impl ToName>>],
+    zone: &impl ToName,
+    keys: &[Dnskey<impl AsRef<[u8]>>],
+    now: Timestamp,
+    verifier: &impl VerifyRaw,
+) -> ValidationStatus
+where
+    N: ToName,
+    D: RecordData + CanonicalOrd + ComposeRecordData,
+{
+    validate_with_keys(rrset, rrsigs, zone, keys.iter(), now, verifier)
+}
+
+/// Validates an RRset using the keys produced by an iterator.
+///
+/// This is the implementation of [`validate_rrset`].
+fn validate_with_keys<'a, N, D, Octs>(
+    rrset: &[Record<N, D>],
+    rrsigs: &[Record<impl ToName, Rrsig<impl AsRef<[u8]>, impl ToName>>],
+    zone: &impl ToName,
+    keys: impl Iterator<Item = &'a Dnskey<Octs>> + Clone,
+    now: Timestamp,
+    verifier: &impl VerifyRaw,
+) -> ValidationStatus
+where
+    N: ToName,
+    D: RecordData + CanonicalOrd + ComposeRecordData,
+    Octs: AsRef<[u8]> + 'a,
+{
+    let first = match rrset.first() {
+        Some(first) => first,
+        None => return ValidationStatus::Bogus(BogusReason::MalformedRrset),
+    };
+    let (owner, class, rtype) = (first.owner(), first.class(), first.rtype());
+    if rrset.iter().any(|rr| {
+        !rr.owner().name_eq(owner)
+            || rr.class() != class
+            || rr.rtype() != rtype
+    }) {
+        return ValidationStatus::Bogus(BogusReason::MalformedRrset);
+    }
+
+    // Without any keys, there is nothing the RRset could be secure with.
+    // This must not be mistaken for unsupported algorithms below.
+    if keys.clone().next().is_none() {
+        return ValidationStatus::Bogus(BogusReason::DnskeyMissing);
+    }
+    if !keys
+        .clone()
+        .any(|key| verifier.supports_algorithm(key.algorithm()))
+    {
+        return ValidationStatus::Insecure;
+    }
+
+    let mut sorted: Vec<_> = rrset.iter().collect();
+    let mut reason = BogusReason::RrsigsMissing;
+    for sig in rrsigs {
+        if !sig.owner().name_eq(owner)
+            || sig.class() != class
+            || sig.data().type_covered() != rtype
+        {
+            continue;
+        }
+        let keys = keys.clone();
+        match check_rrsig(
+            sig.data(),
+            owner,
+            zone,
+            keys,
+            &mut sorted,
+            now,
+            verifier,
+        ) {
+            Ok(()) => return ValidationStatus::Secure,
+            Err(err) => {
+                if err.rank() > reason.rank() {
+                    reason = err
+                }
             }
-            _ => Err(AlgorithmError::Unsupported),
         }
     }
+    ValidationStatus::Bogus(reason)
 }
 
-// This needs to match the algorithms supported in signed_data.
-pub fn supported_algorithm(a: &SecAlg) -> bool {
-    *a == SecAlg::RSASHA1
-        || *a == SecAlg::RSASHA1_NSEC3_SHA1
-        || *a == SecAlg::RSASHA256
-        || *a == SecAlg::RSASHA512
-        || *a == SecAlg::ECDSAP256SHA256
+/// Validates the DNSKEY RRset of a zone using its DS RRset.
+///
+/// The DS records in `ds` are the trusted delegation signer records of
+/// the zone, either taken from a validated DS RRset of the parent zone or
+/// from a trust anchor. The DNSKEY RRset is given via its records in
+/// `dnskeys` and its signatures in `rrsigs`. Signatures are verified using
+/// the cryptographic backend `verifier`.
+///
+/// This follows [RFC 4035, section 5.2]: The DNSKEY RRset is secure if it
+/// is signed by one of its own keys that matches one of the DS records. If
+/// none of the DS records uses both a digest supported by this crate and a
+/// key algorithm supported by `verifier`, the DNSKEY RRset is insecure.
+///
+/// If the DNSKEY RRset is secure, its keys can be used with
+/// [`validate_rrset`] to validate the other RRsets of the zone.
+///
+/// [RFC 4035, section 5.2]: https://tools.ietf.org/html/rfc4035#section-5.2
+pub fn validate_dnskey_rrset<N, Octs>(
+    dnskeys: &[Record<N, Dnskey<Octs>>],
+    rrsigs: &[Record<impl ToName, Rrsig<impl AsRef<[u8]>, impl ToName>>],
+    ds: &[Ds<impl AsRef<[u8]>>],
+    now: Timestamp,
+    verifier: &impl VerifyRaw,
+) -> ValidationStatus
+where
+    N: ToName,
+    Octs: AsRef<[u8]>,
+{
+    let zone = match dnskeys.first() {
+        Some(first) => first.owner(),
+        None => return ValidationStatus::Bogus(BogusReason::DnskeyMissing),
+    };
+
+    let ds: Vec<_> = ds
+        .iter()
+        .filter(|ds| {
+            verifier.supports_algorithm(ds.algorithm())
+                && supported_digest(&ds.digest_type())
+        })
+        .collect();
+    if ds.is_empty() {
+        return ValidationStatus::Insecure;
+    }
+
+    // Only keys that match a DS record may sign the DNSKEY RRset.
+    let keys: Vec<_> = dnskeys
+        .iter()
+        .map(|rr| rr.data())
+        .filter(|key| {
+            !key.is_revoked()
+                && ds.iter().any(|ds| {
+                    ds.key_tag() == key.key_tag()
+                        && ds.algorithm() == key.algorithm()
+                        && key
+                            .digest(zone, ds.digest_type())
+                            .map(|digest| {
+                                digest.as_ref() == ds.digest().as_ref()
+                            })
+                            .unwrap_or(false)
+                })
+        })
+        .collect();
+    if keys.is_empty() {
+        return ValidationStatus::Bogus(BogusReason::DnskeyMissing);
+    }
+    validate_with_keys(dnskeys, rrsigs, zone, keys.into_iter(), now, verifier)
 }
 
-/// Return the RSA exponent and modulus components from DNSKEY record data.
-fn rsa_exponent_modulus(
-    dnskey: &Dnskey<impl AsRef<[u8]>>,
-    min_len: usize,
-) -> Result<(&[u8], &[u8]), AlgorithmError> {
-    let public_key = dnskey.public_key().as_ref();
-    if public_key.len() <= 3 {
-        return Err(AlgorithmError::InvalidData);
+/// Checks a single signature over an RRset.
+///
+/// Follows [RFC 4035, section 5.3.1] for the checks before attempting to
+/// verify the signature with one of the matching keys.
+///
+/// [RFC 4035, section 5.3.1]: https://tools.ietf.org/html/rfc4035#section-5.3.1
+fn check_rrsig<'a, N, D, Octs>(
+    rrsig: &Rrsig<impl AsRef<[u8]>, impl ToName>,
+    owner: &N,
+    zone: &impl ToName,
+    keys: impl Iterator<Item = &'a Dnskey<Octs>>,
+    rrset: &mut [&Record<N, D>],
+    now: Timestamp,
+    verifier: &impl VerifyRaw,
+) -> Result<(), BogusReason>
+where
+    N: ToName,
+    D: RecordData + CanonicalOrd + ComposeRecordData,
+    Octs: AsRef<[u8]> + 'a,
+{
+    // The signer's name must be the zone containing the RRset.
+    if !rrsig.signer_name().name_eq(zone) || !owner.ends_with(zone) {
+        return Err(BogusReason::SignerMismatch);
     }
 
-    let (pos, exp_len) = match public_key[0] {
-        0 => (
-            3,
-            (usize::from(public_key[1]) << 8) | usize::from(public_key[2]),
-        ),
-        len => (1, usize::from(len)),
-    };
+    // Subtract the root label which isn't counted in the Labels field.
+    if owner.iter_labels().count() - 1 < usize::from(rrsig.labels()) {
+        return Err(BogusReason::LabelCount);
+    }
 
-    // Check if there's enough space for exponent and modulus.
-    if public_key[pos..].len() < pos + exp_len {
-        return Err(AlgorithmError::InvalidData);
-    };
+    let (inception, expiration) = (rrsig.inception(), rrsig.expiration());
+    if inception.canonical_gt(&expiration) {
+        return Err(BogusReason::ExpiredBeforeValid);
+    }
+    if now.canonical_lt(&inception) {
+        return Err(BogusReason::NotYetValid);
+    }
+    if now.canonical_gt(&expiration) {
+        return Err(BogusReason::Expired);
+    }
+
+    let mut keys = keys
+        .filter(|key| {
+            key.algorithm() == rrsig.algorithm()
+                && key.key_tag() == rrsig.key_tag()
+                && !key.is_revoked()
+        })
+        .peekable();
+    if keys.peek().is_none() {
+        return Err(BogusReason::DnskeyMissing);
+    }
+
+    let mut signed_data = Vec::new();
+    rrsig
+        .signed_data(&mut signed_data, rrset)
+        .expect("infallible");
+    let mut reason = BogusReason::NoZoneKeyBit;
+    for key in keys {
+        if !key.is_zone_key() {
+            continue;
+        }
+        if rrsig
+            .verify_signed_data_with(key, &signed_data, verifier)
+            .is_ok()
+        {
+            return Ok(());
+        }
+        reason = BogusReason::BadSignature;
+    }
+    Err(reason)
+}
+
+//------------ ValidationStatus ----------------------------------------------
+
+/// The outcome of validating an RRset.
+///
+/// The states are described in [RFC 4033, section 5].
+///
+/// [RFC 4033, section 5]: https://tools.ietf.org/html/rfc4033#section-5
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ValidationStatus {
+    /// The RRset has a valid signature by a trusted key.
+    Secure,
+
+    /// There is no supported authentication path for the RRset.
+    Insecure,
+
+    /// The RRset should be signed but failed to validate.
+    Bogus(BogusReason),
+}
+
+impl ValidationStatus {
+    /// Returns whether the status is secure.
+    pub fn is_secure(self) -> bool {
+        matches!(self, ValidationStatus::Secure)
+    }
+}
+
+//--- Display
+
+impl fmt::Display for ValidationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationStatus::Secure => f.write_str("secure"),
+            ValidationStatus::Insecure => f.write_str("insecure"),
+            ValidationStatus::Bogus(reason) => write!(f, "bogus: {}", reason),
+        }
+    }
+}
+
+//------------ BogusReason ---------------------------------------------------
 
-    // Check for minimum supported key size
-    if public_key[pos..].len() < min_len {
-        return Err(AlgorithmError::Unsupported);
+/// The reason why an RRset failed validation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BogusReason {
+    /// The records do not form a single, non-empty RRset.
+    MalformedRrset,
+
+    /// There are no signatures covering the RRset.
+    RrsigsMissing,
+
+    /// The signer's name is not the name of the zone containing the RRset.
+    SignerMismatch,
+
+    /// The signature has more labels than the RRset's owner name.
+    LabelCount,
+
+    /// The signature's inception time is after its expiration time.
+    ExpiredBeforeValid,
+
+    /// The signature's inception time is in the future.
+    NotYetValid,
+
+    /// The signature has expired.
+    Expired,
+
+    /// There is no trusted key matching the signature.
+    DnskeyMissing,
+
+    /// The matching key does not have the Zone Key flag set.
+    NoZoneKeyBit,
+
+    /// The cryptographic signature could not be verified.
+    BadSignature,
+}
+
+impl BogusReason {
+    /// Returns the extended DNS error code for this reason.
+    ///
+    /// The codes are defined in [RFC 8914].
+    ///
+    /// [RFC 8914]: https://tools.ietf.org/html/rfc8914
+    pub fn ede_code(self) -> ExtendedErrorCode {
+        match self {
+            BogusReason::RrsigsMissing => ExtendedErrorCode::RRSIGS_MISSING,
+            BogusReason::ExpiredBeforeValid => {
+                ExtendedErrorCode::SIGNATURE_EXPIRED_BEFORE_VALID
+            }
+            BogusReason::NotYetValid => {
+                ExtendedErrorCode::SIGNATURE_NOT_YET_VALID
+            }
+            BogusReason::Expired => ExtendedErrorCode::SIGNATURE_EXPIRED,
+            BogusReason::DnskeyMissing => ExtendedErrorCode::DNSKEY_MISSING,
+            BogusReason::NoZoneKeyBit => {
+                ExtendedErrorCode::NO_ZONE_KEY_BIT_SET
+            }
+            BogusReason::MalformedRrset
+            | BogusReason::SignerMismatch
+            | BogusReason::LabelCount
+            | BogusReason::BadSignature => ExtendedErrorCode::DNSSEC_BOGUS,
+        }
+    }
+
+    /// Returns how far through the checks a signature made it.
+    ///
+    /// When several signatures fail, the reason with the highest rank is
+    /// reported as it is the most informative.
+    fn rank(self) -> u8 {
+        match self {
+            BogusReason::MalformedRrset => 0,
+            BogusReason::RrsigsMissing => 1,
+            BogusReason::SignerMismatch => 2,
+            BogusReason::LabelCount => 3,
+            BogusReason::ExpiredBeforeValid => 4,
+            BogusReason::NotYetValid => 5,
+            BogusReason::Expired => 6,
+            BogusReason::DnskeyMissing => 7,
+            BogusReason::NoZoneKeyBit => 8,
+            BogusReason::BadSignature => 9,
+        }
     }
+}
+
+//--- Display
 
-    Ok(public_key[pos..].split_at(exp_len))
+impl fmt::Display for BogusReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            BogusReason::MalformedRrset => "malformed RRset",
+            BogusReason::RrsigsMissing => "no signatures",
+            BogusReason::SignerMismatch => "signer name mismatch",
+            BogusReason::LabelCount => "invalid label count",
+            BogusReason::ExpiredBeforeValid => {
+                "signature expired before valid"
+            }
+            BogusReason::NotYetValid => "signature not yet valid",
+            BogusReason::Expired => "signature expired",
+            BogusReason::DnskeyMissing => "no matching key",
+            BogusReason::NoZoneKeyBit => "key is not a zone key",
+            BogusReason::BadSignature => "bad signature",
+        })
+    }
 }
 
 //============ Error Types ===================================================
@@ -1269,10 +1784,39 @@ impl fmt::Display for AlgorithmError {
 
 impl error::Error for AlgorithmError {}
 
+//------------ Nsec3HashError ------------------------------------------------
+
+/// An NSEC3 hash could not be calculated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Nsec3HashError {
+    /// The hash algorithm is not supported.
+    UnsupportedAlgorithm,
+
+    /// The hashed owner name would be longer than 255 octets.
+    LongName,
+}
+
+//--- Display, Error
+
+impl fmt::Display for Nsec3HashError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Nsec3HashError::UnsupportedAlgorithm => {
+                f.write_str("unsupported NSEC3 hash algorithm")
+            }
+            Nsec3HashError::LongName => {
+                f.write_str("hashed owner name too long")
+            }
+        }
+    }
+}
+
+impl error::Error for Nsec3HashError {}
+
 //============ Test ==========================================================
 
 #[cfg(test)]
-#[cfg(feature = "std")]
+#[cfg(feature = "ring")]
 mod test {
     use super::*;
     use crate::base::iana::{Class, Rtype};
@@ -1462,6 +2006,52 @@ mod test {
         assert!(dnskey.digest(&owner, DigestAlg::GOST).is_err());
     }
 
+    #[test]
+    fn nsec3_hash_rfc5155() {
+        // RFC 5155, appendix A.
+        let param = Nsec3param::new(
+            Nsec3HashAlg::SHA1,
+            0,
+            12,
+            Nsec3Salt::from_octets(vec![0xaa, 0xbb, 0xcc, 0xdd]).unwrap(),
+        );
+        let apex = Name::from_str("example.").unwrap();
+        for (name, hash) in [
+            ("example.", "0p9mhaveqvm6t7vbl5lop2u3t2rp3tom"),
+            ("a.example.", "35mthgpgcu1qg68fab165klnsnk3dpvl"),
+            ("ai.example.", "gjeqe526plbf1g8mklp59enfd789njgi"),
+            ("ns1.example.", "2t7b4g4vsa5smi47k61mv5bv1a22bojr"),
+            ("w.example.", "k8udemvp1j2f7eg6jebps17vp3n8i58h"),
+            ("*.w.example.", "r53bq7cc2uvmubfu5ocmm6pers9tk9en"),
+            ("X.W.Example.", "b4um86eghhds6nea196smvmlo4ors995"),
+        ] {
+            let name = Name::from_str(name).unwrap();
+            assert_eq!(
+                param.hash_name(&name).unwrap().to_string(),
+                hash.to_ascii_uppercase()
+            );
+            assert_eq!(
+                param.hashed_owner_name(&name, &apex).unwrap(),
+                Name::from_str(&format!("{hash}.example.")).unwrap()
+            );
+        }
+
+        assert_eq!(
+            nsec3_hash(
+                &apex,
+                Nsec3HashAlg::from_int(2),
+                0,
+                &Nsec3Salt::<Vec<u8>>::empty()
+            ),
+            Err(Nsec3HashError::UnsupportedAlgorithm)
+        );
+        let long = Name::from_str(&"a.".repeat(115)).unwrap();
+        assert_eq!(
+            param.hashed_owner_name(&apex, &long),
+            Err(Nsec3HashError::LongName)
+        );
+    }
+
     fn rrsig_verify_dnskey(ksk: Dnskey, zsk: Dnskey, rrsig: Rrsig) {
         let mut records: Vec<_> = [&ksk, &zsk]
             .iter()
@@ -1488,6 +2078,19 @@ mod test {
         // Test verifier
         assert!(rrsig.verify_signed_data(&ksk, &signed_data).is_ok());
         assert!(rrsig.verify_signed_data(&zsk, &signed_data).is_err());
+
+        // Test the other backends
+        #[cfg(feature = "openssl")]
+        {
+            let verifier = openssl::Verifier;
+            assert_eq!(
+                rrsig.verify_signed_data_with(&ksk, &signed_data, &verifier),
+                Ok(())
+            );
+            assert!(rrsig
+                .verify_signed_data_with(&zsk, &signed_data, &verifier)
+                .is_err());
+        }
     }
 
     #[test]
@@ -1734,4 +2337,264 @@ mod test {
         // Test verifier
         assert_eq!(rrsig.verify_signed_data(&key, &signed_data), Ok(()));
     }
+
+    // Returns the DNSKEY RRset and its signature for cloudflare.com.
+    fn cloudflare_dnskeys() -> (Vec<Record<Name, Dnskey>>, Record<Name, Rrsig>)
+    {
+        let owner = Name::from_str("cloudflare.com.").unwrap();
+        let keys = [
+            (
+                257,
+                "mdsswUyr3DPW132mOi8V9xESWE8jTo0dxCjjnopKl+GqJxpVXckHAe\
+                F+KkxLbxILfDLUT0rAK9iUzy1L53eKGQ==",
+            ),
+            (
+                256,
+                "oJMRESz5E4gYzS/q6XDrvU1qMPYIjCWzJaOau8XNEZeqCYKD5ar0IR\
+                d8KqXXFJkqmVfRvMGPmM1x8fGAa2XhSA==",
+            ),
+        ]
+        .into_iter()
+        .map(|(flags, key)| {
+            Record::new(
+                owner.clone(),
+                Class::IN,
+                Ttl::from_secs(3600),
+                Dnskey::new(
+                    flags,
+                    3,
+                    SecAlg::ECDSAP256SHA256,
+                    base64::decode::<Vec<u8>>(key).unwrap(),
+                )
+                .unwrap(),
+            )
+        })
+        .collect();
+        let rrsig = Rrsig::new(
+            Rtype::DNSKEY,
+            SecAlg::ECDSAP256SHA256,
+            2,
+            Ttl::from_secs(3600),
+            1560314494.into(),
+            1555130494.into(),
+            2371,
+            owner.clone(),
+            base64::decode::<Vec<u8>>(
+                "8jnAGhG7O52wmL065je10XQztRX1vK8P8KBSyo71Z6h5wAT9+GFxKBaE\
+                zcJBLvRmofYFDAhju21p1uTfLaYHrg==",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        (
+            keys,
+            Record::new(owner, Class::IN, Ttl::from_secs(3600), rrsig),
+        )
+    }
+
+    fn ds_for(record: &Record<Name, Dnskey>, digest_type: DigestAlg) -> Ds {
+        let digest = record.data().digest(record.owner(), digest_type);
+        Ds::new(
+            record.data().key_tag(),
+            record.data().algorithm(),
+            digest_type,
+            digest.map(|d| d.as_ref().to_vec()).unwrap_or_default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn validate_dnskey_rrset_status() {
+        let (keys, sig) = cloudflare_dnskeys();
+        let sigs = [sig];
+        let ds = [ds_for(&keys[0], DigestAlg::SHA256)];
+        let valid = Timestamp::from(1558000000);
+
+        assert_eq!(
+            validate_dnskey_rrset(&keys, &sigs, &ds, valid, &ring::Verifier),
+            ValidationStatus::Secure
+        );
+        assert_eq!(
+            validate_dnskey_rrset(
+                &keys,
+                &sigs,
+                &ds,
+                Timestamp::from(1560314495),
+                &ring::Verifier
+            ),
+            ValidationStatus::Bogus(BogusReason::Expired)
+        );
+        assert_eq!(
+            validate_dnskey_rrset(
+                &keys,
+                &sigs,
+                &ds,
+                Timestamp::from(1555130493),
+                &ring::Verifier
+            ),
+            ValidationStatus::Bogus(BogusReason::NotYetValid)
+        );
+        assert_eq!(
+            validate_dnskey_rrset(
+                &keys,
+                &[] as &[Record<Name, Rrsig>],
+                &ds,
+                valid,
+                &ring::Verifier
+            ),
+            ValidationStatus::Bogus(BogusReason::RrsigsMissing)
+        );
+
+        // The ZSK did not sign the DNSKEY RRset.
+        let zsk_ds = [ds_for(&keys[1], DigestAlg::SHA256)];
+        assert_eq!(
+            validate_dnskey_rrset(
+                &keys,
+                &sigs,
+                &zsk_ds,
+                valid,
+                &ring::Verifier
+            ),
+            ValidationStatus::Bogus(BogusReason::DnskeyMissing)
+        );
+
+        // Without a supported digest, there is no authentication path.
+        let gost_ds = [ds_for(&keys[0], DigestAlg::GOST)];
+        assert_eq!(
+            validate_dnskey_rrset(
+                &keys,
+                &sigs,
+                &gost_ds,
+                valid,
+                &ring::Verifier
+            ),
+            ValidationStatus::Insecure
+        );
+
+        // A modified RRset fails the signature check.
+        assert_eq!(
+            validate_dnskey_rrset(
+                &keys[..1],
+                &sigs,
+                &ds,
+                valid,
+                &ring::Verifier
+            ),
+            ValidationStatus::Bogus(BogusReason::BadSignature)
+        );
+    }
+
+    #[test]
+    fn validate_rrset_wildcard() {
+        // RFC 4035, appendix B.6.
+        let zone = Name::from_str("example.").unwrap();
+        let key = Dnskey::new(
+            256,
+            3,
+            SecAlg::RSASHA1,
+            base64::decode::<Vec<u8>>(
+                "AQOy1bZVvpPqhg4j7EJoM9rI3ZmyEx2OzDBVrZy/lvI5CQePxX\
+                HZS4i8dANH4DX3tbHol61ek8EFMcsGXxKciJFHyhl94C+NwILQd\
+                zsUlSFovBZsyl/NX6yEbtw/xN9ZNcrbYvgjjZ/UVPZIySFNsgEY\
+                vh0z2542lzMKR4Dh8uZffQ==",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let owner = Name::from_str("a.z.w.example.").unwrap();
+        let rrsig = Rrsig::new(
+            Rtype::MX,
+            SecAlg::RSASHA1,
+            2,
+            Ttl::from_secs(3600),
+            Timestamp::from_str("20040509183619").unwrap(),
+            Timestamp::from_str("20040409183619").unwrap(),
+            38519,
+            zone.clone(),
+            base64::decode::<Vec<u8>>(
+                "OMK8rAZlepfzLWW75Dxd63jy2wswESzxDKG2f9AMN1CytCd10cYI\
+                 SAxfAdvXSZ7xujKAtPbctvOQ2ofO7AZJ+d01EeeQTVBPq4/6KCWhq\
+                 e2XTjnkVLNvvhnc0u28aoSsG0+4InvkkOHknKxw4kX18MMR34i8lC\
+                 36SR5xBni8vHI=",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let sigs = [Record::new(
+            owner.clone(),
+            Class::IN,
+            Ttl::from_secs(3600),
+            rrsig,
+        )];
+        let rrset = [Record::new(
+            owner,
+            Class::IN,
+            Ttl::from_secs(3600),
+            Mx::new(1, Name::from_str("ai.example.").unwrap()),
+        )];
+        let now = Timestamp::from_str("20040420000000").unwrap();
+
+        assert!(validate_rrset(
+            &rrset,
+            &sigs,
+            &zone,
+            core::slice::from_ref(&key),
+            now,
+            &ring::Verifier
+        )
+        .is_secure());
+        assert_eq!(
+            validate_rrset(
+                &rrset,
+                &sigs,
+                &Name::from_str("w.example.").unwrap(),
+                core::slice::from_ref(&key),
+                now,
+                &ring::Verifier
+            ),
+            ValidationStatus::Bogus(BogusReason::SignerMismatch)
+        );
+
+        // A key that did not make the signature.
+        let (keys, _) = cloudflare_dnskeys();
+        assert_eq!(
+            validate_rrset(
+                &rrset,
+                &sigs,
+                &zone,
+                &[keys[0].data().clone()],
+                now,
+                &ring::Verifier
+            ),
+            ValidationStatus::Bogus(BogusReason::DnskeyMissing)
+        );
+
+        // No keys at all must not make a signed RRset insecure.
+        assert_eq!(
+            validate_rrset(
+                &rrset,
+                &sigs,
+                &zone,
+                &[] as &[Dnskey],
+                now,
+                &ring::Verifier
+            ),
+            ValidationStatus::Bogus(BogusReason::DnskeyMissing)
+        );
+        assert_eq!(
+            BogusReason::DnskeyMissing.ede_code(),
+            ExtendedErrorCode::DNSKEY_MISSING
+        );
+        assert_eq!(
+            validate_rrset(
+                &[] as &[Record<Name, Mx<Name>>],
+                &sigs,
+                &zone,
+                &[key],
+                now,
+                &ring::Verifier
+            ),
+            ValidationStatus::Bogus(BogusReason::MalformedRrset)
+        );
+    }
 }
@@ -0,0 +1,163 @@
+//! DNSSEC signature verification using OpenSSL.
+//!
+//! This backend supports the following algorithms:
+//!
+//! - RSA/SHA-1, RSA/SHA-1 with NSEC3, RSA/SHA-256, and RSA/SHA-512
+//!   (1024-bit keys or larger)
+//! - ECDSA P-256/SHA-256
+//! - ECDSA P-384/SHA-384
+//! - Ed25519
+//! - Ed448
+
+#![cfg(feature = "openssl")]
+#![cfg_attr(docsrs, doc(cfg(feature = "openssl")))]
+
+use openssl::{
+    bn::{BigNum, BigNumContext},
+    ec::{EcGroup, EcKey, EcPoint},
+    ecdsa::EcdsaSig,
+    error::ErrorStack,
+    hash::MessageDigest,
+    nid::Nid,
+    pkey::{self, PKey, Public},
+    rsa::Rsa,
+    sign,
+};
+
+use crate::base::iana::SecAlg;
+
+use super::{AlgorithmError, PublicKeyBytes, VerifyRaw};
+
+//----------- Verifier -------------------------------------------------------
+
+/// A signature verifier backed by OpenSSL.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Verifier;
+
+//--- VerifyRaw
+
+impl VerifyRaw for Verifier {
+    fn supports_algorithm(&self, algorithm: SecAlg) -> bool {
+        matches!(
+            algorithm,
+            SecAlg::RSASHA1
+                | SecAlg::RSASHA1_NSEC3_SHA1
+                | SecAlg::RSASHA256
+                | SecAlg::RSASHA512
+                | SecAlg::ECDSAP256SHA256
+                | SecAlg::ECDSAP384SHA384
+                | SecAlg::ED25519
+                | SecAlg::ED448
+        )
+    }
+
+    fn verify_raw(
+        &self,
+        key: &PublicKeyBytes,
+        signed_data: &[u8],
+        signature: &[u8],
+    ) -> Result<(), AlgorithmError> {
+        // Use the same lower bound on RSA key sizes as the ring backend.
+        if let PublicKeyBytes::RsaSha1(k)
+        | PublicKeyBytes::RsaSha1Nsec3Sha1(k)
+        | PublicKeyBytes::RsaSha256(k)
+        | PublicKeyBytes::RsaSha512(k) = key
+        {
+            if k.n.len() < 1024 / 8 {
+                return Err(AlgorithmError::Unsupported);
+            }
+        }
+
+        match verify(key, signed_data, signature) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(AlgorithmError::BadSig),
+            Err(_) => Err(AlgorithmError::InvalidData),
+        }
+    }
+}
+
+//----------- Helpers --------------------------------------------------------
+
+/// Verifies a signature, returning whether it is valid.
+///
+/// Errors are only returned if the key or signature could not be imported.
+fn verify(
+    key: &PublicKeyBytes,
+    signed_data: &[u8],
+    signature: &[u8],
+) -> Result<bool, ErrorStack> {
+    match key {
+        PublicKeyBytes::RsaSha1(k)
+        | PublicKeyBytes::RsaSha1Nsec3Sha1(k)
+        | PublicKeyBytes::RsaSha256(k)
+        | PublicKeyBytes::RsaSha512(k) => {
+            let digest = match key {
+                PublicKeyBytes::RsaSha256(_) => MessageDigest::sha256(),
+                PublicKeyBytes::RsaSha512(_) => MessageDigest::sha512(),
+                _ => MessageDigest::sha1(),
+            };
+
+            let n = BigNum::from_slice(&k.n)?;
+            let e = BigNum::from_slice(&k.e)?;
+            let key = PKey::from_rsa(Rsa::from_public_components(n, e)?)?;
+            let mut verifier = sign::Verifier::new(digest, &key)?;
+            verifier.set_rsa_padding(openssl::rsa::Padding::PKCS1)?;
+            verifier.verify_oneshot(signature, signed_data)
+        }
+        PublicKeyBytes::EcdsaP256Sha256(k) => verify_ecdsa(
+            Nid::X9_62_PRIME256V1,
+            MessageDigest::sha256(),
+            k.as_slice(),
+            signed_data,
+            signature,
+        ),
+        PublicKeyBytes::EcdsaP384Sha384(k) => verify_ecdsa(
+            Nid::SECP384R1,
+            MessageDigest::sha384(),
+            k.as_slice(),
+            signed_data,
+            signature,
+        ),
+        PublicKeyBytes::Ed25519(k) => {
+            let id = pkey::Id::ED25519;
+            let key = PKey::public_key_from_raw_bytes(k.as_slice(), id)?;
+            let mut verifier = sign::Verifier::new_without_digest(&key)?;
+            verifier.verify_oneshot(signature, signed_data)
+        }
+        PublicKeyBytes::Ed448(k) => {
+            let id = pkey::Id::ED448;
+            let key = PKey::public_key_from_raw_bytes(k.as_slice(), id)?;
+            let mut verifier = sign::Verifier::new_without_digest(&key)?;
+            verifier.verify_oneshot(signature, signed_data)
+        }
+    }
+}
+
+/// Verifies an ECDSA signature in the fixed-width format used by DNSSEC.
+fn verify_ecdsa(
+    curve: Nid,
+    digest: MessageDigest,
+    key: &[u8],
+    signed_data: &[u8],
+    signature: &[u8],
+) -> Result<bool, ErrorStack> {
+    let group = EcGroup::from_curve_name(curve)?;
+    let mut ctx = BigNumContext::new()?;
+    let point = EcPoint::from_bytes(&group, key, &mut ctx)?;
+    let key: PKey<Public> =
+        PKey::from_ec_key(EcKey::from_public_key(&group, &point)?)?;
+
+    // Convert from the fixed representation to DER.
+    if signature.len() % 2 != 0 {
+        return Ok(false);
+    }
+    let (r, s) = signature.split_at(signature.len() / 2);
+    let signature = EcdsaSig::from_private_components(
+        BigNum::from_slice(r)?,
+        BigNum::from_slice(s)?,
+    )?
+    .to_der()?;
+
+    let mut verifier = sign::Verifier::new(digest, &key)?;
+    verifier.verify_oneshot(&signature, signed_data)
+}
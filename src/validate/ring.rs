@@ -0,0 +1,99 @@
+//! DNSSEC signature verification using `ring`.
+//!
+//! This backend supports the following algorithms:
+//!
+//! - RSA/SHA-1, RSA/SHA-1 with NSEC3, RSA/SHA-256, and RSA/SHA-512
+//!   (1024-bit keys or larger)
+//! - ECDSA P-256/SHA-256
+//! - ECDSA P-384/SHA-384
+//! - Ed25519
+
+#![cfg(feature = "ring")]
+#![cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+
+use ring::signature;
+
+use crate::base::iana::SecAlg;
+
+use super::{AlgorithmError, PublicKeyBytes, VerifyRaw};
+
+//----------- Verifier -------------------------------------------------------
+
+/// A signature verifier backed by `ring`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Verifier;
+
+//--- VerifyRaw
+
+impl VerifyRaw for Verifier {
+    fn supports_algorithm(&self, algorithm: SecAlg) -> bool {
+        matches!(
+            algorithm,
+            SecAlg::RSASHA1
+                | SecAlg::RSASHA1_NSEC3_SHA1
+                | SecAlg::RSASHA256
+                | SecAlg::RSASHA512
+                | SecAlg::ECDSAP256SHA256
+                | SecAlg::ECDSAP384SHA384
+                | SecAlg::ED25519
+        )
+    }
+
+    fn verify_raw(
+        &self,
+        key: &PublicKeyBytes,
+        signed_data: &[u8],
+        signature: &[u8],
+    ) -> Result<(), AlgorithmError> {
+        match key {
+            PublicKeyBytes::RsaSha1(k)
+            | PublicKeyBytes::RsaSha1Nsec3Sha1(k)
+            | PublicKeyBytes::RsaSha256(k)
+            | PublicKeyBytes::RsaSha512(k) => {
+                let algorithm = match key {
+                    PublicKeyBytes::RsaSha256(_) => {
+                        &signature::RSA_PKCS1_1024_8192_SHA256_FOR_LEGACY_USE_ONLY
+                    }
+                    PublicKeyBytes::RsaSha512(_) => {
+                        &signature::RSA_PKCS1_1024_8192_SHA512_FOR_LEGACY_USE_ONLY
+                    }
+                    _ => &signature::RSA_PKCS1_1024_8192_SHA1_FOR_LEGACY_USE_ONLY,
+                };
+
+                // Ring doesn't support keys shorter than 1024 bits.
+                if k.n.len() < 1024 / 8 {
+                    return Err(AlgorithmError::Unsupported);
+                }
+
+                // The key isn't available in either PEM or DER, so use the
+                // direct RSA verifier.
+                signature::RsaPublicKeyComponents { n: &k.n, e: &k.e }
+                    .verify(algorithm, signed_data, signature)
+                    .map_err(|_| AlgorithmError::BadSig)
+            }
+            PublicKeyBytes::EcdsaP256Sha256(k) => {
+                signature::UnparsedPublicKey::new(
+                    &signature::ECDSA_P256_SHA256_FIXED,
+                    k.as_slice(),
+                )
+                .verify(signed_data, signature)
+                .map_err(|_| AlgorithmError::BadSig)
+            }
+            PublicKeyBytes::EcdsaP384Sha384(k) => {
+                signature::UnparsedPublicKey::new(
+                    &signature::ECDSA_P384_SHA384_FIXED,
+                    k.as_slice(),
+                )
+                .verify(signed_data, signature)
+                .map_err(|_| AlgorithmError::BadSig)
+            }
+            PublicKeyBytes::Ed25519(k) => signature::UnparsedPublicKey::new(
+                &signature::ED25519,
+                k.as_slice(),
+            )
+            .verify(signed_data, signature)
+            .map_err(|_| AlgorithmError::BadSig),
+            PublicKeyBytes::Ed448(_) => Err(AlgorithmError::Unsupported),
+        }
+    }
+}
@@ -1,15 +1,35 @@
 //! Create DNSSEC trust anchors.
+//!
+//! Static trust anchors are created via [`TrustAnchors`]. Trust anchors can
+//! also be kept up to date automatically as described in [RFC 5011] using
+//! [`ManagedTrustAnchors`].
+//!
+//! Validation of domains whose DNSSEC setup is known to be broken can be
+//! disabled temporarily via [`NegativeTrustAnchors`] as described in
+//! [RFC 7646].
+//!
+//! [RFC 5011]: https://www.rfc-editor.org/info/rfc5011
+//! [RFC 7646]: https://www.rfc-editor.org/info/rfc7646
 
 use super::context::Error;
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::{Class, Rtype};
 use crate::base::name::{Chain, Name, ToName};
-use crate::base::{Record, RelativeName};
-use crate::rdata::ZoneRecordData;
+use crate::base::{Record, RelativeName, Ttl};
+use crate::rdata::dnssec::Timestamp;
+use crate::rdata::{Dnskey, Rrsig, ZoneRecordData};
+use crate::validate::{
+    validate_rrset, RrsigExt, ValidationStatus, VerifyRaw,
+};
 use crate::zonefile::inplace::{Entry, Zonefile};
 use bytes::Bytes;
-use std::fmt::Debug;
+use serde::{Deserialize, Serialize};
+use std::error;
+use std::fmt::{self, Debug};
 use std::io::Read;
 use std::slice::Iter;
 use std::sync::Arc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use std::vec::Vec;
 
 //----------- TrustAnchor ----------------------------------------------------
@@ -185,3 +205,779 @@ impl TrustAnchors {
             .max_by_key(|ta| ta.label_count)
     }
 }
+
+//----------- ManagedTrustAnchors --------------------------------------------
+
+/// The add hold-down time in seconds.
+///
+/// See [RFC 5011, section 2.4.1].
+///
+/// [RFC 5011, section 2.4.1]: https://www.rfc-editor.org/rfc/rfc5011#section-2.4.1
+const ADD_HOLD_DOWN: u64 = 30 * 24 * 3600;
+
+/// The remove hold-down time in seconds.
+///
+/// See [RFC 5011, section 2.4.2].
+///
+/// [RFC 5011, section 2.4.2]: https://www.rfc-editor.org/rfc/rfc5011#section-2.4.2
+const REMOVE_HOLD_DOWN: u64 = 30 * 24 * 3600;
+
+/// DNSSEC trust anchors that are updated automatically.
+///
+/// This implements automated updates of trust anchors as described in
+/// [RFC 5011]. For each trust point, i.e., each zone with trust anchors, the
+/// key signing keys are tracked through the states described by
+/// [`KeyState`]. Whenever a DNSKEY RRset for a trust point is received, it
+/// should be passed to [`update`][Self::update] together with its
+/// signatures. If the RRset is signed by a currently trusted key, new keys
+/// are added after the add hold-down time has passed, and keys which have
+/// been revoked are no longer trusted.
+///
+/// The keys currently trusted are available as [`TrustAnchors`] via
+/// [`trust_anchors`][Self::trust_anchors]. The state can be serialized and
+/// deserialized via serde. It should be saved after each update so that
+/// it survives restarts.
+///
+/// Fetching the DNSKEY RRsets, including the active refresh described in
+/// [RFC 5011, section 2.3], is left to the user.
+///
+/// [RFC 5011]: https://www.rfc-editor.org/info/rfc5011
+/// [RFC 5011, section 2.3]: https://www.rfc-editor.org/rfc/rfc5011#section-2.3
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ManagedTrustAnchors {
+    /// The trust points.
+    zones: Vec<TrustPoint>,
+}
+
+impl ManagedTrustAnchors {
+    /// Creates an empty set of managed trust anchors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a trusted key for a trust point.
+    ///
+    /// The key is trusted immediately. This is used for the initial trust
+    /// anchors which have to be configured out of band.
+    pub fn add_key(&mut self, owner: Name<Bytes>, dnskey: Dnskey<Bytes>) {
+        let zone = match self.zones.iter().position(|z| z.owner == owner) {
+            Some(idx) => &mut self.zones[idx],
+            None => {
+                self.zones.push(TrustPoint {
+                    owner,
+                    keys: Vec::new(),
+                });
+                self.zones.last_mut().unwrap()
+            }
+        };
+        match zone.find_mut(&dnskey) {
+            Some(key) => key.set_state(KeyState::Valid, None),
+            None => zone.keys.push(ManagedKey {
+                dnskey,
+                state: KeyState::Valid,
+                timer: None,
+            }),
+        }
+    }
+
+    /// Returns the keys of a trust point and their state.
+    pub fn keys<'a>(
+        &'a self,
+        owner: &'a impl ToName,
+    ) -> impl Iterator<Item = (&'a Dnskey<Bytes>, KeyState)> + 'a {
+        self.zones
+            .iter()
+            .filter(move |zone| zone.owner.name_eq(owner))
+            .flat_map(|zone| zone.keys.iter())
+            .map(|key| (&key.dnskey, key.state))
+    }
+
+    /// Returns the currently trusted keys.
+    ///
+    /// These are the keys in the [`Valid`][KeyState::Valid] and
+    /// [`Missing`][KeyState::Missing] states.
+    pub fn trust_anchors(&self) -> TrustAnchors {
+        let mut res = TrustAnchors::empty();
+        for zone in &self.zones {
+            for key in zone.keys.iter().filter(|key| key.state.is_trusted()) {
+                let owner = RelativeName::empty_bytes()
+                    .chain(zone.owner.clone())
+                    .expect("long name");
+                res.add(Record::new(
+                    owner,
+                    Class::IN,
+                    Ttl::ZERO,
+                    ZoneRecordData::Dnskey(key.dnskey.clone()),
+                ));
+            }
+        }
+        res
+    }
+
+    /// Updates a trust point from its current DNSKEY RRset.
+    ///
+    /// The RRset is processed as described in [RFC 5011, section 4]:
+    ///
+    /// * Keys with the REVOKE bit that sign the RRset themselves are
+    ///   revoked and no longer trusted. They are forgotten after the remove
+    ///   hold-down time. As the self-signature is all that is needed to
+    ///   authenticate a revocation, this happens even if the next step
+    ///   fails and an error is returned.
+    /// * If the RRset isn’t signed by one of the remaining trusted keys, an
+    ///   error is returned and no further changes are made.
+    /// * New secure entry point keys start the add hold-down time of 30
+    ///   days, or the TTL of the RRset if that is longer. If they are still
+    ///   present in an update after that time, they become trusted.
+    /// * Trusted keys not present in the RRset become missing but are still
+    ///   trusted. Keys waiting for the add hold-down time are dropped.
+    ///
+    /// `now` is the current time used for checking the signatures and the
+    /// hold-down times. The signatures are verified using `verifier`.
+    ///
+    /// [RFC 5011, section 4]: https://www.rfc-editor.org/rfc/rfc5011#section-4
+    pub fn update<N, Octs>(
+        &mut self,
+        dnskeys: &[Record<N, Dnskey<Octs>>],
+        rrsigs: &[Record<
+            impl ToName,
+            Rrsig<impl AsRef<[u8]>, impl ToName>,
+        >],
+        now: SystemTime,
+        verifier: &impl VerifyRaw,
+    ) -> Result<(), UpdateError>
+    where
+        N: ToName,
+        Octs: AsRef<[u8]>,
+    {
+        let first = dnskeys.first().ok_or(UpdateError::UnknownTrustPoint)?;
+        let owner = first.owner();
+        let zone = self
+            .zones
+            .iter_mut()
+            .find(|zone| zone.owner.name_eq(owner))
+            .ok_or(UpdateError::UnknownTrustPoint)?;
+        let now = now
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_secs())
+            .unwrap_or(0);
+        // Timestamps use serial number arithmetic, so truncating is fine.
+        let timestamp = Timestamp::from(now as u32);
+
+        // Revocation requires the key to sign the RRset itself.
+        for rr in dnskeys.iter().filter(|rr| rr.data().is_revoked()) {
+            let Some(key) = zone.find_mut(rr.data()) else {
+                continue;
+            };
+            if !key.state.is_trusted() {
+                continue;
+            }
+            if is_self_signed(dnskeys, rrsigs, rr.data(), timestamp, verifier)
+            {
+                key.dnskey = to_bytes(rr.data());
+                key.set_state(
+                    KeyState::Revoked,
+                    Some(now.saturating_add(REMOVE_HOLD_DOWN)),
+                );
+            }
+        }
+
+        // The RRset must be signed by a trusted key.
+        let trusted: Vec<_> = zone
+            .keys
+            .iter()
+            .filter(|key| key.state.is_trusted())
+            .map(|key| key.dnskey.clone())
+            .collect();
+        let status = validate_rrset(
+            dnskeys, rrsigs, owner, &trusted, timestamp, verifier,
+        );
+        if !status.is_secure() {
+            return Err(UpdateError::NotSecure(status));
+        }
+
+        // New and returning keys.
+        let add_hold_down = ADD_HOLD_DOWN.max(first.ttl().as_secs().into());
+        for rr in dnskeys {
+            let dnskey = rr.data();
+            if dnskey.is_revoked()
+                || !dnskey.is_zone_key()
+                || !dnskey.is_secure_entry_point()
+            {
+                continue;
+            }
+            match zone.find_mut(dnskey) {
+                Some(key) => match key.state {
+                    KeyState::AddPend => {
+                        if key.timer.is_some_and(|timer| timer <= now) {
+                            key.set_state(KeyState::Valid, None);
+                        }
+                    }
+                    KeyState::Missing => key.set_state(KeyState::Valid, None),
+                    KeyState::Valid
+                    | KeyState::Revoked
+                    | KeyState::Removed => {}
+                },
+                None => zone.keys.push(ManagedKey {
+                    dnskey: to_bytes(dnskey),
+                    state: KeyState::AddPend,
+                    timer: Some(now.saturating_add(add_hold_down)),
+                }),
+            }
+        }
+
+        // Keys no longer present and expired timers.
+        zone.keys.retain_mut(|key| {
+            let present =
+                dnskeys.iter().any(|rr| same_key(&key.dnskey, rr.data()));
+            match key.state {
+                KeyState::AddPend => present,
+                KeyState::Valid => {
+                    if !present {
+                        key.set_state(KeyState::Missing, None);
+                    }
+                    true
+                }
+                KeyState::Revoked => {
+                    if key.timer.is_some_and(|timer| timer <= now) {
+                        key.set_state(KeyState::Removed, None);
+                    }
+                    true
+                }
+                KeyState::Missing | KeyState::Removed => true,
+            }
+        });
+        Ok(())
+    }
+}
+
+//----------- TrustPoint -----------------------------------------------------
+
+/// The keys of a zone with managed trust anchors.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct TrustPoint {
+    /// The name of the zone.
+    owner: Name<Bytes>,
+
+    /// The keys of the zone, including those not trusted (yet).
+    keys: Vec<ManagedKey>,
+}
+
+impl TrustPoint {
+    /// Returns the managed key for a DNSKEY.
+    fn find_mut(
+        &mut self,
+        dnskey: &Dnskey<impl AsRef<[u8]>>,
+    ) -> Option<&mut ManagedKey> {
+        self.keys
+            .iter_mut()
+            .find(|key| same_key(&key.dnskey, dnskey))
+    }
+}
+
+//----------- ManagedKey -----------------------------------------------------
+
+/// A key of a trust point and its state.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct ManagedKey {
+    /// The key as last seen.
+    dnskey: Dnskey<Bytes>,
+
+    /// The state of the key.
+    state: KeyState,
+
+    /// The end of the current hold-down time in seconds since the epoch.
+    timer: Option<u64>,
+}
+
+impl ManagedKey {
+    /// Changes the state of the key.
+    fn set_state(&mut self, state: KeyState, timer: Option<u64>) {
+        self.state = state;
+        self.timer = timer;
+    }
+}
+
+//----------- KeyState -------------------------------------------------------
+
+/// The state of a managed trust anchor key.
+///
+/// See [RFC 5011, section 4].
+///
+/// [RFC 5011, section 4]: https://www.rfc-editor.org/rfc/rfc5011#section-4
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum KeyState {
+    /// The key is new and waiting for the add hold-down time to pass.
+    AddPend,
+
+    /// The key is a trust anchor.
+    Valid,
+
+    /// The key is a trust anchor but was missing from the last update.
+    Missing,
+
+    /// The key has been revoked and is not a trust anchor anymore.
+    ///
+    /// It will be removed after the remove hold-down time.
+    Revoked,
+
+    /// The key has been revoked and will not be used again.
+    Removed,
+}
+
+impl KeyState {
+    /// Returns whether a key in this state is a trust anchor.
+    pub fn is_trusted(self) -> bool {
+        matches!(self, KeyState::Valid | KeyState::Missing)
+    }
+}
+
+//----------- UpdateError ----------------------------------------------------
+
+/// Updating managed trust anchors failed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpdateError {
+    /// The RRset doesn’t belong to a known trust point.
+    UnknownTrustPoint,
+
+    /// The RRset isn’t signed by a trusted key.
+    NotSecure(ValidationStatus),
+}
+
+impl fmt::Display for UpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UpdateError::UnknownTrustPoint => {
+                f.write_str("DNSKEY RRset for unknown trust point")
+            }
+            UpdateError::NotSecure(status) => {
+                write!(f, "DNSKEY RRset not secure: {status}")
+            }
+        }
+    }
+}
+
+impl error::Error for UpdateError {}
+
+//----------- NegativeTrustAnchors -------------------------------------------
+
+/// Negative trust anchors.
+///
+/// A negative trust anchor as described in [RFC 7646] disables DNSSEC
+/// validation for a domain and all names below it until it expires. This
+/// is meant for domains with a known broken DNSSEC setup: validation
+/// failures for names under an active negative trust anchor are reported
+/// as insecure instead of bogus.
+///
+/// [RFC 7646]: https://www.rfc-editor.org/info/rfc7646
+#[derive(Clone, Debug, Default)]
+pub struct NegativeTrustAnchors {
+    /// The domains and the time their anchor expires.
+    anchors: Vec<(Name<Bytes>, Instant)>,
+}
+
+impl NegativeTrustAnchors {
+    /// Creates an empty set of negative trust anchors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a negative trust anchor for a domain.
+    ///
+    /// The anchor is active until `expires`. If there already is an anchor
+    /// for the domain, its expiry time is replaced.
+    pub fn add(&mut self, name: Name<Bytes>, expires: Instant) {
+        match self.anchors.iter_mut().find(|(n, _)| n.name_eq(&name)) {
+            Some(anchor) => anchor.1 = expires,
+            None => self.anchors.push((name, expires)),
+        }
+    }
+
+    /// Removes the negative trust anchor for a domain.
+    ///
+    /// Returns whether there was an anchor for the domain.
+    pub fn remove(&mut self, name: &impl ToName) -> bool {
+        let len = self.anchors.len();
+        self.anchors.retain(|(n, _)| !n.name_eq(name));
+        self.anchors.len() != len
+    }
+
+    /// Removes all anchors that have expired at `now`.
+    pub fn remove_expired(&mut self, now: Instant) {
+        self.anchors.retain(|(_, expires)| *expires > now);
+    }
+
+    /// Returns whether there are no anchors.
+    pub fn is_empty(&self) -> bool {
+        self.anchors.is_empty()
+    }
+
+    /// Returns whether a name is at or below an anchor active at `now`.
+    pub fn covers(&self, name: &impl ToName, now: Instant) -> bool {
+        self.anchors
+            .iter()
+            .any(|(n, expires)| *expires > now && name.ends_with(n))
+    }
+}
+
+//----------- Helper Functions -----------------------------------------------
+
+/// Returns whether two DNSKEYs are the same key.
+///
+/// The REVOKE flag is ignored.
+fn same_key(
+    left: &Dnskey<impl AsRef<[u8]>>,
+    right: &Dnskey<impl AsRef<[u8]>>,
+) -> bool {
+    const REVOKE: u16 = 0b0000_0000_1000_0000;
+    left.flags() | REVOKE == right.flags() | REVOKE
+        && left.protocol() == right.protocol()
+        && left.algorithm() == right.algorithm()
+        && left.public_key().as_ref() == right.public_key().as_ref()
+}
+
+/// Returns whether a DNSKEY RRset is signed by one of its revoked keys.
+///
+/// Validation ignores revoked keys, so this checks the signatures directly.
+fn is_self_signed<N, Octs>(
+    dnskeys: &[Record<N, Dnskey<Octs>>],
+    rrsigs: &[Record<impl ToName, Rrsig<impl AsRef<[u8]>, impl ToName>>],
+    revoked: &Dnskey<Octs>,
+    now: Timestamp,
+    verifier: &impl VerifyRaw,
+) -> bool
+where
+    N: ToName,
+    Octs: AsRef<[u8]>,
+{
+    let Some(owner) = dnskeys.first().map(Record::owner) else {
+        return false;
+    };
+    let mut sorted: Vec<_> = dnskeys.iter().collect();
+    rrsigs
+        .iter()
+        .filter(|sig| sig.owner().name_eq(owner))
+        .map(Record::data)
+        .filter(|rrsig| {
+            rrsig.type_covered() == Rtype::DNSKEY
+                && rrsig.algorithm() == revoked.algorithm()
+                && rrsig.key_tag() == revoked.key_tag()
+                && rrsig.signer_name().name_eq(owner)
+                && !now.canonical_lt(&rrsig.inception())
+                && !now.canonical_gt(&rrsig.expiration())
+        })
+        .any(|rrsig| {
+            let mut signed_data = Vec::new();
+            rrsig
+                .signed_data(&mut signed_data, &mut sorted)
+                .expect("infallible");
+            rrsig
+                .verify_signed_data_with(revoked, &signed_data, verifier)
+                .is_ok()
+        })
+}
+
+/// Converts a DNSKEY into one using `Bytes`.
+fn to_bytes(dnskey: &Dnskey<impl AsRef<[u8]>>) -> Dnskey<Bytes> {
+    Dnskey::new(
+        dnskey.flags(),
+        dnskey.protocol(),
+        dnskey.algorithm(),
+        Bytes::copy_from_slice(dnskey.public_key().as_ref()),
+    )
+    .expect("valid DNSKEY")
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::iana::SecAlg;
+    use crate::validate::ring::Verifier;
+    use core::str::FromStr;
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+    use std::slice;
+    use std::time::Duration;
+
+    const DAY: u64 = 24 * 3600;
+
+    /// The time of the first update.
+    const START: u64 = 1_700_000_000;
+
+    struct TestKey {
+        pair: Ed25519KeyPair,
+        dnskey: Dnskey<Bytes>,
+    }
+
+    impl TestKey {
+        fn new(flags: u16) -> Self {
+            let pkcs8 =
+                Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+            let pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+            let dnskey = Dnskey::new(
+                flags,
+                3,
+                SecAlg::ED25519,
+                Bytes::copy_from_slice(pair.public_key().as_ref()),
+            )
+            .unwrap();
+            Self { pair, dnskey }
+        }
+
+        fn revoked(&self) -> Dnskey<Bytes> {
+            Dnskey::new(
+                self.dnskey.flags() | 0x80,
+                3,
+                SecAlg::ED25519,
+                self.dnskey.public_key().clone(),
+            )
+            .unwrap()
+        }
+    }
+
+    type Rrset = Vec<Record<Name<Bytes>, Dnskey<Bytes>>>;
+    type Rrsigs = Vec<Record<Name<Bytes>, Rrsig<Bytes, Name<Bytes>>>>;
+
+    fn owner() -> Name<Bytes> {
+        Name::from_str("example.").unwrap()
+    }
+
+    fn time(days: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(START + days * DAY)
+    }
+
+    /// Creates a DNSKEY RRset signed by the given keys.
+    fn rrset(
+        keys: &[Dnskey<Bytes>],
+        signers: &[(&TestKey, Dnskey<Bytes>)],
+    ) -> (Rrset, Rrsigs) {
+        let rrset: Rrset = keys
+            .iter()
+            .map(|key| {
+                Record::new(
+                    owner(),
+                    Class::IN,
+                    Ttl::from_secs(3600),
+                    key.clone(),
+                )
+            })
+            .collect();
+        let rrsigs = signers
+            .iter()
+            .map(|(signer, dnskey)| {
+                let rrsig = |signature| {
+                    Rrsig::new(
+                        Rtype::DNSKEY,
+                        SecAlg::ED25519,
+                        1,
+                        Ttl::from_secs(3600),
+                        Timestamp::from((START + 100 * DAY) as u32),
+                        Timestamp::from((START - DAY) as u32),
+                        dnskey.key_tag(),
+                        owner(),
+                        signature,
+                    )
+                    .unwrap()
+                };
+                let mut buf = Vec::new();
+                rrsig(Bytes::new())
+                    .signed_data(&mut buf, &mut rrset.clone())
+                    .unwrap();
+                let signature = signer.pair.sign(&buf);
+                Record::new(
+                    owner(),
+                    Class::IN,
+                    Ttl::from_secs(3600),
+                    rrsig(Bytes::copy_from_slice(signature.as_ref())),
+                )
+            })
+            .collect();
+        (rrset, rrsigs)
+    }
+
+    fn states(
+        anchors: &ManagedTrustAnchors,
+    ) -> Vec<(Dnskey<Bytes>, KeyState)> {
+        anchors
+            .keys(&owner())
+            .map(|(key, state)| (key.clone(), state))
+            .collect()
+    }
+
+    #[test]
+    fn key_rollover() {
+        let k1 = TestKey::new(257);
+        let k2 = TestKey::new(257);
+        let zsk = TestKey::new(256);
+        let mut anchors = ManagedTrustAnchors::new();
+        anchors.add_key(owner(), k1.dnskey.clone());
+        assert!(anchors.trust_anchors().find(owner()).is_some());
+
+        // A new key is pending until the add hold-down time has passed.
+        let (keys, sigs) = rrset(
+            &[k1.dnskey.clone(), k2.dnskey.clone(), zsk.dnskey.clone()],
+            &[(&k1, k1.dnskey.clone())],
+        );
+        anchors.update(&keys, &sigs, time(0), &Verifier).unwrap();
+        assert_eq!(
+            states(&anchors),
+            [
+                (k1.dnskey.clone(), KeyState::Valid),
+                (k2.dnskey.clone(), KeyState::AddPend)
+            ]
+        );
+        anchors.update(&keys, &sigs, time(29), &Verifier).unwrap();
+        assert_eq!(states(&anchors)[1].1, KeyState::AddPend);
+        anchors.update(&keys, &sigs, time(31), &Verifier).unwrap();
+        assert_eq!(states(&anchors)[1].1, KeyState::Valid);
+
+        // The old key is revoked.
+        let (keys, sigs) = rrset(
+            &[k1.revoked(), k2.dnskey.clone(), zsk.dnskey.clone()],
+            &[(&k1, k1.revoked()), (&k2, k2.dnskey.clone())],
+        );
+        anchors.update(&keys, &sigs, time(32), &Verifier).unwrap();
+        assert_eq!(
+            states(&anchors),
+            [
+                (k1.revoked(), KeyState::Revoked),
+                (k2.dnskey.clone(), KeyState::Valid)
+            ]
+        );
+
+        // The revoked key can’t sign anymore.
+        let (keys, sigs) = rrset(
+            &[k1.dnskey.clone(), zsk.dnskey.clone()],
+            &[(&k1, k1.dnskey.clone())],
+        );
+        assert!(matches!(
+            anchors.update(&keys, &sigs, time(33), &Verifier),
+            Err(UpdateError::NotSecure(_))
+        ));
+
+        // And is removed after the remove hold-down time.
+        let (keys, sigs) = rrset(
+            &[k2.dnskey.clone(), zsk.dnskey.clone()],
+            &[(&k2, k2.dnskey.clone())],
+        );
+        anchors.update(&keys, &sigs, time(61), &Verifier).unwrap();
+        assert_eq!(states(&anchors)[0].1, KeyState::Revoked);
+        anchors.update(&keys, &sigs, time(63), &Verifier).unwrap();
+        assert_eq!(states(&anchors)[0].1, KeyState::Removed);
+
+        // Removed keys stay removed.
+        let (keys, sigs) = rrset(
+            &[k1.dnskey.clone(), k2.dnskey.clone(), zsk.dnskey.clone()],
+            &[(&k2, k2.dnskey.clone())],
+        );
+        anchors.update(&keys, &sigs, time(64), &Verifier).unwrap();
+        assert_eq!(states(&anchors)[0].1, KeyState::Removed);
+    }
+
+    #[test]
+    fn missing_and_dropped_keys() {
+        let k1 = TestKey::new(257);
+        let k2 = TestKey::new(257);
+        let mut anchors = ManagedTrustAnchors::new();
+        anchors.add_key(owner(), k1.dnskey.clone());
+
+        // A trusted key missing from the RRset stays trusted.
+        let (keys, sigs) =
+            rrset(slice::from_ref(&k2.dnskey), &[(&k1, k1.dnskey.clone())]);
+        anchors.update(&keys, &sigs, time(0), &Verifier).unwrap();
+        assert_eq!(
+            states(&anchors),
+            [
+                (k1.dnskey.clone(), KeyState::Missing),
+                (k2.dnskey.clone(), KeyState::AddPend)
+            ]
+        );
+        assert!(anchors.trust_anchors().find(owner()).is_some());
+
+        // A pending key that disappears is dropped.
+        let (keys, sigs) =
+            rrset(slice::from_ref(&k1.dnskey), &[(&k1, k1.dnskey.clone())]);
+        anchors.update(&keys, &sigs, time(1), &Verifier).unwrap();
+        assert_eq!(states(&anchors), [(k1.dnskey.clone(), KeyState::Valid)]);
+
+        // A revoked key that didn’t sign the RRset is ignored.
+        let (keys, sigs) =
+            rrset(&[k1.revoked()], &[(&k1, k1.dnskey.clone())]);
+        anchors.update(&keys, &sigs, time(2), &Verifier).unwrap();
+        assert_eq!(states(&anchors), [(k1.dnskey.clone(), KeyState::Valid)]);
+
+        // A self-signed revocation is applied even if the RRset isn’t
+        // signed by any other trusted key.
+        let (keys, sigs) = rrset(&[k1.revoked()], &[(&k1, k1.revoked())]);
+        assert!(matches!(
+            anchors.update(&keys, &sigs, time(3), &Verifier),
+            Err(UpdateError::NotSecure(_))
+        ));
+        assert_eq!(states(&anchors), [(k1.revoked(), KeyState::Revoked)]);
+
+        // Unknown trust points are rejected.
+        assert_eq!(
+            ManagedTrustAnchors::new().update(
+                &keys,
+                &sigs,
+                time(2),
+                &Verifier
+            ),
+            Err(UpdateError::UnknownTrustPoint)
+        );
+    }
+
+    #[test]
+    fn serialize() {
+        let k1 = TestKey::new(257);
+        let k2 = TestKey::new(257);
+        let mut anchors = ManagedTrustAnchors::new();
+        anchors.add_key(owner(), k1.dnskey.clone());
+        let (keys, sigs) = rrset(
+            &[k1.dnskey.clone(), k2.dnskey.clone()],
+            &[(&k1, k1.dnskey.clone())],
+        );
+        anchors.update(&keys, &sigs, time(0), &Verifier).unwrap();
+
+        let json = serde_json::to_string(&anchors).unwrap();
+        let mut restored: ManagedTrustAnchors =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(states(&restored), states(&anchors));
+
+        // The hold-down timer survives as well.
+        restored.update(&keys, &sigs, time(31), &Verifier).unwrap();
+        assert_eq!(states(&restored)[1].1, KeyState::Valid);
+    }
+
+    #[test]
+    fn negative_trust_anchors() {
+        let now = Instant::now();
+        let name = |s| Name::<Bytes>::from_str(s).unwrap();
+        let mut ntas = NegativeTrustAnchors::new();
+        assert!(ntas.is_empty());
+        ntas.add(name("example.com."), now + Duration::from_secs(60));
+        ntas.add(name("example.net."), now);
+
+        assert!(ntas.covers(&name("example.com."), now));
+        assert!(ntas.covers(&name("www.Example.COM."), now));
+        assert!(!ntas.covers(&name("com."), now));
+        assert!(!ntas.covers(&name("example.org."), now));
+        assert!(!ntas.covers(&name("example.net."), now));
+        assert!(!ntas
+            .covers(&name("example.com."), now + Duration::from_secs(60)));
+
+        // Adding again replaces the expiry time.
+        ntas.add(name("EXAMPLE.net."), now + Duration::from_secs(60));
+        assert!(ntas.covers(&name("example.net."), now));
+        ntas.remove_expired(now + Duration::from_secs(30));
+        assert!(ntas.covers(&name("example.net."), now));
+        ntas.remove_expired(now + Duration::from_secs(60));
+        assert!(ntas.is_empty());
+
+        ntas.add(name("example.com."), now + Duration::from_secs(60));
+        assert!(ntas.remove(&name("example.com.")));
+        assert!(!ntas.remove(&name("example.com.")));
+        assert!(!ntas.covers(&name("example.com."), now));
+    }
+}
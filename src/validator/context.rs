@@ -4,7 +4,7 @@
 //! connection for issuing queries, and caches to store previously fetched
 //! or evaluated results.
 
-use super::anchor::{TrustAnchor, TrustAnchors};
+use super::anchor::{NegativeTrustAnchors, TrustAnchor, TrustAnchors};
 use super::group::{Group, GroupSet, SigCache, ValidatedGroup};
 use super::nsec::{
     cached_nsec3_hash, nsec3_for_nodata, nsec3_for_nodata_wildcard,
@@ -33,7 +33,7 @@ use crate::dep::octseq::{Octets, OctetsFrom, OctetsInto};
 use crate::net::client::request::{
     ComposeRequest, RequestMessage, SendRequest,
 };
-use crate::rdata::{AllRecordData, Dnskey, Ds, ZoneRecordData};
+use crate::rdata::{AllRecordData, Cname, Dnskey, Ds, ZoneRecordData};
 use crate::utils::config::DefMinMax;
 use crate::validate::DnskeyExt;
 use crate::validate::{supported_algorithm, supported_digest};
@@ -44,7 +44,7 @@ use std::cmp::min;
 use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::string::ToString;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use std::vec::Vec;
 use std::{error, fmt};
@@ -312,6 +312,9 @@ pub struct ValidationContext<Upstream> {
     /// DNSSEC trust anchors.
     ta: TrustAnchors,
 
+    /// Negative trust anchors.
+    nta: Mutex<NegativeTrustAnchors>,
+
     /// Upstream client transport.
     upstream: Upstream,
 
@@ -345,6 +348,7 @@ impl<Upstream> ValidationContext<Upstream> {
     ) -> Self {
         Self {
             ta,
+            nta: Default::default(),
             upstream,
             node_cache: Cache::new(config.max_node_cache),
             nsec3_cache: Nsec3Cache::new(config.max_nsec3_cache),
@@ -354,6 +358,36 @@ impl<Upstream> ValidationContext<Upstream> {
         }
     }
 
+    /// Replace the negative trust anchors.
+    ///
+    /// Validation failures for names at or below an active negative trust
+    /// anchor are reported as insecure rather than bogus.
+    pub fn set_negative_trust_anchors(&self, nta: NegativeTrustAnchors) {
+        *self.nta.lock().unwrap() = nta;
+    }
+
+    /// Add a negative trust anchor for `name` which is active until
+    /// `expires`.
+    ///
+    /// If there already is a negative trust anchor for `name`, its expiry
+    /// time is replaced.
+    pub fn add_negative_trust_anchor(
+        &self,
+        name: Name<Bytes>,
+        expires: Instant,
+    ) {
+        let mut nta = self.nta.lock().unwrap();
+        nta.remove_expired(Instant::now());
+        nta.add(name, expires);
+    }
+
+    /// Remove the negative trust anchor for `name`.
+    ///
+    /// Returns whether there was a negative trust anchor for `name`.
+    pub fn remove_negative_trust_anchor(&self, name: &impl ToName) -> bool {
+        self.nta.lock().unwrap().remove(name)
+    }
+
     /// Validate a DNS reply message. An Error value will be returned if the
     /// message cannot be parsed or if there is any other message-related
     /// error.
@@ -362,10 +396,50 @@ impl<Upstream> ValidationContext<Upstream> {
     /// provides additional information on the cause of the validation state.
     /// This ExtendError is added to the reply message by the
     /// [validator](crate::net::client::validator) transport.
+    ///
+    /// If the reply is bogus but its question name or the target of a CNAME
+    /// in its answer section is at or below an active negative trust
+    /// anchor, the reply is considered insecure and no ExtendedError is
+    /// returned.
     pub async fn validate_msg<'a, MsgOcts, USOcts>(
         &self,
         msg: &'a mut Message<MsgOcts>,
     ) -> Result<(ValidationState, Option<ExtendedError<Vec<u8>>>), Error>
+    where
+        MsgOcts: Clone + Debug + Octets + OctetsFrom<Vec<u8>> + 'a,
+        <MsgOcts as Octets>::Range<'a>: Debug,
+        USOcts:
+            AsRef<[u8]> + Debug + Octets + OctetsFrom<Vec<u8>> + Send + Sync,
+        Upstream: SendRequest<RequestMessage<USOcts>>,
+    {
+        // The names a negative trust anchor may cover: the question name
+        // and every name the CNAME chain leads to.
+        let mut names: Vec<Name<Bytes>> = Vec::new();
+        if let Ok(question) = msg.sole_question() {
+            names.push(question.qname().to_name());
+        }
+        if let Ok(answer) = msg.answer() {
+            for record in answer.limit_to::<Cname<_>>().flatten() {
+                names.push(record.data().cname().to_name());
+            }
+        }
+        let (state, ede) = self.validate_reply(msg).await?;
+        if state == ValidationState::Bogus {
+            let nta = self.nta.lock().unwrap();
+            let now = Instant::now();
+            if names.iter().any(|name| nta.covers(name, now)) {
+                return Ok((ValidationState::Insecure, None));
+            }
+        }
+        Ok((state, ede))
+    }
+
+    /// Validate a DNS reply message without considering negative trust
+    /// anchors.
+    async fn validate_reply<'a, MsgOcts, USOcts>(
+        &self,
+        msg: &'a mut Message<MsgOcts>,
+    ) -> Result<(ValidationState, Option<ExtendedError<Vec<u8>>>), Error>
     where
         MsgOcts: Clone + Debug + Octets + OctetsFrom<Vec<u8>> + 'a,
         <MsgOcts as Octets>::Range<'a>: Debug,
@@ -2376,3 +2450,141 @@ impl error::Error for Error {
         }
     }
 }
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::iana::{Class, Rcode, Rtype};
+    use crate::base::MessageBuilder;
+    use crate::net::client::request::{Error, GetResponse};
+    use crate::rdata::A;
+    use std::boxed::Box;
+    use std::future::Future;
+    use std::net::Ipv4Addr;
+    use std::pin::Pin;
+    use std::str::FromStr;
+
+    /// An upstream answering every query with unsigned NODATA.
+    struct MockUpstream;
+
+    impl SendRequest<RequestMessage<Bytes>> for MockUpstream {
+        fn send_request(
+            &self,
+            request_msg: RequestMessage<Bytes>,
+        ) -> Box<dyn GetResponse + Send + Sync> {
+            let query = request_msg.to_message().unwrap();
+            let answer = MessageBuilder::new_bytes()
+                .start_answer(&query, Rcode::NOERROR)
+                .unwrap();
+            Box::new(MockResponse(answer.into_message()))
+        }
+    }
+
+    /// The response of a [`MockUpstream`].
+    #[derive(Debug)]
+    struct MockResponse(Message<Bytes>);
+
+    impl GetResponse for MockResponse {
+        fn get_response(
+            &mut self,
+        ) -> Pin<
+            Box<
+                dyn Future<Output = Result<Message<Bytes>, Error>>
+                    + Send
+                    + Sync
+                    + '_,
+            >,
+        > {
+            let answer = self.0.clone();
+            Box::pin(async move { Ok(answer) })
+        }
+    }
+
+    /// Returns an unsigned reply for `www.example.com. A`.
+    ///
+    /// If `cname` is given, the answer is a CNAME to that name and the
+    /// address record is for it.
+    fn reply(cname: Option<&str>) -> Message<Bytes> {
+        let qname = Name::<Bytes>::from_str("www.example.com.").unwrap();
+        let mut query = MessageBuilder::new_bytes().question();
+        query.push((&qname, Rtype::A)).unwrap();
+        let query = query.into_message();
+        let mut answer = MessageBuilder::new_bytes()
+            .start_answer(&query, Rcode::NOERROR)
+            .unwrap();
+        let owner = match cname {
+            Some(cname) => {
+                let cname = Name::<Bytes>::from_str(cname).unwrap();
+                answer
+                    .push((&qname, Class::IN, 60, Cname::new(cname.clone())))
+                    .unwrap();
+                cname
+            }
+            None => qname,
+        };
+        answer
+            .push((
+                &owner,
+                Class::IN,
+                60,
+                A::new(Ipv4Addr::new(192, 0, 2, 1)),
+            ))
+            .unwrap();
+        answer.into_message()
+    }
+
+    #[tokio::test]
+    async fn negative_trust_anchor() {
+        // Since the root keys can’t be found, all replies are bogus.
+        let ta = TrustAnchors::from_u8(
+            b". 172800 IN DS 20326 8 2 \
+              E06D44B80B8F1D39A95C0B0D7C65D08458E880409BBC683457104237C7F8EC8D",
+        )
+        .unwrap();
+        let vc = ValidationContext::new(ta, MockUpstream);
+
+        let (state, ede) =
+            vc.validate_msg::<_, Bytes>(&mut reply(None)).await.unwrap();
+        assert_eq!(state, ValidationState::Bogus);
+        assert!(ede.is_some());
+
+        // An active negative trust anchor turns bogus into insecure.
+        vc.add_negative_trust_anchor(
+            Name::from_str("example.com.").unwrap(),
+            Instant::now() + Duration::from_secs(60),
+        );
+        let (state, ede) =
+            vc.validate_msg::<_, Bytes>(&mut reply(None)).await.unwrap();
+        assert_eq!(state, ValidationState::Insecure);
+        assert!(ede.is_none());
+
+        // An expired one doesn’t.
+        vc.add_negative_trust_anchor(
+            Name::from_str("example.com.").unwrap(),
+            Instant::now(),
+        );
+        let (state, ede) =
+            vc.validate_msg::<_, Bytes>(&mut reply(None)).await.unwrap();
+        assert_eq!(state, ValidationState::Bogus);
+        assert!(ede.is_some());
+
+        // A CNAME chain leading into a covered zone is considered, too.
+        vc.add_negative_trust_anchor(
+            Name::from_str("example.net.").unwrap(),
+            Instant::now() + Duration::from_secs(60),
+        );
+        let (state, ede) = vc
+            .validate_msg::<_, Bytes>(&mut reply(Some("www.example.net.")))
+            .await
+            .unwrap();
+        assert_eq!(state, ValidationState::Insecure);
+        assert!(ede.is_none());
+        let (state, _) = vc
+            .validate_msg::<_, Bytes>(&mut reply(Some("www.example.org.")))
+            .await
+            .unwrap();
+        assert_eq!(state, ValidationState::Bogus);
+    }
+}
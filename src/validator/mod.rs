@@ -17,6 +17,10 @@
 //! The validation context provides the
 //! method [validate_msg()](context::ValidationContext::validate_msg()) to
 //! validate a reply message.
+//! Negative trust anchors
+//! ([RFC 7646](https://www.rfc-editor.org/info/rfc7646)) for domains with
+//! broken DNSSEC can be added to the context at any time, see
+//! [anchor::NegativeTrustAnchors].
 //!
 //! # Caching
 //! The validator has four caches:
@@ -38,7 +42,6 @@
 //!   next request that needs it.
 //! * Currently `DS` and `DNSKEY` requests are issued sequentically. They
 //!   can be issued (optimistically) in parallel to lower latency.
-//! * There is currently no support for negative trust anchors.
 //! * There is currently no support for generating a validation chain
 //!   ([RFC 9102](https://www.rfc-editor.org/info/rfc9102)).
 //! * There is currently no support for validating a chain.
@@ -46,8 +49,10 @@
 //!   ([RFC 7901](https://www.rfc-editor.org/info/rfc7901)).
 //! * There is no support for fetch the IANA trust anchor over HTTP(S)
 //!   ([RFC 7958](https://www.rfc-editor.org/info/rfc7958)).
-//! * There is no support for automated updating of trust anchors
-//!   ([RFC 5011](https://www.rfc-editor.org/info/rfc5011)).
+//! * Automated updates of trust anchors
+//!   ([RFC 5011](https://www.rfc-editor.org/info/rfc5011)) via
+//!   [anchor::ManagedTrustAnchors] require the DNSKEY RRsets of the trust
+//!   points to be fetched by the user.
 //!
 //! # Bugs
 //! * The size of accepted `DS` and `DNSKEY` RRsets is not limited.
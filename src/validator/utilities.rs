@@ -153,15 +153,9 @@ pub fn map_dname(
     dname: &Dname<ParsedName<Bytes>>,
     name: &Name<Bytes>,
 ) -> Result<Name<Bytes>, Error> {
-    let mut tmp_name = name.clone();
-    let mut new_name = NameBuilder::new_bytes();
-    let target_labels = target.label_count();
-    while tmp_name.label_count() > target_labels {
-        new_name.append_label(tmp_name.first().as_slice())?;
-        tmp_name = tmp_name.parent().expect("should not fail");
-    }
-    let name = new_name.append_origin(dname.dname())?;
-    Ok(name)
+    dname
+        .synthesize(target, name)
+        .map_err(|_| Error::PushNameError)
 }
 
 /// Compute the TTL for a signature. Take the original_ttl and the
@@ -0,0 +1,569 @@
+//! Checking the content of zones.
+//!
+//! A zonefile can be syntactically correct and still describe a zone that
+//! is broken. This module provides the [`Checker`] which looks at the
+//! records of a zone as a whole and reports the problems it finds as a
+//! list of [`Diagnostic`]s. The function [`check_zonefile`] runs the
+//! checker over all records of a zonefile, attaching the line number of the
+//! offending record to each diagnostic.
+//!
+//! The following problems are detected:
+//!
+//! * a SOA record missing at the apex, more than one SOA record at the apex,
+//!   or SOA records below the apex,
+//! * NS records missing at the apex,
+//! * CNAME records together with other data (other than the RRSIG and NSEC
+//!   records allowed by [RFC 4035, section 2.5]) or other CNAME records at
+//!   the same owner,
+//! * CNAME and DNAME records at the same owner and data below a DNAME as
+//!   forbidden by [RFC 6672, section 2.4],
+//! * name servers within the zone without any address records, i.e.,
+//!   missing glue for delegations,
+//! * duplicate records, and
+//! * records outside of the zone.
+//!
+//! ```
+//! use domain::base::Name;
+//! use domain::zonefile::check::{check_zonefile, Problem};
+//! use domain::zonefile::inplace::Zonefile;
+//! use std::str::FromStr;
+//!
+//! let mut zone = Zonefile::from(
+//!     r#"@   3600 IN SOA ns1 hostmaster 1 3600 900 604800 300
+//!     3600 IN NS  ns1
+//! www 3600 IN CNAME @
+//! www 3600 IN A 192.0.2.1
+//! "#,
+//! );
+//! let apex = Name::from_str("example.com.").unwrap();
+//! zone.set_origin(apex.clone());
+//! let diagnostics = check_zonefile(zone, apex).unwrap();
+//!
+//! assert_eq!(diagnostics.len(), 2);
+//! assert_eq!(diagnostics[0].problem(), &Problem::MissingGlue(
+//!     Name::from_str("ns1.example.com.").unwrap()
+//! ));
+//! assert_eq!(diagnostics[0].line(), Some(2));
+//! assert_eq!(diagnostics[1].to_string(),
+//!     "line 4: www.example.com.: A record next to a CNAME record"
+//! );
+//! ```
+//!
+//! [RFC 4035, section 2.5]: https://tools.ietf.org/html/rfc4035#section-2.5
+//! [RFC 6672, section 2.4]: https://tools.ietf.org/html/rfc6672#section-2.4
+
+use core::cmp::Ordering;
+use core::fmt;
+use std::collections::HashMap;
+use std::vec::Vec;
+
+use bytes::Bytes;
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::name::{Name, ToLabelIter, ToName};
+use crate::rdata::ZoneRecordData;
+
+use super::inplace::{Entry, Error, ScannedRecord, Zonefile};
+
+//------------ check_zonefile ------------------------------------------------
+
+/// Checks all records of a zonefile.
+///
+/// The zone is assumed to have the given apex. The diagnostics carry the
+/// line number of the record they relate to. `$INCLUDE` directives are
+/// not followed.
+///
+/// Returns an error if the zonefile can’t be scanned.
+pub fn check_zonefile(
+    mut zonefile: Zonefile,
+    apex: Name<Bytes>,
+) -> Result<Vec<Diagnostic>, Error> {
+    let mut checker = Checker::new(apex);
+    while let Some(entry) = zonefile.next_entry()? {
+        if let Entry::Record(record) = entry {
+            checker.push(record, Some(zonefile.entry_line()));
+        }
+    }
+    Ok(checker.check())
+}
+
+//------------ Checker -------------------------------------------------------
+
+/// A collection of the records of a zone to be checked.
+///
+/// Records are added via [`push`][Self::push], optionally with the line
+/// they were found on. Once all records have been added,
+/// [`check`][Self::check] checks the zone.
+#[derive(Clone, Debug)]
+pub struct Checker {
+    /// The apex of the zone.
+    apex: Name<Bytes>,
+
+    /// The records of the zone and their lines.
+    records: Vec<(ScannedRecord, Option<usize>)>,
+}
+
+impl Checker {
+    /// Creates a new checker for the zone with the given apex.
+    pub fn new(apex: Name<Bytes>) -> Self {
+        Checker {
+            apex,
+            records: Vec::new(),
+        }
+    }
+
+    /// Adds a record to the zone.
+    pub fn push(&mut self, record: ScannedRecord, line: Option<usize>) {
+        self.records.push((record, line))
+    }
+
+    /// Checks the zone.
+    ///
+    /// Returns the diagnostics for all problems found, which is empty if
+    /// the zone is fine. Problems of the zone as a whole come first, the
+    /// remaining diagnostics are in the order of the records they relate
+    /// to.
+    pub fn check(self) -> Vec<Diagnostic> {
+        let mut check = Check {
+            apex: &self.apex,
+            records: &self.records,
+            owners: HashMap::new(),
+            diagnostics: Vec::new(),
+        };
+        check.run();
+        let mut diagnostics = check.diagnostics;
+        diagnostics.sort_by_key(|(idx, _)| *idx);
+        diagnostics.into_iter().map(|(_, diag)| diag).collect()
+    }
+}
+
+//------------ Check ---------------------------------------------------------
+
+/// The state of a running check.
+struct Check<'a> {
+    /// The apex of the zone.
+    apex: &'a Name<Bytes>,
+
+    /// The records of the zone and their lines.
+    records: &'a [(ScannedRecord, Option<usize>)],
+
+    /// The indexes of the records of each owner within the zone.
+    owners: HashMap<Name<Bytes>, Vec<usize>>,
+
+    /// The diagnostics found so far with the index of their record.
+    ///
+    /// Diagnostics for the zone as a whole have no index.
+    diagnostics: Vec<(Option<usize>, Diagnostic)>,
+}
+
+impl Check<'_> {
+    fn run(&mut self) {
+        for (idx, (record, _)) in self.records.iter().enumerate() {
+            if !record.owner().ends_with(self.apex) {
+                self.record_problem(idx, Problem::OutOfZone);
+                continue;
+            }
+            self.owners
+                .entry(record.owner().to_bytes())
+                .or_default()
+                .push(idx);
+        }
+
+        self.check_duplicates();
+        let mut records: Vec<_> =
+            self.owners.values().flatten().copied().collect();
+        records.sort_unstable();
+        self.check_apex(&records);
+        self.check_owners();
+        self.check_dnames();
+        self.check_glue(&records);
+    }
+
+    /// Reports duplicate records and removes them from the owners.
+    fn check_duplicates(&mut self) {
+        let mut duplicates = Vec::new();
+        for idxs in self.owners.values_mut() {
+            let mut pos = 0;
+            while pos < idxs.len() {
+                let record = &self.records[idxs[pos]].0;
+                if idxs[..pos].iter().any(|&other| {
+                    record.canonical_cmp(&self.records[other].0)
+                        == Ordering::Equal
+                }) {
+                    duplicates.push(idxs.remove(pos));
+                } else {
+                    pos += 1;
+                }
+            }
+        }
+        for idx in duplicates {
+            self.record_problem(idx, Problem::DuplicateRecord);
+        }
+    }
+
+    /// Checks the SOA and NS records at the apex.
+    fn check_apex(&mut self, records: &[usize]) {
+        let mut soa_seen = false;
+        let mut ns_seen = false;
+        for &idx in records {
+            let record = &self.records[idx].0;
+            let at_apex = record.owner().name_eq(self.apex);
+            match record.rtype() {
+                Rtype::SOA if !at_apex => {
+                    self.record_problem(idx, Problem::SoaBelowApex)
+                }
+                Rtype::SOA => {
+                    if soa_seen {
+                        self.record_problem(idx, Problem::MultipleSoa)
+                    }
+                    soa_seen = true;
+                }
+                Rtype::NS if at_apex => ns_seen = true,
+                _ => {}
+            }
+        }
+        if !soa_seen {
+            self.zone_problem(Problem::MissingSoa);
+        }
+        if !ns_seen {
+            self.zone_problem(Problem::MissingApexNs);
+        }
+    }
+
+    /// Checks the records of each owner for CNAME and DNAME conflicts.
+    fn check_owners(&mut self) {
+        let mut problems = Vec::new();
+        for idxs in self.owners.values() {
+            let mut cname = false;
+            for &idx in idxs {
+                if self.records[idx].0.rtype() == Rtype::CNAME {
+                    if cname {
+                        problems.push((idx, Problem::MultipleCnames))
+                    }
+                    cname = true;
+                }
+            }
+            if !cname {
+                continue;
+            }
+            for &idx in idxs {
+                match self.records[idx].0.rtype() {
+                    Rtype::CNAME | Rtype::RRSIG | Rtype::NSEC => {}
+                    Rtype::DNAME => {
+                        problems.push((idx, Problem::CnameAndDname))
+                    }
+                    _ => problems.push((idx, Problem::CnameAndOtherData)),
+                }
+            }
+        }
+        for (idx, problem) in problems {
+            self.record_problem(idx, problem);
+        }
+    }
+
+    /// Checks that there is no data below DNAME records.
+    fn check_dnames(&mut self) {
+        let dnames: Vec<_> = self
+            .owners
+            .iter()
+            .filter(|(_, idxs)| {
+                idxs.iter()
+                    .any(|&idx| self.records[idx].0.rtype() == Rtype::DNAME)
+            })
+            .map(|(owner, _)| owner.clone())
+            .collect();
+        let mut problems = Vec::new();
+        for (owner, idxs) in &self.owners {
+            let dname = dnames.iter().find(|dname| {
+                owner.ends_with(*dname) && !owner.name_eq(*dname)
+            });
+            if let Some(dname) = dname {
+                for &idx in idxs {
+                    problems.push((idx, Problem::BelowDname(dname.clone())));
+                }
+            }
+        }
+        for (idx, problem) in problems {
+            self.record_problem(idx, problem);
+        }
+    }
+
+    /// Checks that name servers within the zone have address records.
+    fn check_glue(&mut self, records: &[usize]) {
+        for &idx in records {
+            let nsdname = match self.records[idx].0.data() {
+                ZoneRecordData::Ns(ns) => ns.nsdname().to_bytes(),
+                _ => continue,
+            };
+            if !nsdname.ends_with(self.apex) {
+                continue;
+            }
+            let has_address = self.owners.get(&nsdname).is_some_and(|idxs| {
+                idxs.iter().any(|&idx| {
+                    matches!(
+                        self.records[idx].0.rtype(),
+                        Rtype::A | Rtype::AAAA
+                    )
+                })
+            });
+            if !has_address {
+                self.record_problem(idx, Problem::MissingGlue(nsdname));
+            }
+        }
+    }
+
+    /// Adds a diagnostic for the record with the given index.
+    fn record_problem(&mut self, idx: usize, problem: Problem) {
+        let (record, line) = &self.records[idx];
+        self.diagnostics.push((
+            Some(idx),
+            Diagnostic {
+                line: *line,
+                owner: record.owner().to_bytes(),
+                rtype: Some(record.rtype()),
+                problem,
+            },
+        ));
+    }
+
+    /// Adds a diagnostic for the zone as a whole.
+    fn zone_problem(&mut self, problem: Problem) {
+        self.diagnostics.push((
+            None,
+            Diagnostic {
+                line: None,
+                owner: self.apex.clone(),
+                rtype: None,
+                problem,
+            },
+        ));
+    }
+}
+
+//------------ Diagnostic ----------------------------------------------------
+
+/// A problem found in a zone.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    /// The line of the record the problem relates to, if known.
+    line: Option<usize>,
+
+    /// The owner of the record or the apex for problems of the zone.
+    owner: Name<Bytes>,
+
+    /// The type of the record or `None` for problems of the zone.
+    rtype: Option<Rtype>,
+
+    /// The problem.
+    problem: Problem,
+}
+
+impl Diagnostic {
+    /// Returns the line of the record the problem relates to.
+    ///
+    /// Returns `None` if the problem relates to the zone as a whole or if
+    /// the line of the record isn’t known.
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// Returns the owner name of the record the problem relates to.
+    ///
+    /// For problems of the zone as a whole, this is the apex.
+    pub fn owner(&self) -> &Name<Bytes> {
+        &self.owner
+    }
+
+    /// Returns the type of the record the problem relates to.
+    ///
+    /// Returns `None` if the problem relates to the zone as a whole.
+    pub fn rtype(&self) -> Option<Rtype> {
+        self.rtype
+    }
+
+    /// Returns the problem.
+    pub fn problem(&self) -> &Problem {
+        &self.problem
+    }
+}
+
+//--- Display
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        write!(f, "{}: ", self.owner.fmt_with_dot())?;
+        if let Some(rtype) = self.rtype {
+            write!(f, "{} record ", rtype)?;
+        }
+        self.problem.fmt(f)
+    }
+}
+
+//------------ Problem -------------------------------------------------------
+
+/// The kind of problem found in a zone.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Problem {
+    /// There is no SOA record at the apex.
+    MissingSoa,
+
+    /// There is more than one SOA record at the apex.
+    MultipleSoa,
+
+    /// A SOA record is below the apex.
+    SoaBelowApex,
+
+    /// There are no NS records at the apex.
+    MissingApexNs,
+
+    /// A record shares its owner with a CNAME record.
+    CnameAndOtherData,
+
+    /// There is more than one CNAME record at the owner.
+    MultipleCnames,
+
+    /// A DNAME record shares its owner with a CNAME record.
+    CnameAndDname,
+
+    /// A record is below the DNAME record with the given owner.
+    BelowDname(Name<Bytes>),
+
+    /// The given name server within the zone has no address records.
+    MissingGlue(Name<Bytes>),
+
+    /// The record appears more than once.
+    DuplicateRecord,
+
+    /// The record is outside of the zone.
+    OutOfZone,
+}
+
+//--- Display
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::MissingSoa => f.write_str("no SOA record at the apex"),
+            Problem::MultipleSoa => {
+                f.write_str("more than one SOA record at the apex")
+            }
+            Problem::SoaBelowApex => f.write_str("below the apex"),
+            Problem::MissingApexNs => {
+                f.write_str("no NS records at the apex")
+            }
+            Problem::CnameAndOtherData => {
+                f.write_str("next to a CNAME record")
+            }
+            Problem::MultipleCnames => {
+                f.write_str("next to another CNAME record")
+            }
+            Problem::CnameAndDname => f.write_str("next to a CNAME record"),
+            Problem::BelowDname(dname) => {
+                write!(f, "below DNAME at {}", dname.fmt_with_dot())
+            }
+            Problem::MissingGlue(name) => {
+                write!(
+                    f,
+                    "without address records for {}",
+                    name.fmt_with_dot()
+                )
+            }
+            Problem::DuplicateRecord => f.write_str("is a duplicate"),
+            Problem::OutOfZone => f.write_str("outside of the zone"),
+        }
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::str::FromStr;
+    use std::string::{String, ToString};
+
+    fn check(text: &str) -> Vec<String> {
+        let apex = Name::from_str("example.").unwrap();
+        let mut zonefile = Zonefile::from(text);
+        zonefile.set_origin(apex.clone());
+        check_zonefile(zonefile, apex)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn good_zone() {
+        assert!(check(
+            "@ 3600 IN SOA ns1 hostmaster 1 3600 900 604800 300\n\
+             @ 3600 IN NS ns1\n\
+             @ 3600 IN NS ns.example.net.\n\
+             ns1 3600 IN A 192.0.2.1\n\
+             www 3600 IN CNAME @\n\
+             www 3600 IN RRSIG A 15 2 3600 20240101000000 \
+                 20231201000000 1234 example. AAAA\n\
+             sub 3600 IN NS ns.sub\n\
+             ns.sub 3600 IN AAAA 2001:db8::1\n\
+             alias 3600 IN DNAME example.net.\n"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn bad_zone() {
+        assert_eq!(
+            check(
+                "@ 3600 IN SOA ns1 hostmaster 1 3600 900 604800 300\n\
+                 @ 3600 IN SOA ns1 hostmaster 2 3600 900 604800 300\n\
+                 www 3600 IN CNAME @\n\
+                 www 3600 IN CNAME ns1\n\
+                 www 3600 IN TXT \"hello\"\n\
+                 sub 3600 IN NS ns.sub\n\
+                 sub 3600 IN NS ns.sub\n\
+                 alias 3600 IN DNAME example.net.\n\
+                 alias 3600 IN CNAME example.net.\n\
+                 foo.alias 3600 IN A 192.0.2.1\n\
+                 soa.sub 3600 IN SOA ns1 hostmaster 1 3600 900 604800 300\n\
+                 example.net. 3600 IN A 192.0.2.1\n"
+            ),
+            [
+                "example.: no NS records at the apex",
+                "line 2: example.: SOA record more than one SOA record at \
+                 the apex",
+                "line 4: www.example.: CNAME record next to another CNAME \
+                 record",
+                "line 5: www.example.: TXT record next to a CNAME record",
+                "line 6: sub.example.: NS record without address records \
+                 for ns.sub.example.",
+                "line 7: sub.example.: NS record is a duplicate",
+                "line 8: alias.example.: DNAME record next to a CNAME record",
+                "line 10: foo.alias.example.: A record below DNAME at \
+                 alias.example.",
+                "line 11: soa.sub.example.: SOA record below the apex",
+                "line 12: example.net.: A record outside of the zone",
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_soa() {
+        let mut checker = Checker::new(Name::from_str("example.").unwrap());
+        let mut zonefile =
+            Zonefile::from("example. 3600 IN NS ns.example.net.\n");
+        while let Some(Entry::Record(record)) = zonefile.next_entry().unwrap()
+        {
+            checker.push(record, None);
+        }
+        let diagnostics = checker.check();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].problem(), &Problem::MissingSoa);
+        assert_eq!(diagnostics[0].rtype(), None);
+        assert_eq!(diagnostics[0].line(), None);
+    }
+}
@@ -9,9 +9,55 @@
 //! This may or may not be a good strategy. It was primarily implemented to
 //! see that the [`Scan`] trait is powerful enough to build such an
 //! implementation.
+//!
+//! # Example
+//!
+//! The scanner understands the full master file format of RFC 1035: entries
+//! spanning multiple lines via parentheses, quoted strings, comments,
+//! entries reusing the owner of the previous entry, names relative to the
+//! origin, and the `$ORIGIN` and `$TTL` directives. Missing TTLs and
+//! classes are taken from the previous record. It also expands the
+//! `$GENERATE` directive introduced by BIND for generating a range of
+//! records, which is described in the [BIND documentation].
+//!
+//! ```
+//! use domain::base::{Name, Rtype, Ttl};
+//! use domain::zonefile::inplace::{Entry, Zonefile};
+//! use std::str::FromStr;
+//!
+//! let mut zone = Zonefile::from(
+//!     r#"$TTL 300
+//! @      IN SOA ns1 hostmaster (
+//!               2024010101 ; serial
+//!               3600 900 604800 300 )
+//!        NS     ns1
+//! ns1    3600 A 192.0.2.1
+//! www    TXT    "hello world"
+//! "#,
+//! );
+//! zone.set_origin(Name::from_str("example.com.").unwrap());
+//!
+//! let mut records = Vec::new();
+//! for entry in zone {
+//!     match entry.unwrap() {
+//!         Entry::Record(record) => records.push(record),
+//!         // Included files would need to be read separately. There
+//!         // aren’t any here, so we just skip them.
+//!         Entry::Include { .. } => continue,
+//!     }
+//! }
+//! assert_eq!(records.len(), 4);
+//! assert_eq!(records[1].rtype(), Rtype::NS);
+//! assert_eq!(records[1].owner().to_string(), "example.com");
+//! assert_eq!(records[1].ttl(), Ttl::from_secs(300));
+//! assert_eq!(records[3].ttl(), Ttl::from_secs(3600));
+//! ```
+//!
+//! [BIND documentation]: https://bind9.readthedocs.io/en/latest/chapter3.html#bind-primary-file-extension-the-generate-directive
 #![cfg(feature = "bytes")]
 #![cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
 
+use core::fmt::Write;
 use core::str::FromStr;
 use core::{fmt, str};
 
@@ -29,6 +75,8 @@ use crate::base::scan::{
 };
 use crate::base::Ttl;
 use crate::rdata::ZoneRecordData;
+use std::string::String;
+use std::vec::Vec;
 
 //------------ Type Aliases --------------------------------------------------
 
@@ -74,6 +122,12 @@ pub struct Zonefile {
 
     /// The last class.
     last_class: Class,
+
+    /// The `$GENERATE` directive currently being expanded.
+    generate: Option<Generate>,
+
+    /// The line number of the start of the last entry.
+    entry_line: usize,
 }
 
 impl Zonefile {
@@ -97,6 +151,8 @@ impl Zonefile {
             last_owner: None,
             last_ttl: Ttl::from_secs(3600),
             last_class: Class::IN,
+            generate: None,
+            entry_line: 0,
         }
     }
 
@@ -181,16 +237,45 @@ impl Zonefile {
     /// with the question mark operator.
     pub fn next_entry(&mut self) -> Result<Option<Entry>, Error> {
         loop {
+            if let Some(generate) = self.generate.as_mut() {
+                self.entry_line = generate.line;
+                match generate.next_record(
+                    self.origin.clone(),
+                    self.last_ttl,
+                    self.last_class,
+                ) {
+                    Some(Ok(record)) => {
+                        // Records with a blank owner following the
+                        // directive use the last generated owner.
+                        self.last_owner = Some(record.owner().clone());
+                        return Ok(Some(record.into()));
+                    }
+                    Some(Err(err)) => return Err(err),
+                    None => self.generate = None,
+                }
+            }
             match EntryScanner::new(self)?.scan_entry()? {
                 ScannedEntry::Entry(entry) => return Ok(Some(entry)),
                 ScannedEntry::Origin(origin) => self.origin = Some(origin),
                 ScannedEntry::Ttl(ttl) => self.last_ttl = ttl,
+                ScannedEntry::Generate(generate) => {
+                    self.generate = Some(generate)
+                }
                 ScannedEntry::Empty => {}
                 ScannedEntry::Eof => return Ok(None),
             }
         }
     }
 
+    /// Returns the line number of the entry returned last.
+    ///
+    /// This is the line the entry starts on, counting from one. Records
+    /// generated by a `$GENERATE` directive have the line of the directive.
+    /// Before the first entry has been returned, the line number is zero.
+    pub fn entry_line(&self) -> usize {
+        self.entry_line
+    }
+
     /// Returns the origin name of the zonefile.
     pub fn origin(&self) -> Result<Name<Bytes>, EntryError> {
         self.origin
@@ -220,7 +305,8 @@ pub enum Entry {
     ///
     /// When this entry is encountered, the referenced file should be scanned
     /// next. If `origin` is given, this file should be scanned with it as the
-    /// initial origin name,
+    /// initial origin name, otherwise with the current origin.
+    /// [`ResolvingZonefile`] does this automatically.
     Include {
         /// The path to the file to be included.
         path: ScannedString,
@@ -230,6 +316,157 @@ pub enum Entry {
     },
 }
 
+impl From<ScannedRecord> for Entry {
+    fn from(record: ScannedRecord) -> Self {
+        Entry::Record(record)
+    }
+}
+
+//------------ ResolvingZonefile ---------------------------------------------
+
+/// The maximum nesting depth of included files.
+///
+/// This protects against files that include themselves.
+#[cfg(feature = "std")]
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// A zonefile that resolves its include directives.
+///
+/// Whereas [`Zonefile`] returns `$INCLUDE` directives as [`Entry::Include`]
+/// and leaves it to the user to deal with them, this type loads the
+/// included files via an [`IncludeLoader`] and returns their records in
+/// place of the directive. Included files may include further files up to
+/// a nesting depth of 16.
+///
+/// As described in [RFC 1035, section 5.1], an included file starts with
+/// the origin given in the directive or, if there is none, with the
+/// current origin of the including file. Changes to the origin within the
+/// included file don’t affect the including file.
+///
+/// [RFC 1035, section 5.1]: https://tools.ietf.org/html/rfc1035#section-5.1
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Clone, Debug)]
+pub struct ResolvingZonefile<L> {
+    /// The zonefile being scanned and the files that included it.
+    stack: Vec<Zonefile>,
+
+    /// The loader for included files.
+    loader: L,
+}
+
+#[cfg(feature = "std")]
+impl<L: IncludeLoader> ResolvingZonefile<L> {
+    /// Creates a new value scanning `zonefile` with the given loader.
+    pub fn new(zonefile: Zonefile, loader: L) -> Self {
+        ResolvingZonefile {
+            stack: vec![zonefile],
+            loader,
+        }
+    }
+
+    /// Returns the next record.
+    ///
+    /// Returns `Ok(None)` if the end of the zonefile has been reached.
+    /// Returns an error if scanning the next entry failed or if an included
+    /// file could not be loaded.
+    pub fn next_record(
+        &mut self,
+    ) -> Result<Option<ScannedRecord>, IncludeError> {
+        loop {
+            let Some(zonefile) = self.stack.last_mut() else {
+                return Ok(None);
+            };
+            match zonefile.next_entry()? {
+                Some(Entry::Record(record)) => return Ok(Some(record)),
+                Some(Entry::Include { path, origin }) => {
+                    let origin = origin.or_else(|| zonefile.origin.clone());
+                    if self.stack.len() >= MAX_INCLUDE_DEPTH {
+                        return Err(IncludeError::TooDeep);
+                    }
+                    let mut included = self
+                        .loader
+                        .load(&path)
+                        .map_err(|err| IncludeError::Load(path, err))?;
+                    if let Some(origin) = origin {
+                        included.set_origin(origin);
+                    }
+                    self.stack.push(included);
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<L: IncludeLoader> Iterator for ResolvingZonefile<L> {
+    type Item = Result<ScannedRecord, IncludeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().transpose()
+    }
+}
+
+//------------ IncludeLoader -------------------------------------------------
+
+/// A type that loads the files included by a zonefile.
+///
+/// The loader receives the path exactly as given in the `$INCLUDE`
+/// directive. It is up to the loader how to interpret it. This allows
+/// restricting the files that can be included or providing files from
+/// somewhere else than the file system.
+///
+/// The trait is implemented for closures taking the path and returning
+/// the loaded zonefile. [`FsLoader`] loads files from the file system.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub trait IncludeLoader {
+    /// Loads the file with the given path.
+    fn load(&mut self, path: &str) -> Result<Zonefile, std::io::Error>;
+}
+
+#[cfg(feature = "std")]
+impl<F> IncludeLoader for F
+where
+    F: FnMut(&str) -> Result<Zonefile, std::io::Error>,
+{
+    fn load(&mut self, path: &str) -> Result<Zonefile, std::io::Error> {
+        self(path)
+    }
+}
+
+//------------ FsLoader ------------------------------------------------------
+
+/// An include loader that loads files from the file system.
+///
+/// Relative paths are resolved against a base directory, typically the
+/// directory of the zonefile including them.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Clone, Debug)]
+pub struct FsLoader {
+    /// The directory relative paths are resolved against.
+    base: std::path::PathBuf,
+}
+
+#[cfg(feature = "std")]
+impl FsLoader {
+    /// Creates a loader resolving relative paths against `base`.
+    pub fn new(base: impl Into<std::path::PathBuf>) -> Self {
+        FsLoader { base: base.into() }
+    }
+}
+
+#[cfg(feature = "std")]
+impl IncludeLoader for FsLoader {
+    fn load(&mut self, path: &str) -> Result<Zonefile, std::io::Error> {
+        Zonefile::load(&mut std::fs::File::open(self.base.join(path))?)
+    }
+}
+
 //------------ ScannedEntry --------------------------------------------------
 
 /// A raw scanned entry of a zonefile.
@@ -247,6 +484,9 @@ enum ScannedEntry {
     /// A `$TTL` directive changing the default TTL if it isn’t given.
     Ttl(Ttl),
 
+    /// A `$GENERATE` directive producing a range of records.
+    Generate(Generate),
+
     /// An empty entry.
     Empty,
 
@@ -288,6 +528,7 @@ impl<'a> EntryScanner<'a> {
             ItemCat::None => Ok(ScannedEntry::Eof),
             ItemCat::LineFeed => Ok(ScannedEntry::Empty),
             ItemCat::Unquoted | ItemCat::Quoted => {
+                self.zonefile.entry_line = self.zonefile.buf.line_num;
                 if self.zonefile.buf.has_space {
                     // Indented entry: a record with the last owner as the
                     // owner.
@@ -471,6 +712,8 @@ impl<'a> EntryScanner<'a> {
             let ttl = u32::scan(self)?;
             self.zonefile.buf.require_line_feed()?;
             Ok(ScannedEntry::Ttl(Ttl::from_secs(ttl)))
+        } else if ctrl.eq_ignore_ascii_case("$GENERATE") {
+            Generate::scan(self).map(ScannedEntry::Generate)
         } else {
             Err(EntryError::unknown_control())
         }
@@ -953,6 +1196,320 @@ impl EntryScanner<'_> {
     }
 }
 
+//------------ Generate ------------------------------------------------------
+
+/// A `$GENERATE` directive being expanded.
+///
+/// This is an extension introduced by BIND. The directive has the form
+///
+/// ```text
+/// $GENERATE <range> <lhs> [<ttl>] [<class>] <type> <rhs>
+/// ```
+///
+/// where `range` is `<start>-<stop>` optionally followed by `/<step>`. For
+/// each value in the range, a record is generated from the remaining
+/// tokens with each `$` in `lhs` and `rhs` replaced by the value. A
+/// literal dollar sign is given as `\$`. Instead of a plain `$`, the
+/// form `${offset[,width[,base]]}` adds `offset` to the value and prints
+/// it zero-padded to `width` characters in the given base. The base is
+/// one of `d` for decimal, `o` for octal, `x` and `X` for lower and upper
+/// case hexadecimal, and `n` and `N` for the reversed lower and upper case
+/// hexadecimal nibbles separated by dots as used in reverse zones. Width
+/// includes the dots in the latter case.
+#[derive(Clone, Debug)]
+struct Generate {
+    /// The next value of the range.
+    next: u64,
+
+    /// The last value of the range.
+    stop: u64,
+
+    /// The distance between values.
+    step: u64,
+
+    /// The template for the owner name.
+    lhs: Template,
+
+    /// The templates for the remaining tokens.
+    rest: Vec<Template>,
+
+    /// The line of the directive for error reporting.
+    line: usize,
+}
+
+impl Generate {
+    /// Scans the arguments of a `$GENERATE` directive.
+    fn scan(scanner: &mut EntryScanner) -> Result<Self, EntryError> {
+        let line = scanner.zonefile.buf.line_num;
+        let (start, stop, step) = scanner.scan_ascii_str(|s| {
+            let (range, step) = match s.split_once('/') {
+                Some((range, step)) => (range, step.parse::<u32>().ok()),
+                None => (s, Some(1)),
+            };
+            let range = range.split_once('-').and_then(|(start, stop)| {
+                Some((start.parse::<u32>().ok()?, stop.parse::<u32>().ok()?))
+            });
+            match (range, step) {
+                (Some((start, stop)), Some(step))
+                    if start <= stop && step > 0 =>
+                {
+                    Ok((start, stop, step))
+                }
+                _ => Err(EntryError::custom("invalid $GENERATE range")),
+            }
+        })?;
+        let lhs = Template::scan(scanner)?;
+        let mut rest = Vec::new();
+        while !scanner.zonefile.buf.is_line_feed() {
+            rest.push(Template::scan(scanner)?);
+        }
+        if rest.len() < 2 {
+            return Err(EntryError::custom("expected $GENERATE rhs"));
+        }
+        Ok(Generate {
+            next: start.into(),
+            stop: stop.into(),
+            step: step.into(),
+            lhs,
+            rest,
+            line,
+        })
+    }
+
+    /// Returns the next generated record.
+    ///
+    /// Returns `None` once the range is exhausted.
+    fn next_record(
+        &mut self,
+        origin: Option<Name<Bytes>>,
+        last_ttl: Ttl,
+        last_class: Class,
+    ) -> Option<Result<ScannedRecord, Error>> {
+        if self.next > self.stop {
+            return None;
+        }
+        let value = self.next;
+        self.next += self.step;
+        Some(self.generate(value, origin, last_ttl, last_class).map_err(
+            |err| Error {
+                err,
+                line: self.line,
+                col: 1,
+            },
+        ))
+    }
+
+    /// Generates the record for a value.
+    fn generate(
+        &self,
+        value: u64,
+        origin: Option<Name<Bytes>>,
+        last_ttl: Ttl,
+        last_class: Class,
+    ) -> Result<ScannedRecord, EntryError> {
+        let mut entry = String::new();
+        self.lhs.expand(value, &mut entry)?;
+        for template in &self.rest {
+            entry.push(' ');
+            template.expand(value, &mut entry)?;
+        }
+        entry.push('\n');
+
+        let mut zonefile = Zonefile::from(entry.as_str());
+        zonefile.origin = origin;
+        zonefile.last_ttl = last_ttl;
+        zonefile.last_class = last_class;
+        match zonefile.next_entry() {
+            Ok(Some(Entry::Record(record))) => Ok(record),
+            Ok(_) => Err(EntryError::custom("invalid $GENERATE template")),
+            Err(err) => Err(err.err),
+        }
+    }
+}
+
+//------------ Template ------------------------------------------------------
+
+/// A token of a `$GENERATE` directive with its substitutions.
+#[derive(Clone, Debug)]
+struct Template(Vec<TemplatePart>);
+
+/// A part of a template.
+#[derive(Clone, Debug)]
+enum TemplatePart {
+    /// Text to be copied into the record.
+    Literal(String),
+
+    /// A substitution of the value.
+    Value {
+        /// The offset to add to the value.
+        offset: i64,
+
+        /// The minimum width of the value.
+        width: usize,
+
+        /// The base to print the value in.
+        base: char,
+    },
+}
+
+impl Template {
+    /// Scans a template token.
+    fn scan(scanner: &mut EntryScanner) -> Result<Self, EntryError> {
+        let quoted = scanner.zonefile.buf.cat == ItemCat::Quoted;
+        let mut symbols = Vec::new();
+        scanner.scan_symbols(|sym| {
+            symbols.push(sym);
+            Ok(())
+        })?;
+
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        if quoted {
+            literal.push('"');
+        }
+        let mut symbols = symbols.into_iter().peekable();
+        while let Some(sym) = symbols.next() {
+            if sym != Symbol::Char('$') {
+                // Keep escape sequences so the token scans the same.
+                write!(literal, "{}", sym).expect("writing to string");
+                continue;
+            }
+            if !literal.is_empty() {
+                parts.push(TemplatePart::Literal(literal));
+                literal = String::new();
+            }
+            if symbols.next_if_eq(&Symbol::Char('{')).is_none() {
+                parts.push(TemplatePart::Value {
+                    offset: 0,
+                    width: 0,
+                    base: 'd',
+                });
+                continue;
+            }
+            let mut spec = String::new();
+            loop {
+                match symbols.next() {
+                    Some(Symbol::Char('}')) => break,
+                    Some(Symbol::Char(ch)) => spec.push(ch),
+                    _ => {
+                        return Err(EntryError::custom(
+                            "invalid $GENERATE modifier",
+                        ))
+                    }
+                }
+            }
+            parts.push(Self::parse_modifier(&spec)?);
+        }
+        if quoted {
+            literal.push('"');
+        }
+        if !literal.is_empty() {
+            parts.push(TemplatePart::Literal(literal));
+        }
+        Ok(Template(parts))
+    }
+
+    /// Parses the `offset[,width[,base]]` modifier of a substitution.
+    fn parse_modifier(spec: &str) -> Result<TemplatePart, EntryError> {
+        fn invalid() -> EntryError {
+            EntryError::custom("invalid $GENERATE modifier")
+        }
+
+        let mut fields = spec.split(',');
+        let offset = fields
+            .next()
+            .and_then(|offset| offset.parse().ok())
+            .ok_or_else(invalid)?;
+        let width = match fields.next() {
+            Some(width) => width.parse().map_err(|_| invalid())?,
+            None => 0,
+        };
+        // Anything wider can’t be part of a domain name anyway.
+        if width > 255 {
+            return Err(EntryError::custom("$GENERATE width out of range"));
+        }
+        let base = match fields.next() {
+            Some(base) => {
+                let mut chars = base.chars();
+                match (chars.next(), chars.next()) {
+                    (
+                        Some(base @ ('d' | 'o' | 'x' | 'X' | 'n' | 'N')),
+                        None,
+                    ) => base,
+                    _ => return Err(invalid()),
+                }
+            }
+            None => 'd',
+        };
+        if fields.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(TemplatePart::Value {
+            offset,
+            width,
+            base,
+        })
+    }
+
+    /// Appends the template expanded for `value` to `target`.
+    fn expand(
+        &self,
+        value: u64,
+        target: &mut String,
+    ) -> Result<(), EntryError> {
+        for part in &self.0 {
+            let (offset, width, base) = match *part {
+                TemplatePart::Literal(ref literal) => {
+                    target.push_str(literal);
+                    continue;
+                }
+                TemplatePart::Value {
+                    offset,
+                    width,
+                    base,
+                } => (offset, width, base),
+            };
+            let value = i64::try_from(value)
+                .ok()
+                .and_then(|value| value.checked_add(offset))
+                .and_then(|value| u64::try_from(value).ok())
+                .ok_or_else(|| {
+                    EntryError::custom("$GENERATE value out of range")
+                })?;
+            match base {
+                'o' => write!(target, "{:0width$o}", value),
+                'x' => write!(target, "{:0width$x}", value),
+                'X' => write!(target, "{:0width$X}", value),
+                'n' | 'N' => {
+                    let digits = if base == 'n' {
+                        format!("{:x}", value)
+                    } else {
+                        format!("{:X}", value)
+                    };
+                    // Pad with zero nibbles until the dotted form is at
+                    // least `width` characters long.
+                    let padding =
+                        ((width + 2) / 2).saturating_sub(digits.len());
+                    let nibbles = digits
+                        .chars()
+                        .rev()
+                        .chain(core::iter::repeat('0').take(padding));
+                    for (i, ch) in nibbles.enumerate() {
+                        if i > 0 {
+                            target.push('.');
+                        }
+                        target.push(ch);
+                    }
+                    Ok(())
+                }
+                _ => write!(target, "{:0width$}", value),
+            }
+            .expect("writing to string");
+        }
+        Ok(())
+    }
+}
+
 //------------ SourceBuf -----------------------------------------------------
 
 /// The buffer to read data from and also into if possible.
@@ -1531,6 +2088,48 @@ impl fmt::Display for Error {
 #[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
+//------------ IncludeError --------------------------------------------------
+
+/// An error happened while scanning a zonefile with its included files.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug)]
+pub enum IncludeError {
+    /// Scanning an entry failed.
+    Scan(Error),
+
+    /// The included file with the given path could not be loaded.
+    Load(ScannedString, std::io::Error),
+
+    /// Included files were nested too deeply.
+    TooDeep,
+}
+
+#[cfg(feature = "std")]
+impl From<Error> for IncludeError {
+    fn from(err: Error) -> Self {
+        IncludeError::Scan(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for IncludeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IncludeError::Scan(err) => err.fmt(f),
+            IncludeError::Load(path, err) => {
+                write!(f, "cannot include {}: {}", path, err)
+            }
+            IncludeError::TooDeep => {
+                f.write_str("includes nested too deeply")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IncludeError {}
+
 //============ Tests =========================================================
 
 #[cfg(test)]
@@ -1677,4 +2276,176 @@ mod test {
             "../../test-data/zonefiles/stroverflow.yaml"
         ));
     }
+
+    #[test]
+    fn entry_line() {
+        let mut zone = Zonefile::from(
+            "$ORIGIN example.\n\
+             \n\
+             ; comment\n\
+             @ 3600 IN SOA ns1 hostmaster (\n\
+                 1 3600 900 604800 300 )\n\
+             \x20 NS ns1\n\
+             $GENERATE 1-2 host$ A 192.0.2.$\n\
+             ns1 A 192.0.2.1\n",
+        );
+        let mut lines = Vec::new();
+        while zone.next_entry().unwrap().is_some() {
+            lines.push(zone.entry_line());
+        }
+        assert_eq!(lines, [4, 6, 7, 7, 8]);
+    }
+
+    #[test]
+    fn resolve_includes() {
+        use std::string::ToString;
+
+        fn loader(path: &str) -> Result<Zonefile, std::io::Error> {
+            match path {
+                "hosts" => Ok(Zonefile::from(
+                    "www A 192.0.2.1\n\
+                     $INCLUDE more sub.example.com.\n\
+                     mail A 192.0.2.2\n",
+                )),
+                "more" => Ok(Zonefile::from(
+                    "ftp A 192.0.2.3\n\
+                     $ORIGIN other.example.\n\
+                     ftp A 192.0.2.4\n",
+                )),
+                "loop" => Ok(Zonefile::from("$INCLUDE loop\n")),
+                _ => Err(std::io::ErrorKind::NotFound.into()),
+            }
+        }
+
+        let mut zone = Zonefile::from(
+            "$ORIGIN example.com.\n\
+             @ NS ns\n\
+             $INCLUDE hosts\n\
+             ns A 192.0.2.5\n",
+        );
+        zone.set_origin(Name::from_str("example.com.").unwrap());
+        let owners = ResolvingZonefile::new(zone, loader)
+            .map(|record| record.unwrap().owner().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            owners,
+            [
+                "example.com",
+                "www.example.com",
+                "ftp.sub.example.com",
+                "ftp.other.example",
+                "mail.example.com",
+                "ns.example.com",
+            ]
+        );
+
+        let zone = Zonefile::from("$INCLUDE missing example.com.\n");
+        assert!(matches!(
+            ResolvingZonefile::new(zone, loader).next_record(),
+            Err(IncludeError::Load(..))
+        ));
+
+        let zone = Zonefile::from("$INCLUDE loop example.com.\n");
+        assert!(matches!(
+            ResolvingZonefile::new(zone, loader).next_record(),
+            Err(IncludeError::TooDeep)
+        ));
+    }
+
+    #[test]
+    fn generate() {
+        use std::string::{String, ToString};
+
+        fn records(zone: &str) -> Result<Vec<String>, Error> {
+            let mut zone = Zonefile::from(zone);
+            zone.set_origin(Name::from_str("example.com.").unwrap());
+            zone.map(|entry| match entry? {
+                Entry::Record(record) => Ok(record.to_string()),
+                Entry::Include { .. } => unreachable!(),
+            })
+            .collect()
+        }
+
+        assert_eq!(
+            records(
+                "$GENERATE 1-3 host-$ A 192.0.2.$\n\
+                 www 60 A 192.0.2.10\n"
+            )
+            .unwrap(),
+            [
+                "host-1.example.com. 3600 IN A 192.0.2.1",
+                "host-2.example.com. 3600 IN A 192.0.2.2",
+                "host-3.example.com. 3600 IN A 192.0.2.3",
+                "www.example.com. 60 IN A 192.0.2.10",
+            ]
+        );
+        assert_eq!(
+            records(
+                "$GENERATE 0-4/2 ${10,3} 300 IN PTR host${0,2,X}.example.\n"
+            )
+            .unwrap(),
+            [
+                "010.example.com. 300 IN PTR host00.example.",
+                "012.example.com. 300 IN PTR host02.example.",
+                "014.example.com. 300 IN PTR host04.example.",
+            ]
+        );
+        assert_eq!(
+            records(
+                "$GENERATE 10-11 ${0,3,n} CNAME \\$${-10,0,o} ; comment\n"
+            )
+            .unwrap(),
+            [
+                "a.0.example.com. 3600 IN CNAME $0.example.com.",
+                "b.0.example.com. 3600 IN CNAME $1.example.com.",
+            ]
+        );
+        assert_eq!(
+            records(
+                "$GENERATE 1-2 host-$ A 192.0.2.$\n\
+                 \x20 AAAA 2001:db8::1\n"
+            )
+            .unwrap(),
+            [
+                "host-1.example.com. 3600 IN A 192.0.2.1",
+                "host-2.example.com. 3600 IN A 192.0.2.2",
+                "host-2.example.com. 3600 IN AAAA 2001:db8::1",
+            ]
+        );
+        assert_eq!(
+            records("$GENERATE 1-1 @ TXT \"record $\"\n").unwrap(),
+            ["example.com. 3600 IN TXT \"record 1\""]
+        );
+
+        assert!(records("$GENERATE 5-1 x A 192.0.2.1\n").is_err());
+        assert!(records("$GENERATE 1-2 ${a} A 192.0.2.1\n").is_err());
+        assert!(records("$GENERATE 1-2 ${0,1,q} A 192.0.2.1\n").is_err());
+        assert!(records("$GENERATE 1-2 x A\n").is_err());
+        assert!(records("$GENERATE 1-2 ${-2} A 192.0.2.1\n").is_err());
+        assert!(records("$GENERATE 1-1 h${0,256} A 192.0.2.1\n").is_err());
+        assert!(records("$GENERATE 1-1 h${0,2000000000,d} A 192.0.2.1\n")
+            .is_err());
+        assert!(records("$GENERATE 1-1 h${0,70000,x} A 192.0.2.1\n").is_err());
+        assert!(
+            records("$GENERATE 1-1 ${0,2000000,n} A 192.0.2.1\n").is_err()
+        );
+    }
+
+    #[test]
+    fn generate_nibble_width() {
+        use std::string::ToString;
+
+        let mut zone = Zonefile::from(
+            "$GENERATE 1-1 ${0,7,n} PTR host.\n\
+             $GENERATE 18-18 ${0,1,N} PTR host.\n",
+        );
+        zone.set_origin(Name::from_str("example.com.").unwrap());
+        let owners: Vec<_> = zone
+            .map(|entry| match entry.unwrap() {
+                Entry::Record(record) => record.owner().to_string(),
+                Entry::Include { .. } => unreachable!(),
+            })
+            .collect();
+        assert_eq!(owners, ["1.0.0.0.example.com", "2.1.example.com"]);
+    }
 }
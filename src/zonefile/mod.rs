@@ -2,4 +2,6 @@
 #![cfg(feature = "zonefile")]
 #![cfg_attr(docsrs, doc(cfg(feature = "zonefile")))]
 
+pub mod check;
 pub mod inplace;
+pub mod write;
@@ -0,0 +1,385 @@
+//! Writing zonefiles.
+//!
+//! The [`ZonefileWriter`] provided by this module writes a sequence of
+//! records as a master file in the format of RFC 1035 that is meant to be
+//! read and edited by humans as well as machines. If an origin and a
+//! default TTL are given, the file starts with the `$ORIGIN` and `$TTL`
+//! directives, owner names below the origin are written relative to it,
+//! and TTLs equal to the default are left out. (Since some readers,
+//! including this crate’s, assume a left out TTL to be that of the
+//! previous record instead, the TTL is still written for the first
+//! record with the default TTL after one with a different TTL.) The owner
+//! of a record is only written if it differs from the owner of the
+//! previous record, and the fields of each record are aligned in columns.
+//! Record data that would make a line longer than a given maximum is
+//! spread over multiple lines with comments explaining the fields, if the
+//! record type supports this.
+//!
+//! The output can be read again by [`Zonefile`][super::inplace::Zonefile],
+//! which allows reading a zone, modifying it, and writing it back out.
+//!
+//! ```
+//! use domain::base::{Name, Record, Ttl};
+//! use domain::base::iana::Class;
+//! use domain::rdata::{Ns, A};
+//! use domain::zonefile::write::ZonefileWriter;
+//! use std::str::FromStr;
+//!
+//! let origin = Name::<Vec<u8>>::from_str("example.com.").unwrap();
+//! let www = Name::<Vec<u8>>::from_str("www.example.com.").unwrap();
+//! let ttl = Ttl::from_secs(3600);
+//!
+//! let mut writer = ZonefileWriter::new(String::new());
+//! writer.set_origin(origin.clone());
+//! writer.set_default_ttl(ttl);
+//! writer.set_owner_width(8);
+//! writer.write_record(
+//!     &Record::new(origin.clone(), Class::IN, ttl, Ns::new(www.clone()))
+//! ).unwrap();
+//! writer.write_record(&Record::new(
+//!     www.clone(), Class::IN, ttl, A::from_octets(192, 0, 2, 1),
+//! )).unwrap();
+//! writer.write_record(&Record::new(
+//!     www, Class::IN, Ttl::from_secs(60), A::from_octets(192, 0, 2, 2),
+//! )).unwrap();
+//!
+//! assert_eq!(
+//!     writer.into_inner(),
+//!     "$ORIGIN example.com.\n\
+//!      $TTL 3600\n\
+//!      @               IN  NS      www.example.com.\n\
+//!      www             IN  A       192.0.2.1\n\
+//!      \x20       60      IN  A       192.0.2.2\n"
+//! );
+//! ```
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::fmt::Write;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::base::name::{Name, ToLabelIter, ToName};
+use crate::base::rdata::RecordData;
+use crate::base::record::Record;
+use crate::base::zonefile_fmt::{DisplayKind, ZonefileFmt};
+use crate::base::Ttl;
+
+//------------ Constants -----------------------------------------------------
+
+/// The default width of the owner column.
+pub const DEFAULT_OWNER_WIDTH: usize = 24;
+
+/// The default maximum line length before record data is split.
+pub const DEFAULT_MAX_LINE_LEN: usize = 80;
+
+/// The width of the TTL column.
+const TTL_WIDTH: usize = 8;
+
+/// The width of the class column.
+const CLASS_WIDTH: usize = 4;
+
+/// The width of the record type column.
+const RTYPE_WIDTH: usize = 8;
+
+//------------ ZonefileWriter ------------------------------------------------
+
+/// A writer producing a zonefile from a sequence of records.
+///
+/// The writer wraps a target implementing [`fmt::Write`]. The directives
+/// at the start of the file are written together with the first record,
+/// so the origin and default TTL need to be set before that.
+#[derive(Clone, Debug)]
+pub struct ZonefileWriter<W> {
+    /// The target to write to.
+    target: W,
+
+    /// The origin of the zone, if any.
+    origin: Option<Name<Vec<u8>>>,
+
+    /// The default TTL of the zone, if any.
+    default_ttl: Option<Ttl>,
+
+    /// The width of the owner column.
+    owner_width: usize,
+
+    /// The line length above which record data is split.
+    max_line_len: usize,
+
+    /// The owner of the previous record.
+    last_owner: Option<Name<Vec<u8>>>,
+
+    /// The TTL of the previous record.
+    last_ttl: Option<Ttl>,
+
+    /// Whether the directives have been written already.
+    started: bool,
+}
+
+impl<W: fmt::Write> ZonefileWriter<W> {
+    /// Creates a new writer for the given target.
+    pub fn new(target: W) -> Self {
+        ZonefileWriter {
+            target,
+            origin: None,
+            default_ttl: None,
+            owner_width: DEFAULT_OWNER_WIDTH,
+            max_line_len: DEFAULT_MAX_LINE_LEN,
+            last_owner: None,
+            last_ttl: None,
+            started: false,
+        }
+    }
+
+    /// Sets the origin of the zone.
+    ///
+    /// The origin is written as an `$ORIGIN` directive and owner names at
+    /// or below it are written relative to it.
+    pub fn set_origin(&mut self, origin: impl ToName) {
+        self.origin = Some(origin.to_vec());
+    }
+
+    /// Sets the default TTL of the zone.
+    ///
+    /// The TTL is written as a `$TTL` directive and left out of records
+    /// that have this TTL unless the previous record had a different one.
+    pub fn set_default_ttl(&mut self, ttl: Ttl) {
+        self.default_ttl = Some(ttl);
+    }
+
+    /// Sets the width of the owner column.
+    ///
+    /// Owner names that are longer than this are followed by a single
+    /// space. The default is [`DEFAULT_OWNER_WIDTH`].
+    pub fn set_owner_width(&mut self, width: usize) {
+        self.owner_width = width;
+    }
+
+    /// Sets the line length above which record data is split.
+    ///
+    /// The default is [`DEFAULT_MAX_LINE_LEN`].
+    pub fn set_max_line_len(&mut self, len: usize) {
+        self.max_line_len = len;
+    }
+
+    /// Writes a single record.
+    pub fn write_record<N, D>(&mut self, record: &Record<N, D>) -> fmt::Result
+    where
+        N: ToName,
+        D: RecordData + ZonefileFmt,
+    {
+        if !self.started {
+            self.write_directives()?;
+        }
+
+        let mut line = String::new();
+        let owner = record.owner();
+        if self
+            .last_owner
+            .as_ref()
+            .is_some_and(|last| last.name_eq(owner))
+        {
+            pad(&mut line, self.owner_width);
+        } else {
+            self.write_owner(&mut line, owner)?;
+            pad(&mut line, self.owner_width);
+            self.last_owner = Some(owner.to_vec());
+        }
+        let ttl = Some(record.ttl());
+        if ttl != self.default_ttl || ttl != self.last_ttl {
+            write!(line, "{}", record.ttl().as_secs())?;
+        }
+        self.last_ttl = ttl;
+        pad(&mut line, self.owner_width + TTL_WIDTH);
+        write!(line, "{}", record.class())?;
+        pad(&mut line, self.owner_width + TTL_WIDTH + CLASS_WIDTH);
+        write!(line, "{}", record.rtype())?;
+        pad(
+            &mut line,
+            self.owner_width + TTL_WIDTH + CLASS_WIDTH + RTYPE_WIDTH,
+        );
+
+        let data = record.data();
+        let indent = line.len();
+        write!(line, "{}", data.display_zonefile(DisplayKind::Simple))?;
+        if line.len() > self.max_line_len {
+            line.truncate(indent);
+            let multi = data.display_zonefile(DisplayKind::Multiline);
+            let multi = format!("{}", multi);
+            for (i, part) in multi.split('\n').enumerate() {
+                if i > 0 {
+                    line.push('\n');
+                    line.extend(core::iter::repeat(' ').take(indent));
+                }
+                line.push_str(part);
+            }
+        }
+        self.target.write_str(&line)?;
+        self.target.write_char('\n')
+    }
+
+    /// Writes all records of a sequence.
+    pub fn write_records<I, R, N, D>(&mut self, records: I) -> fmt::Result
+    where
+        I: IntoIterator<Item = R>,
+        R: Borrow<Record<N, D>>,
+        N: ToName,
+        D: RecordData + ZonefileFmt,
+    {
+        for record in records {
+            self.write_record(record.borrow())?;
+        }
+        Ok(())
+    }
+
+    /// Returns the target, consuming the writer.
+    pub fn into_inner(self) -> W {
+        self.target
+    }
+
+    /// Writes the `$ORIGIN` and `$TTL` directives.
+    fn write_directives(&mut self) -> fmt::Result {
+        self.started = true;
+        if let Some(origin) = self.origin.as_ref() {
+            writeln!(self.target, "$ORIGIN {}", origin.fmt_with_dot())?;
+        }
+        if let Some(ttl) = self.default_ttl {
+            writeln!(self.target, "$TTL {}", ttl.as_secs())?;
+            self.last_ttl = Some(ttl);
+        }
+        Ok(())
+    }
+
+    /// Writes an owner name relative to the origin if possible.
+    fn write_owner(
+        &self,
+        line: &mut String,
+        owner: &impl ToName,
+    ) -> fmt::Result {
+        let origin = match self.origin.as_ref() {
+            Some(origin) if owner.ends_with(origin) => origin,
+            _ => return write!(line, "{}", owner.fmt_with_dot()),
+        };
+        let count =
+            owner.iter_labels().count() - origin.iter_labels().count();
+        if count == 0 {
+            line.push('@');
+            return Ok(());
+        }
+        for (i, label) in owner.iter_labels().take(count).enumerate() {
+            if i > 0 {
+                line.push('.');
+            }
+            write!(line, "{}", label)?;
+        }
+        Ok(())
+    }
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Pads a line with spaces up to the given column.
+///
+/// At least one space is added if the line is already at or past the
+/// column, so that columns are always separated.
+fn pad(line: &mut String, column: usize) {
+    let start = line.rfind('\n').map(|pos| pos + 1).unwrap_or(0);
+    let len = line.len() - start;
+    let target = if len < column { column } else { len + 1 };
+    line.extend(core::iter::repeat(' ').take(target - len));
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::iana::SecAlg;
+    use crate::zonefile::inplace::{Entry, Zonefile};
+    use core::str::FromStr;
+
+    fn read(
+        text: &str,
+        origin: &str,
+    ) -> Vec<crate::zonefile::inplace::ScannedRecord> {
+        let mut zone = Zonefile::from(text);
+        zone.set_origin(Name::from_str(origin).unwrap());
+        zone.map(|entry| match entry.unwrap() {
+            Entry::Record(record) => record,
+            _ => panic!("unexpected entry"),
+        })
+        .collect()
+    }
+
+    #[test]
+    fn round_trip() {
+        let records = read(
+            "$TTL 3600\n\
+             @ IN SOA ns1 hostmaster 1 3600 900 604800 300\n\
+             \x20 IN NS ns1\n\
+             ns1 IN A 192.0.2.1\n\
+             ns1 300 IN AAAA 2001:db8::1\n\
+             www.sub 3600 IN TXT \"hello world\"\n\
+             other.org. IN CNAME www.sub\n\
+             @ IN DNSKEY 257 3 15 \
+             l02Woi0iS8Aa25FQkUd9RMzZHJpBoRQwAQEX1SxZJA4=\n",
+            "example.com.",
+        );
+
+        let mut writer = ZonefileWriter::new(String::new());
+        writer.set_origin(Name::<Vec<u8>>::from_str("example.com.").unwrap());
+        writer.set_default_ttl(Ttl::from_secs(3600));
+        writer.set_owner_width(8);
+        writer.write_records(&records).unwrap();
+        let text = writer.into_inner();
+
+        assert_eq!(
+            text,
+            [
+                "$ORIGIN example.com.",
+                "$TTL 3600",
+                "@               IN  SOA     ( ns1.example.com.\t; mname",
+                "                              hostmaster.example.com.\t; rname",
+                "                              1\t; serial",
+                "                              3600\t; refresh ( 1 hour)",
+                "                              900\t; retry ( 15 minutes)",
+                "                              604800\t; expire ( 1 week)",
+                "                              300\t; minumum ( 5 minutes)",
+                "                              )",
+                "                IN  NS      ns1.example.com.",
+                "ns1             IN  A       192.0.2.1",
+                "        300     IN  AAAA    2001:db8::1",
+                "www.sub 3600    IN  TXT     \"hello world\"",
+                "other.org.      IN  CNAME   www.sub.example.com.",
+                "@               IN  DNSKEY  ( 257\t; flags: sep zone_key",
+                "                              3\t; protocol",
+                "                              15\t; algorithm: ED25519",
+                "                              \
+                 l02Woi0iS8Aa25FQkUd9RMzZHJpBoRQwAQEX1SxZJA4=\t\
+                 ; key tag: 3613",
+                "                              )",
+                "",
+            ]
+            .join("\n")
+        );
+
+        let reread = read(&text, "example.com.");
+        assert_eq!(reread, records);
+        assert!(matches!(
+            reread.last().unwrap().data(),
+            crate::rdata::ZoneRecordData::Dnskey(key)
+                if key.algorithm() == SecAlg::ED25519
+        ));
+    }
+
+    #[test]
+    fn without_origin() {
+        let records = read("www 60 IN A 192.0.2.1\n", "example.com.");
+        let mut writer = ZonefileWriter::new(String::new());
+        writer.write_records(records.iter()).unwrap();
+        assert_eq!(
+            writer.into_inner(),
+            "www.example.com.        60      IN  A       192.0.2.1\n"
+        );
+    }
+}
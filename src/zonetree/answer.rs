@@ -97,6 +97,20 @@ impl Answer {
         self.content = AnswerContent::Data(answer);
     }
 
+    /// Adds a DNAME and the CNAME synthesized from it to the answer section.
+    ///
+    /// The `cname` should be `None` if the synthesized name would have
+    /// been too long, in which case the answer’s response code should be
+    /// [`Rcode::YXDOMAIN`].
+    pub fn add_dname(
+        &mut self,
+        owner: StoredName,
+        dname: SharedRr,
+        cname: Option<SharedRr>,
+    ) {
+        self.content = AnswerContent::Dname(owner, dname, cname);
+    }
+
     /// Sets the content of the additional section.
     pub fn set_additional(&mut self, additional: AnswerAdditional) {
         self.additional = Some(additional)
@@ -159,6 +173,16 @@ impl Answer {
             AnswerContent::Cname(ref cname) => builder
                 .push((qname, qclass, cname.ttl(), cname.data()))
                 .unwrap(),
+            AnswerContent::Dname(ref owner, ref dname, ref cname) => {
+                builder
+                    .push((owner, qclass, dname.ttl(), dname.data()))
+                    .unwrap();
+                if let Some(cname) = cname {
+                    builder
+                        .push((qname, qclass, cname.ttl(), cname.data()))
+                        .unwrap();
+                }
+            }
             AnswerContent::NoData => {}
         }
 
@@ -244,6 +268,13 @@ pub enum AnswerContent {
     /// An answer consisting of a CNAME RR.
     Cname(SharedRr),
 
+    /// An answer redirected by a DNAME RR.
+    ///
+    /// Contains the owner name of the DNAME RR, the RR itself, and the
+    /// CNAME RR synthesized for the query name unless the synthesized name
+    /// would have been too long.
+    Dname(StoredName, SharedRr, Option<SharedRr>),
+
     /// An empty answer.
     NoData,
 }
@@ -271,7 +302,8 @@ impl AnswerContent {
                 .data()
                 .first()
                 .map(|data| (shared_rrset.ttl(), data.clone())),
-            AnswerContent::Cname(shared_rr) => {
+            AnswerContent::Cname(shared_rr)
+            | AnswerContent::Dname(_, shared_rr, _) => {
                 Some((shared_rr.ttl(), shared_rr.data().clone()))
             }
             AnswerContent::NoData => None,
@@ -0,0 +1,276 @@
+//! Calculating the differences between zone versions.
+//!
+//! Incremental zone transfer as defined in [RFC 1995] transmits the
+//! changes between two versions of a zone rather than the whole of the new
+//! version. This module provides [`diff`] for calculating these changes
+//! from two versions of a zone as an [`InMemoryZoneDiff`], the same type
+//! that is recorded when changing an in-memory zone, and
+//! [`ixfr_records`] for turning such a diff into the sequence of records
+//! used by IXFR.
+//!
+//! The inverse, applying an IXFR response to a zone, is done by feeding the
+//! response messages to an [`XfrResponseInterpreter`] and passing the
+//! resulting updates to a [`ZoneUpdater`].
+//!
+//! [RFC 1995]: https://tools.ietf.org/html/rfc1995
+//! [`XfrResponseInterpreter`]:
+//!     crate::net::xfr::protocol::XfrResponseInterpreter
+//! [`ZoneUpdater`]: super::update::ZoneUpdater
+use std::boxed::Box;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::vec::Vec;
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::{Class, Rtype};
+use crate::base::Record;
+
+use super::types::ZoneDiffError;
+use super::{
+    InMemoryZoneDiff, InMemoryZoneDiffBuilder, Rrset, SharedRrset,
+    StoredName, StoredRecord, Zone,
+};
+
+//------------ diff ----------------------------------------------------------
+
+/// Calculates the differences between two versions of a zone.
+///
+/// The returned diff contains the records that need to be removed from
+/// `old` and the records that need to be added to it to arrive at `new`.
+/// Changes happen on the level of records, so unchanged records of an
+/// RRset aren’t part of the diff. If the TTL of an RRset has changed, the
+/// whole RRset is removed and added again.
+///
+/// Both zones are read via their synchronous
+/// [`walk`][super::ReadableZone::walk].
+///
+/// Returns an error if either zone lacks a SOA record or if the serial of
+/// `new` isn’t greater than that of `old`.
+pub fn diff(
+    old: &Zone,
+    new: &Zone,
+) -> Result<InMemoryZoneDiff, ZoneDiffError> {
+    let old_rrsets = collect_rrsets(old);
+    let mut new_rrsets = collect_rrsets(new);
+    let mut builder = InMemoryZoneDiffBuilder::new();
+
+    for ((owner, rtype), old_rrset) in old_rrsets {
+        let new_rrset = match new_rrsets.remove(&(owner.clone(), rtype)) {
+            Some(new_rrset) => new_rrset,
+            None => {
+                builder.remove(owner, rtype, old_rrset);
+                continue;
+            }
+        };
+        if old_rrset.ttl() != new_rrset.ttl() {
+            builder.remove(owner.clone(), rtype, old_rrset);
+            builder.add(owner, rtype, new_rrset);
+            continue;
+        }
+        let removed = difference(&old_rrset, &new_rrset);
+        if !removed.is_empty() {
+            builder.remove(owner.clone(), rtype, removed.into_shared());
+        }
+        let added = difference(&new_rrset, &old_rrset);
+        if !added.is_empty() {
+            builder.add(owner, rtype, added.into_shared());
+        }
+    }
+    for ((owner, rtype), new_rrset) in new_rrsets {
+        builder.add(owner, rtype, new_rrset);
+    }
+
+    builder.build()
+}
+
+//------------ ixfr_records --------------------------------------------------
+
+/// Returns the records representing a diff in an IXFR response.
+///
+/// As described in [RFC 1995, section 4], the returned sequence starts
+/// with the old SOA record followed by the removed records, then the new
+/// SOA record followed by the added records. The removed and added records
+/// are each given in canonical order.
+///
+/// A complete IXFR response consists of the sequences of one or more
+/// consecutive diffs enclosed in the SOA record of the newest version of
+/// the zone.
+///
+/// [RFC 1995, section 4]: https://tools.ietf.org/html/rfc1995#section-4
+pub fn ixfr_records(
+    diff: &InMemoryZoneDiff,
+    class: Class,
+) -> Vec<StoredRecord> {
+    let mut res = Vec::new();
+    push_records(&mut res, &diff.removed, class);
+    push_records(&mut res, &diff.added, class);
+    res
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Collects all RRsets of a zone.
+fn collect_rrsets(zone: &Zone) -> HashMap<(StoredName, Rtype), SharedRrset> {
+    let rrsets = Arc::new(Mutex::new(HashMap::new()));
+    let cloned_rrsets = rrsets.clone();
+    zone.read()
+        .walk(Box::new(move |owner, rrset, _at_zone_cut| {
+            if !rrset.is_empty() {
+                cloned_rrsets
+                    .lock()
+                    .unwrap()
+                    .insert((owner, rrset.rtype()), rrset.clone());
+            }
+        }));
+    Arc::try_unwrap(rrsets)
+        .map(|rrsets| rrsets.into_inner().unwrap())
+        .unwrap_or_else(|rrsets| rrsets.lock().unwrap().clone())
+}
+
+/// Returns an RRset with the records of `left` that aren’t in `right`.
+fn difference(left: &SharedRrset, right: &SharedRrset) -> Rrset {
+    let mut res = Rrset::new(left.rtype(), left.ttl());
+    for data in left.data() {
+        if !right.data().contains(data) {
+            res.push_data(data.clone());
+        }
+    }
+    res
+}
+
+/// Appends the SOA record and then all other records of one side of a diff.
+fn push_records(
+    target: &mut Vec<StoredRecord>,
+    rrsets: &HashMap<(StoredName, Rtype), SharedRrset>,
+    class: Class,
+) {
+    let mut records = Vec::new();
+    for ((owner, rtype), rrset) in rrsets {
+        for data in rrset.data() {
+            let record =
+                Record::new(owner.clone(), class, rrset.ttl(), data.clone());
+            if *rtype == Rtype::SOA {
+                target.push(record);
+            } else {
+                records.push(record);
+            }
+        }
+    }
+    records.sort_by(CanonicalOrd::canonical_cmp);
+    target.append(&mut records);
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use std::string::{String, ToString};
+
+    use super::*;
+    use crate::base::{MessageBuilder, Name};
+    use crate::net::xfr::protocol::XfrResponseInterpreter;
+    use crate::zonefile::inplace;
+    use crate::zonetree::update::ZoneUpdater;
+
+    const OLD: &str = "\
+        example. 3600 IN SOA ns1 admin 1 1800 900 604800 86400\n\
+        example. 3600 IN NS ns1\n\
+        ns1.example. 3600 IN A 192.0.2.1\n\
+        www.example. 3600 IN A 192.0.2.10\n\
+        www.example. 3600 IN A 192.0.2.11\n\
+        old.example. 3600 IN TXT \"gone\"\n\
+        mail.example. 3600 IN MX 10 ns1\n\
+    ";
+
+    const NEW: &str = "\
+        example. 3600 IN SOA ns1 admin 2 1800 900 604800 86400\n\
+        example. 3600 IN NS ns1\n\
+        ns1.example. 3600 IN A 192.0.2.1\n\
+        www.example. 3600 IN A 192.0.2.10\n\
+        www.example. 3600 IN A 192.0.2.12\n\
+        new.example. 3600 IN TXT \"here\"\n\
+        mail.example. 300 IN MX 10 ns1\n\
+    ";
+
+    fn load(text: &str) -> Zone {
+        let mut zonefile = inplace::Zonefile::new();
+        zonefile.extend_from_slice(text.as_bytes());
+        zonefile.set_origin(Name::bytes_from_str("example.").unwrap());
+        Zone::try_from(zonefile).unwrap()
+    }
+
+    fn display(records: &[StoredRecord]) -> Vec<String> {
+        records.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn diff_and_ixfr_records() {
+        let old = load(OLD);
+        let new = load(NEW);
+        let diff = diff(&old, &new).unwrap();
+        assert_eq!(diff.start_serial, 1.into());
+        assert_eq!(diff.end_serial, 2.into());
+        assert_eq!(
+            display(&ixfr_records(&diff, Class::IN)),
+            [
+                "example. 3600 IN SOA ns1.example. admin.example. \
+                 1 1800 900 604800 86400",
+                "mail.example. 3600 IN MX 10 ns1.example.",
+                "old.example. 3600 IN TXT \"gone\"",
+                "www.example. 3600 IN A 192.0.2.11",
+                "example. 3600 IN SOA ns1.example. admin.example. \
+                 2 1800 900 604800 86400",
+                "mail.example. 300 IN MX 10 ns1.example.",
+                "new.example. 3600 IN TXT \"here\"",
+                "www.example. 3600 IN A 192.0.2.12",
+            ]
+        );
+
+        // Without changes, there is no SOA to start the diff with.
+        assert_eq!(
+            super::diff(&new, &new).unwrap_err(),
+            ZoneDiffError::MissingStartSoa
+        );
+
+        // The serial needs to increase.
+        assert_eq!(
+            super::diff(&new, &old).unwrap_err(),
+            ZoneDiffError::InvalidSerialRange
+        );
+    }
+
+    #[tokio::test]
+    async fn apply_ixfr() {
+        let old = load(OLD);
+        let new = load(NEW);
+        let records = ixfr_records(&diff(&old, &new).unwrap(), Class::IN);
+
+        // Wrap the records into an IXFR response.
+        let apex = Name::bytes_from_str("example.").unwrap();
+        let mut answer = MessageBuilder::new_bytes().question();
+        answer.header_mut().set_qr(true);
+        answer.push((&apex, Rtype::IXFR)).unwrap();
+        let mut answer = answer.answer();
+        let soa = &records[4];
+        answer.push(soa).unwrap();
+        for record in &records {
+            answer.push(record).unwrap();
+        }
+        answer.push(soa).unwrap();
+
+        let mut interpreter = XfrResponseInterpreter::new();
+        let mut updater = ZoneUpdater::new(old.clone()).await.unwrap();
+        for update in interpreter
+            .interpret_response(answer.into_message())
+            .unwrap()
+        {
+            updater.apply(update.unwrap()).await.unwrap();
+        }
+        assert!(updater.is_finished());
+
+        assert_eq!(
+            display(&old.canonical_records()),
+            display(&new.canonical_records())
+        );
+    }
+}
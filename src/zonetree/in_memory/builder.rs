@@ -109,6 +109,11 @@ impl ZoneBuilder {
     /// - [Ds] records
     /// - Glue records _(see [RFC 9499 section 7.2.30])_
     ///
+    /// The glue records are only used for the additional section of
+    /// referrals. They need to be inserted via [`insert_rrset`] as well in
+    /// order to be part of the zone’s content, e.g., when walking the zone.
+    ///
+    /// [`insert_rrset`]: Self::insert_rrset
     /// [Ns]: crate::rdata::rfc1035::Ns
     /// [Ds]: crate::rdata::dnssec::Ds
     /// [RFC 9499 section 7.2.13]:
@@ -8,6 +8,7 @@ use bytes::Bytes;
 use crate::base::iana::{Rcode, Rtype};
 use crate::base::name::Label;
 use crate::base::Name;
+use crate::rdata::{Cname, ZoneRecordData};
 use crate::zonetree::answer::{Answer, AnswerAdditional, AnswerAuthority};
 use crate::zonetree::error::OutOfZone;
 use crate::zonetree::types::ZoneCut;
@@ -91,10 +92,17 @@ impl ReadZone {
                     if let Some(ds) = &cut.ds {
                         walk.op(ds, true);
                     }
-                    for glue_rec in &cut.glue {
-                        walk.op_glue_rec(glue_rec);
-                    }
-                    NodeAnswer::no_data()
+
+                    // The names below the cut are occluded but still part
+                    // of the zone. This includes the glue, so there is no
+                    // need to walk the glue of the cut separately.
+                    self.query_children(
+                        node.children(),
+                        label,
+                        qname,
+                        qtype,
+                        walk,
+                    )
                 } else {
                     // There is nothing more in this zone, only a cut here.
                     // Respond with NODATA and an authority section referring the
@@ -126,13 +134,27 @@ impl ReadZone {
                     walk,
                 )
             }
-            None => self.query_children(
-                node.children(),
-                label,
-                qname,
-                qtype,
-                walk,
-            ),
+            None => {
+                // A DNAME redirects all names below the node. The walk
+                // continues since the names below it are still part of
+                // the zone.
+                if !walk.enabled() {
+                    if let Some(dname) = node
+                        .rrsets()
+                        .get(Rtype::DNAME, self.version)
+                        .and_then(|rrset| rrset.first())
+                    {
+                        return NodeAnswer::dname(dname, qname.count() + 1);
+                    }
+                }
+                self.query_children(
+                    node.children(),
+                    label,
+                    qname,
+                    qtype,
+                    walk,
+                )
+            }
         })
     }
 
@@ -283,15 +305,28 @@ impl ReadableZone for ReadZone {
         qname: Name<Bytes>,
         qtype: Rtype,
     ) -> Result<Answer, OutOfZone> {
-        let mut qname = self.apex.prepare_name(&qname)?;
-
-        let answer = if let Some(label) = qname.next() {
-            self.query_below_apex(label, qname, qtype, WalkState::DISABLED)
+        let mut labels = self.apex.prepare_name(&qname)?;
+
+        let answer = if let Some(label) = labels.next() {
+            match self
+                .apex
+                .rrsets()
+                .get(Rtype::DNAME, self.version)
+                .and_then(|rrset| rrset.first())
+            {
+                Some(dname) => NodeAnswer::dname(dname, labels.count() + 1),
+                None => self.query_below_apex(
+                    label,
+                    labels,
+                    qtype,
+                    WalkState::DISABLED,
+                ),
+            }
         } else {
             self.query_rrsets(self.apex.rrsets(), qtype, WalkState::DISABLED)
         };
 
-        Ok(answer.into_answer(self))
+        Ok(answer.into_answer(self, &qname))
     }
 
     fn walk(&self, op: WalkOp) {
@@ -330,6 +365,12 @@ struct NodeAnswer {
 
     /// Should the answer be flagged as authoritative?
     authoritative: bool,
+
+    /// A DNAME RR redirecting the query and the number of labels the
+    /// query name is below its owner.
+    ///
+    /// The apex needs to synthesize the answer with the full query name.
+    dname: Option<(SharedRr, usize)>,
 }
 
 impl NodeAnswer {
@@ -340,6 +381,7 @@ impl NodeAnswer {
             answer,
             add_soa: false,
             authoritative: true,
+            dname: None,
         }
     }
 
@@ -348,6 +390,7 @@ impl NodeAnswer {
             answer: Answer::new(Rcode::NOERROR),
             add_soa: true,
             authoritative: true,
+            dname: None,
         }
     }
 
@@ -358,6 +401,7 @@ impl NodeAnswer {
             answer,
             add_soa: false,
             authoritative: true,
+            dname: None,
         }
     }
 
@@ -366,6 +410,16 @@ impl NodeAnswer {
             answer: Answer::new(Rcode::NXDOMAIN),
             add_soa: true,
             authoritative: true,
+            dname: None,
+        }
+    }
+
+    fn dname(dname: SharedRr, below: usize) -> Self {
+        NodeAnswer {
+            answer: Answer::new(Rcode::NOERROR),
+            add_soa: false,
+            authoritative: true,
+            dname: Some((dname, below)),
         }
     }
 
@@ -381,10 +435,14 @@ impl NodeAnswer {
             answer,
             add_soa: false,
             authoritative: false,
+            dname: None,
         }
     }
 
-    fn into_answer(mut self, zone: &ReadZone) -> Answer {
+    fn into_answer(mut self, zone: &ReadZone, qname: &Name<Bytes>) -> Answer {
+        if let Some((dname, below)) = self.dname.take() {
+            self.answer = Self::synthesize(dname, below, qname);
+        }
         if self.add_soa {
             if let Some(soa) = zone.apex.get_soa(zone.version) {
                 self.answer.set_authority(AnswerAuthority::new(
@@ -398,4 +456,43 @@ impl NodeAnswer {
         self.answer.set_authoritative(self.authoritative);
         self.answer
     }
+
+    /// Synthesizes the answer for a query redirected by a DNAME RR.
+    ///
+    /// See [RFC 6672, section 3.2].
+    ///
+    /// If the zone content doesn’t allow synthesis, i.e., the DNAME owner
+    /// isn’t a suffix of the query name or the DNAME RRset contains other
+    /// record data, answers with SERVFAIL.
+    ///
+    /// [RFC 6672, section 3.2]: https://tools.ietf.org/html/rfc6672#section-3.2
+    fn synthesize(
+        dname: SharedRr,
+        below: usize,
+        qname: &Name<Bytes>,
+    ) -> Answer {
+        let Some(owner) = qname.iter_suffixes().nth(below) else {
+            return Answer::new(Rcode::SERVFAIL);
+        };
+        let target = match dname.data() {
+            ZoneRecordData::Dname(data) => data.synthesize(&owner, qname),
+            _ => return Answer::new(Rcode::SERVFAIL),
+        };
+        match target {
+            Ok(target) => {
+                let cname = SharedRr::new(
+                    dname.ttl(),
+                    ZoneRecordData::Cname(Cname::new(target)),
+                );
+                let mut answer = Answer::new(Rcode::NOERROR);
+                answer.add_dname(owner, dname, Some(cname));
+                answer
+            }
+            Err(_) => {
+                let mut answer = Answer::new(Rcode::YXDOMAIN);
+                answer.add_dname(owner, dname, None);
+                answer
+            }
+        }
+    }
 }
@@ -0,0 +1,813 @@
+//! Recording incremental changes to a zone in a journal.
+//!
+//! A secondary name server that keeps its zones only in memory would have
+//! to transfer each zone in full again after a restart. To avoid this, it
+//! can save a [snapshot][super::snapshot] of a zone now and then and record
+//! every incremental change it applies to the zone since, be it from an
+//! IXFR response or a dynamic update, in a [`Journal`]. After a restart,
+//! the journal is [replayed][Journal::replay] on top of the snapshot to
+//! arrive at the latest version of the zone.
+//!
+//! The journal is an append-only file. Each change is
+//! [appended][Journal::append] as an [`InMemoryZoneDiff`], which is what
+//! [`ZoneUpdater`] returns when applying an update, and is written to disk
+//! before `append` returns. So that the journal doesn’t grow without
+//! bounds, entries that are already part of a newer snapshot can be
+//! dropped via [`Journal::compact`] and a maximum size can be set beyond
+//! which the oldest entries are dropped automatically.
+//!
+//! The file starts with the four octets `DZSJ` and a version octet,
+//! currently 1. It is followed by the entries, one for each diff. Each
+//! entry consists of its length as a 32 bit integer in network byte order
+//! followed by the number of records, again as a 32 bit integer, and the
+//! records themselves in uncompressed wire format in the order returned by
+//! [`ixfr_records`]. If the last entry is incomplete, for instance because
+//! the server crashed while writing it, it is dropped when the journal is
+//! opened.
+//!
+//! [`ZoneUpdater`]: super::update::ZoneUpdater
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::vec::Vec;
+
+use bytes::Bytes;
+use octseq::parse::Parser;
+
+use crate::base::iana::{Class, Rtype};
+use crate::base::{Record, Serial};
+use crate::net::xfr::protocol::ParsedRecord;
+use crate::rdata::ZoneRecordData;
+
+use super::diff::ixfr_records;
+use super::types::ZoneUpdate;
+use super::update::{self, ZoneUpdater};
+use super::{InMemoryZoneDiff, Zone};
+
+/// The octets every journal starts with.
+const MAGIC: &[u8; 4] = b"DZSJ";
+
+/// The version of the format written by [`Journal`].
+const VERSION: u8 = 1;
+
+//------------ Journal -------------------------------------------------------
+
+/// A journal of the incremental changes to a zone.
+///
+/// The entries of a journal are consecutive, i.e., each entry starts with
+/// the serial the previous entry ends with.
+#[derive(Debug)]
+pub struct Journal {
+    /// The path of the journal file.
+    path: PathBuf,
+
+    /// The journal file opened for appending.
+    file: File,
+
+    /// The entries of the journal.
+    entries: Vec<JournalEntry>,
+
+    /// The current size of the journal file.
+    size: u64,
+
+    /// The size of the journal file beyond which entries are dropped.
+    max_size: Option<u64>,
+}
+
+impl Journal {
+    /// Opens the journal at the given path.
+    ///
+    /// If the file doesn’t exist, an empty journal is created. Otherwise
+    /// the entries of the journal are loaded. An incomplete or unreadable
+    /// last entry is removed from the file. A partially written header is
+    /// rewritten.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, JournalError> {
+        let path = path.into();
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)?;
+        let data = Bytes::from(fs::read(&path)?);
+
+        // An empty file or one with a partial header is treated as a new
+        // journal.
+        if data.len() < header().len() && header().starts_with(&data) {
+            if !data.is_empty() {
+                file.set_len(0)?;
+            }
+            file.write_all(&header())?;
+            file.sync_data()?;
+            return Ok(Journal {
+                path,
+                file,
+                entries: Vec::new(),
+                size: header().len() as u64,
+                max_size: None,
+            });
+        }
+
+        if data.get(..MAGIC.len()) != Some(MAGIC.as_slice()) {
+            return Err(JournalError::NotAJournal);
+        }
+        match data.get(MAGIC.len()) {
+            Some(&VERSION) => {}
+            Some(&version) => {
+                return Err(JournalError::UnsupportedVersion(version))
+            }
+            None => return Err(JournalError::Malformed),
+        }
+
+        let mut entries = Vec::<JournalEntry>::new();
+        let mut pos = header().len();
+        while let Some(len) = data.get(pos..pos + 4) {
+            let len = u32::from_be_bytes(len.try_into().unwrap());
+            let start = pos + 4;
+            let end = match usize::try_from(len) {
+                Ok(len) if len <= data.len() - start => start + len,
+                _ => break,
+            };
+            let entry = match JournalEntry::parse(data.slice(start..end)) {
+                Ok(entry) => entry,
+                // A broken last entry is treated like a partially written
+                // one.
+                Err(_) if end == data.len() => break,
+                Err(err) => return Err(err),
+            };
+            if let Some(last) = entries.last() {
+                if last.end_serial() != entry.start_serial() {
+                    return Err(JournalError::Malformed);
+                }
+            }
+            entries.push(entry);
+            pos = end;
+        }
+
+        // Drop whatever is left of a partially written or broken entry.
+        let size = pos as u64;
+        if size != data.len() as u64 {
+            file.set_len(size)?;
+            file.sync_data()?;
+        }
+
+        Ok(Journal {
+            path,
+            file,
+            entries,
+            size,
+            max_size: None,
+        })
+    }
+
+    /// Returns the path of the journal file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the entries of the journal, oldest first.
+    pub fn entries(&self) -> &[JournalEntry] {
+        &self.entries
+    }
+
+    /// Returns whether the journal is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the current size of the journal file.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the serial of the oldest version of the zone in the journal.
+    pub fn first_serial(&self) -> Option<Serial> {
+        self.entries.first().map(JournalEntry::start_serial)
+    }
+
+    /// Returns the serial of the newest version of the zone in the journal.
+    pub fn last_serial(&self) -> Option<Serial> {
+        self.entries.last().map(JournalEntry::end_serial)
+    }
+
+    /// Returns the maximum size of the journal file.
+    pub fn max_size(&self) -> Option<u64> {
+        self.max_size
+    }
+
+    /// Sets the maximum size of the journal file.
+    ///
+    /// If appending an entry makes the file larger than this size, the
+    /// oldest entries are dropped until it fits again. The newest entry is
+    /// always kept, even if it alone is larger. By default, there is no
+    /// maximum size.
+    pub fn set_max_size(&mut self, max_size: Option<u64>) {
+        self.max_size = max_size;
+    }
+
+    /// Appends a diff to the journal.
+    ///
+    /// The diff has to start with the serial the last entry ends with
+    /// unless the journal is empty. The entry has been written to disk
+    /// when the method returns successfully.
+    pub fn append(
+        &mut self,
+        diff: &InMemoryZoneDiff,
+        class: Class,
+    ) -> Result<(), JournalError> {
+        if let Some(last) = self.last_serial() {
+            if last != diff.start_serial {
+                return Err(JournalError::SerialMismatch);
+            }
+        }
+
+        let records = ixfr_records(diff, class);
+        let count = u32::try_from(records.len())
+            .map_err(|_| JournalError::TooLarge)?;
+        let mut data = Vec::new();
+        data.extend_from_slice(&count.to_be_bytes());
+        for record in &records {
+            record.compose(&mut data).expect("infallible");
+        }
+        let len =
+            u32::try_from(data.len()).map_err(|_| JournalError::TooLarge)?;
+        let entry = JournalEntry::parse(data.into())?;
+
+        let mut buf = Vec::with_capacity(entry.data.len() + 4);
+        buf.extend_from_slice(&len.to_be_bytes());
+        buf.extend_from_slice(&entry.data);
+        if let Err(err) = self
+            .file
+            .write_all(&buf)
+            .and_then(|_| self.file.sync_data())
+        {
+            // Don’t leave part of the entry behind for the next one to be
+            // appended to.
+            let _ = self.file.set_len(self.size);
+            return Err(err.into());
+        }
+        self.size += buf.len() as u64;
+        self.entries.push(entry);
+
+        if let Some(max_size) = self.max_size {
+            if self.size > max_size {
+                let mut size = self.size;
+                let mut drop = 0;
+                while size > max_size && drop + 1 < self.entries.len() {
+                    size -= self.entries[drop].file_len();
+                    drop += 1;
+                }
+                if drop > 0 {
+                    self.rewrite(drop)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Drops the entries leading up to the version with the given serial.
+    ///
+    /// This should be called after a snapshot of the zone at version
+    /// `base` has been saved as the journal only needs to contain the
+    /// changes since. Returns an error if `base` isn’t one of the versions
+    /// of the zone contained in the journal.
+    pub fn compact(&mut self, base: Serial) -> Result<(), JournalError> {
+        let drop = match self
+            .entries
+            .iter()
+            .position(|entry| entry.start_serial() == base)
+        {
+            Some(drop) => drop,
+            None if self.last_serial() == Some(base) => self.entries.len(),
+            None => return Err(JournalError::UnknownSerial(base)),
+        };
+        if drop > 0 {
+            self.rewrite(drop)?;
+        }
+        Ok(())
+    }
+
+    /// Replays the journal on top of the given zone.
+    ///
+    /// All entries starting with the one that starts with the current
+    /// serial of the zone are applied. Returns the number of entries
+    /// applied, which is zero if the zone is already at the newest version
+    /// or the journal is empty. Returns an error if the journal doesn’t
+    /// contain the changes starting at the zone’s serial.
+    pub async fn replay(&self, zone: &Zone) -> Result<usize, JournalError> {
+        let serial = zone_serial(zone).ok_or(JournalError::MissingSoa)?;
+        let start = match self
+            .entries
+            .iter()
+            .position(|entry| entry.start_serial() == serial)
+        {
+            Some(start) => start,
+            None => {
+                return match self.last_serial() {
+                    Some(last) if last != serial => {
+                        Err(JournalError::UnknownSerial(serial))
+                    }
+                    _ => Ok(0),
+                };
+            }
+        };
+
+        let mut updater = ZoneUpdater::new(zone.clone()).await?;
+        for update in
+            self.entries[start..].iter().flat_map(JournalEntry::updates)
+        {
+            updater.apply(update).await?;
+        }
+        let last = &self.entries[self.entries.len() - 1];
+        updater
+            .apply(ZoneUpdate::Finished(last.new_soa().clone()))
+            .await?;
+        Ok(self.entries.len() - start)
+    }
+
+    /// Rewrites the journal file without the first `drop` entries.
+    ///
+    /// The new file is written next to the old one and then renamed, so
+    /// the journal stays intact if this fails half way. The directory is
+    /// synced after the rename so that the new file is durable.
+    fn rewrite(&mut self, drop: usize) -> Result<(), JournalError> {
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+
+        let mut buf = header();
+        for entry in &self.entries[drop..] {
+            buf.extend_from_slice(&(entry.data.len() as u32).to_be_bytes());
+            buf.extend_from_slice(&entry.data);
+        }
+        let mut tmp = File::create(&tmp_path)?;
+        if let Err(err) = tmp.write_all(&buf).and_then(|_| tmp.sync_all()) {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(err.into());
+        }
+        fs::rename(&tmp_path, &self.path)?;
+        sync_parent(&self.path)?;
+
+        self.file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .open(&self.path)?;
+        let _ = self.entries.drain(..drop);
+        self.size = buf.len() as u64;
+        Ok(())
+    }
+}
+
+/// Syncs the directory containing the given path.
+///
+/// This is necessary for a rename to be durable. Directories can only be
+/// synced on Unix-like systems, so this does nothing elsewhere.
+fn sync_parent(path: &Path) -> Result<(), io::Error> {
+    #[cfg(unix)]
+    {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        File::open(dir)?.sync_all()?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+//------------ JournalEntry --------------------------------------------------
+
+/// A single entry of a journal.
+///
+/// An entry contains the changes between two consecutive versions of a
+/// zone in the form of the records used by IXFR.
+#[derive(Clone, Debug)]
+pub struct JournalEntry {
+    /// The entry as stored in the file, i.e., the record count and records.
+    data: Bytes,
+
+    /// The records of the entry in IXFR order.
+    records: Vec<ParsedRecord>,
+
+    /// The index of the SOA record of the new version in `records`.
+    split: usize,
+}
+
+impl JournalEntry {
+    /// Parses an entry from its stored data.
+    fn parse(data: Bytes) -> Result<Self, JournalError> {
+        let mut parser = Parser::from_ref(&data);
+        let count = parser.parse_u32_be()?;
+        let mut records = Vec::new();
+        for _ in 0..count {
+            records.push(
+                Record::parse(&mut parser)?.ok_or(JournalError::Malformed)?,
+            );
+        }
+        if parser.remaining() != 0 {
+            return Err(JournalError::Malformed);
+        }
+
+        // The entry starts with the old SOA record and the new one follows
+        // the removed records.
+        if records.first().and_then(soa_serial).is_none() {
+            return Err(JournalError::Malformed);
+        }
+        let split = records
+            .iter()
+            .skip(1)
+            .position(|record| record.rtype() == Rtype::SOA)
+            .ok_or(JournalError::Malformed)?
+            + 1;
+        if soa_serial(&records[split]).is_none() {
+            return Err(JournalError::Malformed);
+        }
+        Ok(JournalEntry {
+            data,
+            records,
+            split,
+        })
+    }
+
+    /// Returns the serial of the version of the zone the entry starts with.
+    pub fn start_serial(&self) -> Serial {
+        soa_serial(self.old_soa()).expect("checked when parsing")
+    }
+
+    /// Returns the serial of the version of the zone the entry ends with.
+    pub fn end_serial(&self) -> Serial {
+        soa_serial(self.new_soa()).expect("checked when parsing")
+    }
+
+    /// Returns the SOA record of the version the entry starts with.
+    pub fn old_soa(&self) -> &ParsedRecord {
+        &self.records[0]
+    }
+
+    /// Returns the SOA record of the version the entry ends with.
+    pub fn new_soa(&self) -> &ParsedRecord {
+        &self.records[self.split]
+    }
+
+    /// Returns the records removed from the zone.
+    pub fn removed(&self) -> &[ParsedRecord] {
+        &self.records[1..self.split]
+    }
+
+    /// Returns the records added to the zone.
+    pub fn added(&self) -> &[ParsedRecord] {
+        &self.records[self.split + 1..]
+    }
+
+    /// Returns all records in the order used by IXFR.
+    ///
+    /// See [`ixfr_records`] for details.
+    pub fn records(&self) -> &[ParsedRecord] {
+        &self.records
+    }
+
+    /// Returns the zone updates for applying the entry.
+    ///
+    /// The updates don’t include the final [`ZoneUpdate::Finished`].
+    fn updates(&self) -> impl Iterator<Item = ZoneUpdate<ParsedRecord>> + '_ {
+        [ZoneUpdate::BeginBatchDelete(self.old_soa().clone())]
+            .into_iter()
+            .chain(
+                self.removed().iter().cloned().map(ZoneUpdate::DeleteRecord),
+            )
+            .chain([ZoneUpdate::BeginBatchAdd(self.new_soa().clone())])
+            .chain(self.added().iter().cloned().map(ZoneUpdate::AddRecord))
+    }
+
+    /// Returns the size of the entry in the journal file.
+    fn file_len(&self) -> u64 {
+        self.data.len() as u64 + 4
+    }
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Returns the header of a journal file.
+fn header() -> Vec<u8> {
+    let mut res = MAGIC.to_vec();
+    res.push(VERSION);
+    res
+}
+
+/// Returns the serial of a SOA record.
+fn soa_serial(record: &ParsedRecord) -> Option<Serial> {
+    match record.data() {
+        ZoneRecordData::Soa(soa) => Some(soa.serial()),
+        _ => None,
+    }
+}
+
+/// Returns the current serial of a zone.
+fn zone_serial(zone: &Zone) -> Option<Serial> {
+    let answer = zone
+        .read()
+        .query(zone.apex_name().clone(), Rtype::SOA)
+        .ok()?;
+    match answer.content().first()?.1 {
+        ZoneRecordData::Soa(soa) => Some(soa.serial()),
+        _ => None,
+    }
+}
+
+//------------ JournalError --------------------------------------------------
+
+/// An error happened while using a journal.
+#[derive(Debug)]
+pub enum JournalError {
+    /// Accessing the journal file failed.
+    Io(io::Error),
+
+    /// The file doesn’t start like a journal.
+    NotAJournal,
+
+    /// The journal uses an unsupported version of the format.
+    UnsupportedVersion(u8),
+
+    /// The journal is malformed.
+    Malformed,
+
+    /// A diff doesn’t start with the serial the journal ends with.
+    SerialMismatch,
+
+    /// The journal doesn’t contain the given version of the zone.
+    UnknownSerial(Serial),
+
+    /// The zone has no SOA record.
+    MissingSoa,
+
+    /// A diff is too large for the journal.
+    TooLarge,
+
+    /// Applying an entry to the zone failed.
+    Update(update::Error),
+}
+
+//--- From
+
+impl From<io::Error> for JournalError {
+    fn from(err: io::Error) -> Self {
+        JournalError::Io(err)
+    }
+}
+
+impl From<update::Error> for JournalError {
+    fn from(err: update::Error) -> Self {
+        JournalError::Update(err)
+    }
+}
+
+impl From<octseq::parse::ShortInput> for JournalError {
+    fn from(_: octseq::parse::ShortInput) -> Self {
+        JournalError::Malformed
+    }
+}
+
+impl From<crate::base::wire::ParseError> for JournalError {
+    fn from(_: crate::base::wire::ParseError) -> Self {
+        JournalError::Malformed
+    }
+}
+
+//--- Display and Error
+
+impl fmt::Display for JournalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JournalError::Io(err) => write!(f, "journal file: {err}"),
+            JournalError::NotAJournal => f.write_str("not a zone journal"),
+            JournalError::UnsupportedVersion(version) => {
+                write!(f, "unsupported zone journal version {version}")
+            }
+            JournalError::Malformed => f.write_str("malformed zone journal"),
+            JournalError::SerialMismatch => {
+                f.write_str("diff doesn’t continue the journal")
+            }
+            JournalError::UnknownSerial(serial) => {
+                write!(f, "serial {serial} not contained in journal")
+            }
+            JournalError::MissingSoa => f.write_str("zone without SOA"),
+            JournalError::TooLarge => f.write_str("diff too large"),
+            JournalError::Update(err) => {
+                write!(f, "applying journal failed: {err}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for JournalError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use std::string::{String, ToString};
+
+    use super::*;
+    use crate::base::Name;
+    use crate::zonefile::inplace;
+    use crate::zonetree::diff::diff;
+    use crate::zonetree::StoredRecord;
+
+    const V1: &str = "\
+        example. 3600 IN SOA ns1 admin 1 1800 900 604800 86400\n\
+        example. 3600 IN NS ns1\n\
+        ns1.example. 3600 IN A 192.0.2.1\n\
+        www.example. 3600 IN A 192.0.2.10\n\
+        old.example. 3600 IN TXT \"gone\"\n\
+    ";
+
+    const V2: &str = "\
+        example. 3600 IN SOA ns1 admin 2 1800 900 604800 86400\n\
+        example. 3600 IN NS ns1\n\
+        ns1.example. 3600 IN A 192.0.2.1\n\
+        www.example. 3600 IN A 192.0.2.11\n\
+        new.example. 3600 IN TXT \"here\"\n\
+    ";
+
+    const V3: &str = "\
+        example. 3600 IN SOA ns1 admin 3 1800 900 604800 86400\n\
+        example. 3600 IN NS ns1\n\
+        ns1.example. 3600 IN A 192.0.2.1\n\
+        www.example. 300 IN A 192.0.2.11\n\
+        new.example. 3600 IN TXT \"here\"\n\
+        mail.example. 3600 IN MX 10 ns1\n\
+    ";
+
+    fn load(text: &str) -> Zone {
+        let mut zonefile = inplace::Zonefile::new();
+        zonefile.extend_from_slice(text.as_bytes());
+        zonefile.set_origin(Name::bytes_from_str("example.").unwrap());
+        Zone::try_from(zonefile).unwrap()
+    }
+
+    fn display(records: &[StoredRecord]) -> Vec<String> {
+        records.iter().map(ToString::to_string).collect()
+    }
+
+    fn journal_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "domain-journal-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn diffs() -> [InMemoryZoneDiff; 2] {
+        let (v1, v2, v3) = (load(V1), load(V2), load(V3));
+        [diff(&v1, &v2).unwrap(), diff(&v2, &v3).unwrap()]
+    }
+
+    #[tokio::test]
+    async fn append_and_replay() {
+        let path = journal_path("replay");
+        let mut journal = Journal::open(&path).unwrap();
+        assert!(journal.is_empty());
+        for diff in diffs() {
+            journal.append(&diff, Class::IN).unwrap();
+        }
+        assert_eq!(journal.first_serial(), Some(1.into()));
+        assert_eq!(journal.last_serial(), Some(3.into()));
+        let [_, second] = diffs();
+        assert!(matches!(
+            journal.append(&second, Class::IN),
+            Err(JournalError::SerialMismatch)
+        ));
+        drop(journal);
+
+        // Reopening loads the entries again.
+        let journal = Journal::open(&path).unwrap();
+        assert_eq!(journal.entries().len(), 2);
+        let entry = &journal.entries()[0];
+        assert_eq!(entry.start_serial(), 1.into());
+        assert_eq!(entry.end_serial(), 2.into());
+        assert_eq!(entry.removed().len(), 2);
+        assert_eq!(entry.added().len(), 2);
+
+        let zone = load(V1);
+        assert_eq!(journal.replay(&zone).await.unwrap(), 2);
+        assert_eq!(
+            display(&zone.canonical_records()),
+            display(&load(V3).canonical_records())
+        );
+        assert_eq!(journal.replay(&zone).await.unwrap(), 0);
+
+        let zone = load(V2);
+        assert_eq!(journal.replay(&zone).await.unwrap(), 1);
+        assert_eq!(
+            display(&zone.canonical_records()),
+            display(&load(V3).canonical_records())
+        );
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn incomplete_entry() {
+        let path = journal_path("incomplete");
+        let mut journal = Journal::open(&path).unwrap();
+        let [first, second] = diffs();
+        journal.append(&first, Class::IN).unwrap();
+        let size = journal.size();
+        journal.append(&second, Class::IN).unwrap();
+        drop(journal);
+
+        let len = fs::metadata(&path).unwrap().len();
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_len(len - 3)
+            .unwrap();
+        let journal = Journal::open(&path).unwrap();
+        assert_eq!(journal.last_serial(), Some(2.into()));
+        assert_eq!(journal.size(), size);
+        assert_eq!(fs::metadata(&path).unwrap().len(), size);
+        drop(journal);
+
+        // A complete last entry that cannot be parsed is dropped, too.
+        File::options()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"\0\0\0\x02xx")
+            .unwrap();
+        let mut journal = Journal::open(&path).unwrap();
+        assert_eq!(journal.last_serial(), Some(2.into()));
+        assert_eq!(fs::metadata(&path).unwrap().len(), size);
+        journal.append(&second, Class::IN).unwrap();
+        drop(journal);
+        assert_eq!(
+            Journal::open(&path).unwrap().last_serial(),
+            Some(3.into())
+        );
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn incomplete_header() {
+        let path = journal_path("header");
+        fs::write(&path, &MAGIC[..2]).unwrap();
+        let mut journal = Journal::open(&path).unwrap();
+        assert!(journal.is_empty());
+        assert_eq!(fs::read(&path).unwrap(), header());
+        let [first, _] = diffs();
+        journal.append(&first, Class::IN).unwrap();
+        drop(journal);
+        assert_eq!(
+            Journal::open(&path).unwrap().last_serial(),
+            Some(2.into())
+        );
+
+        // Something else is still rejected.
+        fs::write(&path, b"DZ!").unwrap();
+        assert!(matches!(
+            Journal::open(&path),
+            Err(JournalError::NotAJournal)
+        ));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn compact_and_max_size() {
+        let path = journal_path("compact");
+        let mut journal = Journal::open(&path).unwrap();
+        for diff in diffs() {
+            journal.append(&diff, Class::IN).unwrap();
+        }
+
+        assert!(matches!(
+            journal.compact(7.into()),
+            Err(JournalError::UnknownSerial(_))
+        ));
+        journal.compact(2.into()).unwrap();
+        assert_eq!(journal.first_serial(), Some(2.into()));
+        assert!(matches!(
+            journal.replay(&load(V1)).await,
+            Err(JournalError::UnknownSerial(_))
+        ));
+        drop(journal);
+        let mut journal = Journal::open(&path).unwrap();
+        assert_eq!(journal.first_serial(), Some(2.into()));
+        journal.compact(3.into()).unwrap();
+        assert!(journal.is_empty());
+
+        // Only the newest entry fits.
+        journal.set_max_size(Some(1));
+        for diff in diffs() {
+            journal.append(&diff, Class::IN).unwrap();
+        }
+        assert_eq!(journal.entries().len(), 1);
+        assert_eq!(journal.first_serial(), Some(2.into()));
+        assert_eq!(fs::metadata(&path).unwrap().len(), journal.size());
+        drop(journal);
+        assert_eq!(Journal::open(&path).unwrap().entries().len(), 1);
+        fs::remove_file(&path).unwrap();
+    }
+}
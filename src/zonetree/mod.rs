@@ -102,15 +102,19 @@
 //! [`ZoneUpdater`]: update::ZoneUpdater
 
 mod answer;
+pub mod diff;
 pub mod error;
 mod in_memory;
+pub mod journal;
 pub mod parsed;
+pub mod snapshot;
 mod traits;
 mod tree;
 pub mod types;
 pub mod update;
 mod walk;
 mod zone;
+pub mod zonemd;
 
 pub use self::answer::{Answer, AnswerAuthority, AnswerContent};
 pub use self::in_memory::ZoneBuilder;
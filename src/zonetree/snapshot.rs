@@ -0,0 +1,371 @@
+//! Saving and loading zones in a binary snapshot format.
+//!
+//! Parsing a large zone from its presentation format takes a considerable
+//! amount of time. This module provides a compact binary format that a
+//! [`Zone`] can be [saved][save] to and [loaded][load] from much faster,
+//! for instance to avoid re-parsing the zone file every time a server
+//! starts.
+//!
+//! A snapshot stores each distinct owner name once in a name table and
+//! the record data of each RRset in wire format, so loading doesn’t need
+//! to deal with the presentation format at all.
+//!
+//! The format starts with the four octets `DZSS` and a version octet,
+//! currently 1, followed by the class of the zone. Next is the name table
+//! consisting of the number of names as a 32 bit integer and the names in
+//! uncompressed wire format. The first name is the apex of the zone.
+//! Finally, there are the RRsets, again preceded by their number as a 32
+//! bit integer. Each RRset consists of the index of its owner name in the
+//! name table as a 32 bit integer, its record type, its TTL, and the
+//! number of records followed by the record data of each record preceded
+//! by its length as a 16 bit integer. All integers are in network byte
+//! order.
+//!
+//! The format isn’t meant for exchanging zones between different
+//! implementations. Use the zone file format or zone transfers for that.
+//!
+//! # Example
+//!
+//! ```
+//! use domain::base::Name;
+//! use domain::zonefile::inplace;
+//! use domain::zonetree::{snapshot, Zone};
+//!
+//! let mut zonefile = inplace::Zonefile::new();
+//! zonefile.extend_from_slice(
+//!     b"example. 3600 IN SOA ns admin 1 1800 900 604800 86400\n\
+//!       example. 3600 IN NS ns\n\
+//!       ns.example. 3600 IN A 192.0.2.1\n",
+//! );
+//! zonefile.set_origin(Name::bytes_from_str("example.").unwrap());
+//! let zone = Zone::try_from(zonefile).unwrap();
+//!
+//! let mut data = Vec::new();
+//! snapshot::save(&zone, &mut data).unwrap();
+//! let loaded = snapshot::load(data.into()).unwrap();
+//! assert_eq!(loaded.canonical_records(), zone.canonical_records());
+//! ```
+use std::collections::HashMap;
+use std::vec::Vec;
+use std::{fmt, io};
+
+use bytes::Bytes;
+use octseq::parse::Parser;
+
+use crate::base::iana::{Class, Rtype};
+use crate::base::name::{FlattenInto, ParsedName};
+use crate::base::rdata::{ComposeRecordData, ParseRecordData};
+use crate::base::{Record, ToName, Ttl};
+use crate::rdata::ZoneRecordData;
+
+use super::error::{RecordError, ZoneErrors};
+use super::{parsed, StoredName, StoredRecord, Zone};
+
+/// The octets every snapshot starts with.
+const MAGIC: &[u8; 4] = b"DZSS";
+
+/// The version of the format written by [`save`].
+const VERSION: u8 = 1;
+
+//------------ save ----------------------------------------------------------
+
+/// Saves a zone as a snapshot.
+///
+/// The snapshot contains all records of the zone as returned by
+/// [`Zone::canonical_records`]. It is written to `target` in one go, so
+/// there is no need to buffer the writer.
+pub fn save(
+    zone: &Zone,
+    target: &mut impl io::Write,
+) -> Result<(), io::Error> {
+    let records = zone.canonical_records();
+
+    // Assign an index to every owner name, starting with the apex.
+    let mut names = vec![zone.apex_name().clone()];
+    let mut index = HashMap::new();
+    let _ = index.insert(zone.apex_name().clone(), 0u32);
+    for record in &records {
+        if !index.contains_key(record.owner()) {
+            let _ = index.insert(record.owner().clone(), len_u32(&names)?);
+            names.push(record.owner().clone());
+        }
+    }
+
+    let mut buf = Vec::new();
+    buf.extend_from_slice(MAGIC);
+    buf.push(VERSION);
+    buf.extend_from_slice(&zone.class().to_int().to_be_bytes());
+
+    buf.extend_from_slice(&len_u32(&names)?.to_be_bytes());
+    for name in &names {
+        buf.extend_from_slice(name.as_slice());
+    }
+
+    let rrsets = rrsets(&records);
+    buf.extend_from_slice(&len_u32(&rrsets)?.to_be_bytes());
+    let mut rdata = Vec::new();
+    for rrset in rrsets {
+        let first = &rrset[0];
+        let count = u16::try_from(rrset.len()).map_err(|_| too_large())?;
+        buf.extend_from_slice(&index[first.owner()].to_be_bytes());
+        buf.extend_from_slice(&first.rtype().to_int().to_be_bytes());
+        buf.extend_from_slice(&first.ttl().as_secs().to_be_bytes());
+        buf.extend_from_slice(&count.to_be_bytes());
+        for record in rrset {
+            rdata.clear();
+            record.data().compose_rdata(&mut rdata).expect("infallible");
+            let len = u16::try_from(rdata.len()).map_err(|_| too_large())?;
+            buf.extend_from_slice(&len.to_be_bytes());
+            buf.extend_from_slice(&rdata);
+        }
+    }
+
+    target.write_all(&buf)
+}
+
+//------------ load ----------------------------------------------------------
+
+/// Loads a zone from a snapshot.
+///
+/// Returns an error if `data` isn’t a snapshot of a supported version, if
+/// it is malformed, or if its records don’t form a valid zone.
+pub fn load(data: Bytes) -> Result<Zone, SnapshotError> {
+    let mut parser = Parser::from_ref(&data);
+    if parser.peek(MAGIC.len()).ok() != Some(MAGIC.as_slice()) {
+        return Err(SnapshotError::NotASnapshot);
+    }
+    parser.advance(MAGIC.len())?;
+    let version = parser.parse_u8()?;
+    if version != VERSION {
+        return Err(SnapshotError::UnsupportedVersion(version));
+    }
+    let class = Class::from_int(parser.parse_u16_be()?);
+
+    let count = parser.parse_u32_be()?;
+    let mut names = Vec::new();
+    for _ in 0..count {
+        let name = ParsedName::parse(&mut parser)?;
+        if name.is_compressed() {
+            return Err(SnapshotError::Malformed);
+        }
+        let name: StoredName = name.flatten_into();
+        names.push(name);
+    }
+    let apex = names.first().ok_or(SnapshotError::Malformed)?.clone();
+
+    let mut zonefile = parsed::Zonefile::new(apex, class);
+    let mut errors = ZoneErrors::default();
+    let count = parser.parse_u32_be()?;
+    for _ in 0..count {
+        let owner = usize::try_from(parser.parse_u32_be()?)
+            .ok()
+            .and_then(|idx| names.get(idx))
+            .ok_or(SnapshotError::Malformed)?;
+        let rtype = Rtype::from_int(parser.parse_u16_be()?);
+        let ttl = Ttl::from_secs(parser.parse_u32_be()?);
+        for _ in 0..parser.parse_u16_be()? {
+            let len = usize::from(parser.parse_u16_be()?);
+            let mut rdata_parser = parser.parse_parser(len)?;
+            let data = ZoneRecordData::parse_rdata(rtype, &mut rdata_parser)?
+                .ok_or(SnapshotError::Malformed)?;
+            if rdata_parser.remaining() != 0 {
+                return Err(SnapshotError::Malformed);
+            }
+            let record: StoredRecord =
+                Record::new(owner.clone(), class, ttl, data).flatten_into();
+            if let Err(err) = zonefile.insert(record) {
+                errors.add_error(owner.clone(), err);
+            }
+        }
+    }
+    if parser.remaining() != 0 {
+        return Err(SnapshotError::Malformed);
+    }
+    if !errors.is_empty() {
+        return Err(SnapshotError::InvalidZone(errors));
+    }
+
+    Zone::try_from(zonefile).map_err(SnapshotError::InvalidZone)
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Splits records in canonical order into RRsets.
+fn rrsets(records: &[StoredRecord]) -> Vec<&[StoredRecord]> {
+    let mut res = Vec::new();
+    let mut start = 0;
+    for idx in 1..=records.len() {
+        let end = match records.get(idx) {
+            Some(record) => {
+                let first = &records[start];
+                !record.owner().name_eq(first.owner())
+                    || record.rtype() != first.rtype()
+            }
+            None => true,
+        };
+        if end {
+            res.push(&records[start..idx]);
+            start = idx;
+        }
+    }
+    res
+}
+
+/// Returns the length of a slice as a 32 bit integer.
+fn len_u32<T>(slice: &[T]) -> Result<u32, io::Error> {
+    u32::try_from(slice.len()).map_err(|_| too_large())
+}
+
+/// Returns the error for things that don’t fit into the format.
+fn too_large() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "zone too large for snapshot")
+}
+
+//------------ SnapshotError -------------------------------------------------
+
+/// A snapshot could not be loaded.
+#[derive(Clone, Debug)]
+pub enum SnapshotError {
+    /// The data doesn’t start like a snapshot.
+    NotASnapshot,
+
+    /// The snapshot uses an unsupported version of the format.
+    UnsupportedVersion(u8),
+
+    /// The snapshot is malformed.
+    Malformed,
+
+    /// The records of the snapshot don’t form a valid zone.
+    InvalidZone(ZoneErrors<RecordError>),
+}
+
+//--- From
+
+impl From<octseq::parse::ShortInput> for SnapshotError {
+    fn from(_: octseq::parse::ShortInput) -> Self {
+        SnapshotError::Malformed
+    }
+}
+
+impl From<crate::base::wire::ParseError> for SnapshotError {
+    fn from(_: crate::base::wire::ParseError) -> Self {
+        SnapshotError::Malformed
+    }
+}
+
+//--- Display and Error
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::NotASnapshot => f.write_str("not a zone snapshot"),
+            SnapshotError::UnsupportedVersion(version) => {
+                write!(f, "unsupported zone snapshot version {version}")
+            }
+            SnapshotError::Malformed => {
+                f.write_str("malformed zone snapshot")
+            }
+            SnapshotError::InvalidZone(errors) => {
+                write!(f, "invalid zone in snapshot: {errors}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use std::string::{String, ToString};
+
+    use super::*;
+    use crate::base::iana::Rcode;
+    use crate::base::Name;
+    use crate::zonefile::inplace;
+    use crate::zonetree::AnswerContent;
+
+    const ZONE: &str = "\
+        example. 3600 IN SOA ns1 admin 1 1800 900 604800 86400\n\
+        example. 3600 IN NS ns1\n\
+        example. 3600 IN MX 10 mail\n\
+        ns1.example. 3600 IN A 192.0.2.1\n\
+        www.example. 300 IN A 192.0.2.10\n\
+        www.example. 300 IN A 192.0.2.11\n\
+        www.example. 300 IN AAAA 2001:db8::10\n\
+        alias.example. 3600 IN CNAME www\n\
+        text.example. 3600 IN TXT \"some\" \"text\"\n\
+        sub.example. 3600 IN NS ns.sub\n\
+        ns.sub.example. 3600 IN A 192.0.2.20\n\
+        key.example. 3600 IN TYPE65280 \\# 3 010203\n\
+    ";
+
+    fn load_text(text: &str) -> Zone {
+        let mut zonefile = inplace::Zonefile::new();
+        zonefile.extend_from_slice(text.as_bytes());
+        zonefile.set_origin(Name::bytes_from_str("example.").unwrap());
+        Zone::try_from(zonefile).unwrap()
+    }
+
+    fn display(records: &[StoredRecord]) -> Vec<String> {
+        records.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn save_and_load() {
+        let zone = load_text(ZONE);
+        let mut data = Vec::new();
+        save(&zone, &mut data).unwrap();
+        let loaded = load(data.into()).unwrap();
+
+        assert_eq!(loaded.apex_name(), zone.apex_name());
+        assert_eq!(loaded.class(), zone.class());
+        assert_eq!(
+            display(&loaded.canonical_records()),
+            display(&zone.canonical_records())
+        );
+
+        // The zone cut and CNAME survive the round trip.
+        let qname = Name::bytes_from_str("www.sub.example.").unwrap();
+        let answer = loaded.read().query(qname, Rtype::A).unwrap();
+        assert_eq!(answer.rcode(), Rcode::NOERROR);
+        assert!(answer.authority().is_some());
+        let qname = Name::bytes_from_str("alias.example.").unwrap();
+        let answer = loaded.read().query(qname, Rtype::A).unwrap();
+        assert!(matches!(answer.content(), AnswerContent::Cname(_)));
+    }
+
+    #[test]
+    fn load_errors() {
+        let zone = load_text(ZONE);
+        let mut data = Vec::new();
+        save(&zone, &mut data).unwrap();
+
+        assert!(matches!(
+            load(Bytes::from_static(b"$ORIGIN example.\n")),
+            Err(SnapshotError::NotASnapshot)
+        ));
+
+        let mut other_version = data.clone();
+        other_version[4] = 2;
+        assert!(matches!(
+            load(other_version.into()),
+            Err(SnapshotError::UnsupportedVersion(2))
+        ));
+
+        let mut truncated = data.clone();
+        truncated.truncate(data.len() - 1);
+        assert!(matches!(
+            load(truncated.into()),
+            Err(SnapshotError::Malformed)
+        ));
+
+        let mut trailing = data;
+        trailing.push(0);
+        assert!(matches!(
+            load(trailing.into()),
+            Err(SnapshotError::Malformed)
+        ));
+    }
+}
@@ -106,6 +106,14 @@ pub trait ReadableZone: Send + Sync {
     ///
     /// This function visits every node in the tree, synchronously, invoking
     /// the given callback function at every leaf node found.
+    ///
+    /// This includes data occluded by a zone cut or DNAME record which
+    /// can’t be reached via [`query`][ReadableZone::query] but still is
+    /// part of the zone and has to be included in zone transfers as
+    /// required by [RFC 5936, section 3.5].
+    ///
+    /// [RFC 5936, section 3.5]:
+    ///     https://tools.ietf.org/html/rfc5936#section-3.5
     fn walk(&self, _op: WalkOp);
 
     //--- Async variants
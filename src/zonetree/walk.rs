@@ -1,4 +1,3 @@
-use std::borrow::ToOwned;
 use std::boxed::Box;
 use std::sync::{Arc, Mutex};
 use std::vec::Vec;
@@ -6,7 +5,7 @@ use std::vec::Vec;
 use crate::base::name::OwnedLabel;
 use crate::base::NameBuilder;
 
-use super::{Rrset, SharedRrset, StoredName, StoredRecord};
+use super::{SharedRrset, StoredName};
 
 /// A callback function invoked for each node visited while walking a
 /// [`Zone`].
@@ -63,15 +62,6 @@ impl WalkState {
         }
     }
 
-    pub(super) fn op_glue_rec(&self, rec: &StoredRecord) {
-        if let Some(inner) = &self.inner {
-            let owner = rec.owner().to_owned();
-            let rrset: Rrset = rec.clone().into();
-            let rrset = SharedRrset::new(rrset);
-            (inner.op)(owner, &rrset, true);
-        }
-    }
-
     pub(super) fn push(&self, label: OwnedLabel) {
         if let Some(inner) = &self.inner {
             inner.label_stack.lock().unwrap().push(label);
@@ -1,16 +1,21 @@
+use core::cmp::Ordering;
 use std::boxed::Box;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::vec::Vec;
 
-use crate::base::iana::Class;
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::{Class, Rtype};
+use crate::base::Record;
 use crate::zonefile::inplace;
 
 use super::error::{RecordError, ZoneErrors};
 use super::in_memory::ZoneBuilder;
 use super::traits::WritableZone;
-use super::types::StoredName;
+use super::types::{StoredName, StoredRecord};
 use super::{parsed, ReadableZone, ZoneStore};
 
 /// A single DNS zone.
@@ -84,6 +89,101 @@ impl Zone {
     {
         self.store.clone().write()
     }
+
+    /// Returns all records of this zone in canonical order.
+    ///
+    /// The records are sorted in the canonical order defined in [RFC 4034,
+    /// section 6], which is needed for building NSEC chains, calculating
+    /// zone digests, or transferring a signed zone. Duplicate records are
+    /// only included once.
+    ///
+    /// The records are collected via the synchronous
+    /// [`walk`][ReadableZone::walk] of the zone.
+    ///
+    /// [RFC 4034, section 6]: https://tools.ietf.org/html/rfc4034#section-6
+    pub fn canonical_records(&self) -> Vec<StoredRecord> {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let class = self.class();
+        let cloned_records = records.clone();
+        self.read()
+            .walk(Box::new(move |owner, rrset, _at_zone_cut| {
+                let mut records = cloned_records.lock().unwrap();
+                for data in rrset.data() {
+                    records.push(Record::new(
+                        owner.clone(),
+                        class,
+                        rrset.ttl(),
+                        data.clone(),
+                    ));
+                }
+            }));
+        let mut records = Arc::try_unwrap(records)
+            .map(|records| records.into_inner().unwrap())
+            .unwrap_or_else(|records| records.lock().unwrap().clone());
+        records.sort_by(CanonicalOrd::canonical_cmp);
+        records.dedup_by(|a, b| a.canonical_cmp(b) == Ordering::Equal);
+        records
+    }
+
+    /// Returns the records of this zone that aren’t occluded.
+    ///
+    /// A record is occluded if its owner is below a zone cut or a DNAME
+    /// record or if it is at a zone cut but isn’t one of the NS, DS, NSEC,
+    /// or RRSIG records belonging there. Occluded records can’t be found
+    /// by queries, although address records may still be given out as
+    /// glue. They are, however, part of the zone and included in zone
+    /// transfers.
+    ///
+    /// The records are returned in canonical order. See
+    /// [`occluded_records`][Self::occluded_records] for the records that
+    /// are skipped.
+    pub fn visible_records(&self) -> impl Iterator<Item = StoredRecord> {
+        self.partition_occluded().0.into_iter()
+    }
+
+    /// Returns the records of this zone that are occluded.
+    ///
+    /// See [`visible_records`][Self::visible_records] for which records
+    /// are occluded. This can be used to diagnose zones with data that
+    /// unexpectedly can’t be reached. The records are returned in
+    /// canonical order.
+    pub fn occluded_records(&self) -> impl Iterator<Item = StoredRecord> {
+        self.partition_occluded().1.into_iter()
+    }
+
+    /// Splits the canonical records into visible and occluded records.
+    fn partition_occluded(&self) -> (Vec<StoredRecord>, Vec<StoredRecord>) {
+        let records = self.canonical_records();
+        let apex = self.apex_name();
+        let mut cuts = HashSet::new();
+        let mut dnames = HashSet::new();
+        for record in &records {
+            match record.rtype() {
+                Rtype::NS if record.owner() != apex => {
+                    let _ = cuts.insert(record.owner().clone());
+                }
+                Rtype::DNAME => {
+                    let _ = dnames.insert(record.owner().clone());
+                }
+                _ => {}
+            }
+        }
+
+        records.into_iter().partition(|record| {
+            let owner = record.owner();
+            if cuts.contains(owner)
+                && !matches!(
+                    record.rtype(),
+                    Rtype::NS | Rtype::DS | Rtype::NSEC | Rtype::RRSIG
+                )
+            {
+                return false;
+            }
+            !owner.iter_suffixes().skip(1).any(|suffix| {
+                cuts.contains(&suffix) || dnames.contains(&suffix)
+            })
+        })
+    }
 }
 
 impl AsRef<dyn ZoneStore> for Zone {
@@ -128,3 +228,182 @@ impl TryFrom<parsed::Zonefile> for Zone {
         )?))
     }
 }
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::iana::Rcode;
+    use crate::base::name::Name;
+    use crate::rdata::ZoneRecordData;
+    use crate::zonetree::AnswerContent;
+    use std::string::{String, ToString};
+
+    const OCCLUDED: &str = "\
+        example. 3600 IN SOA ns1 admin 1 1800 900 604800 86400\n\
+        example. 3600 IN NS ns1\n\
+        ns1.example. 3600 IN A 192.0.2.1\n\
+        sub.example. 3600 IN NS ns.sub\n\
+        sub.example. 3600 IN A 192.0.2.2\n\
+        ns.sub.example. 3600 IN A 192.0.2.3\n\
+        www.sub.example. 3600 IN TXT \"hidden\"\n\
+        old.example. 3600 IN DNAME new.example.net.\n\
+        www.old.example. 3600 IN A 192.0.2.4\n\
+    ";
+
+    fn load(text: &str) -> Zone {
+        let mut zonefile = inplace::Zonefile::new();
+        zonefile.extend_from_slice(text.as_bytes());
+        zonefile.set_origin(Name::bytes_from_str("example.").unwrap());
+        Zone::try_from(zonefile).unwrap()
+    }
+
+    fn owners(records: impl Iterator<Item = StoredRecord>) -> Vec<String> {
+        records
+            .map(|record| {
+                std::format!("{} {}", record.owner(), record.rtype())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn canonical_records() {
+        // The names from RFC 4034, section 6.1, in random order.
+        let mut zonefile = inplace::Zonefile::new();
+        zonefile.extend_from_slice(
+            b"example. 3600 IN SOA ns1 admin 1 1800 900 604800 86400\n\
+              *.z.example. 3600 IN A 192.0.2.1\n\
+              a.example. 3600 IN A 192.0.2.1\n\
+              \\200.z.example. 3600 IN A 192.0.2.1\n\
+              Z.a.example. 3600 IN A 192.0.2.1\n\
+              yljkjljk.a.example. 3600 IN A 192.0.2.1\n\
+              \\001.z.example. 3600 IN A 192.0.2.1\n\
+              zABC.a.EXAMPLE. 3600 IN A 192.0.2.1\n\
+              z.example. 3600 IN A 192.0.2.1\n\
+              example. 3600 IN NS ns1\n",
+        );
+        zonefile.set_origin(Name::bytes_from_str("example.").unwrap());
+        let zone = Zone::try_from(zonefile).unwrap();
+
+        let owners: Vec<_> = zone
+            .canonical_records()
+            .iter()
+            .map(|record| record.owner().fmt_with_dot().to_string())
+            .collect();
+        assert_eq!(
+            owners,
+            [
+                "example.",
+                "example.",
+                "a.example.",
+                "yljkjljk.a.example.",
+                "Z.a.example.",
+                "zABC.a.example.",
+                "z.example.",
+                "\\001.z.example.",
+                "*.z.example.",
+                "\\200.z.example.",
+            ]
+        );
+    }
+
+    #[test]
+    fn occluded_records() {
+        let zone = load(OCCLUDED);
+        assert_eq!(
+            owners(zone.visible_records()),
+            [
+                "example NS",
+                "example SOA",
+                "ns1.example A",
+                "old.example DNAME",
+                "sub.example NS",
+            ]
+        );
+        assert_eq!(
+            owners(zone.occluded_records()),
+            [
+                "www.old.example A",
+                "sub.example A",
+                "ns.sub.example A",
+                "www.sub.example TXT",
+            ]
+        );
+    }
+
+    #[test]
+    fn query_below_dname() {
+        let zone = load(OCCLUDED);
+        let qname = Name::bytes_from_str("www.old.example.").unwrap();
+        let answer = zone.read().query(qname, Rtype::A).unwrap();
+        assert_eq!(answer.rcode(), Rcode::NOERROR);
+        let AnswerContent::Dname(owner, dname, Some(cname)) =
+            answer.content()
+        else {
+            panic!("expected DNAME answer");
+        };
+        assert_eq!(owner.to_string(), "old.example");
+        assert_eq!(dname.rtype(), Rtype::DNAME);
+        let ZoneRecordData::Cname(cname) = cname.data() else {
+            panic!("expected CNAME");
+        };
+        assert_eq!(cname.cname().to_string(), "www.new.example.net");
+
+        // The DNAME itself is found normally.
+        let qname = Name::bytes_from_str("old.example.").unwrap();
+        let answer = zone.read().query(qname, Rtype::DNAME).unwrap();
+        assert!(matches!(answer.content(), AnswerContent::Data(_)));
+
+        // Too long a synthesized name is YXDOMAIN. The prefix is 240
+        // octets long.
+        let long = std::format!(
+            "{0}.{0}.{0}.{1}.old.example.",
+            "a".repeat(63),
+            "b".repeat(47)
+        );
+        let answer = zone
+            .read()
+            .query(Name::bytes_from_str(&long).unwrap(), Rtype::A)
+            .unwrap();
+        assert_eq!(answer.rcode(), Rcode::YXDOMAIN);
+        assert!(matches!(answer.content(), AnswerContent::Dname(_, _, None)));
+
+        // Data below a zone cut is referred.
+        let qname = Name::bytes_from_str("www.sub.example.").unwrap();
+        let answer = zone.read().query(qname, Rtype::TXT).unwrap();
+        assert!(matches!(answer.content(), AnswerContent::NoData));
+        assert!(answer.authority().is_some());
+    }
+
+    #[test]
+    fn query_below_bad_dname() {
+        use crate::base::Ttl;
+        use crate::rdata::A;
+        use crate::zonetree::{Rrset, SharedRrset};
+
+        // A DNAME RRset with other data can’t be used for synthesis. Such
+        // an RRset can only be created by deserializing it.
+        let mut rrset = Rrset::new(Rtype::A, Ttl::from_secs(3600));
+        rrset.push_data(ZoneRecordData::A(A::from_octets(192, 0, 2, 1)));
+        let mut rrset = serde_json::to_value(rrset).unwrap();
+        rrset["rtype"] = serde_json::to_value(Rtype::DNAME).unwrap();
+        let rrset: Rrset = serde_json::from_value(rrset).unwrap();
+
+        let mut builder = ZoneBuilder::new(
+            Name::bytes_from_str("example.").unwrap(),
+            Class::IN,
+        );
+        builder
+            .insert_rrset(
+                &Name::bytes_from_str("old.example.").unwrap(),
+                SharedRrset::new(rrset),
+            )
+            .unwrap();
+        let zone = builder.build();
+
+        let qname = Name::bytes_from_str("www.old.example.").unwrap();
+        let answer = zone.read().query(qname, Rtype::A).unwrap();
+        assert_eq!(answer.rcode(), Rcode::SERVFAIL);
+    }
+}
@@ -0,0 +1,256 @@
+//! Computing and verifying zone digests.
+//!
+//! A [ZONEMD] record published at the apex of a zone contains a digest
+//! over all the records of the zone. This module provides functions to
+//! calculate this digest for a [`Zone`] and to verify the ZONEMD records
+//! of a zone against it.
+//!
+//! Only the SIMPLE scheme with the SHA-384 and SHA-512 hash algorithms is
+//! currently supported.
+//!
+//! [ZONEMD]: https://tools.ietf.org/html/rfc8976
+#![cfg(feature = "ring")]
+#![cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+
+use std::fmt;
+use std::vec::Vec;
+
+use crate::base::iana::Rtype;
+use crate::base::ToName;
+use crate::rdata::zonemd::{Algorithm, Scheme};
+use crate::rdata::ZoneRecordData;
+
+use super::{StoredRecord, Zone};
+
+//------------ digest --------------------------------------------------------
+
+/// Calculates the digest of a zone.
+///
+/// The digest is calculated over all records of the zone in canonical
+/// order using the given collation scheme and hash algorithm. As required
+/// by [RFC 8976, section 3.3], the ZONEMD RRset at the apex of the zone
+/// and its signatures are excluded from the calculation.
+///
+/// Returns an error if the scheme or algorithm are not supported.
+///
+/// [RFC 8976, section 3.3]: https://tools.ietf.org/html/rfc8976#section-3.3
+pub fn digest(
+    zone: &Zone,
+    scheme: Scheme,
+    algorithm: Algorithm,
+) -> Result<Vec<u8>, ZonemdError> {
+    if scheme != Scheme::Simple {
+        return Err(ZonemdError::UnsupportedScheme);
+    }
+    let mut context = ring::digest::Context::new(match algorithm {
+        Algorithm::Sha384 => &ring::digest::SHA384,
+        Algorithm::Sha512 => &ring::digest::SHA512,
+        _ => return Err(ZonemdError::UnsupportedAlgorithm),
+    });
+
+    let mut buf = Vec::new();
+    for record in zone.canonical_records() {
+        if is_apex_zonemd(zone, &record) {
+            continue;
+        }
+        buf.clear();
+        record.compose_canonical(&mut buf).expect("infallible");
+        context.update(&buf);
+    }
+    Ok(context.finish().as_ref().into())
+}
+
+//------------ verify --------------------------------------------------------
+
+/// Verifies the ZONEMD records of a zone.
+///
+/// Following [RFC 8976, section 4], the function looks for a ZONEMD record
+/// at the apex of the zone with the serial of the zone’s SOA record and a
+/// supported scheme and hash algorithm. Verification succeeds if the
+/// digest of at least one of these records matches the digest calculated
+/// for the zone.
+///
+/// [RFC 8976, section 4]: https://tools.ietf.org/html/rfc8976#section-4
+pub fn verify(zone: &Zone) -> Result<(), ZonemdError> {
+    let records = zone.canonical_records();
+    let apex = zone.apex_name();
+
+    let serial = records
+        .iter()
+        .find_map(|record| match record.data() {
+            ZoneRecordData::Soa(soa) if record.owner().name_eq(apex) => {
+                Some(soa.serial())
+            }
+            _ => None,
+        })
+        .ok_or(ZonemdError::MissingSoa)?;
+
+    let mut found = false;
+    let mut supported = false;
+    for record in &records {
+        let zonemd = match record.data() {
+            ZoneRecordData::Zonemd(zonemd)
+                if record.owner().name_eq(apex) =>
+            {
+                zonemd
+            }
+            _ => continue,
+        };
+        found = true;
+        if zonemd.serial() != serial {
+            continue;
+        }
+        let digest = match digest(zone, zonemd.scheme(), zonemd.algorithm()) {
+            Ok(digest) => digest,
+            Err(_) => continue,
+        };
+        supported = true;
+        if digest.as_slice() == zonemd.digest().as_ref() {
+            return Ok(());
+        }
+    }
+
+    if !found {
+        Err(ZonemdError::MissingZonemd)
+    } else if !supported {
+        Err(ZonemdError::NoUsableZonemd)
+    } else {
+        Err(ZonemdError::DigestMismatch)
+    }
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Returns whether the record is part of the apex ZONEMD RRset.
+///
+/// This includes the signatures covering the RRset.
+fn is_apex_zonemd(zone: &Zone, record: &StoredRecord) -> bool {
+    if !record.owner().name_eq(zone.apex_name()) {
+        return false;
+    }
+    match record.data() {
+        ZoneRecordData::Zonemd(_) => true,
+        ZoneRecordData::Rrsig(rrsig) => rrsig.type_covered() == Rtype::ZONEMD,
+        _ => false,
+    }
+}
+
+//------------ ZonemdError ---------------------------------------------------
+
+/// Verifying or calculating a zone digest has failed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ZonemdError {
+    /// The collation scheme is not supported.
+    UnsupportedScheme,
+
+    /// The hash algorithm is not supported.
+    UnsupportedAlgorithm,
+
+    /// The zone has no SOA record at its apex.
+    MissingSoa,
+
+    /// The zone has no ZONEMD record at its apex.
+    MissingZonemd,
+
+    /// None of the ZONEMD records can be used for verification.
+    ///
+    /// This happens if all records have a serial different from that of
+    /// the zone or use an unsupported scheme or hash algorithm.
+    NoUsableZonemd,
+
+    /// None of the usable ZONEMD records matches the digest of the zone.
+    DigestMismatch,
+}
+
+impl fmt::Display for ZonemdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ZonemdError::UnsupportedScheme => "unsupported ZONEMD scheme",
+            ZonemdError::UnsupportedAlgorithm => {
+                "unsupported ZONEMD hash algorithm"
+            }
+            ZonemdError::MissingSoa => "missing SOA record",
+            ZonemdError::MissingZonemd => "missing ZONEMD record",
+            ZonemdError::NoUsableZonemd => "no usable ZONEMD record",
+            ZonemdError::DigestMismatch => "ZONEMD digest mismatch",
+        })
+    }
+}
+
+impl std::error::Error for ZonemdError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::iana::Class;
+    use crate::base::Name;
+    use crate::zonefile::inplace;
+    use crate::zonetree::ZoneBuilder;
+
+    // RFC 8976, appendix A.1.
+    const SIMPLE: &str = "\
+        example. 86400 IN SOA ns1 admin 2018031900 1800 900 604800 86400\n\
+        example. 86400 IN NS ns1\n\
+        example. 86400 IN NS ns2\n\
+        example. 86400 IN ZONEMD 2018031900 1 1 (\n\
+            c68090d90a7aed716bc459f9340e3d7c\n\
+            1370d4d24b7e2fc3a1ddc0b9a87153b9\n\
+            a9713b3c9ae5cc27777f98b8e730044c )\n\
+        ns1.example. 3600 IN A 203.0.113.63\n\
+        ns2.example. 3600 IN AAAA 2001:db8::63\n\
+    ";
+
+    fn load(text: &str) -> Zone {
+        let mut zonefile = inplace::Zonefile::new();
+        zonefile.extend_from_slice(text.as_bytes());
+        zonefile.set_origin(Name::bytes_from_str("example.").unwrap());
+        Zone::try_from(zonefile).unwrap()
+    }
+
+    #[test]
+    fn verify_simple() {
+        let zone = load(SIMPLE);
+        assert_eq!(verify(&zone), Ok(()));
+        assert_eq!(
+            crate::utils::base16::encode_string(
+                &digest(&zone, Scheme::Simple, Algorithm::Sha384).unwrap()
+            ),
+            "C68090D90A7AED716BC459F9340E3D7C\
+             1370D4D24B7E2FC3A1DDC0B9A87153B9\
+             A9713B3C9AE5CC27777F98B8E730044C"
+        );
+    }
+
+    #[test]
+    fn verify_mismatch() {
+        let zone = load(&SIMPLE.replace("203.0.113.63", "203.0.113.64"));
+        assert_eq!(verify(&zone), Err(ZonemdError::DigestMismatch));
+
+        let zone = load(
+            &SIMPLE.replace("ZONEMD 2018031900 1 1", "ZONEMD 2018031901 1 1"),
+        );
+        assert_eq!(verify(&zone), Err(ZonemdError::NoUsableZonemd));
+
+        let zone = ZoneBuilder::new(
+            Name::bytes_from_str("example.").unwrap(),
+            Class::IN,
+        )
+        .build();
+        assert_eq!(verify(&zone), Err(ZonemdError::MissingSoa));
+    }
+
+    #[test]
+    fn digest_unsupported() {
+        let zone = load(SIMPLE);
+        assert_eq!(
+            digest(&zone, Scheme::from(2), Algorithm::Sha384),
+            Err(ZonemdError::UnsupportedScheme)
+        );
+        assert_eq!(
+            digest(&zone, Scheme::Simple, Algorithm::from(3)),
+            Err(ZonemdError::UnsupportedAlgorithm)
+        );
+    }
+}